@@ -0,0 +1,56 @@
+#!/usr/bin/env python3
+"""
+EVM Block Number Filter (worker mode)
+
+This script is the worker mode counterpart of evm_filter_block_number.py. It is started once
+and keeps running, processing one request per line until stdin is closed.
+
+Input: one JSON request per line, containing:
+    - id: Identifier of the request, echoed in the response
+    - type: "ping" for health checks, "match" for monitor matches
+    - monitor_match: The monitor match data with transaction details
+    - args: Additional arguments passed to the script
+
+Output: one JSON response per line, containing:
+    - id: Identifier of the request being answered
+    - result: true for transactions in even-numbered blocks, false otherwise
+    - error: Error message if the request could not be processed
+"""
+import sys
+import json
+
+def filter_match(monitor_match):
+    # Extract block_number
+    block_number = None
+    if "EVM" in monitor_match:
+        hex_block = monitor_match['EVM']['transaction'].get('blockNumber')
+        if hex_block:
+            # Convert hex string to integer
+            block_number = int(hex_block, 16)
+
+    if block_number is None:
+        return False
+
+    return block_number % 2 == 0
+
+def main():
+    for line in sys.stdin:
+        try:
+            request = json.loads(line)
+        except json.JSONDecodeError as e:
+            print(f"Invalid JSON input: {e}", file=sys.stderr, flush=True)
+            continue
+
+        response = {"id": request.get("id")}
+        try:
+            if request.get("type") == "ping":
+                response["result"] = True
+            else:
+                response["result"] = filter_match(request["monitor_match"])
+        except Exception as e:
+            response["error"] = f"Error processing input: {e}"
+
+        print(json.dumps(response), flush=True)
+
+if __name__ == "__main__":
+    main()
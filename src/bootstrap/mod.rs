@@ -16,30 +16,44 @@
 //!   from the block processing pipeline
 
 use futures::future::BoxFuture;
-use std::{collections::HashMap, error::Error, sync::Arc};
-use tokio::sync::{watch, Mutex};
+use std::{collections::HashMap, error::Error, sync::Arc, time::Duration};
+use tokio::sync::{oneshot, watch, Mutex};
 
 use crate::{
 	models::{
-		BlockChainType, BlockType, ContractSpec, Monitor, MonitorMatch, Network, ProcessedBlock,
-		ScriptLanguage, TriggerConditions,
+		AddressSet, BlockChainType, BlockType, ConfigLoader, ContractSpec, Monitor, MonitorMatch,
+		Network, PriceSourceConfig, ProcessedBlock, ScriptLanguage, Token, TriggerConditions,
 	},
 	repositories::{
 		MonitorRepositoryTrait, MonitorService, NetworkRepositoryTrait, NetworkService,
 		TriggerRepositoryTrait, TriggerService,
 	},
 	services::{
+		aggregation::WindowAggregationService,
 		blockchain::{BlockChainClient, BlockFilterFactory, ClientPoolTrait},
-		filter::{evm_helpers, handle_match, stellar_helpers, FilterService},
+		blockwatcher::BlockStorage,
+		filter::{
+			build_match_variables, evm_helpers, fetch_token_metadata, register_address_sets,
+			register_token_metadata, register_tokens, stellar_helpers, token_metadata,
+			FilterService, PollStateStore,
+		},
 		notification::NotificationService,
+		price::{
+			refresh_prices, register_price_feed, ChainlinkPriceSource, FilePriceSource,
+			HttpPriceSource, PriceFeedPolicy, PriceSource,
+		},
 		trigger::{
-			ScriptError, ScriptExecutorFactory, TriggerError, TriggerExecutionService,
+			insert_throttle_variables, NotificationThrottle, ScriptError, ScriptExecutorFactory,
+			ScriptOutput, ThrottleDecision, TriggerError, TriggerExecutionService,
 			TriggerExecutionServiceTrait,
 		},
 	},
-	utils::normalize_string,
+	utils::{get_next_cron_occurrence, normalize_string},
 };
 
+/// Interval between two drains of the queue trigger outboxes
+const QUEUE_OUTBOX_DRAIN_INTERVAL: Duration = Duration::from_secs(30);
+
 /// Type alias for handling ServiceResult
 pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
 
@@ -105,6 +119,14 @@ where
 		}
 	};
 
+	// Named address sets are referenced from monitor expressions (e.g. `to in @hot_wallets`)
+	let address_sets: HashMap<String, AddressSet> = AddressSet::load_all(None).await?;
+	register_address_sets(address_sets.into_values());
+
+	// Tokens are referenced from monitor expressions (e.g. `value > 10000 USDC`) and templates
+	let tokens: HashMap<String, Token> = Token::load_all(None).await?;
+	register_tokens(tokens.into_values());
+
 	let notification_service = NotificationService::new();
 
 	let filter_service = Arc::new(FilterService::new());
@@ -135,19 +157,26 @@ where
 /// * `filter_service` - Service for filtering blockchain data
 /// * `active_monitors` - List of active monitors
 /// * `client_pools` - Client pools for accessing blockchain clients
+/// * `contract_specs` - Contract specs of the monitored addresses
+/// * `window_service` - Service applying sliding window aggregation to matches
+/// * `poll_store` - Store persisting the last poll of polling monitors
 ///
 /// # Returns
 /// Returns a function that handles incoming blocks
-pub fn create_block_handler<P: ClientPoolTrait + 'static>(
+pub fn create_block_handler<P: ClientPoolTrait + 'static, S: BlockStorage + 'static>(
 	shutdown_tx: watch::Sender<bool>,
 	filter_service: Arc<FilterService>,
 	active_monitors: Vec<Monitor>,
 	client_pools: Arc<P>,
 	contract_specs: Vec<(String, ContractSpec)>,
+	window_service: Arc<WindowAggregationService<S>>,
+	poll_store: Arc<PollStateStore<S>>,
 ) -> Arc<impl Fn(BlockType, Network) -> BoxFuture<'static, ProcessedBlock> + Send + Sync> {
 	Arc::new(
 		move |block: BlockType, network: Network| -> BoxFuture<'static, ProcessedBlock> {
 			let filter_service = filter_service.clone();
+			let window_service = window_service.clone();
+			let poll_store = poll_store.clone();
 			let active_monitors = active_monitors.clone();
 			let client_pools = client_pools.clone();
 			let shutdown_tx = shutdown_tx.clone();
@@ -164,6 +193,11 @@ pub fn create_block_handler<P: ClientPoolTrait + 'static>(
 				if !applicable_monitors.is_empty() {
 					let mut shutdown_rx = shutdown_tx.subscribe();
 
+					// Polling monitors compare their values with the last poll before a restart
+					poll_store
+						.restore(&network.slug, &applicable_monitors)
+						.await;
+
 					let matches = match network.network_type {
 						BlockChainType::EVM => match client_pools.get_evm_client(&network).await {
 							Ok(client) => {
@@ -200,8 +234,17 @@ pub fn create_block_handler<P: ClientPoolTrait + 'static>(
 						BlockChainType::Midnight => None,
 						BlockChainType::Solana => None,
 					};
-
-					processed_block.processing_results = matches.unwrap_or_default();
+					poll_store
+						.persist(&network.slug, &applicable_monitors)
+						.await;
+
+					// Windowed monitors only forward matches once their aggregate fires
+					let timestamp = block
+						.timestamp()
+						.unwrap_or_else(|| chrono::Utc::now().timestamp().max(0) as u64);
+					processed_block.processing_results = window_service
+						.process_matches(&network.slug, timestamp, matches.unwrap_or_default())
+						.await;
 				}
 
 				processed_block
@@ -232,7 +275,12 @@ where
 	T: BlockChainClient + BlockFilterFactory<T>,
 {
 	tokio::select! {
-		result = filter_service.filter_block(client, network, block, applicable_monitors, contract_specs) => {
+		result = async {
+			// Prices read by `usd()` in expressions and templates are refreshed once their TTL
+			// has elapsed
+			refresh_prices().await;
+			filter_service.filter_block(client, network, block, applicable_monitors, contract_specs).await
+		} => {
 			result.ok()
 		}
 		_ = shutdown_rx.changed() => {
@@ -362,9 +410,140 @@ pub async fn get_contract_specs<P: ClientPoolTrait + 'static>(
 	all_specs
 }
 
+/// Fetches the token metadata of the monitored EVM contracts missing from the token configuration
+///
+/// The `decimals()` and `symbol()` of each contract are called once and cached in the token
+/// registry. Contracts that are not tokens are skipped.
+///
+/// # Arguments
+/// * `client_pool` - The client pool to use to call the contracts
+/// * `network_monitors` - The monitors whose addresses are looked up
+pub async fn load_token_metadata<P: ClientPoolTrait + 'static>(
+	client_pool: &Arc<P>,
+	network_monitors: &[(Network, Vec<Monitor>)],
+) {
+	for (network, monitors) in network_monitors {
+		if network.network_type != BlockChainType::EVM {
+			continue;
+		}
+
+		let mut addresses = monitors
+			.iter()
+			.flat_map(|monitor| &monitor.addresses)
+			.map(|monitored_addr| {
+				format!(
+					"0x{}",
+					evm_helpers::normalize_address(&monitored_addr.address)
+				)
+			})
+			.filter(|address| token_metadata(address).is_none())
+			.collect::<Vec<_>>();
+		addresses.sort();
+		addresses.dedup();
+		if addresses.is_empty() {
+			continue;
+		}
+
+		let client: Arc<P::EvmClient> = match client_pool.get_evm_client(network).await {
+			Ok(client) => client,
+			Err(_) => {
+				tracing::warn!("Failed to get EVM client for network {}", network.slug);
+				continue;
+			}
+		};
+
+		let results = futures::future::join_all(addresses.iter().map(|address| {
+			let client = client.clone();
+			async move {
+				(
+					address,
+					fetch_token_metadata(client.as_ref(), address).await,
+				)
+			}
+		}))
+		.await;
+
+		for (address, result) in results {
+			match result {
+				Ok(metadata) => register_token_metadata(address, metadata),
+				Err(e) => {
+					tracing::debug!("No token metadata for address {}: {:?}", address, e);
+				}
+			}
+		}
+	}
+}
+
+/// Registers the USD price feeds of the configured tokens and fetches their first prices
+///
+/// Chainlink aggregators are read with the EVM client of their network. Feeds whose network is
+/// unknown or not an EVM network are skipped.
+///
+/// # Arguments
+/// * `client_pool` - The client pool to use to read Chainlink aggregators
+/// * `tokens` - The configured tokens
+/// * `networks` - The configured networks, keyed by slug
+pub async fn load_price_feeds<P: ClientPoolTrait + 'static>(
+	client_pool: &Arc<P>,
+	tokens: &[Token],
+	networks: &HashMap<String, Network>,
+) {
+	for token in tokens {
+		let Some(price) = &token.price else {
+			continue;
+		};
+
+		let source: Arc<dyn PriceSource> = match &price.source {
+			PriceSourceConfig::Chainlink { network, address } => {
+				let Some(network) = networks
+					.get(network)
+					.filter(|network| network.network_type == BlockChainType::EVM)
+				else {
+					tracing::warn!(
+						"Skipping price feed of token {}: {} is not a configured EVM network",
+						token.symbol,
+						network
+					);
+					continue;
+				};
+				match client_pool.get_evm_client(network).await {
+					Ok(client) => Arc::new(ChainlinkPriceSource::new(client, address.clone())),
+					Err(e) => {
+						tracing::warn!(
+							"Skipping price feed of token {}: failed to get EVM client for \
+							 network {}: {}",
+							token.symbol,
+							network.slug,
+							e
+						);
+						continue;
+					}
+				}
+			}
+			PriceSourceConfig::File { path } => Arc::new(FilePriceSource::new(path)),
+			PriceSourceConfig::Http { url, path, headers } => {
+				match HttpPriceSource::new(url.clone(), path.clone(), headers.clone()) {
+					Ok(source) => Arc::new(source),
+					Err(e) => {
+						tracing::warn!("Skipping price feed of token {}: {}", token.symbol, e);
+						continue;
+					}
+				}
+			}
+		};
+		register_price_feed(&token.symbol, source, PriceFeedPolicy::from(price));
+	}
+
+	refresh_prices().await;
+}
+
 /// Creates a trigger handler function that processes trigger events from the block processing
 /// pipeline.
 ///
+/// Trigger filters of consecutive blocks run concurrently. Message queue triggers publish the
+/// matches of a network in the order the blocks were handed to the handler, while the other
+/// triggers are notified without waiting on previous blocks.
+///
 /// # Arguments
 /// * `shutdown_tx` - Watch channel for shutdown signals
 /// * `trigger_service` - Service for executing triggers
@@ -376,24 +555,82 @@ pub fn create_trigger_handler<S: TriggerExecutionServiceTrait + Send + Sync + 's
 	trigger_service: Arc<S>,
 	active_monitors_trigger_scripts: HashMap<String, (ScriptLanguage, String)>,
 ) -> Arc<impl Fn(&ProcessedBlock) -> tokio::task::JoinHandle<()> + Send + Sync> {
+	// Shared by all blocks so that throttling spans the whole lifetime of the handler
+	let throttle = Arc::new(NotificationThrottle::new());
+	// Completion of the notifications of the last block handed over, per network
+	let last_block_done = Arc::new(std::sync::Mutex::new(HashMap::<
+		String,
+		oneshot::Receiver<()>,
+	>::new()));
+
 	Arc::new(move |block: &ProcessedBlock| {
 		let mut shutdown_rx = shutdown_tx.subscribe();
 		let trigger_service = trigger_service.clone();
 		let trigger_scripts = active_monitors_trigger_scripts.clone();
+		let throttle = throttle.clone();
 		let block = block.clone();
 
+		// The sender is dropped once the block is notified, or its task is cancelled
+		let (block_done_tx, block_done_rx) = oneshot::channel::<()>();
+		let previous_block_done = last_block_done
+			.lock()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+			.insert(block.network_slug.clone(), block_done_rx);
+
 		tokio::spawn(async move {
+			throttle.evict_expired(chrono::Utc::now().timestamp().max(0) as u64);
 			tokio::select! {
 				_ = async {
-					if block.processing_results.is_empty() {
-						return;
-					}
-					let filtered_matches = run_trigger_filters(&block.processing_results, &block.network_slug, &trigger_scripts).await;
-					for monitor_match in &filtered_matches {
-						if let Err(e) = handle_match(monitor_match.clone(), &*trigger_service, &trigger_scripts).await {
-							TriggerError::execution_error(e.to_string(), Some(e.into()), None);
+					let filtered_matches = if block.processing_results.is_empty() {
+						vec![]
+					} else {
+						run_trigger_filters(&block.processing_results, &block.network_slug, &trigger_scripts).await
+					};
+					// Throttling is decided in block order, before waiting on the previous block
+					let mut notifications = vec![];
+					for monitor_match in filtered_matches {
+						let monitor = match &monitor_match {
+							MonitorMatch::EVM(evm_match) => &evm_match.monitor,
+							MonitorMatch::Stellar(stellar_match) => &stellar_match.monitor,
+							MonitorMatch::Poll(poll_match) => &poll_match.monitor,
+						};
+						let mut variables = build_match_variables(&monitor_match);
+						let now = chrono::Utc::now().timestamp().max(0) as u64;
+						match throttle.check(monitor, &variables, now) {
+							ThrottleDecision::Allow { suppressed } => {
+								insert_throttle_variables(&mut variables, suppressed);
+							}
+							ThrottleDecision::Suppress => {
+								tracing::debug!("Notification suppressed by monitor throttle");
+								continue;
+							}
 						}
+						let (ordered, unordered): (Vec<String>, Vec<String>) = monitor
+							.triggers
+							.iter()
+							.cloned()
+							.partition(|slug| trigger_service.is_ordered(slug));
+						notifications.push((monitor_match, variables, ordered, unordered));
 					}
+
+					// Errors are logged by the trigger service, and must not stop other matches
+					let unordered = async {
+						for (monitor_match, variables, _, unordered) in &notifications {
+							let _ = trigger_service.execute(unordered, variables.clone(), monitor_match, &trigger_scripts).await;
+						}
+					};
+					let ordered = async {
+						if let Some(previous_block_done) = previous_block_done {
+							let _ = previous_block_done.await;
+						}
+						for (monitor_match, variables, ordered, _) in &notifications {
+							if !ordered.is_empty() {
+								let _ = trigger_service.execute(ordered, variables.clone(), monitor_match, &trigger_scripts).await;
+							}
+						}
+						drop(block_done_tx);
+					};
+					tokio::join!(unordered, ordered);
 				} => {}
 				_ = shutdown_rx.changed() => {
 					tracing::info!("Shutting down trigger handling task");
@@ -403,6 +640,107 @@ pub fn create_trigger_handler<S: TriggerExecutionServiceTrait + Send + Sync + 's
 	})
 }
 
+/// Spawns a task per digest trigger that flushes its buffered matches on the digest schedule.
+///
+/// Pending matches are flushed one last time when the shutdown signal is received.
+///
+/// # Arguments
+/// * `shutdown_tx` - Watch channel for shutdown signals
+/// * `trigger_execution_service` - Service buffering and sending digest notifications
+///
+/// # Returns
+/// Returns the handles of the spawned tasks
+pub fn spawn_digest_flushers<T: TriggerRepositoryTrait + Send + Sync + 'static>(
+	shutdown_tx: watch::Sender<bool>,
+	trigger_execution_service: Arc<TriggerExecutionService<T>>,
+) -> Vec<tokio::task::JoinHandle<()>> {
+	trigger_execution_service
+		.digest_triggers()
+		.into_iter()
+		.filter_map(|(trigger_slug, trigger)| {
+			let cron_schedule = trigger.digest.as_ref()?.cron_schedule.clone();
+			let mut shutdown_rx = shutdown_tx.subscribe();
+			let trigger_execution_service = trigger_execution_service.clone();
+
+			Some(tokio::spawn(async move {
+				loop {
+					let now = chrono::Utc::now();
+					let Some(next) = get_next_cron_occurrence(&cron_schedule, now) else {
+						tracing::error!("Invalid digest schedule for trigger {}", trigger.name);
+						return;
+					};
+					let delay = (next - now).to_std().unwrap_or_default();
+
+					let shutting_down = tokio::select! {
+						_ = tokio::time::sleep(delay) => false,
+						_ = shutdown_rx.changed() => true,
+					};
+
+					if let Err(e) = trigger_execution_service.flush_digest(&trigger_slug).await {
+						TriggerError::execution_error(e.to_string(), Some(e.into()), None);
+					}
+
+					if shutting_down {
+						tracing::info!("Shutting down digest task for trigger {}", trigger.name);
+						return;
+					}
+				}
+			}))
+		})
+		.collect()
+}
+
+/// Spawns a task publishing the messages queue triggers previously failed to publish.
+///
+/// Outboxes are drained at startup and then periodically, so that the pending messages of a
+/// trigger are delivered even when it has no new match to publish.
+///
+/// # Arguments
+/// * `shutdown_tx` - Watch channel for shutdown signals
+/// * `trigger_execution_service` - Service publishing queue messages
+///
+/// # Returns
+/// Returns the handle of the spawned task, or None if no queue trigger is configured
+pub fn spawn_queue_outbox_drainer<T: TriggerRepositoryTrait + Send + Sync + 'static>(
+	shutdown_tx: watch::Sender<bool>,
+	trigger_execution_service: Arc<TriggerExecutionService<T>>,
+) -> Option<tokio::task::JoinHandle<()>> {
+	let trigger_slugs = trigger_execution_service.queue_trigger_slugs();
+	if trigger_slugs.is_empty() {
+		return None;
+	}
+	let mut shutdown_rx = shutdown_tx.subscribe();
+
+	Some(tokio::spawn(async move {
+		loop {
+			for trigger_slug in &trigger_slugs {
+				match trigger_execution_service
+					.drain_queue_outbox(trigger_slug)
+					.await
+				{
+					Ok(0) => {}
+					Ok(published) => tracing::info!(
+						"Published {} pending messages of trigger {}",
+						published,
+						trigger_slug
+					),
+					Err(e) => {
+						TriggerError::execution_error(e.to_string(), Some(e.into()), None);
+					}
+				}
+			}
+
+			tokio::select! {
+				_ = tokio::time::sleep(QUEUE_OUTBOX_DRAIN_INTERVAL) => {}
+				_ = shutdown_rx.changed() => {
+					tracing::info!("Shutting down queue outbox task");
+					return;
+				}
+			}
+		}
+	}))
+}
+
 /// Checks if a network has any active monitors.
 ///
 /// # Arguments
@@ -447,15 +785,24 @@ fn filter_network_monitors(monitors: &[Monitor], network_slug: &String) -> Vec<M
 		.collect()
 }
 
+/// Evaluates a trigger condition script on a match.
+///
+/// Scripts which fail keep the match, without enriching it.
 async fn execute_trigger_condition(
 	trigger_condition: &TriggerConditions,
 	monitor_match: &MonitorMatch,
 	script_content: &(ScriptLanguage, String),
-) -> bool {
-	let executor = ScriptExecutorFactory::create(&script_content.0, &script_content.1);
+) -> ScriptOutput {
+	let executor = ScriptExecutorFactory::create_with_mode(
+		&script_content.0,
+		&trigger_condition.mode,
+		trigger_condition.interpreter.as_deref(),
+		trigger_condition.sandbox.as_ref(),
+		&script_content.1,
+	);
 
 	let result = executor
-		.execute(
+		.evaluate(
 			monitor_match.clone(),
 			&trigger_condition.timeout_ms,
 			trigger_condition.arguments.as_deref(),
@@ -464,12 +811,11 @@ async fn execute_trigger_condition(
 		.await;
 
 	match result {
-		Ok(true) => true,
+		Ok(output) => output,
 		Err(e) => {
 			ScriptError::execution_error(e.to_string(), None, None);
-			false
+			ScriptOutput::Filter(false)
 		}
-		_ => false,
 	}
 }
 
@@ -482,15 +828,19 @@ async fn run_trigger_filters(
 
 	for monitor_match in matches {
 		let mut is_filtered = false;
+		// Scripts enrich the match they receive, so later conditions see the fields of earlier ones
+		let mut enriched_match = monitor_match.clone();
 		let trigger_conditions = match monitor_match {
 			MonitorMatch::EVM(evm_match) => &evm_match.monitor.trigger_conditions,
 			MonitorMatch::Stellar(stellar_match) => &stellar_match.monitor.trigger_conditions,
+			MonitorMatch::Poll(poll_match) => &poll_match.monitor.trigger_conditions,
 		};
 
 		for trigger_condition in trigger_conditions {
 			let monitor_name = match monitor_match {
 				MonitorMatch::EVM(evm_match) => evm_match.monitor.name.clone(),
 				MonitorMatch::Stellar(stellar_match) => stellar_match.monitor.name.clone(),
+				MonitorMatch::Poll(poll_match) => poll_match.monitor.name.clone(),
 			};
 
 			let script_content = trigger_scripts
@@ -503,15 +853,20 @@ async fn run_trigger_filters(
 					ScriptError::execution_error("Script content not found".to_string(), None, None)
 				});
 			if let Ok(script_content) = script_content {
-				if execute_trigger_condition(trigger_condition, monitor_match, script_content).await
+				match execute_trigger_condition(trigger_condition, &enriched_match, script_content)
+					.await
 				{
-					is_filtered = true;
-					break;
+					ScriptOutput::Filter(true) => {
+						is_filtered = true;
+						break;
+					}
+					ScriptOutput::Filter(false) => {}
+					ScriptOutput::Enrich(fields) => enriched_match.enrich(fields),
 				}
 			}
 		}
 		if !is_filtered {
-			filtered_matches.push(monitor_match.clone());
+			filtered_matches.push(enriched_match);
 		}
 	}
 
@@ -524,7 +879,7 @@ mod tests {
 	use crate::{
 		models::{
 			EVMMonitorMatch, EVMReceiptLog, EVMTransaction, EVMTransactionReceipt, MatchConditions,
-			Monitor, MonitorMatch, ScriptLanguage, StellarBlock, StellarMonitorMatch,
+			Monitor, MonitorMatch, ScriptLanguage, ScriptMode, StellarBlock, StellarMonitorMatch,
 			StellarTransaction, StellarTransactionInfo, TriggerConditions,
 		},
 		utils::tests::{builders::evm::monitor::MonitorBuilder, evm::receipt::ReceiptBuilder},
@@ -623,8 +978,10 @@ mod tests {
 					functions: vec![],
 					events: vec![],
 					transactions: vec![],
+					..Default::default()
 				},
 				matched_on_args: None,
+				enrichment: None,
 			})),
 			BlockChainType::Stellar => MonitorMatch::Stellar(Box::new(StellarMonitorMatch {
 				monitor: create_test_monitor("test", vec![], false, script_path),
@@ -635,8 +992,10 @@ mod tests {
 					functions: vec![],
 					events: vec![],
 					transactions: vec![],
+					..Default::default()
 				},
 				matched_on_args: None,
+				enrichment: None,
 			})),
 			BlockChainType::Midnight => unimplemented!(),
 			BlockChainType::Solana => unimplemented!(),
@@ -658,8 +1017,10 @@ mod tests {
 					functions: vec![],
 					events: vec![],
 					transactions: vec![],
+					..Default::default()
 				},
 				matched_on_args: None,
+				enrichment: None,
 			})),
 			BlockChainType::Stellar => MonitorMatch::Stellar(Box::new(StellarMonitorMatch {
 				monitor,
@@ -670,8 +1031,10 @@ mod tests {
 					functions: vec![],
 					events: vec![],
 					transactions: vec![],
+					..Default::default()
 				},
 				matched_on_args: None,
+				enrichment: None,
 			})),
 			BlockChainType::Midnight => unimplemented!(),
 			BlockChainType::Solana => unimplemented!(),
@@ -868,6 +1231,9 @@ print(result)
 			script_path: temp_file.path().to_str().unwrap().to_string(),
 			timeout_ms: 1000,
 			arguments: None,
+			mode: ScriptMode::Process,
+			sandbox: None,
+			interpreter: None,
 		};
 		let match_item = create_mock_monitor_match_from_path(
 			BlockChainType::EVM,
@@ -877,7 +1243,7 @@ print(result)
 
 		let result =
 			execute_trigger_condition(&trigger_condition, &match_item, &script_content).await;
-		assert!(!result); // Should be false when script returns false
+		assert_eq!(result, ScriptOutput::Filter(false)); // Should be false when script returns false
 	}
 
 	#[tokio::test]
@@ -889,6 +1255,9 @@ print(result)
 			script_path: temp_file.path().to_str().unwrap().to_string(),
 			timeout_ms: 1000,
 			arguments: None,
+			mode: ScriptMode::Process,
+			sandbox: None,
+			interpreter: None,
 		};
 		let match_item = create_mock_monitor_match_from_path(
 			BlockChainType::EVM,
@@ -898,7 +1267,7 @@ print(result)
 
 		let result =
 			execute_trigger_condition(&trigger_condition, &match_item, &script_content).await;
-		assert!(!result); // Should be false when script errors
+		assert_eq!(result, ScriptOutput::Filter(false)); // Should be false when script errors
 	}
 
 	#[tokio::test]
@@ -908,6 +1277,9 @@ print(result)
 			script_path: "non_existent_script.py".to_string(),
 			timeout_ms: 1000,
 			arguments: None,
+			mode: ScriptMode::Process,
+			sandbox: None,
+			interpreter: None,
 		};
 		let match_item = create_mock_monitor_match_from_path(
 			BlockChainType::EVM,
@@ -917,7 +1289,94 @@ print(result)
 
 		let result =
 			execute_trigger_condition(&trigger_condition, &match_item, &script_content).await;
-		assert!(!result); // Should be false for invalid script
+		assert_eq!(result, ScriptOutput::Filter(false)); // Should be false for invalid script
+	}
+
+	#[tokio::test]
+	async fn test_execute_trigger_condition_worker_mode() {
+		let script_content = r#"
+import sys
+import json
+
+calls = 0
+for line in sys.stdin:
+    request = json.loads(line)
+    if request["type"] == "match":
+        calls += 1
+    print(json.dumps({"id": request["id"], "result": calls == 1}), flush=True)
+"#;
+		let temp_file = create_temp_script(script_content);
+		let trigger_condition = TriggerConditions {
+			language: ScriptLanguage::Python,
+			script_path: temp_file.path().to_str().unwrap().to_string(),
+			timeout_ms: 1000,
+			arguments: None,
+			mode: ScriptMode::Worker,
+			sandbox: None,
+			interpreter: None,
+		};
+		let match_item = create_mock_monitor_match_from_path(
+			BlockChainType::EVM,
+			Some(temp_file.path().to_str().unwrap()),
+		);
+		let script_content = (ScriptLanguage::Python, script_content.to_string());
+
+		// The worker keeps its state between matches
+		let first =
+			execute_trigger_condition(&trigger_condition, &match_item, &script_content).await;
+		let second =
+			execute_trigger_condition(&trigger_condition, &match_item, &script_content).await;
+		assert_eq!(first, ScriptOutput::Filter(true));
+		assert_eq!(second, ScriptOutput::Filter(false));
+	}
+
+	#[tokio::test]
+	async fn test_run_trigger_filters_enrich_match() {
+		let monitor = MonitorBuilder::new()
+			.name("monitor_test")
+			.networks(vec!["ethereum_mainnet".to_string()])
+			.trigger_condition("price.py", 1000, ScriptLanguage::Python, None)
+			.trigger_condition("risk.py", 1000, ScriptLanguage::Python, None)
+			.build();
+		let match_item = create_mock_monitor_match_from_monitor(BlockChainType::EVM, monitor);
+
+		let mut trigger_scripts = HashMap::new();
+		trigger_scripts.insert(
+			"monitor_test|price.py".to_string(),
+			(
+				ScriptLanguage::Python,
+				r#"
+import sys
+import json
+sys.stdin.read()
+print(json.dumps({"price_usd": 1.5, "risk_score": 0}))
+"#
+				.to_string(),
+			),
+		);
+		// Later scripts receive the fields of earlier ones, and may replace them
+		trigger_scripts.insert(
+			"monitor_test|risk.py".to_string(),
+			(
+				ScriptLanguage::Python,
+				r#"
+import sys
+import json
+data = json.loads(sys.stdin.read())
+price = data["monitor_match"]["EVM"]["enrichment"]["price_usd"]
+print(json.dumps({"risk_score": 87 if price > 1 else 0}))
+"#
+				.to_string(),
+			),
+		);
+
+		let matches = vec![match_item.clone()];
+		let filtered = run_trigger_filters(&matches, "ethereum_mainnet", &trigger_scripts).await;
+
+		assert_eq!(filtered.len(), 1);
+		let enrichment = filtered[0].enrichment().unwrap();
+		assert_eq!(enrichment["price_usd"], serde_json::json!(1.5));
+		assert_eq!(enrichment["risk_score"], serde_json::json!(87));
 	}
 
 	#[tokio::test]
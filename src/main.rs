@@ -27,16 +27,18 @@ pub mod utils;
 use crate::{
 	bootstrap::{
 		create_block_handler, create_trigger_handler, get_contract_specs, has_active_monitors,
-		initialize_services, Result,
+		initialize_services, load_price_feeds, load_token_metadata, spawn_digest_flushers,
+		spawn_queue_outbox_drainer, Result,
 	},
-	models::{BlockChainType, Network, ScriptLanguage},
+	models::{BlockChainType, ConfigLoader, Network, ScriptLanguage, Token},
 	repositories::{
 		MonitorRepository, MonitorService, NetworkRepository, NetworkService, TriggerRepository,
 	},
 	services::{
+		aggregation::WindowAggregationService,
 		blockchain::{ClientPool, ClientPoolTrait},
 		blockwatcher::{BlockTracker, BlockTrackerTrait, BlockWatcherService, FileBlockStorage},
-		filter::FilterService,
+		filter::{FilterService, PollStateStore},
 		trigger::{TriggerExecutionService, TriggerExecutionServiceTrait},
 	},
 	utils::{
@@ -224,6 +226,12 @@ async fn main() -> Result<()> {
 	.await
 	.map_err(|e| anyhow::anyhow!("Failed to initialize services: {}. Please refer to the documentation quickstart ({}) on how to configure the service.", e, DOCUMENTATION_URL))?;
 
+	// Filter plugins are registered with the filter service before this point, so monitors
+	// referencing a plugin which is not registered are rejected before any block is processed
+	filter_service
+		.check_plugins(&active_monitors)
+		.map_err(|e| anyhow::anyhow!("Invalid monitor configuration: {}", e))?;
+
 	// Pre-load all trigger scripts into memory at startup to reduce file I/O operations.
 	// This prevents repeated file descriptor usage during script execution and improves performance
 	// by keeping scripts readily available in memory.
@@ -237,6 +245,16 @@ async fn main() -> Result<()> {
 
 	let client_pool = Arc::new(ClientPool::new());
 
+	// Register the USD price feeds of the configured tokens, read by `usd()` in expressions and
+	// templates
+	let tokens: HashMap<String, Token> = Token::load_all(None).await?;
+	load_price_feeds(
+		&client_pool,
+		&tokens.into_values().collect::<Vec<_>>(),
+		&networks,
+	)
+	.await;
+
 	let should_test_monitor_execution = monitor_path.is_some();
 	// If monitor path is provided, test monitor execution else start the service
 	if should_test_monitor_execution {
@@ -326,6 +344,13 @@ async fn main() -> Result<()> {
 	// Fetch all contract specs for all active monitors
 	let contract_specs = get_contract_specs(&client_pool, &network_monitors).await;
 
+	// Fetch the token metadata of monitored contracts missing from the token configuration
+	load_token_metadata(&client_pool, &network_monitors).await;
+
+	let file_block_storage = Arc::new(FileBlockStorage::default());
+	let window_service = Arc::new(WindowAggregationService::new(file_block_storage.clone()));
+	let poll_store = Arc::new(PollStateStore::new(file_block_storage.clone()));
+
 	let (shutdown_tx, _) = watch::channel(false);
 	let block_handler = create_block_handler(
 		shutdown_tx.clone(),
@@ -333,14 +358,19 @@ async fn main() -> Result<()> {
 		active_monitors,
 		client_pool.clone(),
 		contract_specs,
+		window_service,
+		poll_store,
 	);
+	let digest_flushers =
+		spawn_digest_flushers(shutdown_tx.clone(), trigger_execution_service.clone());
+	let queue_outbox_drainer =
+		spawn_queue_outbox_drainer(shutdown_tx.clone(), trigger_execution_service.clone());
 	let trigger_handler = create_trigger_handler(
 		shutdown_tx.clone(),
 		trigger_execution_service,
 		active_monitors_trigger_scripts,
 	);
 
-	let file_block_storage = Arc::new(FileBlockStorage::default());
 	let block_watcher = BlockWatcherService::<FileBlockStorage, _, _, JobScheduler>::new(
 		file_block_storage.clone(),
 		block_handler,
@@ -418,6 +448,12 @@ async fn main() -> Result<()> {
 		}
 	}
 
+	// Wait for pending digests to be flushed
+	futures::future::join_all(digest_flushers).await;
+	if let Some(queue_outbox_drainer) = queue_outbox_drainer {
+		let _ = queue_outbox_drainer.await;
+	}
+
 	tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
 
 	info!("Shutdown complete");
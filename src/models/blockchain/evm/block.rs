@@ -91,6 +91,13 @@ impl Block {
 	pub fn number(&self) -> Option<u64> {
 		self.0.number.map(|n| n.to())
 	}
+
+	/// Get the block timestamp
+	///
+	/// Returns the block timestamp in seconds since the Unix epoch, or `None` if it is unset.
+	pub fn timestamp(&self) -> Option<u64> {
+		u64::try_from(self.0.timestamp).ok().filter(|t| *t > 0)
+	}
 }
 
 impl From<BaseBlock<EVMTransaction>> for Block {
@@ -193,6 +200,18 @@ mod tests {
 		assert_eq!(block_no_number.number(), None);
 	}
 
+	#[test]
+	fn test_block_timestamp() {
+		let base_block = create_test_block(12345);
+		assert_eq!(Block(base_block.clone()).timestamp(), None);
+
+		let block = Block(BaseBlock {
+			timestamp: U256::from(1_700_000_000u64),
+			..base_block
+		});
+		assert_eq!(block.timestamp(), Some(1_700_000_000));
+	}
+
 	#[test]
 	fn test_from_base_block() {
 		let base_block = create_test_block(12345);
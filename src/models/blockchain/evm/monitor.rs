@@ -26,6 +26,10 @@ pub struct EVMMonitorMatch {
 
 	/// Decoded arguments from the matched conditions
 	pub matched_on_args: Option<MatchArguments>,
+
+	/// Fields returned by trigger condition scripts to enrich the match
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub enrichment: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
 /// Collection of decoded parameters from matched conditions
@@ -39,6 +43,10 @@ pub struct MatchParamsMap {
 
 	/// Raw function/event signature as bytes
 	pub hex_signature: Option<String>,
+
+	/// Index of the log in the block, for events
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub log_index: Option<u64>,
 }
 
 /// Single decoded parameter from a function or event
@@ -180,6 +188,7 @@ mod tests {
 				},
 			]),
 			hex_signature: Some("0xa9059cbb".to_string()),
+			log_index: None,
 		};
 
 		let monitor_match = EVMMonitorMatch {
@@ -195,11 +204,13 @@ mod tests {
 				}],
 				events: vec![],
 				transactions: vec![],
+				..Default::default()
 			},
 			matched_on_args: Some(MatchArguments {
 				functions: Some(vec![match_params]),
 				events: None,
 			}),
+			enrichment: None,
 		};
 
 		assert_eq!(monitor_match.monitor.name, "TestMonitor");
@@ -256,6 +267,7 @@ mod tests {
 					},
 				]),
 				hex_signature: Some("0xa9059cbb".to_string()),
+				log_index: None,
 			}]),
 			events: Some(vec![MatchParamsMap {
 				signature: "Transfer(address,address,uint256)".to_string(),
@@ -283,6 +295,7 @@ mod tests {
 					"0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
 						.to_string(),
 				),
+				log_index: Some(0),
 			}]),
 		};
 
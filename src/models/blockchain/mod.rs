@@ -7,6 +7,7 @@
 use serde::{Deserialize, Serialize};
 
 pub mod evm;
+pub mod poll;
 pub mod stellar;
 
 /// Supported blockchain platform types
@@ -45,6 +46,14 @@ impl BlockType {
 			BlockType::Stellar(b) => b.number(),
 		}
 	}
+
+	/// Returns the block timestamp in seconds since the Unix epoch, if known
+	pub fn timestamp(&self) -> Option<u64> {
+		match self {
+			BlockType::EVM(b) => b.timestamp(),
+			BlockType::Stellar(b) => b.timestamp(),
+		}
+	}
 }
 
 /// Transaction data from different blockchain platforms
@@ -79,6 +88,36 @@ pub enum MonitorMatch {
 	/// # Note
 	/// Box is used here to equalize the enum variants
 	Stellar(Box<stellar::StellarMonitorMatch>),
+	/// Values read by a state-polling monitor
+	///
+	/// # Note
+	/// Box is used here to equalize the enum variants
+	Poll(Box<poll::PollMonitorMatch>),
+}
+
+impl MonitorMatch {
+	/// Returns the fields trigger condition scripts enriched the match with, if any
+	pub fn enrichment(&self) -> Option<&serde_json::Map<String, serde_json::Value>> {
+		match self {
+			Self::EVM(evm_match) => evm_match.enrichment.as_ref(),
+			Self::Stellar(stellar_match) => stellar_match.enrichment.as_ref(),
+			Self::Poll(poll_match) => poll_match.enrichment.as_ref(),
+		}
+	}
+
+	/// Merges fields returned by a trigger condition script into the enrichment of the match
+	///
+	/// Fields of later scripts replace fields of the same name returned by earlier ones.
+	pub fn enrich(&mut self, fields: serde_json::Map<String, serde_json::Value>) {
+		let enrichment = match self {
+			Self::EVM(evm_match) => &mut evm_match.enrichment,
+			Self::Stellar(stellar_match) => &mut stellar_match.enrichment,
+			Self::Poll(poll_match) => &mut poll_match.enrichment,
+		};
+		enrichment
+			.get_or_insert_with(Default::default)
+			.extend(fields);
+	}
 }
 
 /// Structure to hold block processing results
@@ -0,0 +1,52 @@
+//! Matches of state-polling monitors.
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::Monitor;
+
+/// Result of a poll whose expression held, independent of any transaction
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PollMonitorMatch {
+	/// Monitor configuration that triggered the match
+	pub monitor: Monitor,
+
+	/// Network slug the values were read on
+	pub network_slug: String,
+
+	/// Block (or ledger) at which the values were read
+	pub block_number: u64,
+
+	/// Timestamp of the block in seconds since the Unix epoch, if known
+	pub timestamp: Option<u64>,
+
+	/// Values read by the poll
+	pub values: Vec<PollValue>,
+
+	/// Fields returned by trigger condition scripts to enrich the match
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub enrichment: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+impl PollMonitorMatch {
+	/// Identifier of the poll, standing in for the transaction hash where matches are keyed by
+	/// transaction
+	pub fn poll_id(&self) -> String {
+		format!("poll:{}:{}", self.monitor.name, self.block_number)
+	}
+}
+
+/// Single value read by a poll
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct PollValue {
+	/// Name of the read
+	pub name: String,
+
+	/// Value read at the block of the match
+	pub value: String,
+
+	/// Value read by the previous poll, or the current value on the first poll
+	pub previous: String,
+
+	/// Type of the value (e.g., "uint256")
+	pub kind: String,
+}
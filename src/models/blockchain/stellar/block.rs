@@ -57,6 +57,18 @@ impl Block {
 	pub fn number(&self) -> Option<u64> {
 		Some(self.0.sequence as u64)
 	}
+
+	/// Get the ledger close time in seconds since the Unix epoch
+	///
+	/// The close time is reported either as Unix seconds or as an RFC 3339 timestamp.
+	pub fn timestamp(&self) -> Option<u64> {
+		let close_time = self.0.ledger_close_time.trim();
+		close_time.parse::<u64>().ok().or_else(|| {
+			chrono::DateTime::parse_from_rfc3339(close_time)
+				.ok()
+				.and_then(|t| u64::try_from(t.timestamp()).ok())
+		})
+	}
 }
 
 impl From<LedgerInfo> for Block {
@@ -103,6 +115,27 @@ mod tests {
 		assert_eq!(block.ledger_metadata, "base64metadata");
 	}
 
+	#[test]
+	fn test_block_timestamp() {
+		let mut ledger_info = LedgerInfo {
+			ledger_close_time: "2024-03-20T10:00:00Z".to_string(),
+			..Default::default()
+		};
+		assert_eq!(
+			Block::from(ledger_info.clone()).timestamp(),
+			Some(1710928800)
+		);
+
+		ledger_info.ledger_close_time = "1710928800".to_string();
+		assert_eq!(
+			Block::from(ledger_info.clone()).timestamp(),
+			Some(1710928800)
+		);
+
+		ledger_info.ledger_close_time = String::new();
+		assert_eq!(Block::from(ledger_info).timestamp(), None);
+	}
+
 	#[test]
 	fn test_default_implementation() {
 		let block = Block::default();
@@ -31,6 +31,10 @@ pub struct MonitorMatch {
 
 	/// Decoded arguments from the matched conditions
 	pub matched_on_args: Option<MatchArguments>,
+
+	/// Fields returned by trigger condition scripts to enrich the match
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub enrichment: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
 /// Collection of decoded parameters from matched conditions
@@ -41,6 +45,10 @@ pub struct MatchParamsMap {
 
 	/// Decoded argument values
 	pub args: Option<Vec<MatchParamEntry>>,
+
+	/// Index of the event in its transaction, for events
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub event_index: Option<u64>,
 }
 
 /// Single decoded parameter from a function or event
@@ -476,6 +484,7 @@ mod tests {
 					indexed: false,
 				},
 			]),
+			event_index: None,
 		};
 
 		let monitor_match = MonitorMatch {
@@ -490,11 +499,13 @@ mod tests {
 				}],
 				events: vec![],
 				transactions: vec![],
+				..Default::default()
 			},
 			matched_on_args: Some(MatchArguments {
 				functions: Some(vec![match_params]),
 				events: None,
 			}),
+			enrichment: None,
 		};
 
 		assert_eq!(monitor_match.monitor.name, "TestMonitor");
@@ -583,6 +594,7 @@ mod tests {
 						indexed: false,
 					},
 				]),
+				event_index: None,
 			}]),
 			events: Some(vec![MatchParamsMap {
 				signature: "Transfer(address,address,uint256)".to_string(),
@@ -608,6 +620,7 @@ mod tests {
 						indexed: false,
 					},
 				]),
+				event_index: Some(0),
 			}]),
 		};
 
@@ -0,0 +1,276 @@
+//! Address set configuration loading and validation.
+//!
+//! This module implements the ConfigLoader trait for AddressSet configurations,
+//! allowing named address sets to be loaded from JSON files.
+
+use async_trait::async_trait;
+use std::{collections::HashMap, fs, path::Path};
+
+use crate::{
+	models::{config::error::ConfigError, AddressSet, ConfigLoader},
+	utils::normalize_string,
+};
+
+#[async_trait]
+impl ConfigLoader for AddressSet {
+	/// Resolve all secrets in the address set configuration
+	async fn resolve_secrets(&self) -> Result<Self, ConfigError> {
+		Ok(self.clone())
+	}
+
+	/// Load all address set configurations from a directory
+	///
+	/// Reads and parses all JSON files in the specified directory (or default
+	/// config directory) as address set configurations. Address sets are optional,
+	/// so a missing directory yields an empty collection.
+	async fn load_all<T>(path: Option<&Path>) -> Result<T, ConfigError>
+	where
+		T: FromIterator<(String, Self)>,
+	{
+		let address_set_dir = path.unwrap_or(Path::new("config/address_sets"));
+		let mut pairs = Vec::new();
+
+		if !address_set_dir.exists() {
+			return Ok(T::from_iter(pairs));
+		}
+
+		for entry in fs::read_dir(address_set_dir).map_err(|e| {
+			ConfigError::file_error(
+				format!("failed to read address sets directory: {}", e),
+				Some(Box::new(e)),
+				Some(HashMap::from([(
+					"path".to_string(),
+					address_set_dir.display().to_string(),
+				)])),
+			)
+		})? {
+			let entry = entry.map_err(|e| {
+				ConfigError::file_error(
+					format!("failed to read directory entry: {}", e),
+					Some(Box::new(e)),
+					Some(HashMap::from([(
+						"path".to_string(),
+						address_set_dir.display().to_string(),
+					)])),
+				)
+			})?;
+			let path = entry.path();
+
+			if !Self::is_json_file(&path) {
+				continue;
+			}
+
+			let name = path
+				.file_stem()
+				.and_then(|s| s.to_str())
+				.unwrap_or("unknown")
+				.to_string();
+
+			let address_set = Self::load_from_path(&path).await?;
+
+			let existing_sets: Vec<&AddressSet> = pairs.iter().map(|(_, set)| set).collect();
+			// Check address set name uniqueness before pushing
+			Self::validate_uniqueness(&existing_sets, &address_set, &path.display().to_string())?;
+
+			pairs.push((name, address_set));
+		}
+
+		Ok(T::from_iter(pairs))
+	}
+
+	/// Load an address set configuration from a specific file
+	///
+	/// Reads and parses a single JSON file as an address set configuration.
+	async fn load_from_path(path: &Path) -> Result<Self, ConfigError> {
+		let file = std::fs::File::open(path).map_err(|e| {
+			ConfigError::file_error(
+				format!("failed to open address set config file: {}", e),
+				Some(Box::new(e)),
+				Some(HashMap::from([(
+					"path".to_string(),
+					path.display().to_string(),
+				)])),
+			)
+		})?;
+		let config: AddressSet = serde_json::from_reader(file).map_err(|e| {
+			ConfigError::parse_error(
+				format!("failed to parse address set config: {}", e),
+				Some(Box::new(e)),
+				Some(HashMap::from([(
+					"path".to_string(),
+					path.display().to_string(),
+				)])),
+			)
+		})?;
+
+		// Validate the config after loading
+		config.validate()?;
+
+		Ok(config)
+	}
+
+	/// Validate the address set configuration
+	///
+	/// Ensures that:
+	/// - The name can be referenced from expressions (letters, digits and underscores,
+	///   not starting with a digit)
+	/// - No address entry is empty
+	fn validate(&self) -> Result<(), ConfigError> {
+		let mut name_chars = self.name.chars();
+		let is_valid_name = name_chars
+			.next()
+			.is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+			&& name_chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+		if !is_valid_name {
+			return Err(ConfigError::validation_error(
+				format!(
+					"Invalid address set name '{}': must contain only letters, numbers, and \
+					 underscores and must not start with a number",
+					self.name
+				),
+				None,
+				None,
+			));
+		}
+
+		if self
+			.addresses
+			.iter()
+			.any(|address| address.trim().is_empty())
+		{
+			return Err(ConfigError::validation_error(
+				format!("Address set '{}' contains an empty address", self.name),
+				None,
+				None,
+			));
+		}
+
+		Ok(())
+	}
+
+	/// Address sets have no protocol to validate
+	fn validate_protocol(&self) {}
+
+	fn validate_uniqueness(
+		instances: &[&Self],
+		current_instance: &Self,
+		file_path: &str,
+	) -> Result<(), ConfigError> {
+		if instances.iter().any(|existing_set| {
+			normalize_string(&existing_set.name) == normalize_string(&current_instance.name)
+		}) {
+			Err(ConfigError::validation_error(
+				format!(
+					"Duplicate address set name found: '{}'",
+					current_instance.name
+				),
+				None,
+				Some(HashMap::from([
+					(
+						"address_set_name".to_string(),
+						current_instance.name.to_string(),
+					),
+					("path".to_string(), file_path.to_string()),
+				])),
+			))
+		} else {
+			Ok(())
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	fn create_address_set(name: &str, addresses: &[&str]) -> AddressSet {
+		AddressSet {
+			name: name.to_string(),
+			addresses: addresses.iter().map(|a| a.to_string()).collect(),
+		}
+	}
+
+	#[test]
+	fn test_validate_valid_address_set() {
+		let set = create_address_set(
+			"hot_wallets",
+			&["0x0000000000000000000000000000000000000001"],
+		);
+		assert!(set.validate().is_ok());
+	}
+
+	#[test]
+	fn test_validate_invalid_name() {
+		for name in ["", "1wallets", "hot-wallets", "hot wallets"] {
+			let set = create_address_set(name, &["0x01"]);
+			assert!(
+				matches!(set.validate(), Err(ConfigError::ValidationError(_))),
+				"name '{}' should be rejected",
+				name
+			);
+		}
+	}
+
+	#[test]
+	fn test_validate_empty_address() {
+		let set = create_address_set("hot_wallets", &["0x01", "  "]);
+		assert!(matches!(
+			set.validate(),
+			Err(ConfigError::ValidationError(_))
+		));
+	}
+
+	#[tokio::test]
+	async fn test_load_all_directory_not_found() {
+		let result: Result<HashMap<String, AddressSet>, ConfigError> =
+			AddressSet::load_all(Some(Path::new("non_existent_directory"))).await;
+		assert!(result.unwrap().is_empty());
+	}
+
+	#[tokio::test]
+	async fn test_load_all_address_sets() {
+		let temp_dir = TempDir::new().unwrap();
+		fs::write(
+			temp_dir.path().join("hot_wallets.json"),
+			r#"{"name": "hot_wallets", "addresses": ["0xAbC0000000000000000000000000000000000001"]}"#,
+		)
+		.unwrap();
+		fs::write(
+			temp_dir.path().join("exchanges.json"),
+			r#"{"name": "exchanges", "addresses": []}"#,
+		)
+		.unwrap();
+
+		let result: HashMap<String, AddressSet> =
+			AddressSet::load_all(Some(temp_dir.path())).await.unwrap();
+
+		assert_eq!(result.len(), 2);
+		assert_eq!(result["hot_wallets"].addresses.len(), 1);
+		assert!(result["exchanges"].addresses.is_empty());
+	}
+
+	#[tokio::test]
+	async fn test_load_all_duplicate_address_set_name() {
+		let temp_dir = TempDir::new().unwrap();
+		fs::write(
+			temp_dir.path().join("set1.json"),
+			r#"{"name": "hot_wallets", "addresses": []}"#,
+		)
+		.unwrap();
+		fs::write(
+			temp_dir.path().join("set2.json"),
+			r#"{"name": "Hot_Wallets", "addresses": []}"#,
+		)
+		.unwrap();
+
+		let result: Result<HashMap<String, AddressSet>, ConfigError> =
+			AddressSet::load_all(Some(temp_dir.path())).await;
+
+		assert!(matches!(result, Err(ConfigError::ValidationError(_))));
+		if let Err(ConfigError::ValidationError(err)) = result {
+			assert!(err.message.contains("Duplicate address set name found"));
+		}
+	}
+}
@@ -1,16 +1,18 @@
 //! Configuration loading and validation.
 //!
 //! This module provides traits and implementations for loading and validating
-//! configuration files for networks, monitors, and triggers.
+//! configuration files for networks, monitors, triggers, address sets, and tokens.
 
 #![allow(clippy::result_large_err)]
 
 use async_trait::async_trait;
 use std::path::Path;
 
+mod address_set_config;
 mod error;
 mod monitor_config;
 mod network_config;
+mod token_config;
 mod trigger_config;
 
 pub use error::ConfigError;
@@ -4,11 +4,18 @@
 //! allowing monitors to be loaded from JSON files.
 
 use async_trait::async_trait;
+use ethabi::ParamType;
 use std::{collections::HashMap, fs, path::Path};
 
 use crate::{
-	models::{config::error::ConfigError, ConfigLoader, Monitor},
-	services::trigger::validate_script_config,
+	models::{config::error::ConfigError, ConfigLoader, Monitor, PollRead},
+	services::{
+		filter::{
+			parse_call_signature, parse_return_type, BLOCK_NUMBER_VARIABLE,
+			BLOCK_TIMESTAMP_VARIABLE, PREVIOUS_SUFFIX,
+		},
+		trigger::{validate_script_config, validate_script_executable, validate_script_sandbox},
+	},
 	utils::normalize_string,
 };
 
@@ -172,6 +179,341 @@ impl ConfigLoader for Monitor {
 			}
 		}
 
+		// Validate aggregate conditions
+		for aggregate in &self.match_conditions.aggregates {
+			if !aggregate.signature.contains('(') || !aggregate.signature.contains(')') {
+				return Err(ConfigError::validation_error(
+					format!(
+						"Invalid aggregate event signature format: {}",
+						aggregate.signature
+					),
+					None,
+					None,
+				));
+			}
+
+			if aggregate.function.requires_field()
+				&& aggregate
+					.field
+					.as_deref()
+					.is_none_or(|f| f.trim().is_empty())
+			{
+				return Err(ConfigError::validation_error(
+					format!(
+						"Aggregate function {:?} over {} requires a field",
+						aggregate.function, aggregate.signature
+					),
+					None,
+					None,
+				));
+			}
+
+			if aggregate.expression.trim().is_empty() {
+				return Err(ConfigError::validation_error(
+					format!(
+						"Aggregate over {} requires an expression",
+						aggregate.signature
+					),
+					None,
+					None,
+				));
+			}
+		}
+
+		// Validate window aggregation
+		if let Some(window) = &self.window {
+			if window.window_secs == 0 {
+				return Err(ConfigError::validation_error(
+					"Window duration must be greater than 0",
+					None,
+					None,
+				));
+			}
+
+			if window.function.requires_field()
+				&& window.field.as_deref().is_none_or(|f| f.trim().is_empty())
+			{
+				return Err(ConfigError::validation_error(
+					format!("Window function {:?} requires a field", window.function),
+					None,
+					None,
+				));
+			}
+
+			if window.expression.trim().is_empty() {
+				return Err(ConfigError::validation_error(
+					"Window requires an expression",
+					None,
+					None,
+				));
+			}
+		}
+
+		// Validate notification throttling
+		if let Some(throttle) = &self.throttle {
+			if throttle.max_notifications.is_none() && throttle.cooldown_secs.is_none() {
+				return Err(ConfigError::validation_error(
+					"Throttle requires max_notifications or cooldown_secs",
+					None,
+					None,
+				));
+			}
+
+			if let Some(max_notifications) = throttle.max_notifications {
+				if max_notifications == 0 {
+					return Err(ConfigError::validation_error(
+						"Throttle max_notifications must be greater than 0",
+						None,
+						None,
+					));
+				}
+
+				if throttle.window_secs.is_none_or(|window| window == 0) {
+					return Err(ConfigError::validation_error(
+						"Throttle max_notifications requires a window_secs greater than 0",
+						None,
+						None,
+					));
+				}
+			}
+
+			if throttle
+				.dedup_key
+				.as_deref()
+				.is_some_and(|key| key.trim().is_empty())
+			{
+				return Err(ConfigError::validation_error(
+					"Throttle dedup_key must not be empty",
+					None,
+					None,
+				));
+			}
+		}
+
+		// Validate state calls
+		if let Some(state) = &self.state {
+			if state.calls.is_empty() {
+				return Err(ConfigError::validation_error(
+					"State requires at least one call",
+					None,
+					None,
+				));
+			}
+
+			let mut names = Vec::new();
+			for call in &state.calls {
+				if !is_identifier(&call.name) {
+					return Err(ConfigError::validation_error(
+						format!("Invalid state call name: '{}'", call.name),
+						None,
+						None,
+					));
+				}
+
+				let name_previous = format!("{}{}", call.name, PREVIOUS_SUFFIX);
+				if names.contains(&call.name) || (call.previous && names.contains(&name_previous)) {
+					return Err(ConfigError::validation_error(
+						format!("Duplicate state call name: {}", call.name),
+						None,
+						None,
+					));
+				}
+				names.push(call.name.clone());
+				if call.previous {
+					names.push(name_previous);
+				}
+
+				if call.address.trim().is_empty() {
+					return Err(ConfigError::validation_error(
+						format!("State call {} requires an address", call.name),
+						None,
+						None,
+					));
+				}
+
+				let (_, params) = parse_call_signature(&call.signature).map_err(|e| {
+					ConfigError::validation_error(
+						format!("Invalid state call {}: {}", call.name, e),
+						None,
+						None,
+					)
+				})?;
+				if params.len() != call.args.len() {
+					return Err(ConfigError::validation_error(
+						format!(
+							"State call {} expects {} arguments, got {}",
+							call.name,
+							params.len(),
+							call.args.len()
+						),
+						None,
+						None,
+					));
+				}
+
+				parse_return_type(&call.returns).map_err(|e| {
+					ConfigError::validation_error(
+						format!("Invalid state call {}: {}", call.name, e),
+						None,
+						None,
+					)
+				})?;
+			}
+
+			if state
+				.expression
+				.as_deref()
+				.is_some_and(|expr| expr.trim().is_empty())
+			{
+				return Err(ConfigError::validation_error(
+					"State expression must not be empty",
+					None,
+					None,
+				));
+			}
+		}
+
+		// Validate polling
+		if let Some(poll) = &self.poll {
+			let conditions = &self.match_conditions;
+			if !conditions.functions.is_empty()
+				|| !conditions.events.is_empty()
+				|| !conditions.transactions.is_empty()
+				|| !conditions.aggregates.is_empty()
+				|| self.state.is_some()
+			{
+				return Err(ConfigError::validation_error(
+					"Polling monitors cannot have match conditions or state",
+					None,
+					None,
+				));
+			}
+
+			match (poll.every_blocks, poll.interval_secs) {
+				(Some(0), None) | (None, Some(0)) => {
+					return Err(ConfigError::validation_error(
+						"Poll schedule must be greater than 0",
+						None,
+						None,
+					));
+				}
+				(Some(_), None) | (None, Some(_)) => {}
+				_ => {
+					return Err(ConfigError::validation_error(
+						"Poll requires exactly one of every_blocks and interval_secs",
+						None,
+						None,
+					));
+				}
+			}
+
+			if poll.reads.is_empty() {
+				return Err(ConfigError::validation_error(
+					"Poll requires at least one read",
+					None,
+					None,
+				));
+			}
+
+			let mut names = vec![
+				BLOCK_NUMBER_VARIABLE.to_string(),
+				BLOCK_TIMESTAMP_VARIABLE.to_string(),
+			];
+			for read in &poll.reads {
+				let name = read.name();
+				if !is_identifier(name) {
+					return Err(ConfigError::validation_error(
+						format!("Invalid poll read name: '{}'", name),
+						None,
+						None,
+					));
+				}
+
+				let name_previous = format!("{}{}", name, PREVIOUS_SUFFIX);
+				if names.iter().any(|n| n == name || *n == name_previous) {
+					return Err(ConfigError::validation_error(
+						format!("Duplicate or reserved poll read name: {}", name),
+						None,
+						None,
+					));
+				}
+				names.push(name.to_string());
+				names.push(name_previous);
+
+				match read {
+					PollRead::Balance { address, .. } if address.trim().is_empty() => {
+						return Err(ConfigError::validation_error(
+							format!("Poll read {} requires an address", name),
+							None,
+							None,
+						));
+					}
+					PollRead::Balance { .. } => {}
+					PollRead::Call {
+						address,
+						signature,
+						args,
+						returns,
+						output,
+						..
+					} => {
+						let invalid = |message: String| {
+							ConfigError::validation_error(
+								format!("Invalid poll read {}: {}", name, message),
+								None,
+								None,
+							)
+						};
+						if address.trim().is_empty() {
+							return Err(invalid("address is required".to_string()));
+						}
+						let (_, params) =
+							parse_call_signature(signature).map_err(|e| invalid(e.to_string()))?;
+						if params.len() != args.len() {
+							return Err(invalid(format!(
+								"expects {} arguments, got {}",
+								params.len(),
+								args.len()
+							)));
+						}
+						let returns =
+							parse_return_type(returns).map_err(|e| invalid(e.to_string()))?;
+						match (output, returns) {
+							(None, _) => {}
+							(Some(index), ParamType::Tuple(types)) if *index < types.len() => {}
+							(Some(index), _) => {
+								return Err(invalid(format!(
+									"output {} is not a value of the returned tuple",
+									index
+								)));
+							}
+						}
+					}
+				}
+			}
+
+			if poll.expression.trim().is_empty() {
+				return Err(ConfigError::validation_error(
+					"Poll expression must not be empty",
+					None,
+					None,
+				));
+			}
+		}
+
+		// Filter plugins are registered at runtime, only their names can be checked here
+		if self
+			.filter_plugins
+			.iter()
+			.any(|filter_plugin| filter_plugin.plugin.trim().is_empty())
+		{
+			return Err(ConfigError::validation_error(
+				"Filter plugin name cannot be empty",
+				None,
+				None,
+			));
+		}
+
 		// Validate trigger conditions (focus on script path, timeout, and language)
 		for trigger_condition in &self.trigger_conditions {
 			validate_script_config(
@@ -179,6 +521,14 @@ impl ConfigLoader for Monitor {
 				&trigger_condition.language,
 				&trigger_condition.timeout_ms,
 			)?;
+			validate_script_executable(
+				&trigger_condition.script_path,
+				&trigger_condition.language,
+				trigger_condition.interpreter.as_deref(),
+			)?;
+			if let Some(sandbox) = &trigger_condition.sandbox {
+				validate_script_sandbox(sandbox)?;
+			}
 		}
 
 		// Log a warning if the monitor uses an insecure protocol
@@ -235,11 +585,23 @@ impl ConfigLoader for Monitor {
 	}
 }
 
+/// Checks whether a name can be referenced as a variable from expressions
+fn is_identifier(name: &str) -> bool {
+	let mut chars = name.chars();
+	chars
+		.next()
+		.is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+		&& chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use crate::{
-		models::core::{ScriptLanguage, TransactionStatus},
+		models::core::{
+			AggregateCondition, AggregateFunction, PollConfig, ScriptLanguage, ScriptMode,
+			StateCall, StateConditions, ThrottleConfig, TransactionStatus, WindowAggregation,
+		},
 		utils::tests::builders::evm::monitor::MonitorBuilder,
 	};
 	use std::collections::HashMap;
@@ -402,6 +764,346 @@ mod tests {
 		assert!(invalid_monitor.validate().is_err());
 	}
 
+	#[test]
+	fn test_validate_monitor_with_aggregates() {
+		let aggregate = AggregateCondition {
+			function: AggregateFunction::Sum,
+			signature: "Transfer(address,address,uint256)".to_string(),
+			field: Some("value".to_string()),
+			filter: None,
+			expression: "result > 1000".to_string(),
+		};
+
+		let valid_monitor = MonitorBuilder::new()
+			.name("TestMonitor")
+			.networks(vec!["ethereum_mainnet".to_string()])
+			.aggregate(aggregate.clone())
+			.aggregate(AggregateCondition {
+				function: AggregateFunction::Count,
+				field: None,
+				..aggregate.clone()
+			})
+			.build();
+		assert!(valid_monitor.validate().is_ok());
+
+		let invalid_aggregates = vec![
+			AggregateCondition {
+				signature: "Transfer".to_string(),
+				..aggregate.clone()
+			},
+			AggregateCondition {
+				field: None,
+				..aggregate.clone()
+			},
+			AggregateCondition {
+				expression: " ".to_string(),
+				..aggregate.clone()
+			},
+		];
+		for invalid_aggregate in invalid_aggregates {
+			let invalid_monitor = MonitorBuilder::new()
+				.name("TestMonitor")
+				.networks(vec!["ethereum_mainnet".to_string()])
+				.aggregate(invalid_aggregate)
+				.build();
+			assert!(matches!(
+				invalid_monitor.validate(),
+				Err(ConfigError::ValidationError(_))
+			));
+		}
+	}
+
+	#[test]
+	fn test_validate_monitor_with_window() {
+		let window = WindowAggregation {
+			function: AggregateFunction::Sum,
+			field: Some("events.0.args.value".to_string()),
+			group_by: vec!["events.0.args.from".to_string()],
+			window_secs: 600,
+			expression: "result > 1000".to_string(),
+		};
+
+		let valid_monitor = MonitorBuilder::new()
+			.name("TestMonitor")
+			.networks(vec!["ethereum_mainnet".to_string()])
+			.window(window.clone())
+			.build();
+		assert!(valid_monitor.validate().is_ok());
+
+		let invalid_windows = vec![
+			WindowAggregation {
+				window_secs: 0,
+				..window.clone()
+			},
+			WindowAggregation {
+				field: None,
+				..window.clone()
+			},
+			WindowAggregation {
+				expression: String::new(),
+				..window.clone()
+			},
+		];
+		for invalid_window in invalid_windows {
+			let invalid_monitor = MonitorBuilder::new()
+				.name("TestMonitor")
+				.networks(vec!["ethereum_mainnet".to_string()])
+				.window(invalid_window)
+				.build();
+			assert!(matches!(
+				invalid_monitor.validate(),
+				Err(ConfigError::ValidationError(_))
+			));
+		}
+	}
+
+	#[test]
+	fn test_validate_monitor_with_throttle() {
+		let throttle = ThrottleConfig {
+			max_notifications: Some(5),
+			window_secs: Some(3600),
+			dedup_key: Some("${events.0.args.to}".to_string()),
+			cooldown_secs: None,
+		};
+
+		let valid_monitor = MonitorBuilder::new()
+			.name("TestMonitor")
+			.networks(vec!["ethereum_mainnet".to_string()])
+			.throttle(throttle.clone())
+			.build();
+		assert!(valid_monitor.validate().is_ok());
+
+		let invalid_throttles = vec![
+			ThrottleConfig::default(),
+			ThrottleConfig {
+				max_notifications: Some(0),
+				..throttle.clone()
+			},
+			ThrottleConfig {
+				window_secs: None,
+				..throttle.clone()
+			},
+			ThrottleConfig {
+				dedup_key: Some(" ".to_string()),
+				..throttle.clone()
+			},
+		];
+		for invalid_throttle in invalid_throttles {
+			let invalid_monitor = MonitorBuilder::new()
+				.name("TestMonitor")
+				.networks(vec!["ethereum_mainnet".to_string()])
+				.throttle(invalid_throttle)
+				.build();
+			assert!(matches!(
+				invalid_monitor.validate(),
+				Err(ConfigError::ValidationError(_))
+			));
+		}
+	}
+
+	#[test]
+	fn test_validate_monitor_with_state() {
+		let state = StateConditions {
+			calls: vec![StateCall {
+				name: "balance".to_string(),
+				address: "${transaction.to}".to_string(),
+				signature: "balanceOf(address)".to_string(),
+				returns: "uint256".to_string(),
+				args: vec!["${events.0.args.owner}".to_string()],
+				previous: true,
+			}],
+			expression: Some("balance < balance_previous".to_string()),
+		};
+
+		let valid_monitor = MonitorBuilder::new()
+			.name("TestMonitor")
+			.networks(vec!["ethereum_mainnet".to_string()])
+			.state(state.clone())
+			.build();
+		assert!(valid_monitor.validate().is_ok());
+
+		let call = state.calls[0].clone();
+		let invalid_calls = vec![
+			vec![],
+			vec![StateCall {
+				name: "total-assets".to_string(),
+				..call.clone()
+			}],
+			vec![call.clone(), call.clone()],
+			vec![
+				call.clone(),
+				StateCall {
+					name: "balance_previous".to_string(),
+					..call.clone()
+				},
+			],
+			vec![StateCall {
+				address: String::new(),
+				..call.clone()
+			}],
+			vec![StateCall {
+				signature: "balanceOf".to_string(),
+				..call.clone()
+			}],
+			vec![StateCall {
+				args: vec![],
+				..call.clone()
+			}],
+			vec![StateCall {
+				returns: "integer".to_string(),
+				..call.clone()
+			}],
+		];
+		for invalid_calls in invalid_calls {
+			let invalid_monitor = MonitorBuilder::new()
+				.name("TestMonitor")
+				.networks(vec!["ethereum_mainnet".to_string()])
+				.state(StateConditions {
+					calls: invalid_calls,
+					..state.clone()
+				})
+				.build();
+			assert!(matches!(
+				invalid_monitor.validate(),
+				Err(ConfigError::ValidationError(_))
+			));
+		}
+	}
+
+	#[test]
+	fn test_validate_monitor_with_poll() {
+		let poll = PollConfig {
+			every_blocks: Some(10),
+			interval_secs: None,
+			reads: vec![
+				PollRead::Balance {
+					name: "balance".to_string(),
+					address: "0x0000000000000000000000000000000000000001".to_string(),
+				},
+				PollRead::Call {
+					name: "updated_at".to_string(),
+					address: "0x0000000000000000000000000000000000000002".to_string(),
+					signature: "latestRoundData()".to_string(),
+					args: vec![],
+					returns: "(uint80,int256,uint256,uint256,uint80)".to_string(),
+					output: Some(3),
+				},
+			],
+			expression: "balance < 1000 OR block_timestamp - updated_at > 3600".to_string(),
+		};
+		let monitor = |poll: PollConfig| {
+			MonitorBuilder::new()
+				.name("TestMonitor")
+				.networks(vec!["ethereum_mainnet".to_string()])
+				.poll(poll)
+				.build()
+		};
+		assert!(monitor(poll.clone()).validate().is_ok());
+
+		let call = poll.reads[1].clone();
+		let with_call = |f: fn(&mut PollRead)| {
+			let mut call = call.clone();
+			f(&mut call);
+			PollConfig {
+				reads: vec![call],
+				..poll.clone()
+			}
+		};
+		let invalid_polls = vec![
+			PollConfig {
+				every_blocks: Some(0),
+				..poll.clone()
+			},
+			PollConfig {
+				interval_secs: Some(60),
+				..poll.clone()
+			},
+			PollConfig {
+				every_blocks: None,
+				..poll.clone()
+			},
+			PollConfig {
+				reads: vec![],
+				..poll.clone()
+			},
+			PollConfig {
+				reads: vec![poll.reads[0].clone(), poll.reads[0].clone()],
+				..poll.clone()
+			},
+			PollConfig {
+				reads: vec![PollRead::Balance {
+					name: "block_number".to_string(),
+					address: "0x01".to_string(),
+				}],
+				..poll.clone()
+			},
+			PollConfig {
+				reads: vec![PollRead::Balance {
+					name: "balance".to_string(),
+					address: String::new(),
+				}],
+				..poll.clone()
+			},
+			with_call(|call| {
+				if let PollRead::Call { output, .. } = call {
+					*output = Some(5);
+				}
+			}),
+			with_call(|call| {
+				if let PollRead::Call { returns, .. } = call {
+					*returns = "uint256".to_string();
+				}
+			}),
+			with_call(|call| {
+				if let PollRead::Call { args, .. } = call {
+					args.push("1".to_string());
+				}
+			}),
+			PollConfig {
+				expression: " ".to_string(),
+				..poll.clone()
+			},
+		];
+		for invalid_poll in invalid_polls {
+			assert!(matches!(
+				monitor(invalid_poll).validate(),
+				Err(ConfigError::ValidationError(_))
+			));
+		}
+
+		let with_events = MonitorBuilder::new()
+			.name("TestMonitor")
+			.networks(vec!["ethereum_mainnet".to_string()])
+			.event("Transfer(address,address,uint256)", None)
+			.poll(poll)
+			.build();
+		assert!(matches!(
+			with_events.validate(),
+			Err(ConfigError::ValidationError(_))
+		));
+	}
+
+	#[test]
+	fn test_validate_monitor_with_filter_plugins() {
+		let valid_monitor = MonitorBuilder::new()
+			.name("TestMonitor")
+			.networks(vec!["ethereum_mainnet".to_string()])
+			.filter_plugin("allowlist", serde_json::Map::new())
+			.build();
+		assert!(valid_monitor.validate().is_ok());
+
+		let invalid_monitor = MonitorBuilder::new()
+			.name("TestMonitor")
+			.networks(vec!["ethereum_mainnet".to_string()])
+			.filter_plugin("", serde_json::Map::new())
+			.build();
+		assert!(invalid_monitor
+			.validate()
+			.unwrap_err()
+			.to_string()
+			.contains("Filter plugin name cannot be empty"));
+	}
+
 	#[test]
 	fn test_validate_monitor_with_trigger_conditions() {
 		// Create a temporary directory and script file
@@ -592,14 +1294,24 @@ mod tests {
 				functions: vec![],
 				events: vec![],
 				transactions: vec![],
+				..Default::default()
 			},
 			trigger_conditions: vec![TriggerConditions {
 				script_path: script_path.to_str().unwrap().to_string(),
 				timeout_ms: 1000,
 				arguments: None,
 				language: ScriptLanguage::Bash,
+				mode: ScriptMode::Process,
+				sandbox: None,
+				interpreter: None,
 			}],
+			filter_plugins: vec![],
 			triggers: vec![],
+			window: None,
+			throttle: None,
+			severity: None,
+			state: None,
+			poll: None,
 		};
 
 		monitor.validate_protocol();
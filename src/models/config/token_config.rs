@@ -0,0 +1,502 @@
+//! Token configuration loading and validation.
+//!
+//! This module implements the ConfigLoader trait for Token configurations,
+//! allowing token metadata to be loaded from JSON files.
+
+use async_trait::async_trait;
+use std::{collections::HashMap, fs, path::Path};
+
+use crate::{
+	models::{config::error::ConfigError, ConfigLoader, PriceSourceConfig, Token, TokenPrice},
+	utils::normalize_string,
+};
+
+/// Largest number of decimals whose scaling factor fits in 256 bits
+const MAX_TOKEN_DECIMALS: u32 = 77;
+
+#[async_trait]
+impl ConfigLoader for Token {
+	/// Resolve all secrets in the token configuration
+	async fn resolve_secrets(&self) -> Result<Self, ConfigError> {
+		Ok(self.clone())
+	}
+
+	/// Load all token configurations from a directory
+	///
+	/// Reads and parses all JSON files in the specified directory (or default
+	/// config directory) as token configurations. Tokens are optional, so a
+	/// missing directory yields an empty collection.
+	async fn load_all<T>(path: Option<&Path>) -> Result<T, ConfigError>
+	where
+		T: FromIterator<(String, Self)>,
+	{
+		let token_dir = path.unwrap_or(Path::new("config/tokens"));
+		let mut pairs = Vec::new();
+
+		if !token_dir.exists() {
+			return Ok(T::from_iter(pairs));
+		}
+
+		for entry in fs::read_dir(token_dir).map_err(|e| {
+			ConfigError::file_error(
+				format!("failed to read tokens directory: {}", e),
+				Some(Box::new(e)),
+				Some(HashMap::from([(
+					"path".to_string(),
+					token_dir.display().to_string(),
+				)])),
+			)
+		})? {
+			let entry = entry.map_err(|e| {
+				ConfigError::file_error(
+					format!("failed to read directory entry: {}", e),
+					Some(Box::new(e)),
+					Some(HashMap::from([(
+						"path".to_string(),
+						token_dir.display().to_string(),
+					)])),
+				)
+			})?;
+			let path = entry.path();
+
+			if !Self::is_json_file(&path) {
+				continue;
+			}
+
+			let name = path
+				.file_stem()
+				.and_then(|s| s.to_str())
+				.unwrap_or("unknown")
+				.to_string();
+
+			let token = Self::load_from_path(&path).await?;
+
+			let existing_tokens: Vec<&Token> = pairs.iter().map(|(_, token)| token).collect();
+			// Check token address and price feed uniqueness before pushing
+			Self::validate_uniqueness(&existing_tokens, &token, &path.display().to_string())?;
+
+			pairs.push((name, token));
+		}
+
+		Ok(T::from_iter(pairs))
+	}
+
+	/// Load a token configuration from a specific file
+	///
+	/// Reads and parses a single JSON file as a token configuration.
+	async fn load_from_path(path: &Path) -> Result<Self, ConfigError> {
+		let file = std::fs::File::open(path).map_err(|e| {
+			ConfigError::file_error(
+				format!("failed to open token config file: {}", e),
+				Some(Box::new(e)),
+				Some(HashMap::from([(
+					"path".to_string(),
+					path.display().to_string(),
+				)])),
+			)
+		})?;
+		let config: Token = serde_json::from_reader(file).map_err(|e| {
+			ConfigError::parse_error(
+				format!("failed to parse token config: {}", e),
+				Some(Box::new(e)),
+				Some(HashMap::from([(
+					"path".to_string(),
+					path.display().to_string(),
+				)])),
+			)
+		})?;
+
+		// Validate the config after loading
+		config.validate()?;
+
+		Ok(config)
+	}
+
+	/// Validate the token configuration
+	///
+	/// Ensures that:
+	/// - The symbol can be referenced from expressions (letters, digits and underscores,
+	///   not starting with a digit)
+	/// - The decimals fit in a 256-bit scaling factor
+	/// - No address entry is empty
+	/// - The price feed, if any, is complete and does not cache prices longer than their
+	///   maximum age
+	fn validate(&self) -> Result<(), ConfigError> {
+		let mut symbol_chars = self.symbol.chars();
+		let is_valid_symbol = symbol_chars
+			.next()
+			.is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+			&& symbol_chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+		if !is_valid_symbol {
+			return Err(ConfigError::validation_error(
+				format!(
+					"Invalid token symbol '{}': must contain only letters, numbers, and \
+					 underscores and must not start with a number",
+					self.symbol
+				),
+				None,
+				None,
+			));
+		}
+
+		if self.decimals > MAX_TOKEN_DECIMALS {
+			return Err(ConfigError::validation_error(
+				format!(
+					"Token '{}' has {} decimals, the maximum is {}",
+					self.symbol, self.decimals, MAX_TOKEN_DECIMALS
+				),
+				None,
+				None,
+			));
+		}
+
+		if self
+			.addresses
+			.iter()
+			.any(|address| address.trim().is_empty())
+		{
+			return Err(ConfigError::validation_error(
+				format!("Token '{}' contains an empty address", self.symbol),
+				None,
+				None,
+			));
+		}
+
+		if let Some(price) = &self.price {
+			validate_price(&self.symbol, price)?;
+		}
+
+		Ok(())
+	}
+
+	/// Tokens have no protocol to validate
+	fn validate_protocol(&self) {}
+
+	/// Tokens may share a symbol, e.g. deployments of USDC with different decimals on several
+	/// networks, but an address belongs to a single token and a symbol has at most one price feed
+	fn validate_uniqueness(
+		instances: &[&Self],
+		current_instance: &Self,
+		file_path: &str,
+	) -> Result<(), ConfigError> {
+		let same_symbol = |existing_token: &&&Self| {
+			normalize_string(&existing_token.symbol) == normalize_string(&current_instance.symbol)
+		};
+		let duplicate_address = current_instance.addresses.iter().find(|address| {
+			instances.iter().any(|existing_token| {
+				existing_token
+					.addresses
+					.iter()
+					.any(|existing| normalize_string(existing) == normalize_string(address))
+			})
+		});
+
+		let message = if let Some(address) = duplicate_address {
+			format!("Duplicate token address found: '{}'", address)
+		} else if current_instance.price.is_some()
+			&& instances
+				.iter()
+				.filter(same_symbol)
+				.any(|existing_token| existing_token.price.is_some())
+		{
+			format!(
+				"Duplicate price feed found for token symbol: '{}'",
+				current_instance.symbol
+			)
+		} else {
+			return Ok(());
+		};
+
+		Err(ConfigError::validation_error(
+			message,
+			None,
+			Some(HashMap::from([
+				(
+					"token_symbol".to_string(),
+					current_instance.symbol.to_string(),
+				),
+				("path".to_string(), file_path.to_string()),
+			])),
+		))
+	}
+}
+
+/// Validates the USD price feed of a token
+fn validate_price(symbol: &str, price: &TokenPrice) -> Result<(), ConfigError> {
+	let invalid = |reason: &str| {
+		Err(ConfigError::validation_error(
+			format!("Invalid price feed of token '{}': {}", symbol, reason),
+			None,
+			None,
+		))
+	};
+
+	match &price.source {
+		PriceSourceConfig::Chainlink { network, address } => {
+			if network.trim().is_empty() || address.trim().is_empty() {
+				return invalid("chainlink sources require a network and an address");
+			}
+		}
+		PriceSourceConfig::File { path } => {
+			if path.trim().is_empty() {
+				return invalid("file sources require a path");
+			}
+		}
+		PriceSourceConfig::Http { url, path, .. } => {
+			let is_http_url =
+				url::Url::parse(url).is_ok_and(|url| matches!(url.scheme(), "http" | "https"));
+			if !is_http_url {
+				return invalid(&format!("'{}' is not an HTTP URL", url));
+			}
+			if path.split('.').any(|segment| segment.is_empty()) {
+				return invalid(&format!("'{}' is not a valid response path", path));
+			}
+		}
+	}
+
+	if price.max_age_secs == Some(0) {
+		return invalid("max_age_secs must be greater than 0");
+	}
+	if let (Some(ttl_secs), Some(max_age_secs)) = (price.ttl_secs, price.max_age_secs) {
+		if ttl_secs > max_age_secs {
+			return invalid("ttl_secs must not exceed max_age_secs");
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	fn create_token(symbol: &str, decimals: u32, addresses: &[&str]) -> Token {
+		Token {
+			symbol: symbol.to_string(),
+			decimals,
+			addresses: addresses.iter().map(|a| a.to_string()).collect(),
+			price: None,
+		}
+	}
+
+	#[test]
+	fn test_validate_valid_token() {
+		let token = create_token("USDC", 6, &["0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"]);
+		assert!(token.validate().is_ok());
+	}
+
+	#[test]
+	fn test_validate_invalid_symbol() {
+		for symbol in ["", "1INCH", "USD-C", "US DC"] {
+			let token = create_token(symbol, 6, &[]);
+			assert!(
+				matches!(token.validate(), Err(ConfigError::ValidationError(_))),
+				"symbol '{}' should be rejected",
+				symbol
+			);
+		}
+	}
+
+	#[test]
+	fn test_validate_too_many_decimals() {
+		let token = create_token("HUGE", 78, &[]);
+		assert!(matches!(
+			token.validate(),
+			Err(ConfigError::ValidationError(_))
+		));
+	}
+
+	#[test]
+	fn test_validate_empty_address() {
+		let token = create_token("USDC", 6, &["0x01", " "]);
+		assert!(matches!(
+			token.validate(),
+			Err(ConfigError::ValidationError(_))
+		));
+	}
+
+	#[test]
+	fn test_validate_price() {
+		let mut token = create_token("ETH", 18, &[]);
+		let valid_sources = [
+			PriceSourceConfig::Chainlink {
+				network: "ethereum_mainnet".to_string(),
+				address: "0x5f4eC3Df9cbd43714FE2740f5E3616155c5b8419".to_string(),
+			},
+			PriceSourceConfig::File {
+				path: "config/prices.json".to_string(),
+			},
+			PriceSourceConfig::Http {
+				url: "https://api.example.com/price?ids=ethereum".to_string(),
+				path: "ethereum.usd".to_string(),
+				headers: HashMap::new(),
+			},
+		];
+		for source in valid_sources {
+			token.price = Some(TokenPrice {
+				source,
+				ttl_secs: Some(30),
+				max_age_secs: Some(300),
+			});
+			assert!(token.validate().is_ok());
+		}
+
+		let invalid_prices = [
+			(
+				PriceSourceConfig::Chainlink {
+					network: "ethereum_mainnet".to_string(),
+					address: "".to_string(),
+				},
+				None,
+				None,
+			),
+			(
+				PriceSourceConfig::Http {
+					url: "ftp://example.com/price".to_string(),
+					path: "usd".to_string(),
+					headers: HashMap::new(),
+				},
+				None,
+				None,
+			),
+			(
+				PriceSourceConfig::Http {
+					url: "https://example.com/price".to_string(),
+					path: "ethereum..usd".to_string(),
+					headers: HashMap::new(),
+				},
+				None,
+				None,
+			),
+			(
+				PriceSourceConfig::File {
+					path: "prices.json".to_string(),
+				},
+				None,
+				Some(0),
+			),
+			(
+				PriceSourceConfig::File {
+					path: "prices.json".to_string(),
+				},
+				Some(600),
+				Some(60),
+			),
+		];
+		for (source, ttl_secs, max_age_secs) in invalid_prices {
+			token.price = Some(TokenPrice {
+				source,
+				ttl_secs,
+				max_age_secs,
+			});
+			assert!(matches!(
+				token.validate(),
+				Err(ConfigError::ValidationError(_))
+			));
+		}
+	}
+
+	#[tokio::test]
+	async fn test_load_all_directory_not_found() {
+		let result: Result<HashMap<String, Token>, ConfigError> =
+			Token::load_all(Some(Path::new("non_existent_directory"))).await;
+		assert!(result.unwrap().is_empty());
+	}
+
+	#[tokio::test]
+	async fn test_load_all_tokens() {
+		let temp_dir = TempDir::new().unwrap();
+		fs::write(
+			temp_dir.path().join("usdc.json"),
+			r#"{"symbol": "USDC", "decimals": 6, "addresses": ["0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"]}"#,
+		)
+		.unwrap();
+		fs::write(
+			temp_dir.path().join("weth.json"),
+			r#"{"symbol": "WETH", "decimals": 18, "price": {"source": {"type": "file", "path": "prices.json"}}}"#,
+		)
+		.unwrap();
+
+		let result: HashMap<String, Token> = Token::load_all(Some(temp_dir.path())).await.unwrap();
+
+		assert_eq!(result.len(), 2);
+		assert_eq!(result["usdc"].decimals, 6);
+		assert!(result["weth"].addresses.is_empty());
+		assert_eq!(
+			result["weth"].price.as_ref().unwrap().source,
+			PriceSourceConfig::File {
+				path: "prices.json".to_string()
+			}
+		);
+		assert!(result["usdc"].price.is_none());
+	}
+
+	#[tokio::test]
+	async fn test_load_all_token_symbol_per_network() {
+		let temp_dir = TempDir::new().unwrap();
+		fs::write(
+			temp_dir.path().join("usdc_ethereum.json"),
+			r#"{"symbol": "USDC", "decimals": 6, "addresses": ["0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"], "price": {"source": {"type": "file", "path": "prices.json"}}}"#,
+		)
+		.unwrap();
+		fs::write(
+			temp_dir.path().join("usdc_bsc.json"),
+			r#"{"symbol": "usdc", "decimals": 18, "addresses": ["0x8AC76a51cc950d9822D68b83fE1Ad97B32Cd580d"]}"#,
+		)
+		.unwrap();
+
+		let result: HashMap<String, Token> = Token::load_all(Some(temp_dir.path())).await.unwrap();
+
+		assert_eq!(result.len(), 2);
+		assert_eq!(result["usdc_bsc"].decimals, 18);
+	}
+
+	#[tokio::test]
+	async fn test_load_all_duplicate_token_address() {
+		let temp_dir = TempDir::new().unwrap();
+		fs::write(
+			temp_dir.path().join("token1.json"),
+			r#"{"symbol": "USDC", "decimals": 6, "addresses": ["0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"]}"#,
+		)
+		.unwrap();
+		fs::write(
+			temp_dir.path().join("token2.json"),
+			r#"{"symbol": "USDC_E", "decimals": 6, "addresses": ["0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"]}"#,
+		)
+		.unwrap();
+
+		let result: Result<HashMap<String, Token>, ConfigError> =
+			Token::load_all(Some(temp_dir.path())).await;
+
+		assert!(matches!(result, Err(ConfigError::ValidationError(_))));
+		if let Err(ConfigError::ValidationError(err)) = result {
+			assert!(err.message.contains("Duplicate token address found"));
+		}
+	}
+
+	#[tokio::test]
+	async fn test_load_all_duplicate_token_price_feed() {
+		let temp_dir = TempDir::new().unwrap();
+		let price = r#""price": {"source": {"type": "file", "path": "prices.json"}}"#;
+		fs::write(
+			temp_dir.path().join("token1.json"),
+			format!(r#"{{"symbol": "USDC", "decimals": 6, {}}}"#, price),
+		)
+		.unwrap();
+		fs::write(
+			temp_dir.path().join("token2.json"),
+			format!(r#"{{"symbol": "usdc", "decimals": 18, {}}}"#, price),
+		)
+		.unwrap();
+
+		let result: Result<HashMap<String, Token>, ConfigError> =
+			Token::load_all(Some(temp_dir.path())).await;
+
+		assert!(matches!(result, Err(ConfigError::ValidationError(_))));
+		if let Err(ConfigError::ValidationError(err)) = result {
+			assert!(err.message.contains("Duplicate price feed found"));
+		}
+	}
+}
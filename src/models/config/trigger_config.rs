@@ -4,21 +4,207 @@
 //! allowing triggers to be loaded from JSON files.
 
 use async_trait::async_trait;
+use chrono::Utc;
 use email_address::EmailAddress;
 use serde::Deserialize;
 use std::{collections::HashMap, fs, path::Path};
 
 use crate::{
 	models::{
-		config::error::ConfigError, ConfigLoader, SecretValue, Trigger, TriggerType,
-		TriggerTypeConfig,
+		config::error::ConfigError, ConfigLoader, NotificationMessage, ScriptLanguage, SecretValue,
+		Trigger, TriggerType, TriggerTypeConfig, WebhookPayloadMode,
 	},
-	services::trigger::validate_script_config,
-	utils::normalize_string,
+	services::{
+		notification::{
+			validate_digest_template, validate_json_template, validate_template, DatabaseBackend,
+			DATABASE_KEY_COLUMNS, ROW_LOG_INDEX,
+		},
+		trigger::{validate_script_config, validate_script_executable, validate_script_sandbox},
+	},
+	utils::{get_next_cron_occurrence, normalize_string},
 };
 
 const TELEGRAM_MAX_BODY_LENGTH: usize = 4096;
 const DISCORD_MAX_BODY_LENGTH: usize = 2000;
+const DISCORD_MAX_EMBEDS: usize = 10;
+const SLACK_MAX_BLOCKS: usize = 50;
+const PAGERDUTY_MAX_SUMMARY_LENGTH: usize = 1024;
+const OPSGENIE_MAX_MESSAGE_LENGTH: usize = 130;
+const NATS_URL_SCHEMES: &[&str] = &["nats://", "tls://", "ws://", "wss://"];
+const REDIS_URL_SCHEMES: &[&str] = &["redis://", "rediss://"];
+const AMQP_URL_SCHEMES: &[&str] = &["amqp://", "amqps://"];
+const SECURE_QUEUE_URL_SCHEMES: &[&str] = &["tls://", "wss://", "rediss://", "amqps://"];
+const DATABASE_ROW_VARIABLES: &[&str] = &["event.signature", "event.args.*", ROW_LOG_INDEX];
+
+/// Validates the fields shared by PagerDuty and Opsgenie triggers
+///
+/// # Arguments
+/// * `message` - Notification message of the trigger
+/// * `dedup_key` - Deduplication key or alias template
+/// * `auto_resolve_after_secs` - Delay before the incident is resolved
+/// * `api_url` - API endpoint override
+fn validate_incident_config(
+	message: &NotificationMessage,
+	dedup_key: Option<&str>,
+	auto_resolve_after_secs: Option<u64>,
+	api_url: Option<&str>,
+) -> Result<(), ConfigError> {
+	if message.title.trim().is_empty() {
+		return Err(ConfigError::validation_error(
+			"Title cannot be empty",
+			None,
+			None,
+		));
+	}
+	if message.body.trim().is_empty() {
+		return Err(ConfigError::validation_error(
+			"Body cannot be empty",
+			None,
+			None,
+		));
+	}
+	if let Some(dedup_key) = dedup_key {
+		if dedup_key.trim().is_empty() {
+			return Err(ConfigError::validation_error(
+				"Deduplication key cannot be empty",
+				None,
+				None,
+			));
+		}
+		validate_template(dedup_key, &[]).map_err(|e| {
+			ConfigError::validation_error(
+				format!("Invalid deduplication key template: {}", e),
+				None,
+				None,
+			)
+		})?;
+	}
+	if auto_resolve_after_secs == Some(0) {
+		return Err(ConfigError::validation_error(
+			"Auto-resolve delay must be greater than 0",
+			None,
+			None,
+		));
+	}
+	if let Some(api_url) = api_url {
+		if !api_url.starts_with("http://") && !api_url.starts_with("https://") {
+			return Err(ConfigError::validation_error(
+				"Invalid incident API URL format",
+				None,
+				None,
+			));
+		}
+	}
+	Ok(())
+}
+
+/// Validates the block explorer link template of chat triggers
+///
+/// # Arguments
+/// * `explorer_url` - Block explorer link template, if configured
+fn validate_explorer_url(explorer_url: Option<&str>) -> Result<(), ConfigError> {
+	if let Some(explorer_url) = explorer_url {
+		validate_template(explorer_url, &[]).map_err(|e| {
+			ConfigError::validation_error(
+				format!("Invalid explorer URL template: {}", e),
+				None,
+				None,
+			)
+		})?;
+	}
+	Ok(())
+}
+
+/// Validates the Slack blocks or Discord embeds replacing the default message layout
+///
+/// # Arguments
+/// * `template` - JSON array whose strings are message templates
+/// * `field` - Name of the field, used in error messages
+/// * `max_items` - Maximum number of items accepted by the platform
+fn validate_rich_template(
+	template: &serde_json::Value,
+	field: &str,
+	max_items: usize,
+) -> Result<(), ConfigError> {
+	let items = match template.as_array() {
+		Some(items) if !items.is_empty() && items.iter().all(|item| item.is_object()) => items,
+		_ => {
+			return Err(ConfigError::validation_error(
+				format!("{} must be a non-empty array of objects", field),
+				None,
+				None,
+			));
+		}
+	};
+	if items.len() > max_items {
+		return Err(ConfigError::validation_error(
+			format!("{} should not exceed {} items", field, max_items),
+			None,
+			None,
+		));
+	}
+	validate_json_template(template).map_err(|e| {
+		ConfigError::validation_error(format!("Invalid {} template: {}", field, e), None, None)
+	})
+}
+
+/// Checks whether a name is a plain SQL identifier, optionally qualified by a schema
+///
+/// # Arguments
+/// * `name` - Table or column name
+/// * `allow_schema` - Whether the name may be prefixed with a schema
+fn is_sql_identifier(name: &str, allow_schema: bool) -> bool {
+	let parts = name.split('.').collect::<Vec<_>>();
+	let max_parts = if allow_schema { 2 } else { 1 };
+	parts.len() <= max_parts
+		&& parts.iter().all(|part| {
+			part.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+				&& part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+		})
+}
+
+/// Validates the URL of a queue broker
+///
+/// # Arguments
+/// * `broker` - Name of the broker, used in error messages
+/// * `url` - Broker URL
+/// * `schemes` - Accepted URL schemes
+fn validate_queue_url(broker: &str, url: &str, schemes: &[&str]) -> Result<(), ConfigError> {
+	if !schemes.iter().any(|scheme| url.starts_with(scheme)) {
+		return Err(ConfigError::validation_error(
+			format!("Invalid {} URL format", broker),
+			None,
+			None,
+		));
+	}
+	Ok(())
+}
+
+/// Validates the topic, subject, stream or routing key matches are published to
+///
+/// # Arguments
+/// * `field` - Name of the field, used in error messages
+/// * `destination` - Value of the field
+fn validate_queue_destination(field: &str, destination: &str) -> Result<(), ConfigError> {
+	if destination.trim().is_empty() {
+		return Err(ConfigError::validation_error(
+			format!("{} cannot be empty", field),
+			None,
+			None,
+		));
+	}
+	if destination
+		.chars()
+		.any(|c| c.is_whitespace() || c.is_control())
+	{
+		return Err(ConfigError::validation_error(
+			format!("{} cannot contain whitespace", field),
+			None,
+			None,
+		));
+	}
+	Ok(())
+}
 
 /// File structure for trigger configuration files
 #[derive(Debug, Deserialize)]
@@ -108,6 +294,91 @@ impl ConfigLoader for Trigger {
 				})?;
 				*discord_url = SecretValue::Plain(resolved_url);
 			}
+			TriggerTypeConfig::Teams { teams_url, .. } => {
+				let resolved_url = teams_url.resolve().await.map_err(|e| {
+					ConfigError::parse_error(
+						format!("failed to resolve Teams URL: {}", e),
+						Some(Box::new(e)),
+						None,
+					)
+				})?;
+				*teams_url = SecretValue::Plain(resolved_url);
+			}
+			TriggerTypeConfig::PagerDuty { routing_key, .. } => {
+				let resolved_key = routing_key.resolve().await.map_err(|e| {
+					ConfigError::parse_error(
+						format!("failed to resolve PagerDuty routing key: {}", e),
+						Some(Box::new(e)),
+						None,
+					)
+				})?;
+				*routing_key = SecretValue::Plain(resolved_key);
+			}
+			TriggerTypeConfig::Opsgenie { api_key, .. } => {
+				let resolved_key = api_key.resolve().await.map_err(|e| {
+					ConfigError::parse_error(
+						format!("failed to resolve Opsgenie API key: {}", e),
+						Some(Box::new(e)),
+						None,
+					)
+				})?;
+				*api_key = SecretValue::Plain(resolved_key);
+			}
+			TriggerTypeConfig::Kafka {
+				properties: Some(properties),
+				..
+			} => {
+				for (name, value) in properties.iter_mut() {
+					let resolved_value = value.resolve().await.map_err(|e| {
+						ConfigError::parse_error(
+							format!("failed to resolve Kafka property {}: {}", name, e),
+							Some(Box::new(e)),
+							None,
+						)
+					})?;
+					*value = SecretValue::Plain(resolved_value);
+				}
+			}
+			TriggerTypeConfig::Nats { nats_url, .. } => {
+				let resolved_url = nats_url.resolve().await.map_err(|e| {
+					ConfigError::parse_error(
+						format!("failed to resolve NATS URL: {}", e),
+						Some(Box::new(e)),
+						None,
+					)
+				})?;
+				*nats_url = SecretValue::Plain(resolved_url);
+			}
+			TriggerTypeConfig::Redis { redis_url, .. } => {
+				let resolved_url = redis_url.resolve().await.map_err(|e| {
+					ConfigError::parse_error(
+						format!("failed to resolve Redis URL: {}", e),
+						Some(Box::new(e)),
+						None,
+					)
+				})?;
+				*redis_url = SecretValue::Plain(resolved_url);
+			}
+			TriggerTypeConfig::Amqp { amqp_url, .. } => {
+				let resolved_url = amqp_url.resolve().await.map_err(|e| {
+					ConfigError::parse_error(
+						format!("failed to resolve AMQP URL: {}", e),
+						Some(Box::new(e)),
+						None,
+					)
+				})?;
+				*amqp_url = SecretValue::Plain(resolved_url);
+			}
+			TriggerTypeConfig::Database { database_url, .. } => {
+				let resolved_url = database_url.resolve().await.map_err(|e| {
+					ConfigError::parse_error(
+						format!("failed to resolve database URL: {}", e),
+						Some(Box::new(e)),
+						None,
+					)
+				})?;
+				*database_url = SecretValue::Plain(resolved_url);
+			}
 			_ => {}
 		}
 
@@ -252,11 +523,25 @@ impl ConfigLoader for Trigger {
 			));
 		}
 
+		// Backends left out of the build cannot be used
+		if let Some(feature) = self.config.disabled_feature() {
+			return Err(ConfigError::validation_error(
+				format!(
+					"Trigger '{}' requires the monitor to be built with the `{}` feature",
+					self.name, feature
+				),
+				None,
+				None,
+			));
+		}
+
 		match &self.trigger_type {
 			TriggerType::Slack => {
 				if let TriggerTypeConfig::Slack {
 					slack_url,
 					message,
+					explorer_url,
+					blocks,
 					retry_policy: _,
 				} = &self.config
 				{
@@ -284,6 +569,11 @@ impl ConfigLoader for Trigger {
 							None,
 						));
 					}
+					validate_explorer_url(explorer_url.as_deref())?;
+					// Validate blocks template
+					if let Some(blocks) = blocks {
+						validate_rich_template(blocks, "Blocks", SLACK_MAX_BLOCKS)?;
+					}
 				}
 			}
 			TriggerType::Email => {
@@ -432,6 +722,7 @@ impl ConfigLoader for Trigger {
 					url,
 					method,
 					message,
+					payload,
 					..
 				} = &self.config
 				{
@@ -471,6 +762,30 @@ impl ConfigLoader for Trigger {
 							None,
 						));
 					}
+					// Validate payload
+					match payload {
+						Some(WebhookPayloadMode::Template { template }) => {
+							validate_json_template(template).map_err(|e| {
+								ConfigError::validation_error(
+									format!("Invalid payload template: {}", e),
+									None,
+									None,
+								)
+							})?;
+						}
+						Some(WebhookPayloadMode::Match)
+						| Some(WebhookPayloadMode::Message)
+						| None => {}
+					}
+					if self.digest.is_some()
+						&& !matches!(payload, None | Some(WebhookPayloadMode::Message))
+					{
+						return Err(ConfigError::validation_error(
+							"Digest is only supported with the message payload mode",
+							None,
+							None,
+						));
+					}
 				}
 			}
 			TriggerType::Telegram => {
@@ -551,6 +866,8 @@ impl ConfigLoader for Trigger {
 				if let TriggerTypeConfig::Discord {
 					discord_url,
 					message,
+					explorer_url,
+					embeds,
 					..
 				} = &self.config
 				{
@@ -588,6 +905,230 @@ impl ConfigLoader for Trigger {
 							None,
 						));
 					}
+					validate_explorer_url(explorer_url.as_deref())?;
+					// Validate embeds template
+					if let Some(embeds) = embeds {
+						validate_rich_template(embeds, "Embeds", DISCORD_MAX_EMBEDS)?;
+					}
+				}
+			}
+			TriggerType::Teams => {
+				if let TriggerTypeConfig::Teams {
+					teams_url,
+					message,
+					explorer_url,
+					..
+				} = &self.config
+				{
+					// Validate webhook URL
+					if !teams_url.starts_with("http://") && !teams_url.starts_with("https://") {
+						return Err(ConfigError::validation_error(
+							"Invalid Teams webhook URL format",
+							None,
+							None,
+						));
+					}
+					// Validate message
+					if message.title.trim().is_empty() {
+						return Err(ConfigError::validation_error(
+							"Title cannot be empty",
+							None,
+							None,
+						));
+					}
+					if message.body.trim().is_empty() {
+						return Err(ConfigError::validation_error(
+							"Body cannot be empty",
+							None,
+							None,
+						));
+					}
+					validate_explorer_url(explorer_url.as_deref())?;
+				}
+			}
+			TriggerType::PagerDuty => {
+				if let TriggerTypeConfig::PagerDuty {
+					routing_key,
+					message,
+					dedup_key,
+					auto_resolve_after_secs,
+					events_url,
+					..
+				} = &self.config
+				{
+					if routing_key.trim().is_empty() {
+						return Err(ConfigError::validation_error(
+							"Routing key cannot be empty",
+							None,
+							None,
+						));
+					}
+					if message.title.chars().count() > PAGERDUTY_MAX_SUMMARY_LENGTH {
+						return Err(ConfigError::validation_error(
+							format!(
+								"Title should not exceed {} characters",
+								PAGERDUTY_MAX_SUMMARY_LENGTH
+							),
+							None,
+							None,
+						));
+					}
+					validate_incident_config(
+						message,
+						dedup_key.as_deref(),
+						*auto_resolve_after_secs,
+						events_url.as_deref(),
+					)?;
+				}
+			}
+			TriggerType::Opsgenie => {
+				if let TriggerTypeConfig::Opsgenie {
+					api_key,
+					message,
+					alias,
+					auto_resolve_after_secs,
+					api_url,
+					..
+				} = &self.config
+				{
+					if api_key.trim().is_empty() {
+						return Err(ConfigError::validation_error(
+							"API key cannot be empty",
+							None,
+							None,
+						));
+					}
+					if message.title.chars().count() > OPSGENIE_MAX_MESSAGE_LENGTH {
+						return Err(ConfigError::validation_error(
+							format!(
+								"Title should not exceed {} characters",
+								OPSGENIE_MAX_MESSAGE_LENGTH
+							),
+							None,
+							None,
+						));
+					}
+					validate_incident_config(
+						message,
+						alias.as_deref(),
+						*auto_resolve_after_secs,
+						api_url.as_deref(),
+					)?;
+				}
+			}
+			TriggerType::Kafka => {
+				if let TriggerTypeConfig::Kafka { brokers, topic, .. } = &self.config {
+					if brokers.trim().is_empty() {
+						return Err(ConfigError::validation_error(
+							"Brokers cannot be empty",
+							None,
+							None,
+						));
+					}
+					validate_queue_destination("Topic", topic)?;
+				}
+			}
+			TriggerType::Nats => {
+				if let TriggerTypeConfig::Nats {
+					nats_url, subject, ..
+				} = &self.config
+				{
+					validate_queue_url("NATS", nats_url.as_str(), NATS_URL_SCHEMES)?;
+					validate_queue_destination("Subject", subject)?;
+					if subject.split('.').any(|token| token == "*" || token == ">") {
+						return Err(ConfigError::validation_error(
+							"Subject cannot contain wildcards",
+							None,
+							None,
+						));
+					}
+				}
+			}
+			TriggerType::Redis => {
+				if let TriggerTypeConfig::Redis {
+					redis_url,
+					stream,
+					max_len,
+					..
+				} = &self.config
+				{
+					validate_queue_url("Redis", redis_url.as_str(), REDIS_URL_SCHEMES)?;
+					validate_queue_destination("Stream", stream)?;
+					if *max_len == Some(0) {
+						return Err(ConfigError::validation_error(
+							"Stream maximum length must be greater than 0",
+							None,
+							None,
+						));
+					}
+				}
+			}
+			TriggerType::Amqp => {
+				if let TriggerTypeConfig::Amqp {
+					amqp_url,
+					routing_key,
+					..
+				} = &self.config
+				{
+					validate_queue_url("AMQP", amqp_url.as_str(), AMQP_URL_SCHEMES)?;
+					if let Some(routing_key) = routing_key {
+						validate_queue_destination("Routing key", routing_key)?;
+					}
+				}
+			}
+			TriggerType::Database => {
+				if let TriggerTypeConfig::Database {
+					database_url,
+					table,
+					columns,
+					batch_size,
+					..
+				} = &self.config
+				{
+					if DatabaseBackend::from_url(database_url.as_str()).is_none() {
+						return Err(ConfigError::validation_error(
+							"Invalid database URL format",
+							None,
+							None,
+						));
+					}
+					if !is_sql_identifier(table, true) {
+						return Err(ConfigError::validation_error(
+							format!("Invalid table name: {}", table),
+							None,
+							None,
+						));
+					}
+					for (column, template) in columns {
+						if !is_sql_identifier(column, false) {
+							return Err(ConfigError::validation_error(
+								format!("Invalid column name: {}", column),
+								None,
+								None,
+							));
+						}
+						if DATABASE_KEY_COLUMNS.contains(&column.as_str()) {
+							return Err(ConfigError::validation_error(
+								format!("Column {} is written from the match key", column),
+								None,
+								None,
+							));
+						}
+						validate_template(template, DATABASE_ROW_VARIABLES).map_err(|e| {
+							ConfigError::validation_error(
+								format!("Invalid template of column {}: {}", column, e),
+								None,
+								None,
+							)
+						})?;
+					}
+					if *batch_size == Some(0) {
+						return Err(ConfigError::validation_error(
+							"Batch size must be greater than 0",
+							None,
+							None,
+						));
+					}
 				}
 			}
 			TriggerType::Script => {
@@ -595,12 +1136,124 @@ impl ConfigLoader for Trigger {
 					script_path,
 					language,
 					timeout_ms,
+					sandbox,
+					interpreter,
 					..
 				} = &self.config
 				{
 					validate_script_config(script_path, language, timeout_ms)?;
+					validate_script_executable(script_path, language, interpreter.as_deref())?;
+					// Modules have no access to the host, they can only be trigger conditions
+					if *language == ScriptLanguage::Wasm {
+						return Err(ConfigError::validation_error(
+							"WASM modules cannot send custom notifications",
+							None,
+							None,
+						));
+					}
+					if let Some(sandbox) = sandbox {
+						validate_script_sandbox(sandbox)?;
+					}
 				}
 			}
+			TriggerType::Plugin => {
+				// Plugins are registered at runtime, only their name can be checked here
+				if let TriggerTypeConfig::Plugin { plugin, .. } = &self.config {
+					if plugin.trim().is_empty() {
+						return Err(ConfigError::validation_error(
+							"Plugin name cannot be empty",
+							None,
+							None,
+						));
+					}
+				}
+			}
+		}
+
+		// Validate message template
+		if let Some(message) = self.config.message() {
+			validate_template(&message.body, &[]).map_err(|e| {
+				ConfigError::validation_error(
+					format!("Invalid message template: {}", e),
+					None,
+					None,
+				)
+			})?;
+		}
+
+		// Validate digest
+		if let Some(digest) = &self.digest {
+			if self.trigger_type == TriggerType::Script {
+				return Err(ConfigError::validation_error(
+					"Digest is not supported for script triggers",
+					None,
+					None,
+				));
+			}
+			if matches!(
+				self.trigger_type,
+				TriggerType::PagerDuty | TriggerType::Opsgenie
+			) {
+				return Err(ConfigError::validation_error(
+					"Digest is not supported for incident triggers",
+					None,
+					None,
+				));
+			}
+			if matches!(
+				self.trigger_type,
+				TriggerType::Kafka | TriggerType::Nats | TriggerType::Redis | TriggerType::Amqp
+			) {
+				return Err(ConfigError::validation_error(
+					"Digest is not supported for queue triggers",
+					None,
+					None,
+				));
+			}
+			if self.trigger_type == TriggerType::Database {
+				return Err(ConfigError::validation_error(
+					"Digest is not supported for database triggers",
+					None,
+					None,
+				));
+			}
+			if self.trigger_type == TriggerType::Plugin {
+				return Err(ConfigError::validation_error(
+					"Digest is not supported for plugin triggers",
+					None,
+					None,
+				));
+			}
+			if get_next_cron_occurrence(&digest.cron_schedule, Utc::now()).is_none() {
+				return Err(ConfigError::validation_error(
+					format!("Invalid digest cron schedule: {}", digest.cron_schedule),
+					None,
+					None,
+				));
+			}
+			if digest.message.title.trim().is_empty() {
+				return Err(ConfigError::validation_error(
+					"Digest title cannot be empty",
+					None,
+					None,
+				));
+			}
+			if digest.message.body.trim().is_empty() {
+				return Err(ConfigError::validation_error(
+					"Digest body cannot be empty",
+					None,
+					None,
+				));
+			}
+			for template in [&digest.message.title, &digest.message.body] {
+				validate_digest_template(template).map_err(|e| {
+					ConfigError::validation_error(
+						format!("Invalid digest template: {}", e),
+						None,
+						None,
+					)
+				})?;
+			}
 		}
 
 		// Log a warning if the trigger uses an insecure protocol
@@ -624,7 +1277,7 @@ impl ConfigLoader for Trigger {
 					tracing::warn!("Discord URL uses an insecure protocol: {}", discord_url);
 				}
 			}
-			TriggerTypeConfig::Telegram { .. } => {}
+			TriggerTypeConfig::Telegram { .. } | TriggerTypeConfig::Plugin { .. } => {}
 			TriggerTypeConfig::Script { script_path, .. } => {
 				// Check script file permissions on Unix systems
 				#[cfg(unix)]
@@ -650,6 +1303,57 @@ impl ConfigLoader for Trigger {
 					}
 				}
 			}
+			TriggerTypeConfig::Teams { teams_url, .. } => {
+				if !teams_url.starts_with("https://") {
+					tracing::warn!("Teams URL uses an insecure protocol: {}", teams_url);
+				}
+			}
+			TriggerTypeConfig::PagerDuty {
+				events_url: Some(url),
+				..
+			}
+			| TriggerTypeConfig::Opsgenie {
+				api_url: Some(url), ..
+			} => {
+				if !url.starts_with("https://") {
+					tracing::warn!("Incident API URL uses an insecure protocol: {}", url);
+				}
+			}
+			TriggerTypeConfig::PagerDuty { .. } | TriggerTypeConfig::Opsgenie { .. } => {}
+			TriggerTypeConfig::Kafka { properties, .. } => {
+				let protocol = properties
+					.as_ref()
+					.and_then(|properties| properties.get("security.protocol"))
+					.map(|protocol| protocol.as_ref().to_lowercase());
+				if !matches!(protocol.as_deref(), Some("ssl" | "sasl_ssl")) {
+					tracing::warn!("Kafka brokers are not using a secure protocol");
+				}
+			}
+			TriggerTypeConfig::Nats { nats_url: url, .. }
+			| TriggerTypeConfig::Redis { redis_url: url, .. }
+			| TriggerTypeConfig::Amqp { amqp_url: url, .. } => {
+				if !SECURE_QUEUE_URL_SCHEMES
+					.iter()
+					.any(|scheme| url.starts_with(scheme))
+				{
+					tracing::warn!("Queue URL uses an insecure protocol: {}", url);
+				}
+			}
+			TriggerTypeConfig::Database { database_url, .. } => {
+				let url = database_url.as_str().to_lowercase();
+				let requires_tls = match DatabaseBackend::from_url(&url) {
+					Some(DatabaseBackend::Postgres) => ["sslmode=require", "sslmode=verify"]
+						.iter()
+						.any(|mode| url.contains(mode)),
+					Some(DatabaseBackend::MySql) => ["ssl-mode=required", "ssl-mode=verify"]
+						.iter()
+						.any(|mode| url.contains(mode)),
+					Some(DatabaseBackend::Sqlite) | None => true,
+				};
+				if !requires_tls {
+					tracing::warn!("Database URL does not require an encrypted connection");
+				}
+			}
 			TriggerTypeConfig::Webhook { url, headers, .. } => {
 				if !url.starts_with("https://") {
 					tracing::warn!("Webhook URL uses an insecure protocol: {}", url);
@@ -700,10 +1404,11 @@ impl ConfigLoader for Trigger {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::models::NotificationMessage;
-	use crate::models::{core::Trigger, ScriptLanguage, SecretString};
+	use crate::models::IncidentSeverity;
+	use crate::models::{core::Trigger, SecretString};
 	use crate::utils::tests::builders::trigger::TriggerBuilder;
 	use crate::utils::HttpRetryConfig;
+	use serde_json::json;
 	use std::{fs::File, io::Write, os::unix::fs::PermissionsExt};
 	use tempfile::TempDir;
 	use tracing_test::traced_test;
@@ -1047,7 +1752,23 @@ mod tests {
 			.build();
 		assert!(invalid_path.validate().is_err());
 
+		// WASM modules are only trigger conditions
+		let wasm_path = temp_dir.join("test_script.wasm");
+		std::fs::write(&wasm_path, wat::parse_str("(module)").unwrap()).unwrap();
+		let wasm_trigger = TriggerBuilder::new()
+			.name("test_script")
+			.script(wasm_path.to_str().unwrap(), ScriptLanguage::Wasm)
+			.build();
+		assert!(wasm_trigger.validate().unwrap_err().to_string().contains(
+			if cfg!(feature = "wasm") {
+				"cannot send custom notifications"
+			} else {
+				"requires the monitor to be built with the `wasm` feature"
+			}
+		));
+
 		std::fs::remove_file(script_path).unwrap();
+		std::fs::remove_file(wasm_path).unwrap();
 	}
 
 	#[tokio::test]
@@ -1448,6 +2169,7 @@ mod tests {
 				},
 				retry_policy: HttpRetryConfig::default(),
 			},
+			digest: None,
 		};
 		assert!(max_body_length.validate().is_err());
 	}
@@ -1465,12 +2187,758 @@ mod tests {
 					title: "Test".to_string(),
 					body: "z".repeat(DISCORD_MAX_BODY_LENGTH + 1), // Exceeds max length
 				},
+				explorer_url: None,
+				embeds: None,
 				retry_policy: HttpRetryConfig::default(),
 			},
+			digest: None,
 		};
 		assert!(max_body_length.validate().is_err());
 	}
 
+	#[test]
+	fn test_message_template_validation() {
+		let valid_trigger = TriggerBuilder::new()
+			.name("test_template")
+			.slack("https://hooks.slack.com/services/xxx")
+			.message(
+				"Alert",
+				"{% for event in events %}${event.args.value | decimals(6)} to ${event.args.to | shorten}\n{% endfor %}${transaction.to | default(\"contract creation\")}",
+			)
+			.build();
+		assert!(valid_trigger.validate().is_ok());
+
+		// Fields enriched by trigger condition scripts
+		let enriched_trigger = TriggerBuilder::new()
+			.name("test_template")
+			.slack("https://hooks.slack.com/services/xxx")
+			.message(
+				"Alert",
+				"Risk ${enrichment.risk.score} at ${enrichment.price_usd | default(\"?\")}",
+			)
+			.build();
+		assert!(enriched_trigger.validate().is_ok());
+
+		let invalid_bodies = vec![
+			"${trasaction.hash}",
+			"${risk.score}",
+			"${enrichment}",
+			"${events.0.args.value | unknown_filter}",
+			"{% if events.0.args.value %}unclosed",
+		];
+		for body in invalid_bodies {
+			let invalid_trigger = TriggerBuilder::new()
+				.name("test_template")
+				.slack("https://hooks.slack.com/services/xxx")
+				.message("Alert", body)
+				.build();
+			let err = invalid_trigger.validate().unwrap_err();
+			assert!(err.to_string().contains("Invalid message template"));
+		}
+	}
+
+	#[test]
+	fn test_teams_trigger_validation() {
+		let valid_trigger = TriggerBuilder::new()
+			.name("test_teams")
+			.teams("https://example.webhook.office.com/webhookb2/abc")
+			.explorer_url("https://etherscan.io/tx/${transaction.hash}")
+			.build();
+		assert!(valid_trigger.validate().is_ok());
+
+		let invalid_url = TriggerBuilder::new()
+			.name("test_teams")
+			.teams("example.webhook.office.com")
+			.build();
+		let err = invalid_url.validate().unwrap_err();
+		assert!(err.to_string().contains("Invalid Teams webhook URL format"));
+
+		let invalid_explorer_url = TriggerBuilder::new()
+			.name("test_teams")
+			.teams("https://example.webhook.office.com/webhookb2/abc")
+			.explorer_url("https://etherscan.io/tx/${trasaction.hash}")
+			.build();
+		let err = invalid_explorer_url.validate().unwrap_err();
+		assert!(err.to_string().contains("Invalid explorer URL template"));
+
+		let empty_body = TriggerBuilder::new()
+			.name("test_teams")
+			.teams("https://example.webhook.office.com/webhookb2/abc")
+			.message("Alert", " ")
+			.build();
+		let err = empty_body.validate().unwrap_err();
+		assert!(err.to_string().contains("Body cannot be empty"));
+	}
+
+	#[test]
+	fn test_slack_and_discord_rich_layout_validation() {
+		let valid_slack = TriggerBuilder::new()
+			.name("test_slack")
+			.slack("https://hooks.slack.com/services/xxx")
+			.explorer_url("https://etherscan.io/tx/${transaction.hash}")
+			.slack_blocks(json!([
+				{ "type": "section", "text": { "type": "mrkdwn", "text": "*${monitor.name}*" } }
+			]))
+			.build();
+		assert!(valid_slack.validate().is_ok());
+
+		let invalid_explorer_url = TriggerBuilder::new()
+			.name("test_slack")
+			.slack("https://hooks.slack.com/services/xxx")
+			.explorer_url("https://etherscan.io/tx/${trasaction.hash}")
+			.build();
+		let err = invalid_explorer_url.validate().unwrap_err();
+		assert!(err.to_string().contains("Invalid explorer URL template"));
+
+		let invalid_blocks = TriggerBuilder::new()
+			.name("test_slack")
+			.slack("https://hooks.slack.com/services/xxx")
+			.slack_blocks(json!({ "type": "section" }))
+			.build();
+		let err = invalid_blocks.validate().unwrap_err();
+		assert!(err
+			.to_string()
+			.contains("Blocks must be a non-empty array of objects"));
+
+		let too_many_blocks = TriggerBuilder::new()
+			.name("test_slack")
+			.slack("https://hooks.slack.com/services/xxx")
+			.slack_blocks(json!(vec![
+				json!({ "type": "divider" });
+				SLACK_MAX_BLOCKS + 1
+			]))
+			.build();
+		let err = too_many_blocks.validate().unwrap_err();
+		assert!(err
+			.to_string()
+			.contains("Blocks should not exceed 50 items"));
+
+		let valid_discord = TriggerBuilder::new()
+			.name("test_discord")
+			.discord("https://discord.com/api/webhooks/123/abc")
+			.explorer_url("https://etherscan.io/tx/${transaction.hash}")
+			.discord_embeds(json!([{ "title": "${monitor.name}" }]))
+			.build();
+		assert!(valid_discord.validate().is_ok());
+
+		let invalid_embeds = TriggerBuilder::new()
+			.name("test_discord")
+			.discord("https://discord.com/api/webhooks/123/abc")
+			.discord_embeds(json!([{ "title": "{% if monitor.name %}" }]))
+			.build();
+		let err = invalid_embeds.validate().unwrap_err();
+		assert!(err.to_string().contains("Invalid Embeds template"));
+	}
+
+	#[test]
+	fn test_slack_and_discord_rich_layout_deserialization() {
+		let slack = serde_json::from_value::<TriggerTypeConfig>(json!({
+			"slack_url": { "type": "plain", "value": "https://hooks.slack.com/services/xxx" },
+			"message": { "title": "Alert", "body": "Large transfer" },
+			"explorer_url": "https://etherscan.io/tx/${transaction.hash}",
+			"blocks": [{ "type": "divider" }]
+		}))
+		.unwrap();
+		assert!(matches!(
+			slack,
+			TriggerTypeConfig::Slack {
+				explorer_url: Some(_),
+				blocks: Some(_),
+				..
+			}
+		));
+
+		let discord = serde_json::from_value::<TriggerTypeConfig>(json!({
+			"discord_url": { "type": "plain", "value": "https://discord.com/api/webhooks/123/abc" },
+			"message": { "title": "Alert", "body": "Large transfer" },
+			"embeds": [{ "title": "${monitor.name}" }]
+		}))
+		.unwrap();
+		assert!(matches!(
+			discord,
+			TriggerTypeConfig::Discord {
+				explorer_url: None,
+				embeds: Some(_),
+				..
+			}
+		));
+	}
+
+	#[tokio::test]
+	async fn test_resolve_secrets_teams() {
+		let trigger = TriggerBuilder::new()
+			.name("teams")
+			.teams("https://example.webhook.office.com/webhookb2/abc")
+			.build();
+		let resolved = trigger.resolve_secrets().await.unwrap();
+		assert!(matches!(
+			resolved.config,
+			TriggerTypeConfig::Teams {
+				teams_url: SecretValue::Plain(_),
+				..
+			}
+		));
+	}
+
+	#[test]
+	fn test_incident_trigger_validation() {
+		let pagerduty_trigger = TriggerBuilder::new()
+			.name("test_pagerduty")
+			.pagerduty("https://events.pagerduty.com/v2/enqueue")
+			.incident(
+				IncidentSeverity::Warning,
+				Some("${monitor.name}:${events.0.args.newOwner}"),
+				Some(3600),
+			)
+			.build();
+		assert!(pagerduty_trigger.validate().is_ok());
+
+		let opsgenie_trigger = TriggerBuilder::new()
+			.name("test_opsgenie")
+			.opsgenie("https://api.eu.opsgenie.com")
+			.incident(IncidentSeverity::Critical, None, None)
+			.build();
+		assert!(opsgenie_trigger.validate().is_ok());
+
+		let invalid_triggers = vec![
+			(
+				TriggerBuilder::new()
+					.pagerduty("https://events.pagerduty.com/v2/enqueue")
+					.incident(IncidentSeverity::Critical, Some("${trasaction.hash}"), None),
+				"Invalid deduplication key template",
+			),
+			(
+				TriggerBuilder::new()
+					.pagerduty("https://events.pagerduty.com/v2/enqueue")
+					.incident(IncidentSeverity::Critical, Some(" "), None),
+				"Deduplication key cannot be empty",
+			),
+			(
+				TriggerBuilder::new()
+					.opsgenie("https://api.opsgenie.com")
+					.incident(IncidentSeverity::Critical, None, Some(0)),
+				"Auto-resolve delay must be greater than 0",
+			),
+			(
+				TriggerBuilder::new().opsgenie("api.opsgenie.com"),
+				"Invalid incident API URL format",
+			),
+			(
+				TriggerBuilder::new()
+					.opsgenie("https://api.opsgenie.com")
+					.message(&"a".repeat(OPSGENIE_MAX_MESSAGE_LENGTH + 1), "Body"),
+				"Title should not exceed",
+			),
+			(
+				TriggerBuilder::new()
+					.pagerduty("https://events.pagerduty.com/v2/enqueue")
+					.digest("0 0 * * * *", "Digest", "${digest.count} matches"),
+				"Digest is not supported for incident triggers",
+			),
+		];
+		for (builder, expected_error) in invalid_triggers {
+			let err = builder
+				.name("test_incident")
+				.build()
+				.validate()
+				.unwrap_err();
+			assert!(err.to_string().contains(expected_error), "{}", err);
+		}
+	}
+
+	#[tokio::test]
+	async fn test_resolve_secrets_incident() {
+		let trigger = TriggerBuilder::new()
+			.name("pagerduty")
+			.pagerduty("https://events.pagerduty.com/v2/enqueue")
+			.build();
+		let resolved = trigger.resolve_secrets().await.unwrap();
+		assert!(matches!(
+			resolved.config,
+			TriggerTypeConfig::PagerDuty {
+				routing_key: SecretValue::Plain(_),
+				..
+			}
+		));
+
+		let trigger = TriggerBuilder::new()
+			.name("opsgenie")
+			.opsgenie("https://api.opsgenie.com")
+			.build();
+		let resolved = trigger.resolve_secrets().await.unwrap();
+		assert!(matches!(
+			resolved.config,
+			TriggerTypeConfig::Opsgenie {
+				api_key: SecretValue::Plain(_),
+				..
+			}
+		));
+	}
+
+	#[test]
+	fn test_webhook_payload_validation() {
+		let match_trigger = TriggerBuilder::new()
+			.name("test_payload")
+			.webhook("https://api.example.com/webhook")
+			.webhook_payload(WebhookPayloadMode::Match)
+			.build();
+		assert!(match_trigger.validate().is_ok());
+
+		let template_trigger = TriggerBuilder::new()
+			.name("test_payload")
+			.webhook("https://api.example.com/webhook")
+			.webhook_payload(WebhookPayloadMode::Template {
+				template: serde_json::json!({
+					"hash": "${transaction.hash}",
+					"amounts": ["${events.0.args.value | decimals(18)}"]
+				}),
+			})
+			.build();
+		assert!(template_trigger.validate().is_ok());
+
+		let invalid_template_trigger = TriggerBuilder::new()
+			.name("test_payload")
+			.webhook("https://api.example.com/webhook")
+			.webhook_payload(WebhookPayloadMode::Template {
+				template: serde_json::json!({ "hash": "${trasaction.hash}" }),
+			})
+			.build();
+		let err = invalid_template_trigger.validate().unwrap_err();
+		assert!(err.to_string().contains("Invalid payload template"));
+
+		let digest_trigger = TriggerBuilder::new()
+			.name("test_payload")
+			.webhook("https://api.example.com/webhook")
+			.webhook_payload(WebhookPayloadMode::Match)
+			.digest("0 0 * * * *", "Digest", "${digest.count} matches")
+			.build();
+		let err = digest_trigger.validate().unwrap_err();
+		assert!(err.to_string().contains("Digest is only supported"));
+	}
+
+	#[test]
+	fn test_webhook_payload_deserialization() {
+		let config: TriggerTypeConfig = serde_json::from_value(serde_json::json!({
+			"url": { "type": "plain", "value": "https://api.example.com/webhook" },
+			"method": "POST",
+			"message": { "title": "Alert", "body": "Body" },
+			"payload": { "mode": "template", "template": { "hash": "${transaction.hash}" } }
+		}))
+		.unwrap();
+		assert!(matches!(
+			config,
+			TriggerTypeConfig::Webhook {
+				payload: Some(WebhookPayloadMode::Template { .. }),
+				..
+			}
+		));
+
+		let result = serde_json::from_value::<TriggerTypeConfig>(serde_json::json!({
+			"url": { "type": "plain", "value": "https://api.example.com/webhook" },
+			"message": { "title": "Alert", "body": "Body" },
+			"payload": { "mode": "raw" }
+		}));
+		assert!(result.is_err());
+	}
+
+	#[cfg(all(
+		feature = "amqp",
+		feature = "kafka",
+		feature = "nats",
+		feature = "redis"
+	))]
+	#[test]
+	fn test_queue_trigger_validation() {
+		let valid_triggers = vec![
+			TriggerBuilder::new()
+				.kafka("localhost:9092", "matches")
+				.build(),
+			TriggerBuilder::new()
+				.nats("tls://localhost:4222", "monitor.matches")
+				.build(),
+			TriggerBuilder::new()
+				.redis("rediss://localhost:6379", "matches")
+				.build(),
+			TriggerBuilder::new()
+				.amqp("amqps://localhost:5671", "")
+				.build(),
+		];
+		for trigger in valid_triggers {
+			assert!(trigger.validate().is_ok());
+		}
+
+		let invalid_triggers = vec![
+			(
+				TriggerBuilder::new().kafka(" ", "matches").build(),
+				"Brokers cannot be empty",
+			),
+			(
+				TriggerBuilder::new().kafka("localhost:9092", "").build(),
+				"Topic cannot be empty",
+			),
+			(
+				TriggerBuilder::new()
+					.nats("http://localhost:4222", "matches")
+					.build(),
+				"Invalid NATS URL format",
+			),
+			(
+				TriggerBuilder::new()
+					.nats("nats://localhost:4222", "monitor.*")
+					.build(),
+				"Subject cannot contain wildcards",
+			),
+			(
+				TriggerBuilder::new()
+					.redis("redis://localhost:6379", "my matches")
+					.build(),
+				"Stream cannot contain whitespace",
+			),
+			(
+				TriggerBuilder::new()
+					.amqp("localhost:5672", "matches")
+					.build(),
+				"Invalid AMQP URL format",
+			),
+			(
+				TriggerBuilder::new()
+					.redis("redis://localhost:6379", "matches")
+					.digest("0 */15 * * * *", "Digest", "${digest.count} matches")
+					.build(),
+				"Digest is not supported for queue triggers",
+			),
+		];
+		for (trigger, expected_error) in invalid_triggers {
+			let err = trigger.validate().unwrap_err();
+			assert!(
+				err.to_string().contains(expected_error),
+				"expected {:?}, got {}",
+				expected_error,
+				err
+			);
+		}
+
+		let mut trigger = TriggerBuilder::new()
+			.redis("redis://localhost:6379", "matches")
+			.build();
+		if let TriggerTypeConfig::Redis { max_len, .. } = &mut trigger.config {
+			*max_len = Some(0);
+		}
+		let err = trigger.validate().unwrap_err();
+		assert!(err
+			.to_string()
+			.contains("Stream maximum length must be greater than 0"));
+	}
+
+	#[test]
+	fn test_trigger_backend_feature_validation() {
+		let trigger = TriggerBuilder::new()
+			.name("test_trigger")
+			.kafka("localhost:9092", "matches")
+			.build();
+
+		let result = trigger.validate();
+		if cfg!(feature = "kafka") {
+			assert!(result.is_ok());
+		} else {
+			assert!(result
+				.unwrap_err()
+				.to_string()
+				.contains("requires the monitor to be built with the `kafka` feature"));
+		}
+	}
+
+	#[test]
+	fn test_queue_trigger_deserialization() {
+		let config: TriggerTypeConfig = serde_json::from_value(serde_json::json!({
+			"brokers": "localhost:9092",
+			"topic": "matches",
+			"properties": {
+				"sasl.password": { "type": "plain", "value": "secret" }
+			}
+		}))
+		.unwrap();
+		assert!(matches!(config, TriggerTypeConfig::Kafka { .. }));
+
+		let config: TriggerTypeConfig = serde_json::from_value(serde_json::json!({
+			"nats_url": { "type": "plain", "value": "nats://localhost:4222" },
+			"subject": "monitor.matches"
+		}))
+		.unwrap();
+		assert!(matches!(config, TriggerTypeConfig::Nats { .. }));
+
+		let config: TriggerTypeConfig = serde_json::from_value(serde_json::json!({
+			"redis_url": { "type": "plain", "value": "redis://localhost:6379" },
+			"stream": "matches",
+			"max_len": 10000
+		}))
+		.unwrap();
+		assert!(matches!(
+			config,
+			TriggerTypeConfig::Redis {
+				max_len: Some(10000),
+				..
+			}
+		));
+
+		let config: TriggerTypeConfig = serde_json::from_value(serde_json::json!({
+			"amqp_url": { "type": "plain", "value": "amqp://localhost:5672" },
+			"exchange": "matches",
+			"routing_key": "evm"
+		}))
+		.unwrap();
+		assert!(matches!(config, TriggerTypeConfig::Amqp { .. }));
+	}
+
+	#[tokio::test]
+	async fn test_resolve_secrets_queue() {
+		let mut trigger = TriggerBuilder::new()
+			.kafka("localhost:9092", "matches")
+			.build();
+		if let TriggerTypeConfig::Kafka { properties, .. } = &mut trigger.config {
+			*properties = Some(HashMap::from([(
+				"sasl.password".to_string(),
+				SecretValue::Plain(SecretString::new("secret".to_string())),
+			)]));
+		}
+		let resolved = trigger.resolve_secrets().await.unwrap();
+		assert_eq!(resolved.config, trigger.config);
+
+		let trigger = TriggerBuilder::new()
+			.amqp("amqp://localhost:5672", "matches")
+			.build();
+		let resolved = trigger.resolve_secrets().await.unwrap();
+		assert!(matches!(
+			resolved.config,
+			TriggerTypeConfig::Amqp {
+				amqp_url: SecretValue::Plain(_),
+				..
+			}
+		));
+	}
+
+	#[cfg(all(feature = "mysql", feature = "postgres", feature = "sqlite"))]
+	#[test]
+	fn test_database_trigger_validation() {
+		let valid_triggers = vec![
+			TriggerBuilder::new()
+				.database("sqlite://matches.db", "transfers")
+				.column("from_address", "${event.args.from}")
+				.column("value", "${event.args.value | decimals(6)}")
+				.column("log_position", "${event.log_index}")
+				.build(),
+			TriggerBuilder::new()
+				.database(
+					"postgres://localhost/monitor?sslmode=require",
+					"ledger.transfers",
+				)
+				.column("monitor", "${monitor.name}")
+				.build(),
+			TriggerBuilder::new()
+				.database("mysql://localhost/monitor", "token_transfers")
+				.build(),
+		];
+		for trigger in valid_triggers {
+			assert!(trigger.validate().is_ok());
+		}
+
+		let invalid_triggers = vec![
+			(
+				TriggerBuilder::new()
+					.database("localhost:5432", "transfers")
+					.build(),
+				"Invalid database URL format",
+			),
+			(
+				TriggerBuilder::new()
+					.database("sqlite://matches.db", "transfers; DROP TABLE transfers")
+					.build(),
+				"Invalid table name",
+			),
+			(
+				TriggerBuilder::new()
+					.database("sqlite://matches.db", "a.b.c")
+					.build(),
+				"Invalid table name",
+			),
+			(
+				TriggerBuilder::new()
+					.database("sqlite://matches.db", "transfers")
+					.column("from-address", "${event.args.from}")
+					.build(),
+				"Invalid column name: from-address",
+			),
+			(
+				TriggerBuilder::new()
+					.database("sqlite://matches.db", "transfers")
+					.column("log_index", "${event.log_index}")
+					.build(),
+				"Column log_index is written from the match key",
+			),
+			(
+				TriggerBuilder::new()
+					.database("sqlite://matches.db", "transfers")
+					.column("value", "${event.value}")
+					.build(),
+				"Invalid template of column value",
+			),
+			(
+				TriggerBuilder::new()
+					.database("sqlite://matches.db", "transfers")
+					.digest("0 */15 * * * *", "Digest", "${digest.count} matches")
+					.build(),
+				"Digest is not supported for database triggers",
+			),
+		];
+		for (trigger, expected_error) in invalid_triggers {
+			let err = trigger.validate().unwrap_err();
+			assert!(
+				err.to_string().contains(expected_error),
+				"expected {:?}, got {}",
+				expected_error,
+				err
+			);
+		}
+
+		let mut trigger = TriggerBuilder::new()
+			.database("sqlite://matches.db", "transfers")
+			.build();
+		if let TriggerTypeConfig::Database { batch_size, .. } = &mut trigger.config {
+			*batch_size = Some(0);
+		}
+		let err = trigger.validate().unwrap_err();
+		assert!(err
+			.to_string()
+			.contains("Batch size must be greater than 0"));
+	}
+
+	#[tokio::test]
+	async fn test_database_trigger_deserialization_and_secrets() {
+		let config: TriggerTypeConfig = serde_json::from_value(serde_json::json!({
+			"database_url": { "type": "plain", "value": "sqlite://matches.db" },
+			"table": "transfers",
+			"columns": {
+				"value": "${event.args.value}"
+			},
+			"batch_size": 50,
+			"batch_timeout_ms": 200
+		}))
+		.unwrap();
+		assert!(matches!(
+			config,
+			TriggerTypeConfig::Database {
+				batch_size: Some(50),
+				batch_timeout_ms: Some(200),
+				..
+			}
+		));
+
+		let trigger = TriggerBuilder::new()
+			.database("sqlite://matches.db", "transfers")
+			.build();
+		let resolved = trigger.resolve_secrets().await.unwrap();
+		assert_eq!(resolved.config, trigger.config);
+	}
+
+	#[test]
+	fn test_plugin_trigger_validation() {
+		let config: TriggerTypeConfig = serde_json::from_value(serde_json::json!({
+			"plugin": "ticketing",
+			"options": { "queue": "security" }
+		}))
+		.unwrap();
+		assert!(matches!(
+			&config,
+			TriggerTypeConfig::Plugin { plugin, options }
+				if plugin == "ticketing" && options["queue"] == "security"
+		));
+
+		let trigger = TriggerBuilder::new().plugin("ticketing").build();
+		assert!(trigger.validate().is_ok());
+
+		let invalid_triggers = vec![
+			(
+				TriggerBuilder::new().plugin(" ").build(),
+				"Plugin name cannot be empty",
+			),
+			(
+				TriggerBuilder::new()
+					.plugin("ticketing")
+					.digest("0 */15 * * * *", "Digest", "${digest.count} matches")
+					.build(),
+				"Digest is not supported for plugin triggers",
+			),
+		];
+		for (trigger, expected_error) in invalid_triggers {
+			let err = trigger.validate().unwrap_err();
+			assert!(
+				err.to_string().contains(expected_error),
+				"expected {:?}, got {}",
+				expected_error,
+				err
+			);
+		}
+	}
+
+	#[test]
+	fn test_digest_trigger_validation() {
+		let valid_trigger = TriggerBuilder::new()
+			.name("test_digest")
+			.slack("https://hooks.slack.com/services/xxx")
+			.digest(
+				"0 */15 * * * *",
+				"${digest.count} matches",
+				"{% for match in matches %}- ${match.message}\n{% endfor %}",
+			)
+			.build();
+		assert!(valid_trigger.validate().is_ok());
+
+		let invalid_digests = vec![
+			(
+				"invalid cron",
+				"Digest",
+				"{% for match in matches %}${match.message}{% endfor %}",
+			),
+			(
+				"0 */15 * * * *",
+				"",
+				"{% for match in matches %}${match.message}{% endfor %}",
+			),
+			("0 */15 * * * *", "Digest", " "),
+			(
+				"0 */15 * * * *",
+				"Digest",
+				"{% for match in matches %}${match.message}",
+			),
+			("0 */15 * * * *", "Digest", "{% endfor %}${match.message}"),
+			(
+				"0 */15 * * * *",
+				"Digest",
+				"{% for match in matches %}${match.unknown}{% endfor %}",
+			),
+			("0 */15 * * * *", "${match.message}", "Digest"),
+		];
+		for (cron_schedule, title, body) in invalid_digests {
+			let invalid_trigger = TriggerBuilder::new()
+				.name("test_digest")
+				.slack("https://hooks.slack.com/services/xxx")
+				.digest(cron_schedule, title, body)
+				.build();
+			assert!(invalid_trigger.validate().is_err());
+		}
+
+		// Digests are not supported for script triggers
+		let temp_dir = TempDir::new().unwrap();
+		let script_path = temp_dir.path().join("test_script.py");
+		fs::write(&script_path, "print('test')").unwrap();
+		let script_trigger = TriggerBuilder::new()
+			.name("test_digest")
+			.script(script_path.to_str().unwrap(), ScriptLanguage::Python)
+			.digest("0 */15 * * * *", "Digest", "${digest.count} matches")
+			.build();
+		assert!(script_trigger.validate().is_err());
+	}
+
 	#[tokio::test]
 	async fn test_load_all_duplicate_trigger_name() {
 		let temp_dir = TempDir::new().unwrap();
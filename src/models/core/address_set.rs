@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// A named collection of addresses that can be referenced from filter expressions.
+///
+/// Address sets are defined globally (one JSON file per set) and referenced by name
+/// in monitor expressions using the `@` prefix, e.g. `to in @hot_wallets`. They are
+/// intended for large lists (thousands of entries) that would be impractical to inline.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AddressSet {
+	/// Unique name used to reference the set in expressions
+	pub name: String,
+
+	/// Addresses contained in the set, in the network's native format
+	pub addresses: Vec<String>,
+}
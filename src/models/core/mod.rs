@@ -4,14 +4,31 @@
 //! - Monitors: Configuration for watching blockchain activity
 //! - Networks: Blockchain network definitions and connection details
 //! - Triggers: Actions to take when monitored conditions are met
+//! - Address sets: Named collections of addresses referenced from filter expressions
+//! - Tokens: Symbols and decimals used to normalize token amounts
+//! - Windows: Persisted state of sliding aggregation windows
+//! - Polls: Persisted state of the last poll of polling monitors
 
+mod address_set;
 mod monitor;
 mod network;
+mod poll;
+mod token;
 mod trigger;
+mod window;
 
+pub use address_set::AddressSet;
 pub use monitor::{
-	AddressWithSpec, EventCondition, FunctionCondition, MatchConditions, Monitor, ScriptLanguage,
-	TransactionCondition, TransactionStatus, TriggerConditions,
+	AddressWithSpec, AggregateCondition, AggregateFunction, EventCondition, EventMatchMode,
+	FilterPluginCondition, FunctionCondition, MatchConditions, Monitor, PollConfig, PollRead,
+	ScriptLanguage, ScriptMode, ScriptSandbox, StateCall, StateConditions, ThrottleConfig,
+	TransactionCondition, TransactionStatus, TriggerConditions, WindowAggregation,
 };
 pub use network::{Network, RpcUrl};
-pub use trigger::{NotificationMessage, Trigger, TriggerType, TriggerTypeConfig};
+pub use poll::PollState;
+pub use token::{PriceSourceConfig, Token, TokenPrice};
+pub use trigger::{
+	DigestConfig, IncidentSeverity, NotificationMessage, Trigger, TriggerType, TriggerTypeConfig,
+	WebhookPayloadMode,
+};
+pub use window::{WindowEntry, WindowState};
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::models::blockchain::ContractSpec;
+use crate::models::{blockchain::ContractSpec, IncidentSeverity};
 
 /// Configuration for monitoring specific blockchain activity.
 ///
@@ -32,8 +32,34 @@ pub struct Monitor {
 	/// Conditions that should be met prior to triggering notifications
 	pub trigger_conditions: Vec<TriggerConditions>,
 
+	/// Filter plugins, registered by the embedding application, which must all accept a match
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub filter_plugins: Vec<FilterPluginCondition>,
+
 	/// IDs of triggers to execute when conditions match
 	pub triggers: Vec<String>,
+
+	/// Optional aggregation of matches over a sliding time window. When set, matches are only
+	/// forwarded to triggers once the windowed aggregate satisfies its expression
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub window: Option<WindowAggregation>,
+
+	/// Optional deduplication and rate limiting of the notifications sent for this monitor
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub throttle: Option<ThrottleConfig>,
+
+	/// Optional severity of the matches, colouring Slack and Discord notifications
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub severity: Option<IncidentSeverity>,
+
+	/// Optional contract state read with view calls at the block of each match (EVM only)
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub state: Option<StateConditions>,
+
+	/// Optional periodic read of contract state or account balances. Polling monitors are
+	/// independent of transactions and do not use match conditions
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub poll: Option<PollConfig>,
 }
 
 /// Contract address with optional ABI for decoding transactions and events
@@ -59,6 +85,232 @@ pub struct MatchConditions {
 
 	/// Transaction states to match
 	pub transactions: Vec<TransactionCondition>,
+
+	/// How event conditions are combined within a single transaction
+	#[serde(default, skip_serializing_if = "EventMatchMode::is_any")]
+	pub match_mode: EventMatchMode,
+
+	/// Aggregates over the decoded events of a transaction that must all hold
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub aggregates: Vec<AggregateCondition>,
+}
+
+/// Strategy used to combine event conditions within a single transaction
+#[derive(Debug, Copy, Clone, Deserialize, Serialize, PartialEq, Eq, Default)]
+pub enum EventMatchMode {
+	/// At least one event condition must match
+	#[default]
+	Any,
+	/// Every event condition must be matched by at least one event
+	All,
+	/// Every event condition must be matched, by events emitted in the same order as the
+	/// conditions are declared
+	Sequence,
+}
+
+impl EventMatchMode {
+	/// Returns true for the default mode, where any matching event condition is sufficient
+	pub fn is_any(&self) -> bool {
+		*self == EventMatchMode::Any
+	}
+}
+
+/// Deduplication and rate limiting of a monitor's notifications
+///
+/// Notifications are tracked per deduplication key. Matches that would exceed the limits are
+/// suppressed and counted, and the count is reported with the next notification sent for the
+/// same key.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ThrottleConfig {
+	/// Maximum number of notifications sent per window
+	#[serde(default)]
+	pub max_notifications: Option<u32>,
+
+	/// Length of the rate limiting window in seconds
+	#[serde(default)]
+	pub window_secs: Option<u64>,
+
+	/// Template of the deduplication key (e.g., "${events.0.args.to}"). When omitted, all
+	/// notifications of the monitor share a single key
+	#[serde(default)]
+	pub dedup_key: Option<String>,
+
+	/// Minimum number of seconds between two notifications with the same key
+	#[serde(default)]
+	pub cooldown_secs: Option<u64>,
+}
+
+/// Aggregation of monitor matches over a sliding time window
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct WindowAggregation {
+	/// Aggregate function to apply
+	pub function: AggregateFunction,
+
+	/// Template variable holding the numeric value to aggregate (e.g., "events.0.args.value").
+	/// Not used by `Count`
+	#[serde(default)]
+	pub field: Option<String>,
+
+	/// Template variables whose values key separate windows (e.g., ["events.0.args.from"])
+	#[serde(default)]
+	pub group_by: Vec<String>,
+
+	/// Length of the sliding window in seconds
+	pub window_secs: u64,
+
+	/// Expression evaluated against the aggregate, available as `result`
+	/// (e.g., "result > 50")
+	pub expression: String,
+}
+
+/// Condition on an aggregate computed over the decoded events of a transaction
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AggregateCondition {
+	/// Aggregate function to apply
+	pub function: AggregateFunction,
+
+	/// Signature of the events to aggregate (e.g., "Transfer(address,address,uint256)")
+	pub signature: String,
+
+	/// Numeric event parameter to aggregate (not used by `Count`)
+	#[serde(default)]
+	pub field: Option<String>,
+
+	/// Optional expression selecting which events are included in the aggregate
+	#[serde(default)]
+	pub filter: Option<String>,
+
+	/// Expression evaluated against the aggregate, available as `result`
+	/// (e.g., "result > 1000000")
+	pub expression: String,
+}
+
+/// Functions available for aggregating event parameters
+#[derive(Debug, Copy, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub enum AggregateFunction {
+	/// Number of events
+	Count,
+	/// Sum of the field values
+	Sum,
+	/// Smallest field value
+	Min,
+	/// Largest field value
+	Max,
+}
+
+impl AggregateFunction {
+	/// Returns true if the function aggregates the values of an event parameter
+	pub fn requires_field(&self) -> bool {
+		!matches!(self, AggregateFunction::Count)
+	}
+}
+
+/// Contract state read at the block of a match
+///
+/// The results of the calls are exposed as `state.<name>` template variables, and the match is
+/// only kept if the optional expression holds.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct StateConditions {
+	/// View calls to execute
+	pub calls: Vec<StateCall>,
+
+	/// Optional expression evaluated against the call results, referenced by name
+	/// (e.g., "total_assets < 1000000 USDC")
+	#[serde(default)]
+	pub expression: Option<String>,
+}
+
+/// Read-only contract call executed with `eth_call`
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct StateCall {
+	/// Name of the result in expressions and templates (e.g., "total_assets")
+	pub name: String,
+
+	/// Template of the address of the contract to call (e.g., "${transaction.to}")
+	pub address: String,
+
+	/// Function signature (e.g., "balanceOf(address)")
+	pub signature: String,
+
+	/// Type of the returned value (e.g., "uint256")
+	pub returns: String,
+
+	/// Templates of the function arguments (e.g., ["${events.0.args.owner}"])
+	#[serde(default)]
+	pub args: Vec<String>,
+
+	/// Whether the call is also executed at the previous block, exposing its result as
+	/// `<name>_previous`
+	#[serde(default)]
+	pub previous: bool,
+}
+
+/// Periodic read of values, matching when the expression holds
+///
+/// Exactly one of `every_blocks` and `interval_secs` sets the schedule. Values are read at the
+/// block being processed and are available in the expression by name, along with the value read
+/// by the previous poll as `<name>_previous`, `block_number` and `block_timestamp`.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PollConfig {
+	/// Number of blocks between two polls
+	#[serde(default)]
+	pub every_blocks: Option<u64>,
+
+	/// Number of seconds between two polls, measured with block timestamps
+	#[serde(default)]
+	pub interval_secs: Option<u64>,
+
+	/// Values read at each poll
+	pub reads: Vec<PollRead>,
+
+	/// Expression evaluated against the values read (e.g., "balance < 10 ETH")
+	pub expression: String,
+}
+
+/// Value read by a poll
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase", deny_unknown_fields)]
+pub enum PollRead {
+	/// Native balance of an account, in wei on EVM networks and in stroops on Stellar
+	Balance {
+		/// Name of the value in the expression and templates
+		name: String,
+		/// Address of the account
+		address: String,
+	},
+	/// Return value of a view function (EVM only)
+	Call {
+		/// Name of the value in the expression and templates
+		name: String,
+		/// Address of the contract to call
+		address: String,
+		/// Function signature (e.g., "balanceOf(address)")
+		signature: String,
+		/// Arguments of the call
+		#[serde(default)]
+		args: Vec<String>,
+		/// Type of the returned value, or tuple of the returned values (e.g.,
+		/// "(uint80,int256,uint256,uint256,uint80)")
+		returns: String,
+		/// Index of the returned value to read when the function returns several
+		#[serde(default)]
+		output: Option<usize>,
+	},
+}
+
+impl PollRead {
+	/// Returns the name of the value
+	pub fn name(&self) -> &str {
+		match self {
+			PollRead::Balance { name, .. } | PollRead::Call { name, .. } => name,
+		}
+	}
 }
 
 /// Condition for matching contract function calls
@@ -122,11 +374,89 @@ pub struct TriggerConditions {
 
 	/// The timeout of the script
 	pub timeout_ms: u32,
+
+	/// How the script interpreter is run
+	#[serde(default)]
+	pub mode: ScriptMode,
+
+	/// Restrictions applied to the script process
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub sandbox: Option<ScriptSandbox>,
+
+	/// Command running executable scripts, such as `deno run`, instead of the file itself
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub interpreter: Option<String>,
+}
+/// Reference to a filter plugin applied to the matches of a monitor
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct FilterPluginCondition {
+	/// Name the filter plugin is registered under
+	pub plugin: String,
+
+	/// Options passed to the plugin
+	#[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+	pub options: serde_json::Map<String, serde_json::Value>,
 }
+
 /// The possible languages of the script
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Hash, Eq)]
 pub enum ScriptLanguage {
 	JavaScript,
 	Python,
 	Bash,
+	/// WebAssembly module run in-process
+	Wasm,
+	/// Binary or script with a shebang, run directly or with an interpreter
+	Executable,
+}
+
+/// Execution policy restricting the process of a script
+///
+/// Fields left unset do not restrict the script.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(deny_unknown_fields)]
+pub struct ScriptSandbox {
+	/// Names of the environment variables passed to the script, the environment of the monitor
+	/// is inherited when unset and cleared when empty
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub env_allowlist: Option<Vec<String>>,
+
+	/// Maximum CPU time of the script, in seconds
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub max_cpu_seconds: Option<u64>,
+
+	/// Maximum virtual memory of the script, in bytes
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub max_memory_bytes: Option<u64>,
+
+	/// Maximum number of files the script may open at once
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub max_open_files: Option<u64>,
+
+	/// Current directory of the script
+	///
+	/// Only sets where relative paths resolve from, it does not restrict the files the script
+	/// can access. Also accepted as `working_dir`, its former name.
+	#[serde(
+		default,
+		alias = "working_dir",
+		skip_serializing_if = "Option::is_none"
+	)]
+	pub current_dir: Option<String>,
+
+	/// Whether the script runs without network access, in its own network namespace
+	#[serde(default)]
+	pub no_network: bool,
+}
+
+/// How the interpreter of a script is run
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Hash, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ScriptMode {
+	/// A new interpreter is started for every call, reading a single JSON input from stdin
+	#[default]
+	Process,
+	/// A long-lived interpreter answers newline-delimited JSON requests over stdio
+	Worker,
 }
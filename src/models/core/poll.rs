@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Persisted state of the last poll of a monitor on a network.
+///
+/// Holds the schedule and the values read, so that `_previous` values survive restarts.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Default)]
+pub struct PollState {
+	/// Number of the block of the last poll
+	pub block_number: u64,
+
+	/// Timestamp of the block of the last poll, if known
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub timestamp: Option<u64>,
+
+	/// Values read by the last poll, by name
+	#[serde(default)]
+	pub values: HashMap<String, String>,
+}
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Metadata of a fungible token used to normalize raw on-chain amounts.
+///
+/// Tokens are defined globally (one JSON file per token). Expressions can compare amounts in
+/// whole token units using the symbol, e.g. `value > 10000 USDC`, and templates can format raw
+/// amounts with the token decimals and symbol.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Token {
+	/// Symbol used to reference the token in expressions and templates
+	pub symbol: String,
+
+	/// Number of decimals of the raw amounts, e.g. 6 for USDC
+	pub decimals: u32,
+
+	/// Contract addresses of the token, in the network's native format
+	#[serde(default)]
+	pub addresses: Vec<String>,
+
+	/// Optional USD price feed, used by `usd()` in expressions and templates
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub price: Option<TokenPrice>,
+}
+
+/// USD price feed of a token
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TokenPrice {
+	/// Where the price is read from
+	pub source: PriceSourceConfig,
+
+	/// Number of seconds a fetched price is reused before it is refreshed
+	#[serde(default)]
+	pub ttl_secs: Option<u64>,
+
+	/// Maximum age in seconds of a price before it is considered stale and no longer used
+	#[serde(default)]
+	pub max_age_secs: Option<u64>,
+}
+
+/// Supported sources of USD prices
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase", deny_unknown_fields)]
+pub enum PriceSourceConfig {
+	/// Chainlink aggregator read with `latestRoundData()` on an EVM network
+	Chainlink {
+		/// Slug of the network the aggregator is deployed on
+		network: String,
+		/// Address of the aggregator (or its proxy)
+		address: String,
+	},
+	/// JSON file mapping token symbols to USD prices, e.g. `{"USDC": 1.0}`
+	File {
+		/// Path of the price file
+		path: String,
+	},
+	/// HTTP API returning the price in a JSON document
+	Http {
+		/// URL requested with GET
+		url: String,
+		/// Dot-separated path of the price in the response, e.g. `ethereum.usd`
+		path: String,
+		/// Headers sent with the request, e.g. an API key
+		#[serde(default)]
+		headers: HashMap<String, String>,
+	},
+}
@@ -1,5 +1,9 @@
 use crate::{
-	models::{core::ScriptLanguage, SecretValue},
+	models::{
+		core::{ScriptLanguage, ScriptMode, ScriptSandbox},
+		SecretValue,
+	},
+	services::notification::DatabaseBackend,
 	utils::HttpRetryConfig,
 };
 use email_address::EmailAddress;
@@ -12,11 +16,29 @@ pub struct Trigger {
 	/// Unique name identifying this trigger
 	pub name: String,
 
-	/// Type of trigger (Email, Slack, Webhook, Telegram, Discord, Script)
+	/// Type of trigger (Email, Slack, Webhook, Telegram, Discord, Script, PagerDuty, Opsgenie, Teams,
+	/// Kafka, Nats, Redis, Amqp, Database, Plugin)
 	pub trigger_type: TriggerType,
 
 	/// Configuration specific to the trigger type
 	pub config: TriggerTypeConfig,
+
+	/// Optional digest batching matches into periodic summaries
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub digest: Option<DigestConfig>,
+}
+
+/// Digest configuration of a trigger
+///
+/// Matches of a trigger with a digest are buffered instead of being notified one by one, and
+/// flushed as a single message on the digest schedule.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct DigestConfig {
+	/// Cron schedule on which buffered matches are flushed
+	pub cron_schedule: String,
+	/// Digest message template, looping over the buffered matches with `{% for match in matches %}`
+	pub message: NotificationMessage,
 }
 
 /// Supported trigger action types
@@ -36,6 +58,24 @@ pub enum TriggerType {
 	Discord,
 	/// Execute local script
 	Script,
+	/// Open a PagerDuty incident through the Events API v2
+	PagerDuty,
+	/// Open an Opsgenie alert
+	Opsgenie,
+	/// Send notification to Microsoft Teams
+	Teams,
+	/// Publish the match to a Kafka topic
+	Kafka,
+	/// Publish the match to a NATS JetStream subject
+	Nats,
+	/// Append the match to a Redis stream
+	Redis,
+	/// Publish the match to an AMQP exchange
+	Amqp,
+	/// Insert the match into a SQL table
+	Database,
+	/// Send the match through a notifier plugin registered by the embedding application
+	Plugin,
 }
 
 /// Notification message fields
@@ -48,6 +88,75 @@ pub struct NotificationMessage {
 	pub body: String,
 }
 
+/// Severity of monitors and of incidents opened by PagerDuty and Opsgenie triggers
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum IncidentSeverity {
+	/// Paged immediately, mapped to Opsgenie priority P1
+	#[default]
+	Critical,
+	/// Mapped to Opsgenie priority P2
+	Error,
+	/// Mapped to Opsgenie priority P3
+	Warning,
+	/// Mapped to Opsgenie priority P5
+	Info,
+}
+
+impl IncidentSeverity {
+	/// Severity of the PagerDuty event payload
+	pub fn pagerduty_severity(&self) -> &'static str {
+		match self {
+			Self::Critical => "critical",
+			Self::Error => "error",
+			Self::Warning => "warning",
+			Self::Info => "info",
+		}
+	}
+
+	/// Priority of the Opsgenie alert
+	pub fn opsgenie_priority(&self) -> &'static str {
+		match self {
+			Self::Critical => "P1",
+			Self::Error => "P2",
+			Self::Warning => "P3",
+			Self::Info => "P5",
+		}
+	}
+
+	/// RGB colour of Slack attachments and Discord embeds
+	pub fn color(&self) -> u32 {
+		match self {
+			Self::Critical => 0xE01E5A,
+			Self::Error => 0xE8912D,
+			Self::Warning => 0xECB22E,
+			Self::Info => 0x36C5F0,
+		}
+	}
+}
+
+impl std::fmt::Display for IncidentSeverity {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(self.pagerduty_severity())
+	}
+}
+
+/// Request body format of webhook triggers
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(tag = "mode", rename_all = "lowercase", deny_unknown_fields)]
+pub enum WebhookPayloadMode {
+	/// JSON object with the trigger title and rendered message body
+	#[default]
+	Message,
+	/// Versioned JSON document containing the full monitor match
+	Match,
+	/// User-defined JSON document whose string values are rendered as message templates
+	Template {
+		/// JSON document to render
+		template: serde_json::Value,
+	},
+}
+
 /// Type-specific configuration for triggers
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 #[serde(deny_unknown_fields)]
@@ -59,6 +168,13 @@ pub enum TriggerTypeConfig {
 		slack_url: SecretValue,
 		/// Notification message
 		message: NotificationMessage,
+		/// Block explorer link template, shown as a button on the message
+		#[serde(default)]
+		explorer_url: Option<String>,
+		/// Block Kit blocks whose strings are rendered as message templates, replacing the
+		/// default layout
+		#[serde(default)]
+		blocks: Option<serde_json::Value>,
 		/// Retry policy for HTTP requests
 		#[serde(default)]
 		retry_policy: HttpRetryConfig,
@@ -95,6 +211,9 @@ pub enum TriggerTypeConfig {
 		/// Retry policy for HTTP requests
 		#[serde(default)]
 		retry_policy: HttpRetryConfig,
+		/// Request body format, defaults to the title and rendered message
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		payload: Option<WebhookPayloadMode>,
 	},
 	/// Telegram notification configuration
 	Telegram {
@@ -116,10 +235,142 @@ pub enum TriggerTypeConfig {
 		discord_url: SecretValue,
 		/// Notification message
 		message: NotificationMessage,
+		/// Block explorer link template, linked from the embed title
+		#[serde(default)]
+		explorer_url: Option<String>,
+		/// Embeds whose strings are rendered as message templates, replacing the default embed
+		#[serde(default)]
+		embeds: Option<serde_json::Value>,
+		/// Retry policy for HTTP requests
+		#[serde(default)]
+		retry_policy: HttpRetryConfig,
+	},
+	/// Microsoft Teams notification configuration
+	Teams {
+		/// Teams incoming webhook or workflow URL
+		teams_url: SecretValue,
+		/// Notification message
+		message: NotificationMessage,
+		/// Block explorer link template, shown as a button on the card
+		#[serde(default)]
+		explorer_url: Option<String>,
 		/// Retry policy for HTTP requests
 		#[serde(default)]
 		retry_policy: HttpRetryConfig,
 	},
+	/// PagerDuty Events API v2 configuration
+	PagerDuty {
+		/// Integration key of the PagerDuty service
+		routing_key: SecretValue,
+		/// Notification message, the title is used as the incident summary
+		message: NotificationMessage,
+		/// Incident severity
+		#[serde(default)]
+		severity: IncidentSeverity,
+		/// Deduplication key template, defaults to the monitor name and transaction hash
+		#[serde(default)]
+		dedup_key: Option<String>,
+		/// Seconds after which the incident is resolved automatically
+		#[serde(default)]
+		auto_resolve_after_secs: Option<u64>,
+		/// Events API endpoint, defaults to the PagerDuty US service region
+		#[serde(default)]
+		events_url: Option<String>,
+		/// Retry policy for HTTP requests
+		#[serde(default)]
+		retry_policy: HttpRetryConfig,
+	},
+	/// Opsgenie alert configuration
+	Opsgenie {
+		/// Opsgenie API integration key
+		api_key: SecretValue,
+		/// Notification message, the title is used as the alert message
+		message: NotificationMessage,
+		/// Incident severity, mapped to the alert priority
+		#[serde(default)]
+		severity: IncidentSeverity,
+		/// Alias template deduplicating alerts, defaults to the monitor name and transaction hash
+		#[serde(default)]
+		alias: Option<String>,
+		/// Seconds after which the alert is closed automatically
+		#[serde(default)]
+		auto_resolve_after_secs: Option<u64>,
+		/// API base URL, defaults to the Opsgenie US service region
+		#[serde(default)]
+		api_url: Option<String>,
+		/// Retry policy for HTTP requests
+		#[serde(default)]
+		retry_policy: HttpRetryConfig,
+	},
+	/// Kafka producer configuration
+	Kafka {
+		/// Comma-separated list of bootstrap servers
+		brokers: String,
+		/// Topic matches are published to
+		topic: String,
+		/// Additional librdkafka producer properties, such as SASL credentials
+		#[serde(default)]
+		properties: Option<std::collections::HashMap<String, SecretValue>>,
+		/// Retry policy for publishing
+		#[serde(default)]
+		retry_policy: HttpRetryConfig,
+	},
+	/// NATS JetStream configuration
+	Nats {
+		/// NATS server URL
+		nats_url: SecretValue,
+		/// Subject matches are published to, captured by a JetStream stream
+		subject: String,
+		/// Retry policy for publishing
+		#[serde(default)]
+		retry_policy: HttpRetryConfig,
+	},
+	/// Redis stream configuration
+	Redis {
+		/// Redis server URL
+		redis_url: SecretValue,
+		/// Key of the stream matches are appended to
+		stream: String,
+		/// Approximate maximum number of entries kept in the stream
+		#[serde(default)]
+		max_len: Option<usize>,
+		/// Retry policy for publishing
+		#[serde(default)]
+		retry_policy: HttpRetryConfig,
+	},
+	/// AMQP configuration
+	Amqp {
+		/// AMQP broker URL
+		amqp_url: SecretValue,
+		/// Exchange matches are published to, empty for the default exchange
+		exchange: String,
+		/// Routing key, defaults to the partition key of the match
+		#[serde(default)]
+		routing_key: Option<String>,
+		/// Retry policy for publishing
+		#[serde(default)]
+		retry_policy: HttpRetryConfig,
+	},
+	/// SQL database configuration
+	Database {
+		/// Database URL, `postgres://`, `mysql://` or `sqlite:`
+		database_url: SecretValue,
+		/// Table rows are upserted into
+		table: String,
+		/// Templates of the column values, keyed by column name
+		#[serde(default)]
+		columns: std::collections::BTreeMap<String, String>,
+		/// Maximum number of rows written in a single statement
+		#[serde(default)]
+		batch_size: Option<usize>,
+		/// Milliseconds to wait for more rows before writing a batch, `0` (no batching beyond
+		/// the rows already waiting) by default
+		#[serde(default)]
+		batch_timeout_ms: Option<u64>,
+		/// Retry policy for writing rows
+		#[serde(default)]
+		retry_policy: HttpRetryConfig,
+	},
 	/// Script execution configuration
 	Script {
 		/// Language of the script
@@ -131,10 +382,71 @@ pub enum TriggerTypeConfig {
 		arguments: Option<Vec<String>>,
 		/// Timeout in milliseconds
 		timeout_ms: u32,
+		/// How the script interpreter is run
+		#[serde(default)]
+		mode: ScriptMode,
+		/// Restrictions applied to the script process
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		sandbox: Option<ScriptSandbox>,
+		/// Command running executable scripts, instead of the file itself
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		interpreter: Option<String>,
+	},
+	/// Notifier plugin configuration
+	Plugin {
+		/// Name the notifier plugin is registered under
+		plugin: String,
+		/// Options passed to the plugin
+		#[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+		options: serde_json::Map<String, serde_json::Value>,
 	},
 }
 
 impl TriggerTypeConfig {
+	/// Get the notification message of the trigger type, if applicable.
+	pub fn message(&self) -> Option<&NotificationMessage> {
+		match self {
+			Self::Slack { message, .. }
+			| Self::Email { message, .. }
+			| Self::Webhook { message, .. }
+			| Self::Telegram { message, .. }
+			| Self::Discord { message, .. }
+			| Self::PagerDuty { message, .. }
+			| Self::Opsgenie { message, .. }
+			| Self::Teams { message, .. } => Some(message),
+			Self::Kafka { .. }
+			| Self::Nats { .. }
+			| Self::Redis { .. }
+			| Self::Amqp { .. }
+			| Self::Database { .. }
+			| Self::Script { .. }
+			| Self::Plugin { .. } => None,
+		}
+	}
+
+	/// Returns a copy of the trigger type with its notification message replaced, if applicable.
+	pub fn with_message(&self, new_message: NotificationMessage) -> Self {
+		let mut config = self.clone();
+		match &mut config {
+			Self::Slack { message, .. }
+			| Self::Email { message, .. }
+			| Self::Webhook { message, .. }
+			| Self::Telegram { message, .. }
+			| Self::Discord { message, .. }
+			| Self::PagerDuty { message, .. }
+			| Self::Opsgenie { message, .. }
+			| Self::Teams { message, .. } => *message = new_message,
+			Self::Kafka { .. }
+			| Self::Nats { .. }
+			| Self::Redis { .. }
+			| Self::Amqp { .. }
+			| Self::Database { .. }
+			| Self::Script { .. }
+			| Self::Plugin { .. } => {}
+		}
+		config
+	}
+
 	/// Get the retry policy for the trigger type, if applicable.
 	pub fn get_retry_policy(&self) -> Option<HttpRetryConfig> {
 		match self {
@@ -142,7 +454,39 @@ impl TriggerTypeConfig {
 			Self::Discord { retry_policy, .. } => Some(retry_policy.clone()),
 			Self::Webhook { retry_policy, .. } => Some(retry_policy.clone()),
 			Self::Telegram { retry_policy, .. } => Some(retry_policy.clone()),
+			Self::PagerDuty { retry_policy, .. } => Some(retry_policy.clone()),
+			Self::Opsgenie { retry_policy, .. } => Some(retry_policy.clone()),
+			Self::Teams { retry_policy, .. } => Some(retry_policy.clone()),
+			Self::Kafka { retry_policy, .. } => Some(retry_policy.clone()),
+			Self::Nats { retry_policy, .. } => Some(retry_policy.clone()),
+			Self::Redis { retry_policy, .. } => Some(retry_policy.clone()),
+			Self::Amqp { retry_policy, .. } => Some(retry_policy.clone()),
+			Self::Database { retry_policy, .. } => Some(retry_policy.clone()),
 			_ => None,
 		}
 	}
+
+	/// Get the cargo feature the trigger type requires, if the monitor was built without it.
+	pub fn disabled_feature(&self) -> Option<&'static str> {
+		let (feature, enabled) = match self {
+			Self::Kafka { .. } => ("kafka", cfg!(feature = "kafka")),
+			Self::Nats { .. } => ("nats", cfg!(feature = "nats")),
+			Self::Redis { .. } => ("redis", cfg!(feature = "redis")),
+			Self::Amqp { .. } => ("amqp", cfg!(feature = "amqp")),
+			Self::Database { database_url, .. } => {
+				match DatabaseBackend::from_url(database_url.as_str()) {
+					Some(DatabaseBackend::Postgres) => ("postgres", cfg!(feature = "postgres")),
+					Some(DatabaseBackend::MySql) => ("mysql", cfg!(feature = "mysql")),
+					Some(DatabaseBackend::Sqlite) => ("sqlite", cfg!(feature = "sqlite")),
+					None => return None,
+				}
+			}
+			Self::Script {
+				language: ScriptLanguage::Wasm,
+				..
+			} => ("wasm", cfg!(feature = "wasm")),
+			_ => return None,
+		};
+		(!enabled).then_some(feature)
+	}
 }
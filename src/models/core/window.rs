@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/// Persisted state of a monitor's sliding aggregation window.
+///
+/// Holds every match that is still within the window, so that aggregates survive restarts.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Default)]
+pub struct WindowState {
+	/// Matches currently within the window, in arrival order
+	pub entries: Vec<WindowEntry>,
+}
+
+/// A single match recorded in a sliding aggregation window
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct WindowEntry {
+	/// Group key built from the window's `group_by` variables
+	pub key: String,
+
+	/// Unix timestamp (seconds) of the block containing the match
+	pub timestamp: u64,
+
+	/// Value aggregated for the match (empty for `Count`)
+	pub value: String,
+}
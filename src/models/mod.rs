@@ -23,6 +23,8 @@ pub use blockchain::evm::{
 	EVMTransactionReceipt,
 };
 
+pub use blockchain::poll::{PollMonitorMatch, PollValue};
+
 pub use blockchain::stellar::{
 	StellarBlock, StellarContractFunction, StellarContractInput, StellarContractSpec,
 	StellarDecodedParamEntry, StellarDecodedTransaction, StellarEvent,
@@ -33,9 +35,13 @@ pub use blockchain::stellar::{
 
 // Re-export core types
 pub use core::{
-	AddressWithSpec, EventCondition, FunctionCondition, MatchConditions, Monitor, Network,
-	NotificationMessage, RpcUrl, ScriptLanguage, TransactionCondition, TransactionStatus, Trigger,
-	TriggerConditions, TriggerType, TriggerTypeConfig,
+	AddressSet, AddressWithSpec, AggregateCondition, AggregateFunction, DigestConfig,
+	EventCondition, EventMatchMode, FilterPluginCondition, FunctionCondition, IncidentSeverity,
+	MatchConditions, Monitor, Network, NotificationMessage, PollConfig, PollRead, PollState,
+	PriceSourceConfig, RpcUrl, ScriptLanguage, ScriptMode, ScriptSandbox, StateCall,
+	StateConditions, ThrottleConfig, Token, TokenPrice, TransactionCondition, TransactionStatus,
+	Trigger, TriggerConditions, TriggerType, TriggerTypeConfig, WebhookPayloadMode,
+	WindowAggregation, WindowEntry, WindowState,
 };
 
 // Re-export config types
@@ -6,17 +6,25 @@
 
 #![allow(clippy::result_large_err)]
 
-use std::{collections::HashMap, marker::PhantomData, path::Path};
+use std::{
+	collections::{HashMap, HashSet},
+	marker::PhantomData,
+	path::Path,
+};
 
 use async_trait::async_trait;
 
 use crate::{
-	models::{ConfigLoader, Monitor, Network, ScriptLanguage, Trigger},
+	models::{
+		AddressSet, BlockChainType, ConfigLoader, ContractSpec, Monitor, Network, ScriptLanguage,
+		Trigger,
+	},
 	repositories::{
 		error::RepositoryError,
 		network::{NetworkRepository, NetworkRepositoryTrait, NetworkService},
 		trigger::{TriggerRepository, TriggerRepositoryTrait, TriggerService},
 	},
+	services::{filter::address_set_references, notification::Template},
 };
 
 /// Static mapping of script languages to their file extensions
@@ -93,9 +101,40 @@ impl<
 				}
 			}
 
+			// Validate the arguments used by trigger templates against the contract ABIs
+			if let Some(abi_arguments) = evm_abi_argument_names(monitor) {
+				for trigger in monitor.triggers.iter().filter_map(|id| triggers.get(id)) {
+					let templates = trigger
+						.config
+						.message()
+						.map(|message| message.body.clone())
+						.into_iter()
+						.chain(
+							trigger
+								.digest
+								.as_ref()
+								.map(|digest| digest.message.body.clone()),
+						);
+					for template in templates {
+						let Ok(template) = template.parse::<Template>() else {
+							continue;
+						};
+						for argument in template.argument_names() {
+							if !abi_arguments.contains(&argument) {
+								validation_errors.push(format!(
+									"Monitor '{}' uses trigger '{}' whose template references \
+									 unknown {} argument '{}'",
+									monitor_name, trigger.name, argument.0, argument.1
+								));
+							}
+						}
+					}
+				}
+			}
+
 			// Validate network references
 			for network_slug in &monitor.networks {
-				if !networks.contains_key(network_slug) {
+				let Some(network) = networks.get(network_slug) else {
 					validation_errors.push(format!(
 						"Monitor '{}' references non-existent network '{}'",
 						monitor_name, network_slug
@@ -104,6 +143,19 @@ impl<
 						format!("monitor_{}_invalid_network", monitor_name),
 						network_slug.clone(),
 					);
+					continue;
+				};
+
+				// Event correlation is only implemented by the EVM filter
+				let conditions = &monitor.match_conditions;
+				if network.network_type != BlockChainType::EVM
+					&& (!conditions.match_mode.is_any() || !conditions.aggregates.is_empty())
+				{
+					validation_errors.push(format!(
+						"Monitor '{}' uses match_mode or aggregates, which are only supported on \
+						 EVM networks, on network '{}'",
+						monitor_name, network_slug
+					));
 				}
 			}
 
@@ -164,6 +216,149 @@ impl<
 
 		Ok(())
 	}
+	/// Returns an error if any monitor expression references an address set (e.g. `@hot_wallets`)
+	/// that is not defined.
+	pub fn validate_address_set_references(
+		monitors: &HashMap<String, Monitor>,
+		address_sets: &HashMap<String, AddressSet>,
+	) -> Result<(), RepositoryError> {
+		let defined: HashSet<&str> = address_sets.values().map(|set| set.name.as_str()).collect();
+		let mut validation_errors = Vec::new();
+		let mut metadata = HashMap::new();
+
+		for (monitor_name, monitor) in monitors {
+			for expression in monitor_expressions(monitor) {
+				for set_name in address_set_references(expression) {
+					if !defined.contains(set_name) {
+						validation_errors.push(format!(
+							"Monitor '{}' references non-existent address set '@{}'",
+							monitor_name, set_name
+						));
+						metadata.insert(
+							format!("monitor_{}_invalid_address_set", monitor_name),
+							set_name.to_string(),
+						);
+					}
+				}
+			}
+		}
+
+		if !validation_errors.is_empty() {
+			return Err(RepositoryError::validation_error(
+				format!(
+					"Configuration validation failed:\n{}",
+					validation_errors.join("\n"),
+				),
+				None,
+				Some(metadata),
+			));
+		}
+
+		Ok(())
+	}
+
+	/// Loads the address sets to validate monitor references
+	///
+	/// Address sets are read from the `address_sets` directory next to the given monitor
+	/// directory, falling back to the default config directory when it has no parent.
+	async fn load_address_sets(
+		monitor_dir: Option<&Path>,
+	) -> Result<HashMap<String, AddressSet>, RepositoryError> {
+		let address_set_dir = monitor_dir
+			.and_then(Path::parent)
+			.filter(|config_dir| !config_dir.as_os_str().is_empty())
+			.map(|config_dir| config_dir.join("address_sets"));
+		AddressSet::load_all(address_set_dir.as_deref())
+			.await
+			.map_err(|e| {
+				RepositoryError::load_error(
+					"Failed to load address sets for monitor validation",
+					Some(Box::new(e)),
+					Some(HashMap::from([(
+						"path".to_string(),
+						address_set_dir
+							.map_or_else(|| "default".to_string(), |dir| dir.display().to_string()),
+					)])),
+				)
+			})
+	}
+}
+
+/// Lists the filter expressions of a monitor
+fn monitor_expressions(monitor: &Monitor) -> impl Iterator<Item = &str> {
+	let conditions = &monitor.match_conditions;
+	let functions = conditions
+		.functions
+		.iter()
+		.filter_map(|condition| condition.expression.as_deref());
+	let events = conditions
+		.events
+		.iter()
+		.filter_map(|condition| condition.expression.as_deref());
+	let transactions = conditions
+		.transactions
+		.iter()
+		.filter_map(|condition| condition.expression.as_deref());
+	let aggregates = conditions.aggregates.iter().flat_map(|aggregate| {
+		aggregate
+			.filter
+			.as_deref()
+			.into_iter()
+			.chain([aggregate.expression.as_str()])
+	});
+	let window = monitor
+		.window
+		.iter()
+		.map(|window| window.expression.as_str());
+	let state = monitor
+		.state
+		.iter()
+		.filter_map(|state| state.expression.as_deref());
+	let poll = monitor.poll.iter().map(|poll| poll.expression.as_str());
+
+	functions
+		.chain(events)
+		.chain(transactions)
+		.chain(aggregates)
+		.chain(window)
+		.chain(state)
+		.chain(poll)
+}
+
+/// Collects the event and function argument names of the ABIs of an EVM monitor
+///
+/// Returns `None` when any monitored address has no EVM ABI, or an ABI has unnamed inputs, since
+/// the available arguments cannot be known.
+fn evm_abi_argument_names(monitor: &Monitor) -> Option<HashSet<(String, String)>> {
+	if monitor.addresses.is_empty() {
+		return None;
+	}
+
+	let mut names = HashSet::new();
+	for address in &monitor.addresses {
+		let Some(ContractSpec::EVM(spec)) = &address.contract_spec else {
+			return None;
+		};
+		let events = spec.events().flat_map(|event| {
+			event
+				.inputs
+				.iter()
+				.map(|input| ("events", input.name.clone()))
+		});
+		let functions = spec.functions().flat_map(|function| {
+			function
+				.inputs
+				.iter()
+				.map(|input| ("functions", input.name.clone()))
+		});
+		for (collection, name) in events.chain(functions) {
+			if name.is_empty() {
+				return None;
+			}
+			names.insert((collection.to_string(), name));
+		}
+	}
+	Some(names)
 }
 
 /// Interface for monitor repository implementations
@@ -280,6 +475,7 @@ impl<
 		};
 
 		Self::validate_monitor_references(&monitors, &triggers, &networks)?;
+		Self::validate_address_set_references(&monitors, &Self::load_address_sets(path).await?)?;
 		Ok(monitors)
 	}
 
@@ -316,6 +512,10 @@ impl<
 				};
 				let monitors = HashMap::from([(monitor.name.clone(), monitor)]);
 				Self::validate_monitor_references(&monitors, &triggers, &networks)?;
+				Self::validate_address_set_references(
+					&monitors,
+					&Self::load_address_sets(path.parent()).await?,
+				)?;
 				match monitors.values().next() {
 					Some(monitor) => Ok(monitor.clone()),
 					None => Err(RepositoryError::load_error("No monitors found", None, None)),
@@ -434,7 +634,14 @@ impl<
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::{models::ScriptLanguage, utils::tests::builders::evm::monitor::MonitorBuilder};
+	use crate::{
+		models::{
+			AggregateCondition, AggregateFunction, EVMContractSpec, EventMatchMode, ScriptLanguage,
+		},
+		utils::tests::builders::{
+			evm::monitor::MonitorBuilder, network::NetworkBuilder, trigger::TriggerBuilder,
+		},
+	};
 	use std::fs;
 	use tempfile::TempDir;
 
@@ -577,6 +784,68 @@ mod tests {
 		assert!(err.to_string().contains("references non-existent network"));
 	}
 
+	#[test]
+	fn test_event_correlation_requires_evm_network() {
+		let networks = HashMap::from([
+			(
+				"ethereum_mainnet".to_string(),
+				NetworkBuilder::new()
+					.slug("ethereum_mainnet")
+					.network_type(BlockChainType::EVM)
+					.build(),
+			),
+			(
+				"stellar_mainnet".to_string(),
+				NetworkBuilder::new()
+					.slug("stellar_mainnet")
+					.network_type(BlockChainType::Stellar)
+					.build(),
+			),
+		]);
+		let validate = |monitor: Monitor| {
+			let monitors = HashMap::from([(monitor.name.clone(), monitor)]);
+			MonitorRepository::<NetworkRepository, TriggerRepository>::validate_monitor_references(
+				&monitors,
+				&HashMap::new(),
+				&networks,
+			)
+		};
+		let aggregate = AggregateCondition {
+			function: AggregateFunction::Count,
+			signature: "Transfer(address,address,uint256)".to_string(),
+			field: None,
+			filter: None,
+			expression: "result > 1".to_string(),
+		};
+
+		let evm_monitor = MonitorBuilder::new()
+			.networks(vec!["ethereum_mainnet".to_string()])
+			.match_mode(EventMatchMode::All)
+			.aggregate(aggregate.clone())
+			.build();
+		assert!(validate(evm_monitor).is_ok());
+
+		let stellar_monitors = [
+			MonitorBuilder::new()
+				.networks(vec!["stellar_mainnet".to_string()])
+				.match_mode(EventMatchMode::Sequence)
+				.build(),
+			MonitorBuilder::new()
+				.networks(vec!["stellar_mainnet".to_string()])
+				.aggregate(aggregate)
+				.build(),
+		];
+		for monitor in stellar_monitors {
+			let err = validate(monitor).unwrap_err();
+			assert!(err.to_string().contains("only supported on EVM networks"));
+		}
+
+		let stellar_monitor = MonitorBuilder::new()
+			.networks(vec!["stellar_mainnet".to_string()])
+			.build();
+		assert!(validate(stellar_monitor).is_ok());
+	}
+
 	#[test]
 	fn test_trigger_validation_error() {
 		// Create a monitor with a reference to a non-existent trigger
@@ -602,6 +871,134 @@ mod tests {
 		assert!(err.to_string().contains("references non-existent trigger"));
 	}
 
+	#[test]
+	fn test_address_set_reference_validation() {
+		let monitor = MonitorBuilder::new()
+			.name("test_monitor")
+			.networks(vec![])
+			.event(
+				"Transfer(address,address,uint256)",
+				Some("to in @hot_wallets AND from not in @exchanges".to_string()),
+			)
+			.build();
+		let monitors = HashMap::from([("test_monitor".to_string(), monitor)]);
+		let address_sets = HashMap::from([(
+			"hot_wallets".to_string(),
+			AddressSet {
+				name: "hot_wallets".to_string(),
+				addresses: vec!["0x0000000000000000000000000000000000000001".to_string()],
+			},
+		)]);
+
+		let err = MonitorRepository::<NetworkRepository, TriggerRepository>::validate_address_set_references(
+			&monitors,
+			&address_sets,
+		)
+		.unwrap_err();
+		assert!(err
+			.to_string()
+			.contains("references non-existent address set '@exchanges'"));
+		assert!(!err.to_string().contains("@hot_wallets"));
+
+		let address_sets = HashMap::from([
+			(
+				"hot_wallets".to_string(),
+				address_sets["hot_wallets"].clone(),
+			),
+			(
+				"exchanges".to_string(),
+				AddressSet {
+					name: "exchanges".to_string(),
+					addresses: vec![],
+				},
+			),
+		]);
+		assert!(
+			MonitorRepository::<NetworkRepository, TriggerRepository>::validate_address_set_references(
+				&monitors,
+				&address_sets,
+			)
+			.is_ok()
+		);
+	}
+
+	#[tokio::test]
+	async fn test_address_sets_resolved_next_to_monitor_directory() {
+		let temp_dir = TempDir::new().unwrap();
+		let monitor_dir = temp_dir.path().join("monitors");
+		let address_set_dir = temp_dir.path().join("address_sets");
+		fs::create_dir_all(&monitor_dir).unwrap();
+		fs::create_dir_all(&address_set_dir).unwrap();
+		fs::write(
+			address_set_dir.join("custom_wallets.json"),
+			r#"{"name": "custom_wallets", "addresses": []}"#,
+		)
+		.unwrap();
+
+		let address_sets =
+			MonitorRepository::<NetworkRepository, TriggerRepository>::load_address_sets(Some(
+				&monitor_dir,
+			))
+			.await
+			.unwrap();
+		assert_eq!(address_sets.len(), 1);
+		assert_eq!(address_sets["custom_wallets"].name, "custom_wallets");
+	}
+
+	#[test]
+	fn test_trigger_template_argument_validation() {
+		let abi = serde_json::json!([{
+			"type": "event",
+			"name": "Transfer",
+			"anonymous": false,
+			"inputs": [
+				{"name": "from", "type": "address", "indexed": true},
+				{"name": "to", "type": "address", "indexed": true},
+				{"name": "value", "type": "uint256", "indexed": false}
+			]
+		}]);
+		let monitor = MonitorBuilder::new()
+			.name("test_monitor")
+			.networks(vec![])
+			.address_with_spec(
+				"0x0000000000000000000000000000000000000001",
+				Some(ContractSpec::EVM(EVMContractSpec::from(abi))),
+			)
+			.triggers(vec!["test_trigger".to_string()])
+			.build();
+		let monitors = HashMap::from([("test_monitor".to_string(), monitor)]);
+		let networks = HashMap::new();
+
+		let create_triggers = |body: &str| {
+			HashMap::from([(
+				"test_trigger".to_string(),
+				TriggerBuilder::new()
+					.name("test_trigger")
+					.message("Alert", body)
+					.build(),
+			)])
+		};
+
+		let triggers = create_triggers(
+			"{% for event in events %}${event.args.value} to ${event.args.to}{% endfor %}",
+		);
+		let result =
+			MonitorRepository::<NetworkRepository, TriggerRepository>::validate_monitor_references(
+				&monitors, &triggers, &networks,
+			);
+		assert!(result.is_ok());
+
+		let triggers = create_triggers("${events.0.args.vaule}");
+		let err =
+			MonitorRepository::<NetworkRepository, TriggerRepository>::validate_monitor_references(
+				&monitors, &triggers, &networks,
+			)
+			.unwrap_err();
+		assert!(err
+			.to_string()
+			.contains("references unknown events argument 'vaule'"));
+	}
+
 	#[tokio::test]
 	async fn test_load_from_path_error_handling() {
 		// Create a temporary directory for testing
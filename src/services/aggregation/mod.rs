@@ -0,0 +1,11 @@
+//! Sliding window aggregation of monitor matches.
+//!
+//! This module provides a stateful stage that runs between block filtering, filter plugins
+//! included, and trigger execution. Monitors configured with a `window` only have their matches
+//! forwarded once an aggregate (count, sum, min or max) over a sliding time window satisfies
+//! their expression.
+//! Window state is persisted through the block storage so that it survives restarts.
+
+mod service;
+
+pub use service::{record_window_match, WindowAggregationService};
@@ -0,0 +1,397 @@
+//! Window aggregation service implementation.
+//!
+//! Matches of windowed monitors are recorded as entries keyed by the monitor's `group_by`
+//! variables. Entries older than the window are evicted relative to the most recent block
+//! timestamp, and when the aggregate for a key satisfies the window expression the match is
+//! forwarded and the entries for that key are cleared, so each alert starts a fresh window.
+
+use std::{
+	collections::{hash_map::Entry, HashMap},
+	sync::Arc,
+};
+use tokio::sync::Mutex;
+
+use crate::{
+	models::{Monitor, MonitorMatch, WindowAggregation, WindowEntry, WindowState},
+	services::{
+		blockwatcher::BlockStorage,
+		filter::{
+			aggregate, build_match_variables, evaluate_aggregate_expression, EvaluationError,
+			NumericValue,
+		},
+	},
+};
+
+/// Service applying sliding window aggregation to monitor matches
+pub struct WindowAggregationService<S: BlockStorage> {
+	/// Storage used to persist window state
+	storage: Arc<S>,
+	/// Serializes updates so that concurrently processed blocks do not overwrite each other's
+	/// window state
+	lock: Mutex<()>,
+}
+
+impl<S: BlockStorage> WindowAggregationService<S> {
+	/// Creates a new window aggregation service
+	///
+	/// # Arguments
+	/// * `storage` - Storage used to persist window state
+	pub fn new(storage: Arc<S>) -> Self {
+		Self {
+			storage,
+			lock: Mutex::new(()),
+		}
+	}
+
+	/// Runs the matches of a block through the windows of their monitors
+	///
+	/// Matches of monitors without a window are returned unchanged. Matches of windowed monitors
+	/// are recorded and only returned when they bring the window aggregate to satisfy its
+	/// expression.
+	///
+	/// # Arguments
+	/// * `network_slug` - Network the block belongs to
+	/// * `timestamp` - Block timestamp in seconds since the Unix epoch
+	/// * `matches` - Matches found in the block
+	///
+	/// # Returns
+	/// Matches that should be forwarded to triggers
+	pub async fn process_matches(
+		&self,
+		network_slug: &str,
+		timestamp: u64,
+		matches: Vec<MonitorMatch>,
+	) -> Vec<MonitorMatch> {
+		if matches.iter().all(|m| monitor_of(m).window.is_none()) {
+			return matches;
+		}
+
+		let _guard = self.lock.lock().await;
+		let mut states: HashMap<String, WindowState> = HashMap::new();
+		let mut forwarded = Vec::new();
+
+		for monitor_match in matches {
+			let monitor = monitor_of(&monitor_match);
+			let Some(window) = &monitor.window else {
+				forwarded.push(monitor_match);
+				continue;
+			};
+
+			let state = match states.entry(monitor.name.clone()) {
+				Entry::Occupied(entry) => entry.into_mut(),
+				Entry::Vacant(entry) => {
+					let state = self
+						.storage
+						.get_window_state(network_slug, &monitor.name)
+						.await
+						.unwrap_or_else(|e| {
+							tracing::error!(
+								"Failed to load window state for monitor '{}': {}",
+								monitor.name,
+								e
+							);
+							None
+						});
+					entry.insert(state.unwrap_or_default())
+				}
+			};
+
+			match record_window_match(window, state, &monitor_match, timestamp) {
+				Ok(true) => forwarded.push(monitor_match),
+				Ok(false) => {}
+				Err(e) => {
+					tracing::error!(
+						"Failed to aggregate match for monitor '{}': {}",
+						monitor.name,
+						e
+					);
+				}
+			}
+		}
+
+		for (monitor_name, state) in states {
+			if let Err(e) = self
+				.storage
+				.save_window_state(network_slug, &monitor_name, &state)
+				.await
+			{
+				tracing::error!(
+					"Failed to save window state for monitor '{}': {}",
+					monitor_name,
+					e
+				);
+			}
+		}
+
+		forwarded
+	}
+}
+
+/// Records a match in a sliding window and checks whether the window fires
+///
+/// # Arguments
+/// * `window` - Window configuration of the monitor
+/// * `state` - Current window state, updated in place
+/// * `monitor_match` - Match to record
+/// * `timestamp` - Block timestamp of the match in seconds since the Unix epoch
+///
+/// # Returns
+/// `true` if the aggregate for the match's key satisfies the window expression, in which case
+/// the entries for that key are cleared
+pub fn record_window_match(
+	window: &WindowAggregation,
+	state: &mut WindowState,
+	monitor_match: &MonitorMatch,
+	timestamp: u64,
+) -> Result<bool, EvaluationError> {
+	let variables = build_match_variables(monitor_match);
+	let key = window
+		.group_by
+		.iter()
+		.map(|name| variables.get(name).map(String::as_str).unwrap_or_default())
+		.collect::<Vec<_>>()
+		.join("|");
+
+	let value = match &window.field {
+		Some(field) if window.function.requires_field() => {
+			let value = variables.get(field).ok_or_else(|| {
+				EvaluationError::variable_not_found(
+					format!("Window field '{}' not found in match", field),
+					None,
+					None,
+				)
+			})?;
+			// Validate the value before it is recorded
+			NumericValue::parse(value)?;
+			value.clone()
+		}
+		_ => String::new(),
+	};
+
+	state.entries.push(WindowEntry {
+		key: key.clone(),
+		timestamp,
+		value,
+	});
+
+	// Evict entries that fell out of the window, relative to the most recent block seen
+	let latest = state
+		.entries
+		.iter()
+		.map(|entry| entry.timestamp)
+		.max()
+		.unwrap_or(timestamp);
+	let cutoff = latest.saturating_sub(window.window_secs);
+	state.entries.retain(|entry| entry.timestamp > cutoff);
+
+	let result = aggregate(
+		window.function,
+		state.entries.iter().filter(|entry| entry.key == key),
+		|entry| NumericValue::parse(&entry.value),
+	)?;
+	let fired = match result {
+		Some(result) => evaluate_aggregate_expression(&window.expression, &result)?,
+		None => false,
+	};
+
+	if fired {
+		state.entries.retain(|entry| entry.key != key);
+	}
+
+	Ok(fired)
+}
+
+/// Returns the monitor that produced a match
+fn monitor_of(monitor_match: &MonitorMatch) -> &Monitor {
+	match monitor_match {
+		MonitorMatch::EVM(evm_match) => &evm_match.monitor,
+		MonitorMatch::Stellar(stellar_match) => &stellar_match.monitor,
+		MonitorMatch::Poll(poll_match) => &poll_match.monitor,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		models::{
+			AggregateFunction, EVMMatchArguments, EVMMatchParamEntry, EVMMatchParamsMap,
+			EVMMonitorMatch, EventCondition, MatchConditions,
+		},
+		services::blockwatcher::FileBlockStorage,
+		utils::tests::evm::{monitor::MonitorBuilder, transaction::TransactionBuilder},
+	};
+
+	const TRANSFER: &str = "Transfer(address,address,uint256)";
+
+	fn create_test_window(
+		function: AggregateFunction,
+		field: Option<&str>,
+		group_by: &[&str],
+		expression: &str,
+	) -> WindowAggregation {
+		WindowAggregation {
+			function,
+			field: field.map(str::to_string),
+			group_by: group_by.iter().map(|v| v.to_string()).collect(),
+			window_secs: 600,
+			expression: expression.to_string(),
+		}
+	}
+
+	fn create_test_match(
+		window: Option<WindowAggregation>,
+		from: &str,
+		value: &str,
+	) -> MonitorMatch {
+		let mut builder = MonitorBuilder::new().name("windowed");
+		if let Some(window) = window {
+			builder = builder.window(window);
+		}
+
+		MonitorMatch::EVM(Box::new(EVMMonitorMatch {
+			monitor: builder.build(),
+			transaction: TransactionBuilder::new().build(),
+			receipt: None,
+			logs: None,
+			network_slug: "ethereum_mainnet".to_string(),
+			matched_on: MatchConditions {
+				events: vec![EventCondition {
+					signature: TRANSFER.to_string(),
+					expression: None,
+				}],
+				..Default::default()
+			},
+			matched_on_args: Some(EVMMatchArguments {
+				events: Some(vec![EVMMatchParamsMap {
+					signature: TRANSFER.to_string(),
+					args: Some(vec![
+						EVMMatchParamEntry {
+							name: "from".to_string(),
+							value: from.to_string(),
+							kind: "address".to_string(),
+							indexed: true,
+						},
+						EVMMatchParamEntry {
+							name: "value".to_string(),
+							value: value.to_string(),
+							kind: "uint256".to_string(),
+							indexed: false,
+						},
+					]),
+					hex_signature: None,
+					log_index: None,
+				}]),
+				functions: None,
+			}),
+			enrichment: None,
+		}))
+	}
+
+	#[test]
+	fn test_record_window_match_count() {
+		let window = create_test_window(AggregateFunction::Count, None, &[], "result >= 3");
+		let mut state = WindowState::default();
+		let monitor_match = create_test_match(None, "0x01", "1");
+
+		assert!(!record_window_match(&window, &mut state, &monitor_match, 100).unwrap());
+		assert!(!record_window_match(&window, &mut state, &monitor_match, 200).unwrap());
+		assert!(record_window_match(&window, &mut state, &monitor_match, 300).unwrap());
+
+		// Firing resets the window
+		assert!(state.entries.is_empty());
+		assert!(!record_window_match(&window, &mut state, &monitor_match, 400).unwrap());
+	}
+
+	#[test]
+	fn test_record_window_match_evicts_old_entries() {
+		let window = create_test_window(AggregateFunction::Count, None, &[], "result >= 2");
+		let mut state = WindowState::default();
+		let monitor_match = create_test_match(None, "0x01", "1");
+
+		assert!(!record_window_match(&window, &mut state, &monitor_match, 100).unwrap());
+		// More than 600 seconds later, the first match is no longer in the window
+		assert!(!record_window_match(&window, &mut state, &monitor_match, 800).unwrap());
+		assert_eq!(state.entries.len(), 1);
+		assert!(record_window_match(&window, &mut state, &monitor_match, 900).unwrap());
+	}
+
+	#[test]
+	fn test_record_window_match_sum_grouped() {
+		let window = create_test_window(
+			AggregateFunction::Sum,
+			Some("events.0.args.value"),
+			&["events.0.args.from"],
+			"result > 1000",
+		);
+		let mut state = WindowState::default();
+
+		let first = create_test_match(None, "0x01", "600");
+		let other_sender = create_test_match(None, "0x02", "600");
+		assert!(!record_window_match(&window, &mut state, &first, 100).unwrap());
+		assert!(!record_window_match(&window, &mut state, &other_sender, 110).unwrap());
+		assert!(record_window_match(&window, &mut state, &first, 120).unwrap());
+
+		// Only the entries of the group that fired are cleared
+		assert_eq!(state.entries.len(), 1);
+		assert_eq!(state.entries[0].key, "0x02");
+	}
+
+	#[test]
+	fn test_record_window_match_invalid_field() {
+		let mut state = WindowState::default();
+		let monitor_match = create_test_match(None, "0x01", "1");
+
+		let missing = create_test_window(
+			AggregateFunction::Sum,
+			Some("events.0.args.amount"),
+			&[],
+			"result > 0",
+		);
+		assert!(record_window_match(&missing, &mut state, &monitor_match, 100).is_err());
+
+		let non_numeric = create_test_window(
+			AggregateFunction::Sum,
+			Some("events.0.signature"),
+			&[],
+			"result > 0",
+		);
+		assert!(record_window_match(&non_numeric, &mut state, &monitor_match, 100).is_err());
+		assert!(state.entries.is_empty());
+	}
+
+	#[tokio::test]
+	async fn test_process_matches_persists_state() {
+		let temp_dir = tempfile::tempdir().unwrap();
+		let storage = Arc::new(FileBlockStorage::new(temp_dir.path().to_path_buf()));
+		let window = create_test_window(AggregateFunction::Count, None, &[], "result >= 2");
+
+		let service = WindowAggregationService::new(storage.clone());
+		let unwindowed = create_test_match(None, "0x01", "1");
+		let windowed = create_test_match(Some(window), "0x01", "1");
+
+		let forwarded = service
+			.process_matches(
+				"ethereum_mainnet",
+				100,
+				vec![unwindowed.clone(), windowed.clone()],
+			)
+			.await;
+		assert_eq!(forwarded.len(), 1);
+		assert!(monitor_of(&forwarded[0]).window.is_none());
+
+		// A new service instance continues from the persisted state
+		let service = WindowAggregationService::new(storage.clone());
+		let forwarded = service
+			.process_matches("ethereum_mainnet", 200, vec![windowed])
+			.await;
+		assert_eq!(forwarded.len(), 1);
+
+		let state = storage
+			.get_window_state("ethereum_mainnet", "windowed")
+			.await
+			.unwrap()
+			.unwrap();
+		assert!(state.entries.is_empty());
+	}
+}
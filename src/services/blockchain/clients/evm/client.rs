@@ -6,6 +6,7 @@
 
 use std::marker::PhantomData;
 
+use alloy::primitives::U256;
 use anyhow::Context;
 use async_trait::async_trait;
 use futures;
@@ -92,6 +93,34 @@ pub trait EvmClientTrait {
 		to_block: u64,
 		addresses: Option<Vec<String>>,
 	) -> Result<Vec<EVMReceiptLog>, anyhow::Error>;
+
+	/// Executes a read-only contract call
+	///
+	/// # Arguments
+	/// * `address` - Address of the contract
+	/// * `data` - Hex-encoded call data (function selector followed by the encoded arguments)
+	/// * `block_number` - Block to execute the call at, or the latest block if `None`
+	/// # Returns
+	/// * `Result<String, anyhow::Error>` - Hex-encoded return data or error
+	async fn call_contract(
+		&self,
+		address: &str,
+		data: &str,
+		block_number: Option<u64>,
+	) -> Result<String, anyhow::Error>;
+
+	/// Retrieves the native balance of an account
+	///
+	/// # Arguments
+	/// * `address` - Address of the account
+	/// * `block_number` - Block to read the balance at, or the latest block if `None`
+	/// # Returns
+	/// * `Result<U256, anyhow::Error>` - Balance in wei or error
+	async fn get_balance(
+		&self,
+		address: &str,
+		block_number: Option<u64>,
+	) -> Result<U256, anyhow::Error>;
 }
 
 #[async_trait]
@@ -177,6 +206,74 @@ impl<T: Send + Sync + Clone + BlockchainTransport> EvmClientTrait for EvmClient<
 		// Parse the response into the expected type
 		Ok(serde_json::from_value(logs_data.clone()).with_context(|| "Failed to parse logs")?)
 	}
+
+	/// Executes a read-only contract call with `eth_call`
+	#[instrument(skip(self, data), fields(address, block_number))]
+	async fn call_contract(
+		&self,
+		address: &str,
+		data: &str,
+		block_number: Option<u64>,
+	) -> Result<String, anyhow::Error> {
+		let block = match block_number {
+			Some(block_number) => format!("0x{:x}", block_number),
+			None => "latest".to_string(),
+		};
+		let params = json!([{ "to": address, "data": data }, block])
+			.as_array()
+			.with_context(|| "Failed to create JSON-RPC params array")?
+			.to_vec();
+
+		let response = self
+			.http_client
+			.send_raw_request("eth_call", Some(params))
+			.await
+			.with_context(|| format!("Failed to call contract: {}", address))?;
+
+		// Reverted calls are reported in the "error" field of the JSON-RPC response
+		if let Some(error) = response.get("error") {
+			return Err(anyhow::anyhow!("Contract call failed: {}", error));
+		}
+
+		// Extract the "result" field from the JSON-RPC response
+		response
+			.get("result")
+			.and_then(|result| result.as_str())
+			.map(|result| result.to_string())
+			.with_context(|| "Missing 'result' field")
+	}
+
+	/// Retrieves the native balance of an account with `eth_getBalance`
+	#[instrument(skip(self), fields(address, block_number))]
+	async fn get_balance(
+		&self,
+		address: &str,
+		block_number: Option<u64>,
+	) -> Result<U256, anyhow::Error> {
+		let block = match block_number {
+			Some(block_number) => format!("0x{:x}", block_number),
+			None => "latest".to_string(),
+		};
+		let params = json!([address, block])
+			.as_array()
+			.with_context(|| "Failed to create JSON-RPC params array")?
+			.to_vec();
+
+		let response = self
+			.http_client
+			.send_raw_request("eth_getBalance", Some(params))
+			.await
+			.with_context(|| format!("Failed to get balance: {}", address))?;
+
+		// Extract the "result" field from the JSON-RPC response
+		let balance = response
+			.get("result")
+			.and_then(|result| result.as_str())
+			.with_context(|| "Missing 'result' field")?;
+
+		U256::from_str_radix(balance.trim_start_matches("0x"), 16)
+			.with_context(|| format!("Invalid balance: {}", balance))
+	}
 }
 
 #[async_trait]
@@ -23,6 +23,7 @@ use crate::{
 		},
 		filter::{
 			stellar_helpers::{
+				get_account_ledger_key, get_balance_from_ledger_entry_data,
 				get_contract_code_ledger_key, get_contract_instance_ledger_key, get_contract_spec,
 				get_wasm_code_from_ledger_entry_data, get_wasm_hash_from_ledger_entry_data,
 			},
@@ -170,6 +171,15 @@ pub trait StellarClientTrait {
 		start_sequence: u32,
 		end_sequence: Option<u32>,
 	) -> Result<Vec<StellarEvent>, anyhow::Error>;
+
+	/// Retrieves the native balance of an account at the latest ledger
+	///
+	/// # Arguments
+	/// * `account_id` - The account ID in Stellar strkey format (starts with 'G')
+	///
+	/// # Returns
+	/// * `Result<i64, anyhow::Error>` - Balance in stroops or error
+	async fn get_account_balance(&self, account_id: &str) -> Result<i64, anyhow::Error>;
 }
 
 #[async_trait]
@@ -453,6 +463,36 @@ impl<T: Send + Sync + Clone + BlockchainTransport> StellarClientTrait for Stella
 		}
 		Ok(events)
 	}
+
+	/// Retrieves the native balance of an account from its ledger entry
+	#[instrument(skip(self), fields(account_id))]
+	async fn get_account_balance(&self, account_id: &str) -> Result<i64, anyhow::Error> {
+		let account_ledger_key_xdr = get_account_ledger_key(account_id)
+			.map_err(|e| anyhow::anyhow!("Failed to get account ledger key: {}", e))?
+			.to_xdr_base64(Limits::none())
+			.map_err(|e| anyhow::anyhow!("Failed to convert account ledger key to XDR: {}", e))?;
+
+		let params = json!({
+			"keys": [account_ledger_key_xdr],
+			"xdrFormat": "base64"
+		});
+
+		let response = self
+			.http_client
+			.send_raw_request(RPC_METHOD_GET_LEDGER_ENTRIES, Some(params))
+			.await
+			.with_context(|| format!("Failed to get account entry for {}", account_id))?;
+
+		let account_xdr_base64 = match response["result"]["entries"][0]["xdr"].as_str() {
+			Some(xdr) => xdr,
+			None => {
+				return Err(anyhow::anyhow!("Failed to get account entry XDR"));
+			}
+		};
+
+		get_balance_from_ledger_entry_data(account_xdr_base64)
+			.map_err(|e| anyhow::anyhow!("Failed to get account balance: {}", e))
+	}
 }
 
 impl<T: Send + Sync + Clone + BlockchainTransport> BlockFilterFactory<Self> for StellarClient<T> {
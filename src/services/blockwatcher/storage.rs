@@ -5,12 +5,20 @@
 //! - File-based storage with JSON serialization
 //! - Last processed block tracking
 //! - Block deletion for cleanup
+//! - Sliding aggregation window state for monitors
+//! - Last poll state of polling monitors
 
 use async_trait::async_trait;
 use glob::glob;
 use std::path::PathBuf;
 
-use crate::models::BlockType;
+use crate::models::{BlockType, PollState, WindowState};
+
+/// Subdirectory of the aggregation window state files
+const WINDOWS_DIR: &str = "windows";
+
+/// Subdirectory of the poll state files
+const POLLS_DIR: &str = "polls";
 
 /// Interface for block storage implementations
 ///
@@ -76,6 +84,66 @@ pub trait BlockStorage: Clone + Send + Sync {
 	/// # Returns
 	/// * `Result<(), anyhow::Error>` - Success or error
 	async fn save_missed_block(&self, network_id: &str, block: u64) -> Result<(), anyhow::Error>;
+
+	/// Retrieves the sliding aggregation window state of a monitor on a network
+	///
+	/// # Arguments
+	/// * `network_id` - Unique identifier for the network
+	/// * `monitor_name` - Name of the monitor owning the window
+	///
+	/// # Returns
+	/// * `Result<Option<WindowState>, anyhow::Error>` - Window state or None if not found
+	async fn get_window_state(
+		&self,
+		network_id: &str,
+		monitor_name: &str,
+	) -> Result<Option<WindowState>, anyhow::Error>;
+
+	/// Saves the sliding aggregation window state of a monitor on a network
+	///
+	/// # Arguments
+	/// * `network_id` - Unique identifier for the network
+	/// * `monitor_name` - Name of the monitor owning the window
+	/// * `state` - Window state to save
+	///
+	/// # Returns
+	/// * `Result<(), anyhow::Error>` - Success or error
+	async fn save_window_state(
+		&self,
+		network_id: &str,
+		monitor_name: &str,
+		state: &WindowState,
+	) -> Result<(), anyhow::Error>;
+
+	/// Retrieves the last poll state of a monitor on a network
+	///
+	/// # Arguments
+	/// * `network_id` - Unique identifier for the network
+	/// * `monitor_name` - Name of the polling monitor
+	///
+	/// # Returns
+	/// * `Result<Option<PollState>, anyhow::Error>` - Poll state or None if not found
+	async fn get_poll_state(
+		&self,
+		network_id: &str,
+		monitor_name: &str,
+	) -> Result<Option<PollState>, anyhow::Error>;
+
+	/// Saves the last poll state of a monitor on a network
+	///
+	/// # Arguments
+	/// * `network_id` - Unique identifier for the network
+	/// * `monitor_name` - Name of the polling monitor
+	/// * `state` - Poll state to save
+	///
+	/// # Returns
+	/// * `Result<(), anyhow::Error>` - Success or error
+	async fn save_poll_state(
+		&self,
+		network_id: &str,
+		monitor_name: &str,
+		state: &PollState,
+	) -> Result<(), anyhow::Error>;
 }
 
 /// File-based implementation of block storage
@@ -95,6 +163,23 @@ impl FileBlockStorage {
 	pub fn new(storage_path: PathBuf) -> Self {
 		FileBlockStorage { storage_path }
 	}
+
+	/// Returns the path of a monitor state file on a network
+	///
+	/// Monitor state is kept in a subdirectory per kind of state and network, apart from block
+	/// files. The monitor name is hex encoded, so that every name maps to a distinct and valid
+	/// file name.
+	///
+	/// # Arguments
+	/// * `kind` - Subdirectory of the kind of state
+	/// * `network_id` - Unique identifier for the network
+	/// * `monitor_name` - Name of the monitor owning the state
+	fn monitor_state_path(&self, kind: &str, network_id: &str, monitor_name: &str) -> PathBuf {
+		self.storage_path
+			.join(kind)
+			.join(network_id)
+			.join(format!("{}.json", hex::encode(monitor_name.as_bytes())))
+	}
 }
 
 impl Default for FileBlockStorage {
@@ -225,6 +310,97 @@ impl BlockStorage for FileBlockStorage {
 
 		Ok(())
 	}
+
+	/// Retrieves the window state from a monitor-specific JSON file
+	///
+	/// The file is named "windows/{network_id}/{hex encoded monitor_name}.json"
+	async fn get_window_state(
+		&self,
+		network_id: &str,
+		monitor_name: &str,
+	) -> Result<Option<WindowState>, anyhow::Error> {
+		let file_path = self.monitor_state_path(WINDOWS_DIR, network_id, monitor_name);
+
+		if !file_path.exists() {
+			return Ok(None);
+		}
+
+		let content = tokio::fs::read_to_string(file_path)
+			.await
+			.map_err(|e| anyhow::anyhow!("Failed to read window state: {}", e))?;
+		let state = serde_json::from_str(&content)
+			.map_err(|e| anyhow::anyhow!("Failed to parse window state: {}", e))?;
+		Ok(Some(state))
+	}
+
+	/// Saves the window state to a monitor-specific JSON file
+	///
+	/// # Note
+	/// Overwrites any existing window state file for the monitor
+	async fn save_window_state(
+		&self,
+		network_id: &str,
+		monitor_name: &str,
+		state: &WindowState,
+	) -> Result<(), anyhow::Error> {
+		let file_path = self.monitor_state_path(WINDOWS_DIR, network_id, monitor_name);
+		let json = serde_json::to_string(state)
+			.map_err(|e| anyhow::anyhow!("Failed to serialize window state: {}", e))?;
+		if let Some(parent) = file_path.parent() {
+			tokio::fs::create_dir_all(parent)
+				.await
+				.map_err(|e| anyhow::anyhow!("Failed to save window state: {}", e))?;
+		}
+		tokio::fs::write(file_path, json)
+			.await
+			.map_err(|e| anyhow::anyhow!("Failed to save window state: {}", e))?;
+		Ok(())
+	}
+	/// Retrieves the poll state from a monitor-specific JSON file
+	///
+	/// The file is named "polls/{network_id}/{hex encoded monitor_name}.json"
+	async fn get_poll_state(
+		&self,
+		network_id: &str,
+		monitor_name: &str,
+	) -> Result<Option<PollState>, anyhow::Error> {
+		let file_path = self.monitor_state_path(POLLS_DIR, network_id, monitor_name);
+
+		if !file_path.exists() {
+			return Ok(None);
+		}
+
+		let content = tokio::fs::read_to_string(file_path)
+			.await
+			.map_err(|e| anyhow::anyhow!("Failed to read poll state: {}", e))?;
+		let state = serde_json::from_str(&content)
+			.map_err(|e| anyhow::anyhow!("Failed to parse poll state: {}", e))?;
+		Ok(Some(state))
+	}
+
+	/// Saves the poll state to a monitor-specific JSON file
+	///
+	/// # Note
+	/// Overwrites any existing poll state file for the monitor
+	async fn save_poll_state(
+		&self,
+		network_id: &str,
+		monitor_name: &str,
+		state: &PollState,
+	) -> Result<(), anyhow::Error> {
+		let file_path = self.monitor_state_path(POLLS_DIR, network_id, monitor_name);
+		let json = serde_json::to_string(state)
+			.map_err(|e| anyhow::anyhow!("Failed to serialize poll state: {}", e))?;
+		if let Some(parent) = file_path.parent() {
+			tokio::fs::create_dir_all(parent)
+				.await
+				.map_err(|e| anyhow::anyhow!("Failed to save poll state: {}", e))?;
+		}
+		tokio::fs::write(file_path, json)
+			.await
+			.map_err(|e| anyhow::anyhow!("Failed to save poll state: {}", e))?;
+		Ok(())
+	}
 }
 
 #[cfg(test)]
@@ -414,4 +590,80 @@ mod tests {
 			assert!(err.to_string().contains("Permission denied"));
 		}
 	}
+
+	#[tokio::test]
+	async fn test_window_state() {
+		let temp_dir = tempfile::tempdir().unwrap();
+		let storage = FileBlockStorage::new(temp_dir.path().to_path_buf());
+
+		// Test 1: No state saved yet
+		let result = storage.get_window_state("test", "Large Transfers").await;
+		assert!(result.unwrap().is_none());
+
+		// Test 2: Save and read back
+		let state = WindowState {
+			entries: vec![crate::models::WindowEntry {
+				key: "0x01".to_string(),
+				timestamp: 1_700_000_000,
+				value: "100".to_string(),
+			}],
+		};
+		storage
+			.save_window_state("test", "Large Transfers", &state)
+			.await
+			.unwrap();
+		let result = storage.get_window_state("test", "Large Transfers").await;
+		assert_eq!(result.unwrap(), Some(state.clone()));
+
+		// Test 3: Names differing only in unsafe characters do not collide
+		let result = storage.get_window_state("test", "Large/Transfers").await;
+		assert!(result.unwrap().is_none());
+
+		// Test 4: Block cleanup keeps window state, even of monitors named like block files
+		storage
+			.save_window_state("test", "blocks_1", &state)
+			.await
+			.unwrap();
+		storage.delete_blocks("test").await.unwrap();
+		let result = storage.get_window_state("test", "blocks_1").await;
+		assert_eq!(result.unwrap(), Some(state));
+
+		// Test 5: Invalid state content
+		tokio::fs::write(
+			storage.monitor_state_path(WINDOWS_DIR, "test", "invalid"),
+			"invalid",
+		)
+		.await
+		.unwrap();
+		let result = storage.get_window_state("test", "invalid").await;
+		assert!(result
+			.unwrap_err()
+			.to_string()
+			.contains("Failed to parse window state"));
+	}
+
+	#[tokio::test]
+	async fn test_poll_state() {
+		let temp_dir = tempfile::tempdir().unwrap();
+		let storage = FileBlockStorage::new(temp_dir.path().to_path_buf());
+
+		let result = storage.get_poll_state("test", "Treasury Balance").await;
+		assert!(result.unwrap().is_none());
+
+		let state = PollState {
+			block_number: 100,
+			timestamp: Some(1_700_000_000),
+			values: std::collections::HashMap::from([("balance".to_string(), "1000".to_string())]),
+		};
+		storage
+			.save_poll_state("test", "Treasury Balance", &state)
+			.await
+			.unwrap();
+		let result = storage.get_poll_state("test", "Treasury Balance").await;
+		assert_eq!(result.unwrap(), Some(state));
+
+		// Poll and window state of the same monitor are kept apart
+		let result = storage.get_window_state("test", "Treasury Balance").await;
+		assert!(result.unwrap().is_none());
+	}
 }
@@ -165,7 +165,10 @@ impl<S: BlockStorage> BlockTrackerTrait<S> for BlockTracker<S> {
 
 #[cfg(test)]
 mod tests {
-	use crate::{models::BlockType, utils::tests::network::NetworkBuilder};
+	use crate::{
+		models::{BlockType, PollState, WindowState},
+		utils::tests::network::NetworkBuilder,
+	};
 
 	use super::*;
 	use mockall::mock;
@@ -180,6 +183,10 @@ mod tests {
 			async fn get_last_processed_block(&self, network_slug: &str) -> Result<Option<u64>, anyhow::Error>;
 			async fn save_blocks(&self, network_slug: &str, blocks: &[BlockType]) -> Result<(), anyhow::Error>;
 			async fn delete_blocks(&self, network_slug: &str) -> Result<(), anyhow::Error>;
+			async fn get_window_state(&self, network_slug: &str, monitor_name: &str) -> Result<Option<WindowState>, anyhow::Error>;
+			async fn save_window_state(&self, network_slug: &str, monitor_name: &str, state: &WindowState) -> Result<(), anyhow::Error>;
+			async fn get_poll_state(&self, network_slug: &str, monitor_name: &str) -> Result<Option<PollState>, anyhow::Error>;
+			async fn save_poll_state(&self, network_slug: &str, monitor_name: &str, state: &PollState) -> Result<(), anyhow::Error>;
 		}
 
 		impl Clone for BlockStorage {
@@ -0,0 +1,185 @@
+//! Registry of named address sets used by filter expressions.
+//!
+//! Address sets are loaded once at startup and registered globally so that
+//! chain-specific condition evaluators can resolve `@name` references in
+//! expressions such as `to in @hot_wallets`. Entries are normalized ahead of
+//! time with each chain's address normalization, making lookups O(1) regardless
+//! of the set size.
+
+use lazy_static::lazy_static;
+use std::{
+	collections::{HashMap, HashSet},
+	sync::RwLock,
+};
+
+use crate::{
+	models::{AddressSet, BlockChainType},
+	services::filter::{evm_helpers, stellar_helpers},
+};
+
+lazy_static! {
+	static ref ADDRESS_SETS: RwLock<AddressSetRegistry> =
+		RwLock::new(AddressSetRegistry::default());
+}
+
+/// Addresses of a single set, pre-normalized for each supported chain
+#[derive(Debug, Default)]
+struct NormalizedAddressSet {
+	evm: HashSet<String>,
+	stellar: HashSet<String>,
+}
+
+/// Collection of named address sets keyed by set name
+#[derive(Debug, Default)]
+pub struct AddressSetRegistry {
+	sets: HashMap<String, NormalizedAddressSet>,
+}
+
+impl AddressSetRegistry {
+	/// Adds (or replaces) an address set in the registry
+	pub fn insert(&mut self, address_set: AddressSet) {
+		let normalized = NormalizedAddressSet {
+			evm: address_set
+				.addresses
+				.iter()
+				.map(|address| evm_helpers::normalize_address(address))
+				.collect(),
+			stellar: address_set
+				.addresses
+				.iter()
+				.map(|address| stellar_helpers::normalize_address(address))
+				.collect(),
+		};
+		self.sets.insert(address_set.name, normalized);
+	}
+
+	/// Checks whether an address belongs to the named set
+	///
+	/// # Arguments
+	/// * `set_name` - Name of the address set
+	/// * `chain` - Chain type whose address normalization should be applied
+	/// * `address` - Address to look up
+	///
+	/// # Returns
+	/// `None` if no set with the given name is registered, otherwise whether the
+	/// address is a member of the set
+	pub fn contains(&self, set_name: &str, chain: &BlockChainType, address: &str) -> Option<bool> {
+		let set = self.sets.get(set_name)?;
+		Some(match chain {
+			BlockChainType::EVM => set.evm.contains(&evm_helpers::normalize_address(address)),
+			BlockChainType::Stellar => set
+				.stellar
+				.contains(&stellar_helpers::normalize_address(address)),
+			BlockChainType::Midnight | BlockChainType::Solana => false,
+		})
+	}
+}
+
+/// Registers address sets globally, replacing any previously registered set with the same name
+pub fn register_address_sets(address_sets: impl IntoIterator<Item = AddressSet>) {
+	let mut registry = ADDRESS_SETS
+		.write()
+		.unwrap_or_else(|poisoned| poisoned.into_inner());
+	for address_set in address_sets {
+		registry.insert(address_set);
+	}
+}
+
+/// Checks whether an address belongs to a globally registered address set
+///
+/// Returns `None` if no set with the given name has been registered.
+pub fn address_set_contains(set_name: &str, chain: &BlockChainType, address: &str) -> Option<bool> {
+	ADDRESS_SETS
+		.read()
+		.unwrap_or_else(|poisoned| poisoned.into_inner())
+		.contains(set_name, chain, address)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn create_registry() -> AddressSetRegistry {
+		let mut registry = AddressSetRegistry::default();
+		registry.insert(AddressSet {
+			name: "wallets".to_string(),
+			addresses: vec![
+				"0xAbCdEf0000000000000000000000000000000001".to_string(),
+				"GCXKG6RN4ONIEPCMNFB732A436Z5PNDSRLGWK7GBLCMQLIFO4S7EYWVU".to_string(),
+			],
+		});
+		registry
+	}
+
+	#[test]
+	fn test_contains_evm_address_normalized() {
+		let registry = create_registry();
+		assert_eq!(
+			registry.contains(
+				"wallets",
+				&BlockChainType::EVM,
+				"0xabcdef0000000000000000000000000000000001"
+			),
+			Some(true)
+		);
+		assert_eq!(
+			registry.contains(
+				"wallets",
+				&BlockChainType::EVM,
+				"ABCDEF0000000000000000000000000000000001"
+			),
+			Some(true)
+		);
+		assert_eq!(
+			registry.contains(
+				"wallets",
+				&BlockChainType::EVM,
+				"0x0000000000000000000000000000000000000002"
+			),
+			Some(false)
+		);
+	}
+
+	#[test]
+	fn test_contains_stellar_address_normalized() {
+		let registry = create_registry();
+		assert_eq!(
+			registry.contains(
+				"wallets",
+				&BlockChainType::Stellar,
+				" gcxkg6rn4oniepcmnfb732a436z5pndsrlgwk7gblcmqlifo4s7eywvu "
+			),
+			Some(true)
+		);
+		assert_eq!(
+			registry.contains("wallets", &BlockChainType::Stellar, "GABC"),
+			Some(false)
+		);
+	}
+
+	#[test]
+	fn test_contains_unknown_set() {
+		let registry = create_registry();
+		assert_eq!(
+			registry.contains("unknown", &BlockChainType::EVM, "0x01"),
+			None
+		);
+	}
+
+	#[test]
+	fn test_global_registry() {
+		register_address_sets(vec![AddressSet {
+			name: "test_global_registry_set".to_string(),
+			addresses: vec!["0x0000000000000000000000000000000000000abc".to_string()],
+		}]);
+
+		assert_eq!(
+			address_set_contains(
+				"test_global_registry_set",
+				&BlockChainType::EVM,
+				"0x0000000000000000000000000000000000000ABC"
+			),
+			Some(true)
+		);
+	}
+}
@@ -0,0 +1,147 @@
+//! Aggregate computation over numeric values.
+//!
+//! Shared by per-transaction event aggregates and sliding window aggregation. Aggregates are
+//! computed with [`NumericValue`] arithmetic and checked with a filter expression in which the
+//! aggregate is available as `result`.
+
+use alloy::primitives::U256;
+use std::cmp::Ordering;
+
+use crate::{
+	models::{AggregateFunction, EVMMatchParamEntry},
+	services::filter::{
+		expression::{self, EvaluationError, NumericValue},
+		filters::EVMConditionEvaluator,
+	},
+};
+
+/// Applies an aggregate function to a collection of items
+///
+/// # Arguments
+/// * `function` - Aggregate function to apply
+/// * `items` - Items to aggregate
+/// * `value_of` - Extracts the numeric value of an item (not called for `Count`)
+///
+/// # Returns
+/// The aggregate, or `None` for `Min` and `Max` over an empty collection
+pub fn aggregate<T>(
+	function: AggregateFunction,
+	items: impl IntoIterator<Item = T>,
+	mut value_of: impl FnMut(T) -> Result<NumericValue, EvaluationError>,
+) -> Result<Option<NumericValue>, EvaluationError> {
+	let items = items.into_iter();
+	if function == AggregateFunction::Count {
+		return Ok(Some(NumericValue::from_u256(U256::from(items.count()))));
+	}
+
+	let initial = (function == AggregateFunction::Sum).then(|| NumericValue::from_u256(U256::ZERO));
+	items.into_iter().try_fold(initial, |acc, item| {
+		let value = value_of(item)?;
+		Ok(Some(match acc {
+			None => value,
+			Some(acc) => match function {
+				AggregateFunction::Sum => acc.checked_add(value)?,
+				AggregateFunction::Min if value.numeric_cmp(&acc) == Ordering::Less => value,
+				AggregateFunction::Max if value.numeric_cmp(&acc) == Ordering::Greater => value,
+				_ => acc,
+			},
+		}))
+	})
+}
+
+/// Evaluates an aggregate expression, exposing the aggregate as `result`
+///
+/// # Arguments
+/// * `expression` - Expression to evaluate (e.g., "result > 1000")
+/// * `result` - Aggregate value
+///
+/// # Returns
+/// `true` if the expression matches, `false` otherwise
+pub fn evaluate_aggregate_expression(
+	expression: &str,
+	result: &NumericValue,
+) -> Result<bool, EvaluationError> {
+	let kind = match result {
+		NumericValue::Integer { negative: true, .. } => "int256",
+		NumericValue::Integer { .. } => "uint256",
+		NumericValue::Decimal(_) => "fixed",
+	};
+	let args = [EVMMatchParamEntry {
+		name: "result".to_string(),
+		value: result.to_string(),
+		kind: kind.to_string(),
+		indexed: false,
+	}];
+
+	let parsed_ast = expression::parse(expression).map_err(|e| {
+		let msg = format!("Failed to parse expression '{}': {}", expression, e);
+		EvaluationError::parse_error(msg, None, None)
+	})?;
+	expression::evaluate(&parsed_ast, &EVMConditionEvaluator::new(&args))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn parse_values(values: &[&str]) -> Vec<NumericValue> {
+		values
+			.iter()
+			.map(|v| NumericValue::parse(v).unwrap())
+			.collect()
+	}
+
+	fn run(function: AggregateFunction, values: &[&str]) -> Option<String> {
+		aggregate(function, parse_values(values), Ok)
+			.unwrap()
+			.map(|v| v.to_string())
+	}
+
+	#[test]
+	fn test_aggregate_functions() {
+		let values = ["5", "-2", "10.5"];
+		assert_eq!(run(AggregateFunction::Count, &values).unwrap(), "3");
+		assert_eq!(
+			run(AggregateFunction::Sum, &values).unwrap(),
+			NumericValue::parse("13.5").unwrap().to_string()
+		);
+		assert_eq!(run(AggregateFunction::Min, &values).unwrap(), "-2");
+		assert_eq!(
+			run(AggregateFunction::Max, &values).unwrap(),
+			NumericValue::parse("10.5").unwrap().to_string()
+		);
+	}
+
+	#[test]
+	fn test_aggregate_empty() {
+		assert_eq!(run(AggregateFunction::Count, &[]).unwrap(), "0");
+		assert_eq!(run(AggregateFunction::Sum, &[]).unwrap(), "0");
+		assert_eq!(run(AggregateFunction::Min, &[]), None);
+		assert_eq!(run(AggregateFunction::Max, &[]), None);
+	}
+
+	#[test]
+	fn test_aggregate_value_error() {
+		let result = aggregate(AggregateFunction::Sum, ["1", "abc"], NumericValue::parse);
+		assert!(result.is_err());
+
+		// Values are never extracted for Count
+		let result = aggregate(AggregateFunction::Count, ["1", "abc"], NumericValue::parse);
+		assert_eq!(result.unwrap().unwrap().to_string(), "2");
+	}
+
+	#[test]
+	fn test_evaluate_aggregate_expression() {
+		let positive = NumericValue::parse("1500").unwrap();
+		assert!(evaluate_aggregate_expression("result > 1000", &positive).unwrap());
+		assert!(!evaluate_aggregate_expression("result < 1000", &positive).unwrap());
+
+		let negative = NumericValue::parse("-5").unwrap();
+		assert!(evaluate_aggregate_expression("result < 0", &negative).unwrap());
+
+		let decimal = NumericValue::parse("2.5").unwrap();
+		assert!(evaluate_aggregate_expression("result > 2", &decimal).unwrap());
+
+		assert!(evaluate_aggregate_expression("result >", &positive).is_err());
+	}
+}
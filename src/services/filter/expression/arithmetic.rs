@@ -0,0 +1,566 @@
+//! Numeric values and overflow-checked arithmetic used by arithmetic expressions.
+//!
+//! Integers are stored in sign-magnitude form over `U256`, so every U256 and I256 value can be
+//! used as an operand without loss. Fractional values use `rust_decimal::Decimal`. Mixing an
+//! integer with a decimal promotes the integer to a decimal. Any operation that cannot be
+//! represented exactly in the result type fails with an `ArithmeticError` instead of wrapping or
+//! saturating.
+
+use super::{ast::ComparisonOperator, error::EvaluationError};
+use alloy::primitives::{I256, U256, U512};
+use rust_decimal::Decimal;
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+/// Maximum number of significant digits a `Decimal` can hold
+const DECIMAL_MAX_DIGITS: u32 = 28;
+
+/// A numeric value produced while evaluating an arithmetic expression
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumericValue {
+	/// An integer in sign-magnitude form. Zero is never negative.
+	Integer { negative: bool, magnitude: U256 },
+	/// A fixed-point decimal value
+	Decimal(Decimal),
+}
+
+impl NumericValue {
+	/// Creates an integer value from its sign and magnitude
+	pub fn integer(negative: bool, magnitude: U256) -> Self {
+		Self::Integer {
+			negative: negative && !magnitude.is_zero(),
+			magnitude,
+		}
+	}
+
+	/// Creates a value from an unsigned 256-bit integer
+	pub fn from_u256(value: U256) -> Self {
+		Self::integer(false, value)
+	}
+
+	/// Creates a value from a signed 256-bit integer
+	pub fn from_i256(value: I256) -> Self {
+		Self::integer(value.is_negative(), value.unsigned_abs())
+	}
+
+	/// Parses a numeric literal
+	///
+	/// Supports an optional sign, decimal integers, hexadecimal integers (`0x` prefix),
+	/// decimal fractions (`1.5`) and scientific notation (`1e18`, `2.5E-3`). Values written in
+	/// scientific notation that are whole numbers (e.g. `1e21`) are kept as exact integers.
+	pub fn parse(value: &str) -> Result<Self, EvaluationError> {
+		let trimmed = value.trim();
+		let parse_error = || {
+			let msg = format!("Failed to parse '{}' as a number", value);
+			EvaluationError::parse_error(msg, None, None)
+		};
+
+		let (negative, unsigned) = match trimmed.strip_prefix('-') {
+			Some(rest) => (true, rest),
+			None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+		};
+
+		if let Some(hex) = unsigned
+			.strip_prefix("0x")
+			.or_else(|| unsigned.strip_prefix("0X"))
+		{
+			if hex.is_empty() {
+				return Err(parse_error());
+			}
+			let magnitude = U256::from_str_radix(hex, 16).map_err(|_| parse_error())?;
+			return Ok(Self::integer(negative, magnitude));
+		}
+
+		let (mantissa, exponent) = match unsigned.find(['e', 'E']) {
+			Some(index) => {
+				let exponent = unsigned[index + 1..]
+					.parse::<i64>()
+					.map_err(|_| parse_error())?;
+				(&unsigned[..index], exponent)
+			}
+			None => (unsigned, 0),
+		};
+
+		let (integer_part, fraction_part) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+		let is_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+		if (integer_part.is_empty() && fraction_part.is_empty())
+			|| !is_digits(integer_part)
+			|| !is_digits(fraction_part)
+		{
+			return Err(parse_error());
+		}
+
+		// value = digits * 10^(exponent - fraction digits)
+		let digits = format!("{}{}", integer_part, fraction_part);
+		let scale = exponent - fraction_part.len() as i64;
+
+		if scale >= 0 {
+			let magnitude = U256::from_str(&digits).map_err(|_| parse_error())?;
+			let multiplier = pow10(scale).ok_or_else(|| overflow_error(value))?;
+			let magnitude = magnitude
+				.checked_mul(multiplier)
+				.ok_or_else(|| overflow_error(value))?;
+			return Ok(Self::integer(negative, magnitude));
+		}
+
+		let decimal = if exponent == 0 {
+			Decimal::from_str_exact(mantissa)
+		} else {
+			Decimal::from_scientific(unsigned)
+		}
+		.map_err(|e| {
+			let msg = format!("Failed to parse '{}' as a decimal number", value);
+			EvaluationError::parse_error(msg, Some(e.into()), None)
+		})?;
+
+		Ok(Self::Decimal(if negative { -decimal } else { decimal }))
+	}
+
+	/// Returns true if the value is zero
+	pub fn is_zero(&self) -> bool {
+		match self {
+			Self::Integer { magnitude, .. } => magnitude.is_zero(),
+			Self::Decimal(d) => d.is_zero(),
+		}
+	}
+
+	/// Converts the value into a `Decimal`, failing if it exceeds the decimal range
+	pub fn to_decimal(self) -> Result<Decimal, EvaluationError> {
+		match self {
+			Self::Decimal(d) => Ok(d),
+			Self::Integer {
+				negative,
+				magnitude,
+			} => {
+				let decimal = u128::try_from(magnitude)
+					.ok()
+					.and_then(|m| i128::try_from(m).ok())
+					.and_then(|m| Decimal::try_from_i128_with_scale(m, 0).ok())
+					.ok_or_else(|| {
+						let msg = format!("Value {} exceeds the supported decimal range", self);
+						EvaluationError::arithmetic_error(msg, None, None)
+					})?;
+				Ok(if negative { -decimal } else { decimal })
+			}
+		}
+	}
+
+	/// Adds two values, failing on overflow
+	pub fn checked_add(self, other: Self) -> Result<Self, EvaluationError> {
+		match (self, other) {
+			(
+				Self::Integer {
+					negative: left_negative,
+					magnitude: left,
+				},
+				Self::Integer {
+					negative: right_negative,
+					magnitude: right,
+				},
+			) => {
+				if left_negative == right_negative {
+					let magnitude = left
+						.checked_add(right)
+						.ok_or_else(|| operation_overflow("+", &self, &other))?;
+					Ok(Self::integer(left_negative, magnitude))
+				} else if left >= right {
+					Ok(Self::integer(left_negative, left - right))
+				} else {
+					Ok(Self::integer(right_negative, right - left))
+				}
+			}
+			_ => self
+				.to_decimal()?
+				.checked_add(other.to_decimal()?)
+				.map(Self::Decimal)
+				.ok_or_else(|| operation_overflow("+", &self, &other)),
+		}
+	}
+
+	/// Subtracts `other` from this value, failing on overflow
+	pub fn checked_sub(self, other: Self) -> Result<Self, EvaluationError> {
+		self.checked_add(other.negate())
+	}
+
+	/// Multiplies two values, failing on overflow
+	pub fn checked_mul(self, other: Self) -> Result<Self, EvaluationError> {
+		match (self, other) {
+			(
+				Self::Integer {
+					negative: left_negative,
+					magnitude: left,
+				},
+				Self::Integer {
+					negative: right_negative,
+					magnitude: right,
+				},
+			) => left
+				.checked_mul(right)
+				.map(|magnitude| Self::integer(left_negative != right_negative, magnitude))
+				.ok_or_else(|| operation_overflow("*", &self, &other)),
+			_ => self
+				.to_decimal()?
+				.checked_mul(other.to_decimal()?)
+				.map(Self::Decimal)
+				.ok_or_else(|| operation_overflow("*", &self, &other)),
+		}
+	}
+
+	/// Divides this value by `other`, failing on division by zero or overflow
+	///
+	/// Integer division is exact when the divisor divides the dividend. Otherwise the result
+	/// becomes a decimal truncated to the available decimal precision (28 significant digits).
+	/// Quotients too large to be represented as a decimal keep only their integer part.
+	pub fn checked_div(self, other: Self) -> Result<Self, EvaluationError> {
+		if other.is_zero() {
+			let msg = format!("Division by zero: {} / {}", self, other);
+			return Err(EvaluationError::arithmetic_error(msg, None, None));
+		}
+
+		match (self, other) {
+			(
+				Self::Integer {
+					negative: left_negative,
+					magnitude: left,
+				},
+				Self::Integer {
+					negative: right_negative,
+					magnitude: right,
+				},
+			) => {
+				let negative = left_negative != right_negative;
+				let (quotient, remainder) = left.div_rem(right);
+				if remainder.is_zero() {
+					return Ok(Self::integer(negative, quotient));
+				}
+
+				let quotient_digits = quotient.to_string().trim_start_matches('0').len() as u32;
+				if quotient_digits >= DECIMAL_MAX_DIGITS {
+					return Ok(Self::integer(negative, quotient));
+				}
+
+				// Long division of the remainder to fill the remaining decimal precision
+				let scale = DECIMAL_MAX_DIGITS - quotient_digits;
+				let ten_pow_scale = U512::from(10u8).pow(U512::from(scale));
+				let fraction = U512::from(remainder) * ten_pow_scale / U512::from(right);
+				let mantissa = U512::from(quotient) * ten_pow_scale + fraction;
+				let mantissa =
+					i128::try_from(mantissa).map_err(|_| operation_overflow("/", &self, &other))?;
+				let decimal = Decimal::try_from_i128_with_scale(mantissa, scale)
+					.map_err(|_| operation_overflow("/", &self, &other))?
+					.normalize();
+
+				Ok(Self::Decimal(if negative { -decimal } else { decimal }))
+			}
+			_ => self
+				.to_decimal()?
+				.checked_div(other.to_decimal()?)
+				.map(Self::Decimal)
+				.ok_or_else(|| operation_overflow("/", &self, &other)),
+		}
+	}
+
+	/// Raises this value to the power of `exponent`, failing on overflow
+	///
+	/// The exponent must be a whole number. Negative exponents produce decimal results.
+	pub fn checked_pow(self, exponent: Self) -> Result<Self, EvaluationError> {
+		let (exponent_negative, exponent_magnitude) = match exponent {
+			Self::Integer {
+				negative,
+				magnitude,
+			} => (negative, magnitude),
+			// Whole decimals (e.g. `2.0`) are re-parsed as integers
+			Self::Decimal(d) if d.fract().is_zero() => {
+				return self.checked_pow(Self::parse(&d.trunc().to_string())?);
+			}
+			Self::Decimal(_) => {
+				let msg = format!("Exponent must be a whole number, found: {}", exponent);
+				return Err(EvaluationError::arithmetic_error(msg, None, None));
+			}
+		};
+
+		let exp = u32::try_from(exponent_magnitude)
+			.map_err(|_| operation_overflow("^", &self, &exponent))?;
+
+		let result = match self {
+			Self::Integer {
+				negative,
+				magnitude,
+			} => magnitude
+				.checked_pow(U256::from(exp))
+				.map(|magnitude| Self::integer(negative && exp % 2 == 1, magnitude))
+				.ok_or_else(|| operation_overflow("^", &self, &exponent))?,
+			Self::Decimal(base) => {
+				// Exponentiation by squaring with checked multiplication
+				let mut result = Decimal::ONE;
+				let mut base = base;
+				let mut remaining = exp;
+				while remaining > 0 {
+					if remaining & 1 == 1 {
+						result = result
+							.checked_mul(base)
+							.ok_or_else(|| operation_overflow("^", &self, &exponent))?;
+					}
+					remaining >>= 1;
+					if remaining > 0 {
+						base = base
+							.checked_mul(base)
+							.ok_or_else(|| operation_overflow("^", &self, &exponent))?;
+					}
+				}
+				Self::Decimal(result)
+			}
+		};
+
+		if exponent_negative {
+			Self::Decimal(Decimal::ONE).checked_div(result)
+		} else {
+			Ok(result)
+		}
+	}
+
+	/// Compares two values numerically
+	pub fn numeric_cmp(&self, other: &Self) -> Ordering {
+		match (self, other) {
+			(
+				Self::Integer {
+					negative: left_negative,
+					magnitude: left,
+				},
+				Self::Integer {
+					negative: right_negative,
+					magnitude: right,
+				},
+			) => match (left_negative, right_negative) {
+				(false, false) => left.cmp(right),
+				(true, true) => right.cmp(left),
+				(false, true) => Ordering::Greater,
+				(true, false) => Ordering::Less,
+			},
+			_ => match (self.to_decimal(), other.to_decimal()) {
+				(Ok(left), Ok(right)) => left.cmp(&right),
+				// An integer outside the decimal range is larger in magnitude than any decimal
+				(Err(_), _) => self.sign_ordering(),
+				(_, Err(_)) => other.sign_ordering().reverse(),
+			},
+		}
+	}
+
+	/// Evaluates a comparison operator between two numeric values
+	pub fn compare(
+		&self,
+		operator: &ComparisonOperator,
+		other: &Self,
+	) -> Result<bool, EvaluationError> {
+		let ordering = self.numeric_cmp(other);
+		match operator {
+			ComparisonOperator::Eq => Ok(ordering == Ordering::Equal),
+			ComparisonOperator::Ne => Ok(ordering != Ordering::Equal),
+			ComparisonOperator::Gt => Ok(ordering == Ordering::Greater),
+			ComparisonOperator::Gte => Ok(ordering != Ordering::Less),
+			ComparisonOperator::Lt => Ok(ordering == Ordering::Less),
+			ComparisonOperator::Lte => Ok(ordering != Ordering::Greater),
+			_ => {
+				let msg = format!(
+					"Operator {:?} not supported for arithmetic expressions. Supported: Eq, Ne, \
+					 Gt, Gte, Lt, Lte.",
+					operator
+				);
+				Err(EvaluationError::unsupported_operator(msg, None, None))
+			}
+		}
+	}
+
+	/// Returns the negated value
+	fn negate(self) -> Self {
+		match self {
+			Self::Integer {
+				negative,
+				magnitude,
+			} => Self::integer(!negative, magnitude),
+			Self::Decimal(d) => Self::Decimal(-d),
+		}
+	}
+
+	/// Orders a non-zero value relative to zero
+	fn sign_ordering(&self) -> Ordering {
+		match self {
+			Self::Integer { negative: true, .. } => Ordering::Less,
+			_ => Ordering::Greater,
+		}
+	}
+}
+
+impl fmt::Display for NumericValue {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Integer {
+				negative,
+				magnitude,
+			} => write!(f, "{}{}", if *negative { "-" } else { "" }, magnitude),
+			Self::Decimal(d) => write!(f, "{}", d),
+		}
+	}
+}
+
+/// Computes 10^exponent as a U256, returning None on overflow
+fn pow10(exponent: i64) -> Option<U256> {
+	U256::from(10u8).checked_pow(U256::from(u32::try_from(exponent).ok()?))
+}
+
+fn overflow_error(value: &str) -> EvaluationError {
+	let msg = format!("Numeric literal '{}' overflows 256 bits", value);
+	EvaluationError::arithmetic_error(msg, None, None)
+}
+
+fn operation_overflow(
+	operator: &str,
+	left: &NumericValue,
+	right: &NumericValue,
+) -> EvaluationError {
+	let msg = format!("Arithmetic overflow: {} {} {}", left, operator, right);
+	EvaluationError::arithmetic_error(msg, None, None)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn num(value: &str) -> NumericValue {
+		NumericValue::parse(value).unwrap()
+	}
+
+	#[test]
+	fn test_parse_literals() {
+		assert_eq!(num("123"), NumericValue::from_u256(U256::from(123)));
+		assert_eq!(
+			num("-123"),
+			NumericValue::from_i256(I256::try_from(-123).unwrap())
+		);
+		assert_eq!(num("0x10"), NumericValue::from_u256(U256::from(16)));
+		assert_eq!(
+			num("1e21"),
+			NumericValue::from_u256(U256::from(10u128.pow(21)))
+		);
+		assert_eq!(num("1.5e3"), NumericValue::from_u256(U256::from(1500)));
+		assert_eq!(num("-0"), NumericValue::from_u256(U256::ZERO));
+		assert_eq!(
+			num("1.25"),
+			NumericValue::Decimal(Decimal::from_str("1.25").unwrap())
+		);
+		assert_eq!(
+			num("25e-3"),
+			NumericValue::Decimal(Decimal::from_str("0.025").unwrap())
+		);
+		assert_eq!(
+			num(&U256::MAX.to_string()),
+			NumericValue::from_u256(U256::MAX)
+		);
+
+		for invalid in ["", "-", "abc", "1.2.3", "1e", "0x", "0xzz", "1e-"] {
+			assert!(
+				matches!(
+					NumericValue::parse(invalid),
+					Err(EvaluationError::ParseError(_))
+				),
+				"'{}' should fail to parse",
+				invalid
+			);
+		}
+		assert!(matches!(
+			NumericValue::parse("1e100"),
+			Err(EvaluationError::ArithmeticError(_))
+		));
+	}
+
+	#[test]
+	fn test_integer_arithmetic() {
+		assert_eq!(num("2").checked_add(num("3")).unwrap(), num("5"));
+		assert_eq!(num("2").checked_sub(num("3")).unwrap(), num("-1"));
+		assert_eq!(num("-2").checked_sub(num("-3")).unwrap(), num("1"));
+		assert_eq!(num("-2").checked_mul(num("3")).unwrap(), num("-6"));
+		assert_eq!(num("-6").checked_div(num("-3")).unwrap(), num("2"));
+		assert_eq!(num("-2").checked_pow(num("3")).unwrap(), num("-8"));
+		assert_eq!(num("10").checked_pow(num("18")).unwrap(), num("1e18"));
+		assert_eq!(num("3").checked_sub(num("3")).unwrap(), num("0"));
+	}
+
+	#[test]
+	fn test_non_exact_division_yields_decimal() {
+		assert_eq!(num("7").checked_div(num("2")).unwrap(), num("3.5"));
+		assert_eq!(num("-1").checked_div(num("4")).unwrap(), num("-0.25"));
+		assert_eq!(
+			num("15000000000000000000000")
+				.checked_div(num("1e18"))
+				.unwrap(),
+			num("15000")
+		);
+		assert_eq!(
+			num("15000500000000000000000")
+				.checked_div(num("1e18"))
+				.unwrap(),
+			num("15000.5")
+		);
+		// Quotients beyond the decimal range keep their integer part
+		assert_eq!(
+			NumericValue::from_u256(U256::MAX)
+				.checked_div(num("2"))
+				.unwrap(),
+			NumericValue::from_u256(U256::MAX / U256::from(2))
+		);
+	}
+
+	#[test]
+	fn test_decimal_arithmetic() {
+		assert_eq!(num("1.5").checked_add(num("2")).unwrap(), num("3.5"));
+		assert_eq!(num("1.5").checked_mul(num("-2")).unwrap(), num("-3.0"));
+		assert_eq!(num("0.5").checked_pow(num("2")).unwrap(), num("0.25"));
+		assert_eq!(num("10").checked_pow(num("-2")).unwrap(), num("0.01"));
+	}
+
+	#[test]
+	fn test_overflow_and_division_by_zero() {
+		let max = NumericValue::from_u256(U256::MAX);
+		for result in [
+			max.checked_add(num("1")),
+			num("-1")
+				.checked_sub(max)
+				.and_then(|v| v.checked_sub(num("1"))),
+			max.checked_mul(num("2")),
+			num("2").checked_pow(num("256")),
+			num("1").checked_div(num("0")),
+			num("1.5").checked_div(num("0")),
+			num("1.5").checked_add(max),
+			num("2").checked_pow(num("1.5")),
+		] {
+			assert!(
+				matches!(result, Err(EvaluationError::ArithmeticError(_))),
+				"expected arithmetic error, got {:?}",
+				result
+			);
+		}
+	}
+
+	#[test]
+	fn test_compare() {
+		assert!(num("-1")
+			.compare(&ComparisonOperator::Lt, &num("0"))
+			.unwrap());
+		assert!(num("3.5")
+			.compare(&ComparisonOperator::Gt, &num("3"))
+			.unwrap());
+		assert!(num("3.0")
+			.compare(&ComparisonOperator::Eq, &num("3"))
+			.unwrap());
+		assert!(num("1e21")
+			.compare(&ComparisonOperator::Gte, &num("1000000000000000000000"))
+			.unwrap());
+		// Integers outside the decimal range compare by sign against decimals
+		let max = NumericValue::from_u256(U256::MAX);
+		assert!(max.compare(&ComparisonOperator::Gt, &num("1.5")).unwrap());
+		assert!(num("1.5").compare(&ComparisonOperator::Lt, &max).unwrap());
+		assert!(matches!(
+			num("1").compare(&ComparisonOperator::Contains, &num("1")),
+			Err(EvaluationError::UnsupportedOperator(_))
+		));
+	}
+}
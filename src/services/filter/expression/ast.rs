@@ -18,6 +18,25 @@ pub enum LiteralValue<'a> {
 	/// Store as string slice to preserve original form until evaluation phase.
 	/// Conversion to specific type is done within chain context during evaluation.
 	Number(&'a str),
+	/// An inline list of literal values, used as the right side of set membership operators.
+	/// e.g., "[0xabc, 0xdef]", "['alice', 'bob']"
+	List(Vec<LiteralValue<'a>>),
+	/// A reference to another variable (simple name or path), resolved during evaluation.
+	/// e.g., "to", "args.spender", "amounts[0]"
+	/// Simple names that do not match any parameter are evaluated as unquoted strings.
+	Variable(ConditionLeft<'a>),
+	/// A reference to a named address set defined in the global configuration.
+	/// e.g., "@hot_wallets" (stored without the leading '@')
+	AddressSet(&'a str),
+	/// An amount in whole units of a token registered in the global configuration.
+	/// e.g., "10000 USDC", "1.5 WETH"
+	/// Scaled to a raw integer amount with the token decimals during evaluation.
+	TokenAmount {
+		/// The amount in whole token units, e.g. "1.5"
+		amount: &'a str,
+		/// The token symbol, e.g. "USDC"
+		symbol: &'a str,
+	},
 }
 
 /// Represents the possible comparison operators that can be used in filter expressions.
@@ -42,6 +61,11 @@ pub enum ComparisonOperator {
 	EndsWith,
 	/// - Contains: Checks if the string/collection contains a given item.
 	Contains,
+	/// Set membership operators:
+	/// - In: Checks if the value is a member of a list or named address set.
+	In,
+	/// - NotIn: Checks if the value is not a member of a list or named address set.
+	NotIn,
 }
 
 /// Represents the possible logical operators that can be used in filter expressions.
@@ -114,12 +138,69 @@ pub struct Condition<'a> {
 	pub right: LiteralValue<'a>,
 }
 
+/// Represents the possible arithmetic operators that can be used in arithmetic expressions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArithmeticOperator {
+	/// Addition operator (+)
+	Add,
+	/// Subtraction operator (-)
+	Sub,
+	/// Multiplication operator (*)
+	Mul,
+	/// Division operator (/)
+	Div,
+	/// Exponentiation operator (^)
+	Pow,
+}
+
+/// Represents an arithmetic expression computed before a comparison (e.g., "value / 10^decimals").
+/// Operands are either numeric literals or variables resolved from the chain-specific context.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArithmeticExpression<'a> {
+	/// A numeric literal. e.g., "10", "-1.5", "0xff", "1e18"
+	/// Stored as string slice and converted to a numeric value during evaluation.
+	Number(&'a str),
+	/// A variable (simple name or path), resolved and converted to a numeric value during evaluation.
+	Variable(ConditionLeft<'a>),
+	/// The USD value of a raw token amount (e.g., "usd(value, USDC)"), computed from the token
+	/// decimals and its cached price. The token is referenced by symbol or contract address.
+	Usd {
+		/// The raw token amount.
+		value: Box<ArithmeticExpression<'a>>,
+		/// Symbol or contract address of the token.
+		token: &'a str,
+	},
+	/// A binary operation between two arithmetic sub-expressions.
+	Binary {
+		/// The left operand.
+		left: Box<ArithmeticExpression<'a>>,
+		/// The arithmetic operator.
+		operator: ArithmeticOperator,
+		/// The right operand.
+		right: Box<ArithmeticExpression<'a>>,
+	},
+}
+
+/// Represents a comparison between two arithmetic expressions (e.g., "amount0In + amount1In > 1e21").
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArithmeticCondition<'a> {
+	/// The left side arithmetic expression.
+	pub left: ArithmeticExpression<'a>,
+	/// The comparison operator (only ==, !=, >, >=, <, <= are supported).
+	pub operator: ComparisonOperator,
+	/// The right side arithmetic expression.
+	pub right: ArithmeticExpression<'a>,
+}
+
 /// Represents a complete filter expression.
 /// An expression can be a single condition or a logical combination of multiple conditions.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Expression<'a> {
 	/// A simple condition (e.g., "age > 30")
 	Condition(Condition<'a>),
+	/// A comparison involving arithmetic or `usd()` on at least one side
+	/// (e.g., "value / 10^18 > 100", "usd(value, USDC) > 1000")
+	Arithmetic(ArithmeticCondition<'a>),
 	/// A logical combination of two expressions (e.g., "age > 30 && name == 'John'")
 	/// `Box` is used to avoid infinite type recursion, as `Expression` can contain other `Expression`s.
 	Logical {
@@ -130,4 +211,30 @@ pub enum Expression<'a> {
 		/// The right side sub-expression.
 		right: Box<Expression<'a>>,
 	},
+	/// A negated sub-expression (e.g., "not (age > 30)" or "!(name == 'John')")
+	Not(Box<Expression<'a>>),
+}
+
+impl<'a> Expression<'a> {
+	/// Collects the names of the address sets referenced by the expression
+	/// (e.g., "hot_wallets" for "to in @hot_wallets"), in order of appearance.
+	pub fn address_set_names(&self) -> Vec<&'a str> {
+		let mut names = Vec::new();
+		let mut pending = vec![self];
+		while let Some(expression) = pending.pop() {
+			match expression {
+				Expression::Condition(Condition {
+					right: LiteralValue::AddressSet(name),
+					..
+				}) => names.push(*name),
+				Expression::Condition(_) | Expression::Arithmetic(_) => {}
+				Expression::Logical { left, right, .. } => {
+					pending.push(right);
+					pending.push(left);
+				}
+				Expression::Not(inner) => pending.push(inner),
+			}
+		}
+		names
+	}
 }
@@ -33,6 +33,10 @@ pub enum EvaluationError {
 	/// A field/key is not found during object access in a path.
 	#[error("Field not found during path traversal: {0}")]
 	FieldNotFound(Box<ErrorContext>),
+
+	/// An arithmetic operation overflows or is undefined (e.g., division by zero).
+	#[error("Arithmetic error: {0}")]
+	ArithmeticError(Box<ErrorContext>),
 }
 
 impl EvaluationError {
@@ -110,6 +114,18 @@ impl EvaluationError {
 			message, source, metadata,
 		)))
 	}
+
+	/// Creates a new `ArithmeticError` error.
+	/// The `message` for `ErrorContext` should describe the failed operation.
+	pub fn arithmetic_error(
+		message: impl Into<String>, // e.g., format!("Arithmetic overflow: {} * {}", left, right)
+		source: Option<Box<dyn std::error::Error + Send + Sync + 'static>>,
+		metadata: Option<HashMap<String, String>>,
+	) -> Self {
+		Self::ArithmeticError(Box::new(ErrorContext::new_with_log(
+			message, source, metadata,
+		)))
+	}
 }
 
 impl TraceableError for EvaluationError {
@@ -120,7 +136,8 @@ impl TraceableError for EvaluationError {
 			| Self::UnsupportedOperator(ctx)
 			| Self::ParseError(ctx)
 			| Self::IndexOutOfBounds(ctx)
-			| Self::FieldNotFound(ctx) => ctx.trace_id.clone(),
+			| Self::FieldNotFound(ctx)
+			| Self::ArithmeticError(ctx) => ctx.trace_id.clone(),
 		}
 	}
 }
@@ -224,6 +241,16 @@ mod tests {
 		assert!(matches!(error, EvaluationError::FieldNotFound(_)));
 	}
 
+	#[test]
+	fn test_arithmetic_error() {
+		let error = EvaluationError::arithmetic_error("Division by zero: 1 / 0", None, None);
+		assert_eq!(
+			error.to_string(),
+			"Arithmetic error: Division by zero: 1 / 0"
+		);
+		assert!(matches!(error, EvaluationError::ArithmeticError(_)));
+	}
+
 	#[test]
 	fn test_trace_id_retrieval() {
 		let error_vnf = EvaluationError::variable_not_found("my_var", None, None);
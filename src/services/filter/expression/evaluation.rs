@@ -5,13 +5,19 @@
 //! for evaluating conditions based on the context of the chain.
 
 use super::error::EvaluationError;
-use crate::services::filter::expression::ast::{ComparisonOperator, LiteralValue};
+use crate::services::filter::expression::{
+	arithmetic::NumericValue,
+	ast::{ComparisonOperator, LiteralValue},
+};
 
 /// The `ConditionEvaluator` trait defines methods for evaluating conditions in filter expressions.
 pub trait ConditionEvaluator {
 	/// Gets the raw string value and kind for a base variable name
 	fn get_base_param(&self, name: &str) -> Result<(&str, &str), EvaluationError>;
 
+	/// Checks whether a base variable with the given name exists
+	fn has_base_param(&self, name: &str) -> bool;
+
 	/// Performs the final comparison between the left resolved value (after all path traversal) and the literal value
 	fn compare_final_values(
 		&self,
@@ -23,4 +29,22 @@ pub trait ConditionEvaluator {
 
 	/// Gets the chain-specific kind of a value from a JSON value
 	fn get_kind_from_json_value(&self, value: &serde_json::Value) -> String;
+
+	/// Converts a resolved variable value of the given chain-specific kind into the literal
+	/// form expected by `compare_final_values`, used when a variable appears on the right side
+	fn get_literal_from_value<'v>(&self, kind: &str, value: &'v str) -> LiteralValue<'v>;
+
+	/// Checks whether the left resolved value is a member of a named address set,
+	/// applying the chain-specific address normalization
+	fn is_in_address_set(&self, set_name: &str, value: &str) -> Result<bool, EvaluationError>;
+
+	/// Converts a resolved value of the given chain-specific kind into a numeric value,
+	/// used as an operand in arithmetic expressions
+	fn get_numeric_value(&self, kind: &str, value: &str) -> Result<NumericValue, EvaluationError>;
+
+	/// Address of the contract that emitted the evaluated values, if known. Token amounts such
+	/// as "10000 USDC" use the decimals of this contract when it is a token with the symbol.
+	fn token_contract(&self) -> Option<&str> {
+		None
+	}
 }
@@ -1,10 +1,19 @@
 //! Utility functions for evaluating expressions and resolving JSON paths
 
 use super::{
-	ast::{Accessor, ComparisonOperator, ConditionLeft, Expression, LogicalOperator},
+	arithmetic::NumericValue,
+	ast::{
+		Accessor, ArithmeticExpression, ArithmeticOperator, ComparisonOperator, ConditionLeft,
+		Expression, LiteralValue, LogicalOperator,
+	},
 	error::EvaluationError,
 	evaluation::ConditionEvaluator,
 };
+use crate::services::{
+	filter::{to_raw_amount, token_decimals, token_has_conflicting_decimals, token_metadata},
+	price::token_price,
+};
+use alloy::primitives::U256;
 
 /// Traverses the Expression AST and uses ConditionEvaluator to evaluate conditions
 /// Returns true if the expression evaluates to true, false otherwise
@@ -15,48 +24,31 @@ pub fn evaluate(
 ) -> Result<bool, EvaluationError> {
 	match expression {
 		Expression::Condition(condition) => {
-			let base_name = condition.left.base_name();
-			let accessors = condition.left.accessors();
-			let (base_value_str, base_kind_str) = evaluator.get_base_param(base_name)?;
-
-			let final_left_value_str: String;
-			let final_left_kind: String;
-
-			if accessors.is_empty() {
-				// No accessors, use the base value directly
-				final_left_value_str = base_value_str.to_string();
-				final_left_kind = base_kind_str.to_string();
-			} else {
-				let resolved_value = resolve_path_to_json_value(
-					base_value_str,
-					base_kind_str,
-					accessors,
-					base_name,
-					&condition.left,
-				)?;
-
-				// Get the kind from the resolved JSON value from chain-specific evaluator
-				final_left_kind = evaluator.get_kind_from_json_value(&resolved_value);
-
-				// Convert the resolved JSON value to a string representation
-				final_left_value_str = match resolved_value {
-					serde_json::Value::String(s) => s,
-					serde_json::Value::Number(n) => n.to_string(),
-					serde_json::Value::Bool(b) => b.to_string(),
-					serde_json::Value::Null => "null".to_string(),
-					serde_json::Value::Array(_) | serde_json::Value::Object(_) => {
-						// If the resolved value is an array or object, we need to convert it to a string
-						resolved_value.to_string()
-					}
-				};
+			let (final_left_value_str, final_left_kind) =
+				resolve_variable(&condition.left, evaluator)?;
+
+			match condition.operator {
+				ComparisonOperator::In | ComparisonOperator::NotIn => {
+					let is_member = evaluate_membership(
+						evaluator,
+						&final_left_kind,
+						&final_left_value_str,
+						&condition.right,
+					)?;
+					Ok(if condition.operator == ComparisonOperator::In {
+						is_member
+					} else {
+						!is_member
+					})
+				}
+				_ => compare_with_right_side(
+					evaluator,
+					&final_left_kind,
+					&final_left_value_str,
+					&condition.operator,
+					&condition.right,
+				),
 			}
-
-			evaluator.compare_final_values(
-				&final_left_kind,
-				&final_left_value_str,
-				&condition.operator,
-				&condition.right,
-			)
 		}
 		Expression::Logical {
 			left,
@@ -81,9 +73,201 @@ pub fn evaluate(
 				}
 			}
 		}
+		Expression::Arithmetic(condition) => {
+			let left = evaluate_arithmetic(&condition.left, evaluator)?;
+			let right = evaluate_arithmetic(&condition.right, evaluator)?;
+
+			tracing::debug!(
+				"Comparing arithmetic values: left: {}, op: {:?}, right: {}",
+				left,
+				condition.operator,
+				right
+			);
+
+			left.compare(&condition.operator, &right)
+		}
+		Expression::Not(inner) => Ok(!evaluate(inner, evaluator)?),
 	}
 }
 
+/// Compares the resolved left value with the right side of a condition
+/// Variables on the right side are resolved like the left side and converted to literals by the
+/// chain-specific evaluator. Simple names that do not match any parameter are rejected, so string
+/// literals must be quoted (e.g., "status == 'active'").
+fn compare_with_right_side(
+	evaluator: &impl ConditionEvaluator,
+	left_kind: &str,
+	left_value: &str,
+	operator: &ComparisonOperator,
+	right: &LiteralValue<'_>,
+) -> Result<bool, EvaluationError> {
+	match right {
+		LiteralValue::Variable(ConditionLeft::Simple(name)) if !evaluator.has_base_param(name) => {
+			Err(EvaluationError::variable_not_found(
+				format!(
+					"'{}' on the right-hand side is not a parameter, quote it to compare against a string (e.g., '{}')",
+					name, name
+				),
+				None,
+				None,
+			))
+		}
+		LiteralValue::Variable(variable) => {
+			let (right_value, right_kind) = resolve_variable(variable, evaluator)?;
+			let right_literal = evaluator.get_literal_from_value(&right_kind, &right_value);
+			evaluator.compare_final_values(left_kind, left_value, operator, &right_literal)
+		}
+		LiteralValue::TokenAmount { amount, symbol } => {
+			let raw_amount = resolve_token_amount(amount, symbol, evaluator.token_contract())?;
+			evaluator.compare_final_values(
+				left_kind,
+				left_value,
+				operator,
+				&LiteralValue::Number(&raw_amount),
+			)
+		}
+		_ => evaluator.compare_final_values(left_kind, left_value, operator, right),
+	}
+}
+
+/// Scales an amount in whole token units (e.g., "10000 USDC") to the raw integer amount, using the
+/// decimals of the emitting contract or, failing that, of the tokens registered with the symbol
+fn resolve_token_amount(
+	amount: &str,
+	symbol: &str,
+	contract: Option<&str>,
+) -> Result<String, EvaluationError> {
+	let decimals = symbol_decimals(symbol, contract)?;
+	to_raw_amount(amount, decimals).ok_or_else(|| {
+		let msg = format!(
+			"Amount '{}' has more fractional digits than the {} decimals of {}",
+			amount, decimals, symbol
+		);
+		EvaluationError::parse_error(msg, None, None)
+	})
+}
+
+/// Resolves a variable name or path (on either side of a condition) to its final value and kind
+/// Returns a tuple of (value, kind)
+fn resolve_variable(
+	variable: &ConditionLeft<'_>,
+	evaluator: &impl ConditionEvaluator,
+) -> Result<(String, String), EvaluationError> {
+	let base_name = variable.base_name();
+	let accessors = variable.accessors();
+	let (base_value_str, base_kind_str) = evaluator.get_base_param(base_name)?;
+
+	if accessors.is_empty() {
+		// No accessors, use the base value directly
+		return Ok((base_value_str.to_string(), base_kind_str.to_string()));
+	}
+
+	let resolved_value = resolve_path_to_json_value(
+		base_value_str,
+		base_kind_str,
+		accessors,
+		base_name,
+		variable,
+	)?;
+
+	// Get the kind from the resolved JSON value from chain-specific evaluator
+	let final_kind = evaluator.get_kind_from_json_value(&resolved_value);
+
+	// Convert the resolved JSON value to a string representation
+	let final_value_str = match resolved_value {
+		serde_json::Value::String(s) => s,
+		serde_json::Value::Number(n) => n.to_string(),
+		serde_json::Value::Bool(b) => b.to_string(),
+		serde_json::Value::Null => "null".to_string(),
+		serde_json::Value::Array(_) | serde_json::Value::Object(_) => {
+			// If the resolved value is an array or object, we need to convert it to a string
+			resolved_value.to_string()
+		}
+	};
+
+	Ok((final_value_str, final_kind))
+}
+
+/// Evaluates an arithmetic expression to a numeric value
+/// Variables are resolved and converted by the chain-specific evaluator
+/// Returns an error on overflow, division by zero or non-numeric operands
+fn evaluate_arithmetic(
+	expression: &ArithmeticExpression<'_>,
+	evaluator: &impl ConditionEvaluator,
+) -> Result<NumericValue, EvaluationError> {
+	match expression {
+		ArithmeticExpression::Number(number) => NumericValue::parse(number),
+		ArithmeticExpression::Variable(variable) => {
+			let (value, kind) = resolve_variable(variable, evaluator)?;
+			evaluator.get_numeric_value(&kind, &value)
+		}
+		ArithmeticExpression::Usd { value, token } => {
+			let amount = evaluate_arithmetic(value, evaluator)?;
+			usd_value(amount, token, evaluator.token_contract())
+		}
+		ArithmeticExpression::Binary {
+			left,
+			operator,
+			right,
+		} => {
+			let left = evaluate_arithmetic(left, evaluator)?;
+			let right = evaluate_arithmetic(right, evaluator)?;
+			match operator {
+				ArithmeticOperator::Add => left.checked_add(right),
+				ArithmeticOperator::Sub => left.checked_sub(right),
+				ArithmeticOperator::Mul => left.checked_mul(right),
+				ArithmeticOperator::Div => left.checked_div(right),
+				ArithmeticOperator::Pow => left.checked_pow(right),
+			}
+		}
+	}
+}
+
+/// Returns the decimals of a token symbol, preferring those of the contract that emitted the
+/// evaluated values
+fn symbol_decimals(symbol: &str, contract: Option<&str>) -> Result<u32, EvaluationError> {
+	token_decimals(symbol, contract).ok_or_else(|| {
+		let msg = if token_has_conflicting_decimals(symbol) {
+			format!(
+				"Token {} has different decimals per network, compare a value emitted by one of \
+				 its contracts",
+				symbol
+			)
+		} else {
+			format!("Token not found: {}", symbol)
+		};
+		EvaluationError::variable_not_found(msg, None, None)
+	})
+}
+
+/// Converts a raw token amount to its USD value using the token decimals and cached price
+/// The token is referenced by symbol or contract address
+fn usd_value(
+	amount: NumericValue,
+	token: &str,
+	contract: Option<&str>,
+) -> Result<NumericValue, EvaluationError> {
+	let token_not_found = || {
+		let msg = format!("Token not found: {}", token);
+		EvaluationError::variable_not_found(msg, None, None)
+	};
+	let (symbol, decimals) = if token.starts_with("0x") || token.starts_with("0X") {
+		let metadata = token_metadata(token).ok_or_else(token_not_found)?;
+		(metadata.symbol, metadata.decimals)
+	} else {
+		(token.to_string(), symbol_decimals(token, contract)?)
+	};
+
+	let price = token_price(&symbol).map_err(|e| {
+		let msg = format!("USD price not available: {}", symbol);
+		EvaluationError::variable_not_found(msg, Some(Box::new(e)), None)
+	})?;
+	let scale = NumericValue::from_u256(U256::from(10).pow(U256::from(decimals)));
+	amount
+		.checked_div(scale)?
+		.checked_mul(NumericValue::Decimal(price))
+}
+
 /// Compares two values implementing the Ord trait using the specified comparison operator
 /// Returns true if the comparison is valid, false otherwise
 /// Returns an error if the operator is not supported for the given types
@@ -111,6 +295,42 @@ pub fn compare_ordered_values<T: Ord>(
 	}
 }
 
+/// Checks whether the left resolved value is a member of the right side collection
+/// Inline list items are compared using the evaluator's equality semantics (e.g. address normalization),
+/// named address sets are resolved through the evaluator
+/// Returns an error if the right side is not a list or address set reference
+fn evaluate_membership(
+	evaluator: &impl ConditionEvaluator,
+	left_kind: &str,
+	left_value: &str,
+	right: &LiteralValue<'_>,
+) -> Result<bool, EvaluationError> {
+	match right {
+		LiteralValue::List(items) => {
+			for item in items {
+				if compare_with_right_side(
+					evaluator,
+					left_kind,
+					left_value,
+					&ComparisonOperator::Eq,
+					item,
+				)? {
+					return Ok(true);
+				}
+			}
+			Ok(false)
+		}
+		LiteralValue::AddressSet(set_name) => evaluator.is_in_address_set(set_name, left_value),
+		_ => {
+			let msg = format!(
+				"Expected list or address set for membership comparison, found: {:?}",
+				right
+			);
+			Err(EvaluationError::type_mismatch(msg, None, None))
+		}
+	}
+}
+
 /// Resolves a JSON path from a base variable name and accessors
 /// Returns the resolved JSON value
 /// Returns an error if the traversal fails
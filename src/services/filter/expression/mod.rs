@@ -1,13 +1,15 @@
 //! Shared logic for parsing and evaluating expressions
 
+mod arithmetic;
 mod ast;
 mod error;
 mod evaluation;
 mod helpers;
 mod parsing;
 
+pub use arithmetic::NumericValue;
 pub use ast::{ComparisonOperator, LiteralValue};
 pub use error::EvaluationError;
 pub use evaluation::ConditionEvaluator;
 pub use helpers::{compare_ordered_values, evaluate};
-pub use parsing::parse;
+pub use parsing::{address_set_references, parse};
@@ -3,12 +3,14 @@
 //! The parser converts the input string into an abstract syntax tree (AST) representation of the expression.
 
 use super::ast::{
-	Accessor, ComparisonOperator, Condition, ConditionLeft, Expression, LiteralValue,
-	LogicalOperator, VariablePath,
+	Accessor, ArithmeticCondition, ArithmeticExpression, ArithmeticOperator, ComparisonOperator,
+	Condition, ConditionLeft, Expression, LiteralValue, LogicalOperator, VariablePath,
 };
 use winnow::{
 	ascii::{digit1, space0, space1, Caseless},
-	combinator::{alt, delimited, eof, opt, peek, repeat, Repeat},
+	combinator::{
+		alt, delimited, eof, not, opt, peek, preceded, repeat, separated, terminated, Repeat,
+	},
 	error::{ContextError, ErrMode, ParseError, StrContext, StrContextValue},
 	prelude::*,
 	token::{literal, one_of, take_while},
@@ -24,7 +26,7 @@ type ParserResult<T> = winnow::Result<T, ErrMode<ContextError>>;
 fn is_keyword(ident: &str) -> bool {
 	matches!(
 		ident.to_ascii_lowercase().as_str(),
-		"true" | "false" | "and" | "or" | "contains" | "starts_with" | "ends_with"
+		"true" | "false" | "and" | "or" | "contains" | "starts_with" | "ends_with" | "in" | "not"
 	)
 }
 
@@ -83,6 +85,31 @@ fn parse_number_or_fixed_str<'a>(input: &mut Input<'a>) -> ParserResult<LiteralV
 	.parse_next(input)
 }
 
+/// Parses an amount followed by a token symbol (e.g., "10000 USDC") into `LiteralValue::TokenAmount`
+fn parse_token_amount<'a>(input: &mut Input<'a>) -> ParserResult<LiteralValue<'a>> {
+	(
+		(digit1, opt((literal("."), digit1))).take(),
+		space1,
+		(
+			one_of(|c: char| c.is_alpha() || c == '_'),
+			take_while(0.., |c: char| c.is_alphanum() || c == '_'),
+		)
+			.take()
+			.verify(|symbol: &str| !is_keyword(symbol)),
+		peek(alt((
+			// Ensure the symbol is properly delimited
+			space1.value(()),
+			eof.value(()),
+			one_of(COMMON_DELIMITERS).value(()),
+		))),
+	)
+		.map(|(amount, _, symbol, _)| LiteralValue::TokenAmount { amount, symbol })
+		.context(StrContext::Expected(StrContextValue::Description(
+			"token amount like '10000 USDC'",
+		)))
+		.parse_next(input)
+}
+
 // Parses an unquoted "0x..." or "0X..." sequence as a string.
 fn parse_hex_string<'a>(input: &mut Input<'a>) -> ParserResult<LiteralValue<'a>> {
 	(
@@ -125,6 +152,33 @@ fn parse_quoted_string<'a>(input: &mut Input<'a>) -> ParserResult<LiteralValue<'
 	Ok(LiteralValue::Str(string_content_slice))
 }
 
+// Parses an unquoted Stellar strkey address (e.g., "GABC...", "CABC...", "MABC...") as a string.
+fn parse_stellar_address<'a>(input: &mut Input<'a>) -> ParserResult<LiteralValue<'a>> {
+	(
+		take_while(1.., |c: char| c.is_ascii_alphanumeric()).verify(|s: &&str| {
+			let expected_len = match s.chars().next() {
+				Some('G') | Some('C') => 56,
+				Some('M') => 69,
+				_ => return false,
+			};
+			s.len() == expected_len
+				&& s.chars()
+					.all(|c| c.is_ascii_uppercase() || ('2'..='7').contains(&c))
+		}),
+		peek(alt((
+			space1.value(()),
+			eof.value(()),
+			one_of(COMMON_DELIMITERS).value(()),
+		))),
+	)
+		.take()
+		.map(|s: &str| LiteralValue::Str(s))
+		.context(StrContext::Expected(StrContextValue::Description(
+			"Stellar address literal",
+		)))
+		.parse_next(input)
+}
+
 /// Fallback parser for unquoted strings (applied last)
 fn parse_unquoted_string<'a>(input: &mut Input<'a>) -> ParserResult<LiteralValue<'a>> {
 	take_while(1.., |c: char| c.is_alphanum() || c == '_' || c == '-')
@@ -177,11 +231,11 @@ fn parse_accessor<'a>(input: &mut Input<'a>) -> ParserResult<Accessor<'a>> {
 		)),
 		// Ensure it's properly delimited
 		peek(alt((
-			space1.value(()),                                 // space
-			eof.value(()),                                    // end of input
-			literal("[").value(()),                           // start of index accessor
-			literal(".").value(()),                           // start of another key accessor
-			one_of(['=', '!', '>', '<', ')', '(']).value(()), // Operators or delimiters
+			space1.value(()),                                           // space
+			eof.value(()),                                              // end of input
+			literal("[").value(()),                                     // start of index accessor
+			literal(".").value(()),                                     // start of another key accessor
+			one_of(['=', '!', '>', '<', ')', '(', ',', ']']).value(()), // Operators or delimiters
 		))),
 	)
 		.map(|(_, key_slice, _): (_, &str, _)| Accessor::Key(key_slice))
@@ -241,22 +295,95 @@ fn parse_condition_lhs<'a>(input: &mut Input<'a>) -> ParserResult<ConditionLeft<
 	}
 }
 
-/// Parses any valid LiteralValue (boolean, number, string, or variable)
+/// Parses a reference to another variable (e.g., "to", "args.spender") into `LiteralValue::Variable`
+/// Simple names that do not match any parameter are rejected during evaluation
+fn parse_variable_value<'a>(input: &mut Input<'a>) -> ParserResult<LiteralValue<'a>> {
+	terminated(
+		parse_condition_lhs,
+		peek(alt((
+			// Ensure the variable is properly delimited (e.g. not part of "foo-bar")
+			space1.value(()),
+			eof.value(()),
+			one_of(COMMON_DELIMITERS).value(()),
+		))),
+	)
+	.map(LiteralValue::Variable)
+	.context(StrContext::Expected(StrContextValue::Description(
+		"variable reference",
+	)))
+	.parse_next(input)
+}
+
+/// Parses any scalar LiteralValue (boolean, number or string)
 /// Handles optional whitespace around the value
-fn parse_value<'a>(input: &mut Input<'a>) -> ParserResult<LiteralValue<'a>> {
+fn parse_scalar_value<'a>(input: &mut Input<'a>) -> ParserResult<LiteralValue<'a>> {
 	delimited(
 		space0,
 		alt((
 			parse_quoted_string,       // "'string'" or '"string"'
 			parse_boolean,             // "true" / "false"
 			parse_hex_string,          // "0x..."
+			parse_stellar_address,     // "GABC..." / "CABC..."
+			parse_token_amount,        // "10000 USDC" / "1.5 WETH"
 			parse_number_or_fixed_str, // "123" / "-123" / "123.456"
-			parse_unquoted_string,     // "unquoted_string"
+			parse_variable_value,      // "other_variable" / "args.spender"
+			parse_unquoted_string,     // "unquoted-string"
 		)),
 		space0,
 	)
 	.context(StrContext::Expected(StrContextValue::Description(
-		"boolean, number, hex string or string",
+		"boolean, number, hex string, variable or string",
+	)))
+	.parse_next(input)
+}
+
+/// Parses an inline list of scalar values (e.g., "[0xabc, 0xdef]") into `LiteralValue::List`
+fn parse_list<'a>(input: &mut Input<'a>) -> ParserResult<LiteralValue<'a>> {
+	delimited(
+		literal("["),
+		separated(0.., parse_scalar_value, literal(",")),
+		(space0, literal("]")).context(StrContext::Expected(StrContextValue::Description(
+			"closing bracket ']' for list literal",
+		))),
+	)
+	.map(LiteralValue::List)
+	.context(StrContext::Expected(StrContextValue::Description(
+		"list literal like '[0xabc, 0xdef]'",
+	)))
+	.parse_next(input)
+}
+
+/// Parses a reference to a named address set (e.g., "@hot_wallets") into `LiteralValue::AddressSet`
+fn parse_address_set_ref<'a>(input: &mut Input<'a>) -> ParserResult<LiteralValue<'a>> {
+	preceded(
+		literal("@"),
+		(
+			one_of(|c: char| c.is_alpha() || c == '_'),
+			take_while(0.., |c: char| c.is_alphanum() || c == '_'),
+		)
+			.take(),
+	)
+	.map(LiteralValue::AddressSet)
+	.context(StrContext::Expected(StrContextValue::Description(
+		"address set reference like '@hot_wallets'",
+	)))
+	.parse_next(input)
+}
+
+/// Parses any valid LiteralValue (boolean, number, string, list or address set reference)
+/// Handles optional whitespace around the value
+fn parse_value<'a>(input: &mut Input<'a>) -> ParserResult<LiteralValue<'a>> {
+	delimited(
+		space0,
+		alt((
+			parse_list,            // "[a, b]"
+			parse_address_set_ref, // "@set_name"
+			parse_scalar_value,    // boolean, number or string
+		)),
+		space0,
+	)
+	.context(StrContext::Expected(StrContextValue::Description(
+		"boolean, number, hex string, string, list or address set",
 	)))
 	.parse_next(input)
 }
@@ -264,9 +391,26 @@ fn parse_value<'a>(input: &mut Input<'a>) -> ParserResult<LiteralValue<'a>> {
 /// Parses a comparison operator (e.g., ==, !=, >, >=, <, <=)
 /// Handles optional whitespace around the operator
 fn parse_comparison_operator(input: &mut Input<'_>) -> ParserResult<ComparisonOperator> {
+	// Keyword operators must be followed by whitespace or the start of their operand
+	let membership_delimiter = || {
+		peek(alt((
+			space1.value(()),
+			literal("[").value(()),
+			literal("@").value(()),
+		)))
+	};
+
 	delimited(
 		space0,
 		alt((
+			(
+				literal(Caseless("not")),
+				space1,
+				literal(Caseless("in")),
+				membership_delimiter(),
+			)
+				.map(|_| ComparisonOperator::NotIn),
+			(literal(Caseless("in")), membership_delimiter()).map(|_| ComparisonOperator::In),
 			literal(Caseless("contains")).map(|_| ComparisonOperator::Contains),
 			literal(Caseless("starts_with")).map(|_| ComparisonOperator::StartsWith),
 			literal(Caseless("ends_with")).map(|_| ComparisonOperator::EndsWith),
@@ -280,7 +424,7 @@ fn parse_comparison_operator(input: &mut Input<'_>) -> ParserResult<ComparisonOp
 		space0,
 	)
 	.context(StrContext::Expected(StrContextValue::Description(
-		"comparison operator (e.g., ==, >, starts_with)",
+		"comparison operator (e.g., ==, >, starts_with, in)",
 	)))
 	.parse_next(input)
 }
@@ -288,6 +432,14 @@ fn parse_comparison_operator(input: &mut Input<'_>) -> ParserResult<ComparisonOp
 /// Parses a condition expression (e.g., "a == 1") into an `Expression::Condition`
 fn parse_condition<'a>(input: &mut Input<'a>) -> ParserResult<Expression<'a>> {
 	let (left, operator, right) = (parse_condition_lhs, parse_comparison_operator, parse_value)
+		// Lists and address sets are only meaningful on the right side of membership operators
+		.verify(|(_, operator, right)| {
+			let is_membership_operator =
+				matches!(operator, ComparisonOperator::In | ComparisonOperator::NotIn);
+			let is_collection_literal =
+				matches!(right, LiteralValue::List(_) | LiteralValue::AddressSet(_));
+			is_membership_operator == is_collection_literal
+		})
 		.context(StrContext::Expected(StrContextValue::Description(
 			"condition expression (e.g., variable == value)",
 		)))
@@ -302,11 +454,221 @@ fn parse_condition<'a>(input: &mut Input<'a>) -> ParserResult<Expression<'a>> {
 	Ok(Expression::Condition(condition))
 }
 
-/// Parses the highest precedence components: conditions and parenthesized expressions
+/// Parses a numeric literal used as an arithmetic operand (e.g., "10", "-1.5", "0xff", "1e18")
+fn parse_arithmetic_number<'a>(input: &mut Input<'a>) -> ParserResult<&'a str> {
+	let hex_number = (
+		alt((literal("0x"), literal("0X"))),
+		take_while(1.., |c: char| c.is_ascii_hexdigit()),
+	);
+	let decimal_number = (
+		digit1,
+		opt((literal("."), digit1)), // Optional fractional part
+		opt((one_of(['e', 'E']), opt(one_of(['+', '-'])), digit1)), // Optional exponent
+	);
+
+	(
+		opt(literal("-")),
+		alt((hex_number.void(), decimal_number.void())),
+		// Ensure the number is not the prefix of an identifier
+		peek(not(one_of(|c: char| c.is_alphanum() || c == '_'))),
+	)
+		.take()
+		.context(StrContext::Expected(StrContextValue::Description(
+			"numeric literal (integer, fixed-point, hex or scientific notation)",
+		)))
+		.parse_next(input)
+}
+
+/// Parses the USD value of a token amount (e.g., "usd(value, USDC)", "usd(amount, 0xa0b8...)")
+/// into `ArithmeticExpression::Usd`
+fn parse_usd<'a>(input: &mut Input<'a>) -> ParserResult<ArithmeticExpression<'a>> {
+	let token = alt((
+		(
+			alt((literal("0x"), literal("0X"))),
+			take_while(1.., |c: char| c.is_ascii_hexdigit()),
+		)
+			.take(),
+		(
+			one_of(|c: char| c.is_alpha() || c == '_'),
+			take_while(0.., |c: char| c.is_alphanum() || c == '_'),
+		)
+			.take()
+			.verify(|symbol: &str| !is_keyword(symbol)),
+	));
+
+	(
+		literal("usd"),
+		(space0, literal("(")),
+		parse_arithmetic_expression,
+		literal(","),
+		delimited(space0, token, space0),
+		literal(")"),
+	)
+		.map(|(_, _, value, _, token, _)| ArithmeticExpression::Usd {
+			value: Box::new(value),
+			token,
+		})
+		.context(StrContext::Expected(StrContextValue::Description(
+			"usd function call (e.g., usd(value, USDC))",
+		)))
+		.parse_next(input)
+}
+
+/// Parses an arithmetic operand: a parenthesized arithmetic expression, a number, a `usd()` call
+/// or a variable
+fn parse_arithmetic_operand<'a>(input: &mut Input<'a>) -> ParserResult<ArithmeticExpression<'a>> {
+	delimited(
+		space0,
+		alt((
+			delimited(
+				(literal("("), space0),
+				parse_arithmetic_expression,
+				(space0, literal(")")),
+			),
+			parse_arithmetic_number.map(ArithmeticExpression::Number),
+			parse_usd,
+			parse_condition_lhs.map(ArithmeticExpression::Variable),
+		)),
+		space0,
+	)
+	.parse_next(input)
+}
+
+/// Parses exponentiation, which is right-associative and binds tighter than other operators
+fn parse_arithmetic_power<'a>(input: &mut Input<'a>) -> ParserResult<ArithmeticExpression<'a>> {
+	let base = parse_arithmetic_operand.parse_next(input)?;
+	let exponent = opt(preceded(literal("^"), parse_arithmetic_power)).parse_next(input)?;
+
+	Ok(match exponent {
+		Some(exponent) => ArithmeticExpression::Binary {
+			left: Box::new(base),
+			operator: ArithmeticOperator::Pow,
+			right: Box::new(exponent),
+		},
+		None => base,
+	})
+}
+
+/// Parses multiplication and division (left-associative)
+fn parse_arithmetic_product<'a>(input: &mut Input<'a>) -> ParserResult<ArithmeticExpression<'a>> {
+	let left = parse_arithmetic_power.parse_next(input)?;
+	let operator = alt((
+		literal("*").value(ArithmeticOperator::Mul),
+		literal("/").value(ArithmeticOperator::Div),
+	));
+
+	repeat(0.., (operator, parse_arithmetic_power))
+		.fold(
+			move || left.clone(),
+			|acc, (operator, right)| ArithmeticExpression::Binary {
+				left: Box::new(acc),
+				operator,
+				right: Box::new(right),
+			},
+		)
+		.parse_next(input)
+}
+
+/// Parses addition and subtraction (left-associative, lowest arithmetic precedence)
+fn parse_arithmetic_expression<'a>(
+	input: &mut Input<'a>,
+) -> ParserResult<ArithmeticExpression<'a>> {
+	let left = parse_arithmetic_product.parse_next(input)?;
+	let operator = alt((
+		literal("+").value(ArithmeticOperator::Add),
+		literal("-").value(ArithmeticOperator::Sub),
+	));
+
+	repeat(0.., (operator, parse_arithmetic_product))
+		.fold(
+			move || left.clone(),
+			|acc, (operator, right)| ArithmeticExpression::Binary {
+				left: Box::new(acc),
+				operator,
+				right: Box::new(right),
+			},
+		)
+		.context(StrContext::Expected(StrContextValue::Description(
+			"arithmetic expression (e.g., a + b * 2)",
+		)))
+		.parse_next(input)
+}
+
+/// Parses a comparison where at least one side is an arithmetic operation or a `usd()` call
+/// (e.g., "value / 10^18 > 100") into an `Expression::Arithmetic`
+fn parse_arithmetic_condition<'a>(input: &mut Input<'a>) -> ParserResult<Expression<'a>> {
+	let (left, operator, right) = (
+		parse_arithmetic_expression,
+		parse_comparison_operator,
+		parse_arithmetic_expression,
+	)
+		// Plain operands on both sides are regular conditions
+		.verify(|(left, _, right)| {
+			let is_computed = |side: &ArithmeticExpression<'_>| {
+				matches!(
+					side,
+					ArithmeticExpression::Binary { .. } | ArithmeticExpression::Usd { .. }
+				)
+			};
+			is_computed(left) || is_computed(right)
+		})
+		.context(StrContext::Expected(StrContextValue::Description(
+			"arithmetic condition (e.g., a + b > 10)",
+		)))
+		.parse_next(input)?;
+
+	Ok(Expression::Arithmetic(ArithmeticCondition {
+		left,
+		operator,
+		right,
+	}))
+}
+
+/// Ensures a condition spans the whole term, so that trailing arithmetic (e.g., "a > b - 1")
+/// is parsed as an arithmetic condition instead
+fn parse_term_end(input: &mut Input<'_>) -> ParserResult<()> {
+	peek((
+		space0,
+		alt((
+			eof.void(),
+			literal(")").void(),
+			literal(Caseless("AND")).void(),
+			literal(Caseless("OR")).void(),
+		)),
+	))
+	.void()
+	.parse_next(input)
+}
+
+/// Parses a negated term (e.g., "not a == 1", "!(a == 1 OR b == 2)") into an `Expression::Not`
+/// Negation binds tighter than AND/OR and applies to the term that immediately follows it
+fn parse_not<'a>(input: &mut Input<'a>) -> ParserResult<Expression<'a>> {
+	let not_operator = alt((
+		// Keyword form must be followed by whitespace or an opening parenthesis
+		(
+			literal(Caseless("not")),
+			peek(alt((space1.value(()), literal("(").value(())))),
+		)
+			.value(()),
+		// Symbolic form, making sure not to consume the `!=` operator
+		(literal("!"), peek(not(literal("=")))).value(()),
+	));
+
+	preceded(not_operator, parse_term)
+		.map(|inner| Expression::Not(Box::new(inner)))
+		.context(StrContext::Expected(StrContextValue::Description(
+			"negated expression (e.g., not a == 1)",
+		)))
+		.parse_next(input)
+}
+
+/// Parses the highest precedence components: negations, conditions and parenthesized expressions
 fn parse_term<'a>(input: &mut Input<'a>) -> ParserResult<Expression<'a>> {
 	delimited(
 		space0,
 		alt((
+			// Parse a negated term
+			parse_not,
 			// Parse a parenthesized expression
 			delimited(
 				(literal("("), space0),
@@ -316,12 +678,14 @@ fn parse_term<'a>(input: &mut Input<'a>) -> ParserResult<Expression<'a>> {
 				))),
 			),
 			// Parse a condition
-			parse_condition,
+			terminated(parse_condition, parse_term_end),
+			// Parse a comparison involving arithmetic
+			parse_arithmetic_condition,
 		)),
 		space0,
 	)
 	.context(StrContext::Expected(StrContextValue::Description(
-		"condition or parenthesized expression",
+		"condition, arithmetic condition, negation or parenthesized expression",
 	)))
 	.parse_next(input)
 }
@@ -401,6 +765,14 @@ pub fn parse(expression_str: &str) -> Result<Expression<'_>, ParseError<Input<'_
 	full_expression_parser.parse(expression_str)
 }
 
+/// Returns the names of the address sets referenced by an expression, or an empty list if the
+/// expression cannot be parsed
+pub fn address_set_references(expression_str: &str) -> Vec<&str> {
+	parse(expression_str)
+		.map(|expression| expression.address_set_names())
+		.unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -520,6 +892,31 @@ mod tests {
 		assert_parse_fails(parse_number_or_fixed_str, "5."); // Requires digit after .
 	}
 
+	#[test]
+	fn test_parse_stellar_address() {
+		let account = "GDF32CQINROD3E2LMCGZUDVMWTXCJFR5SBYVRJ7WAAIAS3P7DCVWZEFY";
+		let contract = "CAS3J7GYLGXMF6TDJBBYYSE3HQ6BBSMLNUQ34T6TZMYMW2EVH34XOWMA";
+		assert_parses_ok(
+			parse_stellar_address,
+			account,
+			LiteralValue::Str(account),
+			"",
+		);
+		assert_parses_ok(
+			parse_stellar_address,
+			&format!("{} ", contract),
+			LiteralValue::Str(contract),
+			" ",
+		);
+
+		assert_parse_fails(parse_stellar_address, "GDF32CQ"); // Too short
+		assert_parse_fails(parse_stellar_address, "amount"); // Variable name
+		assert_parse_fails(
+			parse_stellar_address,
+			"XDF32CQINROD3E2LMCGZUDVMWTXCJFR5SBYVRJ7WAAIAS3P7DCVWZEFY",
+		); // Unknown version byte
+	}
+
 	#[test]
 	fn test_parse_hex_string() {
 		// Success cases
@@ -634,6 +1031,7 @@ mod tests {
 		assert!(is_keyword("FALSE"));
 		assert!(is_keyword("AnD"));
 		assert!(is_keyword("cOnTaiNs"));
+		assert!(is_keyword("Not"));
 		// Failures
 		assert!(!is_keyword("trueish"));
 		assert!(!is_keyword("variable"));
@@ -770,7 +1168,7 @@ mod tests {
 
 	#[test]
 	fn test_parse_value_alt_order() {
-		// Order: quoted_string, boolean, hex_string, number_or_fixed, unquoted_string
+		// Order: quoted_string, boolean, hex_string, number_or_fixed, variable, unquoted_string
 		assert_parses_ok(parse_value, " 'hello' ", LiteralValue::Str("hello"), "");
 		assert_parses_ok(parse_value, " true ", LiteralValue::Bool(true), "");
 		assert_parses_ok(parse_value, " 0xAB ", LiteralValue::Str("0xAB"), "");
@@ -779,10 +1177,30 @@ mod tests {
 		assert_parses_ok(
 			parse_value,
 			" unquoted_val ",
-			LiteralValue::Str("unquoted_val"),
+			LiteralValue::Variable(ConditionLeft::Simple("unquoted_val")),
+			"",
+		); // Identifiers are variable references, resolved as strings if no such parameter exists
+		assert_parses_ok(
+			parse_value,
+			" true_val ",
+			LiteralValue::Variable(ConditionLeft::Simple("true_val")),
+			"",
+		); // 'true' is prefix of 'true_val', boolean is tried, fails, then variable.
+		assert_parses_ok(
+			parse_value,
+			" args.spender ",
+			LiteralValue::Variable(ConditionLeft::Path(VariablePath {
+				base: "args",
+				accessors: vec![Accessor::Key("spender")],
+			})),
 			"",
 		);
-		assert_parses_ok(parse_value, " true_val ", LiteralValue::Str("true_val"), ""); // 'true' is prefix of 'true_val', boolean is tried, fails, then unquoted.
+		assert_parses_ok(
+			parse_value,
+			" unquoted-val ",
+			LiteralValue::Str("unquoted-val"),
+			"",
+		); // Not a valid variable name, falls back to unquoted string.
 		assert_parses_ok(parse_value, " 0xVal ", LiteralValue::Str("0xVal"), ""); // '0x' is prefix, hex is tried, fails on 'V', then unquoted.
 	}
 
@@ -950,6 +1368,490 @@ mod tests {
 		assert_eq!(parse(expr_parens).unwrap(), expected_parens);
 	}
 
+	#[test]
+	fn test_parse_list() {
+		assert_parses_ok(
+			parse_list,
+			"[0xabc, 'bob' , 12]",
+			LiteralValue::List(vec![
+				LiteralValue::Str("0xabc"),
+				LiteralValue::Str("bob"),
+				LiteralValue::Number("12"),
+			]),
+			"",
+		);
+		assert_parses_ok(parse_list, "[]", LiteralValue::List(vec![]), "");
+		assert_parses_ok(
+			parse_list,
+			"[ true ]",
+			LiteralValue::List(vec![LiteralValue::Bool(true)]),
+			"",
+		);
+
+		assert_parse_fails(parse_list, "[0xabc"); // Missing closing bracket
+		assert_parse_fails(parse_list, "[0xabc,, 0xdef]"); // Empty item
+		assert_parse_fails(parse_list, "[[0xabc]]"); // Nested lists are not supported
+	}
+
+	#[test]
+	fn test_parse_address_set_ref() {
+		assert_parses_ok(
+			parse_address_set_ref,
+			"@hot_wallets",
+			LiteralValue::AddressSet("hot_wallets"),
+			"",
+		);
+		assert_parses_ok(
+			parse_address_set_ref,
+			"@_set1 ",
+			LiteralValue::AddressSet("_set1"),
+			" ",
+		);
+
+		assert_parse_fails(parse_address_set_ref, "hot_wallets"); // Missing @
+		assert_parse_fails(parse_address_set_ref, "@1set"); // Cannot start with digit
+		assert_parse_fails(parse_address_set_ref, "@"); // Empty name
+	}
+
+	#[test]
+	fn test_address_set_references() {
+		assert_eq!(
+			address_set_references(
+				"to in @hot_wallets OR (from not in @exchanges AND NOT (to in @cold))"
+			),
+			vec!["hot_wallets", "exchanges", "cold"]
+		);
+		assert!(address_set_references("value > 100").is_empty());
+		assert!(address_set_references("to in @").is_empty());
+	}
+
+	#[test]
+	fn test_parse_token_amount() {
+		assert_parses_ok(
+			parse_token_amount,
+			"10000 USDC",
+			LiteralValue::TokenAmount {
+				amount: "10000",
+				symbol: "USDC",
+			},
+			"",
+		);
+		assert_parses_ok(
+			parse_token_amount,
+			"1.5  weth)",
+			LiteralValue::TokenAmount {
+				amount: "1.5",
+				symbol: "weth",
+			},
+			")",
+		);
+
+		assert_parse_fails(parse_token_amount, "10000"); // Missing symbol
+		assert_parse_fails(parse_token_amount, "10000USDC"); // Missing separator
+		assert_parse_fails(parse_token_amount, "-1 USDC"); // Negative amount
+		assert_parse_fails(parse_token_amount, "10 and"); // Keyword
+		assert_parse_fails(parse_token_amount, "10 USD-C"); // Invalid symbol
+	}
+
+	#[test]
+	fn test_parse_token_amount_conditions() {
+		assert_eq!(
+			parse("value > 10000 USDC AND to == 0xabc").unwrap(),
+			Expression::Logical {
+				left: Box::new(Expression::Condition(Condition {
+					left: ConditionLeft::Simple("value"),
+					operator: ComparisonOperator::Gt,
+					right: LiteralValue::TokenAmount {
+						amount: "10000",
+						symbol: "USDC",
+					},
+				})),
+				operator: LogicalOperator::And,
+				right: Box::new(Expression::Condition(Condition {
+					left: ConditionLeft::Simple("to"),
+					operator: ComparisonOperator::Eq,
+					right: LiteralValue::Str("0xabc"),
+				})),
+			}
+		);
+		assert_eq!(
+			parse("value > 10 or value < 1").unwrap(),
+			Expression::Logical {
+				left: Box::new(Expression::Condition(Condition {
+					left: ConditionLeft::Simple("value"),
+					operator: ComparisonOperator::Gt,
+					right: LiteralValue::Number("10"),
+				})),
+				operator: LogicalOperator::Or,
+				right: Box::new(Expression::Condition(Condition {
+					left: ConditionLeft::Simple("value"),
+					operator: ComparisonOperator::Lt,
+					right: LiteralValue::Number("1"),
+				})),
+			}
+		);
+	}
+
+	#[test]
+	fn test_parse_membership_conditions() {
+		assert_eq!(
+			parse("to in @hot_wallets").unwrap(),
+			Expression::Condition(Condition {
+				left: ConditionLeft::Simple("to"),
+				operator: ComparisonOperator::In,
+				right: LiteralValue::AddressSet("hot_wallets"),
+			})
+		);
+		assert_eq!(
+			parse("from NOT IN [0xabc, 0xdef]").unwrap(),
+			Expression::Condition(Condition {
+				left: ConditionLeft::Simple("from"),
+				operator: ComparisonOperator::NotIn,
+				right: LiteralValue::List(vec![
+					LiteralValue::Str("0xabc"),
+					LiteralValue::Str("0xdef"),
+				]),
+			})
+		);
+		assert_eq!(
+			parse("to in[0xabc] AND value > 10").unwrap(),
+			Expression::Logical {
+				left: Box::new(Expression::Condition(Condition {
+					left: ConditionLeft::Simple("to"),
+					operator: ComparisonOperator::In,
+					right: LiteralValue::List(vec![LiteralValue::Str("0xabc")]),
+				})),
+				operator: LogicalOperator::And,
+				right: Box::new(Expression::Condition(Condition {
+					left: ConditionLeft::Simple("value"),
+					operator: ComparisonOperator::Gt,
+					right: LiteralValue::Number("10"),
+				})),
+			}
+		);
+
+		// Collections are only valid with membership operators and vice versa
+		assert!(parse("to == @hot_wallets").is_err());
+		assert!(parse("to == [0xabc]").is_err());
+		assert!(parse("to in 0xabc").is_err());
+		assert!(parse("to inside [0xabc]").is_err());
+	}
+
+	#[test]
+	fn test_parse_not_expressions() {
+		let cond = |name| {
+			Expression::Condition(Condition {
+				left: ConditionLeft::Simple(name),
+				operator: ComparisonOperator::Eq,
+				right: LiteralValue::Number("1"),
+			})
+		};
+
+		// Keyword and symbolic forms over a comparison
+		assert_eq!(
+			parse("not a == 1").unwrap(),
+			Expression::Not(Box::new(cond("a")))
+		);
+		assert_eq!(
+			parse("NOT a == 1").unwrap(),
+			Expression::Not(Box::new(cond("a")))
+		);
+		assert_eq!(
+			parse("!a == 1").unwrap(),
+			Expression::Not(Box::new(cond("a")))
+		);
+
+		// Over a parenthesized sub-expression
+		let expected_group = Expression::Not(Box::new(Expression::Logical {
+			left: Box::new(cond("a")),
+			operator: LogicalOperator::Or,
+			right: Box::new(cond("b")),
+		}));
+		assert_eq!(parse("not (a == 1 OR b == 1)").unwrap(), expected_group);
+		assert_eq!(parse("not(a == 1 OR b == 1)").unwrap(), expected_group);
+		assert_eq!(parse("!(a == 1 OR b == 1)").unwrap(), expected_group);
+
+		// Negation binds tighter than AND
+		assert_eq!(
+			parse("not a == 1 AND b == 1").unwrap(),
+			Expression::Logical {
+				left: Box::new(Expression::Not(Box::new(cond("a")))),
+				operator: LogicalOperator::And,
+				right: Box::new(cond("b")),
+			}
+		);
+
+		// Double negation
+		assert_eq!(
+			parse("!!a == 1").unwrap(),
+			Expression::Not(Box::new(Expression::Not(Box::new(cond("a")))))
+		);
+
+		// Variables starting with "not" are still valid
+		assert_eq!(parse("note == 1").unwrap(), cond("note"));
+		assert_eq!(parse("not_a == 1").unwrap(), cond("not_a"));
+
+		// `!=` and `not in` keep their meaning
+		assert_eq!(
+			parse("a != 1").unwrap(),
+			Expression::Condition(Condition {
+				left: ConditionLeft::Simple("a"),
+				operator: ComparisonOperator::Ne,
+				right: LiteralValue::Number("1"),
+			})
+		);
+		assert!(parse("not a not in [1]").is_ok());
+
+		assert!(parse("not").is_err());
+		assert!(parse("!").is_err());
+		assert!(parse("a == 1 not").is_err());
+		assert!(parse("not == 1").is_err());
+	}
+
+	#[test]
+	fn test_parse_arithmetic_number() {
+		assert_parses_ok(parse_arithmetic_number, "123", "123", "");
+		assert_parses_ok(parse_arithmetic_number, "-1.5 ", "-1.5", " ");
+		assert_parses_ok(parse_arithmetic_number, "1e21)", "1e21", ")");
+		assert_parses_ok(parse_arithmetic_number, "2.5E-3", "2.5E-3", "");
+		assert_parses_ok(parse_arithmetic_number, "0xff*", "0xff", "*");
+
+		assert_parse_fails(parse_arithmetic_number, "abc");
+		assert_parse_fails(parse_arithmetic_number, "1abc"); // Identifier, not a number
+		assert_parse_fails(parse_arithmetic_number, "0xfg");
+	}
+
+	#[test]
+	fn test_parse_arithmetic_conditions() {
+		let var = |name| ArithmeticExpression::Variable(ConditionLeft::Simple(name));
+		let binary = |left, operator, right| ArithmeticExpression::Binary {
+			left: Box::new(left),
+			operator,
+			right: Box::new(right),
+		};
+
+		// Exponentiation binds tighter than division
+		assert_eq!(
+			parse("value / 10^decimals > 10000").unwrap(),
+			Expression::Arithmetic(ArithmeticCondition {
+				left: binary(
+					var("value"),
+					ArithmeticOperator::Div,
+					binary(
+						ArithmeticExpression::Number("10"),
+						ArithmeticOperator::Pow,
+						var("decimals")
+					)
+				),
+				operator: ComparisonOperator::Gt,
+				right: ArithmeticExpression::Number("10000"),
+			})
+		);
+
+		// Multiplication binds tighter than addition, parentheses override precedence
+		assert_eq!(
+			parse("a + b * 2 >= (a + b) * 2").unwrap(),
+			Expression::Arithmetic(ArithmeticCondition {
+				left: binary(
+					var("a"),
+					ArithmeticOperator::Add,
+					binary(
+						var("b"),
+						ArithmeticOperator::Mul,
+						ArithmeticExpression::Number("2")
+					)
+				),
+				operator: ComparisonOperator::Gte,
+				right: binary(
+					binary(var("a"), ArithmeticOperator::Add, var("b")),
+					ArithmeticOperator::Mul,
+					ArithmeticExpression::Number("2")
+				),
+			})
+		);
+
+		// Left associativity for subtraction, right associativity for powers
+		assert_eq!(
+			parse("a - b - c == 2 ^ 3 ^ 2").unwrap(),
+			Expression::Arithmetic(ArithmeticCondition {
+				left: binary(
+					binary(var("a"), ArithmeticOperator::Sub, var("b")),
+					ArithmeticOperator::Sub,
+					var("c")
+				),
+				operator: ComparisonOperator::Eq,
+				right: binary(
+					ArithmeticExpression::Number("2"),
+					ArithmeticOperator::Pow,
+					binary(
+						ArithmeticExpression::Number("3"),
+						ArithmeticOperator::Pow,
+						ArithmeticExpression::Number("2")
+					)
+				),
+			})
+		);
+
+		// Arithmetic only on the right side, combined with logical operators and paths
+		assert_eq!(
+			parse("a > 1 AND tx.gas_used * tx.gas_price > 1e18").unwrap(),
+			Expression::Logical {
+				left: Box::new(Expression::Condition(Condition {
+					left: ConditionLeft::Simple("a"),
+					operator: ComparisonOperator::Gt,
+					right: LiteralValue::Number("1"),
+				})),
+				operator: LogicalOperator::And,
+				right: Box::new(Expression::Arithmetic(ArithmeticCondition {
+					left: binary(
+						ArithmeticExpression::Variable(ConditionLeft::Path(VariablePath {
+							base: "tx",
+							accessors: vec![Accessor::Key("gas_used")],
+						})),
+						ArithmeticOperator::Mul,
+						ArithmeticExpression::Variable(ConditionLeft::Path(VariablePath {
+							base: "tx",
+							accessors: vec![Accessor::Key("gas_price")],
+						}))
+					),
+					operator: ComparisonOperator::Gt,
+					right: ArithmeticExpression::Number("1e18"),
+				})),
+			}
+		);
+		assert!(matches!(
+			parse("a > b - 1").unwrap(),
+			Expression::Arithmetic(_)
+		));
+
+		// Plain conditions keep their meaning, including hyphenated unquoted strings
+		assert_eq!(
+			parse("name == foo-bar").unwrap(),
+			Expression::Condition(Condition {
+				left: ConditionLeft::Simple("name"),
+				operator: ComparisonOperator::Eq,
+				right: LiteralValue::Str("foo-bar"),
+			})
+		);
+		assert!(matches!(
+			parse("(a == 1)").unwrap(),
+			Expression::Condition(_)
+		));
+
+		assert!(parse("a + > 1").is_err());
+		assert!(parse("a + 1").is_err());
+		assert!(parse("(a + 1 > 2").is_err());
+		assert!(parse("a + 'text' > 2").is_err());
+	}
+
+	#[test]
+	fn test_parse_usd() {
+		let usd = |value, token| ArithmeticExpression::Usd {
+			value: Box::new(value),
+			token,
+		};
+		let var = |name| ArithmeticExpression::Variable(ConditionLeft::Simple(name));
+
+		assert_eq!(
+			parse("usd(value, USDC) > 1000000").unwrap(),
+			Expression::Arithmetic(ArithmeticCondition {
+				left: usd(var("value"), "USDC"),
+				operator: ComparisonOperator::Gt,
+				right: ArithmeticExpression::Number("1000000"),
+			})
+		);
+		assert_eq!(
+			parse(
+				"usd (amount0In + amount1In , 0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48) >= 1e6"
+			)
+			.unwrap(),
+			Expression::Arithmetic(ArithmeticCondition {
+				left: usd(
+					ArithmeticExpression::Binary {
+						left: Box::new(var("amount0In")),
+						operator: ArithmeticOperator::Add,
+						right: Box::new(var("amount1In")),
+					},
+					"0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"
+				),
+				operator: ComparisonOperator::Gte,
+				right: ArithmeticExpression::Number("1e6"),
+			})
+		);
+		assert!(matches!(
+			parse("to == 0xabc AND usd(value, ETH) > usd(limit, ETH) * 2").unwrap(),
+			Expression::Logical { .. }
+		));
+
+		// A variable named `usd` keeps its meaning
+		assert_eq!(
+			parse("usd > 5").unwrap(),
+			Expression::Condition(Condition {
+				left: ConditionLeft::Simple("usd"),
+				operator: ComparisonOperator::Gt,
+				right: LiteralValue::Number("5"),
+			})
+		);
+
+		assert!(parse("usd(value) > 5").is_err());
+		assert!(parse("usd(value, 'USDC') > 5").is_err());
+		assert!(parse("usd(value, USDC > 5").is_err());
+	}
+
+	#[test]
+	fn test_parse_variable_comparisons() {
+		assert_eq!(
+			parse("from == to").unwrap(),
+			Expression::Condition(Condition {
+				left: ConditionLeft::Simple("from"),
+				operator: ComparisonOperator::Eq,
+				right: LiteralValue::Variable(ConditionLeft::Simple("to")),
+			})
+		);
+		assert_eq!(
+			parse("args.spender != transaction.from").unwrap(),
+			Expression::Condition(Condition {
+				left: ConditionLeft::Path(VariablePath {
+					base: "args",
+					accessors: vec![Accessor::Key("spender")],
+				}),
+				operator: ComparisonOperator::Ne,
+				right: LiteralValue::Variable(ConditionLeft::Path(VariablePath {
+					base: "transaction",
+					accessors: vec![Accessor::Key("from")],
+				})),
+			})
+		);
+		assert_eq!(
+			parse("to in [0xabc, owners[0]]").unwrap(),
+			Expression::Condition(Condition {
+				left: ConditionLeft::Simple("to"),
+				operator: ComparisonOperator::In,
+				right: LiteralValue::List(vec![
+					LiteralValue::Str("0xabc"),
+					LiteralValue::Variable(ConditionLeft::Path(VariablePath {
+						base: "owners",
+						accessors: vec![Accessor::Index(0)],
+					})),
+				]),
+			})
+		);
+		// Quoted values are always strings
+		assert_eq!(
+			parse("from == 'to'").unwrap(),
+			Expression::Condition(Condition {
+				left: ConditionLeft::Simple("from"),
+				operator: ComparisonOperator::Eq,
+				right: LiteralValue::Str("to"),
+			})
+		);
+		// Trailing arithmetic still produces an arithmetic condition
+		assert!(matches!(
+			parse("amountOut < amountOutMin - 1").unwrap(),
+			Expression::Arithmetic(_)
+		));
+	}
+
 	#[test]
 	fn test_full_parse_with_eof() {
 		assert!(parse("var == 123").is_ok());
@@ -8,15 +8,15 @@
 
 use std::collections::HashMap;
 
-use alloy::primitives::Address;
+use alloy::primitives::{Address, U256};
 use serde_json::{json, Value as JsonValue};
 
 use crate::{
-	models::{MonitorMatch, ScriptLanguage},
+	models::{EVMMonitorMatch, MonitorMatch, ScriptLanguage},
 	services::{
 		filter::{
 			evm_helpers::{b256_to_string, h160_to_string},
-			FilterError,
+			token_metadata, FilterError, TokenMetadata, PREVIOUS_SUFFIX,
 		},
 		trigger::TriggerExecutionServiceTrait,
 	},
@@ -24,9 +24,8 @@ use crate::{
 
 /// Process a monitor match by executing associated triggers.
 ///
-/// Takes a matched monitor event and processes it through the appropriate trigger service.
-/// Converts blockchain-specific data into a standardized format that can be used in trigger
-/// templates.
+/// Takes a matched monitor event and processes it through the appropriate trigger service,
+/// using the template variables built by [`build_match_variables`].
 ///
 /// # Arguments
 /// * `matching_monitor` - The matched monitor event containing transaction and trigger information
@@ -35,6 +34,66 @@ use crate::{
 ///
 /// # Returns
 /// Result indicating success or failure of trigger execution
+pub async fn handle_match<T: TriggerExecutionServiceTrait>(
+	matching_monitor: MonitorMatch,
+	trigger_service: &T,
+	trigger_scripts: &HashMap<String, (ScriptLanguage, String)>,
+) -> Result<(), FilterError> {
+	let variables = build_match_variables(&matching_monitor);
+	handle_match_with_variables(
+		matching_monitor,
+		variables,
+		trigger_service,
+		trigger_scripts,
+	)
+	.await
+}
+
+/// Process a monitor match by executing associated triggers with the given template variables.
+///
+/// # Arguments
+/// * `matching_monitor` - The matched monitor event containing transaction and trigger information
+/// * `variables` - Template variables of the match
+/// * `trigger_service` - Service responsible for executing triggers
+/// * `trigger_scripts` - Scripts to be executed for each trigger
+///
+/// # Returns
+/// Result indicating success or failure of trigger execution
+pub async fn handle_match_with_variables<T: TriggerExecutionServiceTrait>(
+	matching_monitor: MonitorMatch,
+	variables: HashMap<String, String>,
+	trigger_service: &T,
+	trigger_scripts: &HashMap<String, (ScriptLanguage, String)>,
+) -> Result<(), FilterError> {
+	let triggers = match &matching_monitor {
+		MonitorMatch::EVM(evm_monitor_match) => &evm_monitor_match.monitor.triggers,
+		MonitorMatch::Stellar(stellar_monitor_match) => &stellar_monitor_match.monitor.triggers,
+		MonitorMatch::Poll(poll_monitor_match) => &poll_monitor_match.monitor.triggers,
+	};
+
+	// Swallow any errors since it's logged in the trigger service and we want to continue
+	// processing other matches
+	let _ = trigger_service
+		.execute(
+			&triggers.iter().map(|s| s.to_string()).collect::<Vec<_>>(),
+			variables,
+			&matching_monitor,
+			trigger_scripts,
+		)
+		.await;
+	Ok(())
+}
+
+/// Builds the template variables of a monitor match.
+///
+/// Converts blockchain-specific data into a standardized format that can be used in trigger
+/// templates.
+///
+/// # Arguments
+/// * `matching_monitor` - The matched monitor event containing transaction information
+///
+/// # Returns
+/// Flattened template variables keyed by dotted path
 ///
 /// # Example
 /// The function converts blockchain data into template variables like:
@@ -48,13 +107,18 @@ use crate::{
 /// "events.0.args.to": "0x70bf6634ee8cb27d04478f184b9b8bb13e5f4710"
 /// "events.0.args.from": "0x2e8135be71230c6b1b4045696d41c09db0414226"
 /// "events.0.args.value": "88248701"
+/// "token.symbol": "USDT"
+/// "token.decimals": "6"
 /// ```
-pub async fn handle_match<T: TriggerExecutionServiceTrait>(
-	matching_monitor: MonitorMatch,
-	trigger_service: &T,
-	trigger_scripts: &HashMap<String, (ScriptLanguage, String)>,
-) -> Result<(), FilterError> {
-	match &matching_monitor {
+///
+/// Matches of polling monitors expose the values read as `"state.balance": "1000"`, the values
+/// of the previous poll as `"state.balance_previous": "1500"` and the block as
+/// `"poll.block_number"` and `"poll.timestamp"`.
+///
+/// Fields trigger condition scripts enriched the match with are added under `enrichment`, for
+/// instance `"enrichment.risk.score": "87"`.
+pub fn build_match_variables(matching_monitor: &MonitorMatch) -> HashMap<String, String> {
+	let mut variables = match matching_monitor {
 		MonitorMatch::EVM(evm_monitor_match) => {
 			let transaction = evm_monitor_match.transaction.clone();
 			// If sender does not exist, we replace with 0x0000000000000000000000000000000000000000
@@ -79,6 +143,15 @@ pub async fn handle_match<T: TriggerExecutionServiceTrait>(
 				data_json["transaction"]["to"] = json!(h160_to_string(*to));
 			}
 
+			// Add the metadata of the token the match is about, if known
+			if let Some((address, token)) = find_match_token(evm_monitor_match) {
+				data_json["token"] = json!({
+					"address": address,
+					"symbol": token.symbol,
+					"decimals": token.decimals.to_string(),
+				});
+			}
+
 			// Process matched functions
 			let functions = data_json["functions"].as_array_mut().unwrap();
 			for func in evm_monitor_match.matched_on.functions.iter() {
@@ -133,21 +206,7 @@ pub async fn handle_match<T: TriggerExecutionServiceTrait>(
 				events.push(event_data);
 			}
 
-			// Swallow any errors since it's logged in the trigger service and we want to continue
-			// processing other matches
-			let _ = trigger_service
-				.execute(
-					&evm_monitor_match
-						.monitor
-						.triggers
-						.iter()
-						.map(|s| s.to_string())
-						.collect::<Vec<_>>(),
-					json_to_hashmap(&data_json),
-					&matching_monitor,
-					trigger_scripts,
-				)
-				.await;
+			json_to_hashmap(&data_json)
 		}
 		MonitorMatch::Stellar(stellar_monitor_match) => {
 			let transaction = stellar_monitor_match.transaction.clone();
@@ -218,24 +277,68 @@ pub async fn handle_match<T: TriggerExecutionServiceTrait>(
 				events.push(event_data);
 			}
 
-			// Swallow any errors since it's logged in the trigger service and we want to continue
-			// processing other matches
-			let _ = trigger_service
-				.execute(
-					&stellar_monitor_match
-						.monitor
-						.triggers
-						.iter()
-						.map(|s| s.to_string())
-						.collect::<Vec<_>>(),
-					json_to_hashmap(&data_json),
-					&matching_monitor,
-					trigger_scripts,
-				)
-				.await;
+			json_to_hashmap(&data_json)
+		}
+		MonitorMatch::Poll(poll_monitor_match) => {
+			let mut data_json = json!({
+				"monitor": {
+					"name": poll_monitor_match.monitor.name.clone(),
+				},
+				"poll": {
+					"block_number": poll_monitor_match.block_number.to_string(),
+				},
+				"state": {}
+			});
+
+			if let Some(timestamp) = poll_monitor_match.timestamp {
+				data_json["poll"]["timestamp"] = json!(timestamp.to_string());
+			}
+
+			// Values read by the poll, along with the values of the previous poll
+			let state = data_json["state"].as_object_mut().unwrap();
+			for value in &poll_monitor_match.values {
+				state.insert(value.name.clone(), json!(value.value.clone()));
+				state.insert(
+					format!("{}{}", value.name, PREVIOUS_SUFFIX),
+					json!(value.previous.clone()),
+				);
+			}
+
+			json_to_hashmap(&data_json)
+		}
+	};
+
+	// Fields returned by trigger condition scripts are kept apart from the variables of the match
+	if let Some(enrichment) = matching_monitor.enrichment() {
+		for (key, value) in enrichment {
+			flatten_json_path(value, &format!("enrichment.{}", key), &mut variables);
 		}
 	}
-	Ok(())
+
+	variables
+}
+
+/// Finds the registered token an EVM match is about: the contract that emitted the first
+/// matched event, or else the contract the transaction was sent to
+fn find_match_token(evm_monitor_match: &EVMMonitorMatch) -> Option<(String, TokenMetadata)> {
+	let event_addresses = evm_monitor_match
+		.matched_on_args
+		.iter()
+		.flat_map(|args| args.events.iter().flatten())
+		.filter_map(|event| {
+			let log_index = event.log_index?;
+			evm_monitor_match
+				.logs
+				.iter()
+				.flatten()
+				.find(|log| log.log_index == Some(U256::from(log_index)))
+				.map(|log| log.address)
+		});
+
+	event_addresses
+		.chain(evm_monitor_match.transaction.to().copied())
+		.map(h160_to_string)
+		.find_map(|address| token_metadata(&address).map(|token| (address, token)))
 }
 
 /// Converts a JsonValue to a flattened HashMap with dotted path notation
@@ -284,6 +387,13 @@ fn insert_primitive<T: ToString>(prefix: &str, result: &mut HashMap<String, Stri
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::{
+		models::{
+			EVMMatchArguments, EVMMatchParamsMap, EVMMonitorMatch, EVMReceiptLog, MatchConditions,
+			PollMonitorMatch, PollValue,
+		},
+		utils::tests::evm::{monitor::MonitorBuilder, transaction::TransactionBuilder},
+	};
 	use serde_json::json;
 
 	#[test]
@@ -530,4 +640,119 @@ mod tests {
 		insert_primitive("", &mut result8, JsonValue::Null);
 		assert_eq!(result8["value"], "null");
 	}
+
+	#[test]
+	fn test_build_match_variables_with_enrichment() {
+		let mut monitor_match = MonitorMatch::EVM(Box::new(EVMMonitorMatch {
+			monitor: MonitorBuilder::new().name("Test Monitor").build(),
+			transaction: TransactionBuilder::new().build(),
+			receipt: None,
+			logs: None,
+			network_slug: "ethereum_mainnet".to_string(),
+			matched_on: MatchConditions::default(),
+			matched_on_args: None,
+			enrichment: None,
+		}));
+		monitor_match.enrich(
+			json!({
+				"price_usd": 1.5,
+				"risk": { "score": 87 },
+				"monitor": { "name": "Spoofed" }
+			})
+			.as_object()
+			.unwrap()
+			.clone(),
+		);
+
+		let variables = build_match_variables(&monitor_match);
+		assert_eq!(variables["enrichment.price_usd"], "1.5");
+		assert_eq!(variables["enrichment.risk.score"], "87");
+		// Enrichment never replaces the variables of the match
+		assert_eq!(variables["monitor.name"], "Test Monitor");
+		assert_eq!(variables["enrichment.monitor.name"], "Spoofed");
+	}
+
+	#[test]
+	fn test_build_match_variables_with_poll() {
+		let monitor_match = MonitorMatch::Poll(Box::new(PollMonitorMatch {
+			monitor: MonitorBuilder::new().name("Hot Wallet").build(),
+			network_slug: "ethereum_mainnet".to_string(),
+			block_number: 120,
+			timestamp: Some(1700000000),
+			values: vec![PollValue {
+				name: "balance".to_string(),
+				value: "1000".to_string(),
+				previous: "1500".to_string(),
+				kind: "uint256".to_string(),
+			}],
+			enrichment: None,
+		}));
+
+		let variables = build_match_variables(&monitor_match);
+		assert_eq!(variables["monitor.name"], "Hot Wallet");
+		assert_eq!(variables["poll.block_number"], "120");
+		assert_eq!(variables["poll.timestamp"], "1700000000");
+		assert_eq!(variables["state.balance"], "1000");
+		assert_eq!(variables["state.balance_previous"], "1500");
+	}
+
+	#[test]
+	fn test_build_match_variables_with_token() {
+		let token_address = Address::repeat_byte(0x47);
+		let router_address = Address::repeat_byte(0x48);
+		crate::services::filter::register_tokens(vec![crate::models::Token {
+			symbol: "MATCHTOKEN".to_string(),
+			decimals: 6,
+			addresses: vec![h160_to_string(token_address)],
+			price: None,
+		}]);
+
+		let create_match = |to: Address, logs: Option<Vec<EVMReceiptLog>>| {
+			MonitorMatch::EVM(Box::new(EVMMonitorMatch {
+				monitor: MonitorBuilder::new().name("Test Monitor").build(),
+				transaction: TransactionBuilder::new().to(to).build(),
+				receipt: None,
+				logs,
+				network_slug: "ethereum_mainnet".to_string(),
+				matched_on: MatchConditions::default(),
+				matched_on_args: Some(EVMMatchArguments {
+					functions: None,
+					events: Some(vec![EVMMatchParamsMap {
+						signature: "Transfer(address,address,uint256)".to_string(),
+						args: None,
+						hex_signature: None,
+						log_index: Some(2),
+					}]),
+				}),
+				enrichment: None,
+			}))
+		};
+
+		// Contract that emitted the matched event
+		let log = EVMReceiptLog {
+			address: token_address,
+			topics: vec![],
+			data: Default::default(),
+			block_hash: None,
+			block_number: None,
+			transaction_hash: None,
+			transaction_index: None,
+			log_index: Some(U256::from(2)),
+			transaction_log_index: None,
+			log_type: None,
+			removed: None,
+		};
+		let variables = build_match_variables(&create_match(router_address, Some(vec![log])));
+		assert_eq!(variables["token.symbol"], "MATCHTOKEN");
+		assert_eq!(variables["token.decimals"], "6");
+		assert_eq!(variables["token.address"], h160_to_string(token_address));
+
+		// Contract the transaction was sent to
+		let variables = build_match_variables(&create_match(token_address, None));
+		assert_eq!(variables["token.symbol"], "MATCHTOKEN");
+
+		// Unknown contracts
+		let variables = build_match_variables(&create_match(router_address, None));
+		assert!(!variables.contains_key("token.symbol"));
+	}
 }
@@ -3,10 +3,13 @@
 
 use super::helpers::{are_same_address, string_to_i256, string_to_u256};
 use crate::{
-	models::EVMMatchParamEntry,
-	services::filter::expression::{
-		compare_ordered_values, ComparisonOperator, ConditionEvaluator, EvaluationError,
-		LiteralValue,
+	models::{BlockChainType, EVMMatchParamEntry},
+	services::filter::{
+		address_set_contains,
+		expression::{
+			compare_ordered_values, ComparisonOperator, ConditionEvaluator, EvaluationError,
+			LiteralValue, NumericValue,
+		},
 	},
 };
 use rust_decimal::Decimal;
@@ -47,11 +50,21 @@ const ARRAY_KINDS: &[&str] = &[
 
 pub struct EVMConditionEvaluator<'a> {
 	args: &'a EVMArgs,
+	contract: Option<&'a str>,
 }
 
 impl<'a> EVMConditionEvaluator<'a> {
 	pub fn new(args: &'a EVMArgs) -> Self {
-		Self { args }
+		Self {
+			args,
+			contract: None,
+		}
+	}
+
+	/// Sets the contract that emitted the arguments, whose decimals token amounts use
+	pub fn with_contract(mut self, contract: Option<&'a str>) -> Self {
+		self.contract = contract;
+		self
 	}
 
 	/// Helper to check if a serde_json::Value matches a target string.
@@ -613,6 +626,17 @@ impl ConditionEvaluator for EVMConditionEvaluator<'_> {
 			})
 	}
 
+	/// This method is used to check whether a base parameter exists.
+	///
+	/// Arguments:
+	/// - name: The name of the parameter to check.
+	///
+	/// Returns:
+	/// - true if the parameter exists, false otherwise.
+	fn has_base_param(&self, name: &str) -> bool {
+		self.args.iter().any(|p| p.name == name)
+	}
+
 	/// This method is used to compare the final values of the EVM condition evaluator.
 	///
 	/// Arguments:
@@ -723,6 +747,92 @@ impl ConditionEvaluator for EVMConditionEvaluator<'_> {
 			serde_json::Value::Null => "null".to_string(),
 		}
 	}
+
+	/// This method is used to convert a resolved EVM value into a literal value.
+	///
+	/// Arguments:
+	/// - kind: The kind of the value.
+	/// - value: The value as a string.
+	///
+	/// Returns:
+	/// - Bool for booleans, Number for numeric kinds and Str otherwise.
+	fn get_literal_from_value<'v>(&self, kind: &str, value: &'v str) -> LiteralValue<'v> {
+		let kind_lower = kind.to_lowercase();
+
+		if kind_lower == "bool" {
+			if let Ok(b) = value.parse::<bool>() {
+				return LiteralValue::Bool(b);
+			}
+		}
+
+		if SIGNED_INTEGER_KINDS.contains(&kind_lower.as_str())
+			|| UNSIGNED_INTEGER_KINDS.contains(&kind_lower.as_str())
+			|| matches!(kind_lower.as_str(), "fixed" | "ufixed")
+		{
+			return LiteralValue::Number(value);
+		}
+
+		LiteralValue::Str(value)
+	}
+
+	/// This method is used to check whether a value is a member of a named address set.
+	///
+	/// Arguments:
+	/// - set_name: The name of the address set.
+	/// - value: The value to look up.
+	///
+	/// Returns:
+	/// - true if the normalized EVM address is in the set, false otherwise.
+	/// - error if no address set with the given name is registered.
+	fn is_in_address_set(&self, set_name: &str, value: &str) -> Result<bool, EvaluationError> {
+		address_set_contains(set_name, &BlockChainType::EVM, value).ok_or_else(|| {
+			let msg = format!("Address set not found: {}", set_name);
+			EvaluationError::variable_not_found(msg, None, None)
+		})
+	}
+
+	/// This method is used to convert an EVM value into a numeric value for arithmetic.
+	///
+	/// Arguments:
+	/// - kind: The kind of the value.
+	/// - value: The value as a string.
+	///
+	/// Returns:
+	/// - The numeric value.
+	/// - error if the kind is not numeric or the value cannot be parsed.
+	fn get_numeric_value(&self, kind: &str, value: &str) -> Result<NumericValue, EvaluationError> {
+		let kind_lower = kind.to_lowercase();
+
+		if SIGNED_INTEGER_KINDS.contains(&kind_lower.as_str()) {
+			return string_to_i256(value)
+				.map(NumericValue::from_i256)
+				.map_err(|error| {
+					let msg = format!("Failed to parse value '{}' as I256", value);
+					EvaluationError::parse_error(msg, Some(error.into()), None)
+				});
+		}
+
+		if UNSIGNED_INTEGER_KINDS.contains(&kind_lower.as_str()) {
+			return string_to_u256(value)
+				.map(NumericValue::from_u256)
+				.map_err(|error| {
+					let msg = format!("Failed to parse value '{}' as U256", value);
+					EvaluationError::parse_error(msg, Some(error.into()), None)
+				});
+		}
+
+		match kind_lower.as_str() {
+			"fixed" | "ufixed" => NumericValue::parse(value),
+			_ => {
+				let msg = format!("Unsupported EVM parameter kind for arithmetic: {}", kind);
+				Err(EvaluationError::type_mismatch(msg, None, None))
+			}
+		}
+	}
+
+	fn token_contract(&self) -> Option<&str> {
+		self.contract
+	}
 }
 
 #[cfg(test)]
@@ -16,19 +16,21 @@ use tracing::instrument;
 
 use crate::{
 	models::{
-		AddressWithSpec, BlockType, ContractSpec, EVMContractSpec, EVMMatchArguments,
-		EVMMatchParamEntry, EVMMatchParamsMap, EVMMonitorMatch, EVMReceiptLog, EVMTransaction,
-		EVMTransactionReceipt, EventCondition, FunctionCondition, MatchConditions, Monitor,
-		MonitorMatch, Network, TransactionCondition, TransactionStatus,
+		AddressWithSpec, AggregateCondition, BlockType, ContractSpec, EVMContractSpec,
+		EVMMatchArguments, EVMMatchParamEntry, EVMMatchParamsMap, EVMMonitorMatch, EVMReceiptLog,
+		EVMTransaction, EVMTransactionReceipt, EventCondition, EventMatchMode, FunctionCondition,
+		MatchConditions, Monitor, MonitorMatch, Network, PollConfig, StateConditions,
+		TransactionCondition, TransactionStatus,
 	},
 	services::{
 		blockchain::{BlockChainClient, EvmClientTrait},
 		filter::{
+			self,
 			evm_helpers::{
 				are_same_address, are_same_signature, b256_to_string, format_token_value,
 				h160_to_string, h256_to_string, normalize_address,
 			},
-			expression::{self, EvaluationError},
+			expression::{self, ConditionEvaluator, EvaluationError},
 			filters::evm::evaluator::EVMConditionEvaluator,
 			BlockFilter, FilterError,
 		},
@@ -281,7 +283,11 @@ impl<T> EVMBlockFilter<T> {
 										.collect();
 									if let Some(expr) = &condition.expression {
 										// Evaluate the expression condition
-										match self.evaluate_expression(expr, &params) {
+										match self.evaluate_contract_expression(
+											expr,
+											&params,
+											Some(&monitored_addr.address),
+										) {
 											Ok(true) => {
 												matched_functions.push(FunctionCondition {
 													signature: function_signature_with_params
@@ -299,6 +305,7 @@ impl<T> EVMBlockFilter<T> {
 															"0x{}",
 															hex::encode(function.short_signature())
 														)),
+														log_index: None,
 													});
 												}
 												break;
@@ -326,6 +333,7 @@ impl<T> EVMBlockFilter<T> {
 												hex_signature: Some(hex::encode(
 													function.short_signature(),
 												)),
+												log_index: None,
 											});
 										}
 										break;
@@ -407,9 +415,10 @@ impl<T> EVMBlockFilter<T> {
 								} else {
 									// Evaluate the expression condition
 									if let Some(expr) = &condition.expression {
-										match self.evaluate_expression(
+										match self.evaluate_contract_expression(
 											expr,
 											event_condition.args.as_deref().unwrap_or_default(),
+											Some(&monitored_addr.address),
 										) {
 											Ok(true) => {
 												matched_events.push(EventCondition {
@@ -441,6 +450,169 @@ impl<T> EVMBlockFilter<T> {
 		}
 	}
 
+	/// Decodes the events emitted by monitored addresses in a transaction.
+	///
+	/// Events are returned in log order so that they can be correlated with each other.
+	///
+	/// # Arguments
+	/// * `logs` - Transaction receipt containing event logs
+	/// * `monitor` - Monitor containing the addresses and contract specs to decode with
+	///
+	/// # Returns
+	/// Vector of decoded events
+	pub fn decode_monitored_events(
+		&self,
+		logs: &[EVMReceiptLog],
+		monitor: &Monitor,
+	) -> Vec<EVMMatchParamsMap> {
+		logs.iter()
+			.filter_map(|log| {
+				let monitored_addr = monitor
+					.addresses
+					.iter()
+					.find(|addr| are_same_address(&addr.address, &h160_to_string(log.address)))?;
+				let abi = monitored_addr.contract_spec.as_ref()?;
+				self.decode_events(abi, log)
+			})
+			.collect()
+	}
+
+	/// Checks whether the decoded events of a transaction satisfy the monitor's event match mode.
+	///
+	/// - `Any`: at least one event condition is matched by an event
+	/// - `All`: every event condition is matched by at least one event
+	/// - `Sequence`: every event condition is matched, by events emitted in the same order as
+	///   the conditions are declared
+	///
+	/// # Arguments
+	/// * `events` - Decoded events of the transaction, in log order
+	/// * `monitor` - Monitor containing event match conditions
+	pub fn matches_event_mode(&self, events: &[EVMMatchParamsMap], monitor: &Monitor) -> bool {
+		let conditions = &monitor.match_conditions.events;
+		let is_matched_by = |condition: &EventCondition, event: &EVMMatchParamsMap| {
+			self.event_matches(&condition.signature, condition.expression.as_deref(), event)
+		};
+
+		match monitor.match_conditions.match_mode {
+			EventMatchMode::Any => conditions
+				.iter()
+				.any(|condition| events.iter().any(|event| is_matched_by(condition, event))),
+			EventMatchMode::All => conditions
+				.iter()
+				.all(|condition| events.iter().any(|event| is_matched_by(condition, event))),
+			EventMatchMode::Sequence => {
+				let mut remaining = conditions.iter().peekable();
+				for event in events {
+					match remaining.peek() {
+						Some(condition) if is_matched_by(condition, event) => {
+							remaining.next();
+						}
+						Some(_) => continue,
+						None => break,
+					}
+				}
+				remaining.peek().is_none()
+			}
+		}
+	}
+
+	/// Checks whether all aggregate conditions of the monitor hold for a transaction.
+	///
+	/// Aggregates that fail to evaluate are logged and treated as not matching. Nothing is
+	/// recorded, so matches dropped afterwards by filter plugins leave no state behind.
+	///
+	/// # Arguments
+	/// * `events` - Decoded events of the transaction
+	/// * `monitor` - Monitor containing aggregate conditions
+	pub fn matches_aggregates(&self, events: &[EVMMatchParamsMap], monitor: &Monitor) -> bool {
+		monitor.match_conditions.aggregates.iter().all(|aggregate| {
+			match self.evaluate_aggregate(aggregate, events) {
+				Ok(result) => result,
+				Err(e) => {
+					tracing::error!(
+						"Failed to evaluate aggregate over '{}': {}",
+						aggregate.signature,
+						e
+					);
+					false
+				}
+			}
+		})
+	}
+
+	/// Computes an aggregate over the decoded events of a transaction and evaluates its expression.
+	///
+	/// The aggregate value is exposed to the expression as `result`. `Min` and `Max` over an
+	/// empty set of events never match.
+	///
+	/// # Arguments
+	/// * `aggregate` - Aggregate condition to evaluate
+	/// * `events` - Decoded events of the transaction
+	///
+	/// # Returns
+	/// `true` if the aggregate expression matches, `false` otherwise
+	pub fn evaluate_aggregate(
+		&self,
+		aggregate: &AggregateCondition,
+		events: &[EVMMatchParamsMap],
+	) -> Result<bool, EvaluationError> {
+		let selected = events.iter().filter(|event| {
+			self.event_matches(&aggregate.signature, aggregate.filter.as_deref(), event)
+		});
+
+		let field = aggregate.field.as_deref();
+		let result = filter::aggregate(aggregate.function, selected, |event| {
+			let field = field.ok_or_else(|| {
+				EvaluationError::variable_not_found(
+					format!(
+						"Aggregate function {:?} requires a field",
+						aggregate.function
+					),
+					None,
+					None,
+				)
+			})?;
+			let args = event.args.as_deref().unwrap_or_default();
+			let param = args.iter().find(|p| p.name == field).ok_or_else(|| {
+				EvaluationError::variable_not_found(
+					format!("Field '{}' not found in event {}", field, event.signature),
+					None,
+					None,
+				)
+			})?;
+			EVMConditionEvaluator::new(args).get_numeric_value(&param.kind, &param.value)
+		})?;
+
+		match result {
+			Some(result) => filter::evaluate_aggregate_expression(&aggregate.expression, &result),
+			None => Ok(false),
+		}
+	}
+
+	/// Checks whether a decoded event has the given signature and satisfies the optional
+	/// expression. Expressions that fail to evaluate are logged and treated as not matching.
+	fn event_matches(
+		&self,
+		signature: &str,
+		expression: Option<&str>,
+		event: &EVMMatchParamsMap,
+	) -> bool {
+		if !are_same_signature(signature, &event.signature) {
+			return false;
+		}
+
+		let Some(expr) = expression else {
+			return true;
+		};
+		match self.evaluate_expression(expr, event.args.as_deref().unwrap_or_default()) {
+			Ok(result) => result,
+			Err(e) => {
+				tracing::error!("Failed to evaluate expression '{}': {}", expr, e);
+				false
+			}
+		}
+	}
+
 	/// Evaluates a match expression against provided parameters.
 	///
 	/// # Arguments
@@ -453,6 +625,27 @@ impl<T> EVMBlockFilter<T> {
 		&self,
 		expression: &str,
 		args: &[EVMMatchParamEntry],
+	) -> Result<bool, EvaluationError> {
+		self.evaluate_contract_expression(expression, args, None)
+	}
+
+	/// Evaluates a match expression against parameters emitted by a contract.
+	///
+	/// Token amounts such as `10000 USDC` use the decimals of the contract if it is a token with
+	/// the symbol, so that a symbol with different decimals per network resolves correctly.
+	///
+	/// # Arguments
+	/// * `expression` - The expression to evaluate
+	/// * `args` - Parameters to use in evaluation
+	/// * `contract` - Address of the contract that emitted the parameters, if any
+	///
+	/// # Returns
+	/// `true` if the expression matches, `false` otherwise
+	pub fn evaluate_contract_expression(
+		&self,
+		expression: &str,
+		args: &[EVMMatchParamEntry],
+		contract: Option<&str>,
 	) -> Result<bool, EvaluationError> {
 		// Check if the expression is empty
 		if expression.trim().is_empty() {
@@ -464,7 +657,7 @@ impl<T> EVMBlockFilter<T> {
 			));
 		}
 
-		let evaluator = EVMConditionEvaluator::new(args);
+		let evaluator = EVMConditionEvaluator::new(args).with_contract(contract);
 
 		// Parse the expression
 		let parsed_ast = expression::parse(expression).map_err(|e| {
@@ -544,6 +737,7 @@ impl<T> EVMBlockFilter<T> {
 									.collect(),
 							),
 							hex_signature: Some(h256_to_string(event.signature())),
+							log_index: log.log_index.and_then(|index| u64::try_from(index).ok()),
 						};
 						event_params_map
 					})
@@ -552,6 +746,95 @@ impl<T> EVMBlockFilter<T> {
 		decoded_log
 	}
 
+	/// Reads the contract state of a monitor at the block of a match and checks its expression
+	///
+	/// The results of the calls are added to the match as `state.<name>`. Failed calls and
+	/// expressions are logged and drop the match.
+	///
+	/// # Arguments
+	/// * `client` - EVM client used to execute the calls
+	/// * `state` - State calls and expression of the monitor
+	/// * `monitor_match` - Match the calls are executed for
+	/// * `block_number` - Block of the match
+	///
+	/// # Returns
+	/// `true` if the state was read and its expression matches, `false` otherwise
+	async fn matches_state<C: EvmClientTrait + Sync>(
+		&self,
+		client: &C,
+		state: &StateConditions,
+		monitor_match: &mut MonitorMatch,
+		block_number: u64,
+	) -> bool {
+		let variables = filter::build_match_variables(monitor_match);
+		let values = match filter::read_state(client, &state.calls, &variables, block_number).await
+		{
+			Ok(values) => values,
+			Err(e) => {
+				tracing::error!("Failed to read contract state: {}", e);
+				return false;
+			}
+		};
+
+		if let Some(expr) = &state.expression {
+			match filter::evaluate_state_expression(expr, &values) {
+				Ok(true) => {}
+				Ok(false) => return false,
+				Err(e) => {
+					tracing::error!("Failed to evaluate state expression '{}': {}", expr, e);
+					return false;
+				}
+			}
+		}
+
+		monitor_match.enrich(filter::state_enrichment(&values));
+		true
+	}
+
+	/// Polls the state of a polling monitor if it is due at a block
+	///
+	/// Failures to read the values are logged and release the poll, so that the next block polls
+	/// again. Failures to evaluate the expression are logged, and skip the poll.
+	///
+	/// # Returns
+	/// The match of the poll if its expression holds
+	async fn poll_monitor<C: EvmClientTrait + Sync>(
+		&self,
+		client: &C,
+		network: &Network,
+		monitor: &Monitor,
+		poll: &PollConfig,
+		block: &BlockType,
+	) -> Option<MonitorMatch> {
+		let block_number = block.number().unwrap_or(0);
+		let key = filter::poll_key(&network.slug, monitor);
+		let reservation = filter::reserve_poll(&key, poll, block_number, block.timestamp())?;
+
+		let readings = match filter::read_evm_poll(client, poll, block_number).await {
+			Ok(readings) => readings,
+			Err(e) => {
+				tracing::error!("Failed to poll monitor {}: {}", monitor.name, e);
+				// The next block polls again instead of waiting for the next period
+				filter::release_poll(&key, reservation);
+				return None;
+			}
+		};
+		filter::record_poll(&key, &readings);
+
+		filter::evaluate_poll(
+			monitor,
+			&network.slug,
+			block_number,
+			block.timestamp(),
+			&readings,
+			&reservation.previous,
+		)
+		.unwrap_or_else(|e| {
+			tracing::error!("Failed to poll monitor {}: {}", monitor.name, e);
+			None
+		})
+	}
+
 	/// Checks if a monitor has any transaction conditions that require a receipt
 	fn needs_receipt(&self, monitor: &Monitor, logs: &[EVMReceiptLog]) -> bool {
 		monitor
@@ -651,6 +934,13 @@ impl<T: BlockChainClient + EvmClientTrait> BlockFilter for EVMBlockFilter<T> {
 
 		for monitor in monitors {
 			tracing::debug!("Processing monitor: {:?}", monitor.name);
+			if let Some(poll) = &monitor.poll {
+				matching_results.extend(
+					self.poll_monitor(client, network, monitor, poll, block)
+						.await,
+				);
+				continue;
+			}
 			let monitored_addresses: Vec<String> = monitor
 				.addresses
 				.iter()
@@ -750,8 +1040,21 @@ impl<T: BlockChainClient + EvmClientTrait> BlockFilter for EVMBlockFilter<T> {
 				// Only proceed if we have a matching address
 				if has_address_match {
 					let monitor_conditions = &monitor.match_conditions;
-					let has_event_match =
-						!monitor_conditions.events.is_empty() && !matched_events.is_empty();
+
+					// Correlating events requires every decoded event of the transaction, not
+					// only the first condition each event matched
+					let needs_correlation = !monitor_conditions.match_mode.is_any()
+						|| !monitor_conditions.aggregates.is_empty();
+					let decoded_events = if needs_correlation {
+						self.decode_monitored_events(logs, monitor)
+					} else {
+						Vec::new()
+					};
+
+					let has_event_match = !monitor_conditions.events.is_empty()
+						&& !matched_events.is_empty()
+						&& (monitor_conditions.match_mode.is_any()
+							|| self.matches_event_mode(&decoded_events, monitor));
 					let has_function_match =
 						!monitor_conditions.functions.is_empty() && !matched_functions.is_empty();
 					let has_transaction_match = !monitor_conditions.transactions.is_empty()
@@ -776,8 +1079,8 @@ impl<T: BlockChainClient + EvmClientTrait> BlockFilter for EVMBlockFilter<T> {
 						_ => (has_event_match || has_function_match) && has_transaction_match,
 					};
 
-					if should_match {
-						matching_results.push(MonitorMatch::EVM(Box::new(EVMMonitorMatch {
+					if should_match && self.matches_aggregates(&decoded_events, monitor) {
+						let mut monitor_match = MonitorMatch::EVM(Box::new(EVMMonitorMatch {
 							monitor: Monitor {
 								// Omit ABI from monitor since we do not need it here
 								addresses: monitor
@@ -810,6 +1113,8 @@ impl<T: BlockChainClient + EvmClientTrait> BlockFilter for EVMBlockFilter<T> {
 									.into_iter()
 									.filter(|_| has_transaction_match)
 									.collect(),
+								match_mode: monitor_conditions.match_mode,
+								aggregates: monitor_conditions.aggregates.clone(),
 							},
 							matched_on_args: Some(EVMMatchArguments {
 								events: if has_event_match {
@@ -823,7 +1128,23 @@ impl<T: BlockChainClient + EvmClientTrait> BlockFilter for EVMBlockFilter<T> {
 									None
 								},
 							}),
-						})));
+							enrichment: None,
+						}));
+
+						if let Some(state) = &monitor.state {
+							if !self
+								.matches_state(
+									client,
+									state,
+									&mut monitor_match,
+									current_block_number,
+								)
+								.await
+							{
+								continue;
+							}
+						}
+						matching_results.push(monitor_match);
 					}
 				}
 			}
@@ -836,7 +1157,7 @@ impl<T: BlockChainClient + EvmClientTrait> BlockFilter for EVMBlockFilter<T> {
 #[cfg(test)]
 mod tests {
 	use crate::{
-		models::{ContractSpec, EVMContractSpec},
+		models::{AggregateFunction, ContractSpec, EVMContractSpec},
 		utils::tests::evm::{
 			monitor::MonitorBuilder, receipt::ReceiptBuilder, transaction::TransactionBuilder,
 		},
@@ -868,6 +1189,7 @@ mod tests {
 				events: event_conditions,
 				functions: function_conditions,
 				transactions: transaction_conditions,
+				..Default::default()
 			})
 			.addresses_with_spec(
 				addresses
@@ -1872,6 +2194,7 @@ mod tests {
 				}],
 				events: vec![],
 				transactions: vec![],
+				..Default::default()
 			})
 			.addresses_with_spec(vec![(
 				contract_with_spec.0.clone(),
@@ -2076,6 +2399,309 @@ mod tests {
 		assert_eq!(involved_addresses.len(), 0);
 	}
 
+	//////////////////////////////////////////////////////////////////////////////
+	// Test cases for event correlation (match modes and aggregates):
+	//////////////////////////////////////////////////////////////////////////////
+	const TRANSFER: &str = "Transfer(address,address,uint256)";
+	const SWAP: &str = "Swap(address,uint256,uint256)";
+
+	fn create_test_decoded_event(
+		signature: &str,
+		args: &[(&str, &str, &str)],
+	) -> EVMMatchParamsMap {
+		EVMMatchParamsMap {
+			signature: signature.to_string(),
+			args: Some(
+				args.iter()
+					.map(|(name, value, kind)| create_test_param(name, value, kind))
+					.collect(),
+			),
+			hex_signature: None,
+			log_index: None,
+		}
+	}
+
+	fn create_test_transfer(to: &str, value: &str) -> EVMMatchParamsMap {
+		create_test_decoded_event(
+			TRANSFER,
+			&[
+				(
+					"from",
+					"0x0000000000000000000000000000000000001234",
+					"address",
+				),
+				("to", to, "address"),
+				("value", value, "uint256"),
+			],
+		)
+	}
+
+	fn create_test_swap(amount_in: &str) -> EVMMatchParamsMap {
+		create_test_decoded_event(
+			SWAP,
+			&[
+				(
+					"sender",
+					"0x0000000000000000000000000000000000001234",
+					"address",
+				),
+				("amount_in", amount_in, "uint256"),
+				("amount_out", "1", "uint256"),
+			],
+		)
+	}
+
+	fn create_test_correlation_monitor(
+		match_mode: EventMatchMode,
+		event_conditions: Vec<EventCondition>,
+	) -> Monitor {
+		MonitorBuilder::new()
+			.name("test")
+			.networks(vec!["evm_mainnet".to_string()])
+			.match_conditions(MatchConditions {
+				events: event_conditions,
+				match_mode,
+				..Default::default()
+			})
+			.build()
+	}
+
+	fn create_test_aggregate(
+		function: AggregateFunction,
+		field: Option<&str>,
+		filter: Option<&str>,
+		expression: &str,
+	) -> AggregateCondition {
+		AggregateCondition {
+			function,
+			signature: TRANSFER.to_string(),
+			field: field.map(str::to_string),
+			filter: filter.map(str::to_string),
+			expression: expression.to_string(),
+		}
+	}
+
+	#[test]
+	fn test_matches_event_mode_any_and_all() {
+		let filter = create_test_filter();
+		let conditions = vec![
+			EventCondition {
+				signature: TRANSFER.to_string(),
+				expression: Some("value > 100".to_string()),
+			},
+			EventCondition {
+				signature: SWAP.to_string(),
+				expression: None,
+			},
+		];
+		let any_monitor = create_test_correlation_monitor(EventMatchMode::Any, conditions.clone());
+		let all_monitor = create_test_correlation_monitor(EventMatchMode::All, conditions);
+
+		let transfer_only = vec![create_test_transfer(
+			"0x0000000000000000000000000000000000005678",
+			"1000",
+		)];
+		assert!(filter.matches_event_mode(&transfer_only, &any_monitor));
+		assert!(!filter.matches_event_mode(&transfer_only, &all_monitor));
+
+		let transfer_and_swap = vec![
+			create_test_swap("10"),
+			create_test_transfer("0x0000000000000000000000000000000000005678", "1000"),
+		];
+		assert!(filter.matches_event_mode(&transfer_and_swap, &any_monitor));
+		assert!(filter.matches_event_mode(&transfer_and_swap, &all_monitor));
+
+		// The transfer does not satisfy its expression, so not every condition is matched
+		let small_transfer_and_swap = vec![
+			create_test_transfer("0x0000000000000000000000000000000000005678", "1"),
+			create_test_swap("10"),
+		];
+		assert!(filter.matches_event_mode(&small_transfer_and_swap, &any_monitor));
+		assert!(!filter.matches_event_mode(&small_transfer_and_swap, &all_monitor));
+	}
+
+	#[test]
+	fn test_matches_event_mode_sequence() {
+		let filter = create_test_filter();
+		let monitor = create_test_correlation_monitor(
+			EventMatchMode::Sequence,
+			vec![
+				EventCondition {
+					signature: TRANSFER.to_string(),
+					expression: None,
+				},
+				EventCondition {
+					signature: SWAP.to_string(),
+					expression: Some("amount_in >= 10".to_string()),
+				},
+			],
+		);
+		let transfer = create_test_transfer("0x0000000000000000000000000000000000005678", "1");
+
+		// In order, with unrelated events in between
+		let events = vec![
+			create_test_swap("50"),
+			transfer.clone(),
+			create_test_swap("1"),
+			create_test_swap("50"),
+		];
+		assert!(filter.matches_event_mode(&events, &monitor));
+
+		// Out of order
+		let events = vec![create_test_swap("50"), transfer.clone()];
+		assert!(!filter.matches_event_mode(&events, &monitor));
+
+		// The swap following the transfer does not satisfy its expression
+		let events = vec![transfer, create_test_swap("1")];
+		assert!(!filter.matches_event_mode(&events, &monitor));
+	}
+
+	#[test]
+	fn test_evaluate_aggregate_functions() {
+		let filter = create_test_filter();
+		let vault = "0x000000000000000000000000000000000000dead";
+		let events = vec![
+			create_test_transfer(vault, "100"),
+			create_test_swap("5000"),
+			create_test_transfer("0x0000000000000000000000000000000000005678", "1000"),
+			create_test_transfer(vault, "250"),
+		];
+
+		let to_vault = format!("to == {}", vault);
+
+		let cases = vec![
+			(AggregateFunction::Count, None, None, "result == 3", true),
+			(
+				AggregateFunction::Sum,
+				Some("value"),
+				None,
+				"result == 1350",
+				true,
+			),
+			(
+				AggregateFunction::Min,
+				Some("value"),
+				None,
+				"result == 100",
+				true,
+			),
+			(
+				AggregateFunction::Max,
+				Some("value"),
+				None,
+				"result == 1000",
+				true,
+			),
+			(
+				AggregateFunction::Sum,
+				Some("value"),
+				Some(to_vault.as_str()),
+				"result == 350",
+				true,
+			),
+			(
+				AggregateFunction::Sum,
+				Some("value"),
+				Some(to_vault.as_str()),
+				"result > 1000",
+				false,
+			),
+			(
+				AggregateFunction::Count,
+				None,
+				Some("value > 5000"),
+				"result == 0",
+				true,
+			),
+			// Min/max over no events never match
+			(
+				AggregateFunction::Max,
+				Some("value"),
+				Some("value > 5000"),
+				"result >= 0",
+				false,
+			),
+		];
+
+		for (function, field, event_filter, expression, expected) in cases {
+			let aggregate = create_test_aggregate(function, field, event_filter, expression);
+			assert_eq!(
+				filter.evaluate_aggregate(&aggregate, &events).unwrap(),
+				expected,
+				"{:?} over {:?} with filter {:?}: {}",
+				function,
+				field,
+				event_filter,
+				expression
+			);
+		}
+	}
+
+	#[test]
+	fn test_evaluate_aggregate_errors() {
+		let filter = create_test_filter();
+		let events = vec![create_test_transfer(
+			"0x0000000000000000000000000000000000005678",
+			"100",
+		)];
+
+		let missing_field = create_test_aggregate(AggregateFunction::Sum, None, None, "result > 0");
+		assert!(filter.evaluate_aggregate(&missing_field, &events).is_err());
+
+		let unknown_field =
+			create_test_aggregate(AggregateFunction::Sum, Some("amount"), None, "result > 0");
+		assert!(filter.evaluate_aggregate(&unknown_field, &events).is_err());
+
+		let non_numeric_field =
+			create_test_aggregate(AggregateFunction::Sum, Some("to"), None, "result > 0");
+		assert!(filter
+			.evaluate_aggregate(&non_numeric_field, &events)
+			.is_err());
+
+		// Errors are treated as a non-match at the monitor level
+		let monitor = MonitorBuilder::new().aggregate(unknown_field).build();
+		assert!(!filter.matches_aggregates(&events, &monitor));
+	}
+
+	#[test]
+	fn test_decode_monitored_events() {
+		let filter = create_test_filter();
+		let contract_address =
+			Address::from_str("0x0000000000000000000000000000000000004321").unwrap();
+		let receipt = ReceiptBuilder::new()
+			.contract_address(contract_address)
+			.from(Address::from_str("0x0000000000000000000000000000000000001234").unwrap())
+			.to(Address::from_str("0x0000000000000000000000000000000000005678").unwrap())
+			.value(U256::from(100))
+			.build();
+
+		let monitor = create_test_monitor(
+			vec![],
+			vec![],
+			vec![],
+			vec![create_test_address(
+				"0x0000000000000000000000000000000000004321",
+				Some(create_test_abi("event")),
+			)],
+		);
+		let events = filter.decode_monitored_events(&receipt.logs, &monitor);
+		assert_eq!(events.len(), 1);
+		assert_eq!(events[0].signature, TRANSFER);
+
+		let unrelated_monitor = create_test_monitor(
+			vec![],
+			vec![],
+			vec![],
+			vec![create_test_address(
+				"0x0000000000000000000000000000000000009999",
+				Some(create_test_abi("event")),
+			)],
+		);
+		assert!(filter
+			.decode_monitored_events(&receipt.logs, &unrelated_monitor)
+			.is_empty());
+	}
+
 	//////////////////////////////////////////////////////////////////////////////
 	// Test cases for evaluate_expression method:
 	//////////////////////////////////////////////////////////////////////////////
@@ -2210,6 +2836,348 @@ mod tests {
 			.unwrap());
 	}
 
+	#[test]
+	fn test_evaluate_expression_address_membership() {
+		let filter = create_test_filter();
+		let args = vec![create_test_param(
+			"recipient",
+			"0x1234567890123456789012345678901234567890",
+			"address",
+		)];
+
+		crate::services::filter::register_address_sets(vec![crate::models::AddressSet {
+			name: "evm_filter_test_wallets".to_string(),
+			addresses: vec![
+				"0X1234567890ABCDEF1234567890ABCDEF12345678".to_string(),
+				"1234567890123456789012345678901234567890".to_string(),
+			],
+		}]);
+
+		// Inline lists
+		assert!(filter
+			.evaluate_expression(
+				"recipient in [0x0000000000000000000000000000000000000000, 0x1234567890123456789012345678901234567890]",
+				&args
+			)
+			.unwrap());
+		assert!(filter
+			.evaluate_expression(
+				"recipient not in [0x0000000000000000000000000000000000000000]",
+				&args
+			)
+			.unwrap());
+		assert!(!filter
+			.evaluate_expression("recipient in []", &args)
+			.unwrap());
+
+		// Named address sets (normalized: prefix and case are ignored)
+		assert!(filter
+			.evaluate_expression("recipient in @evm_filter_test_wallets", &args)
+			.unwrap());
+		assert!(!filter
+			.evaluate_expression("recipient not in @evm_filter_test_wallets", &args)
+			.unwrap());
+
+		// Unknown address set
+		assert!(matches!(
+			filter.evaluate_expression("recipient in @evm_filter_unknown_set", &args),
+			Err(EvaluationError::VariableNotFound(_))
+		));
+	}
+
+	#[test]
+	fn test_evaluate_expression_token_amounts() {
+		let filter = create_test_filter();
+		let args = vec![create_test_param("value", "10000500000", "uint256")];
+
+		crate::services::filter::register_tokens(vec![crate::models::Token {
+			symbol: "EVMFILTERUSD".to_string(),
+			decimals: 6,
+			addresses: vec![],
+			price: None,
+		}]);
+
+		assert!(filter
+			.evaluate_expression("value > 10000 EVMFILTERUSD", &args)
+			.unwrap());
+		assert!(filter
+			.evaluate_expression("value == 10000.5 evmfilterusd", &args)
+			.unwrap());
+		assert!(!filter
+			.evaluate_expression("value >= 10000.500001 EVMFILTERUSD", &args)
+			.unwrap());
+		assert!(filter
+			.evaluate_expression("value in [1 EVMFILTERUSD, 10000.5 EVMFILTERUSD]", &args)
+			.unwrap());
+
+		// More fractional digits than the token decimals
+		assert!(matches!(
+			filter.evaluate_expression("value > 0.0000001 EVMFILTERUSD", &args),
+			Err(EvaluationError::ParseError(_))
+		));
+
+		// Unknown token
+		assert!(matches!(
+			filter.evaluate_expression("value > 10000 EVMFILTERUNKNOWN", &args),
+			Err(EvaluationError::VariableNotFound(_))
+		));
+	}
+
+	#[test]
+	fn test_evaluate_contract_expression_token_per_network() {
+		let filter = create_test_filter();
+		let ethereum = "0x00000000000000000000000000000000000e7001";
+		let bsc = "0x00000000000000000000000000000000000e7002";
+		crate::services::filter::register_tokens(vec![
+			crate::models::Token {
+				symbol: "EVMFILTERMULTI".to_string(),
+				decimals: 6,
+				addresses: vec![ethereum.to_string()],
+				price: None,
+			},
+			crate::models::Token {
+				symbol: "EVMFILTERMULTI".to_string(),
+				decimals: 18,
+				addresses: vec![bsc.to_string()],
+				price: None,
+			},
+		]);
+		// 10,000.5 tokens with 6 decimals, or about 0.00000001 tokens with 18 decimals
+		let args = vec![create_test_param("value", "10000500000", "uint256")];
+
+		assert!(filter
+			.evaluate_contract_expression("value > 10000 EVMFILTERMULTI", &args, Some(ethereum))
+			.unwrap());
+		assert!(!filter
+			.evaluate_contract_expression("value > 10000 EVMFILTERMULTI", &args, Some(bsc))
+			.unwrap());
+
+		// Without the emitting contract the decimals are ambiguous
+		assert!(matches!(
+			filter.evaluate_expression("value > 10000 EVMFILTERMULTI", &args),
+			Err(EvaluationError::VariableNotFound(_))
+		));
+	}
+
+	#[tokio::test]
+	async fn test_evaluate_expression_usd() {
+		use crate::services::price::{
+			refresh_prices, register_price_feed, HttpPriceSource, PriceFeedPolicy,
+		};
+		use std::{collections::HashMap, sync::Arc};
+
+		let mut server = mockito::Server::new_async().await;
+		server
+			.mock("GET", "/price")
+			.with_status(200)
+			.with_body(r#"{"usd": "2500.5"}"#)
+			.create_async()
+			.await;
+
+		crate::services::filter::register_tokens(vec![crate::models::Token {
+			symbol: "EVMFILTERETH".to_string(),
+			decimals: 18,
+			addresses: vec!["0x00000000000000000000000000000000000e7048".to_string()],
+			price: None,
+		}]);
+		register_price_feed(
+			"EVMFILTERETH",
+			Arc::new(
+				HttpPriceSource::new(format!("{}/price", server.url()), "usd", HashMap::new())
+					.unwrap(),
+			),
+			PriceFeedPolicy::default(),
+		);
+		refresh_prices().await;
+
+		let filter = create_test_filter();
+		// 2 tokens at 2500.5 USD
+		let args = vec![create_test_param("value", "2000000000000000000", "uint256")];
+
+		assert!(filter
+			.evaluate_expression("usd(value, EVMFILTERETH) == 5001", &args)
+			.unwrap());
+		assert!(filter
+			.evaluate_expression("usd(value, EVMFILTERETH) > 5000.99", &args)
+			.unwrap());
+		assert!(filter
+			.evaluate_expression(
+				"usd(value / 2, 0x00000000000000000000000000000000000E7048) < 2500.6",
+				&args
+			)
+			.unwrap());
+
+		// Unknown token and token without a price feed
+		assert!(matches!(
+			filter.evaluate_expression("usd(value, EVMFILTERUNKNOWN) > 1", &args),
+			Err(EvaluationError::VariableNotFound(_))
+		));
+		crate::services::filter::register_tokens(vec![crate::models::Token {
+			symbol: "EVMFILTERNOPRICE".to_string(),
+			decimals: 18,
+			addresses: vec![],
+			price: None,
+		}]);
+		assert!(matches!(
+			filter.evaluate_expression("usd(value, EVMFILTERNOPRICE) > 1", &args),
+			Err(EvaluationError::VariableNotFound(_))
+		));
+	}
+
+	#[test]
+	fn test_evaluate_expression_arithmetic() {
+		let filter = create_test_filter();
+		let args = vec![
+			create_test_param("value", "15000500000000000000000", "uint256"),
+			create_test_param("decimals", "18", "uint8"),
+			create_test_param("amount0In", "600000000000000000000", "uint256"),
+			create_test_param("amount1In", "400000000000000000001", "uint256"),
+			create_test_param("delta", "-5", "int256"),
+			create_test_param("ratio", "1.5", "fixed"),
+			create_test_param("max", &U256::MAX.to_string(), "uint256"),
+			create_test_param(
+				"data",
+				r#"{"gas_used": 21000, "gas_price": 50000000000000}"#,
+				"map",
+			),
+		];
+
+		// Scaling by token decimals keeps the fractional part
+		assert!(filter
+			.evaluate_expression("value / 10^decimals > 15000", &args)
+			.unwrap());
+		assert!(filter
+			.evaluate_expression("value / 10^decimals == 15000.5", &args)
+			.unwrap());
+
+		// Sums compared against scientific notation literals
+		assert!(filter
+			.evaluate_expression("amount0In + amount1In > 1e21", &args)
+			.unwrap());
+		assert!(!filter
+			.evaluate_expression("amount0In + amount1In - 1 > 1e21", &args)
+			.unwrap());
+
+		// Path operands, precedence and parentheses
+		assert!(filter
+			.evaluate_expression("data.gas_used * data.gas_price > 1e18", &args)
+			.unwrap());
+		assert!(filter
+			.evaluate_expression("(delta + 1) * 2 == -8", &args)
+			.unwrap());
+		assert!(filter
+			.evaluate_expression("delta + 1 * 2 == -3", &args)
+			.unwrap());
+		assert!(filter
+			.evaluate_expression("2 ^ 3 ^ 2 == 512", &args)
+			.unwrap());
+		assert!(filter
+			.evaluate_expression("ratio * 2 >= 3 AND delta < 0 - 4", &args)
+			.unwrap());
+		assert!(filter
+			.evaluate_expression("not value / 10^decimals < 1", &args)
+			.unwrap());
+
+		// Overflow and division by zero are reported instead of wrapping
+		assert!(matches!(
+			filter.evaluate_expression("max + 1 > 0", &args),
+			Err(EvaluationError::ArithmeticError(_))
+		));
+		assert!(matches!(
+			filter.evaluate_expression("value / (decimals - 18) > 0", &args),
+			Err(EvaluationError::ArithmeticError(_))
+		));
+
+		// Non-numeric operands and operators
+		let args_with_address = vec![create_test_param(
+			"recipient",
+			"0x1234567890123456789012345678901234567890",
+			"address",
+		)];
+		assert!(matches!(
+			filter.evaluate_expression("recipient + 1 > 0", &args_with_address),
+			Err(EvaluationError::TypeMismatch(_))
+		));
+		assert!(matches!(
+			filter.evaluate_expression("value + 1 contains 1", &args),
+			Err(EvaluationError::UnsupportedOperator(_))
+		));
+	}
+
+	#[test]
+	fn test_evaluate_expression_variable_comparisons() {
+		let filter = create_test_filter();
+		let args = vec![
+			create_test_param(
+				"from",
+				"0x1234567890123456789012345678901234567890",
+				"address",
+			),
+			create_test_param(
+				"to",
+				"0x1234567890ABCDEF1234567890abcdef12345678",
+				"address",
+			),
+			create_test_param(
+				"owner",
+				"1234567890123456789012345678901234567890",
+				"address",
+			),
+			create_test_param("amountOut", "100", "uint256"),
+			create_test_param("amountOutMin", "150", "uint256"),
+			create_test_param("delta", "-10", "int256"),
+			create_test_param("is_active", "true", "bool"),
+			create_test_param("expected_active", "true", "bool"),
+			create_test_param("label", "Active", "string"),
+			create_test_param(
+				"args",
+				r#"{"spender": "0x1234567890abcdef1234567890abcdef12345678", "limits": [50, 200]}"#,
+				"map",
+			),
+		];
+
+		// Address comparisons use the normalized address rules on both sides
+		assert!(filter.evaluate_expression("from == owner", &args).unwrap());
+		assert!(filter.evaluate_expression("from != to", &args).unwrap());
+		assert!(filter
+			.evaluate_expression("args.spender == to", &args)
+			.unwrap());
+
+		// Numeric comparisons between parameters and resolved paths
+		assert!(filter
+			.evaluate_expression("amountOut < amountOutMin", &args)
+			.unwrap());
+		assert!(filter
+			.evaluate_expression("amountOutMin > args.limits[0]", &args)
+			.unwrap());
+		assert!(filter
+			.evaluate_expression("delta < amountOut", &args)
+			.unwrap());
+
+		// Booleans and membership with variables
+		assert!(filter
+			.evaluate_expression("is_active == expected_active", &args)
+			.unwrap());
+		assert!(filter
+			.evaluate_expression("to in [from, args.spender]", &args)
+			.unwrap());
+
+		// Unknown simple names are rejected, string literals must be quoted
+		assert!(matches!(
+			filter.evaluate_expression("from == tto", &args),
+			Err(EvaluationError::VariableNotFound(_))
+		));
+		assert!(filter
+			.evaluate_expression("label == 'active'", &args)
+			.unwrap());
+
+		// Unknown paths are reported
+		assert!(matches!(
+			filter.evaluate_expression("from == unknown.field", &args),
+			Err(EvaluationError::VariableNotFound(_))
+		));
+	}
+
 	#[test]
 	fn test_evaluate_expression_boolean_comparisons() {
 		let filter = create_test_filter();
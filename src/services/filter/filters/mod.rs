@@ -18,10 +18,17 @@ pub mod stellar {
 }
 
 use async_trait::async_trait;
+use std::sync::Arc;
 
 use crate::{
 	models::{BlockType, ContractSpec, Monitor, MonitorMatch, Network},
-	services::{blockchain::BlockFilterFactory, filter::error::FilterError},
+	services::{
+		blockchain::BlockFilterFactory,
+		filter::{
+			error::FilterError,
+			plugins::{FilterPlugin, FilterPluginRegistry},
+		},
+	},
 };
 pub use evm::evaluator::{EVMArgs, EVMConditionEvaluator};
 pub use evm::filter::EVMBlockFilter;
@@ -48,11 +55,42 @@ pub trait BlockFilter {
 /// Service for filtering blockchain data
 ///
 /// This service provides a way to filter blockchain data based on a set of monitors.
-pub struct FilterService {}
+pub struct FilterService {
+	/// Filter plugins referenced by monitors
+	plugins: FilterPluginRegistry,
+}
 
 impl FilterService {
 	pub fn new() -> Self {
-		FilterService {}
+		FilterService {
+			plugins: FilterPluginRegistry::default(),
+		}
+	}
+
+	/// Registers a filter plugin which monitors reference by name in their `filter_plugins`
+	///
+	/// Plugins should be registered at startup, before blocks are processed, and the monitors
+	/// checked with [`FilterService::check_plugins`] once they are all registered. Registering a
+	/// plugin under an existing name replaces it.
+	///
+	/// # Arguments
+	/// * `name` - Name monitors reference the plugin with
+	/// * `plugin` - Plugin deciding whether matches are kept
+	pub fn register_plugin(&self, name: impl Into<String>, plugin: Arc<dyn FilterPlugin>) {
+		self.plugins.register(name, plugin);
+	}
+
+	/// Checks that every filter plugin referenced by the monitors is registered
+	///
+	/// # Arguments
+	/// * `monitors` - Monitors to check
+	///
+	/// # Returns
+	/// * `Result<(), FilterError>` - Error naming the first monitor referencing a plugin which is
+	///   not registered
+	#[allow(clippy::result_large_err)]
+	pub fn check_plugins(&self, monitors: &[Monitor]) -> Result<(), FilterError> {
+		self.plugins.check(monitors)
 	}
 }
 
@@ -72,8 +110,9 @@ impl FilterService {
 		contract_specs: Option<&[(String, ContractSpec)]>,
 	) -> Result<Vec<MonitorMatch>, FilterError> {
 		let filter = T::filter();
-		filter
+		let matches = filter
 			.filter_block(client, network, block, monitors, contract_specs)
-			.await
+			.await?;
+		Ok(self.plugins.apply(matches).await)
 	}
 }
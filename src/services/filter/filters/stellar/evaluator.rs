@@ -3,10 +3,13 @@
 
 use super::helpers;
 use crate::{
-	models::StellarMatchParamEntry,
-	services::filter::expression::{
-		compare_ordered_values, ComparisonOperator, ConditionEvaluator, EvaluationError,
-		LiteralValue,
+	models::{BlockChainType, StellarMatchParamEntry},
+	services::filter::{
+		address_set_contains,
+		expression::{
+			compare_ordered_values, ComparisonOperator, ConditionEvaluator, EvaluationError,
+			LiteralValue, NumericValue,
+		},
 	},
 };
 use serde_json::Value as JsonValue;
@@ -515,6 +518,17 @@ impl ConditionEvaluator for StellarConditionEvaluator<'_> {
 			})
 	}
 
+	/// This method is used to check whether a base parameter exists.
+	///
+	/// Arguments:
+	/// - name: The name of the parameter to check.
+	///
+	/// Returns:
+	/// - true if the parameter exists, false otherwise.
+	fn has_base_param(&self, name: &str) -> bool {
+		self.args.iter().any(|entry| entry.name == name)
+	}
+
 	/// This method is used to get the kind of the value from the JSON value.
 	///
 	/// Arguments:
@@ -565,6 +579,70 @@ impl ConditionEvaluator for StellarConditionEvaluator<'_> {
 			}
 		}
 	}
+
+	/// This method is used to convert a resolved Stellar value into a literal value.
+	///
+	/// Arguments:
+	/// - kind: The kind of the value.
+	/// - value: The value as a string.
+	///
+	/// Returns:
+	/// - Bool for booleans, Number for numeric kinds and Str otherwise.
+	fn get_literal_from_value<'v>(&self, kind: &str, value: &'v str) -> LiteralValue<'v> {
+		match kind.to_lowercase().as_str() {
+			"bool" if value.eq_ignore_ascii_case("true") => LiteralValue::Bool(true),
+			"bool" if value.eq_ignore_ascii_case("false") => LiteralValue::Bool(false),
+			"u32" | "u64" | "timepoint" | "duration" | "i32" | "i64" | "u128" | "i128" | "u256"
+			| "i256" | "f64" => LiteralValue::Number(value),
+			_ => LiteralValue::Str(value),
+		}
+	}
+
+	/// This method is used to check whether a value is a member of a named address set.
+	///
+	/// Arguments:
+	/// - set_name: The name of the address set.
+	/// - value: The value to look up.
+	///
+	/// Returns:
+	/// - true if the normalized Stellar address is in the set, false otherwise.
+	/// - error if no address set with the given name is registered.
+	fn is_in_address_set(&self, set_name: &str, value: &str) -> Result<bool, EvaluationError> {
+		address_set_contains(set_name, &BlockChainType::Stellar, value).ok_or_else(|| {
+			let msg = format!("Address set not found: {}", set_name);
+			EvaluationError::variable_not_found(msg, None, None)
+		})
+	}
+
+	/// This method is used to convert a Stellar value into a numeric value for arithmetic.
+	///
+	/// Arguments:
+	/// - kind: The kind of the value.
+	/// - value: The value as a string.
+	///
+	/// Returns:
+	/// - The numeric value.
+	/// - error if the kind is not numeric or the value cannot be parsed.
+	fn get_numeric_value(&self, kind: &str, value: &str) -> Result<NumericValue, EvaluationError> {
+		match kind.to_lowercase().as_str() {
+			"u32" | "u64" | "timepoint" | "duration" | "i32" | "i64" | "u128" | "i128" | "u256"
+			| "i256" => match NumericValue::parse(value)? {
+				integer @ NumericValue::Integer { .. } => Ok(integer),
+				NumericValue::Decimal(_) => {
+					let msg = format!("Expected integer value for kind {}, found: {}", kind, value);
+					Err(EvaluationError::parse_error(msg, None, None))
+				}
+			},
+			"f64" => NumericValue::parse(value),
+			unknown_type => {
+				let msg = format!(
+					"Unsupported parameter type for arithmetic: {}",
+					unknown_type
+				);
+				Err(EvaluationError::type_mismatch(msg, None, None))
+			}
+		}
+	}
 }
 
 #[cfg(test)]
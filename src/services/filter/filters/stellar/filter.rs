@@ -17,18 +17,20 @@ use tracing::instrument;
 use crate::{
 	models::{
 		BlockType, ContractSpec, EventCondition, FunctionCondition, MatchConditions, Monitor,
-		MonitorMatch, Network, StellarContractFunction, StellarEvent, StellarFormattedContractSpec,
-		StellarMatchArguments, StellarMatchParamEntry, StellarMatchParamsMap, StellarMonitorMatch,
-		StellarTransaction, TransactionCondition, TransactionStatus,
+		MonitorMatch, Network, PollConfig, StellarContractFunction, StellarEvent,
+		StellarFormattedContractSpec, StellarMatchArguments, StellarMatchParamEntry,
+		StellarMatchParamsMap, StellarMonitorMatch, StellarTransaction, TransactionCondition,
+		TransactionStatus,
 	},
 	services::{
 		blockchain::{BlockChainClient, StellarClientTrait},
 		filter::{
+			self,
 			expression::{self, EvaluationError},
 			filters::stellar::evaluator::StellarConditionEvaluator,
 			stellar_helpers::{
-				are_same_signature, get_kind_from_value, normalize_address, parse_xdr_value,
-				process_invoke_host_function,
+				are_same_signature, get_event_index, get_kind_from_value, normalize_address,
+				parse_xdr_value, process_invoke_host_function,
 			},
 			BlockFilter, FilterError,
 		},
@@ -377,6 +379,7 @@ impl<T> StellarBlockFilter<T> {
 								functions.push(StellarMatchParamsMap {
 									signature: parsed_operation.function_signature.clone(),
 									args: Some(param_entries),
+									event_index: None,
 								});
 							}
 						} else {
@@ -405,6 +408,7 @@ impl<T> StellarBlockFilter<T> {
 															.function_signature
 															.clone(),
 														args: Some(param_entries.clone()),
+														event_index: None,
 													});
 												}
 												break;
@@ -431,6 +435,7 @@ impl<T> StellarBlockFilter<T> {
 													.function_signature
 													.clone(),
 												args: Some(param_entries.clone()),
+												event_index: None,
 											});
 										}
 										break;
@@ -525,6 +530,9 @@ impl<T> StellarBlockFilter<T> {
 								signature: event.signature.clone(),
 								expression: None,
 							});
+							if let Some(events) = &mut matched_on_args.events {
+								events.push(event.clone());
+							}
 						}
 					}
 				}
@@ -671,6 +679,7 @@ impl<T> StellarBlockFilter<T> {
 						})
 						.collect(),
 				),
+				event_index: get_event_index(&event.id),
 			};
 
 			decoded_events.push(EventMap {
@@ -718,6 +727,51 @@ impl<T> StellarBlockFilter<T> {
 		// Evaluate the expression
 		expression::evaluate(&parsed_ast, &evaluator)
 	}
+
+	/// Polls the state of a polling monitor if it is due at a ledger
+	///
+	/// Balances are read at the latest ledger. Failures to read the values are logged and release
+	/// the poll, so that the next ledger polls again. Failures to evaluate the expression are
+	/// logged, and skip the poll.
+	///
+	/// # Returns
+	/// The match of the poll if its expression holds
+	async fn poll_monitor<C: StellarClientTrait + Sync>(
+		&self,
+		client: &C,
+		network: &Network,
+		monitor: &Monitor,
+		poll: &PollConfig,
+		block: &BlockType,
+	) -> Option<MonitorMatch> {
+		let sequence = block.number().unwrap_or(0);
+		let key = filter::poll_key(&network.slug, monitor);
+		let reservation = filter::reserve_poll(&key, poll, sequence, block.timestamp())?;
+
+		let readings = match filter::read_stellar_poll(client, poll).await {
+			Ok(readings) => readings,
+			Err(e) => {
+				tracing::error!("Failed to poll monitor {}: {}", monitor.name, e);
+				// The next block polls again instead of waiting for the next period
+				filter::release_poll(&key, reservation);
+				return None;
+			}
+		};
+		filter::record_poll(&key, &readings);
+
+		filter::evaluate_poll(
+			monitor,
+			&network.slug,
+			sequence,
+			block.timestamp(),
+			&readings,
+			&reservation.previous,
+		)
+		.unwrap_or_else(|e| {
+			tracing::error!("Failed to poll monitor {}: {}", monitor.name, e);
+			None
+		})
+	}
 }
 
 #[async_trait]
@@ -807,6 +861,13 @@ impl<T: BlockChainClient + StellarClientTrait> BlockFilter for StellarBlockFilte
 		// Process each monitor first
 		for monitor in monitors {
 			tracing::debug!("Processing monitor: {}", monitor.name);
+			if let Some(poll) = &monitor.poll {
+				matching_results.extend(
+					self.poll_monitor(client, network, monitor, poll, block)
+						.await,
+				);
+				continue;
+			}
 
 			let monitored_addresses = monitor
 				.addresses
@@ -900,6 +961,7 @@ impl<T: BlockChainClient + StellarClientTrait> BlockFilter for StellarBlockFilte
 								.into_iter()
 								.filter(|_| has_transaction_match)
 								.collect(),
+							..Default::default()
 						},
 						matched_on_args: Some(StellarMatchArguments {
 							events: if has_event_match {
@@ -913,6 +975,7 @@ impl<T: BlockChainClient + StellarClientTrait> BlockFilter for StellarBlockFilte
 								None
 							},
 						}),
+						enrichment: None,
 					})));
 				}
 			}
@@ -970,6 +1033,7 @@ mod tests {
 				events: event_conditions,
 				functions: function_conditions,
 				transactions: transaction_conditions,
+				..Default::default()
 			})
 			.build()
 	}
@@ -1152,6 +1216,7 @@ mod tests {
 			event: StellarMatchParamsMap {
 				signature: event_signature.to_string(),
 				args,
+				event_index: None,
 			},
 			tx_hash: tx_hash.to_string(),
 		}
@@ -2088,12 +2153,13 @@ mod tests {
 		// Encode a simple u32 value (100) in base64
 		let value = BASE64.encode([0u8; 4]); // Simplified value encoding
 
-		let event = create_test_stellar_event(
+		let mut event = create_test_stellar_event(
 			contract_address,
 			"tx_hash_123",
 			vec![event_name],
 			Some(value),
 		);
+		event.id = "0000000008589938688-0000000002".to_string();
 
 		let events = vec![event];
 		let contract_specs = vec![];
@@ -2102,6 +2168,7 @@ mod tests {
 		assert_eq!(decoded.len(), 1);
 		assert_eq!(decoded[0].tx_hash, "tx_hash_123");
 		assert!(decoded[0].event.signature.starts_with("Transfer"));
+		assert_eq!(decoded[0].event.event_index, Some(2));
 	}
 
 	#[tokio::test]
@@ -2277,6 +2344,119 @@ mod tests {
 			.unwrap());
 	}
 
+	#[test]
+	fn test_evaluate_expression_address_membership() {
+		let filter = create_test_filter();
+		let args = vec![StellarMatchParamEntry {
+			name: "to".to_string(),
+			value: "GCXKG6RN4ONIEPCMNFB732A436Z5PNDSRLGWK7GBLCMQLIFO4S7EYWVU".to_string(),
+			kind: "address".to_string(),
+			indexed: false,
+		}];
+
+		crate::services::filter::register_address_sets(vec![crate::models::AddressSet {
+			name: "stellar_filter_test_wallets".to_string(),
+			addresses: vec!["gcxkg6rn4oniepcmnfb732a436z5pndsrlgwk7gblcmqlifo4s7eywvu".to_string()],
+		}]);
+
+		assert!(filter
+			.evaluate_expression(
+				"to in ['GBZXN7PIRZGNMHGA7MUUUF4GWPY5AYPV6LY4UV2GL6VJGIQRXFDNMADI', 'gcxkg6rn4oniepcmnfb732a436z5pndsrlgwk7gblcmqlifo4s7eywvu']",
+				&args
+			)
+			.unwrap());
+		assert!(filter
+			.evaluate_expression("to in @stellar_filter_test_wallets", &args)
+			.unwrap());
+		assert!(!filter
+			.evaluate_expression("to not in @stellar_filter_test_wallets", &args)
+			.unwrap());
+	}
+
+	#[test]
+	fn test_evaluate_expression_arithmetic() {
+		let filter = create_test_filter();
+		let param = |name: &str, value: &str, kind: &str| StellarMatchParamEntry {
+			name: name.to_string(),
+			value: value.to_string(),
+			kind: kind.to_string(),
+			indexed: false,
+		};
+		let args = vec![
+			param("amount", "150000000", "I128"),
+			param("fee", "100", "U32"),
+			param("big", "340282366920938463463374607431768211455", "U256"),
+			param("data", r#"{"in": 7, "out": -3}"#, "Map"),
+			param("symbol", "XLM", "Symbol"),
+		];
+
+		assert!(filter
+			.evaluate_expression("amount / 10^7 == 15", &args)
+			.unwrap());
+		assert!(filter
+			.evaluate_expression("(amount - fee) / 10^7 < 15", &args)
+			.unwrap());
+		assert!(filter
+			.evaluate_expression("big * 2 > big + fee", &args)
+			.unwrap());
+		assert!(filter
+			.evaluate_expression("data.in + data.out == 4", &args)
+			.unwrap());
+		assert!(filter
+			.evaluate_expression("data.in / 2 == 3.5", &args)
+			.unwrap());
+
+		assert!(matches!(
+			filter.evaluate_expression("fee / 0 > 1", &args),
+			Err(EvaluationError::ArithmeticError(_))
+		));
+		assert!(matches!(
+			filter.evaluate_expression("symbol + 1 > 0", &args),
+			Err(EvaluationError::TypeMismatch(_))
+		));
+	}
+
+	#[test]
+	fn test_evaluate_expression_variable_comparisons() {
+		let filter = create_test_filter();
+		let param = |name: &str, value: &str, kind: &str| StellarMatchParamEntry {
+			name: name.to_string(),
+			value: value.to_string(),
+			kind: kind.to_string(),
+			indexed: false,
+		};
+		let args = vec![
+			param(
+				"from",
+				"GCXKG6RN4ONIEPCMNFB732A436Z5PNDSRLGWK7GBLCMQLIFO4S7EYWVU",
+				"Address",
+			),
+			param(
+				"to",
+				"gcxkg6rn4oniepcmnfb732a436z5pndsrlgwk7gblcmqlifo4s7eywvu",
+				"Address",
+			),
+			param("amount", "100", "I128"),
+			param("min_amount", "150", "I128"),
+			param("data", r#"{"limit": 50, "flag": true}"#, "Map"),
+			param("enabled", "true", "Bool"),
+		];
+
+		assert!(filter.evaluate_expression("from == to", &args).unwrap());
+		assert!(filter
+			.evaluate_expression("amount < min_amount", &args)
+			.unwrap());
+		assert!(filter
+			.evaluate_expression("amount > data.limit", &args)
+			.unwrap());
+		assert!(filter
+			.evaluate_expression("enabled == data.flag", &args)
+			.unwrap());
+		assert!(!filter
+			.evaluate_expression("amount >= min_amount", &args)
+			.unwrap());
+	}
+
 	#[test]
 	fn test_evaluate_expression_basic_field_access() {
 		let filter = create_test_filter();
@@ -15,8 +15,9 @@ use std::collections::BTreeMap;
 use stellar_strkey::{ed25519::PublicKey as StrkeyPublicKey, Contract};
 use stellar_xdr::curr::{
 	AccountId, ContractExecutable, Hash, HostFunction, Int128Parts, Int256Parts,
-	InvokeHostFunctionOp, LedgerEntryData, LedgerKey, LedgerKeyContractCode, Limits, PublicKey,
-	ReadXdr, ScAddress, ScMapEntry, ScSpecEntry, ScSpecTypeDef, ScVal, UInt128Parts, UInt256Parts,
+	InvokeHostFunctionOp, LedgerEntryData, LedgerKey, LedgerKeyAccount, LedgerKeyContractCode,
+	Limits, PublicKey, ReadXdr, ScAddress, ScMapEntry, ScSpecEntry, ScSpecTypeDef, ScVal,
+	UInt128Parts, UInt256Parts, Uint256,
 };
 
 use crate::models::{
@@ -751,6 +752,24 @@ pub fn normalize_signature(signature: &str) -> String {
 	signature.trim().replace(" ", "").to_lowercase()
 }
 
+/// Gets the index of an event in its transaction from the event identifier.
+///
+/// Event identifiers are `<TOID>-<index>`, the TOID of the operation emitting the event ending
+/// with its 12-bit operation index, and the index counting the events of the operation.
+///
+/// # Arguments
+/// * `event_id` - The identifier of the event, as returned by the RPC
+///
+/// # Returns
+/// The operation index in the high 32 bits and the event index in the low 32 bits, or `None` if
+/// the identifier is malformed
+pub fn get_event_index(event_id: &str) -> Option<u64> {
+	let (toid, index) = event_id.split_once('-')?;
+	let operation_index = toid.parse::<u64>().ok()? & 0xFFF;
+	let event_index = index.parse::<u32>().ok()?;
+	Some((operation_index << 32) | u64::from(event_index))
+}
+
 /// Parses a Stellar Contract Value into a decoded parameter entry.
 ///
 /// # Arguments
@@ -1006,6 +1025,40 @@ pub fn get_wasm_hash_from_ledger_entry_data(
 	}
 }
 
+/// Creates a LedgerKey for an account.
+///
+/// # Arguments
+/// * `account_id` - The account ID in Stellar strkey format (starts with 'G')
+///
+/// # Returns
+/// A Result containing the LedgerKey if successful, or an error if the account ID is invalid
+pub fn get_account_ledger_key(account_id: &str) -> Result<LedgerKey, anyhow::Error> {
+	let public_key = StrkeyPublicKey::from_string(account_id.to_uppercase().as_str())
+		.map_err(|e| anyhow::anyhow!("Failed to decode account ID: {}", e))?;
+
+	Ok(LedgerKey::Account(LedgerKeyAccount {
+		account_id: AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(public_key.0))),
+	}))
+}
+
+/// Get the native balance of an account from its XDR-encoded ledger entry.
+///
+/// # Arguments
+/// * `ledger_entry_data` - The XDR-encoded account entry
+///
+/// # Returns
+/// A Result containing the balance in stroops if successful, or an error if parsing fails
+pub fn get_balance_from_ledger_entry_data(ledger_entry_data: &str) -> Result<i64, anyhow::Error> {
+	let val = LedgerEntryData::from_xdr_base64(ledger_entry_data.as_bytes(), Limits::none())
+		.map_err(|e| anyhow::anyhow!("Failed to parse account entry XDR: {}", e))?;
+
+	if let LedgerEntryData::Account(account) = val {
+		Ok(account.balance)
+	} else {
+		Err(anyhow::anyhow!("XDR value is not an account entry"))
+	}
+}
+
 /// Convert a hexadecimal string to a byte vector.
 ///
 /// # Arguments
@@ -1306,6 +1359,37 @@ mod tests {
 		assert!(result.is_err());
 	}
 
+	#[test]
+	fn test_get_account_balance_from_ledger_entry() {
+		let account_id = "GCXKG6RN4ONIEPCMNFB732A436Z5PNDSRLGWK7GBLCMQLIFO4S7EYWVU";
+		let ledger_key = get_account_ledger_key(account_id).unwrap();
+		let LedgerKey::Account(key) = ledger_key else {
+			panic!("Expected LedgerKey::Account");
+		};
+		let AccountId(PublicKey::PublicKeyTypeEd25519(public_key)) = &key.account_id;
+		assert_eq!(StrkeyPublicKey(public_key.0).to_string(), account_id);
+		assert!(get_account_ledger_key("CINVALID").is_err());
+
+		let entry = LedgerEntryData::Account(stellar_xdr::curr::AccountEntry {
+			account_id: key.account_id,
+			balance: 1_250_000_000,
+			seq_num: stellar_xdr::curr::SequenceNumber(1),
+			num_sub_entries: 0,
+			inflation_dest: None,
+			flags: 0,
+			home_domain: Default::default(),
+			thresholds: stellar_xdr::curr::Thresholds([1, 0, 0, 0]),
+			signers: Default::default(),
+			ext: stellar_xdr::curr::AccountEntryExt::V0,
+		});
+		let xdr = stellar_xdr::curr::WriteXdr::to_xdr_base64(&entry, Limits::none()).unwrap();
+		assert_eq!(
+			get_balance_from_ledger_entry_data(&xdr).unwrap(),
+			1_250_000_000
+		);
+		assert!(get_balance_from_ledger_entry_data("invalid_xdr").is_err());
+	}
+
 	#[test]
 	fn test_get_wasm_code_from_ledger_entry_data() {
 		// Test with valid contract code XDR
@@ -2433,4 +2517,17 @@ mod tests {
 			StellarType::Map(_, _)
 		));
 	}
+
+	#[test]
+	fn test_get_event_index() {
+		// Ledger 2, transaction 1, operation 0
+		assert_eq!(get_event_index("0000000008589938688-0000000003"), Some(3));
+		// Operation 1
+		assert_eq!(
+			get_event_index("0000000008589938689-0000000000"),
+			Some(1 << 32)
+		);
+		assert_eq!(get_event_index("0000000008589938688"), None);
+		assert_eq!(get_event_index("toid-0000000001"), None);
+	}
 }
@@ -4,14 +4,42 @@
 //! - Block filtering for different chain types
 //! - Match handling and processing
 //! - Chain-specific helper functions
+//! - Named address sets referenced from filter expressions
+//! - Aggregates over events and matches
+//! - Native filter plugins registered by embedding applications
+//! - Token metadata used to normalize token amounts
+//! - Contract state read with view calls at the block of a match
+//! - State polling of monitors independent of transactions
 
+mod address_sets;
+mod aggregates;
 mod error;
 mod expression;
 mod filter_match;
 mod filters;
+mod plugins;
+mod polling;
+mod state;
+mod tokens;
 
+pub use address_sets::{address_set_contains, register_address_sets, AddressSetRegistry};
+pub use aggregates::{aggregate, evaluate_aggregate_expression};
 pub use error::FilterError;
-pub use filter_match::handle_match;
+pub use filter_match::{build_match_variables, handle_match, handle_match_with_variables};
+pub use plugins::{FilterPlugin, FilterPluginRegistry};
+pub use polling::{
+	decode_output, evaluate_poll, poll_key, read_evm_poll, read_stellar_poll, record_poll,
+	release_poll, reserve_poll, PollReading, PollReservation, PollScheduler, PollStateStore,
+	BLOCK_NUMBER_VARIABLE, BLOCK_TIMESTAMP_VARIABLE,
+};
+pub use state::{
+	decode_result, encode_call, evaluate_state_expression, parse_call_signature, parse_return_type,
+	read_state, state_enrichment, PREVIOUS_SUFFIX,
+};
+pub use tokens::{
+	fetch_token_metadata, register_token_metadata, register_tokens, to_raw_amount, token_decimals,
+	token_has_conflicting_decimals, token_metadata, TokenMetadata, TokenRegistry,
+};
 
 pub use filters::{
 	evm::helpers as evm_helpers, stellar::helpers as stellar_helpers, BlockFilter, EVMArgs,
@@ -19,4 +47,7 @@ pub use filters::{
 	StellarBlockFilter, StellarConditionEvaluator,
 };
 
-pub use expression::{ComparisonOperator, ConditionEvaluator, EvaluationError, LiteralValue};
+pub use expression::{
+	address_set_references, ComparisonOperator, ConditionEvaluator, EvaluationError, LiteralValue,
+	NumericValue,
+};
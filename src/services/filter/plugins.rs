@@ -0,0 +1,267 @@
+//! Native filter plugins.
+//!
+//! Applications embedding the monitor as a library register filter plugins on the
+//! `FilterService` at startup. Monitors reference them by name in their `filter_plugins`, and a
+//! match is only kept if every plugin referenced by its monitor accepts it.
+
+use async_trait::async_trait;
+use std::{
+	collections::HashMap,
+	sync::{Arc, RwLock},
+};
+
+use crate::{
+	models::{Monitor, MonitorMatch},
+	services::filter::error::FilterError,
+};
+
+/// Interface for custom match predicates written in Rust
+///
+/// Plugins run after the match conditions of a monitor, on the matches they produced. Per
+/// transaction event aggregates are part of the match conditions, so they are evaluated before
+/// plugins, while sliding windows only record the matches plugins accept.
+#[async_trait]
+pub trait FilterPlugin: Send + Sync {
+	/// Returns whether a match is kept
+	///
+	/// # Arguments
+	/// * `monitor_match` - Match produced by the match conditions of the monitor
+	/// * `options` - Options of the plugin in the monitor configuration
+	///
+	/// # Returns
+	/// * `Result<bool, FilterError>` - Whether the match is kept, or error
+	async fn evaluate(
+		&self,
+		monitor_match: &MonitorMatch,
+		options: &serde_json::Map<String, serde_json::Value>,
+	) -> Result<bool, FilterError>;
+}
+
+/// Filter plugins keyed by the name monitors reference them with
+#[derive(Default)]
+pub struct FilterPluginRegistry {
+	plugins: RwLock<HashMap<String, Arc<dyn FilterPlugin>>>,
+}
+
+impl FilterPluginRegistry {
+	/// Registers a plugin, replacing any plugin previously registered with the same name
+	pub fn register(&self, name: impl Into<String>, plugin: Arc<dyn FilterPlugin>) {
+		self.plugins
+			.write()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+			.insert(name.into(), plugin);
+	}
+
+	/// Returns the plugin registered with a name, if any
+	pub fn get(&self, name: &str) -> Option<Arc<dyn FilterPlugin>> {
+		self.plugins
+			.read()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+			.get(name)
+			.cloned()
+	}
+
+	/// Checks that every filter plugin referenced by the monitors is registered
+	///
+	/// # Arguments
+	/// * `monitors` - Monitors to check
+	///
+	/// # Returns
+	/// * `Result<(), FilterError>` - Error naming the first monitor referencing a plugin which is
+	///   not registered
+	#[allow(clippy::result_large_err)]
+	pub fn check(&self, monitors: &[Monitor]) -> Result<(), FilterError> {
+		for monitor in monitors {
+			for condition in &monitor.filter_plugins {
+				if self.get(&condition.plugin).is_none() {
+					return Err(FilterError::internal_error(
+						format!(
+							"Filter plugin '{}' of monitor '{}' is not registered",
+							condition.plugin, monitor.name
+						),
+						None,
+						None,
+					));
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Keeps the matches accepted by every filter plugin of their monitor
+	///
+	/// A match whose monitor references a plugin which is not registered, or for which a plugin
+	/// fails, is logged and dropped without affecting the matches of other monitors.
+	///
+	/// # Arguments
+	/// * `matches` - Matches produced by the match conditions of the monitors
+	///
+	/// # Returns
+	/// * `Vec<MonitorMatch>` - Accepted matches
+	pub async fn apply(&self, matches: Vec<MonitorMatch>) -> Vec<MonitorMatch> {
+		let mut accepted = Vec::with_capacity(matches.len());
+
+		'matches: for monitor_match in matches {
+			let monitor = match &monitor_match {
+				MonitorMatch::EVM(evm_match) => &evm_match.monitor,
+				MonitorMatch::Stellar(stellar_match) => &stellar_match.monitor,
+				MonitorMatch::Poll(poll_match) => &poll_match.monitor,
+			};
+			for condition in &monitor.filter_plugins {
+				let Some(plugin) = self.get(&condition.plugin) else {
+					tracing::error!(
+						"Filter plugin '{}' of monitor '{}' is not registered, dropping match",
+						condition.plugin,
+						monitor.name
+					);
+					continue 'matches;
+				};
+				match plugin.evaluate(&monitor_match, &condition.options).await {
+					Ok(true) => {}
+					Ok(false) => continue 'matches,
+					Err(e) => {
+						tracing::error!(
+							"Filter plugin '{}' of monitor '{}' failed, dropping match: {}",
+							condition.plugin,
+							monitor.name,
+							e
+						);
+						continue 'matches;
+					}
+				}
+			}
+			accepted.push(monitor_match);
+		}
+
+		accepted
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		models::{EVMMonitorMatch, EVMTransactionReceipt, MatchConditions},
+		utils::tests::{
+			builders::evm::monitor::MonitorBuilder, evm::transaction::TransactionBuilder,
+		},
+	};
+
+	/// Keeps the matches of the networks listed in its `networks` option
+	struct NetworkPlugin;
+
+	#[async_trait]
+	impl FilterPlugin for NetworkPlugin {
+		async fn evaluate(
+			&self,
+			monitor_match: &MonitorMatch,
+			options: &serde_json::Map<String, serde_json::Value>,
+		) -> Result<bool, FilterError> {
+			let MonitorMatch::EVM(evm_match) = monitor_match else {
+				return Ok(false);
+			};
+			Ok(options["networks"]
+				.as_array()
+				.into_iter()
+				.flatten()
+				.any(|network| network == evm_match.network_slug.as_str()))
+		}
+	}
+
+	fn create_match(network_slug: &str, plugin: Option<&str>) -> MonitorMatch {
+		let mut monitor = MonitorBuilder::new().name("test");
+		if let Some(plugin) = plugin {
+			let options = serde_json::json!({ "networks": ["ethereum_mainnet"] });
+			monitor = monitor.filter_plugin(plugin, options.as_object().unwrap().clone());
+		}
+		MonitorMatch::EVM(Box::new(EVMMonitorMatch {
+			monitor: monitor.build(),
+			transaction: TransactionBuilder::new().build(),
+			receipt: Some(EVMTransactionReceipt::default()),
+			logs: Some(vec![]),
+			network_slug: network_slug.to_string(),
+			matched_on: MatchConditions::default(),
+			matched_on_args: None,
+			enrichment: None,
+		}))
+	}
+
+	#[tokio::test]
+	async fn test_apply_filter_plugins() {
+		let registry = FilterPluginRegistry::default();
+		registry.register("networks", Arc::new(NetworkPlugin));
+
+		let matches = vec![
+			create_match("ethereum_mainnet", Some("networks")),
+			create_match("ethereum_sepolia", Some("networks")),
+			create_match("ethereum_sepolia", None),
+		];
+		let accepted = registry.apply(matches).await;
+
+		let networks: Vec<_> = accepted
+			.iter()
+			.map(|monitor_match| match monitor_match {
+				MonitorMatch::EVM(evm_match) => evm_match.network_slug.as_str(),
+				_ => unreachable!(),
+			})
+			.collect();
+		assert_eq!(networks, vec!["ethereum_mainnet", "ethereum_sepolia"]);
+	}
+
+	/// Fails on every match
+	struct FailingPlugin;
+
+	#[async_trait]
+	impl FilterPlugin for FailingPlugin {
+		async fn evaluate(
+			&self,
+			_monitor_match: &MonitorMatch,
+			_options: &serde_json::Map<String, serde_json::Value>,
+		) -> Result<bool, FilterError> {
+			Err(FilterError::internal_error("plugin failed", None, None))
+		}
+	}
+
+	#[tokio::test]
+	async fn test_apply_unregistered_or_failing_filter_plugin() {
+		let registry = FilterPluginRegistry::default();
+		registry.register("failing", Arc::new(FailingPlugin));
+
+		// Only the matches of the affected monitors are dropped
+		let accepted = registry
+			.apply(vec![
+				create_match("ethereum_mainnet", Some("missing")),
+				create_match("ethereum_mainnet", Some("failing")),
+				create_match("ethereum_sepolia", None),
+			])
+			.await;
+		assert_eq!(accepted.len(), 1);
+		assert!(accepted[0].enrichment().is_none());
+		match &accepted[0] {
+			MonitorMatch::EVM(evm_match) => assert_eq!(evm_match.network_slug, "ethereum_sepolia"),
+			_ => unreachable!(),
+		}
+	}
+
+	#[test]
+	fn test_check_filter_plugins() {
+		let registry = FilterPluginRegistry::default();
+		registry.register("networks", Arc::new(NetworkPlugin));
+
+		let options = serde_json::Map::new();
+		let registered = MonitorBuilder::new()
+			.filter_plugin("networks", options.clone())
+			.build();
+		let missing = MonitorBuilder::new()
+			.name("test")
+			.filter_plugin("missing", options)
+			.build();
+
+		assert!(registry.check(&[registered.clone()]).is_ok());
+		assert!(registry
+			.check(&[registered, missing])
+			.unwrap_err()
+			.to_string()
+			.contains("Filter plugin 'missing' of monitor 'test' is not registered"));
+	}
+}
@@ -0,0 +1,724 @@
+//! State polling of monitors independent of transactions.
+//!
+//! Polling monitors read balances and view functions every N blocks or every N seconds of block
+//! time, and match when their expression holds against the values read. Each value is also
+//! exposed with the `_previous` suffix, holding the value read by the previous poll of the same
+//! monitor on the same network, so that expressions can detect changes.
+//!
+//! The last poll of each monitor is persisted through the block storage, so that schedules and
+//! previous values survive restarts.
+
+use ethabi::ParamType;
+use lazy_static::lazy_static;
+use std::{
+	collections::{HashMap, HashSet},
+	sync::{Arc, Mutex},
+};
+
+use crate::{
+	models::{
+		EVMMatchParamEntry, Monitor, MonitorMatch, PollConfig, PollMonitorMatch, PollRead,
+		PollState, PollValue,
+	},
+	services::{
+		blockchain::{EvmClientTrait, StellarClientTrait},
+		blockwatcher::BlockStorage,
+		filter::{
+			evm_helpers::format_token_value,
+			state::{
+				decode_result, encode_call, evaluate_state_expression, parse_return_type,
+				PREVIOUS_SUFFIX,
+			},
+		},
+	},
+};
+
+/// Name of the variable holding the number of the polled block
+pub const BLOCK_NUMBER_VARIABLE: &str = "block_number";
+
+/// Name of the variable holding the timestamp of the polled block
+pub const BLOCK_TIMESTAMP_VARIABLE: &str = "block_timestamp";
+
+lazy_static! {
+	static ref POLLS: PollScheduler = PollScheduler::default();
+}
+
+/// Value read by a poll before it is compared with the previous poll
+#[derive(Debug, Clone, PartialEq)]
+pub struct PollReading {
+	/// Name of the read
+	pub name: String,
+	/// Value read, in the value format of match parameters
+	pub value: String,
+	/// Type of the value (e.g., "uint256")
+	pub kind: String,
+}
+
+/// Poll of a monitor reserved at a block
+#[derive(Debug, Clone, PartialEq)]
+pub struct PollReservation {
+	/// Values read by the previous poll, empty on the first poll
+	pub previous: HashMap<String, String>,
+	/// Block the poll is reserved at
+	block_number: u64,
+	/// Timestamp of the block the poll is reserved at
+	timestamp: Option<u64>,
+	/// Block and timestamp of the previous poll, `None` on the first poll
+	last: Option<(u64, Option<u64>)>,
+}
+
+/// Last poll of a monitor on a network
+#[derive(Debug, Default)]
+struct ScheduledPoll {
+	state: PollState,
+	/// Whether the state changed since it was last taken to be persisted
+	updated: bool,
+}
+
+/// Schedule and last values of the polls, keyed by network and monitor
+#[derive(Debug, Default)]
+pub struct PollScheduler {
+	polls: Mutex<HashMap<String, ScheduledPoll>>,
+}
+
+impl PollScheduler {
+	/// Checks whether a monitor is due to be polled at a block, and reserves the poll if so
+	///
+	/// Blocks may be processed concurrently, so the poll is recorded as soon as it is due to
+	/// prevent two blocks of the same interval from both polling. The reservation is released if
+	/// the values cannot be read.
+	///
+	/// # Arguments
+	/// * `key` - Network and monitor the poll belongs to
+	/// * `config` - Polling configuration of the monitor
+	/// * `block_number` - Block being processed
+	/// * `timestamp` - Timestamp of the block, if known
+	///
+	/// # Returns
+	/// * `Option<PollReservation>` - Reservation holding the values read by the previous poll if
+	///   the monitor is due, `None` otherwise
+	pub fn reserve(
+		&self,
+		key: &str,
+		config: &PollConfig,
+		block_number: u64,
+		timestamp: Option<u64>,
+	) -> Option<PollReservation> {
+		let mut polls = self
+			.polls
+			.lock()
+			.unwrap_or_else(|poisoned| poisoned.into_inner());
+
+		if let Some(last) = polls.get(key).map(|poll| &poll.state) {
+			let is_due = match (config.every_blocks, config.interval_secs) {
+				(Some(blocks), _) => block_number >= last.block_number.saturating_add(blocks),
+				(None, Some(secs)) => match (timestamp, last.timestamp) {
+					(Some(timestamp), Some(last)) => timestamp >= last.saturating_add(secs),
+					(Some(_), None) => true,
+					(None, _) => false,
+				},
+				(None, None) => false,
+			};
+			if !is_due {
+				return None;
+			}
+		}
+
+		let last = polls
+			.get(key)
+			.map(|poll| (poll.state.block_number, poll.state.timestamp));
+		let poll = polls.entry(key.to_string()).or_default();
+		poll.state.block_number = block_number;
+		poll.state.timestamp = timestamp;
+		poll.updated = true;
+		Some(PollReservation {
+			previous: poll.state.values.clone(),
+			block_number,
+			timestamp,
+			last,
+		})
+	}
+
+	/// Releases a poll whose values could not be read, so that the next block polls again
+	///
+	/// The schedule of the previous poll is restored, unless a later block reserved the poll since.
+	pub fn release(&self, key: &str, reservation: PollReservation) {
+		let mut polls = self
+			.polls
+			.lock()
+			.unwrap_or_else(|poisoned| poisoned.into_inner());
+		let Some(poll) = polls.get_mut(key) else {
+			return;
+		};
+		if (poll.state.block_number, poll.state.timestamp)
+			!= (reservation.block_number, reservation.timestamp)
+		{
+			return;
+		}
+
+		match reservation.last {
+			Some((block_number, timestamp)) => {
+				poll.state.block_number = block_number;
+				poll.state.timestamp = timestamp;
+				poll.updated = true;
+			}
+			None => {
+				polls.remove(key);
+			}
+		}
+	}
+
+	/// Records the values read by a poll as the previous values of the next poll
+	pub fn record(&self, key: &str, readings: &[PollReading]) {
+		let mut polls = self
+			.polls
+			.lock()
+			.unwrap_or_else(|poisoned| poisoned.into_inner());
+		let poll = polls.entry(key.to_string()).or_default();
+		poll.state.values = readings
+			.iter()
+			.map(|reading| (reading.name.clone(), reading.value.clone()))
+			.collect();
+		poll.updated = true;
+	}
+
+	/// Restores a persisted poll, unless the monitor has already been polled
+	pub fn restore(&self, key: &str, state: PollState) {
+		self.polls
+			.lock()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+			.entry(key.to_string())
+			.or_insert(ScheduledPoll {
+				state,
+				updated: false,
+			});
+	}
+
+	/// Returns the state of a poll if it changed since it was last taken
+	pub fn take_updated(&self, key: &str) -> Option<PollState> {
+		let mut polls = self
+			.polls
+			.lock()
+			.unwrap_or_else(|poisoned| poisoned.into_inner());
+		let poll = polls.get_mut(key).filter(|poll| poll.updated)?;
+		poll.updated = false;
+		Some(poll.state.clone())
+	}
+}
+
+/// Persists the polls of the global scheduler through block storage
+///
+/// The polls of a network are restored before its first block is filtered, and the polls that
+/// changed are saved after each block.
+pub struct PollStateStore<S: BlockStorage> {
+	/// Storage used to persist poll state
+	storage: Arc<S>,
+	/// Keys of the polls already restored
+	restored: Mutex<HashSet<String>>,
+	/// Serializes saves so that the state of a later block is never overwritten by an earlier one
+	lock: tokio::sync::Mutex<()>,
+}
+
+impl<S: BlockStorage> PollStateStore<S> {
+	/// Creates a new poll state store
+	///
+	/// # Arguments
+	/// * `storage` - Storage used to persist poll state
+	pub fn new(storage: Arc<S>) -> Self {
+		Self {
+			storage,
+			restored: Mutex::new(HashSet::new()),
+			lock: tokio::sync::Mutex::new(()),
+		}
+	}
+
+	/// Restores the persisted polls of the polling monitors of a network
+	///
+	/// # Arguments
+	/// * `network_slug` - Network the monitors are polled on
+	/// * `monitors` - Monitors applicable to the network
+	pub async fn restore(&self, network_slug: &str, monitors: &[Monitor]) {
+		self.restore_into(&POLLS, network_slug, monitors).await
+	}
+
+	/// Saves the polls of the polling monitors of a network that changed
+	///
+	/// # Arguments
+	/// * `network_slug` - Network the monitors are polled on
+	/// * `monitors` - Monitors applicable to the network
+	pub async fn persist(&self, network_slug: &str, monitors: &[Monitor]) {
+		self.persist_from(&POLLS, network_slug, monitors).await
+	}
+
+	async fn restore_into(
+		&self,
+		scheduler: &PollScheduler,
+		network_slug: &str,
+		monitors: &[Monitor],
+	) {
+		for monitor in monitors.iter().filter(|monitor| monitor.poll.is_some()) {
+			let key = poll_key(network_slug, monitor);
+			if !self
+				.restored
+				.lock()
+				.unwrap_or_else(|poisoned| poisoned.into_inner())
+				.insert(key.clone())
+			{
+				continue;
+			}
+
+			match self
+				.storage
+				.get_poll_state(network_slug, &monitor.name)
+				.await
+			{
+				Ok(Some(state)) => scheduler.restore(&key, state),
+				Ok(None) => {}
+				Err(e) => tracing::error!(
+					"Failed to load poll state for monitor '{}': {}",
+					monitor.name,
+					e
+				),
+			}
+		}
+	}
+
+	async fn persist_from(
+		&self,
+		scheduler: &PollScheduler,
+		network_slug: &str,
+		monitors: &[Monitor],
+	) {
+		let _guard = self.lock.lock().await;
+		for monitor in monitors.iter().filter(|monitor| monitor.poll.is_some()) {
+			let Some(state) = scheduler.take_updated(&poll_key(network_slug, monitor)) else {
+				continue;
+			};
+			if let Err(e) = self
+				.storage
+				.save_poll_state(network_slug, &monitor.name, &state)
+				.await
+			{
+				tracing::error!(
+					"Failed to save poll state for monitor '{}': {}",
+					monitor.name,
+					e
+				);
+			}
+		}
+	}
+}
+
+/// Key of the polls of a monitor on a network
+pub fn poll_key(network_slug: &str, monitor: &Monitor) -> String {
+	format!("{}:{}", network_slug, monitor.name)
+}
+
+/// Checks whether a monitor is due to be polled at a block with the global scheduler
+pub fn reserve_poll(
+	key: &str,
+	config: &PollConfig,
+	block_number: u64,
+	timestamp: Option<u64>,
+) -> Option<PollReservation> {
+	POLLS.reserve(key, config, block_number, timestamp)
+}
+
+/// Releases a poll of the global scheduler whose values could not be read
+pub fn release_poll(key: &str, reservation: PollReservation) {
+	POLLS.release(key, reservation);
+}
+
+/// Records the values read by a poll in the global scheduler
+pub fn record_poll(key: &str, readings: &[PollReading]) {
+	POLLS.record(key, readings);
+}
+
+/// Decodes the hex-encoded result of a poll call, selecting one of the returned values if the
+/// function returns several
+///
+/// # Returns
+/// * `Result<(String, String), anyhow::Error>` - Value and type of the value
+pub fn decode_output(
+	returns: &str,
+	output: Option<usize>,
+	result: &str,
+) -> Result<(String, String), anyhow::Error> {
+	let returns = parse_return_type(returns)?;
+	let Some(index) = output else {
+		return Ok((decode_result(&returns, result)?, returns.to_string()));
+	};
+
+	let ParamType::Tuple(types) = returns else {
+		return Err(anyhow::anyhow!(
+			"output requires a tuple return type, got {}",
+			returns
+		));
+	};
+	let kind = types
+		.get(index)
+		.ok_or_else(|| anyhow::anyhow!("Output {} out of range", index))?
+		.to_string();
+	let data = hex::decode(result.strip_prefix("0x").unwrap_or(result))
+		.map_err(|e| anyhow::anyhow!("Invalid call result '{}': {}", result, e))?;
+	let tokens = ethabi::decode(&types, &data)
+		.map_err(|e| anyhow::anyhow!("Failed to decode call result: {}", e))?;
+	Ok((format_token_value(&tokens[index]), kind))
+}
+
+/// Reads the values of a poll on an EVM network at a block
+pub async fn read_evm_poll<C: EvmClientTrait + ?Sized>(
+	client: &C,
+	config: &PollConfig,
+	block_number: u64,
+) -> Result<Vec<PollReading>, anyhow::Error> {
+	let mut readings = Vec::new();
+	for read in &config.reads {
+		let (value, kind) = match read {
+			PollRead::Balance { address, .. } => {
+				let balance = client
+					.get_balance(address, Some(block_number))
+					.await
+					.map_err(|e| anyhow::anyhow!("Balance read of {} failed: {}", address, e))?;
+				(balance.to_string(), "uint256".to_string())
+			}
+			PollRead::Call {
+				name,
+				address,
+				signature,
+				args,
+				returns,
+				output,
+			} => {
+				let data = encode_call(signature, args)?;
+				let result = client
+					.call_contract(address, &data, Some(block_number))
+					.await
+					.map_err(|e| anyhow::anyhow!("{} call to {} failed: {}", name, address, e))?;
+				decode_output(returns, *output, &result)?
+			}
+		};
+		readings.push(PollReading {
+			name: read.name().to_string(),
+			value,
+			kind,
+		});
+	}
+	Ok(readings)
+}
+
+/// Reads the values of a poll on a Stellar network at the latest ledger
+///
+/// Only balance reads are supported on Stellar. Stellar RPC only serves the current ledger
+/// entries, so the values are those of the latest ledger rather than of the ledger being
+/// processed. While the monitor catches up on past ledgers, its polls report current values.
+pub async fn read_stellar_poll<C: StellarClientTrait + ?Sized>(
+	client: &C,
+	config: &PollConfig,
+) -> Result<Vec<PollReading>, anyhow::Error> {
+	let mut readings = Vec::new();
+	for read in &config.reads {
+		let value = match read {
+			PollRead::Balance { address, .. } => client
+				.get_account_balance(address)
+				.await
+				.map_err(|e| anyhow::anyhow!("Balance read of {} failed: {}", address, e))?,
+			PollRead::Call { name, .. } => {
+				return Err(anyhow::anyhow!(
+					"{} call reads are not supported on Stellar",
+					name
+				));
+			}
+		};
+		readings.push(PollReading {
+			name: read.name().to_string(),
+			value: value.to_string(),
+			kind: "int64".to_string(),
+		});
+	}
+	Ok(readings)
+}
+
+/// Evaluates the expression of a poll and builds its match
+///
+/// # Arguments
+/// * `monitor` - Polling monitor
+/// * `network_slug` - Network the values were read on
+/// * `block_number` - Block the values were read at
+/// * `timestamp` - Timestamp of the block, if known
+/// * `readings` - Values read by the poll
+/// * `previous` - Values read by the previous poll, empty on the first poll
+///
+/// # Returns
+/// * `Result<Option<MonitorMatch>, anyhow::Error>` - Match if the expression holds
+pub fn evaluate_poll(
+	monitor: &Monitor,
+	network_slug: &str,
+	block_number: u64,
+	timestamp: Option<u64>,
+	readings: &[PollReading],
+	previous: &HashMap<String, String>,
+) -> Result<Option<MonitorMatch>, anyhow::Error> {
+	let Some(config) = &monitor.poll else {
+		return Ok(None);
+	};
+
+	let values = readings
+		.iter()
+		.map(|reading| PollValue {
+			name: reading.name.clone(),
+			value: reading.value.clone(),
+			previous: previous
+				.get(&reading.name)
+				.cloned()
+				.unwrap_or_else(|| reading.value.clone()),
+			kind: reading.kind.clone(),
+		})
+		.collect::<Vec<_>>();
+
+	let entry = |name: String, value: String, kind: &str| EVMMatchParamEntry {
+		name,
+		value,
+		kind: kind.to_string(),
+		indexed: false,
+	};
+	let mut params = vec![entry(
+		BLOCK_NUMBER_VARIABLE.to_string(),
+		block_number.to_string(),
+		"uint64",
+	)];
+	if let Some(timestamp) = timestamp {
+		params.push(entry(
+			BLOCK_TIMESTAMP_VARIABLE.to_string(),
+			timestamp.to_string(),
+			"uint64",
+		));
+	}
+	for value in &values {
+		params.push(entry(value.name.clone(), value.value.clone(), &value.kind));
+		params.push(entry(
+			format!("{}{}", value.name, PREVIOUS_SUFFIX),
+			value.previous.clone(),
+			&value.kind,
+		));
+	}
+
+	let matches = evaluate_state_expression(&config.expression, &params).map_err(|e| {
+		anyhow::anyhow!(
+			"Failed to evaluate poll expression '{}': {}",
+			config.expression,
+			e
+		)
+	})?;
+	if !matches {
+		return Ok(None);
+	}
+
+	Ok(Some(MonitorMatch::Poll(Box::new(PollMonitorMatch {
+		monitor: monitor.clone(),
+		network_slug: network_slug.to_string(),
+		block_number,
+		timestamp,
+		values,
+		enrichment: None,
+	}))))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::utils::tests::evm::monitor::MonitorBuilder;
+
+	fn config(every_blocks: Option<u64>, interval_secs: Option<u64>) -> PollConfig {
+		PollConfig {
+			every_blocks,
+			interval_secs,
+			reads: vec![PollRead::Balance {
+				name: "balance".to_string(),
+				address: "0x0000000000000000000000000000000000000001".to_string(),
+			}],
+			expression: "balance < 1000".to_string(),
+		}
+	}
+
+	fn reading(value: &str) -> PollReading {
+		PollReading {
+			name: "balance".to_string(),
+			value: value.to_string(),
+			kind: "uint256".to_string(),
+		}
+	}
+
+	#[test]
+	fn test_polls_every_blocks() {
+		let scheduler = PollScheduler::default();
+		let config = config(Some(10), None);
+
+		assert_eq!(
+			scheduler
+				.reserve("a", &config, 100, None)
+				.map(|reservation| reservation.previous),
+			Some(HashMap::new())
+		);
+		scheduler.record("a", &[reading("5000")]);
+		assert_eq!(scheduler.reserve("a", &config, 100, None), None);
+		assert_eq!(scheduler.reserve("a", &config, 109, None), None);
+		assert_eq!(
+			scheduler
+				.reserve("a", &config, 110, None)
+				.map(|reservation| reservation.previous),
+			Some(HashMap::from([("balance".to_string(), "5000".to_string())]))
+		);
+		assert_eq!(scheduler.reserve("a", &config, 111, None), None);
+		assert!(scheduler.reserve("b", &config, 111, None).is_some());
+	}
+
+	#[test]
+	fn test_polls_every_interval() {
+		let scheduler = PollScheduler::default();
+		let config = config(None, Some(60));
+
+		assert!(scheduler.reserve("a", &config, 1, Some(1000)).is_some());
+		assert!(scheduler.reserve("a", &config, 2, Some(1059)).is_none());
+		assert!(scheduler.reserve("a", &config, 3, None).is_none());
+		assert!(scheduler.reserve("a", &config, 4, Some(1060)).is_some());
+	}
+
+	#[test]
+	fn test_released_poll_is_due_again() {
+		let scheduler = PollScheduler::default();
+		let config = config(Some(10), None);
+
+		// A failed first poll is retried at the next block
+		let reservation = scheduler.reserve("a", &config, 100, None).unwrap();
+		scheduler.release("a", reservation);
+		assert!(scheduler.take_updated("a").is_none());
+		assert!(scheduler.reserve("a", &config, 101, None).is_some());
+		scheduler.record("a", &[reading("5000")]);
+
+		// A failed poll keeps the schedule and values of the previous poll
+		let reservation = scheduler.reserve("a", &config, 111, None).unwrap();
+		scheduler.release("a", reservation);
+		assert_eq!(scheduler.take_updated("a").unwrap().block_number, 101);
+		assert_eq!(
+			scheduler
+				.reserve("a", &config, 112, None)
+				.map(|reservation| reservation.previous),
+			Some(HashMap::from([("balance".to_string(), "5000".to_string())]))
+		);
+
+		// Releasing a poll does not undo the poll of a later block
+		let reservation = scheduler.reserve("a", &config, 122, None).unwrap();
+		assert!(scheduler.reserve("a", &config, 132, None).is_some());
+		scheduler.release("a", reservation);
+		assert_eq!(scheduler.reserve("a", &config, 133, None), None);
+	}
+
+	#[tokio::test]
+	async fn test_poll_state_survives_restart() {
+		use crate::services::blockwatcher::FileBlockStorage;
+
+		let temp_dir = tempfile::tempdir().unwrap();
+		let storage = Arc::new(FileBlockStorage::new(temp_dir.path().to_path_buf()));
+		let monitors = vec![MonitorBuilder::new()
+			.name("hot wallet")
+			.poll(config(Some(10), None))
+			.build()];
+		let key = poll_key("ethereum_mainnet", &monitors[0]);
+
+		let scheduler = PollScheduler::default();
+		let store = PollStateStore::new(storage.clone());
+		store
+			.restore_into(&scheduler, "ethereum_mainnet", &monitors)
+			.await;
+		assert!(scheduler
+			.reserve(&key, &monitors[0].poll.clone().unwrap(), 100, None)
+			.is_some());
+		scheduler.record(&key, &[reading("5000")]);
+		store
+			.persist_from(&scheduler, "ethereum_mainnet", &monitors)
+			.await;
+		assert!(scheduler.take_updated(&key).is_none());
+
+		// A new scheduler resumes the schedule and previous values of the persisted poll
+		let scheduler = PollScheduler::default();
+		let store = PollStateStore::new(storage);
+		store
+			.restore_into(&scheduler, "ethereum_mainnet", &monitors)
+			.await;
+		let config = monitors[0].poll.clone().unwrap();
+		assert_eq!(scheduler.reserve(&key, &config, 105, None), None);
+		assert_eq!(
+			scheduler
+				.reserve(&key, &config, 110, None)
+				.map(|reservation| reservation.previous),
+			Some(HashMap::from([("balance".to_string(), "5000".to_string())]))
+		);
+	}
+
+	#[test]
+	fn test_decode_output() {
+		let result = format!("0x{:0>64}{:0>64}{:0>64}", "1", "5f5e100", "64");
+		assert_eq!(
+			decode_output("(uint80,int256,uint256)", Some(1), &result).unwrap(),
+			("100000000".to_string(), "int256".to_string())
+		);
+		assert_eq!(
+			decode_output("uint256", None, &format!("0x{:0>64}", "64")).unwrap(),
+			("100".to_string(), "uint256".to_string())
+		);
+		assert!(decode_output("(uint80,int256,uint256)", Some(3), &result).is_err());
+		assert!(decode_output("uint256", Some(0), &result).is_err());
+	}
+
+	#[test]
+	fn test_evaluate_poll() {
+		let monitor = MonitorBuilder::new()
+			.name("hot wallet")
+			.poll(PollConfig {
+				expression: "balance < 1000 AND balance_previous >= 1000 AND block_number == 7"
+					.to_string(),
+				..config(Some(1), None)
+			})
+			.build();
+
+		let previous = HashMap::from([("balance".to_string(), "1500".to_string())]);
+		let result = evaluate_poll(
+			&monitor,
+			"ethereum_mainnet",
+			7,
+			None,
+			&[reading("900")],
+			&previous,
+		)
+		.unwrap();
+		match result {
+			Some(MonitorMatch::Poll(poll_match)) => {
+				assert_eq!(poll_match.network_slug, "ethereum_mainnet");
+				assert_eq!(poll_match.block_number, 7);
+				assert_eq!(
+					poll_match.values,
+					vec![PollValue {
+						name: "balance".to_string(),
+						value: "900".to_string(),
+						previous: "1500".to_string(),
+						kind: "uint256".to_string(),
+					}]
+				);
+			}
+			_ => panic!("Expected a poll match"),
+		}
+
+		// The first poll compares the values read with themselves
+		assert!(evaluate_poll(
+			&monitor,
+			"ethereum_mainnet",
+			7,
+			None,
+			&[reading("900")],
+			&HashMap::new()
+		)
+		.unwrap()
+		.is_none());
+	}
+}
@@ -0,0 +1,267 @@
+//! Contract state read with view calls at the block of a match.
+//!
+//! Monitors declare read-only calls by ABI signature, with the contract address and arguments
+//! templated from the match variables. The calls are executed with `eth_call` at the block of the
+//! match (and optionally at the previous block), their results can gate the match with a filter
+//! expression and are exposed to templates as `state.<name>`.
+
+use ethabi::{
+	param_type::Reader,
+	token::{LenientTokenizer, Tokenizer},
+	ParamType,
+};
+use std::collections::HashMap;
+
+use crate::{
+	models::{EVMMatchParamEntry, StateCall},
+	services::{
+		blockchain::EvmClientTrait,
+		filter::{
+			evm_helpers::format_token_value,
+			expression::{self, EvaluationError},
+			filters::EVMConditionEvaluator,
+		},
+		notification::render_template,
+	},
+};
+
+/// Suffix of the variables holding the results of calls at the previous block
+pub const PREVIOUS_SUFFIX: &str = "_previous";
+
+/// Parses the parameter types of a function signature, e.g. `balanceOf(address)`
+///
+/// # Returns
+/// * `Result<(String, Vec<ParamType>), anyhow::Error>` - Function name and parameter types
+pub fn parse_call_signature(signature: &str) -> Result<(String, Vec<ParamType>), anyhow::Error> {
+	let invalid = || anyhow::anyhow!("Invalid function signature: {}", signature);
+	let (name, params) = signature.trim().split_once('(').ok_or_else(invalid)?;
+	let params = params.strip_suffix(')').ok_or_else(invalid)?;
+	if name.is_empty() {
+		return Err(invalid());
+	}
+	if params.trim().is_empty() {
+		return Ok((name.to_string(), Vec::new()));
+	}
+
+	match Reader::read(&format!("({})", params)) {
+		Ok(ParamType::Tuple(types)) => Ok((name.to_string(), types)),
+		_ => Err(invalid()),
+	}
+}
+
+/// Parses the type returned by a call, e.g. `uint256`
+pub fn parse_return_type(returns: &str) -> Result<ParamType, anyhow::Error> {
+	Reader::read(returns.trim())
+		.map_err(|e| anyhow::anyhow!("Invalid return type '{}': {}", returns, e))
+}
+
+/// Encodes the data of a call
+///
+/// Address and bytes arguments are accepted with or without the `0x` prefix.
+///
+/// # Arguments
+/// * `signature` - Function signature (e.g., "balanceOf(address)")
+/// * `args` - Arguments of the call
+///
+/// # Returns
+/// * `Result<String, anyhow::Error>` - Hex-encoded call data
+pub fn encode_call(signature: &str, args: &[String]) -> Result<String, anyhow::Error> {
+	let (name, types) = parse_call_signature(signature)?;
+	if types.len() != args.len() {
+		return Err(anyhow::anyhow!(
+			"{} expects {} arguments, got {}",
+			signature,
+			types.len(),
+			args.len()
+		));
+	}
+
+	let tokens = types
+		.iter()
+		.zip(args)
+		.map(|(kind, arg)| {
+			let arg = arg.trim();
+			let value = match kind {
+				ParamType::Address | ParamType::Bytes | ParamType::FixedBytes(_) => {
+					arg.strip_prefix("0x").unwrap_or(arg)
+				}
+				_ => arg,
+			};
+			LenientTokenizer::tokenize(kind, value)
+				.map_err(|e| anyhow::anyhow!("Invalid {} argument '{}': {}", kind, arg, e))
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let mut data = ethabi::short_signature(&name, &types).to_vec();
+	data.extend(ethabi::encode(&tokens));
+	Ok(format!("0x{}", hex::encode(data)))
+}
+
+/// Decodes the hex-encoded result of a call into the value format of match parameters
+pub fn decode_result(returns: &ParamType, result: &str) -> Result<String, anyhow::Error> {
+	let data = hex::decode(result.strip_prefix("0x").unwrap_or(result))
+		.map_err(|e| anyhow::anyhow!("Invalid call result '{}': {}", result, e))?;
+	let tokens = ethabi::decode(std::slice::from_ref(returns), &data)
+		.map_err(|e| anyhow::anyhow!("Failed to decode {} result: {}", returns, e))?;
+	tokens
+		.first()
+		.map(format_token_value)
+		.ok_or_else(|| anyhow::anyhow!("Empty call result"))
+}
+
+/// Executes the state calls of a monitor for a match
+///
+/// # Arguments
+/// * `client` - EVM client of the network of the match
+/// * `calls` - Calls to execute
+/// * `variables` - Variables of the match, used to render addresses and arguments
+/// * `block_number` - Block of the match
+///
+/// # Returns
+/// * `Result<Vec<EVMMatchParamEntry>, anyhow::Error>` - Results of the calls named after them,
+///   followed by their `_previous` results
+pub async fn read_state<C: EvmClientTrait + ?Sized>(
+	client: &C,
+	calls: &[StateCall],
+	variables: &HashMap<String, String>,
+	block_number: u64,
+) -> Result<Vec<EVMMatchParamEntry>, anyhow::Error> {
+	let mut values = Vec::new();
+	for call in calls {
+		let address = render_template(&call.address, variables);
+		let args = call
+			.args
+			.iter()
+			.map(|arg| render_template(arg, variables))
+			.collect::<Vec<_>>();
+		let data = encode_call(&call.signature, &args)?;
+		let returns = parse_return_type(&call.returns)?;
+
+		let mut blocks = vec![(call.name.clone(), block_number)];
+		if call.previous && block_number > 0 {
+			blocks.push((
+				format!("{}{}", call.name, PREVIOUS_SUFFIX),
+				block_number - 1,
+			));
+		}
+		for (name, block) in blocks {
+			let result = client
+				.call_contract(&address, &data, Some(block))
+				.await
+				.map_err(|e| anyhow::anyhow!("{} call to {} failed: {}", call.name, address, e))?;
+			values.push(EVMMatchParamEntry {
+				name,
+				value: decode_result(&returns, &result)?,
+				kind: returns.to_string(),
+				indexed: false,
+			});
+		}
+	}
+	Ok(values)
+}
+
+/// Evaluates a state expression against the results of the state calls
+pub fn evaluate_state_expression(
+	expression: &str,
+	values: &[EVMMatchParamEntry],
+) -> Result<bool, EvaluationError> {
+	let parsed_ast = expression::parse(expression).map_err(|e| {
+		let msg = format!("Failed to parse expression '{}': {}", expression, e);
+		EvaluationError::parse_error(msg, None, None)
+	})?;
+	expression::evaluate(&parsed_ast, &EVMConditionEvaluator::new(values))
+}
+
+/// Converts the results of the state calls into the `state` field of a match enrichment
+pub fn state_enrichment(
+	values: &[EVMMatchParamEntry],
+) -> serde_json::Map<String, serde_json::Value> {
+	let state = values
+		.iter()
+		.map(|value| (value.name.clone(), value.value.clone().into()))
+		.collect::<serde_json::Map<_, _>>();
+	serde_json::Map::from_iter([("state".to_string(), state.into())])
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_encode_call() {
+		assert_eq!(encode_call("totalAssets()", &[]).unwrap(), "0x01e1d114");
+		assert_eq!(
+			encode_call(
+				"balanceOf(address)",
+				&["0x00000000000000000000000000000000000000ff".to_string()]
+			)
+			.unwrap(),
+			format!("0x70a08231{:0>64}", "ff")
+		);
+		assert!(encode_call("balanceOf(address)", &[]).is_err());
+		assert!(encode_call("balanceOf(address)", &["0x12".to_string()]).is_err());
+		assert!(encode_call("balanceOf", &[]).is_err());
+	}
+
+	#[test]
+	fn test_parse_call_signature() {
+		let (name, types) = parse_call_signature("getRole(bytes32,(address,uint256))").unwrap();
+		assert_eq!(name, "getRole");
+		assert_eq!(
+			types,
+			vec![
+				ParamType::FixedBytes(32),
+				ParamType::Tuple(vec![ParamType::Address, ParamType::Uint(256)])
+			]
+		);
+		assert!(parse_call_signature("(uint256)").is_err());
+		assert!(parse_call_signature("paused(").is_err());
+	}
+
+	#[test]
+	fn test_decode_result() {
+		let uint = parse_return_type("uint256").unwrap();
+		assert_eq!(
+			decode_result(&uint, &format!("0x{:0>64}", "2710")).unwrap(),
+			"10000"
+		);
+		let boolean = parse_return_type("bool").unwrap();
+		assert_eq!(
+			decode_result(&boolean, &format!("0x{:0>64}", "1")).unwrap(),
+			"true"
+		);
+		assert!(decode_result(&uint, "0x").is_err());
+		assert!(parse_return_type("integer").is_err());
+	}
+
+	#[test]
+	fn test_state_expression_and_enrichment() {
+		let values = vec![
+			EVMMatchParamEntry {
+				name: "paused".to_string(),
+				value: "true".to_string(),
+				kind: "bool".to_string(),
+				indexed: false,
+			},
+			EVMMatchParamEntry {
+				name: "paused_previous".to_string(),
+				value: "false".to_string(),
+				kind: "bool".to_string(),
+				indexed: false,
+			},
+		];
+		assert!(
+			evaluate_state_expression("paused == true AND paused_previous == false", &values)
+				.unwrap()
+		);
+		assert!(!evaluate_state_expression("paused_previous == true", &values).unwrap());
+		assert!(evaluate_state_expression("paused ==", &values).is_err());
+
+		let enrichment = state_enrichment(&values);
+		assert_eq!(enrichment["state"]["paused"], serde_json::json!("true"));
+		assert_eq!(
+			enrichment["state"]["paused_previous"],
+			serde_json::json!("false")
+		);
+	}
+}
@@ -0,0 +1,436 @@
+//! Registry of token metadata used to normalize token amounts.
+//!
+//! Tokens are loaded from the configuration at startup, and the metadata of monitored EVM
+//! contracts missing from the configuration is fetched with `decimals()` and `symbol()` calls
+//! and cached in the same registry. Expressions resolve unit-aware literals such as
+//! `10000 USDC` by symbol, and match variables and templates look tokens up by address.
+//!
+//! A symbol may have different decimals on different networks, e.g. USDC has 6 decimals on
+//! Ethereum and 18 on BNB Chain. Such a symbol resolves to the decimals of the contract that
+//! emitted the compared value, and cannot be used where that contract is unknown.
+
+use alloy::primitives::U256;
+use lazy_static::lazy_static;
+use std::{
+	collections::{HashMap, HashSet},
+	sync::RwLock,
+};
+
+use crate::{
+	models::Token,
+	services::{blockchain::EvmClientTrait, filter::evm_helpers},
+};
+
+/// Selector of the ERC-20 `decimals()` function
+const DECIMALS_SELECTOR: &str = "0x313ce567";
+
+/// Selector of the ERC-20 `symbol()` function
+const SYMBOL_SELECTOR: &str = "0x95d89b41";
+
+/// Largest number of decimals whose scaling factor fits in 256 bits
+const MAX_DECIMALS: u32 = 77;
+
+lazy_static! {
+	static ref TOKENS: RwLock<TokenRegistry> = RwLock::new(TokenRegistry::default());
+}
+
+/// Symbol and decimals of a token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenMetadata {
+	/// Symbol of the token, e.g. `USDC`
+	pub symbol: String,
+	/// Number of decimals of the raw amounts
+	pub decimals: u32,
+}
+
+/// Token metadata keyed by normalized contract address and by symbol
+#[derive(Debug, Default)]
+pub struct TokenRegistry {
+	by_address: HashMap<String, TokenMetadata>,
+	/// Distinct decimals of the contracts registered with each symbol
+	decimals_by_symbol: HashMap<String, Vec<u32>>,
+	/// Symbols of the configured tokens
+	configured_symbols: HashSet<String>,
+}
+
+impl TokenRegistry {
+	/// Adds a configured token
+	///
+	/// The first configured token of a symbol replaces the decimals fetched for the symbol.
+	/// Further tokens with the same symbol, e.g. deployments on other networks, add their
+	/// decimals.
+	pub fn insert(&mut self, token: Token) {
+		let metadata = TokenMetadata {
+			symbol: token.symbol,
+			decimals: token.decimals,
+		};
+		for address in &token.addresses {
+			self.by_address
+				.insert(evm_helpers::normalize_address(address), metadata.clone());
+		}
+		let symbol = metadata.symbol.to_ascii_uppercase();
+		if self.configured_symbols.insert(symbol.clone()) {
+			self.decimals_by_symbol.remove(&symbol);
+		}
+		self.add_symbol_decimals(symbol, metadata.decimals);
+	}
+
+	/// Adds the metadata fetched for a contract
+	///
+	/// Configured tokens take precedence: the address is only added if it is unknown, and the
+	/// fetched decimals are only added to the symbol if no token with the symbol is configured.
+	pub fn insert_fetched(&mut self, address: &str, metadata: TokenMetadata) {
+		let symbol = metadata.symbol.to_ascii_uppercase();
+		if is_valid_symbol(&symbol) && !self.configured_symbols.contains(&symbol) {
+			self.add_symbol_decimals(symbol, metadata.decimals);
+		}
+		self.by_address
+			.entry(evm_helpers::normalize_address(address))
+			.or_insert(metadata);
+	}
+
+	fn add_symbol_decimals(&mut self, symbol: String, decimals: u32) {
+		let known = self.decimals_by_symbol.entry(symbol).or_default();
+		if !known.contains(&decimals) {
+			known.push(decimals);
+		}
+	}
+
+	/// Returns the metadata of the token deployed at an address, if known
+	pub fn get(&self, address: &str) -> Option<&TokenMetadata> {
+		self.by_address
+			.get(&evm_helpers::normalize_address(address))
+	}
+
+	/// Returns the decimals of the token with a symbol (case-insensitive), if known
+	///
+	/// If the token deployed at `contract` has the symbol, its decimals are used. Otherwise
+	/// every contract of the symbol must have the same decimals.
+	///
+	/// # Arguments
+	/// * `symbol` - Symbol of the token
+	/// * `contract` - Contract that emitted the value the amount applies to, if known
+	pub fn decimals(&self, symbol: &str, contract: Option<&str>) -> Option<u32> {
+		if let Some(token) = contract.and_then(|contract| self.get(contract)) {
+			if token.symbol.eq_ignore_ascii_case(symbol) {
+				return Some(token.decimals);
+			}
+		}
+		match self
+			.decimals_by_symbol
+			.get(&symbol.to_ascii_uppercase())?
+			.as_slice()
+		{
+			[decimals] => Some(*decimals),
+			_ => None,
+		}
+	}
+
+	/// Whether contracts registered with a symbol (case-insensitive) have different decimals
+	pub fn has_conflicting_decimals(&self, symbol: &str) -> bool {
+		self.decimals_by_symbol
+			.get(&symbol.to_ascii_uppercase())
+			.is_some_and(|decimals| decimals.len() > 1)
+	}
+}
+
+/// Registers configured tokens globally, replacing any previously registered token with the
+/// same address
+pub fn register_tokens(tokens: impl IntoIterator<Item = Token>) {
+	let mut registry = TOKENS
+		.write()
+		.unwrap_or_else(|poisoned| poisoned.into_inner());
+	for token in tokens {
+		registry.insert(token);
+	}
+}
+
+/// Caches the metadata fetched for a contract in the global registry
+pub fn register_token_metadata(address: &str, metadata: TokenMetadata) {
+	TOKENS
+		.write()
+		.unwrap_or_else(|poisoned| poisoned.into_inner())
+		.insert_fetched(address, metadata);
+}
+
+/// Returns the metadata of the token deployed at an address from the global registry
+pub fn token_metadata(address: &str) -> Option<TokenMetadata> {
+	TOKENS
+		.read()
+		.unwrap_or_else(|poisoned| poisoned.into_inner())
+		.get(address)
+		.cloned()
+}
+
+/// Returns the decimals of the token with a symbol from the global registry, preferring the
+/// decimals of `contract` if it is a token with the symbol
+pub fn token_decimals(symbol: &str, contract: Option<&str>) -> Option<u32> {
+	TOKENS
+		.read()
+		.unwrap_or_else(|poisoned| poisoned.into_inner())
+		.decimals(symbol, contract)
+}
+
+/// Whether the global registry knows contracts with different decimals for a symbol
+pub fn token_has_conflicting_decimals(symbol: &str) -> bool {
+	TOKENS
+		.read()
+		.unwrap_or_else(|poisoned| poisoned.into_inner())
+		.has_conflicting_decimals(symbol)
+}
+
+/// Scales an amount in whole token units up to a raw integer amount,
+/// e.g. `1.5` with 6 decimals is `1500000`
+///
+/// Returns `None` if the amount is not a non-negative decimal number or has more fractional
+/// digits than the token.
+pub fn to_raw_amount(amount: &str, decimals: u32) -> Option<String> {
+	let (integer, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+	let is_digits = |digits: &str| digits.chars().all(|c| c.is_ascii_digit());
+	let decimals = decimals as usize;
+	if integer.is_empty() || !is_digits(integer) || !is_digits(fraction) {
+		return None;
+	}
+	if fraction.len() > decimals {
+		return None;
+	}
+
+	let digits = format!("{}{:0<width$}", integer, fraction, width = decimals);
+	let digits = digits.trim_start_matches('0');
+	Some(if digits.is_empty() {
+		"0".to_string()
+	} else {
+		digits.to_string()
+	})
+}
+
+/// Fetches the symbol and decimals of an ERC-20 contract
+///
+/// Both `string` and `bytes32` return values of `symbol()` are supported.
+///
+/// # Arguments
+/// * `client` - EVM client of the network the contract is deployed on
+/// * `address` - Address of the contract
+///
+/// # Returns
+/// * `Result<TokenMetadata, anyhow::Error>` - Metadata of the token, or an error if a call
+///   failed or returned an unexpected value
+pub async fn fetch_token_metadata<C: EvmClientTrait + ?Sized>(
+	client: &C,
+	address: &str,
+) -> Result<TokenMetadata, anyhow::Error> {
+	let decimals = client
+		.call_contract(address, DECIMALS_SELECTOR, None)
+		.await?;
+	let symbol = client.call_contract(address, SYMBOL_SELECTOR, None).await?;
+
+	Ok(TokenMetadata {
+		decimals: decode_decimals(&decode_call_result(&decimals)?)?,
+		symbol: decode_symbol(&decode_call_result(&symbol)?)?,
+	})
+}
+
+/// Decodes the hexadecimal data returned by a call
+fn decode_call_result(result: &str) -> Result<Vec<u8>, anyhow::Error> {
+	let digits = result.strip_prefix("0x").unwrap_or(result);
+	hex::decode(digits).map_err(|e| anyhow::anyhow!("Invalid call result '{}': {}", result, e))
+}
+
+/// Decodes the `uint8` returned by `decimals()`
+fn decode_decimals(data: &[u8]) -> Result<u32, anyhow::Error> {
+	if data.len() < 32 {
+		return Err(anyhow::anyhow!("Invalid decimals() result"));
+	}
+	U256::from_be_slice(&data[..32])
+		.try_into()
+		.ok()
+		.filter(|decimals| *decimals <= MAX_DECIMALS)
+		.ok_or_else(|| anyhow::anyhow!("Unsupported token decimals"))
+}
+
+/// Decodes the `string` or `bytes32` returned by `symbol()`
+fn decode_symbol(data: &[u8]) -> Result<String, anyhow::Error> {
+	let invalid = || anyhow::anyhow!("Invalid symbol() result");
+	let slice = |start: usize, length: usize| {
+		let end = start.checked_add(length).ok_or_else(invalid)?;
+		data.get(start..end).ok_or_else(invalid)
+	};
+	let word = |offset: usize| -> Result<usize, anyhow::Error> {
+		U256::from_be_slice(slice(offset, 32)?)
+			.try_into()
+			.map_err(|_| invalid())
+	};
+
+	let bytes = if data.len() == 32 {
+		let length = data.iter().position(|byte| *byte == 0).unwrap_or(32);
+		&data[..length]
+	} else {
+		let offset = word(0)?;
+		let length = word(offset)?;
+		slice(offset.checked_add(32).ok_or_else(invalid)?, length)?
+	};
+
+	let symbol = String::from_utf8(bytes.to_vec()).map_err(|_| invalid())?;
+	if symbol.trim().is_empty() {
+		return Err(invalid());
+	}
+	Ok(symbol.trim().to_string())
+}
+
+/// Checks whether a symbol can be referenced from expressions
+fn is_valid_symbol(symbol: &str) -> bool {
+	let mut chars = symbol.chars();
+	chars
+		.next()
+		.is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+		&& chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const USDC: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+
+	fn create_registry() -> TokenRegistry {
+		let mut registry = TokenRegistry::default();
+		registry.insert(Token {
+			symbol: "USDC".to_string(),
+			decimals: 6,
+			addresses: vec![USDC.to_string()],
+			price: None,
+		});
+		registry
+	}
+
+	#[test]
+	fn test_lookup_by_address_and_symbol() {
+		let registry = create_registry();
+		assert_eq!(
+			registry.get(&USDC.to_lowercase()),
+			Some(&TokenMetadata {
+				symbol: "USDC".to_string(),
+				decimals: 6,
+			})
+		);
+		assert_eq!(registry.decimals("usdc", None), Some(6));
+		assert_eq!(registry.decimals("DAI", None), None);
+	}
+
+	#[test]
+	fn test_configured_tokens_take_precedence() {
+		let mut registry = create_registry();
+		registry.insert_fetched(
+			USDC,
+			TokenMetadata {
+				symbol: "USDC".to_string(),
+				decimals: 18,
+			},
+		);
+		registry.insert_fetched(
+			"0x6B175474E89094C44Da98b954EedeAC495271d0F",
+			TokenMetadata {
+				symbol: "DAI".to_string(),
+				decimals: 18,
+			},
+		);
+		registry.insert_fetched(
+			"0x0000000000000000000000000000000000000001",
+			TokenMetadata {
+				symbol: "USD₮0".to_string(),
+				decimals: 6,
+			},
+		);
+
+		assert_eq!(registry.get(USDC).unwrap().decimals, 6);
+		assert_eq!(registry.decimals("USDC", None), Some(6));
+		assert_eq!(registry.decimals("DAI", None), Some(18));
+		assert_eq!(
+			registry
+				.get("0x0000000000000000000000000000000000000001")
+				.unwrap()
+				.symbol,
+			"USD₮0"
+		);
+	}
+
+	#[test]
+	fn test_symbol_with_decimals_per_network() {
+		const BSC_USDC: &str = "0x8AC76a51cc950d9822D68b83fE1Ad97B32Cd580d";
+		let mut registry = create_registry();
+		registry.insert(Token {
+			symbol: "USDC".to_string(),
+			decimals: 18,
+			addresses: vec![BSC_USDC.to_string()],
+			price: None,
+		});
+
+		assert_eq!(registry.decimals("USDC", Some(USDC)), Some(6));
+		assert_eq!(registry.decimals("usdc", Some(BSC_USDC)), Some(18));
+		// The symbol alone is ambiguous
+		assert!(registry.has_conflicting_decimals("USDC"));
+		assert_eq!(registry.decimals("USDC", None), None);
+		assert_eq!(
+			registry.decimals("USDC", Some("0x6B175474E89094C44Da98b954EedeAC495271d0F")),
+			None
+		);
+	}
+
+	#[test]
+	fn test_to_raw_amount() {
+		assert_eq!(to_raw_amount("10000", 6).as_deref(), Some("10000000000"));
+		assert_eq!(to_raw_amount("1.5", 6).as_deref(), Some("1500000"));
+		assert_eq!(to_raw_amount("0.000001", 6).as_deref(), Some("1"));
+		assert_eq!(to_raw_amount("0", 18).as_deref(), Some("0"));
+		assert_eq!(to_raw_amount("7", 0).as_deref(), Some("7"));
+		assert_eq!(to_raw_amount("0.0000001", 6), None);
+		assert_eq!(to_raw_amount("-1", 6), None);
+		assert_eq!(to_raw_amount("1e6", 6), None);
+	}
+
+	#[test]
+	fn test_decode_decimals() {
+		let data = hex::decode(format!("{:0>64}", "12")).unwrap();
+		assert_eq!(decode_decimals(&data).unwrap(), 18);
+		assert!(decode_decimals(&[0u8; 4]).is_err());
+		assert!(decode_decimals(&[0xff; 32]).is_err());
+	}
+
+	#[test]
+	fn test_decode_symbol() {
+		let string_result = hex::decode(format!(
+			"{:0>64}{:0>64}{:0<64}",
+			"20",
+			"4",
+			hex::encode("USDC")
+		))
+		.unwrap();
+		assert_eq!(decode_symbol(&string_result).unwrap(), "USDC");
+
+		let bytes32_result = hex::decode(format!("{:0<64}", hex::encode("MKR"))).unwrap();
+		assert_eq!(decode_symbol(&bytes32_result).unwrap(), "MKR");
+
+		assert!(decode_symbol(&[0u8; 32]).is_err());
+		assert!(decode_symbol(&[0u8; 40]).is_err());
+	}
+
+	#[test]
+	fn test_global_registry() {
+		let address = "0x0000000000000000000000000000000000000def";
+		register_token_metadata(
+			address,
+			TokenMetadata {
+				symbol: "TESTGLOBAL".to_string(),
+				decimals: 8,
+			},
+		);
+
+		assert_eq!(
+			token_metadata(&address.to_uppercase()[2..])
+				.unwrap()
+				.decimals,
+			8
+		);
+		assert_eq!(token_decimals("testglobal", None), Some(8));
+	}
+}
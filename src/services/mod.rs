@@ -1,14 +1,18 @@
 //! Core services implementing the business logic.
 //!
 //! This module contains the main service implementations:
+//! - `aggregation`: Sliding window aggregation of monitor matches
 //! - `blockchain`: Blockchain client interfaces and implementations
 //! - `blockwatcher`: Block monitoring and processing
 //! - `filter`: Transaction and event filtering logic
 //! - `notification`: Alert and notification handling
+//! - `price`: USD prices of tokens from pluggable sources
 //! - `trigger`: Trigger evaluation and execution
 
+pub mod aggregation;
 pub mod blockchain;
 pub mod blockwatcher;
 pub mod filter;
 pub mod notification;
+pub mod price;
 pub mod trigger;
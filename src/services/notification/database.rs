@@ -0,0 +1,985 @@
+//! SQL database notification implementation.
+//!
+//! Upserts monitor matches into a PostgreSQL, MySQL or SQLite table. Every matched event is
+//! written as a row keyed by the network, transaction hash and log index, so that writing a
+//! match again updates its rows instead of duplicating them. Rows written concurrently are
+//! grouped into batches, each written in a single transaction.
+//!
+//! Each database driver is only compiled with its cargo feature: `postgres`, `mysql` and
+//! `sqlite`, all enabled by default.
+
+#[cfg(feature = "database")]
+use sqlx::{
+	any::{install_default_drivers, AnyArguments, AnyPoolOptions},
+	query::Query,
+	Any, AnyPool,
+};
+#[cfg(feature = "database")]
+use std::{
+	collections::{BTreeMap, HashMap},
+	sync::Arc,
+	time::Duration,
+};
+#[cfg(feature = "database")]
+use tokio::{
+	sync::{mpsc, oneshot},
+	time::Instant,
+};
+
+#[cfg(feature = "database")]
+use crate::{
+	models::{MonitorMatch, TriggerTypeConfig},
+	services::notification::{render_template, NotificationError},
+	utils::HttpRetryConfig,
+};
+
+/// Column holding the network slug of the match
+pub const NETWORK_COLUMN: &str = "network";
+
+/// Column holding the hash of the matched transaction (the poll identifier of polling monitors)
+pub const TRANSACTION_HASH_COLUMN: &str = "transaction_hash";
+
+/// Column holding the index of the matched log (the index of the event in its transaction on
+/// Stellar)
+pub const LOG_INDEX_COLUMN: &str = "log_index";
+
+/// Columns of the unique key rows are upserted on
+pub const DATABASE_KEY_COLUMNS: [&str; 3] =
+	[NETWORK_COLUMN, TRANSACTION_HASH_COLUMN, LOG_INDEX_COLUMN];
+
+/// Log index of the row written for a match without events
+pub const NO_LOG_INDEX: i64 = -1;
+
+/// Prefix of the template variables of the event a row is written for
+pub const ROW_EVENT_PREFIX: &str = "event.";
+
+/// Template variable holding the log index of a row
+pub const ROW_LOG_INDEX: &str = "event.log_index";
+
+/// Default maximum number of rows written in a single statement
+pub const DEFAULT_DATABASE_BATCH_SIZE: usize = 100;
+
+/// Default time to wait for more rows before writing a batch
+///
+/// The matches of a block are written one after another, each waiting for its rows, so only
+/// blocks processed concurrently can share a batch. Waiting would delay every write for little
+/// gain: by default, rows are written at once, together with the rows already waiting.
+pub const DEFAULT_DATABASE_BATCH_TIMEOUT_MS: u64 = 0;
+
+/// Maximum number of connections opened to the database
+#[cfg(feature = "database")]
+const MAX_DATABASE_CONNECTIONS: u32 = 4;
+
+/// Time after which acquiring a database connection fails
+#[cfg(feature = "database")]
+const DATABASE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// SQL dialect of a database
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatabaseBackend {
+	Postgres,
+	MySql,
+	Sqlite,
+}
+
+impl DatabaseBackend {
+	/// Returns the backend of a database URL, if supported
+	///
+	/// # Arguments
+	/// * `url` - Database URL
+	pub fn from_url(url: &str) -> Option<Self> {
+		match url.split_once(':')?.0 {
+			"postgres" | "postgresql" => Some(Self::Postgres),
+			"mysql" | "mariadb" => Some(Self::MySql),
+			"sqlite" => Some(Self::Sqlite),
+			_ => None,
+		}
+	}
+}
+
+/// A row written for a monitor match
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatabaseRow {
+	/// Network slug of the match
+	pub network: String,
+	/// Hash of the matched transaction, or the poll identifier of polling monitors
+	pub transaction_hash: String,
+	/// Index of the matched log or Stellar event, or `-1` for a match without events
+	pub log_index: i64,
+	/// Values of the mapped columns, in column name order
+	pub values: Vec<String>,
+}
+
+#[cfg(feature = "database")]
+impl DatabaseRow {
+	/// Returns the unique key of the row
+	fn key(&self) -> (&str, &str, i64) {
+		(&self.network, &self.transaction_hash, self.log_index)
+	}
+}
+
+/// Upsert statement of a table
+#[cfg(feature = "database")]
+#[derive(Debug, Clone)]
+struct UpsertStatement {
+	backend: DatabaseBackend,
+	table: String,
+	/// Mapped columns, written after the key columns
+	columns: Vec<String>,
+}
+
+#[cfg(feature = "database")]
+impl UpsertStatement {
+	/// Builds the statement upserting a number of rows
+	fn sql(&self, row_count: usize) -> String {
+		let columns = DATABASE_KEY_COLUMNS
+			.iter()
+			.copied()
+			.chain(self.columns.iter().map(String::as_str))
+			.collect::<Vec<_>>()
+			.join(", ");
+		let keys = DATABASE_KEY_COLUMNS.join(", ");
+		let updates = |value: &dyn Fn(&str) -> String| {
+			self.columns
+				.iter()
+				.map(|column| format!("{} = {}", column, value(column)))
+				.collect::<Vec<_>>()
+				.join(", ")
+		};
+
+		match self.backend {
+			// Values are sent as a JSON document, so that PostgreSQL converts them to the column
+			// types of the table instead of rejecting text parameters
+			DatabaseBackend::Postgres => {
+				let conflict = if self.columns.is_empty() {
+					"DO NOTHING".to_string()
+				} else {
+					format!(
+						"DO UPDATE SET {}",
+						updates(&|column| format!("EXCLUDED.{}", column))
+					)
+				};
+				format!(
+					"INSERT INTO {table} ({columns}) SELECT {columns} FROM \
+					 json_populate_recordset(NULL::{table}, $1::json) ON CONFLICT ({keys}) {conflict}",
+					table = self.table,
+				)
+			}
+			DatabaseBackend::MySql | DatabaseBackend::Sqlite => {
+				let placeholders = format!(
+					"({})",
+					vec!["?"; DATABASE_KEY_COLUMNS.len() + self.columns.len()].join(", ")
+				);
+				let values = vec![placeholders; row_count].join(", ");
+				let conflict = match (self.backend, self.columns.is_empty()) {
+					(DatabaseBackend::MySql, true) => {
+						format!("ON DUPLICATE KEY UPDATE {0} = {0}", NETWORK_COLUMN)
+					}
+					(DatabaseBackend::MySql, false) => format!(
+						"ON DUPLICATE KEY UPDATE {}",
+						updates(&|column| format!("VALUES({})", column))
+					),
+					(_, true) => format!("ON CONFLICT ({}) DO NOTHING", keys),
+					(_, false) => format!(
+						"ON CONFLICT ({}) DO UPDATE SET {}",
+						keys,
+						updates(&|column| format!("excluded.{}", column))
+					),
+				};
+				format!(
+					"INSERT INTO {} ({}) VALUES {} {}",
+					self.table, columns, values, conflict
+				)
+			}
+		}
+	}
+
+	/// Binds the values of rows to the statement built for them
+	fn bind<'q>(
+		&self,
+		mut query: Query<'q, Any, AnyArguments<'q>>,
+		rows: &[&DatabaseRow],
+	) -> Query<'q, Any, AnyArguments<'q>> {
+		match self.backend {
+			DatabaseBackend::Postgres => {
+				let records = rows
+					.iter()
+					.map(|row| {
+						let mut record = serde_json::Map::from_iter([
+							(NETWORK_COLUMN.to_string(), row.network.clone().into()),
+							(
+								TRANSACTION_HASH_COLUMN.to_string(),
+								row.transaction_hash.clone().into(),
+							),
+							(LOG_INDEX_COLUMN.to_string(), row.log_index.into()),
+						]);
+						record.extend(
+							self.columns
+								.iter()
+								.cloned()
+								.zip(row.values.iter().cloned().map(Into::into)),
+						);
+						serde_json::Value::Object(record)
+					})
+					.collect::<Vec<_>>();
+				query.bind(serde_json::Value::Array(records).to_string())
+			}
+			DatabaseBackend::MySql | DatabaseBackend::Sqlite => {
+				for row in rows {
+					query = query
+						.bind(row.network.clone())
+						.bind(row.transaction_hash.clone())
+						.bind(row.log_index);
+					for value in &row.values {
+						query = query.bind(value.clone());
+					}
+				}
+				query
+			}
+		}
+	}
+}
+
+/// Rows waiting to be written, with the sender of the write result
+#[cfg(feature = "database")]
+struct WriteRequest {
+	rows: Vec<DatabaseRow>,
+	done: oneshot::Sender<Result<(), Arc<sqlx::Error>>>,
+}
+
+/// Task writing the rows of concurrent requests in batches
+#[cfg(feature = "database")]
+struct BatchWriter {
+	pool: AnyPool,
+	statement: UpsertStatement,
+	batch_size: usize,
+	batch_timeout: Duration,
+}
+
+#[cfg(feature = "database")]
+impl BatchWriter {
+	/// Writes requests until every sender is dropped
+	async fn run(self, mut requests: mpsc::UnboundedReceiver<WriteRequest>) {
+		while let Some(request) = requests.recv().await {
+			let deadline = Instant::now() + self.batch_timeout;
+			let mut row_count = request.rows.len();
+			let mut batch = vec![request];
+
+			// Requests already waiting are always added, the timeout only delays the batch
+			// while it is not full
+			while row_count < self.batch_size {
+				match tokio::time::timeout_at(deadline, requests.recv()).await {
+					Ok(Some(request)) => {
+						row_count += request.rows.len();
+						batch.push(request);
+					}
+					_ => break,
+				}
+			}
+
+			let rows = batch
+				.iter()
+				.flat_map(|request| request.rows.iter())
+				.collect::<Vec<_>>();
+			let result = self.write(&rows).await.map_err(Arc::new);
+			for request in batch {
+				let _ = request.done.send(result.clone());
+			}
+		}
+	}
+
+	/// Upserts rows in a single transaction
+	async fn write(&self, rows: &[&DatabaseRow]) -> Result<(), sqlx::Error> {
+		// A statement cannot upsert the same key twice, the last row of a key is kept
+		let mut unique_rows: Vec<&DatabaseRow> = Vec::with_capacity(rows.len());
+		for row in rows {
+			match unique_rows
+				.iter_mut()
+				.find(|unique_row| unique_row.key() == row.key())
+			{
+				Some(unique_row) => *unique_row = row,
+				None => unique_rows.push(row),
+			}
+		}
+
+		let mut transaction = self.pool.begin().await?;
+		for chunk in unique_rows.chunks(self.batch_size) {
+			let sql = self.statement.sql(chunk.len());
+			self.statement
+				.bind(sqlx::query(&sql), chunk)
+				.execute(&mut *transaction)
+				.await?;
+		}
+		transaction.commit().await
+	}
+}
+
+/// Database client upserting rows into a table
+///
+/// Connections are opened lazily, so that an unavailable database only fails the writing of
+/// a match.
+#[cfg(feature = "database")]
+pub struct DatabaseSink {
+	/// Table rows are upserted into
+	table: String,
+	/// Requests sent to the batch writer task
+	requests: mpsc::UnboundedSender<WriteRequest>,
+}
+
+#[cfg(feature = "database")]
+impl DatabaseSink {
+	/// Creates a new database sink, spawning its batch writer task
+	///
+	/// # Arguments
+	/// * `url` - Database URL
+	/// * `table` - Table rows are upserted into
+	/// * `columns` - Mapped columns, written after the key columns
+	/// * `batch_size` - Maximum number of rows written in a single statement
+	/// * `batch_timeout` - Time to wait for more rows before writing a batch
+	///
+	/// # Returns
+	/// * `Result<Self, NotificationError>` - Sink, or error if the URL is invalid
+	pub fn new(
+		url: &str,
+		table: String,
+		columns: Vec<String>,
+		batch_size: usize,
+		batch_timeout: Duration,
+	) -> Result<Self, NotificationError> {
+		let backend = DatabaseBackend::from_url(url).ok_or_else(|| {
+			NotificationError::config_error("Unsupported database URL scheme", None, None)
+		})?;
+
+		install_default_drivers();
+		let pool = AnyPoolOptions::new()
+			.max_connections(MAX_DATABASE_CONNECTIONS)
+			.acquire_timeout(DATABASE_TIMEOUT)
+			.connect_lazy(url)
+			.map_err(|e| {
+				NotificationError::config_error(
+					format!("Invalid database URL: {}", e),
+					Some(e.into()),
+					None,
+				)
+			})?;
+
+		let writer = BatchWriter {
+			pool,
+			statement: UpsertStatement {
+				backend,
+				table: table.clone(),
+				columns,
+			},
+			batch_size: batch_size.max(1),
+			batch_timeout,
+		};
+		let (requests, receiver) = mpsc::unbounded_channel();
+		tokio::spawn(writer.run(receiver));
+
+		Ok(Self { table, requests })
+	}
+
+	/// Upserts rows, returning once the batch containing them is committed
+	///
+	/// # Arguments
+	/// * `rows` - Rows to upsert
+	///
+	/// # Returns
+	/// * `Result<(), NotificationError>` - Success or error
+	pub async fn write(&self, rows: Vec<DatabaseRow>) -> Result<(), NotificationError> {
+		let writer_stopped = || {
+			NotificationError::internal_error(
+				format!("Database writer of table {} stopped", self.table),
+				None,
+				None,
+			)
+		};
+
+		let (done, result) = oneshot::channel();
+		self.requests
+			.send(WriteRequest { rows, done })
+			.map_err(|_| writer_stopped())?;
+
+		result
+			.await
+			.map_err(|_| writer_stopped())?
+			.map_err(|e| match e.as_ref() {
+				sqlx::Error::Io(_)
+				| sqlx::Error::Tls(_)
+				| sqlx::Error::PoolTimedOut
+				| sqlx::Error::PoolClosed => NotificationError::network_error(
+					format!("Failed to connect to database: {}", e),
+					Some(e.into()),
+					None,
+				),
+				_ => NotificationError::notify_failed(
+					format!("Failed to write rows to table {}: {}", self.table, e),
+					Some(e.into()),
+					None,
+				),
+			})
+	}
+}
+
+/// Creates the database client of a trigger configuration
+///
+/// # Arguments
+/// * `config` - Trigger configuration containing database parameters
+///
+/// # Returns
+/// * `Result<DatabaseSink, NotificationError>` - Client if config is a database type
+#[cfg(feature = "database")]
+pub fn create_database_sink(config: &TriggerTypeConfig) -> Result<DatabaseSink, NotificationError> {
+	if let Some(feature) = config.disabled_feature() {
+		let msg = format!(
+			"Database trigger requires the monitor to be built with the `{}` feature",
+			feature
+		);
+		return Err(NotificationError::config_error(msg, None, None));
+	}
+
+	match config {
+		TriggerTypeConfig::Database {
+			database_url,
+			table,
+			columns,
+			batch_size,
+			batch_timeout_ms,
+			..
+		} => DatabaseSink::new(
+			database_url.as_ref(),
+			table.clone(),
+			columns.keys().cloned().collect(),
+			batch_size.unwrap_or(DEFAULT_DATABASE_BATCH_SIZE),
+			Duration::from_millis(batch_timeout_ms.unwrap_or(DEFAULT_DATABASE_BATCH_TIMEOUT_MS)),
+		),
+		_ => {
+			let msg = format!("Invalid database configuration: {:?}", config);
+			Err(NotificationError::config_error(msg, None, None))
+		}
+	}
+}
+
+/// Implementation of database notifications
+#[cfg(feature = "database")]
+pub struct DatabaseNotifier {
+	/// Client of the database
+	sink: Arc<DatabaseSink>,
+	/// Templates of the column values, keyed by column name
+	columns: BTreeMap<String, String>,
+	/// Retry policy for writing rows
+	retry_policy: HttpRetryConfig,
+}
+
+#[cfg(feature = "database")]
+impl DatabaseNotifier {
+	/// Creates a new database notifier instance
+	///
+	/// # Arguments
+	/// * `sink` - Client of the database
+	/// * `columns` - Templates of the column values, keyed by column name
+	/// * `retry_policy` - Retry policy for writing rows
+	pub fn new(
+		sink: Arc<DatabaseSink>,
+		columns: BTreeMap<String, String>,
+		retry_policy: HttpRetryConfig,
+	) -> Self {
+		Self {
+			sink,
+			columns,
+			retry_policy,
+		}
+	}
+
+	/// Creates a database notifier from a trigger configuration
+	///
+	/// # Arguments
+	/// * `config` - Trigger configuration containing database parameters
+	/// * `sink` - Client of the database
+	///
+	/// # Returns
+	/// * `Result<Self, NotificationError>` - Notifier instance if config is a database type
+	pub fn from_config(
+		config: &TriggerTypeConfig,
+		sink: Arc<DatabaseSink>,
+	) -> Result<Self, NotificationError> {
+		match config {
+			TriggerTypeConfig::Database {
+				columns,
+				retry_policy,
+				..
+			} => Ok(Self::new(sink, columns.clone(), retry_policy.clone())),
+			_ => {
+				let msg = format!("Invalid database configuration: {:?}", config);
+				Err(NotificationError::config_error(msg, None, None))
+			}
+		}
+	}
+
+	/// Formats the rows of a monitor match, one per matched event
+	///
+	/// The signature, arguments and log index of the event a row is written for are available
+	/// as `event.*`, next to the match variables. A match without events is written as a single
+	/// row.
+	///
+	/// # Arguments
+	/// * `variables` - Template variables of the match
+	/// * `monitor_match` - Monitor match to write
+	///
+	/// # Returns
+	/// * `Vec<DatabaseRow>` - Rows of the match
+	pub fn format_rows(
+		&self,
+		variables: &HashMap<String, String>,
+		monitor_match: &MonitorMatch,
+	) -> Vec<DatabaseRow> {
+		// Log index, signature and arguments of every matched event
+		let (network, transaction_hash, events) = match monitor_match {
+			MonitorMatch::EVM(evm_match) => (
+				&evm_match.network_slug,
+				evm_match.transaction.hash().to_string(),
+				evm_match
+					.matched_on
+					.events
+					.iter()
+					.enumerate()
+					.map(|(index, condition)| {
+						let params = evm_match
+							.matched_on_args
+							.as_ref()
+							.and_then(|args| args.events.as_ref()?.get(index));
+						let log_index = params
+							.and_then(|params| params.log_index)
+							.and_then(|log_index| i64::try_from(log_index).ok())
+							.unwrap_or(index as i64);
+						let args = params
+							.and_then(|params| params.args.as_ref())
+							.into_iter()
+							.flatten()
+							.map(|arg| (arg.name.clone(), arg.value.clone()))
+							.collect::<Vec<_>>();
+						(log_index, condition.signature.clone(), args)
+					})
+					.collect::<Vec<_>>(),
+			),
+			MonitorMatch::Stellar(stellar_match) => (
+				&stellar_match.network_slug,
+				stellar_match.transaction.hash().clone(),
+				stellar_match
+					.matched_on
+					.events
+					.iter()
+					.enumerate()
+					.map(|(index, condition)| {
+						let params = stellar_match
+							.matched_on_args
+							.as_ref()
+							.and_then(|args| args.events.as_ref()?.get(index));
+						let event_index = params
+							.and_then(|params| params.event_index)
+							.and_then(|event_index| i64::try_from(event_index).ok())
+							.unwrap_or(index as i64);
+						let args = params
+							.and_then(|params| params.args.as_ref())
+							.into_iter()
+							.flatten()
+							.map(|arg| (arg.name.clone(), arg.value.clone()))
+							.collect::<Vec<_>>();
+						(event_index, condition.signature.clone(), args)
+					})
+					.collect(),
+			),
+			MonitorMatch::Poll(poll_match) => {
+				(&poll_match.network_slug, poll_match.poll_id(), Vec::new())
+			}
+		};
+
+		let rows = if events.is_empty() {
+			vec![(NO_LOG_INDEX, None)]
+		} else {
+			events
+				.into_iter()
+				.map(|(log_index, signature, args)| (log_index, Some((signature, args))))
+				.collect()
+		};
+
+		rows.into_iter()
+			.map(|(log_index, event)| {
+				let mut row_variables = variables.clone();
+				if let Some((signature, args)) = event {
+					row_variables.insert(format!("{}signature", ROW_EVENT_PREFIX), signature);
+					row_variables.extend(args.into_iter().map(|(name, value)| {
+						(format!("{}args.{}", ROW_EVENT_PREFIX, name), value)
+					}));
+				}
+				row_variables.insert(ROW_LOG_INDEX.to_string(), log_index.to_string());
+
+				DatabaseRow {
+					network: network.clone(),
+					transaction_hash: transaction_hash.clone(),
+					log_index,
+					values: self
+						.columns
+						.values()
+						.map(|template| render_template(template, &row_variables))
+						.collect(),
+				}
+			})
+			.collect()
+	}
+
+	/// Upserts rows, retrying with exponential backoff until they are committed
+	///
+	/// # Arguments
+	/// * `rows` - Rows to upsert
+	///
+	/// # Returns
+	/// * `Result<(), NotificationError>` - Success, or the error of the last attempt
+	pub async fn write(&self, rows: &[DatabaseRow]) -> Result<(), NotificationError> {
+		let mut attempt = 0;
+		loop {
+			match self.sink.write(rows.to_vec()).await {
+				Ok(()) => return Ok(()),
+				Err(e) if attempt >= self.retry_policy.max_retries => return Err(e),
+				Err(e) => {
+					tracing::warn!(
+						"Failed to write {} rows (attempt {}): {}",
+						rows.len(),
+						attempt + 1,
+						e
+					);
+					tokio::time::sleep(self.backoff(attempt)).await;
+					attempt += 1;
+				}
+			}
+		}
+	}
+
+	/// Delay before the retry following a failed attempt
+	fn backoff(&self, attempt: u32) -> Duration {
+		self.retry_policy
+			.initial_backoff
+			.saturating_mul(self.retry_policy.base_for_backoff.saturating_pow(attempt))
+			.min(self.retry_policy.max_backoff)
+	}
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+	use super::*;
+	use crate::{
+		models::{
+			EVMMatchArguments, EVMMatchParamEntry, EVMMatchParamsMap, EVMMonitorMatch,
+			EVMTransactionReceipt, EventCondition, MatchConditions, StellarBlock,
+			StellarMatchArguments, StellarMatchParamsMap, StellarMonitorMatch, StellarTransaction,
+			StellarTransactionInfo,
+		},
+		services::filter::build_match_variables,
+		utils::tests::{
+			builders::{evm::monitor::MonitorBuilder, trigger::TriggerBuilder},
+			evm::transaction::TransactionBuilder,
+		},
+	};
+
+	fn create_test_match(log_indexes: &[Option<u64>]) -> MonitorMatch {
+		let signature = "Transfer(address,address,uint256)";
+		MonitorMatch::EVM(Box::new(EVMMonitorMatch {
+			monitor: MonitorBuilder::new().name("Large Transfer").build(),
+			transaction: TransactionBuilder::new().build(),
+			receipt: Some(EVMTransactionReceipt::default()),
+			logs: Some(vec![]),
+			network_slug: "ethereum_mainnet".to_string(),
+			matched_on: MatchConditions {
+				events: log_indexes
+					.iter()
+					.map(|_| EventCondition {
+						signature: signature.to_string(),
+						expression: None,
+					})
+					.collect(),
+				..Default::default()
+			},
+			matched_on_args: Some(EVMMatchArguments {
+				functions: None,
+				events: Some(
+					log_indexes
+						.iter()
+						.map(|log_index| EVMMatchParamsMap {
+							signature: signature.to_string(),
+							args: Some(vec![EVMMatchParamEntry {
+								name: "value".to_string(),
+								value: "1000".to_string(),
+								kind: "uint256".to_string(),
+								indexed: false,
+							}]),
+							hex_signature: None,
+							log_index: *log_index,
+						})
+						.collect(),
+				),
+			}),
+			enrichment: None,
+		}))
+	}
+
+	fn create_test_statement(backend: DatabaseBackend, columns: &[&str]) -> UpsertStatement {
+		UpsertStatement {
+			backend,
+			table: "transfers".to_string(),
+			columns: columns.iter().map(|column| column.to_string()).collect(),
+		}
+	}
+
+	fn create_test_config() -> TriggerTypeConfig {
+		TriggerBuilder::new()
+			.database("sqlite::memory:", "transfers")
+			.build()
+			.config
+	}
+
+	#[test]
+	fn test_backend_from_url() {
+		assert_eq!(
+			DatabaseBackend::from_url("postgres://localhost/monitor"),
+			Some(DatabaseBackend::Postgres)
+		);
+		assert_eq!(
+			DatabaseBackend::from_url("mysql://localhost/monitor"),
+			Some(DatabaseBackend::MySql)
+		);
+		assert_eq!(
+			DatabaseBackend::from_url("sqlite::memory:"),
+			Some(DatabaseBackend::Sqlite)
+		);
+		assert_eq!(DatabaseBackend::from_url("localhost:5432"), None);
+		assert_eq!(DatabaseBackend::from_url("monitor.db"), None);
+	}
+
+	#[test]
+	fn test_postgres_upsert_statement() {
+		let statement = create_test_statement(DatabaseBackend::Postgres, &["value"]);
+		assert_eq!(
+			statement.sql(2),
+			"INSERT INTO transfers (network, transaction_hash, log_index, value) SELECT network, \
+			 transaction_hash, log_index, value FROM json_populate_recordset(NULL::transfers, \
+			 $1::json) ON CONFLICT (network, transaction_hash, log_index) DO UPDATE SET value = \
+			 EXCLUDED.value"
+		);
+
+		let statement = create_test_statement(DatabaseBackend::Postgres, &[]);
+		assert!(statement.sql(1).ends_with("DO NOTHING"));
+	}
+
+	#[test]
+	fn test_mysql_upsert_statement() {
+		let statement = create_test_statement(DatabaseBackend::MySql, &["from_address", "value"]);
+		assert_eq!(
+			statement.sql(2),
+			"INSERT INTO transfers (network, transaction_hash, log_index, from_address, value) \
+			 VALUES (?, ?, ?, ?, ?), (?, ?, ?, ?, ?) ON DUPLICATE KEY UPDATE from_address = \
+			 VALUES(from_address), value = VALUES(value)"
+		);
+
+		let statement = create_test_statement(DatabaseBackend::MySql, &[]);
+		assert!(statement
+			.sql(1)
+			.ends_with("ON DUPLICATE KEY UPDATE network = network"));
+	}
+
+	#[test]
+	fn test_sqlite_upsert_statement() {
+		let statement = create_test_statement(DatabaseBackend::Sqlite, &["value"]);
+		assert_eq!(
+			statement.sql(1),
+			"INSERT INTO transfers (network, transaction_hash, log_index, value) VALUES (?, ?, ?, \
+			 ?) ON CONFLICT (network, transaction_hash, log_index) DO UPDATE SET value = \
+			 excluded.value"
+		);
+
+		let statement = create_test_statement(DatabaseBackend::Sqlite, &[]);
+		assert!(statement.sql(1).ends_with("DO NOTHING"));
+	}
+
+	#[tokio::test]
+	async fn test_format_rows_per_event() {
+		let sink = Arc::new(create_database_sink(&create_test_config()).unwrap());
+		let notifier = DatabaseNotifier::new(
+			sink,
+			BTreeMap::from([
+				("monitor".to_string(), "${monitor.name}".to_string()),
+				("value".to_string(), "${event.args.value}".to_string()),
+				("position".to_string(), "${event.log_index}".to_string()),
+			]),
+			HttpRetryConfig::default(),
+		);
+		let monitor_match = create_test_match(&[Some(7), None]);
+		let variables = build_match_variables(&monitor_match);
+
+		let rows = notifier.format_rows(&variables, &monitor_match);
+		assert_eq!(rows.len(), 2);
+		assert_eq!(rows[0].network, "ethereum_mainnet");
+		assert_eq!(rows[0].transaction_hash, variables["transaction.hash"]);
+		assert_eq!(rows[0].log_index, 7);
+		// Values are ordered by column name
+		assert_eq!(rows[0].values, vec!["Large Transfer", "7", "1000"]);
+		// The position of the event is used when its log index is unknown
+		assert_eq!(rows[1].log_index, 1);
+	}
+
+	#[tokio::test]
+	async fn test_format_rows_per_stellar_event() {
+		let sink = Arc::new(create_database_sink(&create_test_config()).unwrap());
+		let notifier = DatabaseNotifier::new(
+			sink,
+			BTreeMap::from([("position".to_string(), "${event.log_index}".to_string())]),
+			HttpRetryConfig::default(),
+		);
+		let signature = "transfer(Address,Address,I128)".to_string();
+		let monitor_match = MonitorMatch::Stellar(Box::new(StellarMonitorMatch {
+			monitor: MonitorBuilder::new().name("Large Transfer").build(),
+			transaction: StellarTransaction::from(StellarTransactionInfo::default()),
+			ledger: StellarBlock::default(),
+			network_slug: "stellar_mainnet".to_string(),
+			matched_on: MatchConditions {
+				events: vec![
+					EventCondition {
+						signature: signature.clone(),
+						expression: None,
+					};
+					2
+				],
+				..Default::default()
+			},
+			matched_on_args: Some(StellarMatchArguments {
+				functions: None,
+				events: Some(
+					[3, 1 << 32]
+						.map(|event_index| StellarMatchParamsMap {
+							signature: signature.clone(),
+							args: None,
+							event_index: Some(event_index),
+						})
+						.to_vec(),
+				),
+			}),
+			enrichment: None,
+		}));
+
+		let rows = notifier.format_rows(&build_match_variables(&monitor_match), &monitor_match);
+		// Events of a transaction are keyed by their index in the transaction
+		assert_eq!(
+			rows.iter().map(|row| row.log_index).collect::<Vec<_>>(),
+			vec![3, 1 << 32]
+		);
+		assert_eq!(rows[1].values, vec![(1i64 << 32).to_string()]);
+	}
+
+	#[tokio::test]
+	async fn test_format_rows_without_events() {
+		let sink = Arc::new(create_database_sink(&create_test_config()).unwrap());
+		let notifier = DatabaseNotifier::new(
+			sink,
+			BTreeMap::from([("position".to_string(), "${event.log_index}".to_string())]),
+			HttpRetryConfig::default(),
+		);
+		let monitor_match = create_test_match(&[]);
+
+		let rows = notifier.format_rows(&build_match_variables(&monitor_match), &monitor_match);
+		assert_eq!(rows.len(), 1);
+		assert_eq!(rows[0].log_index, NO_LOG_INDEX);
+		assert_eq!(rows[0].values, vec!["-1"]);
+	}
+
+	#[tokio::test]
+	async fn test_write_batches_concurrent_rows() {
+		let dir = tempfile::tempdir().unwrap();
+		let url = format!(
+			"sqlite://{}?mode=rwc",
+			dir.path().join("matches.db").display()
+		);
+		let pool = AnyPoolOptions::new().connect_lazy(&url).unwrap();
+		sqlx::query(
+			"CREATE TABLE transfers (network TEXT, transaction_hash TEXT, log_index INTEGER, \
+			 value TEXT, UNIQUE (network, transaction_hash, log_index))",
+		)
+		.execute(&pool)
+		.await
+		.unwrap();
+
+		let sink = DatabaseSink::new(
+			&url,
+			"transfers".to_string(),
+			vec!["value".to_string()],
+			2,
+			Duration::from_millis(50),
+		)
+		.unwrap();
+		let row = |log_index: i64, value: &str| DatabaseRow {
+			network: "ethereum_mainnet".to_string(),
+			transaction_hash: "0x1".to_string(),
+			log_index,
+			values: vec![value.to_string()],
+		};
+
+		// The second row of key 0 replaces the first, within and across batches
+		let (first, second, third) = tokio::join!(
+			sink.write(vec![row(0, "1"), row(1, "2")]),
+			sink.write(vec![row(0, "3"), row(2, "4")]),
+			sink.write(vec![row(2, "5")]),
+		);
+		assert!(first.is_ok() && second.is_ok() && third.is_ok());
+
+		let rows = sqlx::query_as::<_, (i64, String)>(
+			"SELECT log_index, value FROM transfers ORDER BY log_index",
+		)
+		.fetch_all(&pool)
+		.await
+		.unwrap();
+		assert_eq!(
+			rows,
+			vec![
+				(0, "3".to_string()),
+				(1, "2".to_string()),
+				(2, "5".to_string())
+			]
+		);
+	}
+
+	#[tokio::test]
+	async fn test_write_to_missing_table() {
+		let sink = DatabaseSink::new(
+			"sqlite::memory:",
+			"transfers".to_string(),
+			vec![],
+			DEFAULT_DATABASE_BATCH_SIZE,
+			Duration::ZERO,
+		)
+		.unwrap();
+		let result = sink
+			.write(vec![DatabaseRow {
+				network: "ethereum_mainnet".to_string(),
+				transaction_hash: "0x1".to_string(),
+				log_index: 0,
+				values: vec![],
+			}])
+			.await;
+		assert!(matches!(result, Err(NotificationError::NotifyFailed(_))));
+	}
+
+	#[test]
+	fn test_create_database_sink_invalid_type() {
+		let config = TriggerTypeConfig::Script {
+			language: crate::models::ScriptLanguage::Bash,
+			script_path: "script.sh".to_string(),
+			arguments: None,
+			timeout_ms: 1000,
+			mode: crate::models::ScriptMode::Process,
+			sandbox: None,
+			interpreter: None,
+		};
+		assert!(matches!(
+			create_database_sink(&config),
+			Err(NotificationError::ConfigError(_))
+		));
+	}
+}
@@ -0,0 +1,204 @@
+//! Digest notification buffering and rendering.
+//!
+//! Triggers configured with a `digest` do not notify on every match. Their matches are buffered
+//! here and rendered into a single summary message when the digest is flushed on its schedule.
+
+use std::{collections::HashMap, sync::Mutex};
+
+use crate::services::notification::{
+	render_template, template::is_match_variable, Template, TemplateError,
+};
+
+/// Collection of the buffered matches, looped over with `{% for match in matches %}`
+pub const DIGEST_MATCHES: &str = "matches";
+
+/// Template variable holding the number of matches in a digest
+pub const DIGEST_COUNT: &str = "digest.count";
+
+/// Variable of a buffered match holding the trigger message rendered for the match
+pub const MATCH_MESSAGE: &str = "message";
+
+/// Variable holding the rendered digest body when the digest is sent
+pub const DIGEST_BODY: &str = "digest.body";
+
+/// A match buffered for a digest
+#[derive(Debug, Clone, PartialEq)]
+pub struct DigestEntry {
+	/// Template variables of the match
+	pub variables: HashMap<String, String>,
+	/// Trigger message body rendered for the match
+	pub message: String,
+}
+
+/// Buffers the matches of digest triggers until they are flushed
+#[derive(Debug, Default)]
+pub struct DigestBuffer {
+	entries: Mutex<HashMap<String, Vec<DigestEntry>>>,
+}
+
+impl DigestBuffer {
+	/// Creates an empty digest buffer
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Buffers a match for a trigger
+	///
+	/// # Arguments
+	/// * `trigger_name` - Name of the digest trigger
+	/// * `entry` - Match to buffer
+	pub fn push(&self, trigger_name: &str, entry: DigestEntry) {
+		self.entries
+			.lock()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+			.entry(trigger_name.to_string())
+			.or_default()
+			.push(entry);
+	}
+
+	/// Removes and returns all matches buffered for a trigger
+	///
+	/// # Arguments
+	/// * `trigger_name` - Name of the digest trigger
+	pub fn take(&self, trigger_name: &str) -> Vec<DigestEntry> {
+		self.entries
+			.lock()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+			.remove(trigger_name)
+			.unwrap_or_default()
+	}
+}
+
+/// Renders a digest message template for a list of buffered matches
+///
+/// The buffered matches form the `matches` collection, each item exposing the match variables
+/// and the rendered trigger message as `message`, e.g.
+/// `{% for match in matches %}${loop.index}. ${match.message}{% endfor %}`. `${digest.count}` is
+/// available in the whole template.
+///
+/// # Arguments
+/// * `template` - Digest message template
+/// * `entries` - Buffered matches
+pub fn render_digest(template: &str, entries: &[DigestEntry]) -> String {
+	let mut variables = HashMap::from([(DIGEST_COUNT.to_string(), entries.len().to_string())]);
+	for (index, entry) in entries.iter().enumerate() {
+		let prefix = format!("{}.{}", DIGEST_MATCHES, index);
+		variables.insert(
+			format!("{}.{}", prefix, MATCH_MESSAGE),
+			entry.message.clone(),
+		);
+		variables.extend(
+			entry
+				.variables
+				.iter()
+				.map(|(name, value)| (format!("{}.{}", prefix, name), value.clone())),
+		);
+	}
+	render_template(template, &variables)
+}
+
+/// Validates a digest message template at configuration load
+///
+/// Items of the `matches` collection may use the match variables and `message`.
+///
+/// # Arguments
+/// * `template` - Digest message template
+pub fn validate_digest_template(template: &str) -> Result<(), TemplateError> {
+	let item_prefix = format!("{}.*.", DIGEST_MATCHES);
+	template
+		.parse::<Template>()?
+		.validate_variables_with(|path| {
+			path == DIGEST_COUNT
+				|| path.strip_prefix(&item_prefix).is_some_and(|variable| {
+					variable == MATCH_MESSAGE || is_match_variable(variable)
+				})
+		})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn create_test_entry(hash: &str, message: &str) -> DigestEntry {
+		DigestEntry {
+			variables: HashMap::from([("transaction.hash".to_string(), hash.to_string())]),
+			message: message.to_string(),
+		}
+	}
+
+	#[test]
+	fn test_digest_buffer_push_and_take() {
+		let buffer = DigestBuffer::new();
+		buffer.push("daily", create_test_entry("0x1", "first"));
+		buffer.push("daily", create_test_entry("0x2", "second"));
+		buffer.push("hourly", create_test_entry("0x3", "third"));
+
+		let entries = buffer.take("daily");
+		assert_eq!(entries.len(), 2);
+		assert_eq!(entries[0].message, "first");
+		assert_eq!(entries[1].message, "second");
+
+		assert!(buffer.take("daily").is_empty());
+		assert_eq!(buffer.take("hourly").len(), 1);
+	}
+
+	#[test]
+	fn test_render_digest_with_loop() {
+		let entries = vec![
+			create_test_entry("0x1", "Transfer of 10"),
+			create_test_entry("0x2", "Transfer of 20"),
+		];
+		let template = "${digest.count} matches:\n{% for match in matches %}${loop.index}. ${match.message} (${match.transaction.hash})\n{% endfor %}End";
+
+		assert_eq!(
+			render_digest(template, &entries),
+			"2 matches:\n1. Transfer of 10 (0x1)\n2. Transfer of 20 (0x2)\nEnd"
+		);
+	}
+
+	#[test]
+	fn test_render_digest_without_loop() {
+		let entries = vec![create_test_entry("0x1", "Transfer of 10")];
+		assert_eq!(
+			render_digest("${digest.count} new matches", &entries),
+			"1 new matches"
+		);
+	}
+
+	#[test]
+	fn test_render_digest_keeps_match_values_as_text() {
+		let entries = vec![create_test_entry(
+			"${digest.count}",
+			"{% if x %}y{% endif %}",
+		)];
+		assert_eq!(
+			render_digest(
+				"{% for match in matches %}${match.message} ${match.transaction.hash}{% endfor %}",
+				&entries
+			),
+			"{% if x %}y{% endif %} ${digest.count}"
+		);
+	}
+
+	#[test]
+	fn test_validate_digest_template() {
+		let valid_templates = [
+			"${digest.count} matches",
+			"{% for match in matches %}${loop.index}. ${match.message}{% endfor %}",
+			"{% for match in matches %}${match.events.0.args.value}{% endfor %}",
+		];
+		for template in valid_templates {
+			assert!(validate_digest_template(template).is_ok(), "{}", template);
+		}
+
+		let invalid_templates = [
+			"{% for match in matches %}${match.message}",
+			"{% for match in matches %}${match.unknown}{% endfor %}",
+			"${match.message}",
+			"${digest.unknown}",
+		];
+		for template in invalid_templates {
+			assert!(validate_digest_template(template).is_err(), "{}", template);
+		}
+	}
+}
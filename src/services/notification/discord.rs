@@ -2,6 +2,10 @@
 //!
 //! Provides functionality to send formatted messages to Discord channels
 //! via incoming webhooks, supporting message templates with variable substitution.
+//!
+//! Matches are sent as an embed whose title links to the block explorer, with the message as
+//! description and fields for the monitor, network, matched functions and events, and their
+//! arguments. Embeds of monitors with a severity are coloured accordingly.
 
 use async_trait::async_trait;
 use reqwest_middleware::ClientWithMiddleware;
@@ -10,14 +14,36 @@ use serde_json;
 use std::{collections::HashMap, sync::Arc};
 
 use crate::{
-	models::TriggerTypeConfig,
-	services::notification::{NotificationError, Notifier, WebhookConfig, WebhookNotifier},
+	models::{MonitorMatch, TriggerTypeConfig},
+	services::notification::{
+		render_json_template, truncate_text, MatchSummary, NotificationError, Notifier,
+		WebhookConfig, WebhookNotifier,
+	},
 };
 
+/// Maximum length of an embed title
+const DISCORD_MAX_TITLE_LENGTH: usize = 256;
+
+/// Maximum length of an embed description
+const DISCORD_MAX_DESCRIPTION_LENGTH: usize = 4096;
+
+/// Maximum length of an embed field name
+const DISCORD_MAX_FIELD_NAME_LENGTH: usize = 256;
+
+/// Maximum length of an embed field value
+const DISCORD_MAX_FIELD_VALUE_LENGTH: usize = 1024;
+
+/// Maximum number of fields of an embed
+const DISCORD_MAX_FIELDS: usize = 25;
+
 /// Implementation of Discord notifications via webhooks
 #[derive(Debug)]
 pub struct DiscordNotifier {
 	inner: WebhookNotifier,
+	/// Block explorer link template
+	explorer_url: Option<String>,
+	/// Embeds replacing the default embed
+	embeds_template: Option<serde_json::Value>,
 }
 
 /// Represents a field in a Discord embed message
@@ -28,33 +54,55 @@ struct DiscordField {
 	/// The value of the field (max 1024 characters)
 	value: String,
 	/// Indicates whether the field should be displayed inline with other fields (optional)
+	#[serde(skip_serializing_if = "Option::is_none")]
 	inline: Option<bool>,
 }
 
+impl DiscordField {
+	/// Creates a field, truncating its name and value to the embed limits
+	fn new(name: &str, value: &str, inline: bool) -> Self {
+		Self {
+			name: truncate_text(name, DISCORD_MAX_FIELD_NAME_LENGTH),
+			value: truncate_text(value, DISCORD_MAX_FIELD_VALUE_LENGTH),
+			inline: Some(inline),
+		}
+	}
+}
+
 /// Represents an embed message in Discord
 #[derive(Serialize)]
 struct DiscordEmbed {
 	/// The title of the embed (max 256 characters)
 	title: String,
 	/// The description of the embed (max 4096 characters)
+	#[serde(skip_serializing_if = "Option::is_none")]
 	description: Option<String>,
 	/// A URL that the title links to (optional)
+	#[serde(skip_serializing_if = "Option::is_none")]
 	url: Option<String>,
 	/// The color of the embed represented as a hexadecimal integer (optional)
+	#[serde(skip_serializing_if = "Option::is_none")]
 	color: Option<u32>,
 	/// A list of fields included in the embed (max 25 fields, optional)
+	#[serde(skip_serializing_if = "Option::is_none")]
 	fields: Option<Vec<DiscordField>>,
 	/// Indicates whether text-to-speech is enabled for the embed (optional)
+	#[serde(skip_serializing_if = "Option::is_none")]
 	tts: Option<bool>,
 	/// A thumbnail image for the embed (optional)
+	#[serde(skip_serializing_if = "Option::is_none")]
 	thumbnail: Option<String>,
 	/// An image for the embed (optional)
+	#[serde(skip_serializing_if = "Option::is_none")]
 	image: Option<String>,
 	/// Footer information for the embed (max 2048 characters, optional)
+	#[serde(skip_serializing_if = "Option::is_none")]
 	footer: Option<String>,
 	/// Author information for the embed (max 256 characters, optional)
+	#[serde(skip_serializing_if = "Option::is_none")]
 	author: Option<String>,
 	/// A timestamp for the embed (optional)
+	#[serde(skip_serializing_if = "Option::is_none")]
 	timestamp: Option<String>,
 }
 
@@ -98,6 +146,8 @@ impl DiscordNotifier {
 
 		Ok(Self {
 			inner: WebhookNotifier::new(config, http_client)?,
+			explorer_url: None,
+			embeds_template: None,
 		})
 	}
 
@@ -113,6 +163,108 @@ impl DiscordNotifier {
 		format!("*{}*\n\n{}", self.inner.title, message)
 	}
 
+	/// Summarizes the match shown by the embed
+	///
+	/// # Arguments
+	/// * `variables` - Map of variable names to values
+	/// * `monitor_match` - Monitor match being notified, absent for digests
+	///
+	/// # Returns
+	/// * `MatchSummary` - Details of the match
+	pub fn format_summary(
+		&self,
+		variables: &HashMap<String, String>,
+		monitor_match: Option<&MonitorMatch>,
+	) -> MatchSummary {
+		MatchSummary::new(variables, monitor_match, self.explorer_url.as_deref())
+	}
+
+	/// Builds the embeds of a message
+	///
+	/// Configured embeds are rendered with the variables, otherwise a single embed is built from
+	/// the message and match summary. Embeds without a colour are coloured by the severity of
+	/// the monitor.
+	///
+	/// # Arguments
+	/// * `variables` - Map of variable names to values
+	/// * `summary` - Details of the match
+	///
+	/// # Returns
+	/// * `Result<serde_json::Value, NotificationError>` - Array of embeds
+	pub fn format_embeds(
+		&self,
+		variables: &HashMap<String, String>,
+		summary: &MatchSummary,
+	) -> Result<serde_json::Value, NotificationError> {
+		let mut embeds = match &self.embeds_template {
+			Some(template) => render_json_template(template, variables),
+			None => {
+				let mut fields = [
+					("Monitor", summary.monitor.clone()),
+					("Network", summary.network.clone()),
+					(
+						"Severity",
+						summary.severity.map(|severity| severity.to_string()),
+					),
+				]
+				.into_iter()
+				.filter_map(|(name, value)| {
+					value.map(|value| DiscordField::new(name, &value, true))
+				})
+				.collect::<Vec<_>>();
+				if !summary.signatures.is_empty() {
+					let signatures = summary
+						.signatures
+						.iter()
+						.map(|signature| format!("`{}`", signature))
+						.collect::<Vec<_>>()
+						.join("\n");
+					fields.push(DiscordField::new("Matched", &signatures, false));
+				}
+				fields.extend(
+					summary
+						.args
+						.iter()
+						.map(|(name, value)| DiscordField::new(name, value, true)),
+				);
+				fields.truncate(DISCORD_MAX_FIELDS);
+
+				let embed = DiscordEmbed {
+					title: truncate_text(&self.inner.title, DISCORD_MAX_TITLE_LENGTH),
+					description: Some(truncate_text(
+						&self.inner.format_message(variables),
+						DISCORD_MAX_DESCRIPTION_LENGTH,
+					)),
+					url: summary.explorer_link.clone(),
+					color: None,
+					fields: Some(fields).filter(|fields| !fields.is_empty()),
+					tts: None,
+					thumbnail: None,
+					image: None,
+					footer: None,
+					author: None,
+					timestamp: None,
+				};
+				serde_json::to_value(vec![embed]).map_err(|e| {
+					NotificationError::internal_error(
+						format!("Failed to serialize Discord embed: {}", e),
+						Some(e.into()),
+						None,
+					)
+				})?
+			}
+		};
+
+		if let (Some(severity), Some(embeds)) = (summary.severity, embeds.as_array_mut()) {
+			for embed in embeds.iter_mut().filter_map(|embed| embed.as_object_mut()) {
+				embed
+					.entry("color")
+					.or_insert_with(|| serde_json::json!(severity.color()));
+			}
+		}
+		Ok(embeds)
+	}
+
 	/// Creates a Discord notifier from a trigger configuration
 	///
 	/// # Arguments
@@ -128,6 +280,8 @@ impl DiscordNotifier {
 		if let TriggerTypeConfig::Discord {
 			discord_url,
 			message,
+			explorer_url,
+			embeds,
 			..
 		} = config
 		{
@@ -144,12 +298,34 @@ impl DiscordNotifier {
 
 			Ok(Self {
 				inner: WebhookNotifier::new(webhook_config, http_client)?,
+				explorer_url: explorer_url.clone(),
+				embeds_template: embeds.clone(),
 			})
 		} else {
 			let msg = format!("Invalid discord configuration: {:?}", config);
 			Err(NotificationError::config_error(msg, None, None))
 		}
 	}
+
+	/// Sends embeds to Discord
+	///
+	/// # Arguments
+	/// * `message` - The formatted message, covered by the request signature
+	/// * `embeds` - Embeds of the message
+	///
+	/// # Returns
+	/// * `Result<(), NotificationError>` - Success or error
+	pub async fn notify_embeds(
+		&self,
+		message: &str,
+		embeds: &serde_json::Value,
+	) -> Result<(), NotificationError> {
+		let payload_fields = HashMap::from([("embeds".to_string(), embeds.clone())]);
+
+		self.inner
+			.notify_with_payload(message, payload_fields)
+			.await
+	}
 }
 
 #[async_trait]
@@ -184,9 +360,11 @@ impl Notifier for DiscordNotifier {
 #[cfg(test)]
 mod tests {
 	use crate::{
-		models::{NotificationMessage, SecretString, SecretValue},
+		models::{IncidentSeverity, NotificationMessage, SecretString, SecretValue},
 		utils::{tests::create_test_http_client, HttpRetryConfig},
 	};
+	use mockito::Matcher;
+	use serde_json::json;
 
 	use super::*;
 
@@ -209,6 +387,8 @@ mod tests {
 				title: "Test Alert".to_string(),
 				body: "Test message ${value}".to_string(),
 			},
+			explorer_url: None,
+			embeds: None,
 			retry_policy: HttpRetryConfig::default(),
 		}
 	}
@@ -250,6 +430,104 @@ mod tests {
 		assert_eq!(result, "*Alert*\n\n");
 	}
 
+	////////////////////////////////////////////////////////////
+	// format_embeds tests
+	////////////////////////////////////////////////////////////
+
+	fn create_test_summary() -> MatchSummary {
+		MatchSummary {
+			monitor: Some("Large Transfer".to_string()),
+			network: Some("ethereum_mainnet".to_string()),
+			severity: Some(IncidentSeverity::Error),
+			explorer_link: Some("https://etherscan.io/tx/0xabc".to_string()),
+			signatures: vec!["Transfer(address,address,uint256)".to_string()],
+			args: vec![("Transfer.value".to_string(), "100".to_string())],
+		}
+	}
+
+	#[test]
+	fn test_format_embeds() {
+		let notifier = create_test_notifier("Value is ${value}");
+		let variables = HashMap::from([("value".to_string(), "100".to_string())]);
+
+		let embeds = notifier
+			.format_embeds(&variables, &create_test_summary())
+			.unwrap();
+		assert_eq!(
+			embeds,
+			json!([{
+				"title": "Alert",
+				"description": "Value is 100",
+				"url": "https://etherscan.io/tx/0xabc",
+				"color": 0xE8912D,
+				"fields": [
+					{ "name": "Monitor", "value": "Large Transfer", "inline": true },
+					{ "name": "Network", "value": "ethereum_mainnet", "inline": true },
+					{ "name": "Severity", "value": "error", "inline": true },
+					{
+						"name": "Matched",
+						"value": "`Transfer(address,address,uint256)`",
+						"inline": false,
+					},
+					{ "name": "Transfer.value", "value": "100", "inline": true },
+				],
+			}])
+		);
+	}
+
+	#[test]
+	fn test_format_embeds_of_digest() {
+		let notifier = create_test_notifier("3 matches");
+
+		let embeds = notifier
+			.format_embeds(&HashMap::new(), &MatchSummary::default())
+			.unwrap();
+		assert_eq!(
+			embeds,
+			json!([{ "title": "Alert", "description": "3 matches" }])
+		);
+	}
+
+	#[test]
+	fn test_format_embeds_limits_fields() {
+		let notifier = create_test_notifier("Value is ${value}");
+		let summary = MatchSummary {
+			args: (0..30)
+				.map(|i| (format!("Batch.arg{}", i), "x".repeat(2000)))
+				.collect(),
+			..Default::default()
+		};
+
+		let embeds = notifier.format_embeds(&HashMap::new(), &summary).unwrap();
+		let fields = embeds[0]["fields"].as_array().unwrap();
+		assert_eq!(fields.len(), DISCORD_MAX_FIELDS);
+		assert_eq!(
+			fields[0]["value"].as_str().unwrap().chars().count(),
+			DISCORD_MAX_FIELD_VALUE_LENGTH
+		);
+	}
+
+	#[test]
+	fn test_format_embeds_with_template() {
+		let mut notifier = create_test_notifier("Value is ${value}");
+		notifier.embeds_template = Some(json!([
+			{ "title": "${monitor.name}" },
+			{ "title": "Details", "color": 255 }
+		]));
+		let variables = HashMap::from([("monitor.name".to_string(), "Large Transfer".to_string())]);
+
+		let embeds = notifier
+			.format_embeds(&variables, &create_test_summary())
+			.unwrap();
+		assert_eq!(
+			embeds,
+			json!([
+				{ "title": "Large Transfer", "color": 0xE8912D },
+				{ "title": "Details", "color": 255 }
+			])
+		);
+	}
+
 	////////////////////////////////////////////////////////////
 	// from_config tests
 	////////////////////////////////////////////////////////////
@@ -281,6 +559,29 @@ mod tests {
 		assert!(matches!(error, NotificationError::NotifyFailed { .. }));
 	}
 
+	#[tokio::test]
+	async fn test_notify_embeds() {
+		let mut server = mockito::Server::new_async().await;
+		let embeds = json!([{ "title": "Alert", "description": "Hi" }]);
+		let mock = server
+			.mock("POST", "/")
+			.match_body(Matcher::Json(json!({ "embeds": embeds })))
+			.with_status(200)
+			.create_async()
+			.await;
+
+		let notifier = DiscordNotifier::new(
+			server.url(),
+			"Alert".to_string(),
+			"Hi".to_string(),
+			create_test_http_client(),
+		)
+		.unwrap();
+		let result = notifier.notify_embeds("*Alert*\n\nHi", &embeds).await;
+		assert!(result.is_ok());
+		mock.assert();
+	}
+
 	#[tokio::test]
 	async fn test_notify_with_payload_failure() {
 		let notifier = create_test_notifier("Test message");
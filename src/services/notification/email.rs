@@ -16,7 +16,7 @@ use std::{collections::HashMap, sync::Arc};
 
 use crate::{
 	models::TriggerTypeConfig,
-	services::notification::{NotificationError, Notifier},
+	services::notification::{render_template, NotificationError, Notifier},
 };
 use pulldown_cmark::{html, Options, Parser};
 
@@ -106,11 +106,7 @@ impl EmailNotifier<SmtpTransport> {
 	/// # Returns
 	/// * `String` - Formatted message with variables replaced and converted to HTML
 	pub fn format_message(&self, variables: &HashMap<String, String>) -> String {
-		let formatted_message = variables
-			.iter()
-			.fold(self.body_template.clone(), |message, (key, value)| {
-				message.replace(&format!("${{{}}}", key), value)
-			});
+		let formatted_message = render_template(&self.body_template, variables);
 
 		Self::markdown_to_html(&formatted_message)
 	}
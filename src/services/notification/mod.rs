@@ -7,28 +7,78 @@ use async_trait::async_trait;
 
 use std::{collections::HashMap, sync::Arc};
 
+mod database;
+mod digest;
 mod discord;
 mod email;
 mod error;
+mod opsgenie;
+mod pagerduty;
+mod plugin;
 mod pool;
+mod queue;
+mod resolve;
 mod script;
 mod slack;
+mod summary;
+mod teams;
 mod telegram;
+mod template;
 mod webhook;
 
 use crate::{
-	models::{MonitorMatch, ScriptLanguage, Trigger, TriggerType, TriggerTypeConfig},
+	models::{
+		MonitorMatch, NotificationMessage, ScriptLanguage, Trigger, TriggerType, TriggerTypeConfig,
+	},
+	services::trigger::append_throttle_summary,
 	utils::normalize_string,
 };
 
+#[cfg(feature = "database")]
+pub use database::{create_database_sink, DatabaseNotifier, DatabaseSink};
+pub use database::{
+	DatabaseBackend, DatabaseRow, DATABASE_KEY_COLUMNS, DEFAULT_DATABASE_BATCH_SIZE,
+	DEFAULT_DATABASE_BATCH_TIMEOUT_MS, LOG_INDEX_COLUMN, NETWORK_COLUMN, NO_LOG_INDEX,
+	ROW_EVENT_PREFIX, ROW_LOG_INDEX, TRANSACTION_HASH_COLUMN,
+};
+pub use digest::{
+	render_digest, validate_digest_template, DigestBuffer, DigestEntry, DIGEST_BODY, DIGEST_COUNT,
+	DIGEST_MATCHES, MATCH_MESSAGE,
+};
 pub use discord::DiscordNotifier;
 pub use email::{EmailContent, EmailNotifier, SmtpConfig};
 pub use error::NotificationError;
+pub use opsgenie::{OpsgenieNotifier, OPSGENIE_API_URL};
+pub use pagerduty::{PagerDutyNotifier, DEFAULT_DEDUP_KEY, PAGERDUTY_EVENTS_URL};
+pub use plugin::{NotifierPlugin, NotifierPluginRegistry};
 pub use pool::NotificationClientPool;
+#[cfg(feature = "amqp")]
+pub use queue::AmqpPublisher;
+#[cfg(feature = "kafka")]
+pub use queue::KafkaPublisher;
+#[cfg(feature = "nats")]
+pub use queue::NatsPublisher;
+#[cfg(feature = "redis")]
+pub use queue::RedisPublisher;
+pub use queue::{
+	create_queue_publisher, PendingMessages, QueueMessage, QueueNotifier, QueueOutbox,
+	QueueOutboxes, QueuePublisher, DEFAULT_QUEUE_OUTBOX_DIR, MESSAGE_ID_HEADER,
+	PARTITION_KEY_HEADER, QUEUE_CONTENT_TYPE,
+};
+pub use resolve::ResolveTimers;
 pub use script::ScriptNotifier;
 pub use slack::SlackNotifier;
+pub use summary::{format_explorer_link, format_match_args, truncate_text, MatchSummary};
+pub use teams::{TeamsFact, TeamsNotifier};
 pub use telegram::TelegramNotifier;
-pub use webhook::{WebhookConfig, WebhookNotifier};
+pub use template::{
+	render_json_template, render_template, validate_json_template, validate_template, Template,
+	TemplateError,
+};
+pub use webhook::{
+	EVMMatchDetails, MatchDetails, MatchPayload, PollMatchDetails, StellarMatchDetails,
+	WebhookConfig, WebhookNotifier, MATCH_PAYLOAD_VERSION,
+};
 
 /// Interface for notification implementations
 ///
@@ -88,6 +138,14 @@ pub trait ScriptExecutor {
 pub struct NotificationService {
 	/// Client pool for managing notification clients (HTTP, SMTP)
 	client_pool: Arc<NotificationClientPool>,
+	/// Matches buffered for digest triggers
+	digest_buffer: DigestBuffer,
+	/// Notifier plugins referenced by plugin triggers
+	plugins: NotifierPluginRegistry,
+	/// Outboxes of the messages queue triggers failed to publish
+	queue_outboxes: QueueOutboxes,
+	/// Pending resolutions of PagerDuty incidents and Opsgenie alerts
+	resolve_timers: ResolveTimers,
 }
 
 impl NotificationService {
@@ -95,11 +153,40 @@ impl NotificationService {
 	pub fn new() -> Self {
 		NotificationService {
 			client_pool: Arc::new(NotificationClientPool::new()),
+			digest_buffer: DigestBuffer::new(),
+			plugins: NotifierPluginRegistry::default(),
+			queue_outboxes: QueueOutboxes::default(),
+			resolve_timers: ResolveTimers::new(),
 		}
 	}
 
+	/// Stores the outboxes of queue triggers in the given directory instead of the default one
+	///
+	/// # Arguments
+	/// * `dir` - Directory of the outbox files
+	pub fn with_queue_outbox_dir(mut self, dir: impl Into<std::path::PathBuf>) -> Self {
+		self.queue_outboxes = QueueOutboxes::new(dir.into());
+		self
+	}
+
+	/// Registers a notifier plugin which plugin triggers reference by name
+	///
+	/// Plugins should be registered at startup, before matches are notified. Registering a
+	/// plugin under an existing name replaces it.
+	///
+	/// # Arguments
+	/// * `name` - Name triggers reference the plugin with
+	/// * `plugin` - Plugin sending the notifications
+	pub fn register_plugin(&self, name: impl Into<String>, plugin: Arc<dyn NotifierPlugin>) {
+		self.plugins.register(name, plugin);
+	}
+
 	/// Executes a notification based on the trigger configuration
 	///
+	/// Matches of triggers with a digest are buffered until the digest is flushed. When matches
+	/// were suppressed by the monitor throttle, their summary is appended to message bodies which
+	/// do not use throttle variables.
+	///
 	/// # Arguments
 	/// * `trigger` - Trigger containing the notification type and parameters
 	/// * `variables` - Variables to substitute in message templates
@@ -115,13 +202,97 @@ impl NotificationService {
 		variables: &HashMap<String, String>,
 		monitor_match: &MonitorMatch,
 		trigger_scripts: &HashMap<String, (ScriptLanguage, String)>,
+	) -> Result<(), NotificationError> {
+		let summarized = append_throttle_summary(trigger, variables);
+		let trigger = summarized.as_ref().unwrap_or(trigger);
+
+		if trigger.digest.is_some() && trigger.trigger_type != TriggerType::Script {
+			let message = trigger
+				.config
+				.message()
+				.map(|message| render_template(&message.body, variables))
+				.unwrap_or_default();
+			self.digest_buffer.push(
+				&trigger.name,
+				DigestEntry {
+					variables: variables.clone(),
+					message,
+				},
+			);
+			return Ok(());
+		}
+
+		match &trigger.trigger_type {
+			TriggerType::Script => {
+				let notifier = ScriptNotifier::from_config(&trigger.config)?;
+				let monitor_name = match monitor_match {
+					MonitorMatch::EVM(evm_match) => &evm_match.monitor.name,
+					MonitorMatch::Stellar(stellar_match) => &stellar_match.monitor.name,
+					MonitorMatch::Poll(poll_match) => &poll_match.monitor.name,
+				};
+				let script_path = match &trigger.config {
+					TriggerTypeConfig::Script { script_path, .. } => script_path,
+					_ => {
+						return Err(NotificationError::config_error(
+							"Invalid script configuration".to_string(),
+							None,
+							None,
+						))
+					}
+				};
+				let script = trigger_scripts
+					.get(&format!(
+						"{}|{}",
+						normalize_string(monitor_name),
+						script_path
+					))
+					.ok_or_else(|| {
+						NotificationError::config_error(
+							"Script content not found".to_string(),
+							None,
+							None,
+						)
+					});
+				let script_content = match &script {
+					Ok(content) => content,
+					Err(e) => {
+						return Err(NotificationError::config_error(e.to_string(), None, None))
+					}
+				};
+
+				notifier
+					.script_notify(monitor_match, script_content)
+					.await?;
+			}
+			_ => self.send(trigger, variables, Some(monitor_match)).await?,
+		}
+		Ok(())
+	}
+
+	/// Sends a notification through a non-script trigger
+	///
+	/// # Arguments
+	/// * `trigger` - Trigger containing the notification type and parameters
+	/// * `variables` - Variables to substitute in message templates
+	/// * `monitor_match` - Monitor match being notified, absent for digests
+	///
+	/// # Returns
+	/// * `Result<(), NotificationError>` - Success or error
+	async fn send(
+		&self,
+		trigger: &Trigger,
+		variables: &HashMap<String, String>,
+		monitor_match: Option<&MonitorMatch>,
 	) -> Result<(), NotificationError> {
 		match &trigger.trigger_type {
 			// Match Webhook-based triggers
 			TriggerType::Slack
 			| TriggerType::Discord
 			| TriggerType::Webhook
-			| TriggerType::Telegram => {
+			| TriggerType::Telegram
+			| TriggerType::PagerDuty
+			| TriggerType::Opsgenie
+			| TriggerType::Teams => {
 				// Extract retry policy from the trigger configuration
 				let retry_policy = trigger.config.get_retry_policy().ok_or_else(|| {
 					NotificationError::config_error(
@@ -147,13 +318,20 @@ impl NotificationService {
 				match &trigger.trigger_type {
 					TriggerType::Webhook => {
 						let notifier = WebhookNotifier::from_config(&trigger.config, http_client)?;
-						let message = notifier.format_message(variables);
-						notifier.notify(&message).await?;
+						match notifier.format_payload(variables, monitor_match)? {
+							Some(payload) => notifier.notify_json(&payload).await?,
+							None => {
+								let message = notifier.format_message(variables);
+								notifier.notify(&message).await?;
+							}
+						}
 					}
 					TriggerType::Discord => {
 						let notifier = DiscordNotifier::from_config(&trigger.config, http_client)?;
 						let message = notifier.format_message(variables);
-						notifier.notify(&message).await?;
+						let summary = notifier.format_summary(variables, monitor_match);
+						let embeds = notifier.format_embeds(variables, &summary)?;
+						notifier.notify_embeds(&message, &embeds).await?;
 					}
 					TriggerType::Telegram => {
 						let notifier = TelegramNotifier::from_config(&trigger.config, http_client)?;
@@ -163,11 +341,110 @@ impl NotificationService {
 					TriggerType::Slack => {
 						let notifier = SlackNotifier::from_config(&trigger.config, http_client)?;
 						let message = notifier.format_message(variables);
-						notifier.notify(&message).await?;
+						let summary = notifier.format_summary(variables, monitor_match);
+						let blocks = notifier.format_blocks(variables, &summary);
+						notifier
+							.notify_blocks(&message, &blocks, summary.severity)
+							.await?;
+					}
+					TriggerType::Teams => {
+						let notifier = TeamsNotifier::from_config(&trigger.config, http_client)?;
+						let message = notifier.format_message(variables);
+						let facts = notifier.format_facts(variables);
+						let explorer_link = notifier.format_explorer_link(variables);
+						notifier
+							.notify_card(&message, &facts, explorer_link.as_deref())
+							.await?;
+					}
+					TriggerType::PagerDuty => {
+						let notifier =
+							PagerDutyNotifier::from_config(&trigger.config, http_client)?;
+						let message = notifier.format_message(variables);
+						let dedup_key = notifier.format_dedup_key(variables);
+						notifier.trigger_incident(&message, &dedup_key).await?;
+						notifier.schedule_resolve(&self.resolve_timers, &trigger.name, dedup_key);
+					}
+					TriggerType::Opsgenie => {
+						let notifier = OpsgenieNotifier::from_config(&trigger.config, http_client)?;
+						let message = notifier.format_message(variables);
+						let alias = notifier.format_alias(variables);
+						notifier.open_alert(&message, Some(&alias)).await?;
+						notifier.schedule_resolve(&self.resolve_timers, &trigger.name, alias);
 					}
 					_ => unreachable!(),
 				}
 			}
+			TriggerType::Kafka | TriggerType::Nats | TriggerType::Redis | TriggerType::Amqp => {
+				let monitor_match = monitor_match.ok_or_else(|| {
+					NotificationError::config_error(
+						format!(
+							"Queue trigger can only publish monitor matches: {}",
+							trigger.name
+						),
+						None,
+						None,
+					)
+				})?;
+
+				// Get or create the queue client from the pool
+				let publisher = self
+					.client_pool
+					.get_or_create_queue_publisher(&trigger.config)
+					.await
+					.map_err(|e| {
+						NotificationError::execution_error(
+							"Failed to get or create queue client from pool".to_string(),
+							Some(e.into()),
+							None,
+						)
+					})?;
+
+				let notifier = QueueNotifier::from_config(&trigger.config, publisher)?;
+				let message = notifier.format_message(monitor_match)?;
+				let outbox = self.queue_outboxes.get(&trigger.name);
+				notifier.deliver(message, &outbox).await?;
+			}
+			#[cfg(feature = "database")]
+			TriggerType::Database => {
+				let monitor_match = monitor_match.ok_or_else(|| {
+					NotificationError::config_error(
+						format!(
+							"Database trigger can only write monitor matches: {}",
+							trigger.name
+						),
+						None,
+						None,
+					)
+				})?;
+
+				// Get or create the database client from the pool
+				let sink = self
+					.client_pool
+					.get_or_create_database_sink(&trigger.config)
+					.await
+					.map_err(|e| {
+						NotificationError::execution_error(
+							"Failed to get or create database client from pool".to_string(),
+							Some(e.into()),
+							None,
+						)
+					})?;
+
+				let notifier = DatabaseNotifier::from_config(&trigger.config, sink)?;
+				let rows = notifier.format_rows(variables, monitor_match);
+				notifier.write(&rows).await?;
+			}
+			#[cfg(not(feature = "database"))]
+			TriggerType::Database => {
+				return Err(NotificationError::config_error(
+					format!(
+						"Database trigger requires the monitor to be built with a database feature: {}",
+						trigger.name
+					),
+					None,
+					None,
+				));
+			}
 			TriggerType::Email => {
 				// Extract SMTP configuration from the trigger
 				let smtp_config = match &trigger.config {
@@ -209,49 +486,117 @@ impl NotificationService {
 				let message = notifier.format_message(variables);
 				notifier.notify(&message).await?;
 			}
-			TriggerType::Script => {
-				let notifier = ScriptNotifier::from_config(&trigger.config)?;
-				let monitor_name = match monitor_match {
-					MonitorMatch::EVM(evm_match) => &evm_match.monitor.name,
-					MonitorMatch::Stellar(stellar_match) => &stellar_match.monitor.name,
-				};
-				let script_path = match &trigger.config {
-					TriggerTypeConfig::Script { script_path, .. } => script_path,
-					_ => {
-						return Err(NotificationError::config_error(
-							"Invalid script configuration".to_string(),
-							None,
-							None,
-						))
-					}
-				};
-				let script = trigger_scripts
-					.get(&format!(
-						"{}|{}",
-						normalize_string(monitor_name),
-						script_path
-					))
-					.ok_or_else(|| {
-						NotificationError::config_error(
-							"Script content not found".to_string(),
-							None,
-							None,
-						)
-					});
-				let script_content = match &script {
-					Ok(content) => content,
-					Err(e) => {
-						return Err(NotificationError::config_error(e.to_string(), None, None))
-					}
+			TriggerType::Plugin => {
+				let TriggerTypeConfig::Plugin { plugin, options } = &trigger.config else {
+					return Err(NotificationError::config_error(
+						"Invalid plugin configuration".to_string(),
+						None,
+						None,
+					));
 				};
-
-				notifier
-					.script_notify(monitor_match, script_content)
-					.await?;
+				let monitor_match = monitor_match.ok_or_else(|| {
+					NotificationError::config_error(
+						format!(
+							"Plugin trigger can only notify monitor matches: {}",
+							trigger.name
+						),
+						None,
+						None,
+					)
+				})?;
+				let notifier = self.plugins.get(plugin).ok_or_else(|| {
+					NotificationError::config_error(
+						format!("Notifier plugin is not registered: {}", plugin),
+						None,
+						None,
+					)
+				})?;
+				notifier.notify(options, variables, monitor_match).await?;
+			}
+			TriggerType::Script => {
+				return Err(NotificationError::config_error(
+					format!(
+						"Script trigger cannot be sent as a message: {}",
+						trigger.name
+					),
+					None,
+					None,
+				))
 			}
 		}
 		Ok(())
 	}
+
+	/// Sends the buffered matches of a digest trigger as a single notification
+	///
+	/// # Arguments
+	/// * `trigger` - Digest trigger to flush
+	///
+	/// # Returns
+	/// * `Result<usize, NotificationError>` - Number of matches sent, or error
+	pub async fn flush_digest(&self, trigger: &Trigger) -> Result<usize, NotificationError> {
+		let digest = trigger.digest.as_ref().ok_or_else(|| {
+			NotificationError::config_error(
+				format!("Trigger has no digest configured: {}", trigger.name),
+				None,
+				None,
+			)
+		})?;
+
+		let entries = self.digest_buffer.take(&trigger.name);
+		if entries.is_empty() {
+			return Ok(0);
+		}
+
+		// Titles are sent as they are, while the body is rendered by the notifiers. The rendered
+		// body holds match values, so it is substituted as a variable rather than rendered again
+		let variables = HashMap::from([(
+			DIGEST_BODY.to_string(),
+			render_digest(&digest.message.body, &entries),
+		)]);
+		let message = NotificationMessage {
+			title: render_digest(&digest.message.title, &entries),
+			body: format!("${{{}}}", DIGEST_BODY),
+		};
+		let digest_trigger = Trigger {
+			config: trigger.config.with_message(message),
+			digest: None,
+			..trigger.clone()
+		};
+
+		self.send(&digest_trigger, &variables, None).await?;
+		Ok(entries.len())
+	}
+
+	/// Publishes the messages a queue trigger previously failed to publish
+	///
+	/// # Arguments
+	/// * `trigger` - Queue trigger whose outbox is drained
+	///
+	/// # Returns
+	/// * `Result<usize, NotificationError>` - Number of messages published, or error
+	pub async fn drain_queue_outbox(&self, trigger: &Trigger) -> Result<usize, NotificationError> {
+		let outbox = self.queue_outboxes.get(&trigger.name);
+		// Avoid connecting to the brokers of triggers with nothing to publish
+		if outbox.lock().await?.is_empty() {
+			return Ok(0);
+		}
+
+		let publisher = self
+			.client_pool
+			.get_or_create_queue_publisher(&trigger.config)
+			.await
+			.map_err(|e| {
+				NotificationError::execution_error(
+					"Failed to get or create queue client from pool".to_string(),
+					Some(e.into()),
+					None,
+				)
+			})?;
+		QueueNotifier::from_config(&trigger.config, publisher)?
+			.drain(&outbox)
+			.await
+	}
 }
 
 impl Default for NotificationService {
@@ -316,8 +661,10 @@ mod tests {
 				functions: vec![],
 				events: vec![],
 				transactions: vec![],
+				..Default::default()
 			},
 			matched_on_args: None,
+			enrichment: None,
 		}))
 	}
 
@@ -498,4 +845,80 @@ mod tests {
 			_ => panic!("Expected ConfigError"),
 		}
 	}
+
+	/// Records the variables of the matches it is notified of
+	#[derive(Default)]
+	struct RecordingPlugin {
+		notified: std::sync::Mutex<Vec<String>>,
+	}
+
+	#[async_trait]
+	impl NotifierPlugin for RecordingPlugin {
+		async fn notify(
+			&self,
+			options: &serde_json::Map<String, serde_json::Value>,
+			variables: &HashMap<String, String>,
+			_monitor_match: &MonitorMatch,
+		) -> Result<(), NotificationError> {
+			self.notified.lock().unwrap().push(format!(
+				"{}:{}",
+				options["queue"].as_str().unwrap_or_default(),
+				variables["monitor.name"]
+			));
+			Ok(())
+		}
+	}
+
+	#[tokio::test]
+	async fn test_plugin_notification() {
+		let service = NotificationService::new();
+		let plugin = Arc::new(RecordingPlugin::default());
+		service.register_plugin("ticketing", plugin.clone());
+
+		let mut trigger = TriggerBuilder::new()
+			.name("test_plugin")
+			.plugin("ticketing")
+			.build();
+		if let TriggerTypeConfig::Plugin { options, .. } = &mut trigger.config {
+			options.insert("queue".to_string(), "security".into());
+		}
+		let variables = HashMap::from([("monitor.name".to_string(), "test".to_string())]);
+
+		service
+			.execute(
+				&trigger,
+				&variables,
+				&create_mock_monitor_match(),
+				&HashMap::new(),
+			)
+			.await
+			.unwrap();
+		assert_eq!(*plugin.notified.lock().unwrap(), vec!["security:test"]);
+	}
+
+	#[tokio::test]
+	async fn test_unregistered_plugin_notification() {
+		let service = NotificationService::new();
+		let trigger = TriggerBuilder::new()
+			.name("test_plugin")
+			.plugin("missing")
+			.build();
+
+		let result = service
+			.execute(
+				&trigger,
+				&HashMap::new(),
+				&create_mock_monitor_match(),
+				&HashMap::new(),
+			)
+			.await;
+		match result {
+			Err(NotificationError::ConfigError(ctx)) => {
+				assert!(ctx
+					.message
+					.contains("Notifier plugin is not registered: missing"));
+			}
+			_ => panic!("Expected ConfigError"),
+		}
+	}
 }
@@ -0,0 +1,361 @@
+//! Opsgenie notification implementation.
+//!
+//! Opens alerts through the Opsgenie Alert API. Alerts are deduplicated by an alias rendered
+//! from the match and can be closed automatically after a delay.
+
+use async_trait::async_trait;
+use reqwest_middleware::ClientWithMiddleware;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tokio::task::JoinHandle;
+
+use crate::{
+	models::{IncidentSeverity, TriggerTypeConfig},
+	services::notification::{
+		render_template, NotificationError, Notifier, ResolveTimers, WebhookConfig,
+		WebhookNotifier, DEFAULT_DEDUP_KEY,
+	},
+};
+
+/// API base URL of the Opsgenie US service region
+pub const OPSGENIE_API_URL: &str = "https://api.opsgenie.com";
+
+/// Source reported in Opsgenie alerts
+const ALERT_SOURCE: &str = "openzeppelin-monitor";
+
+/// Maximum length of an Opsgenie alert alias
+const MAX_ALIAS_LENGTH: usize = 512;
+
+/// Maximum length of an Opsgenie alert description
+const MAX_DESCRIPTION_LENGTH: usize = 15000;
+
+/// Implementation of Opsgenie notifications via the Alert API
+#[derive(Debug, Clone)]
+pub struct OpsgenieNotifier {
+	/// API base URL
+	api_url: String,
+	/// API integration key
+	api_key: String,
+	/// Alert message
+	title: String,
+	/// Alert description template with variable placeholders
+	body_template: String,
+	/// Severity of opened alerts
+	severity: IncidentSeverity,
+	/// Alias template
+	alias_template: String,
+	/// Delay after which opened alerts are closed
+	auto_resolve_after: Option<Duration>,
+	/// Configured HTTP client for Opsgenie requests with retry capabilities
+	client: Arc<ClientWithMiddleware>,
+}
+
+impl OpsgenieNotifier {
+	/// Formats a message by substituting variables in the template
+	///
+	/// # Arguments
+	/// * `variables` - Map of variable names to values
+	///
+	/// # Returns
+	/// * `String` - Formatted message with variables replaced
+	pub fn format_message(&self, variables: &HashMap<String, String>) -> String {
+		render_template(&self.body_template, variables)
+	}
+
+	/// Renders the alias of a match
+	///
+	/// # Arguments
+	/// * `variables` - Map of variable names to values
+	///
+	/// # Returns
+	/// * `String` - Alias, truncated to the Opsgenie limit
+	pub fn format_alias(&self, variables: &HashMap<String, String>) -> String {
+		render_template(&self.alias_template, variables)
+			.chars()
+			.take(MAX_ALIAS_LENGTH)
+			.collect()
+	}
+
+	/// Creates an Opsgenie notifier from a trigger configuration
+	///
+	/// # Arguments
+	/// * `config` - Trigger configuration containing Opsgenie parameters
+	/// * `http_client` - HTTP client with middleware for retries
+	///
+	/// # Returns
+	/// * `Result<Self, NotificationError>` - Notifier instance if config is Opsgenie type
+	pub fn from_config(
+		config: &TriggerTypeConfig,
+		http_client: Arc<ClientWithMiddleware>,
+	) -> Result<Self, NotificationError> {
+		if let TriggerTypeConfig::Opsgenie {
+			api_key,
+			message,
+			severity,
+			alias,
+			auto_resolve_after_secs,
+			api_url,
+			..
+		} = config
+		{
+			Ok(Self {
+				api_url: api_url
+					.as_deref()
+					.unwrap_or(OPSGENIE_API_URL)
+					.trim_end_matches('/')
+					.to_string(),
+				api_key: api_key.as_ref().to_string(),
+				title: message.title.clone(),
+				body_template: message.body.clone(),
+				severity: *severity,
+				alias_template: alias
+					.clone()
+					.unwrap_or_else(|| DEFAULT_DEDUP_KEY.to_string()),
+				auto_resolve_after: auto_resolve_after_secs.map(Duration::from_secs),
+				client: http_client,
+			})
+		} else {
+			let msg = format!("Invalid Opsgenie configuration: {:?}", config);
+			Err(NotificationError::config_error(msg, None, None))
+		}
+	}
+
+	/// Opens an alert, or increases the count of the open alert with the same alias
+	///
+	/// # Arguments
+	/// * `message` - The formatted message, sent as the alert description
+	/// * `alias` - Alias of the alert
+	///
+	/// # Returns
+	/// * `Result<(), NotificationError>` - Success or error
+	pub async fn open_alert(
+		&self,
+		message: &str,
+		alias: Option<&str>,
+	) -> Result<(), NotificationError> {
+		let description = message
+			.chars()
+			.take(MAX_DESCRIPTION_LENGTH)
+			.collect::<String>();
+		let mut payload_fields = HashMap::from([
+			("message".to_string(), serde_json::json!(self.title)),
+			("description".to_string(), serde_json::json!(description)),
+			(
+				"priority".to_string(),
+				serde_json::json!(self.severity.opsgenie_priority()),
+			),
+			("source".to_string(), serde_json::json!(ALERT_SOURCE)),
+		]);
+		if let Some(alias) = alias {
+			payload_fields.insert("alias".to_string(), serde_json::json!(alias));
+		}
+
+		self.endpoint("/v2/alerts".to_string(), None)?
+			.notify_with_payload(message, payload_fields)
+			.await
+	}
+
+	/// Closes the alert with the given alias
+	///
+	/// # Arguments
+	/// * `alias` - Alias of the alert
+	///
+	/// # Returns
+	/// * `Result<(), NotificationError>` - Success or error
+	pub async fn close_alert(&self, alias: &str) -> Result<(), NotificationError> {
+		let payload_fields =
+			HashMap::from([("source".to_string(), serde_json::json!(ALERT_SOURCE))]);
+
+		self.endpoint(
+			format!("/v2/alerts/{}/close", urlencoding::encode(alias)),
+			Some(HashMap::from([(
+				"identifierType".to_string(),
+				"alias".to_string(),
+			)])),
+		)?
+		.notify_with_payload(alias, payload_fields)
+		.await
+	}
+
+	/// Schedules the closing of an alert when auto-resolve is configured
+	///
+	/// The closing replaces the pending one of the alert, so that it is only sent once the alert
+	/// has not been re-opened for the configured delay.
+	///
+	/// # Arguments
+	/// * `timers` - Pending resolutions shared by all matches
+	/// * `trigger_name` - Name of the trigger opening the alert
+	/// * `alias` - Alias of the alert
+	///
+	/// # Returns
+	/// * `Option<JoinHandle<()>>` - Handle of the scheduled closing, if any
+	pub fn schedule_resolve(
+		&self,
+		timers: &ResolveTimers,
+		trigger_name: &str,
+		alias: String,
+	) -> Option<JoinHandle<()>> {
+		let delay = self.auto_resolve_after?;
+		let notifier = self.clone();
+		Some(
+			timers.schedule(format!("{}|{}", trigger_name, alias), delay, async move {
+				if let Err(e) = notifier.close_alert(&alias).await {
+					tracing::error!("Failed to close Opsgenie alert {}: {}", alias, e);
+				}
+			}),
+		)
+	}
+
+	/// Builds a webhook notifier for an Alert API endpoint
+	///
+	/// # Arguments
+	/// * `path` - Endpoint path, relative to the API base URL
+	/// * `url_params` - Query parameters of the endpoint
+	fn endpoint(
+		&self,
+		path: String,
+		url_params: Option<HashMap<String, String>>,
+	) -> Result<WebhookNotifier, NotificationError> {
+		let webhook_config = WebhookConfig {
+			url: format!("{}{}", self.api_url, path),
+			url_params,
+			title: self.title.clone(),
+			body_template: self.body_template.clone(),
+			method: Some("POST".to_string()),
+			secret: None,
+			headers: Some(HashMap::from([(
+				"Authorization".to_string(),
+				format!("GenieKey {}", self.api_key),
+			)])),
+			payload_fields: None,
+		};
+
+		WebhookNotifier::new(webhook_config, self.client.clone())
+	}
+}
+
+#[async_trait]
+impl Notifier for OpsgenieNotifier {
+	/// Opens an Opsgenie alert without an alias
+	///
+	/// # Arguments
+	/// * `message` - The formatted message to send
+	///
+	/// # Returns
+	/// * `Result<(), NotificationError>` - Success or error
+	async fn notify(&self, message: &str) -> Result<(), NotificationError> {
+		self.open_alert(message, None).await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		models::{NotificationMessage, SecretString, SecretValue},
+		utils::{tests::create_test_http_client, HttpRetryConfig},
+	};
+
+	use super::*;
+	use mockito::Matcher;
+	use serde_json::json;
+
+	fn create_test_opsgenie_config(
+		api_url: &str,
+		auto_resolve_after_secs: Option<u64>,
+	) -> TriggerTypeConfig {
+		TriggerTypeConfig::Opsgenie {
+			api_key: SecretValue::Plain(SecretString::new("api-key".to_string())),
+			message: NotificationMessage {
+				title: "Contract paused".to_string(),
+				body: "Paused by ${account}".to_string(),
+			},
+			severity: IncidentSeverity::Critical,
+			alias: Some("${monitor.name}".to_string()),
+			auto_resolve_after_secs,
+			api_url: Some(api_url.to_string()),
+			retry_policy: HttpRetryConfig::default(),
+		}
+	}
+
+	#[test]
+	fn test_from_config_defaults() {
+		let config = TriggerTypeConfig::Opsgenie {
+			api_key: SecretValue::Plain(SecretString::new("api-key".to_string())),
+			message: NotificationMessage {
+				title: "Contract paused".to_string(),
+				body: "Paused".to_string(),
+			},
+			severity: IncidentSeverity::default(),
+			alias: None,
+			auto_resolve_after_secs: None,
+			api_url: None,
+			retry_policy: HttpRetryConfig::default(),
+		};
+		let notifier = OpsgenieNotifier::from_config(&config, create_test_http_client()).unwrap();
+
+		assert_eq!(notifier.api_url, OPSGENIE_API_URL);
+		assert_eq!(notifier.alias_template, DEFAULT_DEDUP_KEY);
+		assert!(notifier
+			.schedule_resolve(&ResolveTimers::new(), "opsgenie", "alias".to_string())
+			.is_none());
+	}
+
+	#[tokio::test]
+	async fn test_open_alert() {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server
+			.mock("POST", "/v2/alerts")
+			.match_header("Authorization", "GenieKey api-key")
+			.match_body(Matcher::Json(json!({
+				"message": "Contract paused",
+				"description": "Paused by 0x1",
+				"priority": "P1",
+				"source": "openzeppelin-monitor",
+				"alias": "Pausable"
+			})))
+			.with_status(202)
+			.create_async()
+			.await;
+
+		let config = create_test_opsgenie_config(&format!("{}/", server.url()), None);
+		let notifier = OpsgenieNotifier::from_config(&config, create_test_http_client()).unwrap();
+		let variables = HashMap::from([
+			("account".to_string(), "0x1".to_string()),
+			("monitor.name".to_string(), "Pausable".to_string()),
+		]);
+		let alias = notifier.format_alias(&variables);
+
+		let result = notifier
+			.open_alert(&notifier.format_message(&variables), Some(&alias))
+			.await;
+		assert!(result.is_ok());
+		mock.assert();
+	}
+
+	#[tokio::test]
+	async fn test_schedule_resolve() {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server
+			.mock("POST", "/v2/alerts/Pausable%20Alert/close")
+			.match_query(Matcher::UrlEncoded(
+				"identifierType".to_string(),
+				"alias".to_string(),
+			))
+			.match_header("Authorization", "GenieKey api-key")
+			.with_status(202)
+			.create_async()
+			.await;
+
+		let config = create_test_opsgenie_config(&server.url(), Some(0));
+		let notifier = OpsgenieNotifier::from_config(&config, create_test_http_client()).unwrap();
+
+		let handle = notifier
+			.schedule_resolve(
+				&ResolveTimers::new(),
+				"opsgenie",
+				"Pausable Alert".to_string(),
+			)
+			.unwrap();
+		handle.await.unwrap();
+		mock.assert();
+	}
+}
@@ -0,0 +1,376 @@
+//! PagerDuty notification implementation.
+//!
+//! Opens incidents through the PagerDuty Events API v2. Incidents are deduplicated by a key
+//! rendered from the match and can be resolved automatically after a delay.
+
+use async_trait::async_trait;
+use reqwest_middleware::ClientWithMiddleware;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tokio::task::JoinHandle;
+
+use crate::{
+	models::{IncidentSeverity, TriggerTypeConfig},
+	services::notification::{
+		render_template, NotificationError, Notifier, ResolveTimers, WebhookConfig, WebhookNotifier,
+	},
+};
+
+/// Events API v2 endpoint of the PagerDuty US service region
+pub const PAGERDUTY_EVENTS_URL: &str = "https://events.pagerduty.com/v2/enqueue";
+
+/// Deduplication key template used when none is configured
+pub const DEFAULT_DEDUP_KEY: &str = "${monitor.name}:${transaction.hash}";
+
+/// Source reported in PagerDuty event payloads
+const EVENT_SOURCE: &str = "openzeppelin-monitor";
+
+/// Maximum length of a PagerDuty deduplication key
+const MAX_DEDUP_KEY_LENGTH: usize = 255;
+
+/// Maximum length of a PagerDuty event summary
+const MAX_SUMMARY_LENGTH: usize = 1024;
+
+/// Implementation of PagerDuty notifications via the Events API v2
+#[derive(Debug, Clone)]
+pub struct PagerDutyNotifier {
+	inner: WebhookNotifier,
+	/// Integration key of the PagerDuty service
+	routing_key: String,
+	/// Severity of opened incidents
+	severity: IncidentSeverity,
+	/// Deduplication key template
+	dedup_key_template: String,
+	/// Delay after which opened incidents are resolved
+	auto_resolve_after: Option<Duration>,
+}
+
+impl PagerDutyNotifier {
+	/// Formats a message by substituting variables in the template
+	///
+	/// # Arguments
+	/// * `variables` - Map of variable names to values
+	///
+	/// # Returns
+	/// * `String` - Formatted message with variables replaced
+	pub fn format_message(&self, variables: &HashMap<String, String>) -> String {
+		self.inner.format_message(variables)
+	}
+
+	/// Renders the deduplication key of a match
+	///
+	/// # Arguments
+	/// * `variables` - Map of variable names to values
+	///
+	/// # Returns
+	/// * `String` - Deduplication key, truncated to the PagerDuty limit
+	pub fn format_dedup_key(&self, variables: &HashMap<String, String>) -> String {
+		render_template(&self.dedup_key_template, variables)
+			.chars()
+			.take(MAX_DEDUP_KEY_LENGTH)
+			.collect()
+	}
+
+	/// Creates a PagerDuty notifier from a trigger configuration
+	///
+	/// # Arguments
+	/// * `config` - Trigger configuration containing PagerDuty parameters
+	/// * `http_client` - HTTP client with middleware for retries
+	///
+	/// # Returns
+	/// * `Result<Self, NotificationError>` - Notifier instance if config is PagerDuty type
+	pub fn from_config(
+		config: &TriggerTypeConfig,
+		http_client: Arc<ClientWithMiddleware>,
+	) -> Result<Self, NotificationError> {
+		if let TriggerTypeConfig::PagerDuty {
+			routing_key,
+			message,
+			severity,
+			dedup_key,
+			auto_resolve_after_secs,
+			events_url,
+			..
+		} = config
+		{
+			let webhook_config = WebhookConfig {
+				url: events_url
+					.clone()
+					.unwrap_or_else(|| PAGERDUTY_EVENTS_URL.to_string()),
+				url_params: None,
+				title: message.title.clone(),
+				body_template: message.body.clone(),
+				method: Some("POST".to_string()),
+				secret: None,
+				headers: None,
+				payload_fields: None,
+			};
+
+			Ok(Self {
+				inner: WebhookNotifier::new(webhook_config, http_client)?,
+				routing_key: routing_key.as_ref().to_string(),
+				severity: *severity,
+				dedup_key_template: dedup_key
+					.clone()
+					.unwrap_or_else(|| DEFAULT_DEDUP_KEY.to_string()),
+				auto_resolve_after: auto_resolve_after_secs.map(Duration::from_secs),
+			})
+		} else {
+			let msg = format!("Invalid PagerDuty configuration: {:?}", config);
+			Err(NotificationError::config_error(msg, None, None))
+		}
+	}
+
+	/// Opens an incident, or adds the match to the open incident with the same key
+	///
+	/// # Arguments
+	/// * `message` - The formatted message, sent as a custom detail of the incident
+	/// * `dedup_key` - Deduplication key of the incident
+	///
+	/// # Returns
+	/// * `Result<(), NotificationError>` - Success or error
+	pub async fn trigger_incident(
+		&self,
+		message: &str,
+		dedup_key: &str,
+	) -> Result<(), NotificationError> {
+		self.send_event("trigger", Some(dedup_key), Some(message))
+			.await
+	}
+
+	/// Resolves the incident with the given key
+	///
+	/// # Arguments
+	/// * `dedup_key` - Deduplication key of the incident
+	///
+	/// # Returns
+	/// * `Result<(), NotificationError>` - Success or error
+	pub async fn resolve_incident(&self, dedup_key: &str) -> Result<(), NotificationError> {
+		self.send_event("resolve", Some(dedup_key), None).await
+	}
+
+	/// Schedules the resolution of an incident when auto-resolve is configured
+	///
+	/// The resolution replaces the pending one of the incident, so that it is only sent once the
+	/// incident has not been re-triggered for the configured delay.
+	///
+	/// # Arguments
+	/// * `timers` - Pending resolutions shared by all matches
+	/// * `trigger_name` - Name of the trigger opening the incident
+	/// * `dedup_key` - Deduplication key of the incident
+	///
+	/// # Returns
+	/// * `Option<JoinHandle<()>>` - Handle of the scheduled resolution, if any
+	pub fn schedule_resolve(
+		&self,
+		timers: &ResolveTimers,
+		trigger_name: &str,
+		dedup_key: String,
+	) -> Option<JoinHandle<()>> {
+		let delay = self.auto_resolve_after?;
+		let notifier = self.clone();
+		Some(timers.schedule(
+			format!("{}|{}", trigger_name, dedup_key),
+			delay,
+			async move {
+				if let Err(e) = notifier.resolve_incident(&dedup_key).await {
+					tracing::error!("Failed to resolve PagerDuty incident {}: {}", dedup_key, e);
+				}
+			},
+		))
+	}
+
+	/// Sends an event to the Events API
+	///
+	/// # Arguments
+	/// * `event_action` - `trigger` or `resolve`
+	/// * `dedup_key` - Deduplication key, generated by PagerDuty when absent
+	/// * `message` - The formatted message, required to trigger an incident
+	///
+	/// # Returns
+	/// * `Result<(), NotificationError>` - Success or error
+	async fn send_event(
+		&self,
+		event_action: &str,
+		dedup_key: Option<&str>,
+		message: Option<&str>,
+	) -> Result<(), NotificationError> {
+		let mut payload_fields = HashMap::from([
+			(
+				"routing_key".to_string(),
+				serde_json::json!(self.routing_key),
+			),
+			("event_action".to_string(), serde_json::json!(event_action)),
+		]);
+		if let Some(dedup_key) = dedup_key {
+			payload_fields.insert("dedup_key".to_string(), serde_json::json!(dedup_key));
+		}
+		if let Some(message) = message {
+			let summary = self
+				.inner
+				.title
+				.chars()
+				.take(MAX_SUMMARY_LENGTH)
+				.collect::<String>();
+			payload_fields.insert(
+				"payload".to_string(),
+				serde_json::json!({
+					"summary": summary,
+					"source": EVENT_SOURCE,
+					"severity": self.severity.pagerduty_severity(),
+					"custom_details": { "body": message },
+				}),
+			);
+		}
+
+		self.inner
+			.notify_with_payload(message.unwrap_or_default(), payload_fields)
+			.await
+	}
+}
+
+#[async_trait]
+impl Notifier for PagerDutyNotifier {
+	/// Opens a PagerDuty incident with a key generated by PagerDuty
+	///
+	/// # Arguments
+	/// * `message` - The formatted message to send
+	///
+	/// # Returns
+	/// * `Result<(), NotificationError>` - Success or error
+	async fn notify(&self, message: &str) -> Result<(), NotificationError> {
+		self.send_event("trigger", None, Some(message)).await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		models::{NotificationMessage, SecretString, SecretValue},
+		utils::{tests::create_test_http_client, HttpRetryConfig},
+	};
+
+	use super::*;
+	use mockito::Matcher;
+	use serde_json::json;
+
+	fn create_test_pagerduty_config(
+		events_url: &str,
+		auto_resolve_after_secs: Option<u64>,
+	) -> TriggerTypeConfig {
+		TriggerTypeConfig::PagerDuty {
+			routing_key: SecretValue::Plain(SecretString::new("routing-key".to_string())),
+			message: NotificationMessage {
+				title: "Ownership transferred".to_string(),
+				body: "New owner ${owner}".to_string(),
+			},
+			severity: IncidentSeverity::Warning,
+			dedup_key: None,
+			auto_resolve_after_secs,
+			events_url: Some(events_url.to_string()),
+			retry_policy: HttpRetryConfig::default(),
+		}
+	}
+
+	#[test]
+	fn test_from_config_invalid_type() {
+		let config = TriggerTypeConfig::Discord {
+			discord_url: SecretValue::Plain(SecretString::new(
+				"https://discord.example.com".to_string(),
+			)),
+			message: NotificationMessage {
+				title: "Test Alert".to_string(),
+				body: "Test message".to_string(),
+			},
+			explorer_url: None,
+			embeds: None,
+			retry_policy: HttpRetryConfig::default(),
+		};
+
+		let notifier = PagerDutyNotifier::from_config(&config, create_test_http_client());
+		assert!(matches!(notifier, Err(NotificationError::ConfigError(_))));
+	}
+
+	#[test]
+	fn test_format_dedup_key() {
+		let config = create_test_pagerduty_config(PAGERDUTY_EVENTS_URL, None);
+		let notifier = PagerDutyNotifier::from_config(&config, create_test_http_client()).unwrap();
+
+		let variables = HashMap::from([
+			("monitor.name".to_string(), "Ownership".to_string()),
+			("transaction.hash".to_string(), "0xabc".to_string()),
+		]);
+		assert_eq!(notifier.format_dedup_key(&variables), "Ownership:0xabc");
+
+		let long_variables =
+			HashMap::from([("monitor.name".to_string(), "a".repeat(MAX_DEDUP_KEY_LENGTH))]);
+		assert_eq!(
+			notifier.format_dedup_key(&long_variables).len(),
+			MAX_DEDUP_KEY_LENGTH
+		);
+	}
+
+	#[tokio::test]
+	async fn test_trigger_incident() {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server
+			.mock("POST", "/")
+			.match_body(Matcher::Json(json!({
+				"routing_key": "routing-key",
+				"event_action": "trigger",
+				"dedup_key": "Ownership:0xabc",
+				"payload": {
+					"summary": "Ownership transferred",
+					"source": "openzeppelin-monitor",
+					"severity": "warning",
+					"custom_details": { "body": "New owner 0x1" }
+				}
+			})))
+			.with_status(202)
+			.create_async()
+			.await;
+
+		let config = create_test_pagerduty_config(&server.url(), None);
+		let notifier = PagerDutyNotifier::from_config(&config, create_test_http_client()).unwrap();
+		let message =
+			notifier.format_message(&HashMap::from([("owner".to_string(), "0x1".to_string())]));
+
+		let result = notifier.trigger_incident(&message, "Ownership:0xabc").await;
+		assert!(result.is_ok());
+		assert!(notifier
+			.schedule_resolve(
+				&ResolveTimers::new(),
+				"pagerduty",
+				"Ownership:0xabc".to_string()
+			)
+			.is_none());
+		mock.assert();
+	}
+
+	#[tokio::test]
+	async fn test_schedule_resolve() {
+		let mut server = mockito::Server::new_async().await;
+		let mock = server
+			.mock("POST", "/")
+			.match_body(Matcher::Json(json!({
+				"routing_key": "routing-key",
+				"event_action": "resolve",
+				"dedup_key": "Ownership:0xabc"
+			})))
+			.with_status(202)
+			.create_async()
+			.await;
+
+		let config = create_test_pagerduty_config(&server.url(), Some(0));
+		let notifier = PagerDutyNotifier::from_config(&config, create_test_http_client()).unwrap();
+
+		let handle = notifier
+			.schedule_resolve(
+				&ResolveTimers::new(),
+				"pagerduty",
+				"Ownership:0xabc".to_string(),
+			)
+			.unwrap();
+		handle.await.unwrap();
+		mock.assert();
+	}
+}
@@ -0,0 +1,58 @@
+//! Native notifier plugins.
+//!
+//! Applications embedding the monitor as a library register notifier plugins on the
+//! `NotificationService` at startup. Triggers of type `plugin` reference them by name, and
+//! their matches are handed to the plugin instead of a built-in channel.
+
+use async_trait::async_trait;
+use std::{
+	collections::HashMap,
+	sync::{Arc, RwLock},
+};
+
+use crate::{models::MonitorMatch, services::notification::NotificationError};
+
+/// Interface for custom notifier types written in Rust
+#[async_trait]
+pub trait NotifierPlugin: Send + Sync {
+	/// Sends a notification for a match
+	///
+	/// # Arguments
+	/// * `options` - Options of the plugin in the trigger configuration
+	/// * `variables` - Variables of the match, as substituted in message templates
+	/// * `monitor_match` - Match being notified
+	///
+	/// # Returns
+	/// * `Result<(), NotificationError>` - Success or error
+	async fn notify(
+		&self,
+		options: &serde_json::Map<String, serde_json::Value>,
+		variables: &HashMap<String, String>,
+		monitor_match: &MonitorMatch,
+	) -> Result<(), NotificationError>;
+}
+
+/// Notifier plugins keyed by the name triggers reference them with
+#[derive(Default)]
+pub struct NotifierPluginRegistry {
+	plugins: RwLock<HashMap<String, Arc<dyn NotifierPlugin>>>,
+}
+
+impl NotifierPluginRegistry {
+	/// Registers a plugin, replacing any plugin previously registered with the same name
+	pub fn register(&self, name: impl Into<String>, plugin: Arc<dyn NotifierPlugin>) {
+		self.plugins
+			.write()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+			.insert(name.into(), plugin);
+	}
+
+	/// Returns the plugin registered with a name, if any
+	pub fn get(&self, name: &str) -> Option<Arc<dyn NotifierPlugin>> {
+		self.plugins
+			.read()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+			.get(name)
+			.cloned()
+	}
+}
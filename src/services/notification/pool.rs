@@ -1,5 +1,8 @@
+use crate::models::TriggerTypeConfig;
 use crate::services::blockchain::TransientErrorRetryStrategy;
-use crate::services::notification::SmtpConfig;
+#[cfg(feature = "database")]
+use crate::services::notification::{create_database_sink, DatabaseSink};
+use crate::services::notification::{create_queue_publisher, QueuePublisher, SmtpConfig};
 use crate::utils::client_storage::ClientStorage;
 use crate::utils::{create_retryable_http_client, HttpRetryConfig};
 use lettre::transport::smtp::authentication::Credentials;
@@ -17,9 +20,16 @@ pub enum NotificationPoolError {
 
 	#[error("Failed to create SMTP client: {0}")]
 	SmtpClientBuildError(String),
+
+	#[error("Failed to create queue client: {0}")]
+	QueueClientBuildFailed(String),
+
+	#[error("Failed to create database client: {0}")]
+	DatabaseClientBuildFailed(String),
 }
 
-/// Notification client pool that manages HTTP and SMTP clients for sending notifications.
+/// Notification client pool that manages HTTP, SMTP, queue and database clients for sending
+/// notifications.
 ///
 /// Provides a thread-safe way to access and create HTTP and SMTP clients
 /// for sending notifications. It uses a `ClientStorage` to hold the clients,
@@ -27,6 +37,9 @@ pub enum NotificationPoolError {
 pub struct NotificationClientPool {
 	http_clients: ClientStorage<ClientWithMiddleware>,
 	smtp_clients: ClientStorage<SmtpTransport>,
+	queue_clients: ClientStorage<dyn QueuePublisher>,
+	#[cfg(feature = "database")]
+	database_clients: ClientStorage<DatabaseSink>,
 }
 
 impl NotificationClientPool {
@@ -34,6 +47,9 @@ impl NotificationClientPool {
 		Self {
 			http_clients: ClientStorage::new(),
 			smtp_clients: ClientStorage::new(),
+			queue_clients: ClientStorage::new(),
+			#[cfg(feature = "database")]
+			database_clients: ClientStorage::new(),
 		}
 	}
 
@@ -126,6 +142,81 @@ impl NotificationClientPool {
 		Ok(arc_client)
 	}
 
+	/// Get or create a message queue client for publishing matches.
+	///
+	/// # Arguments
+	/// * `config` - Trigger configuration of a queue trigger, identifying the broker and
+	///   destination of the client.
+	/// # Returns
+	/// * `Result<Arc<dyn QueuePublisher>, NotificationPoolError>` - The queue client
+	///   wrapped in an `Arc` for shared ownership, or an error if client creation
+	///   fails.
+	pub async fn get_or_create_queue_publisher(
+		&self,
+		config: &TriggerTypeConfig,
+	) -> Result<Arc<dyn QueuePublisher>, NotificationPoolError> {
+		// Generate a unique key for the client based on the trigger configuration.
+		let key = format!("{:?}", config);
+
+		// Fast path: Read lock to check for an existing client.
+		if let Some(client) = self.queue_clients.clients.read().await.get(&key) {
+			return Ok(client.clone());
+		}
+
+		// Slow path: Write lock to create a new client if needed.
+		let mut clients = self.queue_clients.clients.write().await;
+		// Double-check in case another thread created it while we waited for the lock.
+		if let Some(client) = clients.get(&key) {
+			return Ok(client.clone());
+		}
+
+		// Clients connect on first use, so creating one does not reach the broker.
+		let client = create_queue_publisher(config)
+			.map_err(|e| NotificationPoolError::QueueClientBuildFailed(e.to_string()))?;
+		clients.insert(key, client.clone());
+
+		Ok(client)
+	}
+
+	/// Get or create a database client for writing matches.
+	///
+	/// # Arguments
+	/// * `config` - Trigger configuration of a database trigger, identifying the database and
+	///   table of the client.
+	/// # Returns
+	/// * `Result<Arc<DatabaseSink>, NotificationPoolError>` - The database client
+	///   wrapped in an `Arc` for shared ownership, or an error if client creation
+	///   fails.
+	#[cfg(feature = "database")]
+	pub async fn get_or_create_database_sink(
+		&self,
+		config: &TriggerTypeConfig,
+	) -> Result<Arc<DatabaseSink>, NotificationPoolError> {
+		// Generate a unique key for the client based on the trigger configuration.
+		let key = format!("{:?}", config);
+
+		// Fast path: Read lock to check for an existing client.
+		if let Some(client) = self.database_clients.clients.read().await.get(&key) {
+			return Ok(client.clone());
+		}
+
+		// Slow path: Write lock to create a new client if needed.
+		let mut clients = self.database_clients.clients.write().await;
+		// Double-check in case another thread created it while we waited for the lock.
+		if let Some(client) = clients.get(&key) {
+			return Ok(client.clone());
+		}
+
+		// Clients connect on first use, so creating one does not reach the database.
+		let client = Arc::new(
+			create_database_sink(config)
+				.map_err(|e| NotificationPoolError::DatabaseClientBuildFailed(e.to_string()))?,
+		);
+		clients.insert(key, client.clone());
+
+		Ok(client)
+	}
+
 	/// Get the number of active HTTP clients in the pool
 	#[cfg(test)]
 	pub async fn get_active_http_client_count(&self) -> usize {
@@ -137,6 +228,18 @@ impl NotificationClientPool {
 	pub async fn get_active_smtp_client_count(&self) -> usize {
 		self.smtp_clients.clients.read().await.len()
 	}
+
+	/// Get the number of active queue clients in the pool
+	#[cfg(test)]
+	pub async fn get_active_queue_client_count(&self) -> usize {
+		self.queue_clients.clients.read().await.len()
+	}
+
+	/// Get the number of active database clients in the pool
+	#[cfg(all(test, feature = "database"))]
+	pub async fn get_active_database_client_count(&self) -> usize {
+		self.database_clients.clients.read().await.len()
+	}
 }
 
 impl Default for NotificationClientPool {
@@ -148,6 +251,7 @@ impl Default for NotificationClientPool {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::utils::tests::builders::trigger::TriggerBuilder;
 
 	fn create_pool() -> NotificationClientPool {
 		NotificationClientPool::new()
@@ -369,4 +473,81 @@ mod tests {
 			"Pool should still have two active SMTP clients after getting an existing one"
 		);
 	}
+
+	#[cfg(feature = "redis")]
+	#[tokio::test]
+	async fn test_pool_returns_different_queue_clients_for_different_configs() {
+		let pool = create_pool();
+		let config_1 = TriggerBuilder::new()
+			.redis("redis://localhost:6379", "matches")
+			.build()
+			.config;
+		let config_2 = TriggerBuilder::new()
+			.redis("redis://localhost:6379", "other_matches")
+			.build()
+			.config;
+
+		let client1 = pool.get_or_create_queue_publisher(&config_1).await.unwrap();
+		let client2 = pool.get_or_create_queue_publisher(&config_2).await.unwrap();
+		let client1_again = pool.get_or_create_queue_publisher(&config_1).await.unwrap();
+
+		assert!(
+			!Arc::ptr_eq(&client1, &client2),
+			"Should return different client instances for different configurations"
+		);
+		assert!(
+			Arc::ptr_eq(&client1, &client1_again),
+			"Should return the same client instance when called again with the same config"
+		);
+		assert_eq!(
+			pool.get_active_queue_client_count().await,
+			2,
+			"Pool should have two active queue clients"
+		);
+	}
+
+	#[tokio::test]
+	async fn test_pool_queue_client_invalid_config() {
+		let pool = create_pool();
+		let config = TriggerBuilder::new()
+			.redis("localhost:6379", "matches")
+			.build()
+			.config;
+
+		let result = pool.get_or_create_queue_publisher(&config).await;
+		assert!(matches!(
+			result,
+			Err(NotificationPoolError::QueueClientBuildFailed(_))
+		));
+		assert_eq!(pool.get_active_queue_client_count().await, 0);
+	}
+
+	#[cfg(feature = "sqlite")]
+	#[tokio::test]
+	async fn test_pool_reuses_database_clients() {
+		let pool = create_pool();
+		let config = TriggerBuilder::new()
+			.database("sqlite::memory:", "transfers")
+			.build()
+			.config;
+
+		let client1 = pool.get_or_create_database_sink(&config).await.unwrap();
+		let client2 = pool.get_or_create_database_sink(&config).await.unwrap();
+		assert!(Arc::ptr_eq(&client1, &client2));
+		assert_eq!(pool.get_active_database_client_count().await, 1);
+
+		let result = pool
+			.get_or_create_database_sink(
+				&TriggerBuilder::new()
+					.database("localhost:5432", "transfers")
+					.build()
+					.config,
+			)
+			.await;
+		assert!(matches!(
+			result,
+			Err(NotificationPoolError::DatabaseClientBuildFailed(_))
+		));
+		assert_eq!(pool.get_active_database_client_count().await, 1);
+	}
 }
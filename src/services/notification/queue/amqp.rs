@@ -0,0 +1,137 @@
+//! AMQP publisher implementation.
+//!
+//! Messages are published as persistent and mandatory on a channel in confirm mode, so that
+//! publishing only succeeds once the broker has routed and stored the message.
+
+use async_trait::async_trait;
+use lapin::{
+	options::{BasicPublishOptions, ConfirmSelectOptions},
+	publisher_confirm::Confirmation,
+	types::{AMQPValue, FieldTable},
+	BasicProperties, Channel, Connection, ConnectionProperties,
+};
+use tokio::sync::Mutex;
+
+use crate::services::notification::{
+	queue::{QueueMessage, QueuePublisher, PARTITION_KEY_HEADER, QUEUE_CONTENT_TYPE},
+	NotificationError,
+};
+
+/// Delivery mode of messages persisted by the broker
+const PERSISTENT_DELIVERY_MODE: u8 = 2;
+
+/// AMQP client publishing to an exchange
+pub struct AmqpPublisher {
+	/// AMQP broker URL
+	url: String,
+	/// Exchange messages are published to
+	exchange: String,
+	/// Routing key, defaults to the partition key of the message
+	routing_key: Option<String>,
+	/// Open connection and confirm-mode channel, reopened once closed
+	channel: Mutex<Option<(Connection, Channel)>>,
+}
+
+impl AmqpPublisher {
+	/// Creates a new AMQP publisher
+	///
+	/// # Arguments
+	/// * `url` - AMQP broker URL
+	/// * `exchange` - Exchange messages are published to
+	/// * `routing_key` - Routing key, defaults to the partition key of the message
+	pub fn new(url: String, exchange: String, routing_key: Option<String>) -> Self {
+		Self {
+			url,
+			exchange,
+			routing_key,
+			channel: Mutex::new(None),
+		}
+	}
+
+	/// Returns the publishing channel, connecting to the broker if needed
+	async fn channel(&self) -> Result<Channel, NotificationError> {
+		let mut channel = self.channel.lock().await;
+		if let Some((_, open_channel)) = channel
+			.as_ref()
+			.filter(|(_, open_channel)| open_channel.status().connected())
+		{
+			return Ok(open_channel.clone());
+		}
+
+		let network_error = |e: lapin::Error| {
+			NotificationError::network_error(
+				format!("Failed to connect to AMQP broker: {}", e),
+				Some(e.into()),
+				None,
+			)
+		};
+		let connection = Connection::connect(&self.url, ConnectionProperties::default())
+			.await
+			.map_err(network_error)?;
+		let new_channel = connection.create_channel().await.map_err(network_error)?;
+		new_channel
+			.confirm_select(ConfirmSelectOptions::default())
+			.await
+			.map_err(network_error)?;
+
+		*channel = Some((connection, new_channel.clone()));
+		Ok(new_channel)
+	}
+}
+
+#[async_trait]
+impl QueuePublisher for AmqpPublisher {
+	async fn publish(&self, message: &QueueMessage) -> Result<(), NotificationError> {
+		let publish_error = |reason: String, source: Option<lapin::Error>| {
+			NotificationError::notify_failed(
+				format!(
+					"Failed to publish to AMQP exchange {}: {}",
+					self.exchange, reason
+				),
+				source.map(|e| e.into()),
+				None,
+			)
+		};
+
+		let mut headers = FieldTable::default();
+		headers.insert(
+			PARTITION_KEY_HEADER.into(),
+			AMQPValue::LongString(message.key.as_str().into()),
+		);
+		let properties = BasicProperties::default()
+			.with_content_type(QUEUE_CONTENT_TYPE.into())
+			.with_delivery_mode(PERSISTENT_DELIVERY_MODE)
+			.with_message_id(message.id.as_str().into())
+			.with_headers(headers);
+
+		let confirmation = self
+			.channel()
+			.await?
+			.basic_publish(
+				&self.exchange,
+				self.routing_key.as_deref().unwrap_or(&message.key),
+				BasicPublishOptions {
+					mandatory: true,
+					..Default::default()
+				},
+				&message.payload,
+				properties,
+			)
+			.await
+			.map_err(|e| publish_error(e.to_string(), Some(e)))?
+			.await
+			.map_err(|e| publish_error(e.to_string(), Some(e)))?;
+
+		match confirmation {
+			Confirmation::Ack(None) | Confirmation::NotRequested => Ok(()),
+			Confirmation::Ack(Some(_)) => Err(publish_error(
+				"message could not be routed to a queue".to_string(),
+				None,
+			)),
+			Confirmation::Nack(_) => Err(publish_error(
+				"message was rejected by the broker".to_string(),
+				None,
+			)),
+		}
+	}
+}
@@ -0,0 +1,108 @@
+//! Kafka publisher implementation.
+//!
+//! Messages are keyed by their partition key, so that the matches of a monitor on a network land
+//! on the same partition. The producer is idempotent and waits for all in-sync replicas, which
+//! keeps messages ordered within a partition across internal retries.
+
+use async_trait::async_trait;
+use rdkafka::{
+	message::{Header, OwnedHeaders},
+	producer::{FutureProducer, FutureRecord},
+	util::Timeout,
+	ClientConfig,
+};
+use std::{collections::HashMap, time::Duration};
+
+use crate::services::notification::{
+	queue::{QueueMessage, QueuePublisher, MESSAGE_ID_HEADER},
+	NotificationError,
+};
+
+/// Time after which an unacknowledged message is reported as failed
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Kafka client publishing to a topic
+pub struct KafkaPublisher {
+	producer: FutureProducer,
+	/// Topic messages are published to
+	topic: String,
+}
+
+impl KafkaPublisher {
+	/// Creates a new Kafka publisher
+	///
+	/// The producer connects to the brokers in the background.
+	///
+	/// # Arguments
+	/// * `brokers` - Comma-separated list of bootstrap servers
+	/// * `topic` - Topic messages are published to
+	/// * `properties` - Additional librdkafka producer properties
+	///
+	/// # Returns
+	/// * `Result<Self, NotificationError>` - Publisher, or error if the properties are invalid
+	pub fn new(
+		brokers: &str,
+		topic: String,
+		properties: &HashMap<String, String>,
+	) -> Result<Self, NotificationError> {
+		let mut client_config = ClientConfig::new();
+		client_config
+			.set("bootstrap.servers", brokers)
+			.set("acks", "all")
+			.set("enable.idempotence", "true")
+			.set(
+				"message.timeout.ms",
+				DELIVERY_TIMEOUT.as_millis().to_string(),
+			);
+		for (name, value) in properties {
+			client_config.set(name, value);
+		}
+
+		let producer = client_config.create().map_err(|e| {
+			NotificationError::config_error(
+				format!("Failed to create Kafka producer: {}", e),
+				Some(e.into()),
+				None,
+			)
+		})?;
+
+		Ok(Self { producer, topic })
+	}
+}
+
+#[async_trait]
+impl QueuePublisher for KafkaPublisher {
+	async fn publish(&self, message: &QueueMessage) -> Result<(), NotificationError> {
+		let record = FutureRecord::to(&self.topic)
+			.key(&message.key)
+			.payload(&message.payload)
+			.headers(OwnedHeaders::new().insert(Header {
+				key: MESSAGE_ID_HEADER,
+				value: Some(&message.id),
+			}));
+
+		self.producer
+			.send(record, Timeout::After(DELIVERY_TIMEOUT))
+			.await
+			.map(|_| ())
+			.map_err(|(e, _)| {
+				NotificationError::notify_failed(
+					format!("Failed to publish to Kafka topic {}: {}", self.topic, e),
+					Some(e.into()),
+					None,
+				)
+			})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_new_with_invalid_property() {
+		let properties = HashMap::from([("unknown.property".to_string(), "1".to_string())]);
+		let result = KafkaPublisher::new("localhost:9092", "matches".to_string(), &properties);
+		assert!(matches!(result, Err(NotificationError::ConfigError(_))));
+	}
+}
@@ -0,0 +1,546 @@
+//! Message queue notification implementation.
+//!
+//! Publishes monitor matches to Kafka topics, NATS JetStream subjects, Redis streams and AMQP
+//! exchanges. Messages contain the versioned match document of webhook triggers and are keyed by
+//! the network and monitor, so that brokers keep the matches of a monitor on a network in order.
+//!
+//! Publishing only succeeds once the broker has acknowledged the message, and is retried
+//! according to the trigger retry policy. Since a retried message may already have been stored,
+//! delivery is at-least-once: every message carries an identifier for consumers to deduplicate.
+//!
+//! Messages which still cannot be published are kept in a persistent outbox, and published
+//! again, in order, before the next message of the trigger. Outboxes are also drained at startup
+//! and periodically, so that the messages of triggers without new matches are delivered too.
+//!
+//! Each broker client is only compiled with its cargo feature: `kafka`, `nats`, `redis` and
+//! `amqp`, all enabled by default.
+
+use async_trait::async_trait;
+use std::{sync::Arc, time::Duration};
+
+use crate::{
+	models::{MonitorMatch, TriggerTypeConfig},
+	services::notification::{MatchPayload, NotificationError},
+	utils::HttpRetryConfig,
+};
+
+#[cfg(feature = "amqp")]
+mod amqp;
+#[cfg(feature = "kafka")]
+mod kafka;
+#[cfg(feature = "nats")]
+mod nats;
+mod outbox;
+#[cfg(feature = "redis")]
+mod redis;
+
+#[cfg(feature = "redis")]
+pub use self::redis::RedisPublisher;
+#[cfg(feature = "amqp")]
+pub use amqp::AmqpPublisher;
+#[cfg(feature = "kafka")]
+pub use kafka::KafkaPublisher;
+#[cfg(feature = "nats")]
+pub use nats::NatsPublisher;
+pub use outbox::{PendingMessages, QueueOutbox, QueueOutboxes, DEFAULT_QUEUE_OUTBOX_DIR};
+
+/// Content type of published messages
+pub const QUEUE_CONTENT_TYPE: &str = "application/json";
+
+/// Name of the header carrying the message identifier
+pub const MESSAGE_ID_HEADER: &str = "message-id";
+
+/// Name of the header carrying the partition key
+pub const PARTITION_KEY_HEADER: &str = "partition-key";
+
+/// A monitor match serialized for publishing
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueueMessage {
+	/// Partition key, made of the network slug and monitor name
+	pub key: String,
+	/// Identifier of the match, shared by redeliveries of the same message
+	pub id: String,
+	/// JSON match document
+	pub payload: Vec<u8>,
+}
+
+impl QueueMessage {
+	/// Serializes a monitor match into a message
+	///
+	/// # Arguments
+	/// * `monitor_match` - Monitor match to publish
+	///
+	/// # Returns
+	/// * `Result<Self, NotificationError>` - Message or serialization error
+	pub fn from_match(monitor_match: &MonitorMatch) -> Result<Self, NotificationError> {
+		let payload = MatchPayload::new(monitor_match);
+		let key = format!("{}:{}", payload.network, payload.monitor);
+		let transaction_hash = match monitor_match {
+			MonitorMatch::EVM(evm_match) => evm_match.transaction.hash().to_string(),
+			MonitorMatch::Stellar(stellar_match) => stellar_match.transaction.hash().clone(),
+			MonitorMatch::Poll(poll_match) => poll_match.poll_id(),
+		};
+
+		Ok(Self {
+			id: format!("{}:{}", key, transaction_hash),
+			payload: serde_json::to_vec(&payload).map_err(|e| {
+				NotificationError::internal_error(
+					format!("Failed to serialize monitor match: {}", e),
+					Some(e.into()),
+					None,
+				)
+			})?,
+			key,
+		})
+	}
+}
+
+/// Interface for message queue clients
+///
+/// Implementations connect lazily so that an unavailable broker only fails the publishing of
+/// a match, and reconnect on the next attempt.
+#[async_trait]
+pub trait QueuePublisher: Send + Sync {
+	/// Publishes a message, returning once the broker has acknowledged it
+	///
+	/// # Arguments
+	/// * `message` - Message to publish
+	///
+	/// # Returns
+	/// * `Result<(), NotificationError>` - Success or error
+	async fn publish(&self, message: &QueueMessage) -> Result<(), NotificationError>;
+}
+
+/// Creates the message queue client of a trigger configuration
+///
+/// # Arguments
+/// * `config` - Trigger configuration containing queue parameters
+///
+/// # Returns
+/// * `Result<Arc<dyn QueuePublisher>, NotificationError>` - Client if config is a queue type
+pub fn create_queue_publisher(
+	config: &TriggerTypeConfig,
+) -> Result<Arc<dyn QueuePublisher>, NotificationError> {
+	if let Some(feature) = config.disabled_feature() {
+		let msg = format!(
+			"Queue trigger requires the monitor to be built with the `{}` feature",
+			feature
+		);
+		return Err(NotificationError::config_error(msg, None, None));
+	}
+
+	match config {
+		#[cfg(feature = "kafka")]
+		TriggerTypeConfig::Kafka {
+			brokers,
+			topic,
+			properties,
+			..
+		} => {
+			let properties = properties
+				.iter()
+				.flatten()
+				.map(|(name, value)| (name.clone(), value.as_ref().to_string()))
+				.collect();
+			Ok(Arc::new(KafkaPublisher::new(
+				brokers,
+				topic.clone(),
+				&properties,
+			)?))
+		}
+		#[cfg(feature = "nats")]
+		TriggerTypeConfig::Nats {
+			nats_url, subject, ..
+		} => Ok(Arc::new(NatsPublisher::new(
+			nats_url.as_ref().to_string(),
+			subject.clone(),
+		))),
+		#[cfg(feature = "redis")]
+		TriggerTypeConfig::Redis {
+			redis_url,
+			stream,
+			max_len,
+			..
+		} => Ok(Arc::new(RedisPublisher::new(
+			redis_url.as_ref(),
+			stream.clone(),
+			*max_len,
+		)?)),
+		#[cfg(feature = "amqp")]
+		TriggerTypeConfig::Amqp {
+			amqp_url,
+			exchange,
+			routing_key,
+			..
+		} => Ok(Arc::new(AmqpPublisher::new(
+			amqp_url.as_ref().to_string(),
+			exchange.clone(),
+			routing_key.clone(),
+		))),
+		_ => {
+			let msg = format!("Invalid queue configuration: {:?}", config);
+			Err(NotificationError::config_error(msg, None, None))
+		}
+	}
+}
+
+/// Implementation of message queue notifications
+pub struct QueueNotifier {
+	/// Client of the message queue
+	publisher: Arc<dyn QueuePublisher>,
+	/// Retry policy for publishing
+	retry_policy: HttpRetryConfig,
+}
+
+impl QueueNotifier {
+	/// Creates a new queue notifier instance
+	///
+	/// # Arguments
+	/// * `publisher` - Client of the message queue
+	/// * `retry_policy` - Retry policy for publishing
+	pub fn new(publisher: Arc<dyn QueuePublisher>, retry_policy: HttpRetryConfig) -> Self {
+		Self {
+			publisher,
+			retry_policy,
+		}
+	}
+
+	/// Creates a queue notifier from a trigger configuration
+	///
+	/// # Arguments
+	/// * `config` - Trigger configuration containing queue parameters
+	/// * `publisher` - Client of the message queue
+	///
+	/// # Returns
+	/// * `Result<Self, NotificationError>` - Notifier instance if config is a queue type
+	pub fn from_config(
+		config: &TriggerTypeConfig,
+		publisher: Arc<dyn QueuePublisher>,
+	) -> Result<Self, NotificationError> {
+		match config {
+			TriggerTypeConfig::Kafka { retry_policy, .. }
+			| TriggerTypeConfig::Nats { retry_policy, .. }
+			| TriggerTypeConfig::Redis { retry_policy, .. }
+			| TriggerTypeConfig::Amqp { retry_policy, .. } => Ok(Self::new(publisher, retry_policy.clone())),
+			_ => {
+				let msg = format!("Invalid queue configuration: {:?}", config);
+				Err(NotificationError::config_error(msg, None, None))
+			}
+		}
+	}
+
+	/// Serializes a monitor match into a message
+	///
+	/// # Arguments
+	/// * `monitor_match` - Monitor match to publish
+	///
+	/// # Returns
+	/// * `Result<QueueMessage, NotificationError>` - Message or serialization error
+	pub fn format_message(
+		&self,
+		monitor_match: &MonitorMatch,
+	) -> Result<QueueMessage, NotificationError> {
+		QueueMessage::from_match(monitor_match)
+	}
+
+	/// Publishes a message, retrying with exponential backoff until it is acknowledged
+	///
+	/// # Arguments
+	/// * `message` - Message to publish
+	///
+	/// # Returns
+	/// * `Result<(), NotificationError>` - Success, or the error of the last attempt
+	pub async fn publish(&self, message: &QueueMessage) -> Result<(), NotificationError> {
+		let mut attempt = 0;
+		loop {
+			match self.publisher.publish(message).await {
+				Ok(()) => return Ok(()),
+				Err(e) if attempt >= self.retry_policy.max_retries => return Err(e),
+				Err(e) => {
+					tracing::warn!(
+						"Failed to publish message {} (attempt {}): {}",
+						message.id,
+						attempt + 1,
+						e
+					);
+					tokio::time::sleep(self.backoff(attempt)).await;
+					attempt += 1;
+				}
+			}
+		}
+	}
+
+	/// Publishes a message after the pending messages of an outbox
+	///
+	/// Pending messages are published first, in order. If a message cannot be published, it and
+	/// every following message are kept in the outbox for the next delivery, and the error is
+	/// returned.
+	///
+	/// # Arguments
+	/// * `message` - Message to publish
+	/// * `outbox` - Outbox of the trigger
+	///
+	/// # Returns
+	/// * `Result<(), NotificationError>` - Success, or the error of the failed publishing
+	pub async fn deliver(
+		&self,
+		message: QueueMessage,
+		outbox: &QueueOutbox,
+	) -> Result<(), NotificationError> {
+		let mut pending = outbox.lock().await?;
+		if pending.is_empty() {
+			return match self.publish(&message).await {
+				Ok(()) => Ok(()),
+				Err(e) => {
+					pending.push_back(message);
+					pending.persist().await?;
+					Err(e)
+				}
+			};
+		}
+
+		pending.push_back(message);
+		self.publish_pending(&mut pending).await.map(|_| ())
+	}
+
+	/// Publishes the pending messages of an outbox, in order
+	///
+	/// If a message cannot be published, it and every following message are kept in the outbox,
+	/// and the error is returned.
+	///
+	/// # Arguments
+	/// * `outbox` - Outbox of the trigger
+	///
+	/// # Returns
+	/// * `Result<usize, NotificationError>` - Number of messages published, or the error of the
+	///   failed publishing
+	pub async fn drain(&self, outbox: &QueueOutbox) -> Result<usize, NotificationError> {
+		let mut pending = outbox.lock().await?;
+		if pending.is_empty() {
+			return Ok(0);
+		}
+		self.publish_pending(&mut pending).await
+	}
+
+	/// Publishes pending messages until one fails, and persists the remaining ones
+	async fn publish_pending(
+		&self,
+		pending: &mut PendingMessages<'_>,
+	) -> Result<usize, NotificationError> {
+		let mut published = 0;
+		let mut result = Ok(());
+		while let Some(next) = pending.front() {
+			if let Err(e) = self.publish(&next).await {
+				result = Err(e);
+				break;
+			}
+			pending.pop_front();
+			published += 1;
+		}
+		pending.persist().await?;
+		if result.is_err() {
+			tracing::warn!("{} queue messages kept in outbox", pending.len());
+		}
+		result.map(|()| published)
+	}
+
+	/// Delay before the retry following a failed attempt
+	fn backoff(&self, attempt: u32) -> Duration {
+		self.retry_policy
+			.initial_backoff
+			.saturating_mul(self.retry_policy.base_for_backoff.saturating_pow(attempt))
+			.min(self.retry_policy.max_backoff)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		models::{EVMMonitorMatch, EVMTransactionReceipt, MatchConditions},
+		utils::tests::{
+			builders::evm::monitor::MonitorBuilder, evm::transaction::TransactionBuilder,
+		},
+	};
+	use std::sync::Mutex;
+
+	/// Publisher failing a number of times before acknowledging messages
+	struct FlakyPublisher {
+		failures: Mutex<u32>,
+		published: Mutex<Vec<QueueMessage>>,
+	}
+
+	#[async_trait]
+	impl QueuePublisher for FlakyPublisher {
+		async fn publish(&self, message: &QueueMessage) -> Result<(), NotificationError> {
+			let mut failures = self.failures.lock().unwrap();
+			if *failures > 0 {
+				*failures -= 1;
+				return Err(NotificationError::network_error("broker down", None, None));
+			}
+			self.published.lock().unwrap().push(message.clone());
+			Ok(())
+		}
+	}
+
+	fn create_test_match() -> MonitorMatch {
+		MonitorMatch::EVM(Box::new(EVMMonitorMatch {
+			monitor: MonitorBuilder::new().name("Large Transfer").build(),
+			transaction: TransactionBuilder::new().build(),
+			receipt: Some(EVMTransactionReceipt::default()),
+			logs: Some(vec![]),
+			network_slug: "ethereum_mainnet".to_string(),
+			matched_on: MatchConditions::default(),
+			matched_on_args: None,
+			enrichment: None,
+		}))
+	}
+
+	fn create_test_retry_policy(max_retries: u32) -> HttpRetryConfig {
+		let mut retry_policy = HttpRetryConfig::default();
+		retry_policy.max_retries = max_retries;
+		retry_policy.initial_backoff = Duration::from_millis(1);
+		retry_policy.max_backoff = Duration::from_millis(4);
+		retry_policy
+	}
+
+	fn create_test_message() -> QueueMessage {
+		QueueMessage {
+			key: "ethereum_mainnet:Large Transfer".to_string(),
+			id: "ethereum_mainnet:Large Transfer:0x1".to_string(),
+			payload: b"{}".to_vec(),
+		}
+	}
+
+	#[test]
+	fn test_message_from_match() {
+		let monitor_match = create_test_match();
+		let message = QueueMessage::from_match(&monitor_match).unwrap();
+
+		assert_eq!(message.key, "ethereum_mainnet:Large Transfer");
+		let hash = match &monitor_match {
+			MonitorMatch::EVM(evm_match) => evm_match.transaction.hash().to_string(),
+			_ => unreachable!(),
+		};
+		assert_eq!(
+			message.id,
+			format!("ethereum_mainnet:Large Transfer:{}", hash)
+		);
+
+		let payload: serde_json::Value = serde_json::from_slice(&message.payload).unwrap();
+		assert_eq!(payload["monitor"], "Large Transfer");
+		assert_eq!(payload["network"], "ethereum_mainnet");
+		assert!(payload["match"]["EVM"].is_object());
+	}
+
+	#[tokio::test]
+	async fn test_publish_retries_until_acknowledged() {
+		let publisher = Arc::new(FlakyPublisher {
+			failures: Mutex::new(2),
+			published: Mutex::new(vec![]),
+		});
+		let notifier = QueueNotifier::new(publisher.clone(), create_test_retry_policy(2));
+
+		let result = notifier.publish(&create_test_message()).await;
+		assert!(result.is_ok());
+		assert_eq!(
+			*publisher.published.lock().unwrap(),
+			vec![create_test_message()]
+		);
+	}
+
+	#[tokio::test]
+	async fn test_publish_gives_up_after_max_retries() {
+		let publisher = Arc::new(FlakyPublisher {
+			failures: Mutex::new(3),
+			published: Mutex::new(vec![]),
+		});
+		let notifier = QueueNotifier::new(publisher.clone(), create_test_retry_policy(2));
+
+		let result = notifier.publish(&create_test_message()).await;
+		assert!(matches!(result, Err(NotificationError::NetworkError(_))));
+		assert!(publisher.published.lock().unwrap().is_empty());
+	}
+
+	#[tokio::test]
+	async fn test_deliver_keeps_failed_messages_in_order() {
+		let temp_dir = tempfile::tempdir().unwrap();
+		let outbox = QueueOutbox::new(temp_dir.path().join("outbox.json"));
+		let publisher = Arc::new(FlakyPublisher {
+			failures: Mutex::new(1),
+			published: Mutex::new(vec![]),
+		});
+		let notifier = QueueNotifier::new(publisher.clone(), create_test_retry_policy(0));
+		let message = |id: &str| QueueMessage {
+			id: id.to_string(),
+			..create_test_message()
+		};
+
+		let result = notifier.deliver(message("1"), &outbox).await;
+		assert!(matches!(result, Err(NotificationError::NetworkError(_))));
+		assert!(temp_dir.path().join("outbox.json").exists());
+
+		// The failed message is published before the next one
+		notifier.deliver(message("2"), &outbox).await.unwrap();
+		assert_eq!(
+			*publisher.published.lock().unwrap(),
+			vec![message("1"), message("2")]
+		);
+		assert!(!temp_dir.path().join("outbox.json").exists());
+	}
+
+	#[tokio::test]
+	async fn test_drain_publishes_pending_messages() {
+		let temp_dir = tempfile::tempdir().unwrap();
+		let outbox = QueueOutbox::new(temp_dir.path().join("outbox.json"));
+		let publisher = Arc::new(FlakyPublisher {
+			failures: Mutex::new(1),
+			published: Mutex::new(vec![]),
+		});
+		let notifier = QueueNotifier::new(publisher.clone(), create_test_retry_policy(0));
+
+		assert_eq!(notifier.drain(&outbox).await.unwrap(), 0);
+		assert!(notifier
+			.deliver(create_test_message(), &outbox)
+			.await
+			.is_err());
+
+		// The pending message is published without waiting for another one
+		assert_eq!(notifier.drain(&outbox).await.unwrap(), 1);
+		assert_eq!(
+			*publisher.published.lock().unwrap(),
+			vec![create_test_message()]
+		);
+		assert!(!temp_dir.path().join("outbox.json").exists());
+	}
+
+	#[test]
+	fn test_backoff_is_capped() {
+		let publisher = Arc::new(FlakyPublisher {
+			failures: Mutex::new(0),
+			published: Mutex::new(vec![]),
+		});
+		let notifier = QueueNotifier::new(publisher, create_test_retry_policy(5));
+
+		assert_eq!(notifier.backoff(0), Duration::from_millis(1));
+		assert_eq!(notifier.backoff(1), Duration::from_millis(2));
+		assert_eq!(notifier.backoff(10), Duration::from_millis(4));
+	}
+
+	#[test]
+	fn test_create_queue_publisher_invalid_type() {
+		let config = TriggerTypeConfig::Script {
+			language: crate::models::ScriptLanguage::Bash,
+			script_path: "script.sh".to_string(),
+			arguments: None,
+			timeout_ms: 1000,
+			mode: crate::models::ScriptMode::Process,
+			sandbox: None,
+			interpreter: None,
+		};
+		assert!(matches!(
+			create_queue_publisher(&config),
+			Err(NotificationError::ConfigError(_))
+		));
+	}
+}
@@ -0,0 +1,85 @@
+//! NATS JetStream publisher implementation.
+//!
+//! Messages are published through JetStream, which acknowledges them once they are stored by
+//! the stream capturing the subject. The message identifier is sent as `Nats-Msg-Id`, letting
+//! the stream drop redeliveries within its duplicate window.
+
+use async_nats::{header::NATS_MESSAGE_ID, jetstream, HeaderMap};
+use async_trait::async_trait;
+use tokio::sync::OnceCell;
+
+use crate::services::notification::{
+	queue::{QueueMessage, QueuePublisher, PARTITION_KEY_HEADER},
+	NotificationError,
+};
+
+/// NATS client publishing to a JetStream subject
+pub struct NatsPublisher {
+	/// NATS server URL
+	url: String,
+	/// Subject messages are published to
+	subject: String,
+	/// JetStream context, created on the first publish
+	context: OnceCell<jetstream::Context>,
+}
+
+impl NatsPublisher {
+	/// Creates a new NATS publisher
+	///
+	/// # Arguments
+	/// * `url` - NATS server URL
+	/// * `subject` - Subject messages are published to
+	pub fn new(url: String, subject: String) -> Self {
+		Self {
+			url,
+			subject,
+			context: OnceCell::new(),
+		}
+	}
+
+	/// Returns the JetStream context, connecting to the server if needed
+	async fn context(&self) -> Result<&jetstream::Context, NotificationError> {
+		self.context
+			.get_or_try_init(|| async {
+				let client = async_nats::connect(self.url.as_str()).await.map_err(|e| {
+					NotificationError::network_error(
+						format!("Failed to connect to NATS server: {}", e),
+						Some(e.into()),
+						None,
+					)
+				})?;
+				Ok(jetstream::new(client))
+			})
+			.await
+	}
+}
+
+#[async_trait]
+impl QueuePublisher for NatsPublisher {
+	async fn publish(&self, message: &QueueMessage) -> Result<(), NotificationError> {
+		let mut headers = HeaderMap::new();
+		headers.insert(NATS_MESSAGE_ID, message.id.as_str());
+		headers.insert(PARTITION_KEY_HEADER, message.key.as_str());
+
+		let publish_error = |e: Box<dyn std::error::Error + Send + Sync>| {
+			NotificationError::notify_failed(
+				format!("Failed to publish to NATS subject {}: {}", self.subject, e),
+				Some(e),
+				None,
+			)
+		};
+
+		self.context()
+			.await?
+			.publish_with_headers(
+				self.subject.clone(),
+				headers,
+				message.payload.clone().into(),
+			)
+			.await
+			.map_err(|e| publish_error(e.into()))?
+			.await
+			.map_err(|e| publish_error(e.into()))?;
+		Ok(())
+	}
+}
@@ -0,0 +1,287 @@
+//! Persistent outbox of queue messages.
+//!
+//! Messages which could not be published after all retries are stored on disk, in a file per
+//! trigger, and published again before the next message of the trigger. The outbox therefore
+//! keeps messages in order and survives restarts of the monitor.
+
+use serde::{Deserialize, Serialize};
+use std::{
+	collections::{HashMap, VecDeque},
+	path::PathBuf,
+	sync::{Arc, Mutex},
+};
+
+use crate::services::notification::{queue::QueueMessage, NotificationError};
+
+/// Default directory of the outbox files
+pub const DEFAULT_QUEUE_OUTBOX_DIR: &str = "data/queue_outbox";
+
+/// Queue message as stored in an outbox file
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredMessage {
+	key: String,
+	id: String,
+	payload: String,
+}
+
+impl From<&QueueMessage> for StoredMessage {
+	fn from(message: &QueueMessage) -> Self {
+		Self {
+			key: message.key.clone(),
+			id: message.id.clone(),
+			payload: String::from_utf8_lossy(&message.payload).into_owned(),
+		}
+	}
+}
+
+impl From<StoredMessage> for QueueMessage {
+	fn from(message: StoredMessage) -> Self {
+		Self {
+			key: message.key,
+			id: message.id,
+			payload: message.payload.into_bytes(),
+		}
+	}
+}
+
+/// Messages of a trigger waiting to be published
+///
+/// The outbox lock is held while messages are published, so that the messages of a trigger are
+/// published one at a time and in order.
+pub struct QueueOutbox {
+	/// File storing the pending messages
+	path: PathBuf,
+	/// Pending messages, loaded from the file on first use
+	pending: tokio::sync::Mutex<Option<VecDeque<QueueMessage>>>,
+}
+
+/// Pending messages of an outbox, locked for publishing
+pub struct PendingMessages<'a> {
+	path: &'a PathBuf,
+	messages: tokio::sync::MutexGuard<'a, Option<VecDeque<QueueMessage>>>,
+}
+
+impl QueueOutbox {
+	/// Creates an outbox stored in the given file
+	///
+	/// # Arguments
+	/// * `path` - File storing the pending messages
+	pub fn new(path: PathBuf) -> Self {
+		Self {
+			path,
+			pending: tokio::sync::Mutex::new(None),
+		}
+	}
+
+	/// Locks the outbox, loading the pending messages from disk if needed
+	///
+	/// # Returns
+	/// * `Result<PendingMessages, NotificationError>` - Pending messages or read error
+	pub async fn lock(&self) -> Result<PendingMessages<'_>, NotificationError> {
+		let mut messages = self.pending.lock().await;
+		if messages.is_none() {
+			let stored: Vec<StoredMessage> = match tokio::fs::read_to_string(&self.path).await {
+				Ok(content) => serde_json::from_str(&content).map_err(|e| {
+					NotificationError::internal_error(
+						format!(
+							"Failed to parse queue outbox {}: {}",
+							self.path.display(),
+							e
+						),
+						Some(e.into()),
+						None,
+					)
+				})?,
+				Err(e) if e.kind() == std::io::ErrorKind::NotFound => vec![],
+				Err(e) => {
+					return Err(NotificationError::internal_error(
+						format!("Failed to read queue outbox {}: {}", self.path.display(), e),
+						Some(e.into()),
+						None,
+					))
+				}
+			};
+			*messages = Some(stored.into_iter().map(QueueMessage::from).collect());
+		}
+		Ok(PendingMessages {
+			path: &self.path,
+			messages,
+		})
+	}
+}
+
+impl PendingMessages<'_> {
+	fn queue(&mut self) -> &mut VecDeque<QueueMessage> {
+		self.messages.get_or_insert_with(VecDeque::new)
+	}
+
+	/// Oldest pending message
+	pub fn front(&mut self) -> Option<QueueMessage> {
+		self.queue().front().cloned()
+	}
+
+	/// Removes the oldest pending message
+	pub fn pop_front(&mut self) {
+		self.queue().pop_front();
+	}
+
+	/// Appends a message to the pending messages
+	pub fn push_back(&mut self, message: QueueMessage) {
+		self.queue().push_back(message);
+	}
+
+	/// Number of pending messages
+	pub fn len(&mut self) -> usize {
+		self.queue().len()
+	}
+
+	/// Whether no message is pending
+	pub fn is_empty(&mut self) -> bool {
+		self.queue().is_empty()
+	}
+
+	/// Writes the pending messages to disk, removing the file once the outbox is empty
+	///
+	/// # Returns
+	/// * `Result<(), NotificationError>` - Success or write error
+	pub async fn persist(&mut self) -> Result<(), NotificationError> {
+		let path = self.path.clone();
+		let write_error = |e: std::io::Error| {
+			NotificationError::internal_error(
+				format!("Failed to write queue outbox {}: {}", path.display(), e),
+				Some(e.into()),
+				None,
+			)
+		};
+
+		if self.is_empty() {
+			return match tokio::fs::remove_file(&path).await {
+				Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(write_error(e)),
+				_ => Ok(()),
+			};
+		}
+
+		let stored: Vec<StoredMessage> = self.queue().iter().map(StoredMessage::from).collect();
+		let content = serde_json::to_string(&stored).map_err(|e| {
+			NotificationError::internal_error(
+				format!("Failed to serialize queue outbox: {}", e),
+				Some(e.into()),
+				None,
+			)
+		})?;
+		if let Some(parent) = path.parent() {
+			tokio::fs::create_dir_all(parent)
+				.await
+				.map_err(write_error)?;
+		}
+		// Write to a temporary file first so that a crash never leaves a truncated outbox
+		let temp_path = path.with_extension("json.tmp");
+		tokio::fs::write(&temp_path, content)
+			.await
+			.map_err(write_error)?;
+		tokio::fs::rename(&temp_path, &path)
+			.await
+			.map_err(write_error)
+	}
+}
+
+/// Outboxes of the queue triggers, stored in a common directory
+pub struct QueueOutboxes {
+	/// Directory of the outbox files
+	dir: PathBuf,
+	/// Outboxes by trigger name
+	outboxes: Mutex<HashMap<String, Arc<QueueOutbox>>>,
+}
+
+impl QueueOutboxes {
+	/// Creates the outboxes of a directory
+	///
+	/// # Arguments
+	/// * `dir` - Directory of the outbox files
+	pub fn new(dir: PathBuf) -> Self {
+		Self {
+			dir,
+			outboxes: Mutex::new(HashMap::new()),
+		}
+	}
+
+	/// Returns the outbox of a trigger
+	///
+	/// The file name hex-encodes the trigger name, so that every trigger name maps to a distinct
+	/// and valid file name.
+	///
+	/// # Arguments
+	/// * `trigger_name` - Name of the queue trigger
+	pub fn get(&self, trigger_name: &str) -> Arc<QueueOutbox> {
+		self.outboxes
+			.lock()
+			.unwrap()
+			.entry(trigger_name.to_string())
+			.or_insert_with(|| {
+				Arc::new(QueueOutbox::new(
+					self.dir
+						.join(format!("{}.json", hex::encode(trigger_name.as_bytes()))),
+				))
+			})
+			.clone()
+	}
+}
+
+impl Default for QueueOutboxes {
+	fn default() -> Self {
+		Self::new(PathBuf::from(DEFAULT_QUEUE_OUTBOX_DIR))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn create_test_message(id: &str) -> QueueMessage {
+		QueueMessage {
+			key: "ethereum_mainnet:Large Transfer".to_string(),
+			id: id.to_string(),
+			payload: br#"{"version":1}"#.to_vec(),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_outbox_persists_pending_messages() {
+		let temp_dir = tempfile::tempdir().unwrap();
+		let outboxes = QueueOutboxes::new(temp_dir.path().to_path_buf());
+
+		{
+			let outbox = outboxes.get("kafka/trigger");
+			let mut pending = outbox.lock().await.unwrap();
+			pending.push_back(create_test_message("1"));
+			pending.push_back(create_test_message("2"));
+			pending.persist().await.unwrap();
+		}
+
+		// A new instance reads the messages back from disk
+		let outbox = QueueOutboxes::new(temp_dir.path().to_path_buf()).get("kafka/trigger");
+		let mut pending = outbox.lock().await.unwrap();
+		assert_eq!(pending.len(), 2);
+		assert_eq!(pending.front(), Some(create_test_message("1")));
+
+		pending.pop_front();
+		pending.pop_front();
+		pending.persist().await.unwrap();
+		assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+	}
+
+	#[tokio::test]
+	async fn test_outboxes_are_distinct_per_trigger() {
+		let temp_dir = tempfile::tempdir().unwrap();
+		let outboxes = QueueOutboxes::new(temp_dir.path().to_path_buf());
+
+		assert!(Arc::ptr_eq(
+			&outboxes.get("kafka_trigger"),
+			&outboxes.get("kafka_trigger")
+		));
+		assert_ne!(
+			outboxes.get("kafka_trigger").path,
+			outboxes.get("kafka/trigger").path
+		);
+	}
+}
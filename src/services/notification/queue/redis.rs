@@ -0,0 +1,125 @@
+//! Redis stream publisher implementation.
+//!
+//! Messages are appended to a single stream with `XADD`, whose entries are ordered by the
+//! server. The partition key and message identifier are stored as fields next to the match.
+
+use async_trait::async_trait;
+use redis::{
+	aio::{ConnectionManager, ConnectionManagerConfig},
+	Client,
+};
+use std::time::Duration;
+use tokio::sync::OnceCell;
+
+use crate::services::notification::{
+	queue::{QueueMessage, QueuePublisher},
+	NotificationError,
+};
+
+/// Time after which connecting to the server or appending a message fails
+const REDIS_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Redis client appending to a stream
+pub struct RedisPublisher {
+	client: Client,
+	/// Key of the stream messages are appended to
+	stream: String,
+	/// Approximate maximum number of entries kept in the stream
+	max_len: Option<usize>,
+	/// Reconnecting connection, created on the first publish
+	connection: OnceCell<ConnectionManager>,
+}
+
+impl RedisPublisher {
+	/// Creates a new Redis publisher
+	///
+	/// # Arguments
+	/// * `url` - Redis server URL
+	/// * `stream` - Key of the stream messages are appended to
+	/// * `max_len` - Approximate maximum number of entries kept in the stream
+	///
+	/// # Returns
+	/// * `Result<Self, NotificationError>` - Publisher, or error if the URL is invalid
+	pub fn new(
+		url: &str,
+		stream: String,
+		max_len: Option<usize>,
+	) -> Result<Self, NotificationError> {
+		let client = Client::open(url).map_err(|e| {
+			NotificationError::config_error(
+				format!("Invalid Redis URL: {}", e),
+				Some(e.into()),
+				None,
+			)
+		})?;
+
+		Ok(Self {
+			client,
+			stream,
+			max_len,
+			connection: OnceCell::new(),
+		})
+	}
+}
+
+#[async_trait]
+impl QueuePublisher for RedisPublisher {
+	async fn publish(&self, message: &QueueMessage) -> Result<(), NotificationError> {
+		let mut connection = self
+			.connection
+			.get_or_try_init(|| {
+				// Failed attempts are retried by the notifier with the trigger retry policy
+				let config = ConnectionManagerConfig::new()
+					.set_number_of_retries(0)
+					.set_connection_timeout(REDIS_TIMEOUT)
+					.set_response_timeout(REDIS_TIMEOUT);
+				self.client.get_connection_manager_with_config(config)
+			})
+			.await
+			.map_err(|e| {
+				NotificationError::network_error(
+					format!("Failed to connect to Redis server: {}", e),
+					Some(e.into()),
+					None,
+				)
+			})?
+			.clone();
+
+		let mut command = redis::cmd("XADD");
+		command.arg(&self.stream);
+		if let Some(max_len) = self.max_len {
+			command.arg("MAXLEN").arg("~").arg(max_len);
+		}
+		command
+			.arg("*")
+			.arg("key")
+			.arg(&message.key)
+			.arg("id")
+			.arg(&message.id)
+			.arg("match")
+			.arg(&message.payload);
+
+		command
+			.query_async::<String>(&mut connection)
+			.await
+			.map_err(|e| {
+				NotificationError::notify_failed(
+					format!("Failed to append to Redis stream {}: {}", self.stream, e),
+					Some(e.into()),
+					None,
+				)
+			})?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_new_with_invalid_url() {
+		let result = RedisPublisher::new("localhost:6379", "matches".to_string(), None);
+		assert!(matches!(result, Err(NotificationError::ConfigError(_))));
+	}
+}
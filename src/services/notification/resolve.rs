@@ -0,0 +1,134 @@
+//! Scheduled resolution of incidents.
+//!
+//! PagerDuty and Opsgenie triggers with `auto_resolve_after_secs` resolve their incident once
+//! no match has re-triggered it for the configured delay. A single timer is kept per trigger and
+//! deduplication key: every new trigger of the incident replaces the pending timer.
+//!
+//! Timers are held in memory. Pending resolutions are dropped when the monitor stops, leaving
+//! their incidents open until they are resolved manually or re-triggered after a restart.
+
+use std::{
+	collections::HashMap,
+	future::Future,
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc, Mutex,
+	},
+	time::Duration,
+};
+use tokio::task::{AbortHandle, JoinHandle};
+
+/// Pending resolutions, keyed by trigger and deduplication key
+#[derive(Debug, Default)]
+pub struct ResolveTimers {
+	/// Identifier and task of the pending resolution of each key
+	timers: Arc<Mutex<HashMap<String, (u64, AbortHandle)>>>,
+	/// Identifier of the next scheduled resolution
+	next_id: AtomicU64,
+}
+
+impl ResolveTimers {
+	/// Creates an empty set of timers
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Schedules a resolution, cancelling the pending one of the same key
+	///
+	/// # Arguments
+	/// * `key` - Trigger and deduplication key of the incident
+	/// * `delay` - Delay before the resolution is sent
+	/// * `resolve` - Sends the resolution
+	///
+	/// # Returns
+	/// * `JoinHandle<()>` - Handle of the scheduled resolution
+	pub fn schedule<F>(&self, key: String, delay: Duration, resolve: F) -> JoinHandle<()>
+	where
+		F: Future<Output = ()> + Send + 'static,
+	{
+		let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+		// The lock is held until the timer is registered, so that it can always find itself
+		let mut timers = self.timers.lock().unwrap_or_else(|e| e.into_inner());
+
+		let registry = self.timers.clone();
+		let timer_key = key.clone();
+		let handle = tokio::spawn(async move {
+			tokio::time::sleep(delay).await;
+			{
+				let mut timers = registry.lock().unwrap_or_else(|e| e.into_inner());
+				if timers
+					.get(&timer_key)
+					.is_none_or(|(timer_id, _)| *timer_id != id)
+				{
+					return;
+				}
+				// Deregistered before resolving, so that a new trigger does not abort the
+				// resolution in flight but schedules its own
+				timers.remove(&timer_key);
+			}
+			resolve.await;
+		});
+
+		if let Some((_, previous)) = timers.insert(key, (id, handle.abort_handle())) {
+			previous.abort();
+		}
+		handle
+	}
+
+	/// Number of pending resolutions
+	pub fn pending(&self) -> usize {
+		self.timers.lock().unwrap_or_else(|e| e.into_inner()).len()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::sync::atomic::AtomicUsize;
+
+	#[tokio::test]
+	async fn test_schedule_replaces_pending_timer() {
+		let timers = ResolveTimers::new();
+		let resolved = Arc::new(AtomicUsize::new(0));
+
+		let resolve = |resolved: Arc<AtomicUsize>| async move {
+			resolved.fetch_add(1, Ordering::SeqCst);
+		};
+		let first = timers.schedule(
+			"pagerduty|Ownership:0xabc".to_string(),
+			Duration::from_secs(60),
+			resolve(resolved.clone()),
+		);
+		let second = timers.schedule(
+			"pagerduty|Ownership:0xabc".to_string(),
+			Duration::from_millis(10),
+			resolve(resolved.clone()),
+		);
+		assert_eq!(timers.pending(), 1);
+
+		assert!(first.await.unwrap_err().is_cancelled());
+		second.await.unwrap();
+		assert_eq!(resolved.load(Ordering::SeqCst), 1);
+		assert_eq!(timers.pending(), 0);
+	}
+
+	#[tokio::test]
+	async fn test_schedule_keeps_keys_apart() {
+		let timers = ResolveTimers::new();
+		let resolved = Arc::new(AtomicUsize::new(0));
+
+		let handles: Vec<_> = ["pagerduty|a", "pagerduty|b", "opsgenie|a"]
+			.into_iter()
+			.map(|key| {
+				let resolved = resolved.clone();
+				timers.schedule(key.to_string(), Duration::ZERO, async move {
+					resolved.fetch_add(1, Ordering::SeqCst);
+				})
+			})
+			.collect();
+		for handle in handles {
+			handle.await.unwrap();
+		}
+		assert_eq!(resolved.load(Ordering::SeqCst), 3);
+	}
+}
@@ -44,8 +44,17 @@ impl ScriptExecutor for ScriptNotifier {
 				language,
 				arguments,
 				timeout_ms,
+				mode,
+				sandbox,
+				interpreter,
 			} => {
-				let executor = ScriptExecutorFactory::create(language, &script_content.1);
+				let executor = ScriptExecutorFactory::create_with_mode(
+					language,
+					mode,
+					interpreter.as_deref(),
+					sandbox.as_ref(),
+					&script_content.1,
+				);
 
 				let result = executor
 					.execute(
@@ -87,7 +96,7 @@ mod tests {
 	use crate::{
 		models::{
 			EVMMonitorMatch, EVMTransactionReceipt, MatchConditions, Monitor, MonitorMatch,
-			NotificationMessage, SecretString, SecretValue, TriggerType,
+			NotificationMessage, ScriptMode, SecretString, SecretValue, TriggerType,
 		},
 		services::notification::NotificationService,
 		utils::tests::{
@@ -103,6 +112,9 @@ mod tests {
 			script_path: "test_script.py".to_string(),
 			arguments: Some(vec!["arg1".to_string(), "arg2".to_string()]),
 			timeout_ms: 1000,
+			mode: ScriptMode::Process,
+			sandbox: None,
+			interpreter: None,
 		}
 	}
 
@@ -129,6 +141,7 @@ mod tests {
 			network_slug: "ethereum_mainnet".to_string(),
 			matched_on: MatchConditions::default(),
 			matched_on_args: None,
+			enrichment: None,
 		}))
 	}
 
@@ -148,6 +161,8 @@ mod tests {
 				title: "Test Slack".to_string(),
 				body: "This is a test message".to_string(),
 			},
+			explorer_url: None,
+			blocks: None,
 			retry_policy: Default::default(),
 		};
 
@@ -178,6 +193,9 @@ mod tests {
 			script_path: "test_script.py".to_string(),
 			arguments: None,
 			timeout_ms: 1000, // Timeout longer than sleep time
+			mode: ScriptMode::Process,
+			sandbox: None,
+			interpreter: None,
 		};
 		let notifier = ScriptNotifier::from_config(&config).unwrap();
 		let monitor_match = create_test_monitor_match();
@@ -207,6 +225,9 @@ mod tests {
 			script_path: "test_script.py".to_string(),
 			arguments: None,
 			timeout_ms: 400, // Set timeout lower than the sleep time
+			mode: ScriptMode::Process,
+			sandbox: None,
+			interpreter: None,
 		};
 		let notifier = ScriptNotifier::from_config(&config).unwrap();
 		let monitor_match = create_test_monitor_match();
@@ -255,6 +276,9 @@ mod tests {
 			script_path: "non_existent_script.py".to_string(), // This path won't be in the map
 			arguments: None,
 			timeout_ms: 1000,
+			mode: ScriptMode::Process,
+			sandbox: None,
+			interpreter: None,
 		};
 		let trigger = TriggerBuilder::new()
         .name("test_script_missing")
@@ -2,20 +2,47 @@
 //!
 //! Provides functionality to send formatted messages to Slack channels
 //! via incoming webhooks, supporting message templates with variable substitution.
+//!
+//! Matches are laid out with Block Kit: a header, the message, the monitor and network, the
+//! matched functions and events, a fields grid of their arguments and an explorer link button.
+//! Messages of monitors with a severity are wrapped in an attachment coloured accordingly.
 
 use async_trait::async_trait;
 use reqwest_middleware::ClientWithMiddleware;
+use serde_json::json;
 use std::{collections::HashMap, sync::Arc};
 
 use crate::{
-	models::TriggerTypeConfig,
-	services::notification::{NotificationError, Notifier, WebhookConfig, WebhookNotifier},
+	models::{IncidentSeverity, MonitorMatch, TriggerTypeConfig},
+	services::notification::{
+		render_json_template, truncate_text, MatchSummary, NotificationError, Notifier,
+		WebhookConfig, WebhookNotifier,
+	},
 };
 
+/// Maximum length of the plain text of a header block
+const SLACK_MAX_HEADER_LENGTH: usize = 150;
+
+/// Maximum length of the text of a section block
+const SLACK_MAX_TEXT_LENGTH: usize = 3000;
+
+/// Maximum length of a section field
+const SLACK_MAX_FIELD_LENGTH: usize = 2000;
+
+/// Maximum number of fields of a section block
+const SLACK_MAX_SECTION_FIELDS: usize = 10;
+
+/// Maximum number of matched arguments shown in the fields grid
+const SLACK_MAX_ARGS: usize = 40;
+
 /// Implementation of Slack notifications via webhooks
 #[derive(Debug)]
 pub struct SlackNotifier {
 	inner: WebhookNotifier,
+	/// Block explorer link template
+	explorer_url: Option<String>,
+	/// Blocks replacing the default layout
+	blocks_template: Option<serde_json::Value>,
 }
 
 impl SlackNotifier {
@@ -45,6 +72,8 @@ impl SlackNotifier {
 
 		Ok(Self {
 			inner: WebhookNotifier::new(config, http_client)?,
+			explorer_url: None,
+			blocks_template: None,
 		})
 	}
 
@@ -60,6 +89,110 @@ impl SlackNotifier {
 		format!("*{}*\n\n{}", self.inner.title, message)
 	}
 
+	/// Summarizes the match shown by the message
+	///
+	/// # Arguments
+	/// * `variables` - Map of variable names to values
+	/// * `monitor_match` - Monitor match being notified, absent for digests
+	///
+	/// # Returns
+	/// * `MatchSummary` - Details of the match
+	pub fn format_summary(
+		&self,
+		variables: &HashMap<String, String>,
+		monitor_match: Option<&MonitorMatch>,
+	) -> MatchSummary {
+		MatchSummary::new(variables, monitor_match, self.explorer_url.as_deref())
+	}
+
+	/// Builds the Block Kit blocks of a message
+	///
+	/// Configured blocks are rendered with the variables, otherwise the default layout is built
+	/// from the message and match summary.
+	///
+	/// # Arguments
+	/// * `variables` - Map of variable names to values
+	/// * `summary` - Details of the match
+	///
+	/// # Returns
+	/// * `serde_json::Value` - Array of blocks
+	pub fn format_blocks(
+		&self,
+		variables: &HashMap<String, String>,
+		summary: &MatchSummary,
+	) -> serde_json::Value {
+		if let Some(template) = &self.blocks_template {
+			return render_json_template(template, variables);
+		}
+
+		let mut blocks = vec![
+			json!({
+				"type": "header",
+				"text": {
+					"type": "plain_text",
+					"text": truncate_text(&self.inner.title, SLACK_MAX_HEADER_LENGTH),
+				},
+			}),
+			json!({
+				"type": "section",
+				"text": {
+					"type": "mrkdwn",
+					"text": truncate_text(
+						&self.inner.format_message(variables),
+						SLACK_MAX_TEXT_LENGTH
+					),
+				},
+			}),
+		];
+
+		let details = [
+			("Monitor", summary.monitor.clone()),
+			("Network", summary.network.clone()),
+			(
+				"Severity",
+				summary.severity.map(|severity| severity.to_string()),
+			),
+		]
+		.into_iter()
+		.filter_map(|(name, value)| value.map(|value| (name.to_string(), value)))
+		.collect::<Vec<_>>();
+		if !details.is_empty() {
+			blocks.push(fields_section(&details));
+		}
+
+		if !summary.signatures.is_empty() {
+			let signatures = summary
+				.signatures
+				.iter()
+				.map(|signature| format!("`{}`", signature))
+				.collect::<Vec<_>>()
+				.join("\n");
+			blocks.push(json!({
+				"type": "section",
+				"text": {
+					"type": "mrkdwn",
+					"text": truncate_text(&format!("*Matched*\n{}", signatures), SLACK_MAX_TEXT_LENGTH),
+				},
+			}));
+		}
+
+		let args = &summary.args[..summary.args.len().min(SLACK_MAX_ARGS)];
+		blocks.extend(args.chunks(SLACK_MAX_SECTION_FIELDS).map(fields_section));
+
+		if let Some(link) = &summary.explorer_link {
+			blocks.push(json!({
+				"type": "actions",
+				"elements": [{
+					"type": "button",
+					"text": { "type": "plain_text", "text": "View on explorer" },
+					"url": link,
+				}],
+			}));
+		}
+
+		serde_json::Value::Array(blocks)
+	}
+
 	/// Creates a Slack notifier from a trigger configuration
 	///
 	/// # Arguments
@@ -73,7 +206,11 @@ impl SlackNotifier {
 		http_client: Arc<ClientWithMiddleware>,
 	) -> Result<Self, NotificationError> {
 		if let TriggerTypeConfig::Slack {
-			slack_url, message, ..
+			slack_url,
+			message,
+			explorer_url,
+			blocks,
+			..
 		} = config
 		{
 			let webhook_config = WebhookConfig {
@@ -89,6 +226,8 @@ impl SlackNotifier {
 
 			Ok(Self {
 				inner: WebhookNotifier::new(webhook_config, http_client)?,
+				explorer_url: explorer_url.clone(),
+				blocks_template: blocks.clone(),
 			})
 		} else {
 			Err(NotificationError::config_error(
@@ -98,6 +237,57 @@ impl SlackNotifier {
 			))
 		}
 	}
+
+	/// Sends Block Kit blocks, coloured by the severity of the monitor
+	///
+	/// # Arguments
+	/// * `message` - The formatted message, shown in notifications and by clients without
+	///   Block Kit support
+	/// * `blocks` - Blocks of the message
+	/// * `severity` - Severity of the monitor, if any
+	///
+	/// # Returns
+	/// * `Result<(), NotificationError>` - Success or error
+	pub async fn notify_blocks(
+		&self,
+		message: &str,
+		blocks: &serde_json::Value,
+		severity: Option<IncidentSeverity>,
+	) -> Result<(), NotificationError> {
+		let mut payload_fields = HashMap::from([("text".to_string(), json!(message))]);
+		match severity {
+			// Block Kit has no colour, only the legacy attachments do
+			Some(severity) => payload_fields.insert(
+				"attachments".to_string(),
+				json!([{
+					"color": format!("#{:06X}", severity.color()),
+					"blocks": blocks,
+				}]),
+			),
+			None => payload_fields.insert("blocks".to_string(), blocks.clone()),
+		};
+
+		self.inner
+			.notify_with_payload(message, payload_fields)
+			.await
+	}
+}
+
+/// Builds a section laying out fields in a two column grid
+///
+/// # Arguments
+/// * `fields` - Names and values of the fields, at most ten
+fn fields_section(fields: &[(String, String)]) -> serde_json::Value {
+	let fields = fields
+		.iter()
+		.map(|(name, value)| {
+			json!({
+				"type": "mrkdwn",
+				"text": truncate_text(&format!("*{}*\n{}", name, value), SLACK_MAX_FIELD_LENGTH),
+			})
+		})
+		.collect::<Vec<_>>();
+	json!({ "type": "section", "fields": fields })
 }
 
 #[async_trait]
@@ -134,6 +324,7 @@ mod tests {
 		models::{NotificationMessage, SecretString, SecretValue},
 		utils::{tests::create_test_http_client, HttpRetryConfig},
 	};
+	use mockito::Matcher;
 
 	use super::*;
 
@@ -156,6 +347,8 @@ mod tests {
 				title: "Test Alert".to_string(),
 				body: "Test message ${value}".to_string(),
 			},
+			explorer_url: None,
+			blocks: None,
 			retry_policy: HttpRetryConfig::default(),
 		}
 	}
@@ -197,6 +390,100 @@ mod tests {
 		assert_eq!(result, "*Alert*\n\n");
 	}
 
+	////////////////////////////////////////////////////////////
+	// format_blocks tests
+	////////////////////////////////////////////////////////////
+
+	fn create_test_summary() -> MatchSummary {
+		MatchSummary {
+			monitor: Some("Large Transfer".to_string()),
+			network: Some("ethereum_mainnet".to_string()),
+			severity: Some(IncidentSeverity::Critical),
+			explorer_link: Some("https://etherscan.io/tx/0xabc".to_string()),
+			signatures: vec!["Transfer(address,address,uint256)".to_string()],
+			args: vec![("Transfer.value".to_string(), "100".to_string())],
+		}
+	}
+
+	#[test]
+	fn test_format_blocks() {
+		let notifier = create_test_notifier("Value is ${value}");
+		let variables = HashMap::from([("value".to_string(), "100".to_string())]);
+
+		let blocks = notifier.format_blocks(&variables, &create_test_summary());
+		assert_eq!(
+			blocks,
+			json!([
+				{ "type": "header", "text": { "type": "plain_text", "text": "Alert" } },
+				{ "type": "section", "text": { "type": "mrkdwn", "text": "Value is 100" } },
+				{
+					"type": "section",
+					"fields": [
+						{ "type": "mrkdwn", "text": "*Monitor*\nLarge Transfer" },
+						{ "type": "mrkdwn", "text": "*Network*\nethereum_mainnet" },
+						{ "type": "mrkdwn", "text": "*Severity*\ncritical" },
+					],
+				},
+				{
+					"type": "section",
+					"text": {
+						"type": "mrkdwn",
+						"text": "*Matched*\n`Transfer(address,address,uint256)`",
+					},
+				},
+				{
+					"type": "section",
+					"fields": [{ "type": "mrkdwn", "text": "*Transfer.value*\n100" }],
+				},
+				{
+					"type": "actions",
+					"elements": [{
+						"type": "button",
+						"text": { "type": "plain_text", "text": "View on explorer" },
+						"url": "https://etherscan.io/tx/0xabc",
+					}],
+				},
+			])
+		);
+	}
+
+	#[test]
+	fn test_format_blocks_splits_args_grid() {
+		let notifier = create_test_notifier("Value is ${value}");
+		let summary = MatchSummary {
+			args: (0..25)
+				.map(|i| (format!("Batch.arg{}", i), i.to_string()))
+				.collect(),
+			..Default::default()
+		};
+
+		let blocks = notifier.format_blocks(&HashMap::new(), &summary);
+		let grids = blocks
+			.as_array()
+			.unwrap()
+			.iter()
+			.filter_map(|block| block["fields"].as_array())
+			.map(|fields| fields.len())
+			.collect::<Vec<_>>();
+		assert_eq!(grids, vec![10, 10, 5]);
+	}
+
+	#[test]
+	fn test_format_blocks_with_template() {
+		let mut notifier = create_test_notifier("Value is ${value}");
+		notifier.blocks_template = Some(json!([
+			{ "type": "section", "text": { "type": "mrkdwn", "text": "*${monitor.name}*" } }
+		]));
+		let variables = HashMap::from([("monitor.name".to_string(), "Large Transfer".to_string())]);
+
+		assert_eq!(
+			notifier.format_blocks(&variables, &create_test_summary()),
+			json!([
+				{ "type": "section", "text": { "type": "mrkdwn", "text": "*Large Transfer*" } }
+			])
+		);
+	}
+
 	////////////////////////////////////////////////////////////
 	// from_config tests
 	////////////////////////////////////////////////////////////
@@ -228,6 +515,34 @@ mod tests {
 		assert!(matches!(error, NotificationError::NotifyFailed { .. }));
 	}
 
+	#[tokio::test]
+	async fn test_notify_blocks_with_severity() {
+		let mut server = mockito::Server::new_async().await;
+		let blocks = json!([{ "type": "section", "text": { "type": "mrkdwn", "text": "Hi" } }]);
+		let mock = server
+			.mock("POST", "/")
+			.match_body(Matcher::Json(json!({
+				"text": "*Alert*\n\nHi",
+				"attachments": [{ "color": "#ECB22E", "blocks": blocks }],
+			})))
+			.with_status(200)
+			.create_async()
+			.await;
+
+		let notifier = SlackNotifier::new(
+			server.url(),
+			"Alert".to_string(),
+			"Hi".to_string(),
+			create_test_http_client(),
+		)
+		.unwrap();
+		let result = notifier
+			.notify_blocks("*Alert*\n\nHi", &blocks, Some(IncidentSeverity::Warning))
+			.await;
+		assert!(result.is_ok());
+		mock.assert();
+	}
+
 	#[tokio::test]
 	async fn test_notify_with_payload_failure() {
 		let notifier = create_test_notifier("Test message");
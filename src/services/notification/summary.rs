@@ -0,0 +1,256 @@
+//! Match summaries of rich chat notifications.
+//!
+//! Slack, Discord and Teams notifications lay out the same details of a match: the monitor and
+//! network, a block explorer link, the matched functions and events, and their decoded
+//! arguments. This module extracts them from the match and its template variables.
+
+use std::collections::HashMap;
+
+use crate::{
+	models::{IncidentSeverity, MonitorMatch},
+	services::notification::render_template,
+};
+
+/// Details of a monitor match shown by rich chat notifications
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MatchSummary {
+	/// Name of the monitor that matched
+	pub monitor: Option<String>,
+	/// Network slug the match was found on
+	pub network: Option<String>,
+	/// Severity of the monitor
+	pub severity: Option<IncidentSeverity>,
+	/// Block explorer link of the matched transaction
+	pub explorer_link: Option<String>,
+	/// Signatures of the matched functions and events
+	pub signatures: Vec<String>,
+	/// Matched arguments, named after the signature and argument
+	pub args: Vec<(String, String)>,
+}
+
+impl MatchSummary {
+	/// Summarizes a monitor match
+	///
+	/// Digests are not tied to a single match, their summary only holds the rendered link and
+	/// arguments found in the variables.
+	///
+	/// # Arguments
+	/// * `variables` - Map of variable names to values
+	/// * `monitor_match` - Monitor match being notified, absent for digests
+	/// * `explorer_url` - Block explorer link template
+	pub fn new(
+		variables: &HashMap<String, String>,
+		monitor_match: Option<&MonitorMatch>,
+		explorer_url: Option<&str>,
+	) -> Self {
+		let (monitor, network, severity) = match monitor_match {
+			Some(MonitorMatch::EVM(evm_match)) => (
+				Some(evm_match.monitor.name.clone()),
+				Some(evm_match.network_slug.clone()),
+				evm_match.monitor.severity,
+			),
+			Some(MonitorMatch::Stellar(stellar_match)) => (
+				Some(stellar_match.monitor.name.clone()),
+				Some(stellar_match.network_slug.clone()),
+				stellar_match.monitor.severity,
+			),
+			Some(MonitorMatch::Poll(poll_match)) => (
+				Some(poll_match.monitor.name.clone()),
+				Some(poll_match.network_slug.clone()),
+				poll_match.monitor.severity,
+			),
+			None => (None, None, None),
+		};
+
+		let mut signatures = variables
+			.iter()
+			.filter_map(|(key, signature)| {
+				let (collection, index) = match key.split('.').collect::<Vec<_>>()[..] {
+					[collection @ ("functions" | "events"), index, "signature"] => {
+						(collection, index.parse::<usize>().ok()?)
+					}
+					_ => return None,
+				};
+				Some(((collection, index), signature.clone()))
+			})
+			.collect::<Vec<_>>();
+		signatures.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+		Self {
+			monitor,
+			network,
+			severity,
+			explorer_link: explorer_url
+				.and_then(|template| format_explorer_link(template, variables)),
+			signatures: signatures
+				.into_iter()
+				.map(|(_, signature)| signature)
+				.collect(),
+			args: format_match_args(variables),
+		}
+	}
+}
+
+/// Lists the matched function and event arguments
+///
+/// Arguments are named after the signature and argument, e.g. `Transfer.value`, and ordered by
+/// condition then argument name.
+///
+/// # Arguments
+/// * `variables` - Map of variable names to values
+///
+/// # Returns
+/// * `Vec<(String, String)>` - Names and values of the matched arguments
+pub fn format_match_args(variables: &HashMap<String, String>) -> Vec<(String, String)> {
+	let mut args = variables
+		.iter()
+		.filter_map(|(key, value)| {
+			let mut segments = key.splitn(4, '.');
+			let (collection, index, "args", name) = (
+				segments.next()?,
+				segments.next()?,
+				segments.next()?,
+				segments.next()?,
+			) else {
+				return None;
+			};
+			if collection != "functions" && collection != "events" {
+				return None;
+			}
+			let index = index.parse::<usize>().ok()?;
+			let signature = variables
+				.get(&format!("{}.{}.signature", collection, index))
+				.map(|signature| signature.split('(').next().unwrap_or(signature))
+				.unwrap_or(collection);
+			Some((
+				(collection, index, name),
+				(format!("{}.{}", signature, name), value.clone()),
+			))
+		})
+		.collect::<Vec<_>>();
+	args.sort_by(|(a, _), (b, _)| a.cmp(b));
+	args.into_iter().map(|(_, arg)| arg).collect()
+}
+
+/// Renders the block explorer link of a match
+///
+/// Links whose variables are missing, such as in digests, are left out.
+///
+/// # Arguments
+/// * `template` - Block explorer link template
+/// * `variables` - Map of variable names to values
+///
+/// # Returns
+/// * `Option<String>` - Explorer link, if fully rendered
+pub fn format_explorer_link(template: &str, variables: &HashMap<String, String>) -> Option<String> {
+	Some(render_template(template, variables)).filter(|link| !link.contains("${"))
+}
+
+/// Truncates text to a maximum number of characters, ending it with an ellipsis when cut
+///
+/// # Arguments
+/// * `text` - Text to truncate
+/// * `max_chars` - Maximum number of characters of the result
+pub fn truncate_text(text: &str, max_chars: usize) -> String {
+	if text.chars().count() <= max_chars {
+		return text.to_string();
+	}
+	let mut truncated = text
+		.chars()
+		.take(max_chars.saturating_sub(1))
+		.collect::<String>();
+	truncated.push('…');
+	truncated
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		models::{EVMMonitorMatch, MatchConditions},
+		utils::tests::evm::{monitor::MonitorBuilder, transaction::TransactionBuilder},
+	};
+
+	fn create_test_variables() -> HashMap<String, String> {
+		HashMap::from([
+			("transaction.hash".to_string(), "0xabc".to_string()),
+			(
+				"events.0.signature".to_string(),
+				"Transfer(address,address,uint256)".to_string(),
+			),
+			("events.0.args.value".to_string(), "100".to_string()),
+			("events.0.args.to".to_string(), "0x2".to_string()),
+			(
+				"functions.0.signature".to_string(),
+				"transfer(address,uint256)".to_string(),
+			),
+			("functions.0.args.amount".to_string(), "5".to_string()),
+		])
+	}
+
+	#[test]
+	fn test_match_summary() {
+		let monitor_match = MonitorMatch::EVM(Box::new(EVMMonitorMatch {
+			monitor: MonitorBuilder::new()
+				.name("Large Transfer")
+				.severity(IncidentSeverity::Warning)
+				.build(),
+			transaction: TransactionBuilder::new().build(),
+			receipt: None,
+			logs: None,
+			network_slug: "ethereum_mainnet".to_string(),
+			matched_on: MatchConditions::default(),
+			matched_on_args: None,
+			enrichment: None,
+		}));
+
+		let summary = MatchSummary::new(
+			&create_test_variables(),
+			Some(&monitor_match),
+			Some("https://etherscan.io/tx/${transaction.hash}"),
+		);
+		assert_eq!(summary.monitor.as_deref(), Some("Large Transfer"));
+		assert_eq!(summary.network.as_deref(), Some("ethereum_mainnet"));
+		assert_eq!(summary.severity, Some(IncidentSeverity::Warning));
+		assert_eq!(
+			summary.explorer_link.as_deref(),
+			Some("https://etherscan.io/tx/0xabc")
+		);
+		assert_eq!(
+			summary.signatures,
+			vec![
+				"Transfer(address,address,uint256)".to_string(),
+				"transfer(address,uint256)".to_string(),
+			]
+		);
+		assert_eq!(summary.args.len(), 3);
+	}
+
+	#[test]
+	fn test_match_summary_of_digest() {
+		let summary = MatchSummary::new(
+			&HashMap::new(),
+			None,
+			Some("https://etherscan.io/tx/${transaction.hash}"),
+		);
+		assert_eq!(summary, MatchSummary::default());
+	}
+
+	#[test]
+	fn test_format_match_args() {
+		assert_eq!(
+			format_match_args(&create_test_variables()),
+			vec![
+				("Transfer.to".to_string(), "0x2".to_string()),
+				("Transfer.value".to_string(), "100".to_string()),
+				("transfer.amount".to_string(), "5".to_string()),
+			]
+		);
+	}
+
+	#[test]
+	fn test_truncate_text() {
+		assert_eq!(truncate_text("value", 5), "value");
+		assert_eq!(truncate_text("values", 5), "valu…");
+	}
+}
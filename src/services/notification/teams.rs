@@ -0,0 +1,361 @@
+//! Microsoft Teams notification implementation.
+//!
+//! Provides functionality to send Adaptive Cards to Teams channels via incoming webhooks or
+//! workflows, supporting message templates with variable substitution.
+
+use async_trait::async_trait;
+use reqwest_middleware::ClientWithMiddleware;
+use serde::Serialize;
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+	models::TriggerTypeConfig,
+	services::notification::{
+		format_explorer_link, format_match_args, NotificationError, Notifier, WebhookConfig,
+		WebhookNotifier,
+	},
+};
+
+/// Content type of Adaptive Card attachments
+const ADAPTIVE_CARD_CONTENT_TYPE: &str = "application/vnd.microsoft.card.adaptive";
+
+/// Adaptive Card schema version, the highest supported by Teams on all clients
+const ADAPTIVE_CARD_VERSION: &str = "1.4";
+
+/// Implementation of Microsoft Teams notifications via webhooks
+#[derive(Debug)]
+pub struct TeamsNotifier {
+	inner: WebhookNotifier,
+	/// Block explorer link template
+	explorer_url: Option<String>,
+}
+
+/// Represents a fact in the facts table of an Adaptive Card
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct TeamsFact {
+	/// Name of the fact
+	pub title: String,
+	/// Value of the fact
+	pub value: String,
+}
+
+impl TeamsNotifier {
+	/// Creates a new Teams notifier instance
+	///
+	/// # Arguments
+	/// * `url` - Teams webhook URL
+	/// * `title` - Message title
+	/// * `body_template` - Message template with variables
+	/// * `explorer_url` - Block explorer link template
+	/// * `http_client` - HTTP client with middleware for retries
+	pub fn new(
+		url: String,
+		title: String,
+		body_template: String,
+		explorer_url: Option<String>,
+		http_client: Arc<ClientWithMiddleware>,
+	) -> Result<Self, NotificationError> {
+		let config = WebhookConfig {
+			url,
+			url_params: None,
+			title,
+			body_template,
+			method: Some("POST".to_string()),
+			secret: None,
+			headers: None,
+			payload_fields: None,
+		};
+
+		Ok(Self {
+			inner: WebhookNotifier::new(config, http_client)?,
+			explorer_url,
+		})
+	}
+
+	/// Formats a message by substituting variables in the template
+	///
+	/// # Arguments
+	/// * `variables` - Map of variable names to values
+	///
+	/// # Returns
+	/// * `String` - Formatted message with variables replaced
+	pub fn format_message(&self, variables: &HashMap<String, String>) -> String {
+		self.inner.format_message(variables)
+	}
+
+	/// Builds the facts table of the matched function and event arguments
+	///
+	/// Facts are named after the signature and argument, e.g. `Transfer.value`, and ordered
+	/// by condition then argument name.
+	///
+	/// # Arguments
+	/// * `variables` - Map of variable names to values
+	///
+	/// # Returns
+	/// * `Vec<TeamsFact>` - Facts of the matched arguments
+	pub fn format_facts(&self, variables: &HashMap<String, String>) -> Vec<TeamsFact> {
+		format_match_args(variables)
+			.into_iter()
+			.map(|(title, value)| TeamsFact { title, value })
+			.collect()
+	}
+
+	/// Renders the block explorer link of a match
+	///
+	/// Links whose variables are missing, such as in digests, are left out.
+	///
+	/// # Arguments
+	/// * `variables` - Map of variable names to values
+	///
+	/// # Returns
+	/// * `Option<String>` - Explorer link, if configured and fully rendered
+	pub fn format_explorer_link(&self, variables: &HashMap<String, String>) -> Option<String> {
+		self.explorer_url
+			.as_ref()
+			.and_then(|template| format_explorer_link(template, variables))
+	}
+
+	/// Creates a Teams notifier from a trigger configuration
+	///
+	/// # Arguments
+	/// * `config` - Trigger configuration containing Teams parameters
+	/// * `http_client` - HTTP client with middleware for retries
+	///
+	/// # Returns
+	/// * `Result<Self, NotificationError>` - Notifier instance if config is Teams type
+	pub fn from_config(
+		config: &TriggerTypeConfig,
+		http_client: Arc<ClientWithMiddleware>,
+	) -> Result<Self, NotificationError> {
+		if let TriggerTypeConfig::Teams {
+			teams_url,
+			message,
+			explorer_url,
+			..
+		} = config
+		{
+			Self::new(
+				teams_url.as_ref().to_string(),
+				message.title.clone(),
+				message.body.clone(),
+				explorer_url.clone(),
+				http_client,
+			)
+		} else {
+			let msg = format!("Invalid teams configuration: {:?}", config);
+			Err(NotificationError::config_error(msg, None, None))
+		}
+	}
+
+	/// Sends an Adaptive Card with a facts table and an explorer link button
+	///
+	/// # Arguments
+	/// * `message` - The formatted message to send
+	/// * `facts` - Facts table shown below the message
+	/// * `explorer_link` - Link opened by the card button
+	///
+	/// # Returns
+	/// * `Result<(), NotificationError>` - Success or error
+	pub async fn notify_card(
+		&self,
+		message: &str,
+		facts: &[TeamsFact],
+		explorer_link: Option<&str>,
+	) -> Result<(), NotificationError> {
+		let mut body = vec![
+			serde_json::json!({
+				"type": "TextBlock",
+				"text": self.inner.title,
+				"size": "Medium",
+				"weight": "Bolder",
+				"wrap": true,
+			}),
+			serde_json::json!({
+				"type": "TextBlock",
+				"text": message,
+				"wrap": true,
+			}),
+		];
+		if !facts.is_empty() {
+			body.push(serde_json::json!({ "type": "FactSet", "facts": facts }));
+		}
+		let actions = explorer_link
+			.map(|url| {
+				vec![serde_json::json!({
+					"type": "Action.OpenUrl",
+					"title": "View on explorer",
+					"url": url,
+				})]
+			})
+			.unwrap_or_default();
+
+		let payload_fields = HashMap::from([
+			("type".to_string(), serde_json::json!("message")),
+			(
+				"attachments".to_string(),
+				serde_json::json!([{
+					"contentType": ADAPTIVE_CARD_CONTENT_TYPE,
+					"contentUrl": null,
+					"content": {
+						"$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+						"type": "AdaptiveCard",
+						"version": ADAPTIVE_CARD_VERSION,
+						"body": body,
+						"actions": actions,
+					},
+				}]),
+			),
+		]);
+
+		self.inner
+			.notify_with_payload(message, payload_fields)
+			.await
+	}
+}
+
+#[async_trait]
+impl Notifier for TeamsNotifier {
+	/// Sends a formatted message to Teams as an Adaptive Card
+	///
+	/// # Arguments
+	/// * `message` - The formatted message to send
+	///
+	/// # Returns
+	/// * `Result<(), NotificationError>` - Success or error
+	async fn notify(&self, message: &str) -> Result<(), NotificationError> {
+		self.notify_card(message, &[], None).await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		models::{NotificationMessage, SecretString, SecretValue},
+		utils::{tests::create_test_http_client, HttpRetryConfig},
+	};
+
+	use super::*;
+
+	fn create_test_notifier(explorer_url: Option<&str>) -> TeamsNotifier {
+		TeamsNotifier::new(
+			"https://non-existent-url-teams-webhook.com".to_string(),
+			"Alert".to_string(),
+			"Value is ${value}".to_string(),
+			explorer_url.map(|url| url.to_string()),
+			create_test_http_client(),
+		)
+		.unwrap()
+	}
+
+	fn create_test_variables() -> HashMap<String, String> {
+		HashMap::from([
+			("value".to_string(), "100".to_string()),
+			("transaction.hash".to_string(), "0xabc".to_string()),
+			(
+				"events.0.signature".to_string(),
+				"Transfer(address,address,uint256)".to_string(),
+			),
+			("events.0.args.value".to_string(), "100".to_string()),
+			("events.0.args.to".to_string(), "0x2".to_string()),
+			("functions.0.args.amount".to_string(), "5".to_string()),
+		])
+	}
+
+	#[test]
+	fn test_format_message() {
+		let notifier = create_test_notifier(None);
+		assert_eq!(
+			notifier.format_message(&create_test_variables()),
+			"Value is 100"
+		);
+	}
+
+	#[test]
+	fn test_format_facts() {
+		let notifier = create_test_notifier(None);
+		let facts = notifier.format_facts(&create_test_variables());
+
+		assert_eq!(
+			facts,
+			vec![
+				TeamsFact {
+					title: "Transfer.to".to_string(),
+					value: "0x2".to_string(),
+				},
+				TeamsFact {
+					title: "Transfer.value".to_string(),
+					value: "100".to_string(),
+				},
+				TeamsFact {
+					title: "functions.amount".to_string(),
+					value: "5".to_string(),
+				},
+			]
+		);
+	}
+
+	#[test]
+	fn test_format_explorer_link() {
+		let notifier = create_test_notifier(Some("https://etherscan.io/tx/${transaction.hash}"));
+		assert_eq!(
+			notifier.format_explorer_link(&create_test_variables()),
+			Some("https://etherscan.io/tx/0xabc".to_string())
+		);
+		assert_eq!(notifier.format_explorer_link(&HashMap::new()), None);
+
+		let notifier = create_test_notifier(None);
+		assert_eq!(
+			notifier.format_explorer_link(&create_test_variables()),
+			None
+		);
+	}
+
+	#[test]
+	fn test_from_config_with_teams_config() {
+		let config = TriggerTypeConfig::Teams {
+			teams_url: SecretValue::Plain(SecretString::new(
+				"https://example.webhook.office.com/webhookb2/abc".to_string(),
+			)),
+			message: NotificationMessage {
+				title: "Test Alert".to_string(),
+				body: "Test message ${value}".to_string(),
+			},
+			explorer_url: None,
+			retry_policy: HttpRetryConfig::default(),
+		};
+
+		let notifier = TeamsNotifier::from_config(&config, create_test_http_client()).unwrap();
+		assert_eq!(
+			notifier.inner.url,
+			"https://example.webhook.office.com/webhookb2/abc"
+		);
+		assert_eq!(notifier.inner.title, "Test Alert");
+		assert_eq!(notifier.inner.body_template, "Test message ${value}");
+	}
+
+	#[test]
+	fn test_from_config_invalid_type() {
+		let config = TriggerTypeConfig::Slack {
+			slack_url: SecretValue::Plain(SecretString::new(
+				"https://slack.example.com".to_string(),
+			)),
+			message: NotificationMessage {
+				title: "Test Alert".to_string(),
+				body: "Test message ${value}".to_string(),
+			},
+			explorer_url: None,
+			blocks: None,
+			retry_policy: HttpRetryConfig::default(),
+		};
+
+		let notifier = TeamsNotifier::from_config(&config, create_test_http_client());
+		assert!(matches!(notifier, Err(NotificationError::ConfigError(_))));
+	}
+
+	#[tokio::test]
+	async fn test_notify_failure() {
+		let notifier = create_test_notifier(None);
+		let result = notifier.notify("Test message").await;
+		assert!(result.is_err());
+	}
+}
@@ -0,0 +1,1396 @@
+//! Notification message templates.
+//!
+//! Message templates substitute match variables and support filters, fallbacks, conditionals and
+//! loops:
+//!
+//! - `${events.0.args.value | decimals(6) | thousands}` applies filters to a variable
+//! - `${events.0.args.value | token}` formats a raw amount with the decimals and symbol of the
+//!   registered token the match is about
+//! - `${events.0.args.value.usd}` or `${transaction.value | usd(ETH)}` is the USD value of a raw
+//!   amount of the token of the match, or of a registered token with a price feed
+//! - `${transaction.to | default("contract creation")}` provides a fallback for missing variables
+//! - `{% if events.0.args.value %}...{% elif ... %}...{% else %}...{% endif %}` renders
+//!   conditionally
+//! - `{% for event in events %}${loop.index}. ${event.signature}{% endfor %}` repeats over indexed
+//!   variables
+//!
+//! Templates are parsed strictly when configurations are loaded. When rendering, variables that
+//! are missing are left as written, and templates that cannot be parsed fall back to plain
+//! `${variable}` substitution.
+
+use rust_decimal::Decimal;
+use std::{collections::HashMap, str::FromStr};
+
+use alloy::primitives::U256;
+use chrono::{
+	format::{Item, StrftimeItems},
+	DateTime,
+};
+use thiserror::Error as ThisError;
+
+use crate::services::{
+	filter::token_decimals,
+	notification::DIGEST_MATCHES,
+	price::token_price,
+	trigger::{THROTTLE_SUMMARY, THROTTLE_SUPPRESSED_COUNT},
+};
+
+/// Default format of the `date` filter
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S UTC";
+
+/// Default number of characters kept on each side by the `shorten` filter
+const DEFAULT_SHORTEN_LENGTH: usize = 4;
+
+/// Variable holding the 1-based position of the current loop item
+const LOOP_INDEX: &str = "loop.index";
+
+/// Error raised when a template is invalid
+#[derive(ThisError, Debug, Clone, PartialEq)]
+#[error("{0}")]
+pub struct TemplateError(pub String);
+
+impl TemplateError {
+	fn new(message: impl Into<String>, position: usize) -> Self {
+		Self(format!("{} at position {}", message.into(), position))
+	}
+}
+
+/// A filter applied to a template variable
+#[derive(Debug, Clone, PartialEq)]
+enum Filter {
+	/// Fallback used when the variable is missing or empty
+	Default(String),
+	/// Scales an integer down by a number of token decimals
+	Decimals(u32),
+	/// Formats a raw token amount with the decimals and symbol of a registered token, or of the
+	/// token of the match when no symbol is given
+	Token(Option<String>),
+	/// Converts a raw token amount to its USD value using the price of a registered token, or of
+	/// the token of the match when no symbol is given
+	Usd(Option<String>),
+	/// Converts a hexadecimal value to decimal
+	HexToDec,
+	/// Shortens an address or hash, keeping a number of characters on each side
+	Shorten(usize),
+	/// Builds a block explorer link from a base URL and an optional path segment
+	Explorer {
+		base_url: String,
+		kind: Option<String>,
+	},
+	/// Formats a Unix timestamp in seconds
+	Date(String),
+	/// Rounds a decimal number to a number of decimal places
+	Round(u32),
+	/// Groups the integer digits of a number by thousands
+	Thousands,
+	/// Converts to uppercase
+	Upper,
+	/// Converts to lowercase
+	Lower,
+}
+
+/// A variable with its filters, e.g. `events.0.args.value | decimals(6)`
+#[derive(Debug, Clone, PartialEq)]
+struct Output {
+	/// Template source of the expression, rendered as is when the variable is missing
+	source: String,
+	/// Variable path
+	path: String,
+	/// Filters applied in order
+	filters: Vec<Filter>,
+}
+
+/// Condition of an `if` or `elif` tag
+#[derive(Debug, Clone, PartialEq)]
+enum Condition {
+	/// The variable is present and not empty, `false`, `null` or `0`
+	Truthy(String),
+	/// Negation of `Truthy`
+	Not(String),
+	/// The variable is equal to a literal
+	Equal(String, String),
+	/// The variable is not equal to a literal
+	NotEqual(String, String),
+}
+
+impl Condition {
+	fn path(&self) -> &str {
+		match self {
+			Self::Truthy(path) | Self::Not(path) => path,
+			Self::Equal(path, _) | Self::NotEqual(path, _) => path,
+		}
+	}
+}
+
+/// A node of a parsed template
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+	Text(String),
+	Output(Output),
+	If {
+		branches: Vec<(Condition, Vec<Node>)>,
+		otherwise: Vec<Node>,
+	},
+	For {
+		variable: String,
+		collection: String,
+		body: Vec<Node>,
+	},
+}
+
+/// Lexical token of a template
+#[derive(Debug)]
+enum Token {
+	Text(String),
+	Output(Output),
+	Tag { content: String, position: usize },
+}
+
+/// Token stream consumed by the parser
+type Tokens = std::iter::Peekable<std::vec::IntoIter<Token>>;
+
+/// Closing tag ending a block, with its position
+type ClosingTag = Option<(String, usize)>;
+
+/// A parsed notification message template
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+	nodes: Vec<Node>,
+}
+
+impl FromStr for Template {
+	type Err = TemplateError;
+
+	/// Parses a template strictly, rejecting invalid expressions and tags
+	fn from_str(source: &str) -> Result<Self, Self::Err> {
+		Self::parse(source, true)
+	}
+}
+
+impl Template {
+	/// Parses a template
+	///
+	/// In lenient mode, `${...}` expressions that cannot be parsed are kept as text.
+	fn parse(source: &str, strict: bool) -> Result<Self, TemplateError> {
+		let tokens = tokenize(source, strict)?;
+		let mut tokens = tokens.into_iter().peekable();
+		let (nodes, end) = parse_nodes(&mut tokens, &[])?;
+		if let Some((tag, position)) = end {
+			return Err(TemplateError::new(
+				format!("Unexpected tag '{}'", tag),
+				position,
+			));
+		}
+		Ok(Self { nodes })
+	}
+
+	/// Renders the template with the given variables
+	///
+	/// # Arguments
+	/// * `variables` - Map of variable names to values
+	pub fn render(&self, variables: &HashMap<String, String>) -> String {
+		let mut scope = Scope {
+			variables,
+			loops: Vec::new(),
+		};
+		let mut output = String::new();
+		render_nodes(&self.nodes, &mut scope, &mut output);
+		output
+	}
+
+	/// Checks that every variable used by the template is a known match variable
+	///
+	/// # Arguments
+	/// * `extra_variables` - Variables available in addition to the match variables, a trailing
+	///   `*` making every path under the prefix available
+	pub fn validate_variables(&self, extra_variables: &[&str]) -> Result<(), TemplateError> {
+		self.validate_variables_with(|path| {
+			is_match_variable(path)
+				|| extra_variables
+					.iter()
+					.any(|extra| match extra.strip_suffix('*') {
+						Some(prefix) => path.starts_with(prefix),
+						None => *extra == path,
+					})
+		})
+	}
+
+	/// Checks that every variable used by the template is known to the given predicate
+	///
+	/// Loop items are passed resolved to their collection with a `*` index, and `${loop.index}`
+	/// is always known inside loops.
+	///
+	/// # Arguments
+	/// * `is_known` - Returns whether a variable path is available
+	pub fn validate_variables_with(
+		&self,
+		is_known: impl Fn(&str) -> bool,
+	) -> Result<(), TemplateError> {
+		let mut unknown = Vec::new();
+		visit_paths(&self.nodes, &mut Vec::new(), &mut |path, in_loop| {
+			if !(is_known(path) || (in_loop && path == LOOP_INDEX)) {
+				unknown.push(path.to_string());
+			}
+		});
+
+		match unknown.first() {
+			Some(path) => Err(TemplateError(format!(
+				"Unknown template variable '{}'",
+				path
+			))),
+			None => Ok(()),
+		}
+	}
+
+	/// Returns the event and function argument names used by the template, including those of
+	/// the matches looped over in digests
+	///
+	/// # Returns
+	/// * `Vec<(String, String)>` - Pairs of collection (`events` or `functions`) and argument name
+	pub fn argument_names(&self) -> Vec<(String, String)> {
+		let mut names = Vec::new();
+		let digest_item_prefix = format!("{}.*.", DIGEST_MATCHES);
+		visit_paths(&self.nodes, &mut Vec::new(), &mut |path, _| {
+			let path = path.strip_prefix(&digest_item_prefix).unwrap_or(path);
+			let segments = path.split('.').collect::<Vec<_>>();
+			if let [collection @ ("events" | "functions"), _, "args", name, ..] = segments[..] {
+				let name = (collection.to_string(), name.to_string());
+				if !names.contains(&name) {
+					names.push(name);
+				}
+			}
+		});
+		names
+	}
+}
+
+/// Renders a template with the given variables
+///
+/// Templates that cannot be parsed fall back to plain `${variable}` substitution.
+///
+/// # Arguments
+/// * `template` - Template to render
+/// * `variables` - Map of variable names to values
+pub fn render_template(template: &str, variables: &HashMap<String, String>) -> String {
+	match Template::parse(template, false) {
+		Ok(parsed) => parsed.render(variables),
+		Err(_) => variables
+			.iter()
+			.fold(template.to_string(), |message, (key, value)| {
+				message.replace(&format!("${{{}}}", key), value)
+			}),
+	}
+}
+
+/// Validates a message template at configuration load
+///
+/// # Arguments
+/// * `template` - Template to validate
+/// * `extra_variables` - Variables available in addition to the match variables, a trailing `*`
+///   making every path under the prefix available
+pub fn validate_template(template: &str, extra_variables: &[&str]) -> Result<(), TemplateError> {
+	template
+		.parse::<Template>()?
+		.validate_variables(extra_variables)
+}
+
+/// Renders every string of a JSON template with the given variables
+///
+/// Object keys and non-string values are kept as they are.
+///
+/// # Arguments
+/// * `template` - JSON document whose strings are message templates
+/// * `variables` - Map of variable names to values
+pub fn render_json_template(
+	template: &serde_json::Value,
+	variables: &HashMap<String, String>,
+) -> serde_json::Value {
+	match template {
+		serde_json::Value::String(value) => {
+			serde_json::Value::String(render_template(value, variables))
+		}
+		serde_json::Value::Array(items) => serde_json::Value::Array(
+			items
+				.iter()
+				.map(|item| render_json_template(item, variables))
+				.collect(),
+		),
+		serde_json::Value::Object(fields) => serde_json::Value::Object(
+			fields
+				.iter()
+				.map(|(key, value)| (key.clone(), render_json_template(value, variables)))
+				.collect(),
+		),
+		value => value.clone(),
+	}
+}
+
+/// Validates every string of a JSON template at configuration load
+///
+/// # Arguments
+/// * `template` - JSON document whose strings are message templates
+pub fn validate_json_template(template: &serde_json::Value) -> Result<(), TemplateError> {
+	match template {
+		serde_json::Value::String(value) => validate_template(value, &[]),
+		serde_json::Value::Array(items) => items.iter().try_for_each(validate_json_template),
+		serde_json::Value::Object(fields) => fields.values().try_for_each(validate_json_template),
+		_ => Ok(()),
+	}
+}
+
+/// Checks whether a path is one of the variables built for a monitor match
+///
+/// Indexes of `events` and `functions` may be `*` for loop items.
+pub(super) fn is_match_variable(path: &str) -> bool {
+	let segments = path.split('.').collect::<Vec<_>>();
+	let is_index = |index: &str| index == "*" || index.parse::<usize>().is_ok();
+	match segments[..] {
+		["monitor", "name"] => true,
+		["transaction", "hash" | "from" | "to" | "value"] => true,
+		["token", "address" | "symbol" | "decimals"] => true,
+		["state", _] | ["state", _, "usd"] => true,
+		["poll", "block_number" | "timestamp"] => true,
+		["enrichment", _, ..] => true,
+		["events" | "functions", index, "signature"] => is_index(index),
+		["events" | "functions", index, "args", _, ..] => is_index(index),
+		_ => path == THROTTLE_SUPPRESSED_COUNT || path == THROTTLE_SUMMARY,
+	}
+}
+
+/// Calls `visit` with every variable path of the nodes, loop items being resolved to their
+/// collection with a `*` index
+fn visit_paths(
+	nodes: &[Node],
+	loops: &mut Vec<(String, String)>,
+	visit: &mut impl FnMut(&str, bool),
+) {
+	let resolve = |path: &str, loops: &[(String, String)]| {
+		for (variable, prefix) in loops.iter().rev() {
+			if path == variable {
+				return prefix.clone();
+			}
+			if let Some(rest) = path.strip_prefix(&format!("{}.", variable)) {
+				return format!("{}.{}", prefix, rest);
+			}
+		}
+		path.to_string()
+	};
+
+	for node in nodes {
+		match node {
+			Node::Text(_) => {}
+			Node::Output(output) => visit(&resolve(&output.path, loops), !loops.is_empty()),
+			Node::If {
+				branches,
+				otherwise,
+			} => {
+				for (condition, body) in branches {
+					visit(&resolve(condition.path(), loops), !loops.is_empty());
+					visit_paths(body, loops, visit);
+				}
+				visit_paths(otherwise, loops, visit);
+			}
+			Node::For {
+				variable,
+				collection,
+				body,
+			} => {
+				let collection = resolve(collection, loops);
+				loops.push((variable.clone(), format!("{}.*", collection)));
+				visit_paths(body, loops, visit);
+				loops.pop();
+			}
+		}
+	}
+}
+
+/// Splits a template into text, expressions and tags
+fn tokenize(source: &str, strict: bool) -> Result<Vec<Token>, TemplateError> {
+	let mut tokens = Vec::new();
+	let mut text = String::new();
+	let mut position = 0;
+
+	while position < source.len() {
+		let rest = &source[position..];
+		let next = [rest.find("${"), rest.find("{%")]
+			.into_iter()
+			.flatten()
+			.min();
+		let Some(offset) = next else {
+			text.push_str(rest);
+			break;
+		};
+		text.push_str(&rest[..offset]);
+		let start = position + offset;
+
+		if source[start..].starts_with("${") {
+			let Some(end) = find_closing_brace(source, start + 2) else {
+				if strict {
+					return Err(TemplateError::new("Unclosed expression", start));
+				}
+				text.push_str(&source[start..]);
+				break;
+			};
+			let expression = &source[start..=end];
+			match parse_output(&source[start + 2..end], expression) {
+				Ok(output) => {
+					if !text.is_empty() {
+						tokens.push(Token::Text(std::mem::take(&mut text)));
+					}
+					tokens.push(Token::Output(output));
+				}
+				Err(message) if strict => return Err(TemplateError::new(message, start)),
+				Err(_) => text.push_str(expression),
+			}
+			position = end + 1;
+		} else {
+			let Some(end) = source[start + 2..].find("%}") else {
+				return Err(TemplateError::new("Unclosed tag", start));
+			};
+			if !text.is_empty() {
+				tokens.push(Token::Text(std::mem::take(&mut text)));
+			}
+			tokens.push(Token::Tag {
+				content: source[start + 2..start + 2 + end].trim().to_string(),
+				position: start,
+			});
+			position = start + 2 + end + 2;
+		}
+	}
+
+	if !text.is_empty() {
+		tokens.push(Token::Text(text));
+	}
+	Ok(tokens)
+}
+
+/// Finds the brace closing an expression, ignoring braces inside string literals
+fn find_closing_brace(source: &str, from: usize) -> Option<usize> {
+	let mut in_string = false;
+	let mut escaped = false;
+	for (offset, c) in source[from..].char_indices() {
+		match c {
+			_ if escaped => escaped = false,
+			'\\' if in_string => escaped = true,
+			'"' => in_string = !in_string,
+			'}' if !in_string => return Some(from + offset),
+			_ => {}
+		}
+	}
+	None
+}
+
+/// Parses nodes until one of the given closing tags, which is returned with its position
+fn parse_nodes(
+	tokens: &mut Tokens,
+	closing_tags: &[&str],
+) -> Result<(Vec<Node>, ClosingTag), TemplateError> {
+	let mut nodes = Vec::new();
+
+	while let Some(token) = tokens.next() {
+		match token {
+			Token::Text(text) => nodes.push(Node::Text(text)),
+			Token::Output(output) => nodes.push(Node::Output(output)),
+			Token::Tag { content, position } => {
+				let keyword = content.split_whitespace().next().unwrap_or_default();
+				if closing_tags.contains(&keyword) {
+					return Ok((nodes, Some((content, position))));
+				}
+				match keyword {
+					"if" => nodes.push(parse_if(tokens, &content, position)?),
+					"for" => nodes.push(parse_for(tokens, &content, position)?),
+					_ => {
+						return Err(TemplateError::new(
+							format!("Unexpected tag '{}'", content),
+							position,
+						))
+					}
+				}
+			}
+		}
+	}
+
+	Ok((nodes, None))
+}
+
+/// Parses an `if` block, whose opening tag has already been consumed
+fn parse_if(tokens: &mut Tokens, content: &str, position: usize) -> Result<Node, TemplateError> {
+	let mut branches = Vec::new();
+	let mut condition = parse_condition(&content["if".len()..])
+		.map_err(|message| TemplateError::new(message, position))?;
+
+	loop {
+		let (body, end) = parse_nodes(tokens, &["elif", "else", "endif"])?;
+		let Some((tag, tag_position)) = end else {
+			return Err(TemplateError::new("Unclosed 'if' block", position));
+		};
+		branches.push((condition, body));
+
+		match tag.split_whitespace().next().unwrap_or_default() {
+			"elif" => {
+				condition = parse_condition(&tag["elif".len()..])
+					.map_err(|message| TemplateError::new(message, tag_position))?;
+			}
+			"else" => {
+				let (otherwise, end) = parse_nodes(tokens, &["endif"])?;
+				if end.is_none() {
+					return Err(TemplateError::new("Unclosed 'if' block", position));
+				}
+				return Ok(Node::If {
+					branches,
+					otherwise,
+				});
+			}
+			_ => {
+				return Ok(Node::If {
+					branches,
+					otherwise: Vec::new(),
+				})
+			}
+		}
+	}
+}
+
+/// Parses a `for` block, whose opening tag has already been consumed
+fn parse_for(tokens: &mut Tokens, content: &str, position: usize) -> Result<Node, TemplateError> {
+	let parts = content.split_whitespace().collect::<Vec<_>>();
+	let ["for", variable, "in", collection] = parts[..] else {
+		return Err(TemplateError::new(
+			"Expected '{% for <variable> in <collection> %}'",
+			position,
+		));
+	};
+	if !is_identifier(variable) || variable == "loop" || !is_path(collection) {
+		return Err(TemplateError::new(
+			format!("Invalid loop '{}'", content),
+			position,
+		));
+	}
+
+	let (body, end) = parse_nodes(tokens, &["endfor"])?;
+	if end.is_none() {
+		return Err(TemplateError::new("Unclosed 'for' block", position));
+	}
+	Ok(Node::For {
+		variable: variable.to_string(),
+		collection: collection.to_string(),
+		body,
+	})
+}
+
+/// Parses the condition of an `if` or `elif` tag
+fn parse_condition(condition: &str) -> Result<Condition, String> {
+	let condition = condition.trim();
+	let parsed = if let Some((path, value)) = condition.split_once("==") {
+		Condition::Equal(path.trim().to_string(), parse_literal(value.trim())?)
+	} else if let Some((path, value)) = condition.split_once("!=") {
+		Condition::NotEqual(path.trim().to_string(), parse_literal(value.trim())?)
+	} else if let Some(path) = condition.strip_prefix("not ") {
+		Condition::Not(path.trim().to_string())
+	} else {
+		Condition::Truthy(condition.to_string())
+	};
+
+	if !is_path(parsed.path()) {
+		return Err(format!("Invalid condition '{}'", condition));
+	}
+	Ok(parsed)
+}
+
+/// Parses a `${...}` expression
+fn parse_output(expression: &str, source: &str) -> Result<Output, String> {
+	let mut parts = split_outside_strings(expression, '|').into_iter();
+	let path = parts.next().unwrap_or_default().trim().to_string();
+	if !is_path(&path) {
+		return Err(format!("Invalid variable '{}'", path));
+	}
+
+	let filters = parts
+		.map(|filter| parse_filter(filter.trim()))
+		.collect::<Result<Vec<_>, _>>()?;
+
+	Ok(Output {
+		source: source.to_string(),
+		path,
+		filters,
+	})
+}
+
+/// Parses a filter such as `decimals(6)`
+fn parse_filter(filter: &str) -> Result<Filter, String> {
+	let (name, arguments) = match filter.split_once('(') {
+		Some((name, rest)) => {
+			let arguments = rest
+				.strip_suffix(')')
+				.ok_or_else(|| format!("Unclosed arguments of filter '{}'", name.trim()))?;
+			let arguments = split_outside_strings(arguments, ',')
+				.into_iter()
+				.map(|argument| parse_literal(argument.trim()))
+				.collect::<Result<Vec<_>, _>>()?;
+			(name.trim(), arguments)
+		}
+		None => (filter, Vec::new()),
+	};
+
+	let number = |argument: &String| {
+		argument
+			.parse::<u32>()
+			.map_err(|_| format!("Filter '{}' expects a number, got '{}'", name, argument))
+	};
+
+	match (name, &arguments[..]) {
+		("default", [fallback]) => Ok(Filter::Default(fallback.clone())),
+		("decimals", [decimals]) => Ok(Filter::Decimals(number(decimals)?)),
+		("token", []) => Ok(Filter::Token(None)),
+		("token", [symbol]) => Ok(Filter::Token(Some(symbol.clone()))),
+		("usd", []) => Ok(Filter::Usd(None)),
+		("usd", [symbol]) => Ok(Filter::Usd(Some(symbol.clone()))),
+		("hex_to_dec", []) => Ok(Filter::HexToDec),
+		("shorten", []) => Ok(Filter::Shorten(DEFAULT_SHORTEN_LENGTH)),
+		("shorten", [length]) => Ok(Filter::Shorten(number(length)? as usize)),
+		("explorer", [base_url]) => Ok(Filter::Explorer {
+			base_url: base_url.clone(),
+			kind: None,
+		}),
+		("explorer", [base_url, kind]) => Ok(Filter::Explorer {
+			base_url: base_url.clone(),
+			kind: Some(kind.clone()),
+		}),
+		("date", []) => Ok(Filter::Date(DEFAULT_DATE_FORMAT.to_string())),
+		("date", [format]) => {
+			if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+				return Err(format!("Invalid date format '{}'", format));
+			}
+			Ok(Filter::Date(format.clone()))
+		}
+		("round", [places]) => Ok(Filter::Round(number(places)?)),
+		("thousands", []) => Ok(Filter::Thousands),
+		("upper", []) => Ok(Filter::Upper),
+		("lower", []) => Ok(Filter::Lower),
+		(
+			"default" | "decimals" | "token" | "usd" | "hex_to_dec" | "shorten" | "explorer"
+			| "date" | "round" | "thousands" | "upper" | "lower",
+			_,
+		) => Err(format!("Invalid number of arguments for filter '{}'", name)),
+		_ => Err(format!("Unknown filter '{}'", name)),
+	}
+}
+
+/// Parses a filter argument or comparison operand: a double-quoted string or a bare word
+fn parse_literal(literal: &str) -> Result<String, String> {
+	if let Some(inner) = literal
+		.strip_prefix('"')
+		.and_then(|rest| rest.strip_suffix('"'))
+	{
+		return Ok(inner.replace("\\\"", "\"").replace("\\\\", "\\"));
+	}
+	if !literal.is_empty()
+		&& literal
+			.chars()
+			.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+	{
+		return Ok(literal.to_string());
+	}
+	Err(format!("Invalid literal '{}'", literal))
+}
+
+/// Splits on a separator, ignoring separators inside string literals
+fn split_outside_strings(input: &str, separator: char) -> Vec<&str> {
+	let mut parts = Vec::new();
+	let mut in_string = false;
+	let mut escaped = false;
+	let mut start = 0;
+	for (index, c) in input.char_indices() {
+		match c {
+			_ if escaped => escaped = false,
+			'\\' if in_string => escaped = true,
+			'"' => in_string = !in_string,
+			c if c == separator && !in_string => {
+				parts.push(&input[start..index]);
+				start = index + c.len_utf8();
+			}
+			_ => {}
+		}
+	}
+	parts.push(&input[start..]);
+	parts
+}
+
+fn is_identifier(name: &str) -> bool {
+	!name.is_empty()
+		&& name
+			.chars()
+			.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-'))
+}
+
+fn is_path(path: &str) -> bool {
+	path.split('.').all(is_identifier)
+}
+
+/// Loop being rendered
+struct LoopFrame {
+	variable: String,
+	prefix: String,
+	index: usize,
+}
+
+/// Variables available while rendering
+struct Scope<'a> {
+	variables: &'a HashMap<String, String>,
+	loops: Vec<LoopFrame>,
+}
+
+impl Scope<'_> {
+	/// Resolves loop item variables to the variables of their collection
+	fn resolve(&self, path: &str) -> String {
+		for frame in self.loops.iter().rev() {
+			if path == frame.variable {
+				return frame.prefix.clone();
+			}
+			if let Some(rest) = path.strip_prefix(&format!("{}.", frame.variable)) {
+				return format!("{}.{}", frame.prefix, rest);
+			}
+		}
+		path.to_string()
+	}
+
+	fn get(&self, path: &str) -> Option<String> {
+		if path == LOOP_INDEX {
+			if let Some(frame) = self.loops.last() {
+				return Some((frame.index + 1).to_string());
+			}
+		}
+		self.variables.get(&self.resolve(path)).cloned()
+	}
+
+	/// Checks whether a collection has an item at the given index
+	fn has_item(&self, collection: &str, index: usize) -> bool {
+		let item = format!("{}.{}", collection, index);
+		let item_prefix = format!("{}.", item);
+		self.variables
+			.keys()
+			.any(|key| *key == item || key.starts_with(&item_prefix))
+	}
+}
+
+fn render_nodes(nodes: &[Node], scope: &mut Scope, output: &mut String) {
+	for node in nodes {
+		match node {
+			Node::Text(text) => output.push_str(text),
+			Node::Output(expression) => output.push_str(&render_output(expression, scope)),
+			Node::If {
+				branches,
+				otherwise,
+			} => {
+				let body = branches
+					.iter()
+					.find(|(condition, _)| evaluate_condition(condition, scope))
+					.map(|(_, body)| body)
+					.unwrap_or(otherwise);
+				render_nodes(body, scope, output);
+			}
+			Node::For {
+				variable,
+				collection,
+				body,
+			} => {
+				let collection = scope.resolve(collection);
+				let mut index = 0;
+				while scope.has_item(&collection, index) {
+					scope.loops.push(LoopFrame {
+						variable: variable.clone(),
+						prefix: format!("{}.{}", collection, index),
+						index,
+					});
+					render_nodes(body, scope, output);
+					scope.loops.pop();
+					index += 1;
+				}
+			}
+		}
+	}
+}
+
+fn render_output(expression: &Output, scope: &Scope) -> String {
+	let mut value = scope.get(&expression.path).or_else(|| {
+		// `<variable>.usd` is the USD value of a raw amount of the token of the match
+		let amount = scope.get(expression.path.strip_suffix(".usd")?)?;
+		match_token_usd_value(&amount, scope)
+	});
+	for filter in &expression.filters {
+		value = match filter {
+			Filter::Default(fallback) => match value {
+				Some(value) if !value.is_empty() => Some(value),
+				_ => Some(fallback.clone()),
+			},
+			// The token of the match is described by the variables of the match
+			Filter::Token(None) => value.map(|value| {
+				let decimals = scope
+					.get("token.decimals")
+					.and_then(|decimals| decimals.parse().ok());
+				match (decimals, scope.get("token.symbol")) {
+					(Some(decimals), Some(symbol)) => {
+						format_token_amount(&value, decimals, &symbol).unwrap_or(value)
+					}
+					_ => value,
+				}
+			}),
+			Filter::Usd(None) => {
+				value.map(|value| match_token_usd_value(&value, scope).unwrap_or(value))
+			}
+			// A symbol with different decimals per network resolves to the token of the match
+			Filter::Token(Some(symbol)) => value.map(|value| {
+				token_decimals(symbol, scope.get("token.address").as_deref())
+					.and_then(|decimals| format_token_amount(&value, decimals, symbol))
+					.unwrap_or(value)
+			}),
+			Filter::Usd(Some(symbol)) => value.map(|value| {
+				token_decimals(symbol, scope.get("token.address").as_deref())
+					.and_then(|decimals| usd_value(&value, decimals, symbol))
+					.unwrap_or(value)
+			}),
+			// Values that a filter cannot handle are rendered unchanged
+			filter => value.map(|value| apply_filter(filter, &value).unwrap_or(value)),
+		};
+	}
+	value.unwrap_or_else(|| expression.source.clone())
+}
+
+fn evaluate_condition(condition: &Condition, scope: &Scope) -> bool {
+	let value = scope.get(condition.path());
+	let is_truthy = |value: &Option<String>| {
+		value
+			.as_deref()
+			.is_some_and(|value| !matches!(value, "" | "false" | "null" | "0"))
+	};
+	match condition {
+		Condition::Truthy(_) => is_truthy(&value),
+		Condition::Not(_) => !is_truthy(&value),
+		Condition::Equal(_, expected) => value.as_deref() == Some(expected.as_str()),
+		Condition::NotEqual(_, expected) => value.as_deref() != Some(expected.as_str()),
+	}
+}
+
+fn apply_filter(filter: &Filter, value: &str) -> Option<String> {
+	match filter {
+		Filter::Default(_) => Some(value.to_string()),
+		Filter::Decimals(decimals) => scale_decimals(value, *decimals),
+		Filter::Token(Some(symbol)) => {
+			format_token_amount(value, token_decimals(symbol, None)?, symbol)
+		}
+		Filter::Token(None) => None,
+		Filter::Usd(Some(symbol)) => usd_value(value, token_decimals(symbol, None)?, symbol),
+		Filter::Usd(None) => None,
+		Filter::HexToDec => {
+			let digits = value
+				.strip_prefix("0x")
+				.or_else(|| value.strip_prefix("0X"))?;
+			U256::from_str_radix(digits, 16)
+				.ok()
+				.map(|number| number.to_string())
+		}
+		Filter::Shorten(length) => {
+			let prefix_length = if value.starts_with("0x") {
+				length + 2
+			} else {
+				*length
+			};
+			let chars = value.chars().collect::<Vec<_>>();
+			if chars.len() <= prefix_length + length + 3 {
+				return Some(value.to_string());
+			}
+			Some(format!(
+				"{}...{}",
+				chars[..prefix_length].iter().collect::<String>(),
+				chars[chars.len() - length..].iter().collect::<String>()
+			))
+		}
+		Filter::Explorer { base_url, kind } => {
+			let kind = kind.as_deref().or_else(|| explorer_kind(value))?;
+			Some(format!(
+				"{}/{}/{}",
+				base_url.trim_end_matches('/'),
+				kind,
+				value
+			))
+		}
+		Filter::Date(format) => {
+			let timestamp = value.parse::<i64>().ok()?;
+			let date = DateTime::from_timestamp(timestamp, 0)?;
+			Some(date.format(format).to_string())
+		}
+		Filter::Round(places) => round_decimal(value, *places),
+		Filter::Thousands => {
+			let (sign, number) = split_sign(value);
+			let (integer, fraction) = number.split_once('.').unwrap_or((number, ""));
+			if integer.is_empty() || !integer.chars().all(|c| c.is_ascii_digit()) {
+				return None;
+			}
+			let grouped = integer
+				.as_bytes()
+				.rchunks(3)
+				.rev()
+				.map(|chunk| String::from_utf8_lossy(chunk).into_owned())
+				.collect::<Vec<_>>()
+				.join(",");
+			Some(match fraction {
+				"" => format!("{}{}", sign, grouped),
+				fraction => format!("{}{}.{}", sign, grouped, fraction),
+			})
+		}
+		Filter::Upper => Some(value.to_uppercase()),
+		Filter::Lower => Some(value.to_lowercase()),
+	}
+}
+
+/// Infers the block explorer path segment of a hash or address
+fn explorer_kind(value: &str) -> Option<&'static str> {
+	let is_hex = |digits: &str| digits.chars().all(|c| c.is_ascii_hexdigit());
+	match value.strip_prefix("0x") {
+		Some(digits) if digits.len() == 64 && is_hex(digits) => Some("tx"),
+		Some(digits) if digits.len() == 40 && is_hex(digits) => Some("address"),
+		None if value.len() == 64 && is_hex(value) => Some("tx"),
+		None if value.len() == 56 && value.starts_with('G') => Some("account"),
+		None if value.len() == 56 && value.starts_with('C') => Some("contract"),
+		_ => None,
+	}
+}
+
+fn split_sign(value: &str) -> (&str, &str) {
+	match value.strip_prefix('-') {
+		Some(number) => ("-", number),
+		None => ("", value),
+	}
+}
+
+/// Scales an integer down by a number of decimals, e.g. `1500000` with 6 decimals is `1.5`
+fn scale_decimals(value: &str, decimals: u32) -> Option<String> {
+	let (sign, digits) = split_sign(value);
+	if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+		return None;
+	}
+	let decimals = decimals as usize;
+	let padded = format!("{:0>width$}", digits, width = decimals + 1);
+	let (integer, fraction) = padded.split_at(padded.len() - decimals);
+	let integer = integer.trim_start_matches('0');
+	let integer = if integer.is_empty() { "0" } else { integer };
+	let fraction = fraction.trim_end_matches('0');
+	Some(match fraction {
+		"" => format!("{}{}", sign, integer),
+		fraction => format!("{}{}.{}", sign, integer, fraction),
+	})
+}
+
+/// Formats a raw token amount, e.g. `1500000` with 6 decimals and symbol `USDC` is `1.5 USDC`
+fn format_token_amount(value: &str, decimals: u32, symbol: &str) -> Option<String> {
+	scale_decimals(value, decimals).map(|amount| format!("{} {}", amount, symbol))
+}
+
+/// USD value of a raw amount of the token of the match, described by the variables of the match
+fn match_token_usd_value(value: &str, scope: &Scope) -> Option<String> {
+	let decimals = scope.get("token.decimals")?.parse().ok()?;
+	usd_value(value, decimals, &scope.get("token.symbol")?)
+}
+
+/// Converts a raw token amount to USD with two decimal places using the cached price of the
+/// token, e.g. `1500000` with 6 decimals at 0.9998 USD is `1499.70`
+fn usd_value(value: &str, decimals: u32, symbol: &str) -> Option<String> {
+	let amount = Decimal::from_str(&scale_decimals(value, decimals)?).ok()?;
+	let usd = amount.checked_mul(token_price(symbol).ok()?)?;
+	Some(format!("{:.2}", usd.round_dp(2)))
+}
+
+/// Rounds a decimal number half away from zero to a number of decimal places
+fn round_decimal(value: &str, places: u32) -> Option<String> {
+	let (sign, number) = split_sign(value);
+	let (integer, fraction) = number.split_once('.').unwrap_or((number, ""));
+	let is_digits = |digits: &str| digits.chars().all(|c| c.is_ascii_digit());
+	if integer.is_empty() || !is_digits(integer) || !is_digits(fraction) {
+		return None;
+	}
+
+	let places = places as usize;
+	let fraction = format!("{:0<width$}", fraction, width = places + 1);
+	let round_up = fraction.as_bytes()[places] >= b'5';
+	let mut digits = format!("{}{}", integer, &fraction[..places])
+		.into_bytes()
+		.into_iter()
+		.map(|digit| digit - b'0')
+		.collect::<Vec<_>>();
+
+	if round_up {
+		let mut carry = true;
+		for digit in digits.iter_mut().rev() {
+			if !carry {
+				break;
+			}
+			*digit += 1;
+			carry = *digit == 10;
+			if carry {
+				*digit = 0;
+			}
+		}
+		if carry {
+			digits.insert(0, 1);
+		}
+	}
+
+	let digits = digits
+		.into_iter()
+		.map(|digit| char::from(digit + b'0'))
+		.collect::<String>();
+	let (integer, fraction) = digits.split_at(digits.len() - places);
+	let integer = match integer.trim_start_matches('0') {
+		"" => "0",
+		integer => integer,
+	};
+	Some(match fraction {
+		"" => format!("{}{}", sign, integer),
+		fraction => format!("{}{}.{}", sign, integer, fraction),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn create_test_variables() -> HashMap<String, String> {
+		HashMap::from([
+			("monitor.name".to_string(), "Large Transfers".to_string()),
+			(
+				"transaction.hash".to_string(),
+				format!("0x{}", "ab".repeat(32)),
+			),
+			(
+				"events.0.args.from".to_string(),
+				"0x1234567890abcdef1234567890abcdef12345678".to_string(),
+			),
+			("events.0.args.value".to_string(), "1500000".to_string()),
+			("events.0.signature".to_string(), "Transfer".to_string()),
+			("events.1.args.value".to_string(), "2500000".to_string()),
+			("events.1.signature".to_string(), "Approval".to_string()),
+		])
+	}
+
+	#[test]
+	fn test_render_plain_variables() {
+		let variables = create_test_variables();
+		assert_eq!(
+			render_template("${monitor.name}: ${events.0.args.value}", &variables),
+			"Large Transfers: 1500000"
+		);
+		// Missing variables are left as written
+		assert_eq!(
+			render_template("Value is ${events.0.args.vaule}", &variables),
+			"Value is ${events.0.args.vaule}"
+		);
+		// Expressions that cannot be parsed are kept as text
+		assert_eq!(
+			render_template("${} ${a b} ${", &variables),
+			"${} ${a b} ${"
+		);
+	}
+
+	#[test]
+	fn test_render_filters() {
+		let variables = create_test_variables();
+		let cases = vec![
+			("${events.0.args.value | decimals(6)}", "1.5"),
+			("${events.0.args.value | decimals(8)}", "0.015"),
+			("${events.0.args.from | shorten}", "0x1234...5678"),
+			("${events.0.args.from | shorten(2)}", "0x12...78"),
+			(
+				"${events.0.args.from | upper}",
+				"0X1234567890ABCDEF1234567890ABCDEF12345678",
+			),
+			(
+				"${transaction.to | default(\"contract creation\")}",
+				"contract creation",
+			),
+			("${transaction.to | default(\"n/a\") | upper}", "N/A"),
+			(
+				"${events.0.args.from | explorer(\"https://etherscan.io/\")}",
+				"https://etherscan.io/address/0x1234567890abcdef1234567890abcdef12345678",
+			),
+			(
+				"${events.0.signature | explorer(\"https://etherscan.io\")}",
+				"Transfer",
+			),
+		];
+		for (template, expected) in cases {
+			assert_eq!(
+				render_template(template, &variables),
+				expected,
+				"{}",
+				template
+			);
+		}
+	}
+
+	#[test]
+	fn test_render_token_filter() {
+		crate::services::filter::register_tokens(vec![crate::models::Token {
+			symbol: "TEMPLATEUSD".to_string(),
+			decimals: 4,
+			addresses: vec![],
+			price: None,
+		}]);
+
+		let mut variables = create_test_variables();
+		// Without the token variables of the match the amount is rendered unchanged
+		assert_eq!(
+			render_template("${events.0.args.value | token}", &variables),
+			"1500000"
+		);
+
+		variables.insert("token.symbol".to_string(), "USDC".to_string());
+		variables.insert("token.decimals".to_string(), "6".to_string());
+		let cases = vec![
+			("${events.0.args.value | token}", "1.5 USDC"),
+			(
+				"${events.0.args.value | token(TEMPLATEUSD)}",
+				"150 TEMPLATEUSD",
+			),
+			("${events.0.args.value | token(UNKNOWNUSD)}", "1500000"),
+			("${events.0.signature | token}", "Transfer"),
+		];
+		for (template, expected) in cases {
+			assert_eq!(
+				render_template(template, &variables),
+				expected,
+				"{}",
+				template
+			);
+		}
+	}
+
+	#[tokio::test]
+	async fn test_render_usd_values() {
+		use crate::services::price::{
+			refresh_prices, register_price_feed, HttpPriceSource, PriceFeedPolicy,
+		};
+
+		let mut server = mockito::Server::new_async().await;
+		for (path, price) in [("/stable", "0.9998"), ("/native", "2000")] {
+			server
+				.mock("GET", path)
+				.with_status(200)
+				.with_body(format!(r#"{{"price": {}}}"#, price))
+				.create_async()
+				.await;
+		}
+		for (symbol, decimals, path) in [
+			("TEMPLATESTABLE", 6, "/stable"),
+			("TEMPLATEETH", 18, "/native"),
+		] {
+			crate::services::filter::register_tokens(vec![crate::models::Token {
+				symbol: symbol.to_string(),
+				decimals,
+				addresses: vec![],
+				price: None,
+			}]);
+			let source =
+				HttpPriceSource::new(format!("{}{}", server.url(), path), "price", HashMap::new())
+					.unwrap();
+			register_price_feed(
+				symbol,
+				std::sync::Arc::new(source),
+				PriceFeedPolicy::default(),
+			);
+		}
+		refresh_prices().await;
+
+		let mut variables = create_test_variables();
+		variables.insert("events.0.args.value".to_string(), "1500000000".to_string());
+		variables.insert("events.1.args.value".to_string(), "2500000000".to_string());
+		variables.insert(
+			"transaction.value".to_string(),
+			"1500000000000000000".to_string(),
+		);
+		// Without the token variables of the match USD values are not available
+		assert_eq!(
+			render_template("${events.0.args.value.usd}", &variables),
+			"${events.0.args.value.usd}"
+		);
+
+		variables.insert("token.symbol".to_string(), "TEMPLATESTABLE".to_string());
+		variables.insert("token.decimals".to_string(), "6".to_string());
+		let cases = vec![
+			("${events.0.args.value.usd}", "1499.70"),
+			("${events.1.args.value.usd | thousands}", "2,499.50"),
+			("${events.0.args.value | usd}", "1499.70"),
+			("${transaction.value | usd(TEMPLATEETH)}", "3000.00"),
+			(
+				"${transaction.value | usd(TEMPLATEUNKNOWN)}",
+				"1500000000000000000",
+			),
+			("${events.0.signature | usd}", "Transfer"),
+		];
+		for (template, expected) in cases {
+			assert_eq!(
+				render_template(template, &variables),
+				expected,
+				"{}",
+				template
+			);
+		}
+		assert!(validate_template("${events.0.args.value.usd}", &[]).is_ok());
+	}
+
+	#[test]
+	fn test_apply_number_filters() {
+		let cases = vec![
+			(Filter::HexToDec, "0xff", Some("255")),
+			(Filter::HexToDec, "ff", None),
+			(Filter::Decimals(2), "-12345", Some("-123.45")),
+			(Filter::Decimals(0), "100", Some("100")),
+			(Filter::Decimals(18), "abc", None),
+			(Filter::Round(2), "1.005", Some("1.01")),
+			(Filter::Round(0), "9.5", Some("10")),
+			(Filter::Round(3), "-0.1", Some("-0.100")),
+			(Filter::Thousands, "1234567.89", Some("1,234,567.89")),
+			(Filter::Thousands, "-100", Some("-100")),
+			(
+				Filter::Date(DEFAULT_DATE_FORMAT.to_string()),
+				"1700000000",
+				Some("2023-11-14 22:13:20 UTC"),
+			),
+			(Filter::Date("%Y".to_string()), "soon", None),
+		];
+		for (filter, value, expected) in cases {
+			assert_eq!(
+				apply_filter(&filter, value).as_deref(),
+				expected,
+				"{:?} {}",
+				filter,
+				value
+			);
+		}
+	}
+
+	#[test]
+	fn test_render_conditionals() {
+		let variables = create_test_variables();
+		let template = "{% if transaction.to %}to ${transaction.to}{% elif events.0.signature == \"Transfer\" %}transfer{% else %}other{% endif %}";
+		assert_eq!(render_template(template, &variables), "transfer");
+
+		let template = "{% if not transaction.to %}creation{% endif %}";
+		assert_eq!(render_template(template, &variables), "creation");
+
+		let template = "{% if events.0.signature != Transfer %}other{% else %}same{% endif %}";
+		assert_eq!(render_template(template, &variables), "same");
+	}
+
+	#[test]
+	fn test_render_loops() {
+		let variables = create_test_variables();
+		let template = "{% for event in events %}${loop.index}. ${event.signature} ${event.args.value | decimals(6)}\n{% endfor %}";
+		assert_eq!(
+			render_template(template, &variables),
+			"1. Transfer 1.5\n2. Approval 2.5\n"
+		);
+
+		let template = "{% for function in functions %}${function.signature}{% endfor %}none";
+		assert_eq!(render_template(template, &variables), "none");
+	}
+
+	#[test]
+	fn test_invalid_templates() {
+		let invalid_templates = vec![
+			"${events.0.args.value | unknown}",
+			"${events.0.args.value | decimals}",
+			"${events.0.args.value | decimals(six)}",
+			"${events.0.args.value | date(\"%Q\")}",
+			"${events.0.args.value",
+			"${}",
+			"{% if monitor.name %}unclosed",
+			"{% for event in %}{% endfor %}",
+			"{% endif %}",
+			"{% while %}",
+		];
+		for template in invalid_templates {
+			assert!(template.parse::<Template>().is_err(), "{}", template);
+		}
+	}
+
+	#[test]
+	fn test_validate_template_variables() {
+		let valid_templates = vec![
+			"${monitor.name} ${transaction.hash} ${events.0.args.value} ${functions.1.signature}",
+			"{% for event in events %}${loop.index} ${event.args.to}{% endfor %}",
+			"${throttle.summary}",
+			"${state.total_assets}",
+		];
+		for template in valid_templates {
+			assert!(validate_template(template, &[]).is_ok(), "{}", template);
+		}
+
+		let invalid_templates = vec![
+			"${trasaction.hash}",
+			"${events.0.arg.value}",
+			"${events.first.args.value}",
+			"${loop.index}",
+			"{% for event in events %}${event.value}{% endfor %}",
+		];
+		for template in invalid_templates {
+			assert!(validate_template(template, &[]).is_err(), "{}", template);
+		}
+
+		assert!(validate_template("${digest.count}", &["digest.count"]).is_ok());
+		assert!(validate_template("${event.args.value}", &["event.args.*"]).is_ok());
+		assert!(validate_template("${event.value}", &["event.args.*"]).is_err());
+	}
+
+	#[test]
+	fn test_argument_names() {
+		let template: Template =
+			"${events.0.args.value} {% for f in functions %}${f.args.to}{% endfor %}"
+				.parse()
+				.unwrap();
+		assert_eq!(
+			template.argument_names(),
+			vec![
+				("events".to_string(), "value".to_string()),
+				("functions".to_string(), "to".to_string())
+			]
+		);
+	}
+
+	#[test]
+	fn test_render_json_template() {
+		let variables = create_test_variables();
+		let template = serde_json::json!({
+			"monitor": "${monitor.name}",
+			"amount": "${events.0.args.value | decimals(6)}",
+			"tags": ["${events.0.signature}", "static"],
+			"severity": 2,
+			"${monitor.name}": null
+		});
+
+		assert_eq!(
+			render_json_template(&template, &variables),
+			serde_json::json!({
+				"monitor": "Large Transfers",
+				"amount": "1.5",
+				"tags": ["Transfer", "static"],
+				"severity": 2,
+				"${monitor.name}": null
+			})
+		);
+	}
+
+	#[test]
+	fn test_validate_json_template() {
+		assert!(validate_json_template(&serde_json::json!({
+			"hash": "${transaction.hash}",
+			"args": ["${events.0.args.value}"],
+			"count": 1
+		}))
+		.is_ok());
+		assert!(validate_json_template(&serde_json::json!({
+			"nested": { "hash": "${trasaction.hash}" }
+		}))
+		.is_err());
+		assert!(validate_json_template(&serde_json::json!(["{% if monitor.name %}"])).is_err());
+	}
+}
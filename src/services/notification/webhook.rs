@@ -16,13 +16,28 @@ use sha2::Sha256;
 use std::{collections::HashMap, sync::Arc};
 
 use crate::{
-	models::TriggerTypeConfig,
-	services::notification::{NotificationError, Notifier},
+	models::{
+		EVMMatchArguments, EVMReceiptLog, EVMTransaction, EVMTransactionReceipt, MatchConditions,
+		MonitorMatch, PollValue, StellarBlock, StellarMatchArguments, StellarTransaction,
+		TriggerTypeConfig, WebhookPayloadMode,
+	},
+	services::notification::{render_json_template, render_template, NotificationError, Notifier},
 };
 
 /// HMAC SHA256 type alias
 type HmacSha256 = Hmac<Sha256>;
 
+/// Schema version of the document posted in `match` payload mode
+///
+/// Bumped whenever a field is added, removed or changes meaning, so receivers can reject
+/// documents they do not understand. The document is built from dedicated types rather than the
+/// internal match models, so that changes to the models do not leak into it.
+///
+/// * `1`: the internal monitor match, including the monitor configuration
+/// * `2`: explicit match details without the monitor configuration, top-level `enrichment` and
+///   the `Poll` variant
+pub const MATCH_PAYLOAD_VERSION: u32 = 2;
+
 /// Represents a webhook configuration
 #[derive(Clone)]
 pub struct WebhookConfig {
@@ -37,7 +52,7 @@ pub struct WebhookConfig {
 }
 
 /// Implementation of webhook notifications via webhooks
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct WebhookNotifier {
 	/// Webhook URL for message delivery
 	pub url: String,
@@ -57,6 +72,123 @@ pub struct WebhookNotifier {
 	pub headers: Option<HashMap<String, String>>,
 	/// Payload fields to use for the webhook request
 	pub payload_fields: Option<HashMap<String, serde_json::Value>>,
+	/// Request body format
+	pub payload_mode: WebhookPayloadMode,
+}
+
+/// Document posted in `match` payload mode
+#[derive(Serialize, Debug)]
+pub struct MatchPayload<'a> {
+	/// Schema version of the document
+	pub version: u32,
+	/// Name of the monitor that matched
+	pub monitor: &'a str,
+	/// Network slug the match was found on
+	pub network: &'a str,
+	/// Block number, or ledger sequence on Stellar, containing the matched transaction
+	pub block_number: Option<u64>,
+	/// Chain specific details of the match
+	#[serde(rename = "match")]
+	pub details: MatchDetails<'a>,
+	/// Fields returned by trigger condition scripts to enrich the match
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub enrichment: Option<&'a serde_json::Map<String, serde_json::Value>>,
+}
+
+/// Chain specific details of a match document
+#[derive(Serialize, Debug)]
+pub enum MatchDetails<'a> {
+	EVM(EVMMatchDetails<'a>),
+	Stellar(StellarMatchDetails<'a>),
+	Poll(PollMatchDetails<'a>),
+}
+
+/// Details of an EVM match
+#[derive(Serialize, Debug)]
+pub struct EVMMatchDetails<'a> {
+	/// Transaction that triggered the match
+	pub transaction: &'a EVMTransaction,
+	/// Transaction receipt with execution results
+	pub receipt: Option<&'a EVMTransactionReceipt>,
+	/// Transaction logs
+	pub logs: Option<&'a [EVMReceiptLog]>,
+	/// Conditions that were matched
+	pub matched_on: &'a MatchConditions,
+	/// Decoded arguments from the matched conditions
+	pub matched_on_args: Option<&'a EVMMatchArguments>,
+}
+
+/// Details of a Stellar match
+#[derive(Serialize, Debug)]
+pub struct StellarMatchDetails<'a> {
+	/// Transaction that triggered the match
+	pub transaction: &'a StellarTransaction,
+	/// Ledger containing the matched transaction
+	pub ledger: &'a StellarBlock,
+	/// Conditions that were matched
+	pub matched_on: &'a MatchConditions,
+	/// Decoded arguments from the matched conditions
+	pub matched_on_args: Option<&'a StellarMatchArguments>,
+}
+
+/// Details of a polling monitor match
+#[derive(Serialize, Debug)]
+pub struct PollMatchDetails<'a> {
+	/// Timestamp of the block in seconds since the Unix epoch, if known
+	pub timestamp: Option<u64>,
+	/// Values read by the poll
+	pub values: &'a [PollValue],
+}
+
+impl<'a> MatchPayload<'a> {
+	/// Builds the payload document of a monitor match
+	pub fn new(monitor_match: &'a MonitorMatch) -> Self {
+		let (monitor, network, block_number, details, enrichment) = match monitor_match {
+			MonitorMatch::EVM(evm_match) => (
+				&evm_match.monitor.name,
+				&evm_match.network_slug,
+				evm_match.transaction.block_number.map(|n| n.to::<u64>()),
+				MatchDetails::EVM(EVMMatchDetails {
+					transaction: &evm_match.transaction,
+					receipt: evm_match.receipt.as_ref(),
+					logs: evm_match.logs.as_deref(),
+					matched_on: &evm_match.matched_on,
+					matched_on_args: evm_match.matched_on_args.as_ref(),
+				}),
+				evm_match.enrichment.as_ref(),
+			),
+			MonitorMatch::Stellar(stellar_match) => (
+				&stellar_match.monitor.name,
+				&stellar_match.network_slug,
+				Some(u64::from(stellar_match.transaction.ledger)),
+				MatchDetails::Stellar(StellarMatchDetails {
+					transaction: &stellar_match.transaction,
+					ledger: &stellar_match.ledger,
+					matched_on: &stellar_match.matched_on,
+					matched_on_args: stellar_match.matched_on_args.as_ref(),
+				}),
+				stellar_match.enrichment.as_ref(),
+			),
+			MonitorMatch::Poll(poll_match) => (
+				&poll_match.monitor.name,
+				&poll_match.network_slug,
+				Some(poll_match.block_number),
+				MatchDetails::Poll(PollMatchDetails {
+					timestamp: poll_match.timestamp,
+					values: &poll_match.values,
+				}),
+				poll_match.enrichment.as_ref(),
+			),
+		};
+		Self {
+			version: MATCH_PAYLOAD_VERSION,
+			monitor,
+			network,
+			block_number,
+			details,
+			enrichment,
+		}
+	}
 }
 
 /// Represents a formatted webhook message
@@ -94,6 +226,7 @@ impl WebhookNotifier {
 			secret: config.secret,
 			headers: Some(headers),
 			payload_fields: config.payload_fields,
+			payload_mode: WebhookPayloadMode::default(),
 		})
 	}
 
@@ -105,11 +238,47 @@ impl WebhookNotifier {
 	/// # Returns
 	/// * `String` - Formatted message with variables replaced
 	pub fn format_message(&self, variables: &HashMap<String, String>) -> String {
-		let mut message = self.body_template.clone();
-		for (key, value) in variables {
-			message = message.replace(&format!("${{{}}}", key), value);
+		render_template(&self.body_template, variables)
+	}
+
+	/// Builds the JSON request body of the `match` and `template` payload modes
+	///
+	/// # Arguments
+	/// * `variables` - Map of variable names to values
+	/// * `monitor_match` - Monitor match being notified, if any
+	///
+	/// # Returns
+	/// * `Result<Option<serde_json::Value>, NotificationError>` - Request body, or `None` in
+	///   `message` mode
+	pub fn format_payload(
+		&self,
+		variables: &HashMap<String, String>,
+		monitor_match: Option<&MonitorMatch>,
+	) -> Result<Option<serde_json::Value>, NotificationError> {
+		match &self.payload_mode {
+			WebhookPayloadMode::Message => Ok(None),
+			WebhookPayloadMode::Match => {
+				let monitor_match = monitor_match.ok_or_else(|| {
+					NotificationError::config_error(
+						"Match payload mode requires a monitor match".to_string(),
+						None,
+						None,
+					)
+				})?;
+				serde_json::to_value(MatchPayload::new(monitor_match))
+					.map(Some)
+					.map_err(|e| {
+						NotificationError::internal_error(
+							format!("Failed to serialize monitor match: {}", e),
+							Some(e.into()),
+							None,
+						)
+					})
+			}
+			WebhookPayloadMode::Template { template } => {
+				Ok(Some(render_json_template(template, variables)))
+			}
 		}
-		message
 	}
 
 	/// Creates a Webhook notifier from a trigger configuration
@@ -130,6 +299,7 @@ impl WebhookNotifier {
 			method,
 			secret,
 			headers,
+			payload,
 			..
 		} = config
 		{
@@ -144,7 +314,9 @@ impl WebhookNotifier {
 				payload_fields: None,
 			};
 
-			WebhookNotifier::new(webhook_config, http_client)
+			let mut notifier = WebhookNotifier::new(webhook_config, http_client)?;
+			notifier.payload_mode = payload.clone().unwrap_or_default();
+			Ok(notifier)
 		} else {
 			let msg = format!("Invalid webhook configuration: {:?}", config);
 			Err(NotificationError::config_error(msg, None, None))
@@ -155,6 +327,22 @@ impl WebhookNotifier {
 		&self,
 		secret: &str,
 		payload: &WebhookMessage,
+	) -> Result<(String, String), NotificationError> {
+		self.sign_content(secret, &format!("{:?}", payload))
+	}
+
+	/// Signs request content with HMAC SHA256 over the content followed by the timestamp
+	///
+	/// # Arguments
+	/// * `secret` - Secret used as the HMAC key
+	/// * `content` - Content to sign
+	///
+	/// # Returns
+	/// * `Result<(String, String), NotificationError>` - Hex signature and millisecond timestamp
+	pub fn sign_content(
+		&self,
+		secret: &str,
+		content: &str,
 	) -> Result<(String, String), NotificationError> {
 		// Explicitly reject empty secret, because `HmacSha256::new_from_slice` currently allows empty secrets
 		if secret.is_empty() {
@@ -173,7 +361,7 @@ impl WebhookNotifier {
 		})?; // Handle error if secret is invalid
 
 		// Create the message to sign
-		let message = format!("{:?}{}", payload, timestamp);
+		let message = format!("{}{}", content, timestamp);
 		mac.update(message.as_bytes());
 
 		// Get the HMAC result
@@ -213,6 +401,61 @@ impl Notifier for WebhookNotifier {
 		&self,
 		message: &str,
 		mut payload_fields: HashMap<String, serde_json::Value>,
+	) -> Result<(), NotificationError> {
+		// Merge with default payload fields if they exist
+		if let Some(default_fields) = &self.payload_fields {
+			for (key, value) in default_fields {
+				if !payload_fields.contains_key(key) {
+					payload_fields.insert(key.clone(), value.clone());
+				}
+			}
+		}
+
+		// The signature covers the title and message rather than the serialized fields
+		let payload_for_signing = WebhookMessage {
+			title: self.title.clone(),
+			body: message.to_string(),
+		};
+		let body = serde_json::to_string(&payload_fields).map_err(|e| {
+			NotificationError::internal_error(
+				format!("Failed to serialize webhook payload: {}", e),
+				Some(e.into()),
+				None,
+			)
+		})?;
+
+		self.send_request(body, &format!("{:?}", payload_for_signing))
+			.await
+	}
+}
+
+impl WebhookNotifier {
+	/// Sends a JSON document as the webhook request body
+	///
+	/// When a secret is configured, the signature covers the serialized document.
+	///
+	/// # Arguments
+	/// * `payload` - JSON document to send
+	///
+	/// # Returns
+	/// * `Result<(), NotificationError>` - Success or error
+	pub async fn notify_json(&self, payload: &serde_json::Value) -> Result<(), NotificationError> {
+		let body = payload.to_string();
+		self.send_request(body.clone(), &body).await
+	}
+
+	/// Sends a webhook request with the configured URL parameters, method and headers
+	///
+	/// # Arguments
+	/// * `body` - Serialized JSON request body
+	/// * `signed_content` - Content covered by the request signature
+	///
+	/// # Returns
+	/// * `Result<(), NotificationError>` - Success or error
+	async fn send_request(
+		&self,
+		body: String,
+		signed_content: &str,
 	) -> Result<(), NotificationError> {
 		let mut url = self.url.clone();
 		// Add URL parameters if present
@@ -226,15 +469,6 @@ impl Notifier for WebhookNotifier {
 			}
 		}
 
-		// Merge with default payload fields if they exist
-		if let Some(default_fields) = &self.payload_fields {
-			for (key, value) in default_fields {
-				if !payload_fields.contains_key(key) {
-					payload_fields.insert(key.clone(), value.clone());
-				}
-			}
-		}
-
 		let method = if let Some(ref m) = self.method {
 			Method::from_bytes(m.as_bytes()).unwrap_or(Method::POST)
 		} else {
@@ -249,17 +483,10 @@ impl Notifier for WebhookNotifier {
 		);
 
 		if let Some(secret) = &self.secret {
-			// Create a WebhookMessage for signing
-			let payload_for_signing = WebhookMessage {
-				title: self.title.clone(),
-				body: message.to_string(),
-			};
-
 			let (signature, timestamp) =
-				self.sign_request(secret, &payload_for_signing)
-					.map_err(|e| {
-						NotificationError::internal_error(e.to_string(), Some(e.into()), None)
-					})?;
+				self.sign_content(secret, signed_content).map_err(|e| {
+					NotificationError::internal_error(e.to_string(), Some(e.into()), None)
+				})?;
 
 			// Add signature headers
 			headers.insert(
@@ -310,7 +537,7 @@ impl Notifier for WebhookNotifier {
 			.client
 			.request(method, url.as_str())
 			.headers(headers)
-			.json(&payload_fields)
+			.body(body)
 			.send()
 			.await
 			.map_err(|e| {
@@ -338,9 +565,20 @@ impl Notifier for WebhookNotifier {
 #[cfg(test)]
 mod tests {
 	use crate::{
-		models::{NotificationMessage, SecretString, SecretValue},
-		utils::{tests::create_test_http_client, HttpRetryConfig},
+		models::{
+			EVMBaseTransaction as BaseTransaction, EVMMonitorMatch, EVMTransaction,
+			EVMTransactionReceipt, MatchConditions, NotificationMessage, PollMonitorMatch,
+			PollValue, SecretString, SecretValue,
+		},
+		utils::{
+			tests::{
+				builders::evm::monitor::MonitorBuilder, create_test_http_client,
+				evm::transaction::TransactionBuilder,
+			},
+			HttpRetryConfig,
+		},
 	};
+	use alloy::primitives::U64;
 
 	use super::*;
 	use mockito::{Matcher, Mock};
@@ -377,9 +615,27 @@ mod tests {
 				body: "Test message ${value}".to_string(),
 			},
 			retry_policy: HttpRetryConfig::default(),
+			payload: None,
 		}
 	}
 
+	fn create_test_monitor_match() -> MonitorMatch {
+		let transaction = TransactionBuilder::new().build();
+		MonitorMatch::EVM(Box::new(EVMMonitorMatch {
+			monitor: MonitorBuilder::new().name("test").build(),
+			transaction: EVMTransaction(BaseTransaction {
+				block_number: Some(U64::from(123)),
+				..transaction.0
+			}),
+			receipt: Some(EVMTransactionReceipt::default()),
+			logs: Some(vec![]),
+			network_slug: "ethereum_mainnet".to_string(),
+			matched_on: MatchConditions::default(),
+			matched_on_args: None,
+			enrichment: None,
+		}))
+	}
+
 	////////////////////////////////////////////////////////////
 	// format_message tests
 	////////////////////////////////////////////////////////////
@@ -427,6 +683,132 @@ mod tests {
 		assert_eq!(result, "");
 	}
 
+	////////////////////////////////////////////////////////////
+	// format_payload tests
+	////////////////////////////////////////////////////////////
+
+	#[test]
+	fn test_format_payload_message_mode() {
+		let notifier =
+			create_test_notifier("https://webhook.example.com", "Test message", None, None);
+		let result = notifier
+			.format_payload(&HashMap::new(), Some(&create_test_monitor_match()))
+			.unwrap();
+		assert!(result.is_none());
+	}
+
+	#[test]
+	fn test_format_payload_match_mode() {
+		let mut notifier =
+			create_test_notifier("https://webhook.example.com", "Test message", None, None);
+		notifier.payload_mode = WebhookPayloadMode::Match;
+
+		let monitor_match = create_test_monitor_match();
+		let payload = notifier
+			.format_payload(&HashMap::new(), Some(&monitor_match))
+			.unwrap()
+			.unwrap();
+
+		assert_eq!(payload["version"], json!(MATCH_PAYLOAD_VERSION));
+		assert_eq!(payload["monitor"], json!("test"));
+		assert_eq!(payload["network"], json!("ethereum_mainnet"));
+		assert_eq!(payload["block_number"], json!(123));
+		assert_eq!(
+			payload["match"]["EVM"]["transaction"],
+			serde_json::to_value(match &monitor_match {
+				MonitorMatch::EVM(evm_match) => &evm_match.transaction,
+				_ => unreachable!(),
+			})
+			.unwrap()
+		);
+		// The monitor configuration is not part of the document
+		assert!(payload["match"]["EVM"].get("monitor").is_none());
+
+		// Digests have no single match to send
+		let result = notifier.format_payload(&HashMap::new(), None);
+		assert!(matches!(result, Err(NotificationError::ConfigError(_))));
+	}
+
+	#[test]
+	fn test_match_payload_schema() {
+		// Bump MATCH_PAYLOAD_VERSION when this document changes
+		let poll_match = MonitorMatch::Poll(Box::new(PollMonitorMatch {
+			monitor: MonitorBuilder::new().name("Treasury Balance").build(),
+			network_slug: "ethereum_mainnet".to_string(),
+			block_number: 100,
+			timestamp: Some(1_700_000_000),
+			values: vec![PollValue {
+				name: "balance".to_string(),
+				value: "5".to_string(),
+				previous: "10".to_string(),
+				kind: "uint256".to_string(),
+			}],
+			enrichment: Some(serde_json::Map::from_iter([(
+				"risk".to_string(),
+				json!("high"),
+			)])),
+		}));
+		assert_eq!(
+			serde_json::to_value(MatchPayload::new(&poll_match)).unwrap(),
+			json!({
+				"version": 2,
+				"monitor": "Treasury Balance",
+				"network": "ethereum_mainnet",
+				"block_number": 100,
+				"match": {
+					"Poll": {
+						"timestamp": 1_700_000_000,
+						"values": [{
+							"name": "balance",
+							"value": "5",
+							"previous": "10",
+							"kind": "uint256"
+						}]
+					}
+				},
+				"enrichment": { "risk": "high" }
+			})
+		);
+
+		let payload =
+			serde_json::to_value(MatchPayload::new(&create_test_monitor_match())).unwrap();
+		let keys = |value: &serde_json::Value| {
+			value
+				.as_object()
+				.unwrap()
+				.keys()
+				.cloned()
+				.collect::<Vec<_>>()
+		};
+		assert_eq!(
+			keys(&payload),
+			vec!["block_number", "match", "monitor", "network", "version"]
+		);
+		assert_eq!(
+			keys(&payload["match"]["EVM"]),
+			vec![
+				"logs",
+				"matched_on",
+				"matched_on_args",
+				"receipt",
+				"transaction"
+			]
+		);
+	}
+
+	#[test]
+	fn test_format_payload_template_mode() {
+		let mut notifier =
+			create_test_notifier("https://webhook.example.com", "Test message", None, None);
+		notifier.payload_mode = WebhookPayloadMode::Template {
+			template: json!({ "hash": "${transaction.hash}", "severity": 3 }),
+		};
+
+		let variables = HashMap::from([("transaction.hash".to_string(), "0xabc".to_string())]);
+		let payload = notifier.format_payload(&variables, None).unwrap();
+		assert_eq!(payload, Some(json!({ "hash": "0xabc", "severity": 3 })));
+	}
+
 	////////////////////////////////////////////////////////////
 	// sign_request tests
 	////////////////////////////////////////////////////////////
@@ -497,6 +879,8 @@ mod tests {
 				title: "Test Alert".to_string(),
 				body: "Test message ${value}".to_string(),
 			},
+			explorer_url: None,
+			blocks: None,
 			retry_policy: HttpRetryConfig::default(),
 		};
 
@@ -549,6 +933,39 @@ mod tests {
 		mock.assert();
 	}
 
+	#[tokio::test]
+	async fn test_notify_json_signs_body() {
+		let payload = json!({ "version": 1, "monitor": "test" });
+		let mut server = mockito::Server::new_async().await;
+		let mock = server
+			.mock("POST", "/")
+			.match_header("X-Signature", Matcher::Regex("^[0-9a-f]{64}$".to_string()))
+			.match_header("X-Timestamp", Matcher::Regex("^[0-9]+$".to_string()))
+			.match_body(Matcher::Json(payload.clone()))
+			.with_status(200)
+			.create_async()
+			.await;
+
+		let notifier = create_test_notifier(
+			server.url().as_str(),
+			"Test message",
+			Some("top-secret"),
+			None,
+		);
+
+		let result = notifier.notify_json(&payload).await;
+		assert!(result.is_ok());
+		mock.assert();
+
+		// The signature is computed over the exact body followed by the timestamp
+		let (signature, timestamp) = notifier
+			.sign_content("top-secret", &payload.to_string())
+			.unwrap();
+		let mut mac = HmacSha256::new_from_slice(b"top-secret").unwrap();
+		mac.update(format!("{}{}", payload, timestamp).as_bytes());
+		assert_eq!(signature, hex::encode(mac.finalize().into_bytes()));
+	}
+
 	////////////////////////////////////////////////////////////
 	// notify header validation tests
 	////////////////////////////////////////////////////////////
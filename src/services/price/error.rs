@@ -0,0 +1,108 @@
+//! Price error types and handling.
+//!
+//! Provides error types for fetching token prices and reading them from the cache.
+
+use crate::utils::logging::error::{ErrorContext, TraceableError};
+use std::collections::HashMap;
+use thiserror::Error as ThisError;
+
+/// Represents errors that can occur while fetching or reading token prices
+#[derive(ThisError, Debug)]
+pub enum PriceError {
+	/// Errors related to reaching the source of a price
+	#[error("Price fetch error: {0}")]
+	FetchError(Box<ErrorContext>),
+
+	/// Errors related to malformed or invalid prices returned by a source
+	#[error("Price parse error: {0}")]
+	ParseError(Box<ErrorContext>),
+
+	/// No price is available for a token
+	#[error("Price not available: {0}")]
+	NotAvailable(Box<ErrorContext>),
+
+	/// The cached price of a token is older than its maximum age
+	#[error("Stale price: {0}")]
+	StalePrice(Box<ErrorContext>),
+}
+
+impl PriceError {
+	// Fetch error
+	pub fn fetch_error(
+		msg: impl Into<String>,
+		source: Option<Box<dyn std::error::Error + Send + Sync + 'static>>,
+		metadata: Option<HashMap<String, String>>,
+	) -> Self {
+		Self::FetchError(Box::new(ErrorContext::new_with_log(msg, source, metadata)))
+	}
+
+	// Parse error
+	pub fn parse_error(
+		msg: impl Into<String>,
+		source: Option<Box<dyn std::error::Error + Send + Sync + 'static>>,
+		metadata: Option<HashMap<String, String>>,
+	) -> Self {
+		Self::ParseError(Box::new(ErrorContext::new_with_log(msg, source, metadata)))
+	}
+
+	// Not available error
+	pub fn not_available(
+		msg: impl Into<String>,
+		source: Option<Box<dyn std::error::Error + Send + Sync + 'static>>,
+		metadata: Option<HashMap<String, String>>,
+	) -> Self {
+		Self::NotAvailable(Box::new(ErrorContext::new_with_log(msg, source, metadata)))
+	}
+
+	// Stale price error
+	pub fn stale_price(
+		msg: impl Into<String>,
+		source: Option<Box<dyn std::error::Error + Send + Sync + 'static>>,
+		metadata: Option<HashMap<String, String>>,
+	) -> Self {
+		Self::StalePrice(Box::new(ErrorContext::new_with_log(msg, source, metadata)))
+	}
+}
+
+impl TraceableError for PriceError {
+	fn trace_id(&self) -> String {
+		match self {
+			Self::FetchError(ctx) => ctx.trace_id.clone(),
+			Self::ParseError(ctx) => ctx.trace_id.clone(),
+			Self::NotAvailable(ctx) => ctx.trace_id.clone(),
+			Self::StalePrice(ctx) => ctx.trace_id.clone(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_error_formatting() {
+		let error = PriceError::fetch_error("test error", None, None);
+		assert_eq!(error.to_string(), "Price fetch error: test error");
+
+		let error = PriceError::parse_error("test error", None, None);
+		assert_eq!(error.to_string(), "Price parse error: test error");
+
+		let error = PriceError::not_available("test error", None, None);
+		assert_eq!(error.to_string(), "Price not available: test error");
+
+		let error = PriceError::stale_price(
+			"test error",
+			None,
+			Some(HashMap::from([("symbol".to_string(), "ETH".to_string())])),
+		);
+		assert_eq!(error.to_string(), "Stale price: test error [symbol=ETH]");
+	}
+
+	#[test]
+	fn test_trace_id_propagation() {
+		let error = PriceError::stale_price("test error", None, None);
+		if let PriceError::StalePrice(ctx) = &error {
+			assert_eq!(error.trace_id(), ctx.trace_id);
+		}
+	}
+}
@@ -0,0 +1,18 @@
+//! USD prices of tokens.
+//!
+//! Prices are read from pluggable sources: Chainlink aggregators on EVM networks, static price
+//! files and HTTP price APIs. Applications embedding the monitor can implement `PriceSource`
+//! for other providers. The price oracle caches the price of each token for a TTL and refuses
+//! prices older than their maximum age. Prices are refreshed before blocks are filtered, so that
+//! `usd()` in expressions and templates can read them without waiting on a source.
+
+mod error;
+mod oracle;
+mod sources;
+
+pub use error::PriceError;
+pub use oracle::{
+	refresh_prices, register_price_feed, token_price, Price, PriceFeedPolicy, PriceOracle,
+	PriceSource, DEFAULT_PRICE_MAX_AGE_SECS, DEFAULT_PRICE_TTL_SECS,
+};
+pub use sources::{ChainlinkPriceSource, FilePriceSource, HttpPriceSource};
@@ -0,0 +1,373 @@
+//! Cache of the USD prices of tokens.
+//!
+//! Each token with a price feed has its price refreshed from its source once its TTL has
+//! elapsed. A failed refresh keeps the previous price, which remains usable until it is older
+//! than the maximum age of the feed.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+use rust_decimal::Decimal;
+use std::{
+	collections::HashMap,
+	sync::{Arc, RwLock},
+	time::{Duration, Instant},
+};
+
+use crate::{models::TokenPrice, services::price::PriceError};
+
+/// Number of seconds a fetched price is reused when the feed does not set `ttl_secs`
+pub const DEFAULT_PRICE_TTL_SECS: u64 = 60;
+
+/// Maximum age in seconds of a price when the feed does not set `max_age_secs`
+pub const DEFAULT_PRICE_MAX_AGE_SECS: u64 = 3600;
+
+lazy_static! {
+	static ref PRICES: PriceOracle = PriceOracle::default();
+}
+
+/// USD price of a token
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Price {
+	/// Price of one whole token in USD
+	pub usd: Decimal,
+	/// Time the price was last updated by its source
+	pub updated_at: DateTime<Utc>,
+}
+
+/// Interface for sources of token prices
+#[async_trait]
+pub trait PriceSource: Send + Sync {
+	/// Fetches the current USD price of a token
+	///
+	/// # Arguments
+	/// * `symbol` - Symbol of the token
+	///
+	/// # Returns
+	/// * `Result<Price, PriceError>` - Price of the token, or an error if it could not be fetched
+	async fn fetch_price(&self, symbol: &str) -> Result<Price, PriceError>;
+}
+
+/// Caching policy of a price feed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriceFeedPolicy {
+	/// Time a fetched price is reused before it is refreshed
+	pub ttl: Duration,
+	/// Maximum age of a price before it is considered stale
+	pub max_age: Duration,
+}
+
+impl Default for PriceFeedPolicy {
+	fn default() -> Self {
+		Self {
+			ttl: Duration::from_secs(DEFAULT_PRICE_TTL_SECS),
+			max_age: Duration::from_secs(DEFAULT_PRICE_MAX_AGE_SECS),
+		}
+	}
+}
+
+impl From<&TokenPrice> for PriceFeedPolicy {
+	fn from(price: &TokenPrice) -> Self {
+		Self {
+			ttl: Duration::from_secs(price.ttl_secs.unwrap_or(DEFAULT_PRICE_TTL_SECS)),
+			max_age: Duration::from_secs(price.max_age_secs.unwrap_or(DEFAULT_PRICE_MAX_AGE_SECS)),
+		}
+	}
+}
+
+/// Last price of a feed and time of the last refresh attempt
+#[derive(Default)]
+struct FeedState {
+	price: Option<Price>,
+	refreshed_at: Option<Instant>,
+}
+
+/// Source of the price of a token with its cache
+struct PriceFeed {
+	source: Arc<dyn PriceSource>,
+	policy: PriceFeedPolicy,
+	state: RwLock<FeedState>,
+	/// Serializes refreshes so that concurrent blocks fetch a price only once
+	refresh_lock: tokio::sync::Mutex<()>,
+}
+
+impl PriceFeed {
+	fn is_expired(&self) -> bool {
+		self.state
+			.read()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+			.refreshed_at
+			.is_none_or(|refreshed_at| refreshed_at.elapsed() >= self.policy.ttl)
+	}
+
+	async fn refresh(&self, symbol: &str) {
+		if !self.is_expired() {
+			return;
+		}
+		let _guard = self.refresh_lock.lock().await;
+		if !self.is_expired() {
+			return;
+		}
+
+		let result = self.source.fetch_price(symbol).await;
+		let mut state = self
+			.state
+			.write()
+			.unwrap_or_else(|poisoned| poisoned.into_inner());
+		state.refreshed_at = Some(Instant::now());
+		match result {
+			Ok(price) => state.price = Some(price),
+			Err(e) => tracing::warn!("Failed to refresh the price of {}: {}", symbol, e),
+		}
+	}
+}
+
+/// Price feeds keyed by token symbol
+#[derive(Default)]
+pub struct PriceOracle {
+	feeds: RwLock<HashMap<String, Arc<PriceFeed>>>,
+}
+
+impl PriceOracle {
+	/// Registers the price feed of a token, replacing any feed previously registered for the
+	/// same symbol (case-insensitive)
+	pub fn register(&self, symbol: &str, source: Arc<dyn PriceSource>, policy: PriceFeedPolicy) {
+		let feed = PriceFeed {
+			source,
+			policy,
+			state: RwLock::new(FeedState::default()),
+			refresh_lock: tokio::sync::Mutex::new(()),
+		};
+		self.feeds
+			.write()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+			.insert(symbol.to_ascii_uppercase(), Arc::new(feed));
+	}
+
+	/// Refreshes the prices whose TTL has elapsed
+	///
+	/// Failures are logged and keep the previous price.
+	pub async fn refresh(&self) {
+		let feeds = self
+			.feeds
+			.read()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+			.iter()
+			.map(|(symbol, feed)| (symbol.clone(), feed.clone()))
+			.collect::<Vec<_>>();
+
+		futures::future::join_all(feeds.iter().map(|(symbol, feed)| feed.refresh(symbol))).await;
+	}
+
+	/// Returns the cached USD price of a token
+	///
+	/// # Arguments
+	/// * `symbol` - Symbol of the token (case-insensitive)
+	///
+	/// # Returns
+	/// * `Result<Decimal, PriceError>` - Price of one whole token in USD, or an error if the token
+	///   has no price feed, no price was fetched yet or the price is stale
+	pub fn price(&self, symbol: &str) -> Result<Decimal, PriceError> {
+		let feed = self
+			.feeds
+			.read()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+			.get(&symbol.to_ascii_uppercase())
+			.cloned()
+			.ok_or_else(|| {
+				PriceError::not_available(
+					format!("No price feed registered for token {}", symbol),
+					None,
+					None,
+				)
+			})?;
+
+		let price = feed
+			.state
+			.read()
+			.unwrap_or_else(|poisoned| poisoned.into_inner())
+			.price
+			.ok_or_else(|| {
+				PriceError::not_available(
+					format!("No price fetched yet for token {}", symbol),
+					None,
+					None,
+				)
+			})?;
+
+		let age = (Utc::now() - price.updated_at).to_std().unwrap_or_default();
+		if age > feed.policy.max_age {
+			return Err(PriceError::stale_price(
+				format!(
+					"Price of token {} was updated {}s ago, the maximum age is {}s",
+					symbol,
+					age.as_secs(),
+					feed.policy.max_age.as_secs()
+				),
+				None,
+				Some(HashMap::from([(
+					"updated_at".to_string(),
+					price.updated_at.to_rfc3339(),
+				)])),
+			));
+		}
+
+		Ok(price.usd)
+	}
+}
+
+/// Registers the price feed of a token globally
+pub fn register_price_feed(symbol: &str, source: Arc<dyn PriceSource>, policy: PriceFeedPolicy) {
+	PRICES.register(symbol, source, policy);
+}
+
+/// Refreshes the global prices whose TTL has elapsed
+pub async fn refresh_prices() {
+	PRICES.refresh().await;
+}
+
+/// Returns the cached USD price of a token from the global oracle
+pub fn token_price(symbol: &str) -> Result<Decimal, PriceError> {
+	PRICES.price(symbol)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::{
+		str::FromStr,
+		sync::atomic::{AtomicUsize, Ordering},
+	};
+
+	/// Source returning a fixed price, or failing once its prices are exhausted
+	struct MockSource {
+		prices: Vec<Price>,
+		fetches: AtomicUsize,
+	}
+
+	impl MockSource {
+		fn new(prices: Vec<Price>) -> Arc<Self> {
+			Arc::new(Self {
+				prices,
+				fetches: AtomicUsize::new(0),
+			})
+		}
+	}
+
+	#[async_trait]
+	impl PriceSource for MockSource {
+		async fn fetch_price(&self, _symbol: &str) -> Result<Price, PriceError> {
+			let fetch = self.fetches.fetch_add(1, Ordering::SeqCst);
+			self.prices
+				.get(fetch)
+				.copied()
+				.ok_or_else(|| PriceError::fetch_error("source unavailable", None, None))
+		}
+	}
+
+	fn price(usd: &str, age_secs: i64) -> Price {
+		Price {
+			usd: Decimal::from_str(usd).unwrap(),
+			updated_at: Utc::now() - chrono::Duration::seconds(age_secs),
+		}
+	}
+
+	fn policy(ttl_secs: u64, max_age_secs: u64) -> PriceFeedPolicy {
+		PriceFeedPolicy {
+			ttl: Duration::from_secs(ttl_secs),
+			max_age: Duration::from_secs(max_age_secs),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_prices_are_cached_for_ttl() {
+		let oracle = PriceOracle::default();
+		let source = MockSource::new(vec![price("3500.5", 0), price("3600", 0)]);
+		oracle.register("eth", source.clone(), policy(60, 3600));
+
+		assert!(matches!(
+			oracle.price("ETH"),
+			Err(PriceError::NotAvailable(_))
+		));
+
+		oracle.refresh().await;
+		oracle.refresh().await;
+		assert_eq!(
+			oracle.price("ETH").unwrap(),
+			Decimal::from_str("3500.5").unwrap()
+		);
+		assert_eq!(source.fetches.load(Ordering::SeqCst), 1);
+	}
+
+	#[tokio::test]
+	async fn test_expired_prices_are_refreshed() {
+		let oracle = PriceOracle::default();
+		let source = MockSource::new(vec![price("1.0", 0), price("0.99", 0)]);
+		oracle.register("USDC", source.clone(), policy(0, 3600));
+
+		oracle.refresh().await;
+		oracle.refresh().await;
+		assert_eq!(
+			oracle.price("usdc").unwrap(),
+			Decimal::from_str("0.99").unwrap()
+		);
+		assert_eq!(source.fetches.load(Ordering::SeqCst), 2);
+	}
+
+	#[tokio::test]
+	async fn test_failed_refresh_keeps_previous_price() {
+		let oracle = PriceOracle::default();
+		oracle.register(
+			"DAI",
+			MockSource::new(vec![price("1.001", 0)]),
+			policy(0, 3600),
+		);
+
+		oracle.refresh().await;
+		oracle.refresh().await;
+		assert_eq!(
+			oracle.price("DAI").unwrap(),
+			Decimal::from_str("1.001").unwrap()
+		);
+	}
+
+	#[tokio::test]
+	async fn test_stale_prices_are_rejected() {
+		let oracle = PriceOracle::default();
+		oracle.register(
+			"WBTC",
+			MockSource::new(vec![price("60000", 7200)]),
+			policy(60, 3600),
+		);
+
+		oracle.refresh().await;
+		assert!(matches!(
+			oracle.price("WBTC"),
+			Err(PriceError::StalePrice(_))
+		));
+	}
+
+	#[tokio::test]
+	async fn test_unknown_token() {
+		let oracle = PriceOracle::default();
+		assert!(matches!(
+			oracle.price("UNKNOWN"),
+			Err(PriceError::NotAvailable(_))
+		));
+	}
+
+	#[test]
+	fn test_policy_from_config() {
+		let config = TokenPrice {
+			source: crate::models::PriceSourceConfig::File {
+				path: "prices.json".to_string(),
+			},
+			ttl_secs: Some(30),
+			max_age_secs: None,
+		};
+		assert_eq!(
+			PriceFeedPolicy::from(&config),
+			policy(30, DEFAULT_PRICE_MAX_AGE_SECS)
+		);
+	}
+}
@@ -0,0 +1,443 @@
+//! Built-in sources of token prices.
+//!
+//! - `ChainlinkPriceSource` reads `latestRoundData()` from a Chainlink aggregator with `eth_call`
+//! - `FilePriceSource` reads a JSON file mapping token symbols to prices
+//! - `HttpPriceSource` requests a JSON document from a price API
+
+use alloy::primitives::{I256, U256};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::{
+	collections::HashMap,
+	path::PathBuf,
+	str::FromStr,
+	sync::{Arc, OnceLock},
+	time::Duration,
+};
+
+use crate::services::{
+	blockchain::EvmClientTrait,
+	price::{Price, PriceError, PriceSource},
+};
+
+/// Selector of the aggregator `decimals()` function
+const DECIMALS_SELECTOR: &str = "0x313ce567";
+
+/// Selector of the aggregator `latestRoundData()` function
+const LATEST_ROUND_DATA_SELECTOR: &str = "0xfeaf968c";
+
+/// Timeout of the requests to price APIs
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Price read from a Chainlink aggregator on an EVM network
+///
+/// The time of the price is the `updatedAt` of the latest round, so prices of aggregators that
+/// stopped updating become stale.
+pub struct ChainlinkPriceSource<C> {
+	client: Arc<C>,
+	address: String,
+	decimals: OnceLock<u32>,
+}
+
+impl<C> ChainlinkPriceSource<C> {
+	/// Creates a source reading the aggregator deployed at an address
+	pub fn new(client: Arc<C>, address: impl Into<String>) -> Self {
+		Self {
+			client,
+			address: address.into(),
+			decimals: OnceLock::new(),
+		}
+	}
+}
+
+impl<C: EvmClientTrait + Send + Sync> ChainlinkPriceSource<C> {
+	async fn call(&self, selector: &str) -> Result<Vec<u8>, PriceError> {
+		let result = self
+			.client
+			.call_contract(&self.address, selector, None)
+			.await
+			.map_err(|e| {
+				PriceError::fetch_error(
+					format!("Failed to call aggregator {}", self.address),
+					Some(e.into()),
+					None,
+				)
+			})?;
+		hex::decode(result.strip_prefix("0x").unwrap_or(&result)).map_err(|e| {
+			PriceError::parse_error(
+				format!("Invalid result of aggregator {}", self.address),
+				Some(Box::new(e)),
+				None,
+			)
+		})
+	}
+}
+
+#[async_trait]
+impl<C: EvmClientTrait + Send + Sync> PriceSource for ChainlinkPriceSource<C> {
+	async fn fetch_price(&self, _symbol: &str) -> Result<Price, PriceError> {
+		let decimals = match self.decimals.get() {
+			Some(decimals) => *decimals,
+			None => {
+				let decimals = decode_aggregator_decimals(&self.call(DECIMALS_SELECTOR).await?)?;
+				*self.decimals.get_or_init(|| decimals)
+			}
+		};
+		decode_round_data(&self.call(LATEST_ROUND_DATA_SELECTOR).await?, decimals)
+	}
+}
+
+/// Decodes the `uint8` returned by `decimals()`
+fn decode_aggregator_decimals(data: &[u8]) -> Result<u32, PriceError> {
+	data.get(..32)
+		.and_then(|word| u32::try_from(U256::from_be_slice(word)).ok())
+		.filter(|decimals| *decimals <= Decimal::MAX_SCALE)
+		.ok_or_else(|| PriceError::parse_error("Invalid aggregator decimals()", None, None))
+}
+
+/// Decodes the answer and `updatedAt` of the round returned by `latestRoundData()`
+fn decode_round_data(data: &[u8], decimals: u32) -> Result<Price, PriceError> {
+	let invalid = |reason: &str| {
+		PriceError::parse_error(format!("Invalid aggregator round: {}", reason), None, None)
+	};
+	if data.len() < 5 * 32 {
+		return Err(invalid("expected 5 words"));
+	}
+	let word = |index: usize| &data[index * 32..(index + 1) * 32];
+
+	let answer = I256::try_from_be_slice(word(1)).ok_or_else(|| invalid("invalid answer"))?;
+	if !answer.is_positive() {
+		return Err(invalid("answer is not positive"));
+	}
+	let usd = i128::try_from(answer)
+		.ok()
+		.and_then(|answer| Decimal::try_from_i128_with_scale(answer, decimals).ok())
+		.ok_or_else(|| invalid("answer out of range"))?;
+
+	let updated_at = i64::try_from(U256::from_be_slice(word(3)))
+		.ok()
+		.filter(|timestamp| *timestamp > 0)
+		.and_then(|timestamp| DateTime::from_timestamp(timestamp, 0))
+		.ok_or_else(|| invalid("round is not complete"))?;
+
+	Ok(Price { usd, updated_at })
+}
+
+/// Prices read from a JSON file mapping token symbols to prices, e.g. `{"USDC": 1.0}`
+///
+/// The file is read again on every refresh, so it can be updated while the monitor runs.
+pub struct FilePriceSource {
+	path: PathBuf,
+}
+
+impl FilePriceSource {
+	/// Creates a source reading the file at a path
+	pub fn new(path: impl Into<PathBuf>) -> Self {
+		Self { path: path.into() }
+	}
+}
+
+#[async_trait]
+impl PriceSource for FilePriceSource {
+	async fn fetch_price(&self, symbol: &str) -> Result<Price, PriceError> {
+		let metadata = || {
+			Some(HashMap::from([(
+				"path".to_string(),
+				self.path.display().to_string(),
+			)]))
+		};
+		let content = tokio::fs::read_to_string(&self.path).await.map_err(|e| {
+			PriceError::fetch_error("Failed to read price file", Some(Box::new(e)), metadata())
+		})?;
+		let prices: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&content)
+			.map_err(|e| {
+				PriceError::parse_error("Failed to parse price file", Some(Box::new(e)), metadata())
+			})?;
+
+		let usd = prices
+			.iter()
+			.find(|(key, _)| key.eq_ignore_ascii_case(symbol))
+			.and_then(|(_, value)| parse_json_price(value))
+			.ok_or_else(|| {
+				PriceError::not_available(
+					format!("No valid price of token {} in price file", symbol),
+					None,
+					metadata(),
+				)
+			})?;
+
+		Ok(Price {
+			usd,
+			updated_at: Utc::now(),
+		})
+	}
+}
+
+/// Price read from a JSON document returned by an HTTP API
+pub struct HttpPriceSource {
+	client: reqwest::Client,
+	url: String,
+	path: String,
+	headers: HashMap<String, String>,
+}
+
+impl HttpPriceSource {
+	/// Creates a source requesting a URL with GET
+	///
+	/// # Arguments
+	/// * `url` - URL of the price API
+	/// * `path` - Dot-separated path of the price in the response, e.g. `ethereum.usd`, numeric
+	///   segments indexing arrays
+	/// * `headers` - Headers sent with the request
+	pub fn new(
+		url: impl Into<String>,
+		path: impl Into<String>,
+		headers: HashMap<String, String>,
+	) -> Result<Self, PriceError> {
+		let client = reqwest::Client::builder()
+			.timeout(HTTP_TIMEOUT)
+			.build()
+			.map_err(|e| {
+				PriceError::fetch_error("Failed to create HTTP client", Some(Box::new(e)), None)
+			})?;
+		Ok(Self {
+			client,
+			url: url.into(),
+			path: path.into(),
+			headers,
+		})
+	}
+}
+
+#[async_trait]
+impl PriceSource for HttpPriceSource {
+	async fn fetch_price(&self, symbol: &str) -> Result<Price, PriceError> {
+		let metadata = || Some(HashMap::from([("url".to_string(), self.url.clone())]));
+		let mut request = self.client.get(&self.url);
+		for (name, value) in &self.headers {
+			request = request.header(name, value);
+		}
+
+		let response = request.send().await.map_err(|e| {
+			PriceError::fetch_error("Failed to request price API", Some(Box::new(e)), metadata())
+		})?;
+		if !response.status().is_success() {
+			return Err(PriceError::fetch_error(
+				format!("Price API returned status {}", response.status()),
+				None,
+				metadata(),
+			));
+		}
+		let document: serde_json::Value = response.json().await.map_err(|e| {
+			PriceError::parse_error(
+				"Failed to parse price API response",
+				Some(Box::new(e)),
+				metadata(),
+			)
+		})?;
+
+		let usd = self
+			.path
+			.split('.')
+			.try_fold(&document, |value, segment| match value {
+				serde_json::Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+				value => value.get(segment),
+			})
+			.and_then(parse_json_price)
+			.ok_or_else(|| {
+				PriceError::parse_error(
+					format!("No valid price of token {} at '{}'", symbol, self.path),
+					None,
+					metadata(),
+				)
+			})?;
+
+		Ok(Price {
+			usd,
+			updated_at: Utc::now(),
+		})
+	}
+}
+
+/// Parses a positive price given as a JSON number or string
+fn parse_json_price(value: &serde_json::Value) -> Option<Decimal> {
+	let text = match value {
+		serde_json::Value::Number(number) => number.to_string(),
+		serde_json::Value::String(text) => text.trim().to_string(),
+		_ => return None,
+	};
+	Decimal::from_str(&text)
+		.or_else(|_| Decimal::from_scientific(&text))
+		.ok()
+		.filter(|price| price.is_sign_positive() && !price.is_zero())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use mockito::Server;
+	use serde_json::json;
+
+	fn encode_round(answer: I256, updated_at: u64) -> Vec<u8> {
+		let mut data = Vec::new();
+		data.extend_from_slice(&U256::from(1).to_be_bytes::<32>());
+		data.extend_from_slice(&answer.to_be_bytes::<32>());
+		data.extend_from_slice(&U256::from(updated_at).to_be_bytes::<32>());
+		data.extend_from_slice(&U256::from(updated_at).to_be_bytes::<32>());
+		data.extend_from_slice(&U256::from(1).to_be_bytes::<32>());
+		data
+	}
+
+	#[test]
+	fn test_decode_round_data() {
+		let price = decode_round_data(
+			&encode_round(I256::try_from(350012345678i64).unwrap(), 1_700_000_000),
+			8,
+		)
+		.unwrap();
+		assert_eq!(price.usd, Decimal::from_str("3500.12345678").unwrap());
+		assert_eq!(price.updated_at.timestamp(), 1_700_000_000);
+
+		assert!(decode_round_data(&encode_round(I256::ZERO, 1_700_000_000), 8).is_err());
+		assert!(decode_round_data(&encode_round(I256::MINUS_ONE, 1_700_000_000), 8).is_err());
+		assert!(decode_round_data(&encode_round(I256::ONE, 0), 8).is_err());
+		assert!(decode_round_data(&[0u8; 64], 8).is_err());
+	}
+
+	#[test]
+	fn test_decode_aggregator_decimals() {
+		assert_eq!(
+			decode_aggregator_decimals(&U256::from(8).to_be_bytes::<32>()).unwrap(),
+			8
+		);
+		assert!(decode_aggregator_decimals(&U256::from(29).to_be_bytes::<32>()).is_err());
+		assert!(decode_aggregator_decimals(&[0u8; 4]).is_err());
+	}
+
+	#[test]
+	fn test_parse_json_price() {
+		assert_eq!(
+			parse_json_price(&json!(3500.12)),
+			Some(Decimal::from_str("3500.12").unwrap())
+		);
+		assert_eq!(
+			parse_json_price(&json!("0.9998")),
+			Some(Decimal::from_str("0.9998").unwrap())
+		);
+		assert_eq!(
+			parse_json_price(&json!(1.5e-5)),
+			Some(Decimal::from_str("0.000015").unwrap())
+		);
+		assert_eq!(parse_json_price(&json!(0)), None);
+		assert_eq!(parse_json_price(&json!(-1)), None);
+		assert_eq!(parse_json_price(&json!("abc")), None);
+		assert_eq!(parse_json_price(&json!(null)), None);
+	}
+
+	#[tokio::test]
+	async fn test_file_price_source() {
+		let temp_dir = tempfile::TempDir::new().unwrap();
+		let path = temp_dir.path().join("prices.json");
+		std::fs::write(&path, r#"{"ETH": 3500.5, "usdc": "1.0", "BAD": -1}"#).unwrap();
+		let source = FilePriceSource::new(&path);
+
+		assert_eq!(
+			source.fetch_price("ETH").await.unwrap().usd,
+			Decimal::from_str("3500.5").unwrap()
+		);
+		assert_eq!(
+			source.fetch_price("USDC").await.unwrap().usd,
+			Decimal::from_str("1.0").unwrap()
+		);
+		assert!(matches!(
+			source.fetch_price("BAD").await,
+			Err(PriceError::NotAvailable(_))
+		));
+		assert!(matches!(
+			FilePriceSource::new(temp_dir.path().join("missing.json"))
+				.fetch_price("ETH")
+				.await,
+			Err(PriceError::FetchError(_))
+		));
+	}
+
+	#[tokio::test]
+	async fn test_http_price_source() {
+		let mut server = Server::new_async().await;
+		let mock = server
+			.mock("GET", "/simple/price?ids=ethereum&vs_currencies=usd")
+			.match_header("x-api-key", "secret")
+			.with_status(200)
+			.with_body(r#"{"ethereum": {"usd": 3512.25}, "data": [{"price": "1.0001"}]}"#)
+			.expect(1)
+			.create_async()
+			.await;
+
+		let source = HttpPriceSource::new(
+			format!(
+				"{}/simple/price?ids=ethereum&vs_currencies=usd",
+				server.url()
+			),
+			"ethereum.usd",
+			HashMap::from([("x-api-key".to_string(), "secret".to_string())]),
+		)
+		.unwrap();
+		assert_eq!(
+			source.fetch_price("ETH").await.unwrap().usd,
+			Decimal::from_str("3512.25").unwrap()
+		);
+		mock.assert_async().await;
+	}
+
+	#[tokio::test]
+	async fn test_http_price_source_array_path() {
+		let mut server = Server::new_async().await;
+		server
+			.mock("GET", "/prices")
+			.with_status(200)
+			.with_body(r#"{"data": [{"price": "1.0001"}]}"#)
+			.create_async()
+			.await;
+
+		let source = HttpPriceSource::new(
+			format!("{}/prices", server.url()),
+			"data.0.price",
+			HashMap::new(),
+		)
+		.unwrap();
+		assert_eq!(
+			source.fetch_price("USDC").await.unwrap().usd,
+			Decimal::from_str("1.0001").unwrap()
+		);
+
+		let source = HttpPriceSource::new(
+			format!("{}/prices", server.url()),
+			"data.1.price",
+			HashMap::new(),
+		)
+		.unwrap();
+		assert!(matches!(
+			source.fetch_price("USDC").await,
+			Err(PriceError::ParseError(_))
+		));
+	}
+
+	#[tokio::test]
+	async fn test_http_price_source_error_status() {
+		let mut server = Server::new_async().await;
+		server
+			.mock("GET", "/prices")
+			.with_status(429)
+			.create_async()
+			.await;
+
+		let source =
+			HttpPriceSource::new(format!("{}/prices", server.url()), "usd", HashMap::new())
+				.unwrap();
+		assert!(matches!(
+			source.fetch_price("ETH").await,
+			Err(PriceError::FetchError(_))
+		));
+	}
+}
@@ -2,15 +2,23 @@
 //!
 //! This module provides functionality to manage and execute triggers,
 //! which are configurable actions that can be initiated based on
-//! various conditions.
+//! various conditions, and to throttle the notifications they send.
 
 mod error;
 mod script;
 mod service;
+mod throttle;
 
 pub use error::TriggerError;
 pub use script::{
-	process_script_output, validate_script_config, ScriptError, ScriptExecutor,
-	ScriptExecutorFactory,
+	process_script_output, validate_script_config, validate_script_executable,
+	validate_script_sandbox, ScriptError, ScriptExecutor, ScriptExecutorFactory, ScriptOutput,
+	WorkerScriptExecutor,
 };
+#[cfg(feature = "wasm")]
+pub use script::{WasmScriptExecutor, WASM_FUEL_PER_MS, WASM_MEMORY_LIMIT};
 pub use service::{TriggerExecutionService, TriggerExecutionServiceTrait};
+pub use throttle::{
+	append_throttle_summary, insert_throttle_variables, NotificationThrottle, ThrottleDecision,
+	THROTTLE_SUMMARY, THROTTLE_SUPPRESSED_COUNT,
+};
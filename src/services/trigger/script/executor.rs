@@ -2,18 +2,49 @@
 //!
 //! This module provides functionality to execute scripts in different languages.
 
-use crate::models::MonitorMatch;
+use crate::{
+	models::{MonitorMatch, ScriptSandbox},
+	services::trigger::script::sandbox::{sandbox_violation, spawn_sandboxed},
+};
 use anyhow::Context;
 use async_trait::async_trait;
 use std::{any::Any, process::Stdio, time::Duration};
 use tokio::{io::AsyncWriteExt, time::timeout};
 
+/// Outcome of a script evaluating a match
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptOutput {
+	/// Whether the match is filtered out
+	Filter(bool),
+	/// Fields enriching the match, which is kept
+	Enrich(serde_json::Map<String, serde_json::Value>),
+}
+
 /// A trait that defines the interface for executing custom scripts in different languages.
 /// Implementors must be both Send and Sync to ensure thread safety.
 #[async_trait]
 pub trait ScriptExecutor: Send + Sync + Any {
 	/// Enables downcasting by returning a reference to `Any`
 	fn as_any(&self) -> &dyn Any;
+	/// Evaluates the script with the given MonitorMatch input.
+	///
+	/// # Arguments
+	/// * `input` - A MonitorMatch instance containing the data to be processed by the script
+	/// * `timeout_ms` - The timeout for the script execution in milliseconds
+	/// * `args` - Additional arguments passed to the script
+	/// * `from_custom_notification` - Whether the script is from a custom notification
+	///
+	/// # Returns
+	/// * `Result<ScriptOutput, anyhow::Error>` - Returns whether the match is filtered out or the
+	///   fields enriching it, or an error
+	async fn evaluate(
+		&self,
+		input: MonitorMatch,
+		timeout_ms: &u32,
+		args: Option<&[String]>,
+		from_custom_notification: bool,
+	) -> Result<ScriptOutput, anyhow::Error>;
+
 	/// Executes the script with the given MonitorMatch input.
 	///
 	/// # Arguments
@@ -23,20 +54,28 @@ pub trait ScriptExecutor: Send + Sync + Any {
 	/// * `from_custom_notification` - Whether the script is from a custom notification
 	///
 	/// # Returns
-	/// * `Result<bool, anyhow::Error>` - Returns true/false based on script execution or an error
+	/// * `Result<bool, anyhow::Error>` - Returns true/false based on script execution or an error,
+	///   scripts enriching the match return false
 	async fn execute(
 		&self,
 		input: MonitorMatch,
 		timeout_ms: &u32,
 		args: Option<&[String]>,
 		from_custom_notification: bool,
-	) -> Result<bool, anyhow::Error>;
+	) -> Result<bool, anyhow::Error> {
+		let output = self
+			.evaluate(input, timeout_ms, args, from_custom_notification)
+			.await?;
+		Ok(output == ScriptOutput::Filter(true))
+	}
 }
 
 /// Executes Python scripts using the python3 interpreter.
 pub struct PythonScriptExecutor {
 	/// Content of the Python script file to be executed
 	pub script_content: String,
+	/// Restrictions applied to the script process
+	pub sandbox: Option<ScriptSandbox>,
 }
 
 #[async_trait]
@@ -44,13 +83,13 @@ impl ScriptExecutor for PythonScriptExecutor {
 	fn as_any(&self) -> &dyn Any {
 		self
 	}
-	async fn execute(
+	async fn evaluate(
 		&self,
 		input: MonitorMatch,
 		timeout_ms: &u32,
 		args: Option<&[String]>,
 		from_custom_notification: bool,
-	) -> Result<bool, anyhow::Error> {
+	) -> Result<ScriptOutput, anyhow::Error> {
 		let combined_input = serde_json::json!({
 			"monitor_match": input,
 			"args": args
@@ -58,16 +97,23 @@ impl ScriptExecutor for PythonScriptExecutor {
 		let input_json = serde_json::to_string(&combined_input)
 			.with_context(|| "Failed to serialize monitor match and arguments")?;
 
-		let cmd = tokio::process::Command::new("python3")
+		let mut command = tokio::process::Command::new("python3");
+		command
 			.arg("-c")
 			.arg(&self.script_content)
 			.stdin(Stdio::piped())
 			.stdout(Stdio::piped())
-			.stderr(Stdio::piped())
-			.spawn()
-			.with_context(|| "Failed to spawn python3 process")?;
-
-		process_command(cmd, &input_json, timeout_ms, from_custom_notification).await
+			.stderr(Stdio::piped());
+		let cmd = spawn_sandboxed(command, self.sandbox.as_ref(), "python3 process")?;
+
+		process_command(
+			cmd,
+			&input_json,
+			timeout_ms,
+			from_custom_notification,
+			self.sandbox.as_ref(),
+		)
+		.await
 	}
 }
 
@@ -75,6 +121,8 @@ impl ScriptExecutor for PythonScriptExecutor {
 pub struct JavaScriptScriptExecutor {
 	/// Content of the JavaScript script file to be executed
 	pub script_content: String,
+	/// Restrictions applied to the script process
+	pub sandbox: Option<ScriptSandbox>,
 }
 
 #[async_trait]
@@ -82,13 +130,13 @@ impl ScriptExecutor for JavaScriptScriptExecutor {
 	fn as_any(&self) -> &dyn Any {
 		self
 	}
-	async fn execute(
+	async fn evaluate(
 		&self,
 		input: MonitorMatch,
 		timeout_ms: &u32,
 		args: Option<&[String]>,
 		from_custom_notification: bool,
-	) -> Result<bool, anyhow::Error> {
+	) -> Result<ScriptOutput, anyhow::Error> {
 		// Create a combined input with both the monitor match and arguments
 		let combined_input = serde_json::json!({
 			"monitor_match": input,
@@ -97,15 +145,22 @@ impl ScriptExecutor for JavaScriptScriptExecutor {
 		let input_json = serde_json::to_string(&combined_input)
 			.with_context(|| "Failed to serialize monitor match and arguments")?;
 
-		let cmd = tokio::process::Command::new("node")
+		let mut command = tokio::process::Command::new("node");
+		command
 			.arg("-e")
 			.arg(&self.script_content)
 			.stdin(Stdio::piped())
 			.stdout(Stdio::piped())
-			.stderr(Stdio::piped())
-			.spawn()
-			.with_context(|| "Failed to spawn node process")?;
-		process_command(cmd, &input_json, timeout_ms, from_custom_notification).await
+			.stderr(Stdio::piped());
+		let cmd = spawn_sandboxed(command, self.sandbox.as_ref(), "node process")?;
+		process_command(
+			cmd,
+			&input_json,
+			timeout_ms,
+			from_custom_notification,
+			self.sandbox.as_ref(),
+		)
+		.await
 	}
 }
 
@@ -113,6 +168,8 @@ impl ScriptExecutor for JavaScriptScriptExecutor {
 pub struct BashScriptExecutor {
 	/// Content of the Bash script file to be executed
 	pub script_content: String,
+	/// Restrictions applied to the script process
+	pub sandbox: Option<ScriptSandbox>,
 }
 
 #[async_trait]
@@ -120,13 +177,13 @@ impl ScriptExecutor for BashScriptExecutor {
 	fn as_any(&self) -> &dyn Any {
 		self
 	}
-	async fn execute(
+	async fn evaluate(
 		&self,
 		input: MonitorMatch,
 		timeout_ms: &u32,
 		args: Option<&[String]>,
 		from_custom_notification: bool,
-	) -> Result<bool, anyhow::Error> {
+	) -> Result<ScriptOutput, anyhow::Error> {
 		// Create a combined input with both the monitor match and arguments
 		let combined_input = serde_json::json!({
 			"monitor_match": input,
@@ -136,37 +193,148 @@ impl ScriptExecutor for BashScriptExecutor {
 		let input_json = serde_json::to_string(&combined_input)
 			.with_context(|| "Failed to serialize monitor match and arguments")?;
 
-		let cmd = tokio::process::Command::new("sh")
+		let mut command = tokio::process::Command::new("sh");
+		command
 			.arg("-c")
 			.arg(&self.script_content)
 			.stdin(Stdio::piped())
 			.stdout(Stdio::piped())
-			.stderr(Stdio::piped())
-			.spawn()
-			.with_context(|| "Failed to spawn shell process")?;
+			.stderr(Stdio::piped());
+		let cmd = spawn_sandboxed(command, self.sandbox.as_ref(), "shell process")?;
+
+		process_command(
+			cmd,
+			&input_json,
+			timeout_ms,
+			from_custom_notification,
+			self.sandbox.as_ref(),
+		)
+		.await
+	}
+}
+
+/// Executes binaries and scripts with a shebang, directly or with an interpreter.
+pub struct ExecutableScriptExecutor {
+	/// Absolute path of the executable
+	pub script_path: String,
+	/// Command running the executable instead of the file itself, such as `deno run`
+	pub interpreter: Option<String>,
+	/// Restrictions applied to the script process
+	pub sandbox: Option<ScriptSandbox>,
+}
+
+#[async_trait]
+impl ScriptExecutor for ExecutableScriptExecutor {
+	fn as_any(&self) -> &dyn Any {
+		self
+	}
+	async fn evaluate(
+		&self,
+		input: MonitorMatch,
+		timeout_ms: &u32,
+		args: Option<&[String]>,
+		from_custom_notification: bool,
+	) -> Result<ScriptOutput, anyhow::Error> {
+		let combined_input = serde_json::json!({
+			"monitor_match": input,
+			"args": args
+		});
+		let input_json = serde_json::to_string(&combined_input)
+			.with_context(|| "Failed to serialize monitor match and arguments")?;
+
+		let mut command = executable_command(&self.script_path, self.interpreter.as_deref())?;
+		command
+			.stdin(Stdio::piped())
+			.stdout(Stdio::piped())
+			.stderr(Stdio::piped());
+		let cmd = spawn_sandboxed(
+			command,
+			self.sandbox.as_ref(),
+			&format!("executable {}", self.script_path),
+		)?;
+
+		process_command(
+			cmd,
+			&input_json,
+			timeout_ms,
+			from_custom_notification,
+			self.sandbox.as_ref(),
+		)
+		.await
+	}
+}
+
+/// Builds the command running an executable script
+///
+/// # Arguments
+/// * `script_path` - Path of the executable
+/// * `interpreter` - Command line run with the path as its last argument, instead of the file
+///   itself
+pub(super) fn executable_command(
+	script_path: &str,
+	interpreter: Option<&str>,
+) -> Result<tokio::process::Command, anyhow::Error> {
+	let Some(interpreter) = interpreter else {
+		return Ok(tokio::process::Command::new(script_path));
+	};
+
+	let mut words = interpreter.split_whitespace();
+	let program = words
+		.next()
+		.ok_or_else(|| anyhow::anyhow!("Script interpreter is empty"))?;
+	let mut command = tokio::process::Command::new(program);
+	command.args(words).arg(script_path);
+	Ok(command)
+}
 
-		process_command(cmd, &input_json, timeout_ms, from_custom_notification).await
+/// Stands in for the executor of a language the monitor was built without.
+#[cfg(not(feature = "wasm"))]
+pub struct UnavailableScriptExecutor {
+	/// Cargo feature the language requires
+	pub feature: &'static str,
+}
+
+#[cfg(not(feature = "wasm"))]
+#[async_trait]
+impl ScriptExecutor for UnavailableScriptExecutor {
+	fn as_any(&self) -> &dyn Any {
+		self
+	}
+	async fn evaluate(
+		&self,
+		_input: MonitorMatch,
+		_timeout_ms: &u32,
+		_args: Option<&[String]>,
+		_from_custom_notification: bool,
+	) -> Result<ScriptOutput, anyhow::Error> {
+		Err(anyhow::anyhow!(
+			"Script requires the monitor to be built with the `{}` feature",
+			self.feature
+		))
 	}
 }
 
 /// Processes the output from script execution.
 ///
+/// The last line of the output is either a boolean, true filtering the match out, or a JSON
+/// object whose fields enrich the match.
+///
 /// # Arguments
 /// * `output` - The process output containing stdout, stderr, and status
 /// * `from_custom_notification` - Whether the script is from a custom notification
 /// # Returns
-/// * `Result<bool, anyhow::Error>` - Returns parsed boolean result or error
+/// * `Result<ScriptOutput, anyhow::Error>` - Returns parsed result or error
 ///
 /// # Errors
 /// Returns an error if:
 /// * The script execution was not successful (non-zero exit code)
-/// * The output cannot be parsed as a boolean
+/// * The output cannot be parsed as a boolean or a JSON object
 /// * The script produced no output
 #[allow(clippy::result_large_err)]
 pub fn process_script_output(
 	output: std::process::Output,
 	from_custom_notification: bool,
-) -> Result<bool, anyhow::Error> {
+) -> Result<ScriptOutput, anyhow::Error> {
 	if !output.status.success() {
 		let error_message = String::from_utf8_lossy(&output.stderr).to_string();
 		return Err(anyhow::anyhow!(
@@ -178,7 +346,7 @@ pub fn process_script_output(
 	// If the script is from a custom notification and the status is success, we don't need to check
 	// the output
 	if from_custom_notification {
-		return Ok(true);
+		return Ok(ScriptOutput::Filter(true));
 	}
 
 	let stdout = String::from_utf8_lossy(&output.stdout);
@@ -193,9 +361,15 @@ pub fn process_script_output(
 		.ok_or_else(|| anyhow::anyhow!("No output from script"))?
 		.trim();
 
+	if last_line.starts_with('{') {
+		return serde_json::from_str(last_line)
+			.map(ScriptOutput::Enrich)
+			.map_err(|e| anyhow::anyhow!("Last line of output is not a valid JSON object: {}", e));
+	}
+
 	match last_line.to_lowercase().as_str() {
-		"true" => Ok(true),
-		"false" => Ok(false),
+		"true" => Ok(ScriptOutput::Filter(true)),
+		"false" => Ok(ScriptOutput::Filter(false)),
 		_ => Err(anyhow::anyhow!(
 			"Last line of output is not a valid boolean: {}",
 			last_line
@@ -203,12 +377,22 @@ pub fn process_script_output(
 	}
 }
 
+/// Writes the input of a script to its process and processes its output.
+///
+/// # Arguments
+/// * `cmd` - The spawned script process
+/// * `input_json` - Serialized input of the script
+/// * `timeout_ms` - The timeout for the script execution in milliseconds
+/// * `from_custom_notification` - Whether the script is from a custom notification
+/// * `sandbox` - Restrictions the script process runs under, violations are reported as
+///   `ScriptError`s
 async fn process_command(
 	mut cmd: tokio::process::Child,
 	input_json: &str,
 	timeout_ms: &u32,
 	from_custom_notification: bool,
-) -> Result<bool, anyhow::Error> {
+	sandbox: Option<&ScriptSandbox>,
+) -> Result<ScriptOutput, anyhow::Error> {
 	if let Some(mut stdin) = cmd.stdin.take() {
 		stdin
 			.write_all(input_json.as_bytes())
@@ -230,6 +414,9 @@ async fn process_command(
 		Ok(result) => {
 			let output =
 				result.map_err(|e| anyhow::anyhow!("Failed to wait for script output: {}", e))?;
+			if let Some(violation) = sandbox_violation(&output.status, &output.stderr, sandbox) {
+				return Err(violation.into());
+			}
 			process_script_output(output, from_custom_notification)
 		}
 		Err(_) => Err(anyhow::anyhow!("Script execution timed out")),
@@ -244,6 +431,7 @@ mod tests {
 			AddressWithSpec, EVMMonitorMatch, EVMReceiptLog, EventCondition, FunctionCondition,
 			MatchConditions, Monitor, MonitorMatch, TransactionCondition,
 		},
+		services::trigger::script::ScriptError,
 		utils::tests::evm::{
 			monitor::MonitorBuilder, receipt::ReceiptBuilder, transaction::TransactionBuilder,
 		},
@@ -304,8 +492,10 @@ mod tests {
 				functions: vec![],
 				events: vec![],
 				transactions: vec![],
+				..Default::default()
 			},
 			matched_on_args: None,
+			enrichment: None,
 		}))
 	}
 
@@ -327,6 +517,7 @@ print(result)
 
 		let executor = PythonScriptExecutor {
 			script_content: script_content.to_string(),
+			sandbox: None,
 		};
 
 		let input = create_mock_monitor_match();
@@ -351,6 +542,7 @@ print(result)
 
 		let executor = PythonScriptExecutor {
 			script_content: script_content.to_string(),
+			sandbox: None,
 		};
 
 		let input = create_mock_monitor_match();
@@ -384,6 +576,7 @@ print("true")
 
 		let executor = PythonScriptExecutor {
 			script_content: script_content.to_string(),
+			sandbox: None,
 		};
 
 		let input = create_mock_monitor_match();
@@ -423,6 +616,7 @@ print("true")
 
 		let executor = JavaScriptScriptExecutor {
 			script_content: script_content.to_string(),
+			sandbox: None,
 		};
 
 		let input = create_mock_monitor_match();
@@ -456,6 +650,7 @@ print("true")
 
 		let executor = JavaScriptScriptExecutor {
 			script_content: script_content.to_string(),
+			sandbox: None,
 		};
 
 		let input = create_mock_monitor_match();
@@ -482,6 +677,7 @@ echo "true"
 "#;
 		let executor = BashScriptExecutor {
 			script_content: script_content.to_string(),
+			sandbox: None,
 		};
 
 		let input = create_mock_monitor_match();
@@ -490,6 +686,47 @@ echo "true"
 		assert!(result.unwrap());
 	}
 
+	#[cfg(unix)]
+	#[tokio::test]
+	async fn test_executable_script_executor_shebang() {
+		use std::os::unix::fs::PermissionsExt;
+
+		let dir = tempfile::TempDir::new().unwrap();
+		let script_path = dir.path().join("filter");
+		fs::write(&script_path, "#!/bin/sh\ncat > /dev/null\necho true\n").unwrap();
+		fs::set_permissions(&script_path, fs::Permissions::from_mode(0o755)).unwrap();
+
+		let executor = ExecutableScriptExecutor {
+			script_path: script_path.to_str().unwrap().to_string(),
+			interpreter: None,
+			sandbox: None,
+		};
+
+		let result = executor
+			.execute(create_mock_monitor_match(), &1000, None, false)
+			.await;
+		assert!(result.unwrap());
+	}
+
+	#[tokio::test]
+	async fn test_executable_script_executor_interpreter() {
+		let dir = tempfile::TempDir::new().unwrap();
+		let script_path = dir.path().join("filter.ts");
+		fs::write(&script_path, "cat > /dev/null\necho false\n").unwrap();
+
+		// The file is not executable, it is passed to the interpreter instead
+		let executor = ExecutableScriptExecutor {
+			script_path: script_path.to_str().unwrap().to_string(),
+			interpreter: Some("sh -e".to_string()),
+			sandbox: None,
+		};
+
+		let result = executor
+			.execute(create_mock_monitor_match(), &1000, None, false)
+			.await;
+		assert!(!result.unwrap());
+	}
+
 	#[tokio::test]
 	async fn test_bash_script_executor_invalid_output() {
 		let script_content = r#"
@@ -503,6 +740,7 @@ echo "not a boolean"
 
 		let executor = BashScriptExecutor {
 			script_content: script_content.to_string(),
+			sandbox: None,
 		};
 
 		let input = create_mock_monitor_match();
@@ -520,6 +758,60 @@ echo "not a boolean"
 		}
 	}
 
+	#[tokio::test]
+	async fn test_python_script_executor_enrich() {
+		let script_content = r#"
+import sys
+import json
+
+input_json = sys.stdin.read()
+print("debugging...")
+print(json.dumps({"price_usd": 1.5, "risk": {"score": 87}}))
+"#;
+
+		let executor = PythonScriptExecutor {
+			script_content: script_content.to_string(),
+			sandbox: None,
+		};
+
+		let result = executor
+			.evaluate(create_mock_monitor_match(), &1000, None, false)
+			.await;
+		let ScriptOutput::Enrich(fields) = result.unwrap() else {
+			panic!("Expected enrichment");
+		};
+		assert_eq!(fields["price_usd"], serde_json::json!(1.5));
+		assert_eq!(fields["risk"]["score"], serde_json::json!(87));
+
+		// Enriched matches are kept
+		let result = executor
+			.execute(create_mock_monitor_match(), &1000, None, false)
+			.await;
+		assert!(!result.unwrap());
+	}
+
+	#[tokio::test]
+	async fn test_python_script_executor_invalid_json_output() {
+		let script_content = r#"
+import sys
+input_json = sys.stdin.read()
+print("{not json")
+"#;
+
+		let executor = PythonScriptExecutor {
+			script_content: script_content.to_string(),
+			sandbox: None,
+		};
+
+		let result = executor
+			.evaluate(create_mock_monitor_match(), &1000, None, false)
+			.await;
+		assert!(result
+			.unwrap_err()
+			.to_string()
+			.contains("Last line of output is not a valid JSON object"));
+	}
+
 	#[tokio::test]
 	async fn test_script_executor_empty_output() {
 		let script_content = r#"
@@ -530,6 +822,7 @@ input_json = sys.stdin.read()
 
 		let executor = PythonScriptExecutor {
 			script_content: script_content.to_string(),
+			sandbox: None,
 		};
 
 		let input = create_mock_monitor_match();
@@ -554,6 +847,7 @@ print("     true    ")  # Should handle whitespace correctly
 
 		let executor = PythonScriptExecutor {
 			script_content: script_content.to_string(),
+			sandbox: None,
 		};
 
 		let input = create_mock_monitor_match();
@@ -577,6 +871,7 @@ print("     true    ")  # Should handle whitespace correctly
 
 		let executor = PythonScriptExecutor {
 			script_content: script_content.to_string(),
+			sandbox: None,
 		};
 
 		// Create an invalid MonitorMatch that will fail JSON serialization
@@ -604,6 +899,7 @@ print("true")
 
 		let executor = PythonScriptExecutor {
 			script_content: script_content.to_string(),
+			sandbox: None,
 		};
 
 		let input = create_mock_monitor_match();
@@ -636,6 +932,7 @@ else:
 
 		let executor = PythonScriptExecutor {
 			script_content: script_content.to_string(),
+			sandbox: None,
 		};
 
 		let input = create_mock_monitor_match();
@@ -667,6 +964,7 @@ else:
 
 		let executor = PythonScriptExecutor {
 			script_content: script_content.to_string(),
+			sandbox: None,
 		};
 
 		let input = create_mock_monitor_match();
@@ -711,6 +1009,7 @@ else:
 
 		let executor = PythonScriptExecutor {
 			script_content: script_content.to_string(),
+			sandbox: None,
 		};
 
 		let input = create_mock_monitor_match();
@@ -739,7 +1038,10 @@ else:
 	#[tokio::test]
 	async fn test_python_script_executor_with_verbose_arg() {
 		let script_content = read_fixture("evm_filter_by_arguments.py");
-		let executor = PythonScriptExecutor { script_content };
+		let executor = PythonScriptExecutor {
+			script_content,
+			sandbox: None,
+		};
 		let input = create_mock_monitor_match();
 		let args = vec![String::from("--verbose")];
 		let result = executor
@@ -753,7 +1055,10 @@ else:
 	#[tokio::test]
 	async fn test_python_script_executor_with_wrong_arg() {
 		let script_content = read_fixture("evm_filter_by_arguments.py");
-		let executor = PythonScriptExecutor { script_content };
+		let executor = PythonScriptExecutor {
+			script_content,
+			sandbox: None,
+		};
 
 		let input = create_mock_monitor_match();
 		let args = vec![String::from("--wrong_arg"), String::from("--test")];
@@ -774,6 +1079,7 @@ input_json = sys.stdin.read()
 
 		let executor = PythonScriptExecutor {
 			script_content: script_content.to_string(),
+			sandbox: None,
 		};
 
 		let input = create_mock_monitor_match();
@@ -793,6 +1099,7 @@ sys.exit(1)
 
 		let executor = PythonScriptExecutor {
 			script_content: script_content.to_string(),
+			sandbox: None,
 		};
 
 		let input = create_mock_monitor_match();
@@ -820,6 +1127,7 @@ time.sleep(0.3)
 
 		let executor = PythonScriptExecutor {
 			script_content: script_content.to_string(),
+			sandbox: None,
 		};
 
 		let input = create_mock_monitor_match();
@@ -845,6 +1153,7 @@ time.sleep(0.5)
 
 		let executor = PythonScriptExecutor {
 			script_content: script_content.to_string(),
+			sandbox: None,
 		};
 
 		let input = create_mock_monitor_match();
@@ -869,6 +1178,7 @@ sys.exit(1)
 
 		let executor = PythonScriptExecutor {
 			script_content: script_content.to_string(),
+			sandbox: None,
 		};
 
 		let input = create_mock_monitor_match();
@@ -909,6 +1219,7 @@ sys.exit(1)
 
 		let executor = JavaScriptScriptExecutor {
 			script_content: script_content.to_string(),
+			sandbox: None,
 		};
 
 		let input = create_mock_monitor_match();
@@ -937,6 +1248,7 @@ exit 1
 
 		let executor = BashScriptExecutor {
 			script_content: script_content.to_string(),
+			sandbox: None,
 		};
 
 		let input = create_mock_monitor_match();
@@ -951,4 +1263,47 @@ exit 1
 			_ => panic!("Expected ExecutionError"),
 		}
 	}
+
+	#[tokio::test]
+	async fn test_bash_script_executor_with_sandbox() {
+		std::env::set_var("EXECUTOR_TEST_SECRET", "secret");
+		let script_content = r#"
+input_json=$(cat)
+if [ -z "$EXECUTOR_TEST_SECRET" ]; then echo "true"; else echo "false"; fi
+"#;
+
+		let executor = BashScriptExecutor {
+			script_content: script_content.to_string(),
+			sandbox: Some(ScriptSandbox {
+				env_allowlist: Some(vec![]),
+				max_open_files: Some(64),
+				..Default::default()
+			}),
+		};
+
+		let input = create_mock_monitor_match();
+		let result = executor.execute(input, &1000, None, false).await;
+		assert!(result.unwrap());
+	}
+
+	#[cfg(unix)]
+	#[tokio::test]
+	async fn test_bash_script_executor_cpu_limit_violation() {
+		let executor = BashScriptExecutor {
+			script_content: "while :; do :; done".to_string(),
+			sandbox: Some(ScriptSandbox {
+				max_cpu_seconds: Some(1),
+				..Default::default()
+			}),
+		};
+
+		let input = create_mock_monitor_match();
+		let result = executor.execute(input, &10000, None, false).await;
+		let error = result.unwrap_err();
+		assert!(matches!(
+			error.downcast_ref::<ScriptError>(),
+			Some(ScriptError::ExecutionError(_))
+		));
+		assert!(error.to_string().contains("exceeded its CPU time limit"));
+	}
 }
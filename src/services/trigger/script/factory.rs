@@ -3,12 +3,21 @@
 //! This module provides functionality to create script executors based on the script language.
 
 use crate::{
-	models::ScriptLanguage,
-	services::trigger::script::executor::{
-		BashScriptExecutor, JavaScriptScriptExecutor, PythonScriptExecutor, ScriptExecutor,
+	models::{ScriptLanguage, ScriptMode, ScriptSandbox},
+	services::trigger::script::{
+		executor::{
+			BashScriptExecutor, ExecutableScriptExecutor, JavaScriptScriptExecutor,
+			PythonScriptExecutor, ScriptExecutor,
+		},
+		worker::WorkerScriptExecutor,
 	},
 };
 
+#[cfg(not(feature = "wasm"))]
+use crate::services::trigger::script::executor::UnavailableScriptExecutor;
+#[cfg(feature = "wasm")]
+use crate::services::trigger::script::wasm::WasmScriptExecutor;
+
 /// Factory for creating script executors based on the script language.
 pub struct ScriptExecutorFactory;
 
@@ -28,12 +37,87 @@ impl ScriptExecutorFactory {
 		match language {
 			ScriptLanguage::Python => Box::new(PythonScriptExecutor {
 				script_content: script_content.to_string(),
+				sandbox: None,
 			}),
 			ScriptLanguage::JavaScript => Box::new(JavaScriptScriptExecutor {
 				script_content: script_content.to_string(),
+				sandbox: None,
 			}),
 			ScriptLanguage::Bash => Box::new(BashScriptExecutor {
 				script_content: script_content.to_string(),
+				sandbox: None,
+			}),
+			#[cfg(feature = "wasm")]
+			ScriptLanguage::Wasm => Box::new(WasmScriptExecutor {
+				script_content: script_content.to_string(),
+			}),
+			#[cfg(not(feature = "wasm"))]
+			ScriptLanguage::Wasm => Box::new(UnavailableScriptExecutor { feature: "wasm" }),
+			ScriptLanguage::Executable => Box::new(ExecutableScriptExecutor {
+				script_path: script_content.to_string(),
+				interpreter: None,
+				sandbox: None,
+			}),
+		}
+	}
+
+	/// Creates a new script executor for the specified language, execution mode and sandbox.
+	///
+	/// # Arguments
+	///
+	/// * `language` - The programming language of the script
+	/// * `mode` - Whether the script is spawned per call or kept running as a worker, WASM
+	///   modules always run in-process
+	/// * `interpreter` - Command running executable scripts instead of the file itself
+	/// * `sandbox` - Restrictions applied to the script process, WASM modules are bounded by
+	///   their own fuel and memory limits instead
+	/// * `script_content` - The content of the script, or the path of executable scripts
+	///
+	/// # Returns
+	///
+	/// Returns a boxed trait object implementing the `ScriptExecutor` trait
+	pub fn create_with_mode(
+		language: &ScriptLanguage,
+		mode: &ScriptMode,
+		interpreter: Option<&str>,
+		sandbox: Option<&ScriptSandbox>,
+		script_content: &str,
+	) -> Box<dyn ScriptExecutor> {
+		let interpreter = interpreter.map(str::to_string);
+		let sandbox = sandbox.cloned();
+		let script_content = script_content.to_string();
+		match (language, mode) {
+			// WASM modules run in-process, there is no interpreter to keep running
+			#[cfg(feature = "wasm")]
+			(ScriptLanguage::Wasm, _) => Box::new(WasmScriptExecutor { script_content }),
+			#[cfg(not(feature = "wasm"))]
+			(ScriptLanguage::Wasm, _) => Box::new(UnavailableScriptExecutor { feature: "wasm" }),
+			(ScriptLanguage::Python, ScriptMode::Process) => Box::new(PythonScriptExecutor {
+				script_content,
+				sandbox,
+			}),
+			(ScriptLanguage::JavaScript, ScriptMode::Process) => {
+				Box::new(JavaScriptScriptExecutor {
+					script_content,
+					sandbox,
+				})
+			}
+			(ScriptLanguage::Bash, ScriptMode::Process) => Box::new(BashScriptExecutor {
+				script_content,
+				sandbox,
+			}),
+			(ScriptLanguage::Executable, ScriptMode::Process) => {
+				Box::new(ExecutableScriptExecutor {
+					script_path: script_content,
+					interpreter,
+					sandbox,
+				})
+			}
+			(_, ScriptMode::Worker) => Box::new(WorkerScriptExecutor {
+				language: language.clone(),
+				script_content,
+				interpreter,
+				sandbox,
 			}),
 		}
 	}
@@ -42,7 +126,7 @@ impl ScriptExecutorFactory {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::models::ScriptLanguage;
+	use crate::models::{ScriptLanguage, ScriptMode};
 
 	#[test]
 	fn test_create_python_executor() {
@@ -115,4 +199,71 @@ mod tests {
 			.script_content
 			.is_empty());
 	}
+
+	#[test]
+	fn test_create_with_mode() {
+		let script = "print('Hello')";
+		let executor = ScriptExecutorFactory::create_with_mode(
+			&ScriptLanguage::Python,
+			&ScriptMode::Process,
+			None,
+			None,
+			script,
+		);
+		assert!(executor
+			.as_any()
+			.downcast_ref::<PythonScriptExecutor>()
+			.is_some());
+
+		let executor = ScriptExecutorFactory::create_with_mode(
+			&ScriptLanguage::Python,
+			&ScriptMode::Worker,
+			None,
+			None,
+			script,
+		);
+		let worker = executor
+			.as_any()
+			.downcast_ref::<WorkerScriptExecutor>()
+			.unwrap();
+		assert_eq!(worker.language, ScriptLanguage::Python);
+		assert_eq!(worker.script_content, script);
+	}
+
+	#[cfg(feature = "wasm")]
+	#[test]
+	fn test_create_wasm_executor() {
+		let executor = ScriptExecutorFactory::create_with_mode(
+			&ScriptLanguage::Wasm,
+			&ScriptMode::Worker,
+			None,
+			None,
+			"AGFzbQEAAAA=",
+		);
+		assert_eq!(
+			executor
+				.as_any()
+				.downcast_ref::<WasmScriptExecutor>()
+				.unwrap()
+				.script_content,
+			"AGFzbQEAAAA="
+		);
+	}
+
+	#[test]
+	fn test_create_executable_executor() {
+		let executor = ScriptExecutorFactory::create_with_mode(
+			&ScriptLanguage::Executable,
+			&ScriptMode::Process,
+			Some("deno run"),
+			None,
+			"/scripts/filter.ts",
+		);
+		let executable = executor
+			.as_any()
+			.downcast_ref::<ExecutableScriptExecutor>()
+			.unwrap();
+		assert_eq!(executable.script_path, "/scripts/filter.ts");
+		assert_eq!(executable.interpreter.as_deref(), Some("deno run"));
+	}
 }
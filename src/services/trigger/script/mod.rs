@@ -7,8 +7,15 @@
 mod error;
 mod executor;
 mod factory;
+mod sandbox;
 mod validation;
+#[cfg(feature = "wasm")]
+mod wasm;
+mod worker;
 pub use error::ScriptError;
-pub use executor::{process_script_output, ScriptExecutor};
+pub use executor::{process_script_output, ScriptExecutor, ScriptOutput};
 pub use factory::ScriptExecutorFactory;
-pub use validation::validate_script_config;
+pub use validation::{validate_script_config, validate_script_executable, validate_script_sandbox};
+#[cfg(feature = "wasm")]
+pub use wasm::{WasmScriptExecutor, WASM_FUEL_PER_MS, WASM_MEMORY_LIMIT};
+pub use worker::WorkerScriptExecutor;
@@ -0,0 +1,409 @@
+//! Trigger script sandbox implementation.
+//!
+//! This module applies the execution policy of a script to the process running it: a cleared or
+//! allow-listed environment, resource limits, a current directory and, on Linux, a private network
+//! namespace in which only an unconfigured loopback interface exists.
+//!
+//! The current directory only sets where relative paths resolve from: it does not confine the
+//! script, which can access any file the monitor can.
+
+use crate::{models::ScriptSandbox, services::trigger::script::error::ScriptError};
+use std::{path::Path, process::ExitStatus};
+use tokio::process::{Child, Command};
+
+/// Spawns the process of a script, restricted by its sandbox policy
+///
+/// # Arguments
+/// * `command` - Command running the script interpreter
+/// * `sandbox` - Policy applied to the process, if any
+/// * `description` - Description of the process used in error messages
+///
+/// # Returns
+/// * `Result<Child, anyhow::Error>` - The spawned process, failures to apply the policy are
+///   reported as `ScriptError`s
+pub fn spawn_sandboxed(
+	mut command: Command,
+	sandbox: Option<&ScriptSandbox>,
+	description: &str,
+) -> Result<Child, anyhow::Error> {
+	let Some(sandbox) = sandbox else {
+		return command
+			.spawn()
+			.map_err(|e| anyhow::anyhow!("Failed to spawn {}: {}", description, e));
+	};
+
+	apply_sandbox(&mut command, sandbox)?;
+	command.spawn().map_err(|e| {
+		ScriptError::system_error(
+			format!("Failed to spawn {} in sandbox: {}", description, e),
+			Some(Box::new(e)),
+			None,
+		)
+		.into()
+	})
+}
+
+/// Messages printed by interpreters and shells failing to allocate memory
+const OUT_OF_MEMORY_MESSAGES: [&str; 4] = [
+	"MemoryError",
+	"out of memory",
+	"Cannot allocate memory",
+	"allocation failed",
+];
+
+/// Returns the violation of a sandbox policy which terminated a script, if any
+///
+/// Exceeding the CPU time limit raises a signal. Exceeding the memory limit makes allocations
+/// fail, which is only reported as a violation when the standard error of the script shows a
+/// failed allocation: other crashes are left to be reported as they are.
+///
+/// # Arguments
+/// * `status` - Exit status of the script process
+/// * `stderr` - Standard error output of the script, empty when it is not captured
+/// * `sandbox` - Policy the script ran under, if any
+pub fn sandbox_violation(
+	status: &ExitStatus,
+	stderr: &[u8],
+	sandbox: Option<&ScriptSandbox>,
+) -> Option<ScriptError> {
+	let sandbox = sandbox?;
+	if status.success() {
+		return None;
+	}
+
+	if let Some(max_cpu_seconds) = sandbox.max_cpu_seconds {
+		// The soft CPU limit raises SIGXCPU, the hard limit one second later kills the process
+		#[cfg(unix)]
+		if matches!(
+			std::os::unix::process::ExitStatusExt::signal(status),
+			Some(libc::SIGXCPU) | Some(libc::SIGKILL)
+		) {
+			return Some(ScriptError::execution_error(
+				format!(
+					"Script exceeded its CPU time limit of {} seconds",
+					max_cpu_seconds
+				),
+				None,
+				None,
+			));
+		}
+		#[cfg(not(unix))]
+		let _ = max_cpu_seconds;
+	}
+
+	if let Some(max_memory_bytes) = sandbox.max_memory_bytes {
+		let stderr = String::from_utf8_lossy(stderr);
+		if OUT_OF_MEMORY_MESSAGES
+			.iter()
+			.any(|message| stderr.contains(message))
+		{
+			return Some(ScriptError::execution_error(
+				format!(
+					"Script exceeded its memory limit of {} bytes",
+					max_memory_bytes
+				),
+				None,
+				None,
+			));
+		}
+	}
+
+	None
+}
+
+/// Applies the environment, current directory and process limits of a policy to a command
+#[allow(clippy::result_large_err)]
+fn apply_sandbox(command: &mut Command, sandbox: &ScriptSandbox) -> Result<(), ScriptError> {
+	if let Some(allowlist) = &sandbox.env_allowlist {
+		command.env_clear();
+		for name in allowlist {
+			if let Some(value) = std::env::var_os(name) {
+				command.env(name, value);
+			}
+		}
+	}
+
+	if let Some(current_dir) = &sandbox.current_dir {
+		if !Path::new(current_dir).is_dir() {
+			return Err(ScriptError::not_found(
+				format!("Script current directory does not exist: {}", current_dir),
+				None,
+				None,
+			));
+		}
+		command.current_dir(current_dir);
+	}
+
+	apply_process_limits(command, sandbox)
+}
+
+#[cfg(unix)]
+#[allow(clippy::result_large_err)]
+fn apply_process_limits(command: &mut Command, sandbox: &ScriptSandbox) -> Result<(), ScriptError> {
+	#[cfg(not(target_os = "linux"))]
+	if sandbox.no_network {
+		return Err(ScriptError::system_error(
+			"Network isolation of scripts requires Linux network namespaces",
+			None,
+			None,
+		));
+	}
+
+	// The hard CPU limit leaves the script a second to handle SIGXCPU
+	let limits = [
+		(
+			libc::RLIMIT_CPU,
+			sandbox.max_cpu_seconds.map(|limit| (limit, limit + 1)),
+		),
+		(
+			libc::RLIMIT_AS,
+			sandbox.max_memory_bytes.map(|limit| (limit, limit)),
+		),
+		(
+			libc::RLIMIT_NOFILE,
+			sandbox.max_open_files.map(|limit| (limit, limit)),
+		),
+	];
+	let no_network = sandbox.no_network;
+
+	if limits.iter().all(|(_, limit)| limit.is_none()) && !no_network {
+		return Ok(());
+	}
+
+	// SAFETY: the closure runs in the forked child before exec, it only performs system calls
+	// which are async-signal-safe and does not allocate
+	unsafe {
+		command.pre_exec(move || {
+			for (resource, limit) in limits {
+				let Some((soft, hard)) = limit else {
+					continue;
+				};
+				// Limits can only be lowered, never above the current hard limit
+				let mut current = libc::rlimit {
+					rlim_cur: 0,
+					rlim_max: 0,
+				};
+				if libc::getrlimit(resource, &mut current) != 0 {
+					return Err(std::io::Error::last_os_error());
+				}
+				let hard = (hard as libc::rlim_t).min(current.rlim_max);
+				let limit = libc::rlimit {
+					rlim_cur: (soft as libc::rlim_t).min(hard),
+					rlim_max: hard,
+				};
+				if libc::setrlimit(resource, &limit) != 0 {
+					return Err(std::io::Error::last_os_error());
+				}
+			}
+			#[cfg(target_os = "linux")]
+			if no_network {
+				unshare_network()?;
+			}
+			Ok(())
+		});
+	}
+
+	Ok(())
+}
+
+#[cfg(not(unix))]
+#[allow(clippy::result_large_err)]
+fn apply_process_limits(
+	_command: &mut Command,
+	sandbox: &ScriptSandbox,
+) -> Result<(), ScriptError> {
+	if sandbox.max_cpu_seconds.is_some()
+		|| sandbox.max_memory_bytes.is_some()
+		|| sandbox.max_open_files.is_some()
+		|| sandbox.no_network
+	{
+		return Err(ScriptError::system_error(
+			"Script process limits are only supported on Unix systems",
+			None,
+			None,
+		));
+	}
+	Ok(())
+}
+
+/// Moves the current process into a new network namespace
+///
+/// Unprivileged processes first enter a new user namespace, which allows them to create one.
+#[cfg(target_os = "linux")]
+fn unshare_network() -> std::io::Result<()> {
+	// SAFETY: unshare only affects the namespaces of the calling process
+	if unsafe { libc::unshare(libc::CLONE_NEWNET) } == 0 {
+		return Ok(());
+	}
+	// SAFETY: see above
+	if unsafe { libc::unshare(libc::CLONE_NEWUSER | libc::CLONE_NEWNET) } == 0 {
+		return Ok(());
+	}
+	Err(std::io::Error::last_os_error())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+	use tokio::io::AsyncReadExt;
+
+	async fn run_sh(script: &str, sandbox: &ScriptSandbox) -> Result<String, anyhow::Error> {
+		let mut command = Command::new("sh");
+		command
+			.arg("-c")
+			.arg(script)
+			.stdout(std::process::Stdio::piped());
+		let mut child = spawn_sandboxed(command, Some(sandbox), "shell process")?;
+		let mut output = String::new();
+		child
+			.stdout
+			.take()
+			.unwrap()
+			.read_to_string(&mut output)
+			.await?;
+		child.wait().await?;
+		Ok(output.trim().to_string())
+	}
+
+	#[tokio::test]
+	async fn test_env_allowlist() {
+		std::env::set_var("SANDBOX_TEST_ALLOWED", "allowed");
+		std::env::set_var("SANDBOX_TEST_SECRET", "secret");
+		let sandbox = ScriptSandbox {
+			env_allowlist: Some(vec!["SANDBOX_TEST_ALLOWED".to_string()]),
+			..Default::default()
+		};
+
+		let output = run_sh(
+			"echo \"$SANDBOX_TEST_ALLOWED:$SANDBOX_TEST_SECRET\"",
+			&sandbox,
+		)
+		.await
+		.unwrap();
+		assert_eq!(output, "allowed:");
+	}
+
+	#[tokio::test]
+	async fn test_current_dir() {
+		let dir = TempDir::new().unwrap();
+		let sandbox = ScriptSandbox {
+			current_dir: Some(dir.path().to_str().unwrap().to_string()),
+			..Default::default()
+		};
+
+		let output = run_sh("pwd", &sandbox).await.unwrap();
+		assert_eq!(
+			Path::new(&output).canonicalize().unwrap(),
+			dir.path().canonicalize().unwrap()
+		);
+	}
+
+	#[tokio::test]
+	async fn test_missing_current_dir() {
+		let sandbox = ScriptSandbox {
+			current_dir: Some("/nonexistent/sandbox".to_string()),
+			..Default::default()
+		};
+
+		let error = run_sh("pwd", &sandbox).await.unwrap_err();
+		assert!(matches!(
+			error.downcast_ref::<ScriptError>(),
+			Some(ScriptError::NotFound(_))
+		));
+	}
+
+	#[cfg(unix)]
+	#[tokio::test]
+	async fn test_open_files_limit() {
+		let sandbox = ScriptSandbox {
+			max_open_files: Some(16),
+			..Default::default()
+		};
+
+		let output = run_sh("ulimit -n", &sandbox).await.unwrap();
+		assert_eq!(output, "16");
+	}
+
+	#[cfg(target_os = "linux")]
+	#[tokio::test]
+	async fn test_no_network() {
+		use std::os::unix::process::CommandExt;
+
+		// Network namespaces need privileges or user namespaces, which some hosts disable
+		let mut probe = std::process::Command::new("true");
+		// SAFETY: unshare_network only performs a system call
+		unsafe {
+			probe.pre_exec(unshare_network);
+		}
+		if probe.status().is_err() {
+			eprintln!("Skipping test_no_network: network namespaces are not available");
+			return;
+		}
+
+		let sandbox = ScriptSandbox {
+			no_network: true,
+			..Default::default()
+		};
+
+		// Only the loopback interface exists in the namespace of the script
+		let output = run_sh(
+			"tail -n +3 /proc/net/dev | cut -d: -f1 | tr -d ' '",
+			&sandbox,
+		)
+		.await
+		.unwrap();
+		assert_eq!(output, "lo");
+	}
+
+	#[cfg(unix)]
+	#[tokio::test]
+	async fn test_cpu_limit_violation() {
+		let sandbox = ScriptSandbox {
+			max_cpu_seconds: Some(1),
+			..Default::default()
+		};
+		let mut command = Command::new("sh");
+		command.arg("-c").arg("while :; do :; done");
+
+		let mut child = spawn_sandboxed(command, Some(&sandbox), "shell process").unwrap();
+		let status = child.wait().await.unwrap();
+		let violation = sandbox_violation(&status, &[], Some(&sandbox)).unwrap();
+		assert!(violation
+			.to_string()
+			.contains("exceeded its CPU time limit"));
+	}
+
+	#[cfg(unix)]
+	#[tokio::test]
+	async fn test_memory_limit_violation() {
+		let sandbox = ScriptSandbox {
+			max_memory_bytes: Some(256 * 1024 * 1024),
+			..Default::default()
+		};
+		let mut command = Command::new("python3");
+		command
+			.arg("-c")
+			.arg("data = bytearray(1024 * 1024 * 1024)")
+			.stderr(std::process::Stdio::piped());
+
+		let child = spawn_sandboxed(command, Some(&sandbox), "python process").unwrap();
+		let output = child.wait_with_output().await.unwrap();
+		let violation = sandbox_violation(&output.status, &output.stderr, Some(&sandbox)).unwrap();
+		assert!(violation.to_string().contains("exceeded its memory limit"));
+
+		// Failures unrelated to the limits are not violations
+		let mut command = Command::new("sh");
+		command.arg("-c").arg("exit 1");
+		let mut child = spawn_sandboxed(command, Some(&sandbox), "shell process").unwrap();
+		let status = child.wait().await.unwrap();
+		assert!(sandbox_violation(&status, b"error", Some(&sandbox)).is_none());
+
+		// Crashes are not reported as memory violations without a failed allocation
+		let mut command = Command::new("sh");
+		command.arg("-c").arg("kill -SEGV $$");
+		let mut child = spawn_sandboxed(command, Some(&sandbox), "shell process").unwrap();
+		let status = child.wait().await.unwrap();
+		assert!(sandbox_violation(&status, b"", Some(&sandbox)).is_none());
+	}
+}
@@ -2,9 +2,14 @@
 //!
 //! This module provides functionality to validate script configuration parameters.
 
-use crate::models::{ConfigError, ScriptLanguage};
+use crate::models::{ConfigError, ScriptLanguage, ScriptSandbox};
 use std::path::Path;
 
+#[cfg(feature = "wasm")]
+use crate::services::trigger::script::wasm::compile_wasm_module;
+#[cfg(feature = "wasm")]
+use base64::{prelude::BASE64_STANDARD, Engine as _};
+
 /// Validates script configuration parameters
 ///
 /// # Arguments
@@ -41,6 +46,9 @@ pub fn validate_script_config(
 		ScriptLanguage::Python => extension == "py",
 		ScriptLanguage::JavaScript => extension == "js",
 		ScriptLanguage::Bash => extension == "sh",
+		ScriptLanguage::Wasm => extension == "wasm",
+		// Executables are identified by their permissions or shebang, not their name
+		ScriptLanguage::Executable => true,
 	};
 
 	if !valid_extension {
@@ -63,6 +71,152 @@ pub fn validate_script_config(
 		));
 	}
 
+	#[cfg(not(feature = "wasm"))]
+	if *language == ScriptLanguage::Wasm {
+		return Err(ConfigError::validation_error(
+			format!(
+				"WASM modules require the monitor to be built with the `wasm` feature: {}",
+				script_path
+			),
+			None,
+			None,
+		));
+	}
+
+	// Validate WASM modules compile, as they are not checked by an interpreter
+	#[cfg(feature = "wasm")]
+	if *language == ScriptLanguage::Wasm {
+		let content = std::fs::read(script_path).map_err(|e| {
+			ConfigError::validation_error(
+				format!("Failed to read WASM module {}: {}", script_path, e),
+				None,
+				None,
+			)
+		})?;
+		compile_wasm_module(&BASE64_STANDARD.encode(content)).map_err(|e| {
+			ConfigError::validation_error(format!("{}: {}", e, script_path), None, None)
+		})?;
+	}
+
+	Ok(())
+}
+
+/// Validates how an executable script is run
+///
+/// Executables without an interpreter override are run directly, they must have an executable
+/// permission bit set. Interpreter overrides only apply to executables.
+///
+/// # Arguments
+/// * `script_path` - Path to the script file
+/// * `language` - The supported script language
+/// * `interpreter` - Command running the script instead of the file itself
+///
+/// # Returns
+/// * `Ok(())` if validation passes
+/// * `Err(ConfigError)` if any validation fails
+#[allow(clippy::result_large_err)]
+pub fn validate_script_executable(
+	script_path: &str,
+	language: &ScriptLanguage,
+	interpreter: Option<&str>,
+) -> Result<(), ConfigError> {
+	match (language, interpreter) {
+		(ScriptLanguage::Executable, Some(interpreter)) => {
+			if interpreter.trim().is_empty() {
+				return Err(ConfigError::validation_error(
+					"Script interpreter must not be empty",
+					None,
+					None,
+				));
+			}
+			Ok(())
+		}
+		(ScriptLanguage::Executable, None) => {
+			if !is_executable(Path::new(script_path)) {
+				return Err(ConfigError::validation_error(
+					format!("Script file is not executable: {}", script_path),
+					None,
+					None,
+				));
+			}
+			Ok(())
+		}
+		(_, Some(_)) => Err(ConfigError::validation_error(
+			format!(
+				"Script interpreter is only supported for the Executable language, not {:?}",
+				language
+			),
+			None,
+			None,
+		)),
+		(_, None) => Ok(()),
+	}
+}
+
+/// Returns whether a file can be executed
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+	use std::os::unix::fs::PermissionsExt;
+
+	path.metadata()
+		.map(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+		.unwrap_or(false)
+}
+
+/// Returns whether a file can be executed
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+	path.is_file()
+}
+
+/// Validates the sandbox policy of a script
+///
+/// # Arguments
+/// * `sandbox` - Restrictions applied to the script process
+///
+/// # Returns
+/// * `Ok(())` if validation passes
+/// * `Err(ConfigError)` if any validation fails
+#[allow(clippy::result_large_err)]
+pub fn validate_script_sandbox(sandbox: &ScriptSandbox) -> Result<(), ConfigError> {
+	let limits = [
+		("max_cpu_seconds", sandbox.max_cpu_seconds),
+		("max_memory_bytes", sandbox.max_memory_bytes),
+		("max_open_files", sandbox.max_open_files),
+	];
+	for (name, limit) in limits {
+		if limit == Some(0) {
+			return Err(ConfigError::validation_error(
+				format!("Sandbox {} must be greater than 0", name),
+				None,
+				None,
+			));
+		}
+	}
+
+	if let Some(name) = sandbox
+		.env_allowlist
+		.iter()
+		.flatten()
+		.find(|name| name.is_empty() || name.contains('='))
+	{
+		return Err(ConfigError::validation_error(
+			format!("Invalid sandbox environment variable name: '{}'", name),
+			None,
+			None,
+		));
+	}
+
+	if let Some(current_dir) = &sandbox.current_dir {
+		if !Path::new(current_dir).is_dir() {
+			return Err(ConfigError::validation_error(
+				format!("Sandbox current directory does not exist: {}", current_dir),
+				None,
+				None,
+			));
+		}
+	}
+
 	Ok(())
 }
 
@@ -127,4 +281,105 @@ mod tests {
 		}
 		fs::remove_file(python_path).unwrap();
 	}
+
+	#[cfg(feature = "wasm")]
+	#[test]
+	fn test_validate_script_config_wasm() {
+		let temp_file = NamedTempFile::new().unwrap();
+		let wasm_path = temp_file.path().to_str().unwrap().to_string() + ".wasm";
+		fs::write(&wasm_path, wat::parse_str("(module)").unwrap()).unwrap();
+
+		let result = validate_script_config(&wasm_path, &ScriptLanguage::Wasm, &1000);
+		assert!(result.is_ok());
+
+		fs::write(&wasm_path, "print(True)").unwrap();
+		let result = validate_script_config(&wasm_path, &ScriptLanguage::Wasm, &1000);
+		assert!(result
+			.unwrap_err()
+			.to_string()
+			.contains("Invalid WASM module"));
+		fs::remove_file(wasm_path).unwrap();
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn test_validate_script_executable() {
+		use std::os::unix::fs::PermissionsExt;
+
+		let temp_file = NamedTempFile::new().unwrap();
+		let path = temp_file.path().to_str().unwrap();
+		fs::write(path, "#!/bin/sh\necho true\n").unwrap();
+
+		assert!(validate_script_config(path, &ScriptLanguage::Executable, &1000).is_ok());
+		assert!(
+			validate_script_executable(path, &ScriptLanguage::Executable, None)
+				.unwrap_err()
+				.to_string()
+				.contains("Script file is not executable")
+		);
+		assert!(validate_script_executable(path, &ScriptLanguage::Executable, Some("sh")).is_ok());
+
+		fs::set_permissions(path, fs::Permissions::from_mode(0o755)).unwrap();
+		assert!(validate_script_executable(path, &ScriptLanguage::Executable, None).is_ok());
+	}
+
+	#[test]
+	fn test_validate_script_executable_interpreter() {
+		assert!(
+			validate_script_executable("script.py", &ScriptLanguage::Python, Some("python3"))
+				.unwrap_err()
+				.to_string()
+				.contains("only supported for the Executable language")
+		);
+		assert!(
+			validate_script_executable("script", &ScriptLanguage::Executable, Some("  "))
+				.unwrap_err()
+				.to_string()
+				.contains("Script interpreter must not be empty")
+		);
+		assert!(validate_script_executable("script.py", &ScriptLanguage::Python, None).is_ok());
+	}
+
+	#[test]
+	fn test_validate_script_sandbox() {
+		let sandbox = ScriptSandbox {
+			env_allowlist: Some(vec!["PATH".to_string()]),
+			max_cpu_seconds: Some(5),
+			current_dir: Some(std::env::temp_dir().to_str().unwrap().to_string()),
+			..Default::default()
+		};
+		assert!(validate_script_sandbox(&sandbox).is_ok());
+
+		let zero_limit = ScriptSandbox {
+			max_open_files: Some(0),
+			..Default::default()
+		};
+		assert!(validate_script_sandbox(&zero_limit)
+			.unwrap_err()
+			.to_string()
+			.contains("Sandbox max_open_files must be greater than 0"));
+
+		let invalid_env = ScriptSandbox {
+			env_allowlist: Some(vec!["A=B".to_string()]),
+			..Default::default()
+		};
+		assert!(validate_script_sandbox(&invalid_env)
+			.unwrap_err()
+			.to_string()
+			.contains("Invalid sandbox environment variable name"));
+
+		let missing_dir = ScriptSandbox {
+			current_dir: Some("/nonexistent/sandbox".to_string()),
+			..Default::default()
+		};
+		assert!(validate_script_sandbox(&missing_dir)
+			.unwrap_err()
+			.to_string()
+			.contains("Sandbox current directory does not exist"));
+
+		// The former name of the option is still accepted
+		let renamed: ScriptSandbox =
+			serde_json::from_value(serde_json::json!({ "working_dir": "/tmp" })).unwrap();
+		assert_eq!(renamed.current_dir.as_deref(), Some("/tmp"));
+	}
 }
@@ -0,0 +1,366 @@
+//! WebAssembly script executor implementation.
+//!
+//! WASM modules run in-process on an embedded interpreter, without access to the host: they
+//! cannot import any function, so they can only compute over the input they are given. Instead
+//! of a wall-clock timeout, each call is bounded by fuel, derived from `timeout_ms`, and by a
+//! linear memory limit.
+//!
+//! Modules export their `memory` and an `alloc(len: i32) -> i32` function, which the monitor
+//! calls to reserve space for the JSON input `{"args": ..., "monitor_match": ...}`. The input is
+//! then passed to `evaluate(ptr: i32, len: i32) -> i32`, returning non-zero to filter the match
+//! out.
+//!
+//! Modules only serve as trigger conditions: without access to the host, they cannot send custom
+//! notifications.
+
+use crate::models::MonitorMatch;
+use anyhow::Context;
+use async_trait::async_trait;
+use base64::{prelude::BASE64_STANDARD, Engine as _};
+use lazy_static::lazy_static;
+use std::{
+	any::Any,
+	collections::HashMap,
+	sync::{Arc, Mutex},
+};
+use wasmi::{
+	core::TrapCode, Config, Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder,
+};
+
+use super::executor::{ScriptExecutor, ScriptOutput};
+
+/// Fuel granted to a call for each millisecond of its timeout
+pub const WASM_FUEL_PER_MS: u64 = 100_000;
+
+/// Maximum size of the linear memory of a module, in bytes
+pub const WASM_MEMORY_LIMIT: usize = 64 * 1024 * 1024;
+
+lazy_static! {
+	/// Engine shared by every module, metering fuel
+	static ref WASM_ENGINE: Engine = {
+		let mut config = Config::default();
+		config.consume_fuel(true);
+		Engine::new(&config)
+	};
+	/// Compiled modules keyed by their encoded content
+	static ref WASM_MODULES: Mutex<HashMap<String, Arc<Module>>> = Mutex::new(HashMap::new());
+}
+
+/// Decodes and compiles a module
+///
+/// WASM modules are binary, their content is loaded base64 encoded.
+///
+/// # Arguments
+/// * `script_content` - Base64 encoded content of the module
+pub fn compile_wasm_module(script_content: &str) -> Result<Module, anyhow::Error> {
+	let bytes = BASE64_STANDARD
+		.decode(script_content.trim())
+		.with_context(|| "WASM module content is not base64 encoded")?;
+	Module::new(&WASM_ENGINE, &bytes[..]).map_err(|e| anyhow::anyhow!("Invalid WASM module: {}", e))
+}
+
+/// Returns the compiled module of a script, compiling it on first use
+fn wasm_module(script_content: &str) -> Result<Arc<Module>, anyhow::Error> {
+	if let Some(module) = WASM_MODULES
+		.lock()
+		.unwrap_or_else(|e| e.into_inner())
+		.get(script_content)
+	{
+		return Ok(module.clone());
+	}
+
+	let module = Arc::new(compile_wasm_module(script_content)?);
+	WASM_MODULES
+		.lock()
+		.unwrap_or_else(|e| e.into_inner())
+		.insert(script_content.to_string(), module.clone());
+	Ok(module)
+}
+
+/// Describes an error raised while running a module, naming exceeded limits
+fn wasm_error(error: wasmi::Error) -> anyhow::Error {
+	match error.as_trap_code() {
+		Some(TrapCode::OutOfFuel) => anyhow::anyhow!("WASM module ran out of fuel"),
+		Some(TrapCode::GrowthOperationLimited) => anyhow::anyhow!(
+			"WASM module exceeded its memory limit of {} bytes",
+			WASM_MEMORY_LIMIT
+		),
+		_ => anyhow::anyhow!("WASM module failed: {}", error),
+	}
+}
+
+/// Instantiates a module and passes it the input
+///
+/// # Arguments
+/// * `module` - Compiled module
+/// * `input` - JSON input of the call
+/// * `fuel` - Fuel available to the call
+///
+/// # Returns
+/// * `Result<bool, anyhow::Error>` - Whether `evaluate` filtered the match out, or an error
+fn run_wasm_module(module: &Module, input: &[u8], fuel: u64) -> Result<bool, anyhow::Error> {
+	let limits = StoreLimitsBuilder::new()
+		.memory_size(WASM_MEMORY_LIMIT)
+		.trap_on_grow_failure(true)
+		.build();
+	let mut store = Store::new(&WASM_ENGINE, limits);
+	store.limiter(|limits: &mut StoreLimits| limits);
+	store
+		.set_fuel(fuel)
+		.map_err(|e| anyhow::anyhow!("Failed to set WASM fuel: {}", e))?;
+
+	// No host function is linked, modules importing any fail to instantiate
+	let instance = Linker::<StoreLimits>::new(&WASM_ENGINE)
+		.instantiate(&mut store, module)
+		.map_err(|e| anyhow::anyhow!("Failed to instantiate WASM module: {}", e))?
+		.start(&mut store)
+		.map_err(wasm_error)?;
+
+	let memory = instance
+		.get_memory(&store, "memory")
+		.ok_or_else(|| anyhow::anyhow!("WASM module does not export its memory"))?;
+	let alloc = instance
+		.get_typed_func::<i32, i32>(&store, "alloc")
+		.map_err(|e| anyhow::anyhow!("WASM module does not export alloc: {}", e))?;
+
+	let len = i32::try_from(input.len()).with_context(|| "WASM module input is too large")?;
+	let ptr = alloc.call(&mut store, len).map_err(wasm_error)?;
+	memory
+		.write(&mut store, ptr as u32 as usize, input)
+		.map_err(|e| anyhow::anyhow!("Failed to write input to WASM module: {}", e))?;
+
+	let evaluate = instance
+		.get_typed_func::<(i32, i32), i32>(&store, "evaluate")
+		.map_err(|e| anyhow::anyhow!("WASM module does not export evaluate: {}", e))?;
+	let result = evaluate.call(&mut store, (ptr, len)).map_err(wasm_error)?;
+	Ok(result != 0)
+}
+
+/// Executes WebAssembly modules in-process.
+pub struct WasmScriptExecutor {
+	/// Base64 encoded content of the module
+	pub script_content: String,
+}
+
+#[async_trait]
+impl ScriptExecutor for WasmScriptExecutor {
+	fn as_any(&self) -> &dyn Any {
+		self
+	}
+	async fn evaluate(
+		&self,
+		input: MonitorMatch,
+		timeout_ms: &u32,
+		args: Option<&[String]>,
+		from_custom_notification: bool,
+	) -> Result<ScriptOutput, anyhow::Error> {
+		if from_custom_notification {
+			return Err(anyhow::anyhow!(
+				"WASM modules cannot send custom notifications"
+			));
+		}
+
+		let combined_input = serde_json::json!({
+			"args": args,
+			"monitor_match": input
+		});
+		let input_json = serde_json::to_vec(&combined_input)
+			.with_context(|| "Failed to serialize monitor match and arguments")?;
+
+		let module = wasm_module(&self.script_content)?;
+		let fuel = u64::from(*timeout_ms).saturating_mul(WASM_FUEL_PER_MS);
+
+		// Modules run to completion or exhaustion of their fuel, off the async workers
+		let result =
+			tokio::task::spawn_blocking(move || run_wasm_module(&module, &input_json, fuel))
+				.await
+				.with_context(|| "WASM module execution panicked")??;
+
+		Ok(ScriptOutput::Filter(result))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		models::{EVMMonitorMatch, MatchConditions},
+		utils::tests::evm::{monitor::MonitorBuilder, transaction::TransactionBuilder},
+	};
+
+	fn create_mock_monitor_match() -> MonitorMatch {
+		MonitorMatch::EVM(Box::new(EVMMonitorMatch {
+			monitor: MonitorBuilder::new().name("test").build(),
+			transaction: TransactionBuilder::new().build(),
+			receipt: None,
+			logs: None,
+			network_slug: "evm_mainnet".to_string(),
+			matched_on: MatchConditions::default(),
+			matched_on_args: None,
+			enrichment: None,
+		}))
+	}
+
+	/// Creates an executor of a module with a bump allocator and the given functions
+	fn create_executor(functions: &str) -> WasmScriptExecutor {
+		let wat = format!(
+			r#"
+(module
+  (memory (export "memory") 1)
+  (global $next (mut i32) (i32.const 1024))
+  (func (export "alloc") (param $len i32) (result i32)
+    (local $ptr i32)
+    (local.set $ptr (global.get $next))
+    (global.set $next (i32.add (global.get $next) (local.get $len)))
+    (if (i32.gt_u (global.get $next) (i32.mul (memory.size) (i32.const 65536)))
+      (then (drop (memory.grow (i32.const 16)))))
+    (local.get $ptr))
+  {functions}
+)"#
+		);
+		WasmScriptExecutor {
+			script_content: BASE64_STANDARD.encode(wat::parse_str(wat).unwrap()),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_wasm_evaluate() {
+		// Filters out the match when the input starts with `{"args":["skip"]`
+		let executor = create_executor(
+			r#"
+  (data (i32.const 0) "{\"args\":[\"skip\"]")
+  (func (export "evaluate") (param $ptr i32) (param $len i32) (result i32)
+    (local $i i32)
+    (block $differ
+      (loop $compare
+        (br_if $differ (i32.ne
+          (i32.load8_u (i32.add (local.get $ptr) (local.get $i)))
+          (i32.load8_u (local.get $i))))
+        (local.set $i (i32.add (local.get $i) (i32.const 1)))
+        (br_if $compare (i32.lt_u (local.get $i) (i32.const 16))))
+      (return (i32.const 1)))
+    (i32.const 0))"#,
+		);
+
+		let result = executor
+			.execute(
+				create_mock_monitor_match(),
+				&1000,
+				Some(&["skip".to_string()]),
+				false,
+			)
+			.await;
+		assert!(result.unwrap());
+
+		let result = executor
+			.execute(create_mock_monitor_match(), &1000, None, false)
+			.await;
+		assert!(!result.unwrap());
+	}
+
+	#[tokio::test]
+	async fn test_wasm_custom_notification_rejected() {
+		let executor = create_executor(
+			r#"
+  (func (export "evaluate") (param $ptr i32) (param $len i32) (result i32)
+    (i32.const 0))"#,
+		);
+
+		let result = executor
+			.execute(create_mock_monitor_match(), &1000, None, true)
+			.await;
+		assert!(result
+			.unwrap_err()
+			.to_string()
+			.contains("cannot send custom notifications"));
+	}
+
+	#[tokio::test]
+	async fn test_wasm_out_of_fuel() {
+		let executor = create_executor(
+			r#"
+  (func (export "evaluate") (param $ptr i32) (param $len i32) (result i32)
+    (loop $forever (br $forever))
+    (i32.const 0))"#,
+		);
+
+		let result = executor
+			.execute(create_mock_monitor_match(), &10, None, false)
+			.await;
+		assert!(result
+			.unwrap_err()
+			.to_string()
+			.contains("WASM module ran out of fuel"));
+	}
+
+	#[tokio::test]
+	async fn test_wasm_memory_limit() {
+		let executor = create_executor(
+			r#"
+  (func (export "evaluate") (param $ptr i32) (param $len i32) (result i32)
+    (drop (memory.grow (i32.const 2048)))
+    (i32.const 0))"#,
+		);
+
+		let result = executor
+			.execute(create_mock_monitor_match(), &1000, None, false)
+			.await;
+		assert!(result
+			.unwrap_err()
+			.to_string()
+			.contains("WASM module exceeded its memory limit"));
+	}
+
+	#[tokio::test]
+	async fn test_wasm_trap() {
+		let executor = create_executor(
+			r#"
+  (func (export "evaluate") (param $ptr i32) (param $len i32) (result i32)
+    (unreachable))"#,
+		);
+
+		let result = executor
+			.execute(create_mock_monitor_match(), &1000, None, false)
+			.await;
+		assert!(result
+			.unwrap_err()
+			.to_string()
+			.contains("WASM module failed"));
+	}
+
+	#[tokio::test]
+	async fn test_wasm_missing_export() {
+		let executor = create_executor("");
+
+		let result = executor
+			.execute(create_mock_monitor_match(), &1000, None, false)
+			.await;
+		assert!(result
+			.unwrap_err()
+			.to_string()
+			.contains("WASM module does not export evaluate"));
+	}
+
+	#[tokio::test]
+	async fn test_wasm_host_imports_are_rejected() {
+		let executor = WasmScriptExecutor {
+			script_content: BASE64_STANDARD.encode(
+				wat::parse_str(r#"(module (import "env" "system" (func (param i32))))"#).unwrap(),
+			),
+		};
+
+		let result = executor
+			.execute(create_mock_monitor_match(), &1000, None, false)
+			.await;
+		assert!(result
+			.unwrap_err()
+			.to_string()
+			.contains("Failed to instantiate WASM module"));
+	}
+
+	#[test]
+	fn test_compile_invalid_module() {
+		assert!(compile_wasm_module("not base64!").is_err());
+		assert!(compile_wasm_module(&BASE64_STANDARD.encode(b"print(True)")).is_err());
+	}
+}
@@ -0,0 +1,564 @@
+//! Persistent script worker implementation.
+//!
+//! In worker mode a script is started once and kept running, instead of being spawned for every
+//! match. Requests and responses are exchanged as newline-delimited JSON over the worker's stdin
+//! and stdout:
+//!
+//! * `{"id": 1, "type": "ping"}` checks that a (re)started worker is healthy
+//! * `{"id": 2, "type": "match", "monitor_match": {...}, "args": [...]}` evaluates a match
+//!
+//! Each request is answered with `{"id": 2, "result": true}`, a JSON object as `result` to enrich
+//! the match, or `{"id": 2, "error": "..."}` when the script failed to process it. Output lines which are not a response to the pending request
+//! are ignored. Workers exit once their stdin is closed.
+
+use crate::{
+	models::{MonitorMatch, ScriptLanguage, ScriptSandbox},
+	services::trigger::script::sandbox::{sandbox_violation, spawn_sandboxed},
+};
+use anyhow::Context;
+use async_trait::async_trait;
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use std::{
+	any::Any,
+	collections::HashMap,
+	process::Stdio,
+	sync::{Arc, Mutex},
+	time::Duration,
+};
+use tokio::{
+	io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines},
+	process::{Child, ChildStdin, ChildStdout, Command},
+	time::timeout,
+};
+
+use super::executor::{executable_command, ScriptExecutor, ScriptOutput};
+
+/// Slot holding the running worker of a script, if any
+type WorkerSlot = Arc<tokio::sync::Mutex<Option<ScriptWorker>>>;
+
+/// Identifies the worker of a script run with an interpreter under a sandbox policy
+type WorkerKey = (
+	ScriptLanguage,
+	String,
+	Option<String>,
+	Option<ScriptSandbox>,
+);
+
+lazy_static! {
+	/// Workers shared by every executor of the same script and sandbox policy
+	static ref SCRIPT_WORKERS: Mutex<HashMap<WorkerKey, WorkerSlot>> =
+		Mutex::new(HashMap::new());
+}
+
+/// Response of a worker to a request
+#[derive(Debug, Deserialize)]
+struct WorkerResponse {
+	/// Identifier of the request being answered
+	id: u64,
+	/// Outcome of the request, a boolean or the fields enriching the match
+	#[serde(default)]
+	result: Option<serde_json::Value>,
+	/// Error raised by the script while processing the request
+	#[serde(default)]
+	error: Option<String>,
+}
+
+/// Long-lived interpreter running a script
+struct ScriptWorker {
+	child: Child,
+	stdin: ChildStdin,
+	stdout: Lines<BufReader<ChildStdout>>,
+	/// Identifier of the next request
+	next_id: u64,
+	/// Restrictions the worker process runs under
+	sandbox: Option<ScriptSandbox>,
+}
+
+impl ScriptWorker {
+	/// Starts the interpreter of a script
+	///
+	/// # Arguments
+	/// * `language` - Language of the script
+	/// * `script_content` - Content of the script, or path of an executable
+	/// * `interpreter` - Command running an executable instead of the file itself
+	/// * `sandbox` - Restrictions applied to the worker process
+	fn spawn(
+		language: &ScriptLanguage,
+		script_content: &str,
+		interpreter: Option<&str>,
+		sandbox: Option<&ScriptSandbox>,
+	) -> Result<Self, anyhow::Error> {
+		let (mut command, program) = match language {
+			ScriptLanguage::Python => (inline_command("python3", "-c", script_content), "python3"),
+			ScriptLanguage::JavaScript => (inline_command("node", "-e", script_content), "node"),
+			ScriptLanguage::Bash => (inline_command("sh", "-c", script_content), "sh"),
+			ScriptLanguage::Executable => (
+				executable_command(script_content, interpreter)?,
+				script_content,
+			),
+			ScriptLanguage::Wasm => {
+				return Err(anyhow::anyhow!(
+					"WASM modules run in-process, not as workers"
+				))
+			}
+		};
+
+		command
+			.stdin(Stdio::piped())
+			.stdout(Stdio::piped())
+			.stderr(Stdio::piped())
+			.kill_on_drop(true);
+		let mut child = spawn_sandboxed(command, sandbox, &format!("{} worker process", program))?;
+
+		let stdin = child
+			.stdin
+			.take()
+			.ok_or_else(|| anyhow::anyhow!("Failed to get stdin handle"))?;
+		let stdout = child
+			.stdout
+			.take()
+			.ok_or_else(|| anyhow::anyhow!("Failed to get stdout handle"))?;
+
+		// Drain stderr so that a chatty worker never blocks on a full pipe
+		if let Some(stderr) = child.stderr.take() {
+			tokio::spawn(async move {
+				let mut lines = BufReader::new(stderr).lines();
+				while let Ok(Some(line)) = lines.next_line().await {
+					tracing::debug!("Script worker stderr: {}", line);
+				}
+			});
+		}
+
+		Ok(Self {
+			child,
+			stdin,
+			stdout: BufReader::new(stdout).lines(),
+			next_id: 1,
+			sandbox: sandbox.cloned(),
+		})
+	}
+
+	/// Whether the worker process has exited
+	fn has_exited(&mut self) -> bool {
+		!matches!(self.child.try_wait(), Ok(None))
+	}
+
+	/// Sends a request and waits for its response
+	///
+	/// # Arguments
+	/// * `request` - Request object, its identifier is assigned here
+	/// * `timeout_duration` - Time after which the request fails
+	async fn call(
+		&mut self,
+		mut request: serde_json::Value,
+		timeout_duration: Duration,
+	) -> Result<WorkerResponse, anyhow::Error> {
+		let id = self.next_id;
+		self.next_id += 1;
+		request["id"] = serde_json::json!(id);
+
+		let mut line = serde_json::to_string(&request)
+			.with_context(|| "Failed to serialize monitor match and arguments")?;
+		line.push('\n');
+
+		match timeout(timeout_duration, self.exchange(&line, id)).await {
+			Ok(result) => result,
+			Err(_) => Err(anyhow::anyhow!("Script execution timed out")),
+		}
+	}
+
+	/// Returns the error reported for a worker which closed its output
+	async fn exit_error(&mut self) -> anyhow::Error {
+		if let Ok(status) = self.child.wait().await {
+			// Worker stderr is drained to the logs, so only CPU limit violations are told from the
+			// exit status, and other crashes are reported with it
+			if let Some(violation) = sandbox_violation(&status, &[], self.sandbox.as_ref()) {
+				return violation.into();
+			}
+			return anyhow::anyhow!("Script worker exited unexpectedly ({})", status);
+		}
+		anyhow::anyhow!("Script worker exited unexpectedly")
+	}
+
+	/// Writes a request line and reads output until the response to the request
+	async fn exchange(&mut self, line: &str, id: u64) -> Result<WorkerResponse, anyhow::Error> {
+		self.stdin
+			.write_all(line.as_bytes())
+			.await
+			.map_err(|e| anyhow::anyhow!("Failed to write request to script worker: {}", e))?;
+		self.stdin
+			.flush()
+			.await
+			.map_err(|e| anyhow::anyhow!("Failed to write request to script worker: {}", e))?;
+
+		loop {
+			let output = self
+				.stdout
+				.next_line()
+				.await
+				.map_err(|e| anyhow::anyhow!("Failed to read script worker output: {}", e))?;
+			let Some(output) = output else {
+				return Err(self.exit_error().await);
+			};
+
+			match serde_json::from_str::<WorkerResponse>(&output) {
+				Ok(response) if response.id == id => return Ok(response),
+				_ => tracing::debug!("Ignoring script worker output: {}", output),
+			}
+		}
+	}
+}
+
+/// Builds the command passing the content of a script to its interpreter
+fn inline_command(program: &str, flag: &str, script_content: &str) -> Command {
+	let mut command = Command::new(program);
+	command.arg(flag).arg(script_content);
+	command
+}
+
+/// Returns the worker slot of a script, shared by every executor running it
+fn worker_slot(
+	language: &ScriptLanguage,
+	script_content: &str,
+	interpreter: Option<&str>,
+	sandbox: Option<&ScriptSandbox>,
+) -> WorkerSlot {
+	SCRIPT_WORKERS
+		.lock()
+		.unwrap_or_else(|e| e.into_inner())
+		.entry((
+			language.clone(),
+			script_content.to_string(),
+			interpreter.map(str::to_string),
+			sandbox.cloned(),
+		))
+		.or_default()
+		.clone()
+}
+
+/// Executes scripts in a persistent worker process, one per script.
+///
+/// Calls to the same script are serialized. Workers which crashed are restarted and
+/// health-checked before the next call, and workers which timed out or stopped responding are
+/// killed. Resource limits of a sandbox apply to the whole lifetime of a worker, not to each call.
+pub struct WorkerScriptExecutor {
+	/// Language of the script
+	pub language: ScriptLanguage,
+	/// Content of the script run by the worker, or path of an executable
+	pub script_content: String,
+	/// Command running an executable instead of the file itself
+	pub interpreter: Option<String>,
+	/// Restrictions applied to the worker process
+	pub sandbox: Option<ScriptSandbox>,
+}
+
+#[async_trait]
+impl ScriptExecutor for WorkerScriptExecutor {
+	fn as_any(&self) -> &dyn Any {
+		self
+	}
+	async fn evaluate(
+		&self,
+		input: MonitorMatch,
+		timeout_ms: &u32,
+		args: Option<&[String]>,
+		from_custom_notification: bool,
+	) -> Result<ScriptOutput, anyhow::Error> {
+		let timeout_duration = Duration::from_millis(u64::from(*timeout_ms));
+		let slot = worker_slot(
+			&self.language,
+			&self.script_content,
+			self.interpreter.as_deref(),
+			self.sandbox.as_ref(),
+		);
+		let mut slot = slot.lock().await;
+
+		if let Some(worker) = slot.as_mut() {
+			if worker.has_exited() {
+				tracing::warn!("Script worker exited, restarting it");
+				*slot = None;
+			}
+		}
+
+		let worker = match slot.as_mut() {
+			Some(worker) => worker,
+			None => {
+				let mut worker = ScriptWorker::spawn(
+					&self.language,
+					&self.script_content,
+					self.interpreter.as_deref(),
+					self.sandbox.as_ref(),
+				)?;
+				let ping = worker
+					.call(serde_json::json!({ "type": "ping" }), timeout_duration)
+					.await;
+				match ping {
+					Ok(WorkerResponse { error: None, .. }) => {}
+					Ok(WorkerResponse {
+						error: Some(error), ..
+					}) => {
+						return Err(anyhow::anyhow!(
+							"Script worker failed health check: {}",
+							error
+						))
+					}
+					Err(e) => {
+						return Err(anyhow::anyhow!("Script worker failed health check: {}", e))
+					}
+				}
+				slot.insert(worker)
+			}
+		};
+
+		let request = serde_json::json!({
+			"type": "match",
+			"monitor_match": input,
+			"args": args
+		});
+		let response = match worker.call(request, timeout_duration).await {
+			Ok(response) => response,
+			Err(e) => {
+				// The worker is in an unknown state, dropping it kills the process
+				*slot = None;
+				return Err(e);
+			}
+		};
+
+		if let Some(error) = response.error {
+			return Err(anyhow::anyhow!("Script execution failed: {}", error));
+		}
+
+		// Notification scripts succeed as long as they did not report an error
+		if from_custom_notification {
+			return Ok(ScriptOutput::Filter(true));
+		}
+
+		match response.result {
+			Some(serde_json::Value::Bool(result)) => Ok(ScriptOutput::Filter(result)),
+			Some(serde_json::Value::Object(fields)) => Ok(ScriptOutput::Enrich(fields)),
+			Some(result) => Err(anyhow::anyhow!(
+				"Script worker result is not a boolean or a JSON object: {}",
+				result
+			)),
+			None => Err(anyhow::anyhow!("Script worker response has no result")),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		models::{EVMMonitorMatch, MatchConditions},
+		utils::tests::evm::{monitor::MonitorBuilder, transaction::TransactionBuilder},
+	};
+
+	fn create_mock_monitor_match() -> MonitorMatch {
+		MonitorMatch::EVM(Box::new(EVMMonitorMatch {
+			monitor: MonitorBuilder::new().name("test").build(),
+			transaction: TransactionBuilder::new().build(),
+			receipt: None,
+			logs: None,
+			network_slug: "evm_mainnet".to_string(),
+			matched_on: MatchConditions::default(),
+			matched_on_args: None,
+			enrichment: None,
+		}))
+	}
+
+	/// Creates a Python worker, `body` handles a match request and sets `result`
+	///
+	/// Workers are shared by identical scripts, `name` keeps the worker of each test apart.
+	fn create_python_executor(name: &str, body: &str) -> WorkerScriptExecutor {
+		let script_content = format!(
+			r#"
+# {name}
+import sys
+import json
+import time
+
+calls = 0
+for line in sys.stdin:
+    request = json.loads(line)
+    if request["type"] == "ping":
+        print(json.dumps({{"id": request["id"], "result": True}}), flush=True)
+        continue
+    calls += 1
+    result = None
+{body}
+    print(json.dumps({{"id": request["id"], "result": result}}), flush=True)
+"#
+		);
+		WorkerScriptExecutor {
+			language: ScriptLanguage::Python,
+			script_content,
+			interpreter: None,
+			sandbox: None,
+		}
+	}
+
+	#[tokio::test]
+	async fn test_worker_persists_between_calls() {
+		let executor = create_python_executor(
+			"persists",
+			r#"    print("debugging...", flush=True)
+    result = calls == 2"#,
+		);
+
+		let first = executor
+			.execute(create_mock_monitor_match(), &5000, None, false)
+			.await;
+		let second = executor
+			.execute(create_mock_monitor_match(), &5000, None, false)
+			.await;
+		assert!(!first.unwrap());
+		assert!(second.unwrap());
+	}
+
+	#[tokio::test]
+	async fn test_worker_receives_arguments() {
+		let executor = create_python_executor(
+			"arguments",
+			r#"    result = request["args"] == ["--verbose"] and "EVM" in request["monitor_match"]"#,
+		);
+
+		let result = executor
+			.execute(
+				create_mock_monitor_match(),
+				&5000,
+				Some(&["--verbose".to_string()]),
+				false,
+			)
+			.await;
+		assert!(result.unwrap());
+	}
+
+	#[tokio::test]
+	async fn test_worker_timeout_restarts_worker() {
+		let executor = create_python_executor(
+			"timeout",
+			r#"    if calls == 1:
+        time.sleep(2)
+    result = True"#,
+		);
+
+		let result = executor
+			.execute(create_mock_monitor_match(), &200, None, false)
+			.await;
+		assert!(result
+			.unwrap_err()
+			.to_string()
+			.contains("Script execution timed out"));
+
+		// The timed out worker was killed, a fresh one starts counting again
+		let result = executor
+			.execute(create_mock_monitor_match(), &5000, None, false)
+			.await;
+		assert!(result.is_ok());
+	}
+
+	#[tokio::test]
+	async fn test_worker_crash_restarts_worker() {
+		let executor = create_python_executor(
+			"crash",
+			r#"    if request["args"] == ["crash"]:
+        sys.exit(1)
+    result = calls == 1"#,
+		);
+
+		let result = executor
+			.execute(
+				create_mock_monitor_match(),
+				&5000,
+				Some(&["crash".to_string()]),
+				false,
+			)
+			.await;
+		assert!(result
+			.unwrap_err()
+			.to_string()
+			.contains("Script worker exited unexpectedly"));
+
+		let result = executor
+			.execute(create_mock_monitor_match(), &5000, None, false)
+			.await;
+		assert!(result.unwrap());
+	}
+
+	#[tokio::test]
+	async fn test_worker_failed_health_check() {
+		let executor = WorkerScriptExecutor {
+			language: ScriptLanguage::Python,
+			script_content: "import sys\nsys.exit(1)".to_string(),
+			interpreter: None,
+			sandbox: None,
+		};
+
+		let result = executor
+			.execute(create_mock_monitor_match(), &5000, None, false)
+			.await;
+		assert!(result
+			.unwrap_err()
+			.to_string()
+			.contains("Script worker failed health check"));
+	}
+
+	#[tokio::test]
+	async fn test_worker_error_response() {
+		let executor = WorkerScriptExecutor {
+			language: ScriptLanguage::Python,
+			script_content: r#"
+import sys
+import json
+
+for line in sys.stdin:
+    request = json.loads(line)
+    if request["type"] == "ping":
+        print(json.dumps({"id": request["id"], "result": True}), flush=True)
+    else:
+        print(json.dumps({"id": request["id"], "error": "invalid match"}), flush=True)
+"#
+			.to_string(),
+			interpreter: None,
+			sandbox: None,
+		};
+
+		let result = executor
+			.execute(create_mock_monitor_match(), &5000, None, true)
+			.await;
+		assert!(result.unwrap_err().to_string().contains("invalid match"));
+	}
+
+	#[tokio::test]
+	async fn test_worker_enrich() {
+		let executor = create_python_executor("enrich", r#"    result = {"calls": calls}"#);
+
+		let result = executor
+			.evaluate(create_mock_monitor_match(), &5000, None, false)
+			.await;
+		let ScriptOutput::Enrich(fields) = result.unwrap() else {
+			panic!("Expected enrichment");
+		};
+		assert_eq!(fields["calls"], serde_json::json!(1));
+	}
+
+	#[tokio::test]
+	async fn test_worker_from_custom_notification() {
+		// Notification scripts do not need to report a result
+		let executor = create_python_executor("notification", "    pass");
+
+		let result = executor
+			.execute(create_mock_monitor_match(), &5000, None, true)
+			.await;
+		assert!(result.unwrap());
+
+		let result = executor
+			.execute(create_mock_monitor_match(), &5000, None, false)
+			.await;
+		assert!(result
+			.unwrap_err()
+			.to_string()
+			.contains("Script worker response has no result"));
+	}
+}
@@ -7,9 +7,10 @@ use std::{collections::HashMap, path::Path};
 
 use anyhow::Context;
 use async_trait::async_trait;
+use base64::{prelude::BASE64_STANDARD, Engine as _};
 
 use crate::{
-	models::{Monitor, MonitorMatch, ScriptLanguage, TriggerTypeConfig},
+	models::{Monitor, MonitorMatch, ScriptLanguage, Trigger, TriggerType, TriggerTypeConfig},
 	repositories::{TriggerRepositoryTrait, TriggerService},
 	services::{notification::NotificationService, trigger::error::TriggerError},
 	utils::normalize_string,
@@ -32,6 +33,41 @@ pub trait TriggerExecutionServiceTrait {
 		&self,
 		monitors: &[Monitor],
 	) -> Result<HashMap<String, (ScriptLanguage, String)>, TriggerError>;
+
+	/// Whether the notifications of a trigger must be sent in block order
+	///
+	/// Message queue triggers publish the matches of a network in block order, the other
+	/// triggers are notified as soon as their matches are found.
+	///
+	/// # Arguments
+	/// * `trigger_slug` - Identifier of the trigger
+	fn is_ordered(&self, _trigger_slug: &str) -> bool {
+		false
+	}
+}
+
+/// Reads the content of a script
+///
+/// WASM modules are binary, their content is base64 encoded so that every script is held as
+/// text. Executables are run from disk, they are held as their absolute path.
+async fn read_script(script_path: &Path, language: &ScriptLanguage) -> std::io::Result<String> {
+	match language {
+		ScriptLanguage::Executable => tokio::fs::canonicalize(script_path)
+			.await
+			.map(|path| path.to_string_lossy().into_owned()),
+		ScriptLanguage::Wasm => tokio::fs::read(script_path)
+			.await
+			.map(|content| BASE64_STANDARD.encode(content)),
+		_ => tokio::fs::read_to_string(script_path).await,
+	}
+}
+
+/// Whether a trigger publishes its matches to a message queue
+fn is_queue_trigger(trigger: &Trigger) -> bool {
+	matches!(
+		trigger.trigger_type,
+		TriggerType::Kafka | TriggerType::Nats | TriggerType::Redis | TriggerType::Amqp
+	)
 }
 
 /// Service for executing triggers with notifications
@@ -63,6 +99,68 @@ impl<T: TriggerRepositoryTrait> TriggerExecutionService<T> {
 			notification_service,
 		}
 	}
+
+	/// Returns the notification service, on which notifier plugins are registered
+	pub fn notification_service(&self) -> &NotificationService {
+		&self.notification_service
+	}
+
+	/// Returns the triggers configured with a digest, with their identifiers
+	pub fn digest_triggers(&self) -> Vec<(String, Trigger)> {
+		self.trigger_service
+			.get_all()
+			.into_iter()
+			.filter(|(_, trigger)| trigger.digest.is_some())
+			.collect()
+	}
+
+	/// Sends the matches buffered for a digest trigger
+	///
+	/// # Arguments
+	/// * `trigger_slug` - Identifier of the digest trigger to flush
+	///
+	/// # Returns
+	/// * `Result<usize, TriggerError>` - Number of matches sent, or error
+	pub async fn flush_digest(&self, trigger_slug: &str) -> Result<usize, TriggerError> {
+		let trigger = self
+			.trigger_service
+			.get(trigger_slug)
+			.ok_or_else(|| TriggerError::not_found(trigger_slug.to_string(), None, None))?;
+
+		self.notification_service
+			.flush_digest(&trigger)
+			.await
+			.map_err(|e| TriggerError::execution_error(e.to_string(), Some(e.into()), None))
+	}
+
+	/// Returns the identifiers of the message queue triggers
+	pub fn queue_trigger_slugs(&self) -> Vec<String> {
+		self.trigger_service
+			.get_all()
+			.into_iter()
+			.filter(|(_, trigger)| is_queue_trigger(trigger))
+			.map(|(trigger_slug, _)| trigger_slug)
+			.collect()
+	}
+
+	/// Publishes the messages a queue trigger previously failed to publish
+	///
+	/// # Arguments
+	/// * `trigger_slug` - Identifier of the queue trigger
+	///
+	/// # Returns
+	/// * `Result<usize, TriggerError>` - Number of messages published, or error
+	pub async fn drain_queue_outbox(&self, trigger_slug: &str) -> Result<usize, TriggerError> {
+		let trigger = self
+			.trigger_service
+			.get(trigger_slug)
+			.ok_or_else(|| TriggerError::not_found(trigger_slug.to_string(), None, None))?;
+
+		self.notification_service
+			.drain_queue_outbox(&trigger)
+			.await
+			.map_err(|e| TriggerError::execution_error(e.to_string(), Some(e.into()), None))
+	}
 }
 
 #[async_trait]
@@ -132,6 +230,13 @@ impl<T: TriggerRepositoryTrait + Send + Sync> TriggerExecutionServiceTrait
 			))
 		}
 	}
+
+	fn is_ordered(&self, trigger_slug: &str) -> bool {
+		self.trigger_service
+			.get(trigger_slug)
+			.is_some_and(|trigger| is_queue_trigger(&trigger))
+	}
+
 	/// Loads trigger condition scripts for monitors
 	///
 	/// # Arguments
@@ -160,7 +265,7 @@ impl<T: TriggerRepositoryTrait + Send + Sync> TriggerExecutionServiceTrait
 				let script_path = Path::new(&condition.script_path);
 
 				// Read the script content
-				let content = tokio::fs::read_to_string(script_path)
+				let content = read_script(script_path, &condition.language)
 					.await
 					.with_context(|| {
 						format!("Failed to read script file: {}", condition.script_path)
@@ -192,13 +297,16 @@ impl<T: TriggerRepositoryTrait + Send + Sync> TriggerExecutionServiceTrait
 					script_path,
 					arguments: _,
 					timeout_ms: _,
+					mode: _,
+					sandbox: _,
+					interpreter: _,
 				} = &trigger_config.config
 				else {
 					continue;
 				};
 
 				let script_path = Path::new(script_path);
-				let content = tokio::fs::read_to_string(script_path).await.map_err(|e| {
+				let content = read_script(script_path, language).await.map_err(|e| {
 					TriggerError::configuration_error(
 						format!(
 							"Failed to read script file {}: {}",
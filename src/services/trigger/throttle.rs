@@ -0,0 +1,417 @@
+//! Notification throttling and deduplication.
+//!
+//! Tracks the notifications sent for monitors configured with a `throttle`, per deduplication
+//! key, and decides whether a new match may notify. Suppressed matches are counted so that the
+//! next notification sent for the same key can report them. Keys whose window and cooldown have
+//! expired are evicted, and the summary of the matches they suppressed is logged since no later
+//! notification is left to report it.
+
+use std::{
+	collections::{HashMap, VecDeque},
+	sync::Mutex,
+};
+
+use crate::{
+	models::{Monitor, NotificationMessage, ThrottleConfig, Trigger},
+	services::notification::render_template,
+};
+
+/// Template variable holding the number of matches suppressed since the previous notification
+pub const THROTTLE_SUPPRESSED_COUNT: &str = "throttle.suppressed_count";
+
+/// Template variable holding a human readable summary of the suppressed matches
+pub const THROTTLE_SUMMARY: &str = "throttle.summary";
+
+/// Minimum interval between two sweeps of the expired keys, in seconds
+const THROTTLE_SWEEP_INTERVAL_SECS: u64 = 60;
+
+/// Outcome of a throttling check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThrottleDecision {
+	/// The notification may be sent. Carries the number of matches suppressed for the same key
+	/// since the previous notification
+	Allow { suppressed: u64 },
+	/// The notification must be suppressed
+	Suppress,
+}
+
+/// Notification history of a single deduplication key
+#[derive(Debug, Default)]
+struct KeyState {
+	/// Timestamps of the notifications sent within the rate limiting window
+	sent: VecDeque<u64>,
+	/// Timestamp of the last notification sent
+	last_sent: Option<u64>,
+	/// Number of matches suppressed since the last notification
+	suppressed: u64,
+	/// Time at which the window and cooldown of the last notification have both expired
+	expires_at: u64,
+}
+
+/// Notification history of every deduplication key
+#[derive(Debug, Default)]
+struct ThrottleStates {
+	keys: HashMap<String, KeyState>,
+	/// Time of the last sweep of the expired keys
+	last_sweep: u64,
+}
+
+/// In-memory throttle shared by all matches handed to the triggers
+#[derive(Debug, Default)]
+pub struct NotificationThrottle {
+	states: Mutex<ThrottleStates>,
+}
+
+impl NotificationThrottle {
+	/// Creates an empty throttle
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Decides whether a match of a monitor may notify
+	///
+	/// Monitors without a `throttle` are always allowed. Allowed notifications are recorded, and
+	/// suppressed ones are counted against their key.
+	///
+	/// # Arguments
+	/// * `monitor` - Monitor that produced the match
+	/// * `variables` - Template variables of the match, used to render the deduplication key
+	/// * `now` - Current time in seconds since the Unix epoch
+	pub fn check(
+		&self,
+		monitor: &Monitor,
+		variables: &HashMap<String, String>,
+		now: u64,
+	) -> ThrottleDecision {
+		let Some(throttle) = &monitor.throttle else {
+			return ThrottleDecision::Allow { suppressed: 0 };
+		};
+
+		let key = match &throttle.dedup_key {
+			Some(template) => format!("{}|{}", monitor.name, render_template(template, variables)),
+			None => monitor.name.clone(),
+		};
+
+		let mut states = self
+			.states
+			.lock()
+			.unwrap_or_else(|poisoned| poisoned.into_inner());
+		// The checked key reports its own suppressed matches
+		evict_expired_keys(&mut states, now, Some(&key));
+		let state = states.keys.entry(key).or_default();
+
+		if let Some(window_secs) = throttle.window_secs {
+			while state
+				.sent
+				.front()
+				.is_some_and(|sent| sent.saturating_add(window_secs) <= now)
+			{
+				state.sent.pop_front();
+			}
+		}
+
+		if is_limited(throttle, state, now) {
+			state.suppressed += 1;
+			return ThrottleDecision::Suppress;
+		}
+
+		if throttle.window_secs.is_some() {
+			state.sent.push_back(now);
+		}
+		state.last_sent = Some(now);
+		state.expires_at = now.saturating_add(
+			throttle
+				.window_secs
+				.unwrap_or(0)
+				.max(throttle.cooldown_secs.unwrap_or(0)),
+		);
+		ThrottleDecision::Allow {
+			suppressed: std::mem::take(&mut state.suppressed),
+		}
+	}
+
+	/// Evicts the keys whose window and cooldown have expired
+	///
+	/// Called for every processed block, so that keys are evicted, and their suppressed matches
+	/// reported, even when their monitor stops matching.
+	///
+	/// # Arguments
+	/// * `now` - Current time in seconds since the Unix epoch
+	pub fn evict_expired(&self, now: u64) {
+		let mut states = self
+			.states
+			.lock()
+			.unwrap_or_else(|poisoned| poisoned.into_inner());
+		evict_expired_keys(&mut states, now, None);
+	}
+}
+
+/// Evicts the expired keys other than `keep`, at most once per sweep interval
+///
+/// Expired keys behave like unknown ones. Matches they suppressed can no longer be reported by a
+/// later notification, so their summary is logged instead.
+fn evict_expired_keys(states: &mut ThrottleStates, now: u64, keep: Option<&str>) {
+	if now
+		< states
+			.last_sweep
+			.saturating_add(THROTTLE_SWEEP_INTERVAL_SECS)
+	{
+		return;
+	}
+	states.keys.retain(|key, state| {
+		if now < state.expires_at || keep == Some(key.as_str()) {
+			return true;
+		}
+		if state.suppressed > 0 {
+			tracing::info!(
+				"Throttle expired for '{}': {}",
+				key,
+				suppressed_summary(state.suppressed)
+			);
+		}
+		false
+	});
+	states.last_sweep = now;
+}
+
+/// Describes the number of matches suppressed since the last notification
+fn suppressed_summary(suppressed: u64) -> String {
+	match suppressed {
+		0 => String::new(),
+		1 => "1 similar notification was suppressed since the last alert".to_string(),
+		n => format!(
+			"{} similar notifications were suppressed since the last alert",
+			n
+		),
+	}
+}
+
+/// Adds the throttle variables describing suppressed matches to a match's template variables
+///
+/// # Arguments
+/// * `variables` - Template variables of the match
+/// * `suppressed` - Number of matches suppressed since the previous notification
+pub fn insert_throttle_variables(variables: &mut HashMap<String, String>, suppressed: u64) {
+	variables.insert(
+		THROTTLE_SUPPRESSED_COUNT.to_string(),
+		suppressed.to_string(),
+	);
+	variables.insert(THROTTLE_SUMMARY.to_string(), suppressed_summary(suppressed));
+}
+
+/// Appends the summary of suppressed matches to the message of a trigger
+///
+/// Only applies when matches were suppressed and the message body does not already use a
+/// throttle variable, so that custom layouts stay untouched.
+///
+/// # Arguments
+/// * `trigger` - Trigger about to notify a match
+/// * `variables` - Template variables of the match
+///
+/// # Returns
+/// * `Option<Trigger>` - Trigger with the summary appended, or None if it is unchanged
+pub fn append_throttle_summary(
+	trigger: &Trigger,
+	variables: &HashMap<String, String>,
+) -> Option<Trigger> {
+	let message = trigger.config.message()?;
+	let has_summary = variables
+		.get(THROTTLE_SUMMARY)
+		.is_some_and(|summary| !summary.is_empty());
+	if !has_summary || message.body.contains("${throttle.") {
+		return None;
+	}
+
+	let message = NotificationMessage {
+		title: message.title.clone(),
+		body: format!("{}\n\n${{{}}}", message.body, THROTTLE_SUMMARY),
+	};
+	Some(Trigger {
+		config: trigger.config.with_message(message),
+		..trigger.clone()
+	})
+}
+
+/// Checks whether the cooldown or the rate limit of a key is currently reached
+fn is_limited(throttle: &ThrottleConfig, state: &KeyState, now: u64) -> bool {
+	let in_cooldown = throttle
+		.cooldown_secs
+		.zip(state.last_sent)
+		.is_some_and(|(cooldown, last_sent)| now < last_sent.saturating_add(cooldown));
+	let over_limit = throttle
+		.max_notifications
+		.is_some_and(|max| state.sent.len() >= max as usize);
+	in_cooldown || over_limit
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::utils::tests::{evm::monitor::MonitorBuilder, trigger::TriggerBuilder};
+
+	fn create_test_monitor(throttle: Option<ThrottleConfig>) -> Monitor {
+		let builder = MonitorBuilder::new().name("noisy");
+		match throttle {
+			Some(throttle) => builder.throttle(throttle).build(),
+			None => builder.build(),
+		}
+	}
+
+	fn create_test_variables(to: &str) -> HashMap<String, String> {
+		HashMap::from([("events.0.args.to".to_string(), to.to_string())])
+	}
+
+	#[test]
+	fn test_check_without_throttle() {
+		let throttle = NotificationThrottle::new();
+		let monitor = create_test_monitor(None);
+		for now in 0..10 {
+			assert_eq!(
+				throttle.check(&monitor, &HashMap::new(), now),
+				ThrottleDecision::Allow { suppressed: 0 }
+			);
+		}
+	}
+
+	#[test]
+	fn test_check_max_notifications_per_window() {
+		let throttle = NotificationThrottle::new();
+		let monitor = create_test_monitor(Some(ThrottleConfig {
+			max_notifications: Some(2),
+			window_secs: Some(60),
+			..Default::default()
+		}));
+		let variables = HashMap::new();
+
+		assert_eq!(
+			throttle.check(&monitor, &variables, 0),
+			ThrottleDecision::Allow { suppressed: 0 }
+		);
+		assert_eq!(
+			throttle.check(&monitor, &variables, 10),
+			ThrottleDecision::Allow { suppressed: 0 }
+		);
+		assert_eq!(
+			throttle.check(&monitor, &variables, 20),
+			ThrottleDecision::Suppress
+		);
+		assert_eq!(
+			throttle.check(&monitor, &variables, 30),
+			ThrottleDecision::Suppress
+		);
+		// The first notification left the window
+		assert_eq!(
+			throttle.check(&monitor, &variables, 61),
+			ThrottleDecision::Allow { suppressed: 2 }
+		);
+		assert_eq!(
+			throttle.check(&monitor, &variables, 62),
+			ThrottleDecision::Suppress
+		);
+	}
+
+	#[test]
+	fn test_check_cooldown_with_dedup_key() {
+		let throttle = NotificationThrottle::new();
+		let monitor = create_test_monitor(Some(ThrottleConfig {
+			dedup_key: Some("${events.0.args.to}".to_string()),
+			cooldown_secs: Some(300),
+			..Default::default()
+		}));
+		let alice = create_test_variables("0xa11ce");
+		let bob = create_test_variables("0xb0b");
+
+		assert_eq!(
+			throttle.check(&monitor, &alice, 0),
+			ThrottleDecision::Allow { suppressed: 0 }
+		);
+		assert_eq!(
+			throttle.check(&monitor, &alice, 100),
+			ThrottleDecision::Suppress
+		);
+		// Other keys are tracked separately
+		assert_eq!(
+			throttle.check(&monitor, &bob, 100),
+			ThrottleDecision::Allow { suppressed: 0 }
+		);
+		assert_eq!(
+			throttle.check(&monitor, &alice, 300),
+			ThrottleDecision::Allow { suppressed: 1 }
+		);
+	}
+
+	#[test]
+	fn test_check_evicts_expired_keys() {
+		let throttle = NotificationThrottle::new();
+		let monitor = create_test_monitor(Some(ThrottleConfig {
+			dedup_key: Some("${events.0.args.to}".to_string()),
+			cooldown_secs: Some(30),
+			..Default::default()
+		}));
+
+		for (now, to) in [(0, "0xa11ce"), (1, "0xb0b"), (2, "0xb0b")] {
+			throttle.check(&monitor, &create_test_variables(to), now);
+		}
+		assert_eq!(throttle.states.lock().unwrap().keys.len(), 2);
+
+		// Both keys expired, including Bob's whose suppressed match is logged
+		throttle.check(&monitor, &create_test_variables("0xca201"), 100);
+		{
+			let states = throttle.states.lock().unwrap();
+			assert_eq!(states.keys.len(), 1);
+			assert!(states.keys.contains_key("noisy|0xca201"));
+		}
+
+		// Keys are evicted without further matches
+		throttle.evict_expired(130);
+		assert_eq!(throttle.states.lock().unwrap().keys.len(), 1);
+		throttle.evict_expired(160);
+		assert!(throttle.states.lock().unwrap().keys.is_empty());
+	}
+
+	#[test]
+	fn test_append_throttle_summary() {
+		let trigger = TriggerBuilder::new()
+			.name("slack")
+			.slack("https://hooks.slack.com/services/A/B/C")
+			.message("Alert", "Large transfer")
+			.build();
+		let mut variables = HashMap::new();
+
+		insert_throttle_variables(&mut variables, 0);
+		assert!(append_throttle_summary(&trigger, &variables).is_none());
+
+		insert_throttle_variables(&mut variables, 2);
+		let summarized = append_throttle_summary(&trigger, &variables).unwrap();
+		assert_eq!(
+			summarized.config.message().unwrap().body,
+			"Large transfer\n\n${throttle.summary}"
+		);
+
+		// Messages using throttle variables keep their layout
+		let custom = TriggerBuilder::new()
+			.name("slack")
+			.slack("https://hooks.slack.com/services/A/B/C")
+			.message(
+				"Alert",
+				"Large transfer (${throttle.suppressed_count} suppressed)",
+			)
+			.build();
+		assert!(append_throttle_summary(&custom, &variables).is_none());
+	}
+
+	#[test]
+	fn test_insert_throttle_variables() {
+		let mut variables = HashMap::new();
+		insert_throttle_variables(&mut variables, 0);
+		assert_eq!(variables[THROTTLE_SUPPRESSED_COUNT], "0");
+		assert_eq!(variables[THROTTLE_SUMMARY], "");
+
+		insert_throttle_variables(&mut variables, 3);
+		assert_eq!(variables[THROTTLE_SUPPRESSED_COUNT], "3");
+		assert_eq!(
+			variables[THROTTLE_SUMMARY],
+			"3 similar notifications were suppressed since the last alert"
+		);
+	}
+}
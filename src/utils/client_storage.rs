@@ -6,11 +6,11 @@ use tokio::sync::RwLock;
 /// Clients are stored in a thread-safe way using a HashMap and an RwLock.
 /// The HashMap is indexed by the network slug and the value is an Arc of the client.
 #[derive(Default)]
-pub struct ClientStorage<T> {
+pub struct ClientStorage<T: ?Sized> {
 	pub clients: Arc<RwLock<HashMap<String, Arc<T>>>>,
 }
 
-impl<T> ClientStorage<T> {
+impl<T: ?Sized> ClientStorage<T> {
 	pub fn new() -> Self {
 		Self {
 			clients: Arc::new(RwLock::new(HashMap::new())),
@@ -2,7 +2,7 @@
 //!
 //! This module provides helper functions for parsing and analyzing cron expressions,
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use cron::Schedule;
 
 /// Calculates the time interval between two consecutive occurrences of a cron schedule
@@ -40,3 +40,22 @@ pub fn get_cron_interval_ms(cron_schedule: &str) -> Option<i64> {
 		None // Return None if we cannot find two occurrences
 	}
 }
+
+/// Calculates the next occurrence of a cron schedule after a given time
+///
+/// # Arguments
+///
+/// * `cron_schedule` - A string slice containing a valid cron expression (e.g., "0 0 * * *")
+/// * `after` - Time after which the next occurrence is searched
+///
+/// # Returns
+///
+/// * `Some(DateTime<Utc>)` - The next occurrence of the schedule
+/// * `None` - If the cron expression is invalid or has no upcoming occurrence
+pub fn get_next_cron_occurrence(
+	cron_schedule: &str,
+	after: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+	let schedule = cron_schedule.parse::<Schedule>().ok()?;
+	schedule.after(&after).next()
+}
@@ -89,6 +89,11 @@ pub async fn execute_monitor<
 
 	tracing::debug!(monitor_name = %monitor.name, "Monitor loaded successfully");
 
+	config
+		.filter_service
+		.check_plugins(std::slice::from_ref(&monitor))
+		.map_err(|e| MonitorExecutionError::execution_error(e.to_string(), None, None))?;
+
 	let networks_for_monitor = if let Some(network_slug) = config.network_slug {
 		tracing::debug!(network = %network_slug, "Finding specific network");
 		let network = config
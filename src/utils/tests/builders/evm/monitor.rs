@@ -3,8 +3,10 @@
 //! - `MonitorBuilder`: Builder for creating test Monitor instances
 
 use crate::models::{
-	AddressWithSpec, ContractSpec, EventCondition, FunctionCondition, MatchConditions, Monitor,
-	ScriptLanguage, TransactionCondition, TransactionStatus, TriggerConditions,
+	AddressWithSpec, AggregateCondition, ContractSpec, EventCondition, EventMatchMode,
+	FilterPluginCondition, FunctionCondition, IncidentSeverity, MatchConditions, Monitor,
+	PollConfig, ScriptLanguage, ScriptMode, StateConditions, ThrottleConfig, TransactionCondition,
+	TransactionStatus, TriggerConditions, WindowAggregation,
 };
 
 /// Builder for creating test Monitor instances
@@ -15,7 +17,13 @@ pub struct MonitorBuilder {
 	addresses: Vec<AddressWithSpec>,
 	match_conditions: MatchConditions,
 	trigger_conditions: Vec<TriggerConditions>,
+	filter_plugins: Vec<FilterPluginCondition>,
 	triggers: Vec<String>,
+	window: Option<WindowAggregation>,
+	throttle: Option<ThrottleConfig>,
+	severity: Option<IncidentSeverity>,
+	state: Option<StateConditions>,
+	poll: Option<PollConfig>,
 }
 
 impl Default for MonitorBuilder {
@@ -32,9 +40,16 @@ impl Default for MonitorBuilder {
 				functions: vec![],
 				events: vec![],
 				transactions: vec![],
+				..Default::default()
 			},
 			trigger_conditions: vec![],
+			filter_plugins: vec![],
 			triggers: vec![],
+			window: None,
+			throttle: None,
+			severity: None,
+			state: None,
+			poll: None,
 		}
 	}
 }
@@ -121,6 +136,16 @@ impl MonitorBuilder {
 		self
 	}
 
+	pub fn match_mode(mut self, match_mode: EventMatchMode) -> Self {
+		self.match_conditions.match_mode = match_mode;
+		self
+	}
+
+	pub fn aggregate(mut self, aggregate: AggregateCondition) -> Self {
+		self.match_conditions.aggregates.push(aggregate);
+		self
+	}
+
 	pub fn transaction(mut self, status: TransactionStatus, expression: Option<String>) -> Self {
 		self.match_conditions
 			.transactions
@@ -140,6 +165,21 @@ impl MonitorBuilder {
 			timeout_ms,
 			arguments,
 			language,
+			mode: ScriptMode::Process,
+			sandbox: None,
+			interpreter: None,
+		});
+		self
+	}
+
+	pub fn filter_plugin(
+		mut self,
+		plugin: &str,
+		options: serde_json::Map<String, serde_json::Value>,
+	) -> Self {
+		self.filter_plugins.push(FilterPluginCondition {
+			plugin: plugin.to_string(),
+			options,
 		});
 		self
 	}
@@ -149,6 +189,31 @@ impl MonitorBuilder {
 		self
 	}
 
+	pub fn window(mut self, window: WindowAggregation) -> Self {
+		self.window = Some(window);
+		self
+	}
+
+	pub fn throttle(mut self, throttle: ThrottleConfig) -> Self {
+		self.throttle = Some(throttle);
+		self
+	}
+
+	pub fn severity(mut self, severity: IncidentSeverity) -> Self {
+		self.severity = Some(severity);
+		self
+	}
+
+	pub fn state(mut self, state: StateConditions) -> Self {
+		self.state = Some(state);
+		self
+	}
+
+	pub fn poll(mut self, poll: PollConfig) -> Self {
+		self.poll = Some(poll);
+		self
+	}
+
 	pub fn match_conditions(mut self, match_conditions: MatchConditions) -> Self {
 		self.match_conditions = match_conditions;
 		self
@@ -162,7 +227,13 @@ impl MonitorBuilder {
 			addresses: self.addresses,
 			match_conditions: self.match_conditions,
 			trigger_conditions: self.trigger_conditions,
+			filter_plugins: self.filter_plugins,
 			triggers: self.triggers,
+			window: self.window,
+			throttle: self.throttle,
+			severity: self.severity,
+			state: self.state,
+			poll: self.poll,
 		}
 	}
 }
@@ -313,6 +384,7 @@ mod tests {
 				}],
 				events: vec![],
 				transactions: vec![],
+				..Default::default()
 			})
 			.build();
 		assert_eq!(monitor.match_conditions.functions.len(), 1);
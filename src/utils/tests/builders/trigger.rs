@@ -4,8 +4,9 @@
 
 use crate::{
 	models::{
-		NotificationMessage, ScriptLanguage, SecretString, SecretValue, Trigger, TriggerType,
-		TriggerTypeConfig,
+		DigestConfig, IncidentSeverity, NotificationMessage, ScriptLanguage, ScriptMode,
+		ScriptSandbox, SecretString, SecretValue, Trigger, TriggerType, TriggerTypeConfig,
+		WebhookPayloadMode,
 	},
 	utils::HttpRetryConfig,
 };
@@ -16,6 +17,7 @@ pub struct TriggerBuilder {
 	name: String,
 	trigger_type: TriggerType,
 	config: TriggerTypeConfig,
+	digest: Option<DigestConfig>,
 }
 
 impl Default for TriggerBuilder {
@@ -35,7 +37,9 @@ impl Default for TriggerBuilder {
 					body: "Test message".to_string(),
 				},
 				retry_policy: HttpRetryConfig::default(),
+				payload: None,
 			},
+			digest: None,
 		}
 	}
 }
@@ -67,6 +71,7 @@ impl TriggerBuilder {
 				body: "Test message".to_string(),
 			},
 			retry_policy: HttpRetryConfig::default(),
+			payload: None,
 		};
 		self
 	}
@@ -79,6 +84,8 @@ impl TriggerBuilder {
 				title: "Alert".to_string(),
 				body: "Test message".to_string(),
 			},
+			explorer_url: None,
+			blocks: None,
 			retry_policy: HttpRetryConfig::default(),
 		};
 		self
@@ -92,11 +99,191 @@ impl TriggerBuilder {
 				title: "Alert".to_string(),
 				body: "Test message".to_string(),
 			},
+			explorer_url: None,
+			embeds: None,
 			retry_policy: HttpRetryConfig::default(),
 		};
 		self
 	}
 
+	pub fn teams(mut self, webhook_url: &str) -> Self {
+		self.trigger_type = TriggerType::Teams;
+		self.config = TriggerTypeConfig::Teams {
+			teams_url: SecretValue::Plain(SecretString::new(webhook_url.to_string())),
+			message: NotificationMessage {
+				title: "Alert".to_string(),
+				body: "Test message".to_string(),
+			},
+			explorer_url: None,
+			retry_policy: HttpRetryConfig::default(),
+		};
+		self
+	}
+
+	pub fn explorer_url(mut self, explorer_url: &str) -> Self {
+		match &mut self.config {
+			TriggerTypeConfig::Slack {
+				explorer_url: e, ..
+			}
+			| TriggerTypeConfig::Discord {
+				explorer_url: e, ..
+			}
+			| TriggerTypeConfig::Teams {
+				explorer_url: e, ..
+			} => *e = Some(explorer_url.to_string()),
+			_ => {}
+		}
+		self
+	}
+
+	pub fn slack_blocks(mut self, blocks: serde_json::Value) -> Self {
+		if let TriggerTypeConfig::Slack { blocks: b, .. } = &mut self.config {
+			*b = Some(blocks);
+		}
+		self
+	}
+
+	pub fn discord_embeds(mut self, embeds: serde_json::Value) -> Self {
+		if let TriggerTypeConfig::Discord { embeds: e, .. } = &mut self.config {
+			*e = Some(embeds);
+		}
+		self
+	}
+
+	pub fn kafka(mut self, brokers: &str, topic: &str) -> Self {
+		self.trigger_type = TriggerType::Kafka;
+		self.config = TriggerTypeConfig::Kafka {
+			brokers: brokers.to_string(),
+			topic: topic.to_string(),
+			properties: None,
+			retry_policy: HttpRetryConfig::default(),
+		};
+		self
+	}
+
+	pub fn nats(mut self, nats_url: &str, subject: &str) -> Self {
+		self.trigger_type = TriggerType::Nats;
+		self.config = TriggerTypeConfig::Nats {
+			nats_url: SecretValue::Plain(SecretString::new(nats_url.to_string())),
+			subject: subject.to_string(),
+			retry_policy: HttpRetryConfig::default(),
+		};
+		self
+	}
+
+	pub fn redis(mut self, redis_url: &str, stream: &str) -> Self {
+		self.trigger_type = TriggerType::Redis;
+		self.config = TriggerTypeConfig::Redis {
+			redis_url: SecretValue::Plain(SecretString::new(redis_url.to_string())),
+			stream: stream.to_string(),
+			max_len: None,
+			retry_policy: HttpRetryConfig::default(),
+		};
+		self
+	}
+
+	pub fn amqp(mut self, amqp_url: &str, exchange: &str) -> Self {
+		self.trigger_type = TriggerType::Amqp;
+		self.config = TriggerTypeConfig::Amqp {
+			amqp_url: SecretValue::Plain(SecretString::new(amqp_url.to_string())),
+			exchange: exchange.to_string(),
+			routing_key: None,
+			retry_policy: HttpRetryConfig::default(),
+		};
+		self
+	}
+
+	pub fn database(mut self, database_url: &str, table: &str) -> Self {
+		self.trigger_type = TriggerType::Database;
+		self.config = TriggerTypeConfig::Database {
+			database_url: SecretValue::Plain(SecretString::new(database_url.to_string())),
+			table: table.to_string(),
+			columns: std::collections::BTreeMap::new(),
+			batch_size: None,
+			batch_timeout_ms: None,
+			retry_policy: HttpRetryConfig::default(),
+		};
+		self
+	}
+
+	pub fn plugin(mut self, plugin: &str) -> Self {
+		self.trigger_type = TriggerType::Plugin;
+		self.config = TriggerTypeConfig::Plugin {
+			plugin: plugin.to_string(),
+			options: serde_json::Map::new(),
+		};
+		self
+	}
+
+	pub fn column(mut self, name: &str, template: &str) -> Self {
+		if let TriggerTypeConfig::Database { columns, .. } = &mut self.config {
+			columns.insert(name.to_string(), template.to_string());
+		}
+		self
+	}
+
+	pub fn pagerduty(mut self, events_url: &str) -> Self {
+		self.trigger_type = TriggerType::PagerDuty;
+		self.config = TriggerTypeConfig::PagerDuty {
+			routing_key: SecretValue::Plain(SecretString::new("routing-key".to_string())),
+			message: NotificationMessage {
+				title: "Alert".to_string(),
+				body: "Test message".to_string(),
+			},
+			severity: IncidentSeverity::default(),
+			dedup_key: None,
+			auto_resolve_after_secs: None,
+			events_url: Some(events_url.to_string()),
+			retry_policy: HttpRetryConfig::default(),
+		};
+		self
+	}
+
+	pub fn opsgenie(mut self, api_url: &str) -> Self {
+		self.trigger_type = TriggerType::Opsgenie;
+		self.config = TriggerTypeConfig::Opsgenie {
+			api_key: SecretValue::Plain(SecretString::new("api-key".to_string())),
+			message: NotificationMessage {
+				title: "Alert".to_string(),
+				body: "Test message".to_string(),
+			},
+			severity: IncidentSeverity::default(),
+			alias: None,
+			auto_resolve_after_secs: None,
+			api_url: Some(api_url.to_string()),
+			retry_policy: HttpRetryConfig::default(),
+		};
+		self
+	}
+
+	pub fn incident(
+		mut self,
+		severity: IncidentSeverity,
+		key: Option<&str>,
+		auto_resolve_after_secs: Option<u64>,
+	) -> Self {
+		match &mut self.config {
+			TriggerTypeConfig::PagerDuty {
+				severity: s,
+				dedup_key: k,
+				auto_resolve_after_secs: a,
+				..
+			}
+			| TriggerTypeConfig::Opsgenie {
+				severity: s,
+				alias: k,
+				auto_resolve_after_secs: a,
+				..
+			} => {
+				*s = severity;
+				*k = key.map(|key| key.to_string());
+				*a = auto_resolve_after_secs;
+			}
+			_ => {}
+		}
+		self
+	}
+
 	pub fn telegram(mut self, token: &str, chat_id: &str, disable_web_preview: bool) -> Self {
 		self.trigger_type = TriggerType::Telegram;
 		self.config = TriggerTypeConfig::Telegram {
@@ -126,10 +313,27 @@ impl TriggerBuilder {
 			arguments: None,
 			language,
 			timeout_ms: 1000,
+			mode: ScriptMode::Process,
+			sandbox: None,
+			interpreter: None,
 		};
 		self
 	}
 
+	pub fn script_mode(mut self, mode: ScriptMode) -> Self {
+		if let TriggerTypeConfig::Script { mode: m, .. } = &mut self.config {
+			*m = mode;
+		}
+		self
+	}
+
+	pub fn script_sandbox(mut self, sandbox: ScriptSandbox) -> Self {
+		if let TriggerTypeConfig::Script { sandbox: s, .. } = &mut self.config {
+			*s = Some(sandbox);
+		}
+		self
+	}
+
 	pub fn script_arguments(mut self, arguments: Vec<String>) -> Self {
 		if let TriggerTypeConfig::Script { arguments: a, .. } = &mut self.config {
 			*a = Some(arguments);
@@ -150,7 +354,10 @@ impl TriggerBuilder {
 			| TriggerTypeConfig::Slack { message, .. }
 			| TriggerTypeConfig::Discord { message, .. }
 			| TriggerTypeConfig::Telegram { message, .. }
-			| TriggerTypeConfig::Email { message, .. } => {
+			| TriggerTypeConfig::Email { message, .. }
+			| TriggerTypeConfig::PagerDuty { message, .. }
+			| TriggerTypeConfig::Opsgenie { message, .. }
+			| TriggerTypeConfig::Teams { message, .. } => {
 				message.title = title.to_string();
 				message.body = body.to_string();
 			}
@@ -159,6 +366,25 @@ impl TriggerBuilder {
 		self
 	}
 
+	pub fn retry_policy(mut self, policy: HttpRetryConfig) -> Self {
+		match &mut self.config {
+			TriggerTypeConfig::Webhook { retry_policy, .. }
+			| TriggerTypeConfig::Slack { retry_policy, .. }
+			| TriggerTypeConfig::Discord { retry_policy, .. }
+			| TriggerTypeConfig::Telegram { retry_policy, .. }
+			| TriggerTypeConfig::PagerDuty { retry_policy, .. }
+			| TriggerTypeConfig::Opsgenie { retry_policy, .. }
+			| TriggerTypeConfig::Teams { retry_policy, .. }
+			| TriggerTypeConfig::Kafka { retry_policy, .. }
+			| TriggerTypeConfig::Nats { retry_policy, .. }
+			| TriggerTypeConfig::Redis { retry_policy, .. }
+			| TriggerTypeConfig::Amqp { retry_policy, .. }
+			| TriggerTypeConfig::Database { retry_policy, .. } => *retry_policy = policy,
+			_ => {}
+		}
+		self
+	}
+
 	pub fn trigger_type(mut self, trigger_type: TriggerType) -> Self {
 		self.trigger_type = trigger_type;
 		self
@@ -240,6 +466,13 @@ impl TriggerBuilder {
 		self
 	}
 
+	pub fn webhook_payload(mut self, payload: WebhookPayloadMode) -> Self {
+		if let TriggerTypeConfig::Webhook { payload: p, .. } = &mut self.config {
+			*p = Some(payload);
+		}
+		self
+	}
+
 	pub fn url(mut self, url: SecretValue) -> Self {
 		self.config = match self.config {
 			TriggerTypeConfig::Webhook {
@@ -249,6 +482,7 @@ impl TriggerBuilder {
 				secret,
 				message,
 				retry_policy,
+				payload,
 			} => TriggerTypeConfig::Webhook {
 				url,
 				method,
@@ -256,23 +490,43 @@ impl TriggerBuilder {
 				secret,
 				message,
 				retry_policy,
+				payload,
 			},
 			TriggerTypeConfig::Discord {
 				discord_url: _,
 				message,
+				explorer_url,
+				embeds,
 				retry_policy,
 			} => TriggerTypeConfig::Discord {
 				discord_url: url,
 				message,
+				explorer_url,
+				embeds,
 				retry_policy,
 			},
 			TriggerTypeConfig::Slack {
 				slack_url: _,
 				message,
+				explorer_url,
+				blocks,
 				retry_policy,
 			} => TriggerTypeConfig::Slack {
 				slack_url: url,
 				message,
+				explorer_url,
+				blocks,
+				retry_policy,
+			},
+			TriggerTypeConfig::Teams {
+				teams_url: _,
+				message,
+				explorer_url,
+				retry_policy,
+			} => TriggerTypeConfig::Teams {
+				teams_url: url,
+				message,
+				explorer_url,
 				retry_policy,
 			},
 			config => config,
@@ -280,11 +534,23 @@ impl TriggerBuilder {
 		self
 	}
 
+	pub fn digest(mut self, cron_schedule: &str, title: &str, body: &str) -> Self {
+		self.digest = Some(DigestConfig {
+			cron_schedule: cron_schedule.to_string(),
+			message: NotificationMessage {
+				title: title.to_string(),
+				body: body.to_string(),
+			},
+		});
+		self
+	}
+
 	pub fn build(self) -> Trigger {
 		Trigger {
 			name: self.name,
 			trigger_type: self.trigger_type,
 			config: self.config,
+			digest: self.digest,
 		}
 	}
 }
@@ -325,6 +591,7 @@ mod tests {
 					body: "Test message".to_string(),
 				},
 				retry_policy: HttpRetryConfig::default(),
+				payload: None,
 			})
 			.build();
 
@@ -388,6 +655,7 @@ mod tests {
 				headers: h,
 				message,
 				retry_policy: _,
+				payload: _,
 			} => {
 				assert_eq!(url.as_ref().to_string(), "https://webhook.example.com");
 				assert_eq!(method, Some("POST".to_string()));
@@ -416,6 +684,8 @@ mod tests {
 			TriggerTypeConfig::Slack {
 				slack_url,
 				message,
+				explorer_url: _,
+				blocks: _,
 				retry_policy: _,
 			} => {
 				assert_eq!(slack_url.as_ref().to_string(), "https://slack.webhook.com");
@@ -439,6 +709,8 @@ mod tests {
 			TriggerTypeConfig::Discord {
 				discord_url,
 				message,
+				explorer_url: _,
+				embeds: _,
 				retry_policy: _,
 			} => {
 				assert_eq!(
@@ -45,10 +45,16 @@ mod integration {
 		}
 	}
 	mod notifications {
+		#[cfg(feature = "sqlite")]
+		mod database;
 		mod discord;
 		mod email;
+		mod opsgenie;
+		mod pagerduty;
+		mod queue;
 		mod script;
 		mod slack;
+		mod teams;
 		mod telegram;
 		mod webhook;
 	}
@@ -117,6 +117,66 @@ async fn test_get_logs_for_blocks_missing_result() {
 	assert!(err.to_string().contains("Missing 'result' field"));
 }
 
+#[tokio::test]
+async fn test_call_contract_implementation() {
+	let mut mock_evm = MockEVMTransportClient::new();
+
+	let expected_params = json!([
+		{
+			"to": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+			"data": "0x313ce567"
+		},
+		"0x10"
+	]);
+	let mock_response = json!({
+		"result": "0x0000000000000000000000000000000000000000000000000000000000000006"
+	});
+
+	mock_evm
+		.expect_send_raw_request()
+		.with(
+			predicate::eq("eth_call"),
+			predicate::eq(Some(expected_params.as_array().unwrap().to_vec())),
+		)
+		.returning(move |_: &str, _: Option<Vec<Value>>| Ok(mock_response.clone()));
+
+	let client = EvmClient::<MockEVMTransportClient>::new_with_transport(mock_evm);
+	let result = client
+		.call_contract(
+			"0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+			"0x313ce567",
+			Some(16),
+		)
+		.await;
+
+	assert_eq!(
+		result.unwrap(),
+		"0x0000000000000000000000000000000000000000000000000000000000000006"
+	);
+}
+
+#[tokio::test]
+async fn test_call_contract_reverted() {
+	let mut mock_evm = MockEVMTransportClient::new();
+
+	let mock_response = json!({
+		"error": { "code": 3, "message": "execution reverted" }
+	});
+
+	mock_evm
+		.expect_send_raw_request()
+		.with(predicate::eq("eth_call"), predicate::always())
+		.returning(move |_: &str, _: Option<Vec<Value>>| Ok(mock_response.clone()));
+
+	let client = EvmClient::<MockEVMTransportClient>::new_with_transport(mock_evm);
+	let result = client.call_contract("0x01", "0x95d89b41", None).await;
+
+	assert!(result
+		.unwrap_err()
+		.to_string()
+		.contains("execution reverted"));
+}
+
 #[tokio::test]
 async fn test_get_logs_for_blocks_invalid_format() {
 	let mut mock_evm = MockEVMTransportClient::new();
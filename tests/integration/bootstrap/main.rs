@@ -4,28 +4,33 @@ use crate::integration::{
 		setup_trigger_service,
 	},
 	mocks::{
-		create_test_block, create_test_network, create_test_transaction, MockClientPool,
-		MockEVMTransportClient, MockEvmClientTrait, MockMonitorRepository, MockNetworkRepository,
-		MockStellarClientTrait, MockStellarTransportClient, MockTriggerExecutionService,
-		MockTriggerRepository,
+		create_test_block, create_test_network, create_test_transaction, MockBlockStorage,
+		MockClientPool, MockEVMTransportClient, MockEvmClientTrait, MockMonitorRepository,
+		MockNetworkRepository, MockStellarClientTrait, MockStellarTransportClient,
+		MockTriggerExecutionService, MockTriggerRepository,
 	},
 };
 use openzeppelin_monitor::{
 	bootstrap::{
 		create_block_handler, create_trigger_handler, get_contract_specs, initialize_services,
-		process_block,
+		load_price_feeds, load_token_metadata, process_block,
 	},
 	models::{
 		AddressWithSpec, BlockChainType, ContractSpec, EVMContractSpec, EVMMonitorMatch,
-		EVMTransactionReceipt, MatchConditions, Monitor, MonitorMatch, ProcessedBlock,
-		ScriptLanguage, SecretString, SecretValue, StellarBlock, StellarContractSpec,
-		StellarFormattedContractSpec, StellarMonitorMatch, TransactionType, Trigger,
-		TriggerConditions,
+		EVMTransactionReceipt, MatchConditions, Monitor, MonitorMatch, PriceSourceConfig,
+		ProcessedBlock, ScriptLanguage, ScriptMode, SecretString, SecretValue, StellarBlock,
+		StellarContractSpec, StellarFormattedContractSpec, StellarMonitorMatch, Token, TokenPrice,
+		TransactionType, Trigger, TriggerConditions,
 	},
 	services::{
-		filter::{stellar_helpers::are_same_address, FilterService},
+		aggregation::WindowAggregationService,
+		filter::{
+			stellar_helpers::are_same_address, token_decimals, token_metadata, FilterService,
+			PollStateStore,
+		},
 		notification::NotificationService,
-		trigger::{TriggerExecutionService, TriggerExecutionServiceTrait},
+		price::{token_price, PriceError},
+		trigger::{TriggerError, TriggerExecutionService, TriggerExecutionServiceTrait},
 	},
 	utils::{
 		tests::{
@@ -76,6 +81,7 @@ fn create_test_monitor_match(chain: BlockChainType) -> MonitorMatch {
 			logs: Some(vec![]),
 			matched_on: MatchConditions::default(),
 			matched_on_args: None,
+			enrichment: None,
 		})),
 		BlockChainType::Stellar => MonitorMatch::Stellar(Box::new(StellarMonitorMatch {
 			monitor: create_test_monitor("test", vec!["stellar_mainnet"], false, vec![]),
@@ -87,6 +93,7 @@ fn create_test_monitor_match(chain: BlockChainType) -> MonitorMatch {
 			ledger: StellarBlock::default(),
 			matched_on: MatchConditions::default(),
 			matched_on_args: None,
+			enrichment: None,
 		})),
 		_ => panic!("Unsupported chain"),
 	}
@@ -162,6 +169,16 @@ async fn test_initialize_services() {
 	assert!(Arc::strong_count(&trigger_service) >= 1);
 }
 
+fn create_test_window_service() -> Arc<WindowAggregationService<MockBlockStorage>> {
+	Arc::new(WindowAggregationService::new(Arc::new(
+		MockBlockStorage::new(),
+	)))
+}
+
+fn create_test_poll_store() -> Arc<PollStateStore<MockBlockStorage>> {
+	Arc::new(PollStateStore::new(Arc::new(MockBlockStorage::new())))
+}
+
 #[tokio::test]
 async fn test_create_block_handler_evm() {
 	let (shutdown_tx, _) = watch::channel(false);
@@ -192,12 +209,14 @@ async fn test_create_block_handler_evm() {
 	let network_monitors = vec![(network.clone(), monitors.clone())];
 	let contract_specs = get_contract_specs(&client_pool, &network_monitors).await;
 
-	let block_handler = create_block_handler::<MockClientPool>(
+	let block_handler = create_block_handler::<MockClientPool, _>(
 		shutdown_tx,
 		filter_service,
 		monitors,
 		client_pool,
 		contract_specs,
+		create_test_window_service(),
+		create_test_poll_store(),
 	);
 
 	let result = block_handler(block, network).await;
@@ -345,12 +364,14 @@ async fn test_create_block_handler_stellar() {
 	let network_monitors = vec![(network.clone(), monitors.clone())];
 	let contract_specs = get_contract_specs(&Arc::new(contract_spec_pool), &network_monitors).await;
 
-	let block_handler = create_block_handler::<MockClientPool>(
+	let block_handler = create_block_handler::<MockClientPool, _>(
 		shutdown_tx,
 		filter_service,
 		monitors,
 		Arc::new(handle_block_client_pool),
 		contract_specs,
+		create_test_window_service(),
+		create_test_poll_store(),
 	);
 	let result = block_handler(block, network).await;
 
@@ -386,12 +407,14 @@ async fn test_create_block_handler_evm_client_error() {
 	let network_monitors = vec![(network.clone(), monitors.clone())];
 	let contract_specs = get_contract_specs(&client_pool, &network_monitors).await;
 
-	let block_handler = create_block_handler::<MockClientPool>(
+	let block_handler = create_block_handler::<MockClientPool, _>(
 		shutdown_tx,
 		filter_service,
 		monitors,
 		client_pool,
 		contract_specs,
+		create_test_window_service(),
+		create_test_poll_store(),
 	);
 	let result = block_handler(block, network).await;
 
@@ -425,12 +448,14 @@ async fn test_create_block_handler_stellar_client_error() {
 	let network_monitors = vec![(network.clone(), monitors.clone())];
 	let contract_specs = get_contract_specs(&client_pool, &network_monitors).await;
 
-	let block_handler = create_block_handler::<MockClientPool>(
+	let block_handler = create_block_handler::<MockClientPool, _>(
 		shutdown_tx,
 		filter_service,
 		monitors,
 		client_pool,
 		contract_specs,
+		create_test_window_service(),
+		create_test_poll_store(),
 	);
 
 	let result = block_handler(block, network).await;
@@ -492,6 +517,9 @@ print(True)  # Always return true for test
 		language: ScriptLanguage::Python,
 		timeout_ms: 1000,
 		arguments: None,
+		mode: ScriptMode::Process,
+		sandbox: None,
+		interpreter: None,
 	}];
 
 	let processed_block = ProcessedBlock {
@@ -505,6 +533,7 @@ print(True)  # Always return true for test
 			network_slug: "ethereum_mainnet".to_string(),
 			matched_on: MatchConditions::default(),
 			matched_on_args: None,
+			enrichment: None,
 		}))],
 	};
 
@@ -648,6 +677,70 @@ async fn test_load_scripts() {
 	// Cleanup is handled automatically when temp_dir is dropped
 }
 
+#[cfg(feature = "wasm")]
+#[tokio::test]
+async fn test_load_scripts_wasm() {
+	use base64::{prelude::BASE64_STANDARD, Engine as _};
+	use openzeppelin_monitor::services::trigger::ScriptExecutorFactory;
+
+	let temp_dir = tempfile::tempdir().unwrap();
+	let script_path = temp_dir.path().join("test_script.wasm");
+	let module = wat::parse_str(
+		r#"(module
+  (memory (export "memory") 1)
+  (func (export "alloc") (param i32) (result i32) (i32.const 0))
+  (func (export "evaluate") (param i32 i32) (result i32) (i32.const 1)))"#,
+	)
+	.unwrap();
+	tokio::fs::write(&script_path, &module).await.unwrap();
+
+	let monitor = MonitorBuilder::new()
+		.name("test_monitor")
+		.networks(vec!["evm_mainnet".to_string()])
+		.trigger_condition(
+			script_path.to_str().unwrap(),
+			1000,
+			ScriptLanguage::Wasm,
+			None,
+		)
+		.build();
+
+	let trigger_service = setup_trigger_service(HashMap::new());
+	let notification_service = NotificationService::new();
+	let trigger_execution_service =
+		TriggerExecutionService::new(trigger_service, notification_service);
+
+	let scripts = trigger_execution_service
+		.load_scripts(&[monitor.clone()])
+		.await
+		.unwrap();
+
+	// Binary modules are held base64 encoded
+	let script_key = format!("test_monitor|{}", script_path.to_str().unwrap());
+	let (lang, content) = &scripts[&script_key];
+	assert_eq!(*lang, ScriptLanguage::Wasm);
+	assert_eq!(BASE64_STANDARD.decode(content).unwrap(), module);
+
+	let result = ScriptExecutorFactory::create(lang, content)
+		.execute(
+			MonitorMatch::EVM(Box::new(EVMMonitorMatch {
+				monitor,
+				transaction: TransactionBuilder::new().build(),
+				receipt: None,
+				logs: None,
+				network_slug: "evm_mainnet".to_string(),
+				matched_on: MatchConditions::default(),
+				matched_on_args: None,
+				enrichment: None,
+			})),
+			&1000,
+			None,
+			false,
+		)
+		.await;
+	assert!(result.unwrap());
+}
+
 // Also add a test for the error case
 #[tokio::test]
 async fn test_load_scripts_error() {
@@ -864,16 +957,8 @@ async fn test_trigger_execution_service_execute_multiple_triggers_failed_retryab
 	let default_retries_count = HttpRetryConfig::default().max_retries as usize;
 	let mock = server
 		.mock("POST", "/")
-		.match_body(mockito::Matcher::Json(json!({
-			"blocks": [
-				{
-					"type": "section",
-					"text": {
-						"type": "mrkdwn",
-						"text": "*Test Alert*\n\nTest message with value 42"
-					}
-				}
-			]
+		.match_body(mockito::Matcher::PartialJson(json!({
+			"text": "*Test Alert*\n\nTest message with value 42"
 		})))
 		.with_status(500)
 		.expect(1 + default_retries_count)
@@ -946,16 +1031,8 @@ async fn test_trigger_execution_service_execute_multiple_triggers_failed_non_ret
 	let mut server = mockito::Server::new_async().await;
 	let mock = server
 		.mock("POST", "/")
-		.match_body(mockito::Matcher::Json(json!({
-			"blocks": [
-				{
-					"type": "section",
-					"text": {
-						"type": "mrkdwn",
-						"text": "*Test Alert*\n\nTest message with value 42"
-					}
-				}
-			]
+		.match_body(mockito::Matcher::PartialJson(json!({
+			"text": "*Test Alert*\n\nTest message with value 42"
 		})))
 		.with_status(400) // Non-retryable error
 		.expect(1) // 1 initial call, no retries
@@ -1030,16 +1107,8 @@ async fn test_trigger_execution_service_execute_multiple_triggers_success() {
 	// Set up Slack mock
 	let slack_mock = slack_server
 		.mock("POST", "/")
-		.match_body(mockito::Matcher::Json(json!({
-			"blocks": [
-				{
-					"type": "section",
-					"text": {
-						"type": "mrkdwn",
-						"text": "*Test Alert*\n\nTest message with value 42"
-					}
-				}
-			]
+		.match_body(mockito::Matcher::PartialJson(json!({
+			"text": "*Test Alert*\n\nTest message with value 42"
 		})))
 		.with_status(200)
 		.create_async()
@@ -1112,16 +1181,8 @@ async fn test_trigger_execution_service_execute_multiple_triggers_partial_succes
 	// Set up Slack mock
 	let slack_mock = slack_server
 		.mock("POST", "/")
-		.match_body(mockito::Matcher::Json(json!({
-			"blocks": [
-				{
-					"type": "section",
-					"text": {
-						"type": "mrkdwn",
-						"text": "*Test Alert*\n\nTest message with value 42"
-					}
-				}
-			]
+		.match_body(mockito::Matcher::PartialJson(json!({
+			"text": "*Test Alert*\n\nTest message with value 42"
 		})))
 		.with_status(500)
 		.expect(1 + default_retries_count)
@@ -1194,6 +1255,114 @@ async fn test_trigger_execution_service_execute_multiple_triggers_partial_succes
 	webhook_mock.assert();
 }
 
+#[tokio::test]
+async fn test_load_token_metadata() {
+	let token_address = "0x00000000000000000000000000000000000a0047";
+	let contract_address = "0x00000000000000000000000000000000000b0047";
+
+	let mut mock_client = MockEvmClientTrait::<MockEVMTransportClient>::new();
+	mock_client
+		.expect_call_contract()
+		.returning(move |address, data, _| {
+			if address != token_address {
+				return Err(anyhow::anyhow!("execution reverted"));
+			}
+			Ok(match data {
+				// decimals()
+				"0x313ce567" => format!("0x{:0>64}", "8"),
+				// symbol()
+				_ => format!(
+					"0x{:0>64}{:0>64}{:0<64}",
+					"20",
+					"9",
+					hex::encode("BOOTTOKEN")
+				),
+			})
+		});
+
+	let network = create_test_network("Ethereum", "ethereum_mainnet", BlockChainType::EVM);
+	let mut mock_pool = MockClientPool::new();
+	mock_pool
+		.expect_get_evm_client()
+		.return_once(move |_| Ok(Arc::new(mock_client)));
+	let client_pool = Arc::new(mock_pool);
+
+	let mut monitor = create_test_monitor("test", vec!["ethereum_mainnet"], false, vec![]);
+	for address in [token_address, contract_address] {
+		monitor.addresses.push(AddressWithSpec {
+			address: address.to_string(),
+			contract_spec: None,
+		});
+	}
+
+	load_token_metadata(&client_pool, &[(network, vec![monitor])]).await;
+
+	let token = token_metadata(token_address).unwrap();
+	assert_eq!(token.symbol, "BOOTTOKEN");
+	assert_eq!(token.decimals, 8);
+	assert_eq!(token_decimals("boottoken", None), Some(8));
+	assert!(token_metadata(contract_address).is_none());
+}
+
+#[tokio::test]
+async fn test_load_price_feeds() {
+	let aggregator = "0x00000000000000000000000000000000000c0048";
+	let updated_at = chrono::Utc::now().timestamp();
+
+	let mut mock_client = MockEvmClientTrait::<MockEVMTransportClient>::new();
+	mock_client
+		.expect_call_contract()
+		.returning(move |address, data, _| {
+			assert_eq!(address, aggregator);
+			Ok(match data {
+				// decimals()
+				"0x313ce567" => format!("0x{:0>64}", "8"),
+				// latestRoundData(): 2500.5 USD with 8 decimals
+				_ => format!(
+					"0x{:0>64}{:0>64x}{:0>64x}{:0>64x}{:0>64}",
+					"1", 250050000000u64, updated_at, updated_at, "1"
+				),
+			})
+		});
+
+	let network = create_test_network("Ethereum", "ethereum_mainnet", BlockChainType::EVM);
+	let mut mock_pool = MockClientPool::new();
+	mock_pool
+		.expect_get_evm_client()
+		.return_once(move |_| Ok(Arc::new(mock_client)));
+	let client_pool = Arc::new(mock_pool);
+
+	let create_token = |symbol: &str, network: &str| Token {
+		symbol: symbol.to_string(),
+		decimals: 18,
+		addresses: vec![],
+		price: Some(TokenPrice {
+			source: PriceSourceConfig::Chainlink {
+				network: network.to_string(),
+				address: aggregator.to_string(),
+			},
+			ttl_secs: None,
+			max_age_secs: None,
+		}),
+	};
+	let tokens = vec![
+		create_token("BOOTETH", "ethereum_mainnet"),
+		create_token("BOOTSKIPPED", "unknown_network"),
+	];
+	let networks = HashMap::from([("ethereum_mainnet".to_string(), network)]);
+
+	load_price_feeds(&client_pool, &tokens, &networks).await;
+
+	assert_eq!(
+		token_price("booteth").unwrap(),
+		rust_decimal::Decimal::from_str("2500.5").unwrap()
+	);
+	assert!(matches!(
+		token_price("BOOTSKIPPED"),
+		Err(PriceError::NotAvailable(_))
+	));
+}
+
 #[tokio::test]
 async fn test_get_contract_specs() {
 	// Test EVM contract specs
@@ -1360,3 +1529,105 @@ async fn test_get_contract_specs() {
 		_ => panic!("Expected Stellar contract spec"),
 	}
 }
+
+/// Trigger execution service recording the triggers it notifies as `monitor:trigger`, slowly for
+/// the queue trigger of the `slow` monitor. Triggers starting with `queue` are ordered.
+struct RecordingTriggerExecutionService {
+	notified: std::sync::Mutex<Vec<String>>,
+}
+
+#[async_trait::async_trait]
+impl TriggerExecutionServiceTrait for RecordingTriggerExecutionService {
+	async fn execute(
+		&self,
+		trigger_slugs: &[String],
+		_variables: HashMap<String, String>,
+		monitor_match: &MonitorMatch,
+		_trigger_scripts: &HashMap<String, (ScriptLanguage, String)>,
+	) -> Result<(), TriggerError> {
+		let monitor_name = match monitor_match {
+			MonitorMatch::EVM(evm_match) => evm_match.monitor.name.clone(),
+			MonitorMatch::Stellar(stellar_match) => stellar_match.monitor.name.clone(),
+			MonitorMatch::Poll(poll_match) => poll_match.monitor.name.clone(),
+		};
+		for trigger_slug in trigger_slugs {
+			if monitor_name == "slow" && self.is_ordered(trigger_slug) {
+				tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+			}
+			self.notified
+				.lock()
+				.unwrap()
+				.push(format!("{}:{}", monitor_name, trigger_slug));
+		}
+		Ok(())
+	}
+
+	fn is_ordered(&self, trigger_slug: &str) -> bool {
+		trigger_slug.starts_with("queue")
+	}
+
+	async fn load_scripts(
+		&self,
+		_monitors: &[Monitor],
+	) -> Result<HashMap<String, (ScriptLanguage, String)>, TriggerError> {
+		Ok(HashMap::new())
+	}
+}
+
+#[tokio::test]
+async fn test_create_trigger_handler_notifies_network_blocks_in_order() {
+	let trigger_execution_service = Arc::new(RecordingTriggerExecutionService {
+		notified: std::sync::Mutex::new(vec![]),
+	});
+	let (shutdown_tx, _) = watch::channel(false);
+	let trigger_handler = create_trigger_handler(
+		shutdown_tx,
+		trigger_execution_service.clone(),
+		HashMap::new(),
+	);
+
+	let create_block = |block_number: u64, monitor_name: &str, network_slug: &str| ProcessedBlock {
+		block_number,
+		network_slug: network_slug.to_string(),
+		processing_results: vec![MonitorMatch::EVM(Box::new(EVMMonitorMatch {
+			monitor: create_test_monitor(
+				monitor_name,
+				vec![network_slug],
+				false,
+				vec!["slack", "queue"],
+			),
+			transaction: TransactionBuilder::new().build(),
+			receipt: Some(EVMTransactionReceipt::default()),
+			logs: Some(vec![]),
+			network_slug: network_slug.to_string(),
+			matched_on: MatchConditions::default(),
+			matched_on_args: None,
+			enrichment: None,
+		}))],
+	};
+
+	let handles = vec![
+		trigger_handler(&create_block(100, "slow", "ethereum_mainnet")),
+		trigger_handler(&create_block(101, "fast", "ethereum_mainnet")),
+		trigger_handler(&create_block(100, "other_network", "polygon_mainnet")),
+	];
+	for handle in handles {
+		handle
+			.await
+			.expect("Trigger handler task should complete successfully");
+	}
+
+	// Queue triggers publish the blocks of a network in order, other triggers and networks are
+	// not held back
+	let notified = trigger_execution_service.notified.lock().unwrap().clone();
+	let position = |entry: &str| {
+		notified
+			.iter()
+			.position(|notified| notified == entry)
+			.unwrap()
+	};
+	assert_eq!(notified.len(), 6);
+	assert!(position("fast:slack") < position("slow:queue"));
+	assert!(position("other_network:queue") < position("slow:queue"));
+	assert!(position("slow:queue") < position("fast:queue"));
+}
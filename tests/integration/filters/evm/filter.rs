@@ -10,7 +10,8 @@ use std::collections::HashMap;
 use openzeppelin_monitor::{
 	models::{
 		BlockType, ContractSpec, EVMReceiptLog, EVMTransactionReceipt, EventCondition,
-		FunctionCondition, Monitor, MonitorMatch, TransactionCondition, TransactionStatus,
+		FunctionCondition, MatchConditions, Monitor, MonitorMatch, PollConfig, PollRead, StateCall,
+		StateConditions, TransactionCondition, TransactionStatus,
 	},
 	services::{
 		blockchain::{EvmClient, TransportError},
@@ -742,6 +743,7 @@ async fn test_handle_match_with_key_collision() -> Result<(), Box<FilterError>>
 			}],
 			events: vec![],
 			transactions: vec![],
+			..Default::default()
 		},
 		matched_on_args: Some(EVMMatchArguments {
 			functions: Some(vec![EVMMatchParamsMap {
@@ -761,9 +763,11 @@ async fn test_handle_match_with_key_collision() -> Result<(), Box<FilterError>>
 					},
 				]),
 				hex_signature: Some("0xdeadbeef".to_string()),
+				log_index: None,
 			}]),
 			events: None,
 		}),
+		enrichment: None,
 	};
 
 	let match_wrapper = MonitorMatch::EVM(Box::new(evm_match));
@@ -1119,3 +1123,178 @@ async fn test_filter_block_needs_receipt_for_status() -> Result<(), Box<FilterEr
 
 	Ok(())
 }
+
+#[tokio::test]
+async fn test_filter_block_with_state() -> Result<(), Box<FilterError>> {
+	let test_data = load_test_data("evm");
+	let filter_service = FilterService::new();
+	let block_number = match &test_data.blocks[0] {
+		BlockType::EVM(block) => block.number.unwrap().to::<u64>(),
+		_ => panic!("Expected EVM block"),
+	};
+	let logs = test_data.receipts[0].logs.clone();
+
+	let mut mock_transport = MockEVMTransportClient::new();
+	mock_transport
+		.expect_send_raw_request()
+		.returning(move |method, params| match method {
+			"eth_getLogs" => Ok(json!({ "result": logs })),
+			"eth_call" => {
+				let params = params.unwrap();
+				assert_eq!(
+					params[0]["data"],
+					"0x70a0823100000000000000000000000058b704065b7aff3ed351052f8560019e05925023"
+				);
+				// The balance of the sender decreased in the block of the match
+				let balance = if params[1] == json!(format!("0x{:x}", block_number)) {
+					"2710"
+				} else {
+					"4e20"
+				};
+				Ok(json!({ "result": format!("0x{:0>64}", balance) }))
+			}
+			_ => Ok(json!({ "result": "1" })),
+		});
+	let client = EvmClient::new_with_transport(mock_transport);
+
+	let mut monitor = make_monitor_with_events(test_data.monitor, true);
+	let state = StateConditions {
+		calls: vec![StateCall {
+			name: "balance".to_string(),
+			address: "${transaction.to}".to_string(),
+			signature: "balanceOf(address)".to_string(),
+			returns: "uint256".to_string(),
+			args: vec!["${events.0.args.from}".to_string()],
+			previous: true,
+		}],
+		expression: Some("balance < balance_previous".to_string()),
+	};
+
+	monitor.state = Some(state.clone());
+	let matches = filter_service
+		.filter_block(
+			&client,
+			&test_data.network,
+			&test_data.blocks[0],
+			&[monitor.clone()],
+			None,
+		)
+		.await?;
+
+	assert_eq!(matches.len(), 1, "Expected exactly one match");
+	let enrichment = matches[0].enrichment().unwrap();
+	assert_eq!(enrichment["state"]["balance"], json!("10000"));
+	assert_eq!(enrichment["state"]["balance_previous"], json!("20000"));
+
+	monitor.state = Some(StateConditions {
+		expression: Some("balance > balance_previous".to_string()),
+		..state
+	});
+	let matches = filter_service
+		.filter_block(
+			&client,
+			&test_data.network,
+			&test_data.blocks[0],
+			&[monitor],
+			None,
+		)
+		.await?;
+	assert!(
+		matches.is_empty(),
+		"State expression should reject the match"
+	);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_filter_block_with_poll() -> Result<(), Box<FilterError>> {
+	let test_data = load_test_data("evm");
+	let filter_service = FilterService::new();
+	let block_number = match &test_data.blocks[0] {
+		BlockType::EVM(block) => block.number.unwrap().to::<u64>(),
+		_ => panic!("Expected EVM block"),
+	};
+	let logs = test_data.receipts[0].logs.clone();
+
+	let mut mock_transport = MockEVMTransportClient::new();
+	mock_transport
+		.expect_send_raw_request()
+		.returning(move |method, params| match method {
+			"eth_getLogs" => Ok(json!({ "result": logs })),
+			"eth_getBalance" => {
+				let params = params.unwrap();
+				assert_eq!(params[0], "0x58b704065b7aff3ed351052f8560019e05925023");
+				assert_eq!(params[1], json!(format!("0x{:x}", block_number)));
+				Ok(json!({ "result": "0x2710" }))
+			}
+			"eth_call" => {
+				// latestRoundData() of a price feed updated at timestamp 100
+				assert_eq!(params.unwrap()[0]["data"], "0xfeaf968c");
+				Ok(json!({
+					"result": format!("0x{:0>64}{:0>64}{:0>64}{:0>64}{:0>64}", "1", "5f5e100", "64", "64", "1")
+				}))
+			}
+			_ => Ok(json!({ "result": "1" })),
+		});
+	let client = EvmClient::new_with_transport(mock_transport);
+
+	let mut monitor = test_data.monitor;
+	monitor.name = "Hot wallet poll".to_string();
+	monitor.match_conditions = MatchConditions::default();
+	monitor.poll = Some(PollConfig {
+		every_blocks: Some(100),
+		interval_secs: None,
+		reads: vec![
+			PollRead::Balance {
+				name: "balance".to_string(),
+				address: "0x58b704065b7aff3ed351052f8560019e05925023".to_string(),
+			},
+			PollRead::Call {
+				name: "updated_at".to_string(),
+				address: "0x0000000000000000000000000000000000000001".to_string(),
+				signature: "latestRoundData()".to_string(),
+				args: vec![],
+				returns: "(uint80,int256,uint256,uint256,uint80)".to_string(),
+				output: Some(3),
+			},
+		],
+		expression: "balance < 20000 AND block_timestamp - updated_at > 3600".to_string(),
+	});
+
+	let matches = filter_service
+		.filter_block(
+			&client,
+			&test_data.network,
+			&test_data.blocks[0],
+			&[monitor.clone()],
+			None,
+		)
+		.await?;
+
+	assert_eq!(matches.len(), 1, "Expected exactly one poll match");
+	match &matches[0] {
+		MonitorMatch::Poll(poll_match) => {
+			assert_eq!(poll_match.block_number, block_number);
+			assert_eq!(poll_match.values[0].value, "10000");
+			assert_eq!(poll_match.values[0].previous, "10000");
+			assert_eq!(poll_match.values[1].value, "100");
+			assert_eq!(poll_match.values[1].kind, "uint256");
+		}
+		_ => panic!("Expected poll match"),
+	}
+
+	// The monitor is not polled again before the next 100 blocks
+	let matches = filter_service
+		.filter_block(
+			&client,
+			&test_data.network,
+			&test_data.blocks[0],
+			&[monitor],
+			None,
+		)
+		.await?;
+	assert!(matches.is_empty(), "Monitor should not be polled again");
+
+	Ok(())
+}
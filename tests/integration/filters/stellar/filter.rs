@@ -134,10 +134,14 @@ async fn test_monitor_events_with_no_expressions() -> Result<(), Box<FilterError
 					== "transfer(Address,Address,String,I128)"
 			);
 
+			// Arguments are recorded for every matched event so they stay aligned with
+			// `matched_on.events`, as on EVM
 			let matched_on_args = stellar_match.matched_on_args.as_ref().unwrap();
-			assert!(
-				matched_on_args.events.as_ref().unwrap().is_empty(),
-				"Expected no events arguments to be matched"
+			let event_args = matched_on_args.events.as_ref().unwrap();
+			assert_eq!(event_args.len(), 1, "Expected one set of event arguments");
+			assert_eq!(
+				event_args[0].signature,
+				"transfer(Address,Address,String,I128)"
 			);
 		}
 		_ => {
@@ -991,6 +995,7 @@ async fn test_handle_match_with_key_collision() -> Result<(), Box<FilterError>>
 			}],
 			events: vec![],
 			transactions: vec![],
+			..Default::default()
 		},
 		matched_on_args: Some(StellarMatchArguments {
 			functions: Some(vec![StellarMatchParamsMap {
@@ -1009,9 +1014,11 @@ async fn test_handle_match_with_key_collision() -> Result<(), Box<FilterError>>
 						indexed: false,
 					},
 				]),
+				event_index: None,
 			}]),
 			events: None,
 		}),
+		enrichment: None,
 	};
 
 	let match_wrapper = MonitorMatch::Stellar(Box::new(stellar_match));
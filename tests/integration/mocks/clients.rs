@@ -25,6 +25,7 @@ use openzeppelin_monitor::{
 	},
 };
 
+use alloy::primitives::U256;
 use async_trait::async_trait;
 use mockall::{mock, predicate::*};
 
@@ -62,6 +63,19 @@ mock! {
 			to_block: u64,
 			addresses: Option<Vec<String>>,
 		) -> Result<Vec<EVMReceiptLog>,  anyhow::Error>;
+
+		async fn call_contract(
+			&self,
+			address: &str,
+			data: &str,
+			block_number: Option<u64>,
+		) -> Result<String, anyhow::Error>;
+
+		async fn get_balance(
+			&self,
+			address: &str,
+			block_number: Option<u64>,
+		) -> Result<U256, anyhow::Error>;
 	}
 
 	impl<T: Send + Sync + Clone + 'static> Clone for EvmClientTrait<T> {
@@ -108,7 +122,7 @@ mock! {
 			end_sequence: Option<u32>,
 		) -> Result<Vec<StellarEvent>, anyhow::Error>;
 
-
+		async fn get_account_balance(&self, account_id: &str) -> Result<i64, anyhow::Error>;
 	}
 
 	impl<T: Send + Sync + Clone + 'static> Clone for StellarClientTrait<T> {
@@ -3,7 +3,7 @@ use mockall::mock;
 use std::collections::HashMap;
 
 use openzeppelin_monitor::{
-	models::{BlockType, Monitor, MonitorMatch, Network, ScriptLanguage},
+	models::{BlockType, Monitor, MonitorMatch, Network, PollState, ScriptLanguage, WindowState},
 	repositories::{TriggerRepositoryTrait, TriggerService},
 	services::{
 		blockchain::BlockFilterFactory,
@@ -55,6 +55,10 @@ mock! {
 		async fn get_last_processed_block(&self, network_slug: &str) -> Result<Option<u64>, anyhow::Error>;
 		async fn save_blocks(&self, network_slug: &str, blocks: &[BlockType]) -> Result<(), anyhow::Error>;
 		async fn delete_blocks(&self, network_slug: &str) -> Result<(), anyhow::Error>;
+		async fn get_window_state(&self, network_slug: &str, monitor_name: &str) -> Result<Option<WindowState>, anyhow::Error>;
+		async fn save_window_state(&self, network_slug: &str, monitor_name: &str, state: &WindowState) -> Result<(), anyhow::Error>;
+		async fn get_poll_state(&self, network_slug: &str, monitor_name: &str) -> Result<Option<PollState>, anyhow::Error>;
+		async fn save_poll_state(&self, network_slug: &str, monitor_name: &str, state: &PollState) -> Result<(), anyhow::Error>;
 	}
 
 	impl Clone for BlockStorage {
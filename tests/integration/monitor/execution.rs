@@ -10,7 +10,8 @@ use crate::integration::{
 use mockall::predicate;
 use openzeppelin_monitor::{
 	models::{
-		BlockChainType, EVMTransactionReceipt, Monitor, ScriptLanguage, Trigger, TriggerConditions,
+		BlockChainType, EVMTransactionReceipt, Monitor, ScriptLanguage, ScriptMode, Trigger,
+		TriggerConditions,
 	},
 	repositories::{
 		MonitorRepository, MonitorRepositoryTrait, NetworkRepository, NetworkService,
@@ -935,6 +936,9 @@ async fn test_execute_monitor_evm_with_trigger_scripts() {
 		language: ScriptLanguage::Python,
 		timeout_ms: 10000,
 		arguments: None,
+		mode: ScriptMode::Process,
+		sandbox: None,
+		interpreter: None,
 	}];
 	mocked_monitors.insert("monitor".to_string(), test_data.monitor.clone());
 	let mock_monitor_service = setup_monitor_service(mocked_monitors);
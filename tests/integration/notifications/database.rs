@@ -0,0 +1,244 @@
+//! Integration tests for database triggers.
+//!
+//! Matches are written to SQLite databases created in temporary directories.
+
+use openzeppelin_monitor::{
+	models::{
+		EVMMatchArguments, EVMMatchParamEntry, EVMMatchParamsMap, EVMMonitorMatch, EventCondition,
+		MatchConditions, Monitor, MonitorMatch, TriggerTypeConfig,
+	},
+	services::{
+		filter::build_match_variables,
+		notification::{NotificationError, NotificationService},
+	},
+	utils::{
+		tests::{
+			evm::{monitor::MonitorBuilder, transaction::TransactionBuilder},
+			trigger::TriggerBuilder,
+		},
+		HttpRetryConfig,
+	},
+};
+use sqlx::{any::AnyPoolOptions, AnyPool};
+use std::collections::HashMap;
+use tokio::net::TcpListener;
+
+use crate::integration::mocks::{create_test_evm_logs, create_test_evm_transaction_receipt};
+
+fn create_test_monitor(name: &str) -> Monitor {
+	MonitorBuilder::new()
+		.name(name)
+		.networks(vec!["ethereum_mainnet".to_string()])
+		.paused(false)
+		.triggers(vec!["test_trigger".to_string()])
+		.build()
+}
+
+/// Creates a match of a transaction emitting a transfer of each value
+fn create_test_evm_match(monitor: Monitor, values: &[&str]) -> MonitorMatch {
+	let signature = "Transfer(address,address,uint256)";
+	MonitorMatch::EVM(Box::new(EVMMonitorMatch {
+		monitor,
+		transaction: TransactionBuilder::new().build(),
+		receipt: Some(create_test_evm_transaction_receipt()),
+		logs: Some(create_test_evm_logs()),
+		network_slug: "ethereum_mainnet".to_string(),
+		matched_on: MatchConditions {
+			events: values
+				.iter()
+				.map(|_| EventCondition {
+					signature: signature.to_string(),
+					expression: None,
+				})
+				.collect(),
+			..Default::default()
+		},
+		matched_on_args: Some(EVMMatchArguments {
+			functions: None,
+			events: Some(
+				values
+					.iter()
+					.enumerate()
+					.map(|(index, value)| EVMMatchParamsMap {
+						signature: signature.to_string(),
+						args: Some(vec![EVMMatchParamEntry {
+							name: "value".to_string(),
+							value: value.to_string(),
+							kind: "uint256".to_string(),
+							indexed: false,
+						}]),
+						hex_signature: None,
+						log_index: Some(10 + index as u64),
+					})
+					.collect(),
+			),
+		}),
+		enrichment: None,
+	}))
+}
+
+/// Creates a SQLite database with a transfers table, returning its URL and a pool to read it
+async fn create_test_database(dir: &tempfile::TempDir) -> (String, AnyPool) {
+	sqlx::any::install_default_drivers();
+	let url = format!(
+		"sqlite://{}?mode=rwc",
+		dir.path().join("matches.db").display()
+	);
+	let pool = AnyPoolOptions::new().connect(&url).await.unwrap();
+	sqlx::query(
+		"CREATE TABLE transfers (
+			network TEXT NOT NULL,
+			transaction_hash TEXT NOT NULL,
+			log_index INTEGER NOT NULL,
+			monitor TEXT,
+			value TEXT,
+			PRIMARY KEY (network, transaction_hash, log_index)
+		)",
+	)
+	.execute(&pool)
+	.await
+	.unwrap();
+	(url, pool)
+}
+
+#[tokio::test]
+async fn test_notification_service_database_execution() {
+	let dir = tempfile::tempdir().unwrap();
+	let (url, pool) = create_test_database(&dir).await;
+	let notification_service = NotificationService::new();
+	let trigger = TriggerBuilder::new()
+		.name("test_trigger")
+		.database(&url, "transfers")
+		.column("monitor", "${monitor.name}")
+		.column("value", "${event.args.value}")
+		.build();
+	let monitor_match = create_test_evm_match(create_test_monitor("Large Transfer"), &["1", "2"]);
+	let variables = build_match_variables(&monitor_match);
+
+	// Writing the same match again updates its rows
+	for _ in 0..2 {
+		let result = notification_service
+			.execute(&trigger, &variables, &monitor_match, &HashMap::new())
+			.await;
+		assert!(result.is_ok());
+	}
+
+	let rows = sqlx::query_as::<_, (String, String, i64, String, String)>(
+		"SELECT network, transaction_hash, log_index, monitor, value FROM transfers
+		 ORDER BY log_index",
+	)
+	.fetch_all(&pool)
+	.await
+	.unwrap();
+	let hash = variables["transaction.hash"].clone();
+	assert_eq!(
+		rows,
+		vec![
+			(
+				"ethereum_mainnet".to_string(),
+				hash.clone(),
+				10,
+				"Large Transfer".to_string(),
+				"1".to_string()
+			),
+			(
+				"ethereum_mainnet".to_string(),
+				hash,
+				11,
+				"Large Transfer".to_string(),
+				"2".to_string()
+			),
+		]
+	);
+}
+
+#[tokio::test]
+async fn test_notification_service_database_concurrent_matches() {
+	let dir = tempfile::tempdir().unwrap();
+	let (url, pool) = create_test_database(&dir).await;
+	let notification_service = NotificationService::new();
+	let mut trigger = TriggerBuilder::new()
+		.name("test_trigger")
+		.database(&url, "transfers")
+		.column("monitor", "${monitor.name}")
+		.build();
+	if let TriggerTypeConfig::Database {
+		batch_size,
+		batch_timeout_ms,
+		..
+	} = &mut trigger.config
+	{
+		*batch_size = Some(10);
+		*batch_timeout_ms = Some(50);
+	}
+
+	// Transactions built by the test builder share a hash, so every monitor writes its own
+	// log index
+	let matches = (0..5)
+		.map(|index| {
+			let mut monitor_match =
+				create_test_evm_match(create_test_monitor(&format!("Monitor {}", index)), &["1"]);
+			if let MonitorMatch::EVM(evm_match) = &mut monitor_match {
+				evm_match
+					.matched_on_args
+					.as_mut()
+					.unwrap()
+					.events
+					.as_mut()
+					.unwrap()[0]
+					.log_index = Some(index);
+			}
+			monitor_match
+		})
+		.collect::<Vec<_>>();
+	let trigger_scripts = HashMap::new();
+	let variables = matches
+		.iter()
+		.map(build_match_variables)
+		.collect::<Vec<_>>();
+	let results = futures::future::join_all(matches.iter().zip(&variables).map(
+		|(monitor_match, variables)| {
+			notification_service.execute(&trigger, variables, monitor_match, &trigger_scripts)
+		},
+	))
+	.await;
+	assert!(results.iter().all(Result::is_ok));
+
+	let count = sqlx::query_as::<_, (i64,)>("SELECT COUNT(*) FROM transfers")
+		.fetch_one(&pool)
+		.await
+		.unwrap();
+	assert_eq!(count.0, 5);
+}
+
+#[cfg(feature = "postgres")]
+#[tokio::test]
+async fn test_notification_service_database_unavailable() {
+	// Nothing listens on the port once the listener is dropped
+	let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+	let url = format!(
+		"postgres://monitor@{}/monitor",
+		listener.local_addr().unwrap()
+	);
+	drop(listener);
+
+	let notification_service = NotificationService::new();
+	let mut retry_policy = HttpRetryConfig::default();
+	retry_policy.max_retries = 0;
+	let trigger = TriggerBuilder::new()
+		.name("test_trigger")
+		.database(&url, "transfers")
+		.retry_policy(retry_policy)
+		.build();
+	let monitor_match = create_test_evm_match(create_test_monitor("Large Transfer"), &["1"]);
+
+	let result = notification_service
+		.execute(
+			&trigger,
+			&build_match_variables(&monitor_match),
+			&monitor_match,
+			&HashMap::new(),
+		)
+		.await;
+	assert!(matches!(result, Err(NotificationError::NetworkError(_))));
+}
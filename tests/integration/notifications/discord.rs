@@ -1,5 +1,5 @@
 use openzeppelin_monitor::{
-	models::{EVMMonitorMatch, MatchConditions, Monitor, MonitorMatch},
+	models::{EVMMonitorMatch, IncidentSeverity, MatchConditions, Monitor, MonitorMatch},
 	services::notification::{DiscordNotifier, NotificationError, NotificationService, Notifier},
 	utils::{
 		tests::{
@@ -36,6 +36,7 @@ fn create_test_evm_match(monitor: Monitor) -> MonitorMatch {
 		network_slug: "ethereum_mainnet".to_string(),
 		matched_on: MatchConditions::default(),
 		matched_on_args: None,
+		enrichment: None,
 	}))
 }
 
@@ -133,6 +134,16 @@ async fn test_notification_service_discord_execution() {
 	// Setup mock Discord webhook server
 	let mock = server
 		.mock("POST", "/")
+		.match_body(mockito::Matcher::Json(json!({
+			"embeds": [{
+				"title": "Test Alert",
+				"description": "Test message 42",
+				"fields": [
+					{ "name": "Monitor", "value": "test_monitor", "inline": true },
+					{ "name": "Network", "value": "ethereum_mainnet", "inline": true }
+				]
+			}]
+		})))
 		.with_status(200)
 		.create_async()
 		.await;
@@ -191,3 +202,51 @@ async fn test_notification_service_discord_execution_failure() {
 
 	mock.assert();
 }
+
+#[tokio::test]
+async fn test_notification_service_discord_embeds_template_with_severity() {
+	let notification_service = NotificationService::new();
+	let mut server = mockito::Server::new_async().await;
+	let mock = server
+		.mock("POST", "/")
+		.match_body(mockito::Matcher::Json(json!({
+			"embeds": [{
+				"title": "test_monitor",
+				"url": "https://etherscan.io/tx/0xabc",
+				"color": 0xECB22E
+			}]
+		})))
+		.with_status(200)
+		.create_async()
+		.await;
+
+	let trigger = TriggerBuilder::new()
+		.name("test_trigger")
+		.discord(&server.url())
+		.message("Test Alert", "Test message")
+		.discord_embeds(json!([{
+			"title": "${monitor.name}",
+			"url": "https://etherscan.io/tx/${transaction.hash}"
+		}]))
+		.build();
+	let monitor = MonitorBuilder::new()
+		.name("test_monitor")
+		.severity(IncidentSeverity::Warning)
+		.build();
+	let variables = HashMap::from([
+		("monitor.name".to_string(), "test_monitor".to_string()),
+		("transaction.hash".to_string(), "0xabc".to_string()),
+	]);
+
+	let result = notification_service
+		.execute(
+			&trigger,
+			&variables,
+			&create_test_evm_match(monitor),
+			&HashMap::new(),
+		)
+		.await;
+
+	assert!(result.is_ok());
+	mock.assert();
+}
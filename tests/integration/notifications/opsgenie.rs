@@ -0,0 +1,112 @@
+use openzeppelin_monitor::{
+	models::{EVMMonitorMatch, IncidentSeverity, MatchConditions, Monitor, MonitorMatch},
+	services::notification::NotificationService,
+	utils::tests::{
+		evm::{monitor::MonitorBuilder, transaction::TransactionBuilder},
+		trigger::TriggerBuilder,
+	},
+};
+use serde_json::json;
+use std::collections::HashMap;
+
+use crate::integration::mocks::{create_test_evm_logs, create_test_evm_transaction_receipt};
+
+fn create_test_monitor(name: &str) -> Monitor {
+	MonitorBuilder::new()
+		.name(name)
+		.networks(vec!["ethereum_mainnet".to_string()])
+		.paused(false)
+		.triggers(vec!["test_trigger".to_string()])
+		.build()
+}
+
+fn create_test_evm_match(monitor: Monitor) -> MonitorMatch {
+	MonitorMatch::EVM(Box::new(EVMMonitorMatch {
+		monitor,
+		transaction: TransactionBuilder::new().build(),
+		receipt: Some(create_test_evm_transaction_receipt()),
+		logs: Some(create_test_evm_logs()),
+		network_slug: "ethereum_mainnet".to_string(),
+		matched_on: MatchConditions::default(),
+		matched_on_args: None,
+		enrichment: None,
+	}))
+}
+
+#[tokio::test]
+async fn test_notification_service_opsgenie_execution() {
+	let notification_service = NotificationService::new();
+	let mut server = mockito::Server::new_async().await;
+
+	let mock = server
+		.mock("POST", "/v2/alerts")
+		.match_header("Authorization", "GenieKey api-key")
+		.match_body(mockito::Matcher::Json(json!({
+			"message": "Contract paused",
+			"description": "Paused by 0x1",
+			"priority": "P3",
+			"source": "openzeppelin-monitor",
+			"alias": "pause-test_monitor"
+		})))
+		.with_status(202)
+		.expect(1)
+		.create_async()
+		.await;
+
+	let trigger = TriggerBuilder::new()
+		.name("test_trigger")
+		.opsgenie(&server.url())
+		.message("Contract paused", "Paused by ${account}")
+		.incident(
+			IncidentSeverity::Warning,
+			Some("pause-${monitor.name}"),
+			None,
+		)
+		.build();
+	let variables = HashMap::from([
+		("account".to_string(), "0x1".to_string()),
+		("monitor.name".to_string(), "test_monitor".to_string()),
+	]);
+
+	let result = notification_service
+		.execute(
+			&trigger,
+			&variables,
+			&create_test_evm_match(create_test_monitor("test_monitor")),
+			&HashMap::new(),
+		)
+		.await;
+
+	assert!(result.is_ok());
+	mock.assert();
+}
+
+#[tokio::test]
+async fn test_notification_service_opsgenie_execution_failure() {
+	let notification_service = NotificationService::new();
+	let mut server = mockito::Server::new_async().await;
+
+	let mock = server
+		.mock("POST", "/v2/alerts")
+		.with_status(401)
+		.expect(1)
+		.create_async()
+		.await;
+
+	let trigger = TriggerBuilder::new()
+		.name("test_trigger")
+		.opsgenie(&server.url())
+		.build();
+
+	let result = notification_service
+		.execute(
+			&trigger,
+			&HashMap::new(),
+			&create_test_evm_match(create_test_monitor("test_monitor")),
+			&HashMap::new(),
+		)
+		.await;
+
+	assert!(result.is_err());
+	mock.assert();
+}
@@ -0,0 +1,126 @@
+use openzeppelin_monitor::{
+	models::{EVMMonitorMatch, IncidentSeverity, MatchConditions, Monitor, MonitorMatch},
+	services::notification::NotificationService,
+	utils::tests::{
+		evm::{monitor::MonitorBuilder, transaction::TransactionBuilder},
+		trigger::TriggerBuilder,
+	},
+};
+use serde_json::json;
+use std::collections::HashMap;
+
+use crate::integration::mocks::{create_test_evm_logs, create_test_evm_transaction_receipt};
+
+fn create_test_monitor(name: &str) -> Monitor {
+	MonitorBuilder::new()
+		.name(name)
+		.networks(vec!["ethereum_mainnet".to_string()])
+		.paused(false)
+		.triggers(vec!["test_trigger".to_string()])
+		.build()
+}
+
+fn create_test_evm_match(monitor: Monitor) -> MonitorMatch {
+	MonitorMatch::EVM(Box::new(EVMMonitorMatch {
+		monitor,
+		transaction: TransactionBuilder::new().build(),
+		receipt: Some(create_test_evm_transaction_receipt()),
+		logs: Some(create_test_evm_logs()),
+		network_slug: "ethereum_mainnet".to_string(),
+		matched_on: MatchConditions::default(),
+		matched_on_args: None,
+		enrichment: None,
+	}))
+}
+
+#[tokio::test]
+async fn test_notification_service_pagerduty_execution() {
+	let notification_service = NotificationService::new();
+	let mut server = mockito::Server::new_async().await;
+
+	let trigger_mock = server
+		.mock("POST", "/")
+		.match_body(mockito::Matcher::Json(json!({
+			"routing_key": "routing-key",
+			"event_action": "trigger",
+			"dedup_key": "test_monitor:0xabc",
+			"payload": {
+				"summary": "Ownership transferred",
+				"source": "openzeppelin-monitor",
+				"severity": "error",
+				"custom_details": { "body": "New owner 0x1" }
+			}
+		})))
+		.with_status(202)
+		.expect(1)
+		.create_async()
+		.await;
+	let resolve_mock = server
+		.mock("POST", "/")
+		.match_body(mockito::Matcher::PartialJson(json!({
+			"event_action": "resolve",
+			"dedup_key": "test_monitor:0xabc"
+		})))
+		.with_status(202)
+		.expect(1)
+		.create_async()
+		.await;
+
+	let trigger = TriggerBuilder::new()
+		.name("test_trigger")
+		.pagerduty(&server.url())
+		.message("Ownership transferred", "New owner ${owner}")
+		.incident(IncidentSeverity::Error, None, Some(1))
+		.build();
+	let variables = HashMap::from([
+		("owner".to_string(), "0x1".to_string()),
+		("monitor.name".to_string(), "test_monitor".to_string()),
+		("transaction.hash".to_string(), "0xabc".to_string()),
+	]);
+
+	let result = notification_service
+		.execute(
+			&trigger,
+			&variables,
+			&create_test_evm_match(create_test_monitor("test_monitor")),
+			&HashMap::new(),
+		)
+		.await;
+
+	assert!(result.is_ok());
+	trigger_mock.assert();
+
+	// The incident is resolved once the auto-resolve delay has passed
+	tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+	resolve_mock.assert();
+}
+
+#[tokio::test]
+async fn test_notification_service_pagerduty_execution_failure() {
+	let notification_service = NotificationService::new();
+	let mut server = mockito::Server::new_async().await;
+
+	let mock = server
+		.mock("POST", "/")
+		.with_status(400)
+		.expect(1)
+		.create_async()
+		.await;
+
+	let trigger = TriggerBuilder::new()
+		.name("test_trigger")
+		.pagerduty(&server.url())
+		.build();
+
+	let result = notification_service
+		.execute(
+			&trigger,
+			&HashMap::new(),
+			&create_test_evm_match(create_test_monitor("test_monitor")),
+			&HashMap::new(),
+		)
+		.await;
+
+	assert!(result.is_err());
+	mock.assert();
+}
@@ -0,0 +1,582 @@
+//! Integration tests for message queue triggers.
+//!
+//! Brokers are replaced by local stand-ins speaking just enough of the Redis, NATS and AMQP
+//! protocols to acknowledge published messages, by the librdkafka mock cluster for Kafka, and by
+//! an in-memory publisher for retries and ordering. Tests of a broker only run when its feature
+//! is enabled.
+#![cfg_attr(
+	not(all(
+		feature = "amqp",
+		feature = "kafka",
+		feature = "nats",
+		feature = "redis"
+	)),
+	allow(unused_imports)
+)]
+
+use async_trait::async_trait;
+use openzeppelin_monitor::{
+	models::{EVMMonitorMatch, MatchConditions, Monitor, MonitorMatch},
+	services::notification::{
+		NotificationError, NotificationService, QueueMessage, QueueNotifier, QueuePublisher,
+		MATCH_PAYLOAD_VERSION,
+	},
+	utils::{
+		tests::{
+			evm::{monitor::MonitorBuilder, transaction::TransactionBuilder},
+			trigger::TriggerBuilder,
+		},
+		HttpRetryConfig,
+	},
+};
+use std::{
+	collections::HashMap,
+	sync::{Arc, Mutex},
+	time::Duration,
+};
+use tokio::{
+	io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+	net::TcpListener,
+	sync::mpsc,
+};
+
+use crate::integration::mocks::{create_test_evm_logs, create_test_evm_transaction_receipt};
+
+fn create_test_monitor(name: &str) -> Monitor {
+	MonitorBuilder::new()
+		.name(name)
+		.networks(vec!["ethereum_mainnet".to_string()])
+		.paused(false)
+		.triggers(vec!["test_trigger".to_string()])
+		.build()
+}
+
+fn create_test_evm_match(monitor: Monitor) -> MonitorMatch {
+	MonitorMatch::EVM(Box::new(EVMMonitorMatch {
+		monitor,
+		transaction: TransactionBuilder::new().build(),
+		receipt: Some(create_test_evm_transaction_receipt()),
+		logs: Some(create_test_evm_logs()),
+		network_slug: "ethereum_mainnet".to_string(),
+		matched_on: MatchConditions::default(),
+		matched_on_args: None,
+		enrichment: None,
+	}))
+}
+
+/// Starts a Redis stand-in acknowledging every command, and forwarding `XADD` arguments
+#[cfg(feature = "redis")]
+async fn start_redis_stand_in() -> (String, mpsc::UnboundedReceiver<Vec<Vec<u8>>>) {
+	start_redis_stand_in_on(TcpListener::bind("127.0.0.1:0").await.unwrap())
+}
+
+/// Starts a Redis stand-in on the given listener
+#[cfg(feature = "redis")]
+fn start_redis_stand_in_on(
+	listener: TcpListener,
+) -> (String, mpsc::UnboundedReceiver<Vec<Vec<u8>>>) {
+	let url = format!("redis://{}", listener.local_addr().unwrap());
+	let (tx, rx) = mpsc::unbounded_channel();
+
+	tokio::spawn(async move {
+		while let Ok((stream, _)) = listener.accept().await {
+			let tx = tx.clone();
+			tokio::spawn(async move {
+				let (reader, mut writer) = stream.into_split();
+				let mut reader = BufReader::new(reader);
+				let mut line = String::new();
+				while reader.read_line(&mut line).await.unwrap_or(0) > 0 {
+					// Commands are arrays of bulk strings
+					let count = line.trim()[1..].parse::<usize>().unwrap();
+					let mut command = Vec::with_capacity(count);
+					for _ in 0..count {
+						line.clear();
+						reader.read_line(&mut line).await.unwrap();
+						let len = line.trim()[1..].parse::<usize>().unwrap();
+						let mut arg = vec![0; len + 2];
+						reader.read_exact(&mut arg).await.unwrap();
+						arg.truncate(len);
+						command.push(arg);
+					}
+					line.clear();
+
+					let reply: &[u8] = if command[0].eq_ignore_ascii_case(b"XADD") {
+						tx.send(command).unwrap();
+						b"$3\r\n1-0\r\n"
+					} else {
+						b"+OK\r\n"
+					};
+					writer.write_all(reply).await.unwrap();
+				}
+			});
+		}
+	});
+
+	(url, rx)
+}
+
+/// Starts a NATS stand-in acknowledging JetStream publishes, and forwarding published messages
+#[cfg(feature = "nats")]
+async fn start_nats_stand_in() -> (String, mpsc::UnboundedReceiver<(String, String)>) {
+	let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+	let url = format!("nats://{}", listener.local_addr().unwrap());
+	let (tx, rx) = mpsc::unbounded_channel();
+
+	tokio::spawn(async move {
+		while let Ok((stream, _)) = listener.accept().await {
+			let tx = tx.clone();
+			tokio::spawn(async move {
+				let (reader, mut writer) = stream.into_split();
+				let mut reader = BufReader::new(reader);
+				writer
+					.write_all(b"INFO {\"server_id\":\"stand-in\",\"version\":\"2.10.0\",\"proto\":1,\"headers\":true,\"max_payload\":1048576}\r\n")
+					.await
+					.unwrap();
+
+				let mut reply_sid = String::new();
+				let mut sequence = 0;
+				let mut line = String::new();
+				while reader.read_line(&mut line).await.unwrap_or(0) > 0 {
+					let tokens = line.split_whitespace().collect::<Vec<_>>();
+					match tokens.first().copied() {
+						Some("PING") => writer.write_all(b"PONG\r\n").await.unwrap(),
+						Some("SUB") => reply_sid = tokens[tokens.len() - 1].to_string(),
+						Some("HPUB") => {
+							let (subject, reply_to) = (tokens[1].to_string(), tokens[2]);
+							let header_len = tokens[3].parse::<usize>().unwrap();
+							let total_len = tokens[4].parse::<usize>().unwrap();
+							let mut message = vec![0; total_len + 2];
+							reader.read_exact(&mut message).await.unwrap();
+							let headers =
+								String::from_utf8_lossy(&message[..header_len]).to_string();
+							tx.send((subject, headers)).unwrap();
+
+							sequence += 1;
+							let ack = format!("{{\"stream\":\"MATCHES\",\"seq\":{}}}", sequence);
+							writer
+								.write_all(
+									format!(
+										"MSG {} {} {}\r\n{}\r\n",
+										reply_to,
+										reply_sid,
+										ack.len(),
+										ack
+									)
+									.as_bytes(),
+								)
+								.await
+								.unwrap();
+						}
+						_ => {}
+					}
+					line.clear();
+				}
+			});
+		}
+	});
+
+	(url, rx)
+}
+
+/// Message published to the AMQP stand-in
+#[cfg(feature = "amqp")]
+struct AmqpPublish {
+	exchange: String,
+	routing_key: String,
+	/// Raw content header, carrying the message properties
+	header: Vec<u8>,
+	body: Vec<u8>,
+}
+
+/// Writes an AMQP frame
+#[cfg(feature = "amqp")]
+async fn write_amqp_frame(
+	writer: &mut tokio::net::tcp::OwnedWriteHalf,
+	frame_type: u8,
+	channel: u16,
+	payload: &[u8],
+) {
+	let mut frame = vec![frame_type];
+	frame.extend_from_slice(&channel.to_be_bytes());
+	frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+	frame.extend_from_slice(payload);
+	frame.push(0xCE);
+	writer.write_all(&frame).await.unwrap();
+}
+
+/// Writes an AMQP method frame
+#[cfg(feature = "amqp")]
+async fn write_amqp_method(
+	writer: &mut tokio::net::tcp::OwnedWriteHalf,
+	channel: u16,
+	class_id: u16,
+	method_id: u16,
+	arguments: &[u8],
+) {
+	let mut payload = class_id.to_be_bytes().to_vec();
+	payload.extend_from_slice(&method_id.to_be_bytes());
+	payload.extend_from_slice(arguments);
+	write_amqp_frame(writer, 1, channel, &payload).await;
+}
+
+/// Reads a short string argument, returning it with the remaining arguments
+#[cfg(feature = "amqp")]
+fn read_amqp_short_string(arguments: &[u8]) -> (String, &[u8]) {
+	let len = arguments[0] as usize;
+	let value = String::from_utf8_lossy(&arguments[1..=len]).to_string();
+	(value, &arguments[len + 1..])
+}
+
+/// Starts an AMQP 0-9-1 stand-in confirming every publish, and forwarding published messages
+#[cfg(feature = "amqp")]
+async fn start_amqp_stand_in() -> (String, mpsc::UnboundedReceiver<AmqpPublish>) {
+	let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+	let url = format!("amqp://{}/%2f", listener.local_addr().unwrap());
+	let (tx, rx) = mpsc::unbounded_channel();
+
+	tokio::spawn(async move {
+		while let Ok((stream, _)) = listener.accept().await {
+			let tx = tx.clone();
+			tokio::spawn(async move {
+				let (mut reader, mut writer) = stream.into_split();
+				let mut protocol_header = [0; 8];
+				if reader.read_exact(&mut protocol_header).await.is_err() {
+					return;
+				}
+
+				// Connection.Start: version 0-9, no server properties, PLAIN mechanism
+				let mut start = vec![0, 9, 0, 0, 0, 0];
+				for long_string in [&b"PLAIN"[..], &b"en_US"[..]] {
+					start.extend_from_slice(&(long_string.len() as u32).to_be_bytes());
+					start.extend_from_slice(long_string);
+				}
+				write_amqp_method(&mut writer, 0, 10, 10, &start).await;
+
+				let mut publish: Option<AmqpPublish> = None;
+				let mut body_size = 0;
+				let mut delivery_tag = 0u64;
+				loop {
+					let mut frame_header = [0; 7];
+					if reader.read_exact(&mut frame_header).await.is_err() {
+						return;
+					}
+					let frame_type = frame_header[0];
+					let channel = u16::from_be_bytes([frame_header[1], frame_header[2]]);
+					let size = u32::from_be_bytes(frame_header[3..7].try_into().unwrap());
+					let mut payload = vec![0; size as usize + 1];
+					reader.read_exact(&mut payload).await.unwrap();
+					payload.truncate(size as usize);
+
+					match frame_type {
+						// Method frames
+						1 => {
+							let class_id = u16::from_be_bytes([payload[0], payload[1]]);
+							let method_id = u16::from_be_bytes([payload[2], payload[3]]);
+							let arguments = &payload[4..];
+							match (class_id, method_id) {
+								// Connection.StartOk: Connection.Tune without heartbeats
+								(10, 11) => {
+									let mut tune = 2047u16.to_be_bytes().to_vec();
+									tune.extend_from_slice(&131072u32.to_be_bytes());
+									tune.extend_from_slice(&0u16.to_be_bytes());
+									write_amqp_method(&mut writer, 0, 10, 30, &tune).await;
+								}
+								// Connection.Open
+								(10, 40) => write_amqp_method(&mut writer, 0, 10, 41, &[0]).await,
+								// Connection.Close
+								(10, 50) => write_amqp_method(&mut writer, 0, 10, 51, &[]).await,
+								// Channel.Open
+								(20, 10) => {
+									write_amqp_method(&mut writer, channel, 20, 11, &[0; 4]).await
+								}
+								// Channel.Close
+								(20, 40) => {
+									write_amqp_method(&mut writer, channel, 20, 41, &[]).await
+								}
+								// Confirm.Select
+								(85, 10) => {
+									write_amqp_method(&mut writer, channel, 85, 11, &[]).await
+								}
+								// Basic.Publish, followed by a content header and body frames
+								(60, 40) => {
+									let (exchange, arguments) =
+										read_amqp_short_string(&arguments[2..]);
+									let (routing_key, _) = read_amqp_short_string(arguments);
+									publish = Some(AmqpPublish {
+										exchange,
+										routing_key,
+										header: vec![],
+										body: vec![],
+									});
+								}
+								_ => {}
+							}
+						}
+						// Content header frames
+						2 => {
+							body_size = u64::from_be_bytes(payload[4..12].try_into().unwrap());
+							if let Some(publish) = publish.as_mut() {
+								publish.header = payload;
+							}
+						}
+						// Content body frames
+						3 => {
+							if let Some(publish) = publish.as_mut() {
+								publish.body.extend_from_slice(&payload);
+							}
+						}
+						_ => {}
+					}
+
+					// Confirm the message once its whole body is received
+					let complete = publish.as_ref().is_some_and(|publish| {
+						!publish.header.is_empty() && publish.body.len() as u64 == body_size
+					});
+					if complete {
+						delivery_tag += 1;
+						let mut ack = delivery_tag.to_be_bytes().to_vec();
+						ack.push(0);
+						write_amqp_method(&mut writer, channel, 60, 80, &ack).await;
+						tx.send(publish.take().unwrap()).unwrap();
+					}
+				}
+			});
+		}
+	});
+
+	(url, rx)
+}
+
+/// Publisher failing the first attempt of every message, and recording acknowledged messages
+#[derive(Default)]
+struct FlakyPublisher {
+	attempts: Mutex<HashMap<String, u32>>,
+	published: Mutex<Vec<QueueMessage>>,
+}
+
+#[async_trait]
+impl QueuePublisher for FlakyPublisher {
+	async fn publish(&self, message: &QueueMessage) -> Result<(), NotificationError> {
+		let attempt = {
+			let mut attempts = self.attempts.lock().unwrap();
+			let attempt = attempts.entry(message.id.clone()).or_default();
+			*attempt += 1;
+			*attempt
+		};
+		if attempt == 1 {
+			return Err(NotificationError::network_error(
+				"broker unavailable",
+				None,
+				None,
+			));
+		}
+		self.published.lock().unwrap().push(message.clone());
+		Ok(())
+	}
+}
+
+#[cfg(feature = "redis")]
+#[tokio::test]
+async fn test_notification_service_redis_execution() {
+	let (url, mut commands) = start_redis_stand_in().await;
+	let notification_service = NotificationService::new();
+	let trigger = TriggerBuilder::new()
+		.name("test_trigger")
+		.redis(&url, "matches")
+		.build();
+	let monitor_match = create_test_evm_match(create_test_monitor("Large Transfer"));
+
+	let result = notification_service
+		.execute(&trigger, &HashMap::new(), &monitor_match, &HashMap::new())
+		.await;
+	assert!(result.is_ok());
+
+	let command = commands.recv().await.unwrap();
+	assert_eq!(command[1], b"matches");
+	assert_eq!(command[2], b"*");
+	assert_eq!(command[3], b"key");
+	assert_eq!(command[4], b"ethereum_mainnet:Large Transfer");
+	assert_eq!(command[7], b"match");
+	let payload: serde_json::Value = serde_json::from_slice(&command[8]).unwrap();
+	assert_eq!(payload["version"], MATCH_PAYLOAD_VERSION);
+	assert_eq!(payload["monitor"], "Large Transfer");
+	assert_eq!(payload["network"], "ethereum_mainnet");
+}
+
+#[cfg(feature = "nats")]
+#[tokio::test]
+async fn test_notification_service_nats_execution() {
+	let (url, mut messages) = start_nats_stand_in().await;
+	let notification_service = NotificationService::new();
+	let trigger = TriggerBuilder::new()
+		.name("test_trigger")
+		.nats(&url, "monitor.matches")
+		.build();
+	let monitor_match = create_test_evm_match(create_test_monitor("Large Transfer"));
+
+	let result = notification_service
+		.execute(&trigger, &HashMap::new(), &monitor_match, &HashMap::new())
+		.await;
+	assert!(result.is_ok());
+
+	let (subject, headers) = messages.recv().await.unwrap();
+	assert_eq!(subject, "monitor.matches");
+	assert!(headers.contains("partition-key: ethereum_mainnet:Large Transfer"));
+	assert!(headers.contains("Nats-Msg-Id: ethereum_mainnet:Large Transfer:"));
+}
+
+#[cfg(feature = "kafka")]
+#[tokio::test]
+async fn test_notification_service_kafka_execution() {
+	use rdkafka::{
+		consumer::{BaseConsumer, Consumer},
+		message::Headers,
+		mocking::MockCluster,
+		ClientConfig, Message, Offset, TopicPartitionList,
+	};
+
+	let cluster = MockCluster::new(1).unwrap();
+	cluster.create_topic("matches", 1, 1).unwrap();
+	let notification_service = NotificationService::new();
+	let trigger = TriggerBuilder::new()
+		.name("test_trigger")
+		.kafka(&cluster.bootstrap_servers(), "matches")
+		.build();
+	let monitor_match = create_test_evm_match(create_test_monitor("Large Transfer"));
+
+	let result = notification_service
+		.execute(&trigger, &HashMap::new(), &monitor_match, &HashMap::new())
+		.await;
+	assert!(result.is_ok());
+
+	let consumer: BaseConsumer = ClientConfig::new()
+		.set("bootstrap.servers", cluster.bootstrap_servers())
+		.set("group.id", "test")
+		.create()
+		.unwrap();
+	let mut assignment = TopicPartitionList::new();
+	assignment
+		.add_partition_offset("matches", 0, Offset::Beginning)
+		.unwrap();
+	consumer.assign(&assignment).unwrap();
+	let message = consumer
+		.poll(Duration::from_secs(30))
+		.expect("published message")
+		.unwrap();
+
+	assert_eq!(message.key(), Some(&b"ethereum_mainnet:Large Transfer"[..]));
+	let header = message.headers().unwrap().get(0);
+	assert_eq!(header.key, "message-id");
+	assert!(header
+		.value
+		.unwrap()
+		.starts_with(b"ethereum_mainnet:Large Transfer:"));
+	let payload: serde_json::Value = serde_json::from_slice(message.payload().unwrap()).unwrap();
+	assert_eq!(payload["version"], MATCH_PAYLOAD_VERSION);
+	assert_eq!(payload["monitor"], "Large Transfer");
+}
+
+#[cfg(feature = "amqp")]
+#[tokio::test]
+async fn test_notification_service_amqp_execution() {
+	let (url, mut messages) = start_amqp_stand_in().await;
+	let notification_service = NotificationService::new();
+	let trigger = TriggerBuilder::new()
+		.name("test_trigger")
+		.amqp(&url, "matches")
+		.build();
+	let monitor_match = create_test_evm_match(create_test_monitor("Large Transfer"));
+
+	let result = notification_service
+		.execute(&trigger, &HashMap::new(), &monitor_match, &HashMap::new())
+		.await;
+	assert!(result.is_ok());
+
+	let message = messages.recv().await.unwrap();
+	assert_eq!(message.exchange, "matches");
+	// Without a routing key, messages are routed by their partition key
+	assert_eq!(message.routing_key, "ethereum_mainnet:Large Transfer");
+	let header = String::from_utf8_lossy(&message.header);
+	assert!(header.contains("application/json"));
+	assert!(header.contains("ethereum_mainnet:Large Transfer:"));
+	let payload: serde_json::Value = serde_json::from_slice(&message.body).unwrap();
+	assert_eq!(payload["version"], MATCH_PAYLOAD_VERSION);
+	assert_eq!(payload["network"], "ethereum_mainnet");
+}
+
+#[cfg(feature = "redis")]
+#[tokio::test]
+async fn test_notification_service_queue_unavailable_broker() {
+	// Nothing listens on the port once the listener is dropped
+	let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+	let url = format!("redis://{}", listener.local_addr().unwrap());
+	drop(listener);
+
+	let outbox_dir = tempfile::tempdir().unwrap();
+	let notification_service = NotificationService::new().with_queue_outbox_dir(outbox_dir.path());
+	let mut retry_policy = HttpRetryConfig::default();
+	retry_policy.max_retries = 1;
+	retry_policy.initial_backoff = Duration::from_millis(1);
+	let trigger = TriggerBuilder::new()
+		.name("test_trigger")
+		.redis(&url, "matches")
+		.retry_policy(retry_policy)
+		.build();
+	let monitor_match = create_test_evm_match(create_test_monitor("Large Transfer"));
+
+	let result = notification_service
+		.execute(&trigger, &HashMap::new(), &monitor_match, &HashMap::new())
+		.await;
+	assert!(matches!(result, Err(NotificationError::NetworkError(_))));
+	// The message is kept for the next publishing
+	assert_eq!(std::fs::read_dir(outbox_dir.path()).unwrap().count(), 1);
+
+	// Once the broker is back, the outbox is drained without a new match
+	let address = url.trim_start_matches("redis://").to_string();
+	let (_, mut commands) = start_redis_stand_in_on(TcpListener::bind(address).await.unwrap());
+	assert_eq!(
+		notification_service
+			.drain_queue_outbox(&trigger)
+			.await
+			.unwrap(),
+		1
+	);
+	let command = commands.recv().await.unwrap();
+	assert_eq!(command[4], b"ethereum_mainnet:Large Transfer");
+	assert_eq!(std::fs::read_dir(outbox_dir.path()).unwrap().count(), 0);
+	assert_eq!(
+		notification_service
+			.drain_queue_outbox(&trigger)
+			.await
+			.unwrap(),
+		0
+	);
+}
+
+#[tokio::test]
+async fn test_queue_notifier_retries_preserve_order() {
+	let publisher = Arc::new(FlakyPublisher::default());
+	let mut retry_policy = HttpRetryConfig::default();
+	retry_policy.initial_backoff = Duration::from_millis(1);
+	let notifier = QueueNotifier::new(publisher.clone(), retry_policy);
+
+	for monitor_name in ["first", "second", "third"] {
+		let message = notifier
+			.format_message(&create_test_evm_match(create_test_monitor(monitor_name)))
+			.unwrap();
+		notifier.publish(&message).await.unwrap();
+	}
+
+	let published = publisher.published.lock().unwrap();
+	assert_eq!(
+		published
+			.iter()
+			.map(|message| message.key.as_str())
+			.collect::<Vec<_>>(),
+		vec![
+			"ethereum_mainnet:first",
+			"ethereum_mainnet:second",
+			"ethereum_mainnet:third"
+		]
+	);
+}
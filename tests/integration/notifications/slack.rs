@@ -1,5 +1,7 @@
 use openzeppelin_monitor::{
-	models::{EVMMonitorMatch, MatchConditions, Monitor, MonitorMatch, ScriptLanguage},
+	models::{
+		EVMMonitorMatch, IncidentSeverity, MatchConditions, Monitor, MonitorMatch, ScriptLanguage,
+	},
 	services::notification::{NotificationService, Notifier, SlackNotifier},
 	utils::{
 		tests::{
@@ -35,6 +37,7 @@ fn create_test_evm_match(monitor: Monitor) -> MonitorMatch {
 		network_slug: "ethereum_mainnet".to_string(),
 		matched_on: MatchConditions::default(),
 		matched_on_args: None,
+		enrichment: None,
 	}))
 }
 
@@ -148,13 +151,22 @@ async fn test_notification_service_slack_execution_success() {
 	let mock = server
 		.mock("POST", "/")
 		.match_body(mockito::Matcher::Json(json!({
+			"text": "*Test Alert*\n\nTest message with value 42",
 			"blocks": [
+				{
+					"type": "header",
+					"text": { "type": "plain_text", "text": "Test Alert" }
+				},
 				{
 					"type": "section",
-					"text": {
-						"type": "mrkdwn",
-						"text": "*Test Alert*\n\nTest message with value 42"
-					}
+					"text": { "type": "mrkdwn", "text": "Test message with value 42" }
+				},
+				{
+					"type": "section",
+					"fields": [
+						{ "type": "mrkdwn", "text": "*Monitor*\ntest_monitor" },
+						{ "type": "mrkdwn", "text": "*Network*\nethereum_mainnet" }
+					]
 				}
 			]
 		})))
@@ -223,3 +235,119 @@ async fn test_notification_service_slack_execution_failure() {
 	assert!(result.is_err());
 	mock.assert();
 }
+
+#[tokio::test]
+async fn test_notification_service_slack_rich_layout_with_severity() {
+	let notification_service = NotificationService::new();
+	let mut server = mockito::Server::new_async().await;
+	let mock = server
+		.mock("POST", "/")
+		.match_body(mockito::Matcher::PartialJson(json!({
+			"attachments": [{
+				"color": "#E01E5A",
+				"blocks": [
+					{ "type": "header" },
+					{ "type": "section" },
+					{
+						"type": "section",
+						"fields": [
+							{ "type": "mrkdwn", "text": "*Monitor*\nLarge Transfer" },
+							{ "type": "mrkdwn", "text": "*Network*\nethereum_mainnet" },
+							{ "type": "mrkdwn", "text": "*Severity*\ncritical" }
+						]
+					},
+					{
+						"type": "section",
+						"text": {
+							"type": "mrkdwn",
+							"text": "*Matched*\n`Transfer(address,address,uint256)`"
+						}
+					},
+					{
+						"type": "section",
+						"fields": [{ "type": "mrkdwn", "text": "*Transfer.value*\n100" }]
+					},
+					{
+						"type": "actions",
+						"elements": [{ "url": "https://etherscan.io/tx/0xabc" }]
+					}
+				]
+			}]
+		})))
+		.with_status(200)
+		.create_async()
+		.await;
+
+	let trigger = TriggerBuilder::new()
+		.name("test_trigger")
+		.slack(&server.url())
+		.message("Large Transfer", "${events.0.args.value} tokens moved")
+		.explorer_url("https://etherscan.io/tx/${transaction.hash}")
+		.build();
+	let monitor = MonitorBuilder::new()
+		.name("Large Transfer")
+		.severity(IncidentSeverity::Critical)
+		.build();
+	let variables = HashMap::from([
+		("transaction.hash".to_string(), "0xabc".to_string()),
+		(
+			"events.0.signature".to_string(),
+			"Transfer(address,address,uint256)".to_string(),
+		),
+		("events.0.args.value".to_string(), "100".to_string()),
+	]);
+
+	let result = notification_service
+		.execute(
+			&trigger,
+			&variables,
+			&create_test_evm_match(monitor),
+			&HashMap::new(),
+		)
+		.await;
+
+	assert!(result.is_ok());
+	mock.assert();
+}
+
+#[tokio::test]
+async fn test_notification_service_slack_blocks_template() {
+	let notification_service = NotificationService::new();
+	let mut server = mockito::Server::new_async().await;
+	let mock = server
+		.mock("POST", "/")
+		.match_body(mockito::Matcher::Json(json!({
+			"text": "*Test Alert*\n\nTest message",
+			"blocks": [
+				{ "type": "section", "text": { "type": "mrkdwn", "text": "test_monitor moved 42" } }
+			]
+		})))
+		.with_status(200)
+		.create_async()
+		.await;
+
+	let trigger = TriggerBuilder::new()
+		.name("test_trigger")
+		.slack(&server.url())
+		.message("Test Alert", "Test message")
+		.slack_blocks(json!([
+			{ "type": "section", "text": { "type": "mrkdwn", "text": "${monitor.name} moved ${value}" } }
+		]))
+		.build();
+	let variables = HashMap::from([
+		("monitor.name".to_string(), "test_monitor".to_string()),
+		("value".to_string(), "42".to_string()),
+	]);
+
+	let result = notification_service
+		.execute(
+			&trigger,
+			&variables,
+			&create_test_evm_match(create_test_monitor("test_monitor")),
+			&HashMap::new(),
+		)
+		.await;
+
+	assert!(result.is_ok());
+	mock.assert();
+}
@@ -0,0 +1,173 @@
+use openzeppelin_monitor::{
+	models::{EVMMonitorMatch, MatchConditions, Monitor, MonitorMatch},
+	services::notification::{NotificationService, Notifier, TeamsNotifier},
+	utils::{
+		tests::{
+			evm::{monitor::MonitorBuilder, transaction::TransactionBuilder},
+			get_http_client_from_notification_pool,
+			trigger::TriggerBuilder,
+		},
+		HttpRetryConfig,
+	},
+};
+
+use serde_json::json;
+use std::collections::HashMap;
+
+use crate::integration::mocks::{create_test_evm_logs, create_test_evm_transaction_receipt};
+
+fn create_test_monitor(name: &str) -> Monitor {
+	MonitorBuilder::new()
+		.name(name)
+		.networks(vec!["ethereum_mainnet".to_string()])
+		.paused(false)
+		.triggers(vec!["test_trigger".to_string()])
+		.build()
+}
+
+fn create_test_evm_match(monitor: Monitor) -> MonitorMatch {
+	let transaction = TransactionBuilder::new().build();
+
+	MonitorMatch::EVM(Box::new(EVMMonitorMatch {
+		monitor,
+		transaction,
+		receipt: Some(create_test_evm_transaction_receipt()),
+		logs: Some(create_test_evm_logs()),
+		network_slug: "ethereum_mainnet".to_string(),
+		matched_on: MatchConditions::default(),
+		matched_on_args: None,
+		enrichment: None,
+	}))
+}
+
+#[tokio::test]
+async fn test_teams_notification_success() {
+	// Setup async mock server
+	let mut server = mockito::Server::new_async().await;
+	let mock = server
+		.mock("POST", "/")
+		.match_body(mockito::Matcher::PartialJson(json!({
+			"type": "message",
+			"attachments": [{
+				"contentType": "application/vnd.microsoft.card.adaptive",
+				"content": {
+					"type": "AdaptiveCard",
+					"body": [
+						{ "type": "TextBlock", "text": "Test Alert" },
+						{ "type": "TextBlock", "text": "Test message with value 42" }
+					],
+					"actions": []
+				}
+			}]
+		})))
+		.with_status(200)
+		.create_async()
+		.await;
+
+	let notifier = TeamsNotifier::new(
+		server.url(),
+		"Test Alert".to_string(),
+		"Test message with value ${value}".to_string(),
+		None,
+		get_http_client_from_notification_pool().await,
+	)
+	.unwrap();
+
+	let variables = HashMap::from([("value".to_string(), "42".to_string())]);
+	let message = notifier.format_message(&variables);
+
+	let result = notifier.notify(&message).await;
+
+	assert!(result.is_ok());
+	mock.assert();
+}
+
+#[tokio::test]
+async fn test_teams_notification_failure_retryable_error() {
+	// Setup async mock server to simulate failure
+	let mut server = mockito::Server::new_async().await;
+	let default_retries_count = HttpRetryConfig::default().max_retries as usize;
+	let mock = server
+		.mock("POST", "/")
+		.with_status(500)
+		.with_body("Internal Server Error")
+		.expect(1 + default_retries_count)
+		.create_async()
+		.await;
+
+	let notifier = TeamsNotifier::new(
+		server.url(),
+		"Test Alert".to_string(),
+		"Test message".to_string(),
+		None,
+		get_http_client_from_notification_pool().await,
+	)
+	.unwrap();
+
+	let result = notifier.notify("Test message").await;
+
+	assert!(result.is_err());
+	mock.assert();
+}
+
+#[tokio::test]
+async fn test_notification_service_teams_execution() {
+	let notification_service = NotificationService::new();
+	let mut server = mockito::Server::new_async().await;
+
+	let mock = server
+		.mock("POST", "/")
+		.match_body(mockito::Matcher::PartialJson(json!({
+			"attachments": [{
+				"content": {
+					"body": [
+						{ "type": "TextBlock", "text": "Test Alert" },
+						{ "type": "TextBlock", "text": "Transfer of 42" },
+						{
+							"type": "FactSet",
+							"facts": [
+								{ "title": "Transfer.to", "value": "0x2" },
+								{ "title": "Transfer.value", "value": "42" }
+							]
+						}
+					],
+					"actions": [{
+						"type": "Action.OpenUrl",
+						"url": "https://etherscan.io/tx/0xabc"
+					}]
+				}
+			}]
+		})))
+		.with_status(200)
+		.expect(1)
+		.create_async()
+		.await;
+
+	let trigger = TriggerBuilder::new()
+		.name("test_trigger")
+		.teams(&server.url())
+		.message("Test Alert", "Transfer of ${events.0.args.value}")
+		.explorer_url("https://etherscan.io/tx/${transaction.hash}")
+		.build();
+	let variables = HashMap::from([
+		("transaction.hash".to_string(), "0xabc".to_string()),
+		(
+			"events.0.signature".to_string(),
+			"Transfer(address,address,uint256)".to_string(),
+		),
+		("events.0.args.to".to_string(), "0x2".to_string()),
+		("events.0.args.value".to_string(), "42".to_string()),
+	]);
+
+	let result = notification_service
+		.execute(
+			&trigger,
+			&variables,
+			&create_test_evm_match(create_test_monitor("test_monitor")),
+			&HashMap::new(),
+		)
+		.await;
+
+	assert!(result.is_ok());
+	mock.assert();
+}
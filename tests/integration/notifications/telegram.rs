@@ -35,6 +35,7 @@ fn create_test_evm_match(monitor: Monitor) -> MonitorMatch {
 		network_slug: "ethereum_mainnet".to_string(),
 		matched_on: MatchConditions::default(),
 		matched_on_args: None,
+		enrichment: None,
 	}))
 }
 
@@ -1,7 +1,11 @@
 use openzeppelin_monitor::{
-	models::{EVMMonitorMatch, MatchConditions, Monitor, MonitorMatch, TriggerType},
+	models::{
+		EVMMonitorMatch, MatchConditions, Monitor, MonitorMatch, SecretString, SecretValue,
+		TriggerType, WebhookPayloadMode,
+	},
 	services::notification::{
 		NotificationError, NotificationService, Notifier, WebhookConfig, WebhookNotifier,
+		MATCH_PAYLOAD_VERSION,
 	},
 	utils::{
 		tests::{
@@ -37,6 +41,7 @@ fn create_test_evm_match(monitor: Monitor) -> MonitorMatch {
 		network_slug: "ethereum_mainnet".to_string(),
 		matched_on: MatchConditions::default(),
 		matched_on_args: None,
+		enrichment: None,
 	}))
 }
 
@@ -174,6 +179,140 @@ async fn test_notification_service_webhook_execution() {
 	mock.assert();
 }
 
+#[tokio::test]
+async fn test_notification_service_webhook_digest() {
+	let notification_service = NotificationService::new();
+	let mut server = mockito::Server::new_async().await;
+
+	// A single request is expected once the digest is flushed
+	let mock = server
+		.mock("POST", "/")
+		.match_body(mockito::Matcher::Json(json!({
+			"title": "2 transfers",
+			"body": "- Transfer of 10\n- Transfer of ${digest.count}\n"
+		})))
+		.with_status(200)
+		.expect(1)
+		.create_async()
+		.await;
+
+	let trigger = TriggerBuilder::new()
+		.name("test_trigger")
+		.webhook(&server.url())
+		.message("Test Alert", "Transfer of ${value}")
+		.digest(
+			"0 */15 * * * *",
+			"${digest.count} transfers",
+			"{% for match in matches %}- ${match.message}\n{% endfor %}",
+		)
+		.build();
+	let monitor_match = create_test_evm_match(create_test_monitor("test_monitor"));
+
+	// Match values are sent as they are, without being rendered as templates
+	for value in ["10", "${digest.count}"] {
+		let variables = HashMap::from([("value".to_string(), value.to_string())]);
+		let result = notification_service
+			.execute(&trigger, &variables, &monitor_match, &HashMap::new())
+			.await;
+		assert!(result.is_ok());
+	}
+
+	assert_eq!(
+		notification_service.flush_digest(&trigger).await.unwrap(),
+		2
+	);
+	// Nothing is left to flush
+	assert_eq!(
+		notification_service.flush_digest(&trigger).await.unwrap(),
+		0
+	);
+	mock.assert();
+}
+
+#[tokio::test]
+async fn test_notification_service_webhook_match_payload() {
+	let notification_service = NotificationService::new();
+	let mut server = mockito::Server::new_async().await;
+	let monitor_match = create_test_evm_match(create_test_monitor("test_monitor"));
+
+	let mock = server
+		.mock("POST", "/")
+		.match_header(
+			"X-Signature",
+			mockito::Matcher::Regex("^[0-9a-f]{64}$".to_string()),
+		)
+		.match_body(mockito::Matcher::PartialJson(json!({
+			"version": MATCH_PAYLOAD_VERSION,
+			"monitor": "test_monitor",
+			"network": "ethereum_mainnet",
+			"match": { "EVM": { "matched_on_args": null } }
+		})))
+		.with_status(200)
+		.expect(1)
+		.create_async()
+		.await;
+
+	let trigger = TriggerBuilder::new()
+		.name("test_trigger")
+		.webhook(&server.url())
+		.webhook_secret(SecretValue::Plain(SecretString::new(
+			"test-secret".to_string(),
+		)))
+		.webhook_payload(WebhookPayloadMode::Match)
+		.build();
+
+	let result = notification_service
+		.execute(&trigger, &HashMap::new(), &monitor_match, &HashMap::new())
+		.await;
+
+	assert!(result.is_ok());
+	mock.assert();
+}
+
+#[tokio::test]
+async fn test_notification_service_webhook_template_payload() {
+	let notification_service = NotificationService::new();
+	let mut server = mockito::Server::new_async().await;
+
+	let mock = server
+		.mock("POST", "/")
+		.match_body(mockito::Matcher::Json(json!({
+			"event": "transfer",
+			"details": { "value": "10", "tags": ["test_monitor"] }
+		})))
+		.with_status(200)
+		.expect(1)
+		.create_async()
+		.await;
+
+	let trigger = TriggerBuilder::new()
+		.name("test_trigger")
+		.webhook(&server.url())
+		.webhook_payload(WebhookPayloadMode::Template {
+			template: json!({
+				"event": "transfer",
+				"details": { "value": "${value}", "tags": ["${monitor.name}"] }
+			}),
+		})
+		.build();
+	let variables = HashMap::from([
+		("value".to_string(), "10".to_string()),
+		("monitor.name".to_string(), "test_monitor".to_string()),
+	]);
+
+	let result = notification_service
+		.execute(
+			&trigger,
+			&variables,
+			&create_test_evm_match(create_test_monitor("test_monitor")),
+			&HashMap::new(),
+		)
+		.await;
+
+	assert!(result.is_ok());
+	mock.assert();
+}
+
 #[tokio::test]
 async fn test_notification_service_webhook_execution_failure() {
 	let notification_service = NotificationService::new();
@@ -974,6 +974,184 @@ proptest! {
 		prop_assert_eq!(result, expected);
 	}
 
+	// Tests logical NOT over comparisons and parenthesized sub-expressions
+	// Verifies that negation inverts the result of the negated expression
+	#[test]
+	fn test_not_expression_evaluation(
+		amount in 0u128..1000000u128,
+		threshold in 0u128..1000000u128,
+		addr in valid_address(),
+		other_addr in valid_address(),
+		use_symbol in any::<bool>(),
+	) {
+		let not_operator = if use_symbol { "!" } else { "not " };
+		let negated_comparison = format!("{}amount >= {}", not_operator, threshold);
+		let negated_group = format!(
+			"{}(amount >= {} AND recipient == {})",
+			not_operator, threshold, other_addr
+		);
+
+		let params = vec![
+			EVMMatchParamEntry {
+				name: "amount".to_string(),
+				value: amount.to_string(),
+				kind: "uint256".to_string(),
+				indexed: false,
+			},
+			EVMMatchParamEntry {
+				name: "recipient".to_string(),
+				value: addr.clone(),
+				kind: "address".to_string(),
+				indexed: false,
+			}
+		];
+
+		let filter = EVMBlockFilter::<EvmClient<EVMTransportClient>> {
+			_client: PhantomData,
+		};
+
+		let result = filter.evaluate_expression(&negated_comparison, &params).unwrap();
+		prop_assert_eq!(result, amount < threshold);
+
+		let result = filter.evaluate_expression(&negated_group, &params).unwrap();
+		let expected = !(amount >= threshold && are_same_address(&addr, &other_addr));
+		prop_assert_eq!(result, expected);
+	}
+
+	// Tests that negating an expression always yields the opposite result
+	// Verifies `not (expr)` against the evaluation of `expr` itself, including double negation
+	#[test]
+	fn test_not_expression_inverts_result(
+		amount in 0u128..1000000u128,
+		threshold1 in 0u128..1000000u128,
+		threshold2 in 0u128..1000000u128,
+	) {
+		let expr = format!("amount > {} OR amount < {}", threshold1, threshold2);
+
+		let params = vec![EVMMatchParamEntry {
+			name: "amount".to_string(),
+			value: amount.to_string(),
+			kind: "uint256".to_string(),
+			indexed: false,
+		}];
+
+		let filter = EVMBlockFilter::<EvmClient<EVMTransportClient>> {
+			_client: PhantomData,
+		};
+		let result = filter.evaluate_expression(&expr, &params).unwrap();
+		let negated = filter
+			.evaluate_expression(&format!("not ({})", expr), &params)
+			.unwrap();
+		let double_negated = filter
+			.evaluate_expression(&format!("!!({})", expr), &params)
+			.unwrap();
+
+		prop_assert_eq!(negated, !result);
+		prop_assert_eq!(double_negated, result);
+	}
+
+	// Tests arithmetic on both sides of a comparison
+	// Verifies that sums, products and exact division match native integer arithmetic
+	#[test]
+	fn test_arithmetic_expression_evaluation(
+		amount0 in 0u64..u64::MAX,
+		amount1 in 0u64..u64::MAX,
+		divisor in 1u64..1000000u64,
+		threshold in 0u128..u128::MAX,
+	) {
+		let params = vec![
+			EVMMatchParamEntry {
+				name: "amount0".to_string(),
+				value: amount0.to_string(),
+				kind: "uint256".to_string(),
+				indexed: false,
+			},
+			EVMMatchParamEntry {
+				name: "amount1".to_string(),
+				value: amount1.to_string(),
+				kind: "uint256".to_string(),
+				indexed: false,
+			},
+		];
+
+		let filter = EVMBlockFilter::<EvmClient<EVMTransportClient>> {
+			_client: PhantomData,
+		};
+
+		let sum = amount0 as u128 + amount1 as u128;
+		let result = filter
+			.evaluate_expression(&format!("amount0 + amount1 > {}", threshold), &params)
+			.unwrap();
+		prop_assert_eq!(result, sum > threshold);
+
+		let product = amount0 as u128 * amount1 as u128;
+		let result = filter
+			.evaluate_expression(&format!("amount0 * amount1 == {}", product), &params)
+			.unwrap();
+		prop_assert!(result);
+
+		// Multiplying back after dividing restores the original value exactly
+		let result = filter
+			.evaluate_expression(&format!("amount0 * {} / {} == amount0", divisor, divisor), &params)
+			.unwrap();
+		prop_assert!(result);
+
+		let result = filter
+			.evaluate_expression("amount0 - amount1 == 0 - (amount1 - amount0)", &params)
+			.unwrap();
+		prop_assert!(result);
+	}
+
+	// Tests comparisons between two variables
+	// Verifies that the right side is resolved and compared with the same rules as literals
+	#[test]
+	fn test_variable_comparison_evaluation(
+		amount_out in 0u128..1000000u128,
+		amount_out_min in 0u128..1000000u128,
+		from in valid_address(),
+		to in valid_address(),
+	) {
+		let params = vec![
+			EVMMatchParamEntry {
+				name: "amountOut".to_string(),
+				value: amount_out.to_string(),
+				kind: "uint256".to_string(),
+				indexed: false,
+			},
+			EVMMatchParamEntry {
+				name: "amountOutMin".to_string(),
+				value: amount_out_min.to_string(),
+				kind: "uint256".to_string(),
+				indexed: false,
+			},
+			EVMMatchParamEntry {
+				name: "from".to_string(),
+				value: from.clone(),
+				kind: "address".to_string(),
+				indexed: false,
+			},
+			EVMMatchParamEntry {
+				name: "to".to_string(),
+				value: to.to_uppercase().replacen("0X", "0x", 1),
+				kind: "address".to_string(),
+				indexed: false,
+			},
+		];
+
+		let filter = EVMBlockFilter::<EvmClient<EVMTransportClient>> {
+			_client: PhantomData,
+		};
+
+		let result = filter.evaluate_expression("amountOut < amountOutMin", &params).unwrap();
+		prop_assert_eq!(result, amount_out < amount_out_min);
+
+		let result = filter.evaluate_expression("from == to", &params).unwrap();
+		prop_assert_eq!(result, are_same_address(&from, &to));
+
+		let result = filter.evaluate_expression("to == to", &params).unwrap();
+		prop_assert!(result);
+	}
+
 	// Tests complex expressions combining AND/OR with parentheses
 	// Verifies that nested logical operations work correctly with different types
 	#[test]
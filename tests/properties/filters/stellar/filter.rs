@@ -219,8 +219,8 @@ prop_compose! {
 		MonitorBuilder::new()
 			.name("Test Monitor")
 			.addresses(vec![address])
-			.transaction(TransactionStatus::Success, Some(format!("hash == {}", hash)))
-			.transaction(TransactionStatus::Failure, Some(format!("hash != {}", hash)))
+			.transaction(TransactionStatus::Success, Some(format!("hash == '{}'", hash)))
+			.transaction(TransactionStatus::Failure, Some(format!("hash != '{}'", hash)))
 			.build()
 	}
 }
@@ -1074,6 +1074,80 @@ proptest! {
 	}
 
 
+	// Tests logical NOT over comparisons and parenthesized sub-expressions
+	#[test]
+	fn test_not_expression_evaluation(
+		amount in 0u128..1000000u128,
+		threshold in 0u128..1000000u128,
+		addr in valid_address(),
+		other_addr in valid_address(),
+		use_symbol in any::<bool>(),
+	) {
+		let not_operator = if use_symbol { "!" } else { "not " };
+		let negated_comparison = format!("{}param0 >= {}", not_operator, threshold);
+		let negated_group = format!(
+			"{}(param0 >= {} AND param1 == {})",
+			not_operator, threshold, other_addr
+		);
+
+		let params = vec![
+			StellarMatchParamEntry {
+				name: "param0".to_string(),
+				value: amount.to_string(),
+				kind: "I128".to_string(),
+				indexed: false,
+			},
+			StellarMatchParamEntry {
+				name: "param1".to_string(),
+				value: addr.clone(),
+				kind: "Address".to_string(),
+				indexed: false,
+			}
+		];
+
+		let filter = StellarBlockFilter::<StellarClient<StellarTransportClient>> {
+			_client: PhantomData,
+		};
+
+		let result = filter.evaluate_expression(&negated_comparison, &params).unwrap();
+		prop_assert_eq!(result, amount < threshold);
+
+		let result = filter.evaluate_expression(&negated_group, &params).unwrap();
+		let expected = !(amount >= threshold && are_same_address(&addr, &other_addr));
+		prop_assert_eq!(result, expected);
+	}
+
+	// Tests that negating an expression always yields the opposite result
+	#[test]
+	fn test_not_expression_inverts_result(
+		amount in 0u128..1000000u128,
+		threshold1 in 0u128..1000000u128,
+		threshold2 in 0u128..1000000u128,
+	) {
+		let expr = format!("param0 > {} OR param0 < {}", threshold1, threshold2);
+
+		let params = vec![StellarMatchParamEntry {
+			name: "param0".to_string(),
+			value: amount.to_string(),
+			kind: "I128".to_string(),
+			indexed: false,
+		}];
+
+		let filter = StellarBlockFilter::<StellarClient<StellarTransportClient>> {
+			_client: PhantomData,
+		};
+		let result = filter.evaluate_expression(&expr, &params).unwrap();
+		let negated = filter
+			.evaluate_expression(&format!("not ({})", expr), &params)
+			.unwrap();
+		let double_negated = filter
+			.evaluate_expression(&format!("!!({})", expr), &params)
+			.unwrap();
+
+		prop_assert_eq!(negated, !result);
+		prop_assert_eq!(double_negated, result);
+	}
+
 	// Tests complex combinations of AND/OR expressions
 	#[test]
 	fn test_and_or_expression_evaluation(
@@ -1621,6 +1695,7 @@ proptest! {
 						indexed: false,
 					}
 				]),
+				event_index: None,
 			},
 			tx_hash: tx_hash.clone(),
 		};
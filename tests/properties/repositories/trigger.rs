@@ -93,7 +93,7 @@ proptest! {
 			// Test invalid cases
 			match &trigger.trigger_type {
 				TriggerType::Slack => {
-					if let TriggerTypeConfig::Slack { slack_url: _, message: _, retry_policy: _ } = &trigger.config {
+					if let TriggerTypeConfig::Slack { slack_url: _, message: _, explorer_url: _, blocks: _, retry_policy: _ } = &trigger.config {
 						invalid_trigger = trigger.clone();
 						if let TriggerTypeConfig::Slack { slack_url, .. } = &mut invalid_trigger.config {
 							*slack_url = SecretValue::Plain(SecretString::new("not-a-url".to_string())); // Invalid URL format
@@ -150,7 +150,7 @@ proptest! {
 					}
 				}
 				TriggerType::Webhook => {
-					if let TriggerTypeConfig::Webhook { url: _, method: _, headers: _, secret: _, message: _, retry_policy: _ } = &trigger.config {
+					if let TriggerTypeConfig::Webhook { url: _, method: _, headers: _, secret: _, message: _, retry_policy: _, payload: _ } = &trigger.config {
 						// Test invalid method
 						invalid_trigger = trigger.clone();
 						if let TriggerTypeConfig::Webhook { method: m, .. } = &mut invalid_trigger.config {
@@ -187,7 +187,7 @@ proptest! {
 					}
 				}
 				TriggerType::Discord => {
-					if let TriggerTypeConfig::Discord { discord_url: _, message: _, retry_policy: _ } = &trigger.config {
+					if let TriggerTypeConfig::Discord { discord_url: _, message: _, explorer_url: _, embeds: _, retry_policy: _ } = &trigger.config {
 						// Test invalid URL
 						invalid_trigger = trigger.clone();
 						if let TriggerTypeConfig::Discord { discord_url: u, .. } = &mut invalid_trigger.config {
@@ -254,7 +254,7 @@ proptest! {
 					}
 				}
 				TriggerType::Script => {
-					if let TriggerTypeConfig::Script { script_path: _, arguments: _, language: _, timeout_ms: _ } = &trigger.config {
+					if let TriggerTypeConfig::Script { script_path: _, arguments: _, language: _, timeout_ms: _, mode: _, sandbox: _, interpreter: _ } = &trigger.config {
 						// Test invalid path
 						invalid_trigger = trigger.clone();
 						if let TriggerTypeConfig::Script { script_path: p, .. } = &mut invalid_trigger.config {
@@ -270,6 +270,45 @@ proptest! {
 						prop_assert!(invalid_trigger.validate().is_err());
 					}
 				}
+				TriggerType::PagerDuty | TriggerType::Opsgenie | TriggerType::Teams => {
+					// Test empty body
+					invalid_trigger = trigger.clone();
+					if let TriggerTypeConfig::PagerDuty { message: m, .. } | TriggerTypeConfig::Opsgenie { message: m, .. } | TriggerTypeConfig::Teams { message: m, .. } = &mut invalid_trigger.config {
+						*m = NotificationMessage {
+							title: "Alert".to_string(),
+							body: "".to_string(),
+						};
+					}
+					prop_assert!(invalid_trigger.validate().is_err());
+				}
+				TriggerType::Kafka | TriggerType::Nats | TriggerType::Redis | TriggerType::Amqp => {
+					// Test empty destination
+					invalid_trigger = trigger.clone();
+					match &mut invalid_trigger.config {
+						TriggerTypeConfig::Kafka { topic: d, .. }
+						| TriggerTypeConfig::Nats { subject: d, .. }
+						| TriggerTypeConfig::Redis { stream: d, .. } => *d = "".to_string(),
+						TriggerTypeConfig::Amqp { routing_key: d, .. } => *d = Some("".to_string()),
+						_ => {}
+					}
+					prop_assert!(invalid_trigger.validate().is_err());
+				}
+				TriggerType::Database => {
+					// Test invalid table name
+					invalid_trigger = trigger.clone();
+					if let TriggerTypeConfig::Database { table, .. } = &mut invalid_trigger.config {
+						*table = "matches; DROP TABLE matches".to_string();
+					}
+					prop_assert!(invalid_trigger.validate().is_err());
+				}
+				TriggerType::Plugin => {
+					// Test empty plugin name
+					invalid_trigger = trigger.clone();
+					if let TriggerTypeConfig::Plugin { plugin, .. } = &mut invalid_trigger.config {
+						*plugin = "".to_string();
+					}
+					prop_assert!(invalid_trigger.validate().is_err());
+				}
 			}
 		}
 	}
@@ -2,9 +2,9 @@ use email_address::EmailAddress;
 use openzeppelin_monitor::{
 	models::{
 		AddressWithSpec, BlockChainType, EventCondition, FunctionCondition, MatchConditions,
-		Monitor, Network, NotificationMessage, RpcUrl, ScriptLanguage, SecretString, SecretValue,
-		TransactionCondition, TransactionStatus, Trigger, TriggerConditions, TriggerType,
-		TriggerTypeConfig,
+		Monitor, Network, NotificationMessage, RpcUrl, ScriptLanguage, ScriptMode, SecretString,
+		SecretValue, TransactionCondition, TransactionStatus, Trigger, TriggerConditions,
+		TriggerType, TriggerTypeConfig,
 	},
 	utils::{
 		tests::{evm::monitor::MonitorBuilder, network::NetworkBuilder, trigger::TriggerBuilder},
@@ -97,6 +97,8 @@ pub fn trigger_strategy() -> impl Strategy<Value = Trigger> {
 				.prop_map(|(slack_url, message)| TriggerTypeConfig::Slack {
 					slack_url: SecretValue::Plain(SecretString::new(slack_url)),
 					message,
+					explorer_url: None,
+					blocks: None,
 					retry_policy: HttpRetryConfig::default(),
 				})
 		)
@@ -163,6 +165,7 @@ pub fn trigger_strategy() -> impl Strategy<Value = Trigger> {
 						secret: secret.map(|s| SecretValue::Plain(SecretString::new(s))),
 						message,
 						retry_policy: HttpRetryConfig::default(),
+						payload: None,
 					}
 				})
 		)
@@ -281,6 +284,7 @@ pub fn match_conditions_strategy() -> impl Strategy<Value = MatchConditions> {
 			functions,
 			events,
 			transactions,
+			..Default::default()
 		})
 }
 
@@ -312,6 +316,9 @@ pub fn trigger_conditions_strategy() -> impl Strategy<Value = Vec<TriggerConditi
 				arguments: Some(arguments.split(',').map(|s| s.to_string()).collect()),
 				language,
 				timeout_ms,
+				mode: ScriptMode::Process,
+				sandbox: None,
+				interpreter: None,
 			}]
 		})
 }
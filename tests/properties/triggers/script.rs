@@ -1,5 +1,5 @@
 use crate::properties::strategies::process_output_strategy;
-use openzeppelin_monitor::services::trigger::process_script_output;
+use openzeppelin_monitor::services::trigger::{process_script_output, ScriptOutput};
 use proptest::{prelude::*, test_runner::Config};
 use std::os::unix::process::ExitStatusExt;
 
@@ -12,16 +12,17 @@ proptest! {
 	#[test]
 	fn test_process_script_output(output in process_output_strategy()) {
 		let result = process_script_output(output.clone(), false);
-		if let Ok(parse_result) = result {
+		if let Ok(parse_result) = result.as_ref() {
 			match parse_result {
-				true => {
+				ScriptOutput::Filter(true) => {
 					prop_assert!(result.is_ok());
-					prop_assert!(result.unwrap());
+					prop_assert_eq!(result.unwrap(), ScriptOutput::Filter(true));
 				},
-				false => {
+				ScriptOutput::Filter(false) => {
 					prop_assert!(result.is_ok());
-					prop_assert!(!result.unwrap());
+					prop_assert_eq!(result.unwrap(), ScriptOutput::Filter(false));
 				},
+				ScriptOutput::Enrich(_) => prop_assert!(false, "Expected a boolean output"),
 			}
 		} else {
 			prop_assert!(result.is_err());
@@ -60,7 +61,7 @@ proptest! {
 
 		if append_bool {
 			prop_assert!(result.is_ok());
-			prop_assert!(result.unwrap());
+			prop_assert_eq!(result.unwrap(), ScriptOutput::Filter(true));
 		} else {
 			prop_assert!(result.is_err());
 		}
@@ -116,7 +117,7 @@ proptest! {
 
 		let result = process_script_output(output, false);
 		prop_assert!(result.is_ok());
-		prop_assert_eq!(result.unwrap(), value);
+		prop_assert_eq!(result.unwrap(), ScriptOutput::Filter(value));
 	}
 
 	#[test]
@@ -139,7 +140,7 @@ proptest! {
 		// - Err(ExecutionError) if exit_code is not 0
 		if exit_code == 0 {
 			prop_assert!(result.is_ok());
-			prop_assert!(result.unwrap());
+			prop_assert_eq!(result.unwrap(), ScriptOutput::Filter(true));
 		} else {
 			prop_assert!(result.is_err());
 			if let Err(e) = result {